@@ -0,0 +1,137 @@
+//! Criterion benchmarks for reading, parsing and validating OML and for every
+//! generator, over synthetic models of three sizes:
+//!
+//! ```sh
+//! cargo bench                       # everything
+//! cargo bench -- parse/huge         # one benchmark
+//! cargo bench -- --save-baseline before && cargo bench -- --baseline before
+//! ```
+//!
+//! Generators run on the medium model only, as the huge one would make the
+//! whole suite take minutes per target.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::hint::black_box;
+
+use clap::Parser;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use oml::cli::oml::OmlCli;
+use oml::core::oml_object::OmlObject;
+
+/// Model sizes as (name, objects, fields per object).
+const SIZES: [(&str, usize, usize); 3] = [
+    ("small", 4, 4),
+    ("medium", 100, 12),
+    ("huge", 3_000, 24),
+];
+
+/// The language flags whose generators are benchmarked.
+const TARGETS: [&str; 28] = [
+    "c", "cpp", "python", "kotlin", "java", "rust", "typescript", "sql", "go",
+    "csharp", "dart", "php", "ruby", "scala", "haskell", "zig", "lua", "julia",
+    "elixir", "fsharp", "ocaml", "proto", "jsonschema", "graphql", "openapi",
+    "javascript", "gdscript", "docs",
+];
+
+const FIELD_TYPES: [&str; 6] = ["int32", "uint64", "double", "bool", "string", "float[3]"];
+
+/// A model of one enum per ten objects and classes and structs whose fields
+/// cover every modifier, array kind and a reference to an earlier object.
+fn model(objects: usize, fields: usize) -> String {
+    let mut oml = String::new();
+    for i in 0..objects {
+        if i % 10 == 0 {
+            let _ = writeln!(oml, "/// Kind {i}\nenum Kind{i} {{");
+            for v in 0..fields.min(8) {
+                let _ = writeln!(oml, "    string VALUE_{v};");
+            }
+            let _ = writeln!(oml, "}}\n");
+            continue;
+        }
+        let kind = if i % 2 == 0 { "class" } else { "struct" };
+        let _ = writeln!(oml, "/// Object {i}\n{kind} Object{i} {{");
+        for f in 0..fields {
+            let line = match f % 8 {
+                0 => format!("public Kind{} kind{f};", i / 10 * 10),
+                1 if i % 10 > 1 => format!("private Object{} previous{f};", i - 1),
+                2 => format!("public optional string note{f};"),
+                3 => format!("public list uint16 samples{f};"),
+                4 => format!("protected static int64 count{f};"),
+                _ => format!("public {} field{f};", FIELD_TYPES[f % FIELD_TYPES.len()]),
+            };
+            let _ = writeln!(oml, "    /// Field {f}\n    {line}");
+        }
+        let _ = writeln!(oml, "}}\n");
+    }
+    oml
+}
+
+fn parse(content: &str) -> Vec<OmlObject> {
+    OmlObject::scan_file(content.to_string()).expect("benchmark model parses")
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, objects, fields) in SIZES {
+        let content = model(objects, fields);
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &content, |b, content| {
+            b.iter(|| OmlObject::scan_file(black_box(content.clone())))
+        });
+    }
+    group.finish();
+}
+
+fn bench_read(c: &mut Criterion) {
+    let dir = std::env::temp_dir().join(format!("oml_bench_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("temp dir is writable");
+
+    let mut group = c.benchmark_group("read");
+    for (name, objects, fields) in SIZES {
+        let content = model(objects, fields);
+        let path = dir.join(format!("{name}.oml"));
+        fs::write(&path, &content).expect("temp dir is writable");
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &path, |b, path| {
+            b.iter(|| OmlObject::get_from_file(black_box(path)))
+        });
+    }
+    group.finish();
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+fn bench_validate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate");
+    let imported = HashSet::new();
+    for (name, objects, fields) in SIZES {
+        let objects = parse(&model(objects, fields));
+        group.throughput(Throughput::Elements(objects.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &objects, |b, objects| {
+            b.iter(|| OmlObject::validate_custom_types(black_box(objects), &imported))
+        });
+    }
+    group.finish();
+}
+
+fn bench_generate(c: &mut Criterion) {
+    let (_, objects, fields) = SIZES[1];
+    let objects = parse(&model(objects, fields));
+
+    let mut group = c.benchmark_group("generate");
+    group.throughput(Throughput::Elements(objects.len() as u64));
+    for target in TARGETS {
+        let cli = OmlCli::parse_from(["oml", &format!("--{target}")]);
+        for generator in cli.generator_args.generators() {
+            group.bench_function(target, |b| {
+                b.iter(|| generator.generate_files(black_box(&objects), "models"))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_read, bench_validate, bench_generate);
+criterion_main!(benches);
@@ -1 +1,3 @@
-pub mod oml;
\ No newline at end of file
+pub mod oml;
+pub mod repl;
+pub mod scaffold;
\ No newline at end of file
@@ -1,17 +1,51 @@
-use clap::{Parser, CommandFactory, Subcommand};
+use clap::{Args, Parser, CommandFactory, Subcommand};
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+use crate::core::banner::Banner;
 use crate::core::errors;
-use crate::core::dir_parser::parse_dir_from_string;
-use crate::core::generate::{Generate, BackwardsGenerate};
-use crate::core::oml_object::OmlFile;
+use crate::core::generated_comment::{GeneratedComment, SourceNames};
+use crate::core::naming::{Case, NamingConvention, NamingStyle};
+use crate::core::member_order::MemberOrder;
+use crate::core::type_map::{TypeMap, TypeMapping};
+use crate::core::file_name_template::{FileNameTemplate, Templated};
+use crate::core::code_writer::{BraceStyle, CodeStyle, IndentKind};
+use crate::core::diagnostics::{ColorChoice, Diagnostic, MessageFormat};
+use crate::core::verify::VerifyTarget;
+use crate::core::highlight::HighlightFormat;
+use crate::core::dir_parser::{list_files, parse_dir_each, parse_dir_from_string};
+use crate::core::generate::{AccessorMode, Generate, BackwardsGenerate, SerdeFormat, escape_keywords};
+use crate::core::oml_object::{ObjectType, OmlFile, OmlObject};
 
 use crate::generators::{
-    cpp::oml_cpp::CppGenerator,
+    c::oml_c::{CGenerator, CStringStrategy},
+    cpp::{jni::JniBridge, oml_cpp::{CppGenerator, CppStandard, GuardSource, PointerType}},
+    csharp::oml_csharp::CsharpGenerator,
+    dart::oml_dart::DartGenerator,
+    docs::oml_docs::{DocsFormat, DocsGenerator},
+    elixir::oml_elixir::ElixirGenerator,
+    fsharp::oml_fsharp::FsharpGenerator,
+    gdscript::oml_gdscript::GdscriptGenerator,
+    go::oml_go::GoGenerator,
+    graphql::oml_graphql::GraphqlGenerator,
+    haskell::oml_haskell::HaskellGenerator,
     java::oml_java::JavaGenerator,
+    javascript::oml_javascript::JavascriptGenerator,
+    jsonschema::oml_jsonschema::JsonSchemaGenerator,
+    julia::oml_julia::JuliaGenerator,
     kotlin::oml_kotlin::KotlinGenerator,
-    python::oml_python::PythonGenerator,
+    lua::oml_lua::LuaGenerator,
+    ocaml::oml_ocaml::OcamlGenerator,
+    openapi::oml_openapi::OpenapiGenerator,
+    php::oml_php::PhpGenerator,
+    proto::oml_proto::ProtoGenerator,
+    python::oml_python::{PythonEnumStyle, PythonGenerator},
+    ruby::oml_ruby::RubyGenerator,
     rust::oml_rust::RustGenerator,
+    scala::oml_scala::ScalaGenerator,
     sql::oml_sql::SqlGenerator,
     typescript::oml_typescript::TypescriptGenerator,
+    zig::oml_zig::ZigGenerator,
 };
 
 #[derive(Parser)]
@@ -35,17 +69,119 @@ pub struct OmlCli {
     #[arg(short, long, default_value_t = 3)]
     depth: usize,
 
+    /// Generate everything again instead of skipping inputs unchanged since
+    /// the last run, which <OUTPUT>/.oml-cache records
+    #[arg(long)]
+    pub no_cache: bool,
+
+    #[command(flatten)]
+    pub generator_args: GeneratorArgs,
+
+    /// Check generated files with an external tool when one is installed,
+    /// reporting what it finds against the objects they came from
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub verify: Vec<VerifyTarget>,
+
+    /// Extra arguments for the --verify tools, such as the include path of
+    /// nlohmann/json.hpp for headers generated with --cpp-json
+    #[arg(long, value_name = "FLAGS", requires = "verify", allow_hyphen_values = true)]
+    pub verify_flags: Option<String>,
+
+    /// Carry on past inputs and outputs that fail and list them at the end;
+    /// the default
+    #[arg(long, global = true, conflicts_with = "fail_fast")]
+    pub keep_going: bool,
+
+    /// Stop at the first input or output that fails
+    #[arg(long, global = true)]
+    pub fail_fast: bool,
+
+    /// How errors and warnings are printed
+    #[arg(long, value_enum, global = true, default_value_t = MessageFormat::Human)]
+    pub message_format: MessageFormat,
+
+    /// When to color errors and warnings; NO_COLOR turns off `auto`
+    #[arg(long, value_enum, global = true, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+}
+
+/// Target languages and their options, shared by the main command and `translate`.
+#[derive(Args, Debug, Clone)]
+pub struct GeneratorArgs {
     #[arg(long)]
     use_data_class: bool,
 
     // language conversions
 
+    /// C headers; these share the .h extension with --cpp, so using both
+    /// needs a --file-name-template telling them apart
+    #[arg(long)]
+    c: bool,
+
     #[arg(long)]
     cpp: bool,
 
+    /// Make --cpp emit Unreal Engine USTRUCT/UCLASS types
+    #[arg(long)]
+    cpp_unreal: bool,
+
     #[arg(long)]
     python: bool,
 
+    /// Make --python emit pydantic BaseModel classes
+    #[arg(long)]
+    python_pydantic: bool,
+
+    /// Make --python emit attrs classes
+    #[arg(long)]
+    python_attrs: bool,
+
+    /// Make --python also write a .pyi stub for each module
+    #[arg(long)]
+    python_stubs: bool,
+
+    /// Make --python generate to_dict/from_dict and to_json/from_json methods
+    #[arg(long)]
+    python_dict: bool,
+
+    /// How --python assigns enum values
+    #[arg(long, value_enum, default_value_t = PythonEnumStyle::Positional)]
+    python_enum: PythonEnumStyle,
+
+    /// Make --python decorate enums with @unique
+    #[arg(long)]
+    python_enum_unique: bool,
+
+    /// Make --python name fields in snake_case and static constants in
+    /// SCREAMING_SNAKE_CASE
+    #[arg(long)]
+    python_snake_case: bool,
+
+    /// Make --python dataclasses use slots=True
+    #[arg(long)]
+    python_slots: bool,
+
+    /// Make --python dataclass fields keyword-only
+    #[arg(long)]
+    python_kw_only: bool,
+
+    /// Comma-separated dataclasses --python freezes, instead of those whose
+    /// fields are all const
+    #[arg(long, value_delimiter = ',')]
+    python_frozen: Option<Vec<String>>,
+
+    /// Make --python check the value ranges and lengths OML types imply
+    #[arg(long)]
+    python_validate: bool,
+
+    /// Make --python also write an __init__.py exporting every type
+    #[arg(long)]
+    python_package: bool,
+
+    /// Which fields of --python regular classes get properties
+    #[arg(long, value_enum, default_value_t = AccessorMode::All)]
+    python_accessors: AccessorMode,
+
     #[arg(long)]
     java: bool,
 
@@ -60,6 +196,584 @@ pub struct OmlCli {
 
     #[arg(long)]
     sql: bool,
+
+    #[arg(long)]
+    go: bool,
+
+    #[arg(long)]
+    csharp: bool,
+
+    #[arg(long)]
+    dart: bool,
+
+    #[arg(long)]
+    php: bool,
+
+    #[arg(long)]
+    ruby: bool,
+
+    #[arg(long)]
+    scala: bool,
+
+    #[arg(long)]
+    haskell: bool,
+
+    #[arg(long)]
+    zig: bool,
+
+    #[arg(long)]
+    lua: bool,
+
+    #[arg(long)]
+    julia: bool,
+
+    #[arg(long)]
+    elixir: bool,
+
+    #[arg(long)]
+    fsharp: bool,
+
+    #[arg(long)]
+    ocaml: bool,
+
+    /// Emit proto3 schemas; field numbers come from `@tag(N)` annotations
+    #[arg(long)]
+    proto: bool,
+
+    /// One JSON Schema (draft 2020-12) document per object
+    #[arg(long)]
+    jsonschema: bool,
+
+    #[arg(long)]
+    graphql: bool,
+
+    /// OpenAPI `components.schemas` as YAML
+    #[arg(long)]
+    openapi: bool,
+
+    /// ES modules with JSDoc type annotations
+    #[arg(long)]
+    javascript: bool,
+
+    /// Godot 4 scripts, one per object
+    #[arg(long)]
+    gdscript: bool,
+
+    /// Emit TypeScript interfaces instead of classes
+    #[arg(long)]
+    ts_interfaces: bool,
+
+    /// Package clause used by the Go generator
+    #[arg(long, default_value = "models")]
+    go_package: String,
+
+    /// How the C generator lays out string fields
+    #[arg(long, value_enum, default_value_t = CStringStrategy::PointerLength)]
+    c_strings: CStringStrategy,
+
+    /// Buffer size of strings with `--c-strings fixed`, including the NUL
+    #[arg(long, default_value_t = 64)]
+    c_string_capacity: usize,
+
+    /// Make --cpp use `#pragma once` instead of include guards
+    #[arg(long)]
+    cpp_pragma_once: bool,
+
+    /// Prefix for the include guards of --cpp, e.g. the project name
+    #[arg(long)]
+    cpp_guard_prefix: Option<String>,
+
+    /// What the include guards of --cpp are named after: the OML file, or
+    /// the objects each header declares, which tells apart headers of OML
+    /// files with the same name
+    #[arg(long, value_enum, default_value_t = GuardSource::File)]
+    cpp_guard_from: GuardSource,
+
+    /// Namespace for the C++ generator, e.g. `myproj::models`
+    #[arg(long)]
+    cpp_namespace: Option<String>,
+
+    /// C++ standard the --cpp output targets
+    #[arg(long, value_enum, default_value_t = CppStandard::Cpp17)]
+    cpp_std: CppStandard,
+
+    /// Make --cpp generate comparison operators
+    #[arg(long)]
+    cpp_comparisons: bool,
+
+    /// Make --cpp generate operator<< for printing
+    #[arg(long)]
+    cpp_stream: bool,
+
+    /// Make --cpp generate nlohmann::json to_json/from_json functions
+    #[arg(long)]
+    cpp_json: bool,
+
+    /// Make --cpp generate std::hash specializations
+    #[arg(long)]
+    cpp_hash: bool,
+
+    /// Make --cpp generate a nested Builder for each class and struct
+    #[arg(long)]
+    cpp_builder: bool,
+
+    /// Make --cpp also write <file>_bindings.cpp exposing the header to
+    /// Python through pybind11
+    #[arg(long)]
+    cpp_pybind11: bool,
+
+    /// Make --cpp also write <file>_jni.h converting its types to and from
+    /// the --kotlin classes through JNI, with the shared oml_jni.h
+    #[arg(long)]
+    cpp_jni: bool,
+
+    /// Make --cpp generate to_string/from_string and value lists for enums
+    #[arg(long)]
+    cpp_enum_utils: bool,
+
+    /// Comma-separated classes and structs whose copy members --cpp deletes
+    #[arg(long, value_delimiter = ',')]
+    cpp_move_only: Vec<String>,
+
+    /// Comma-separated classes and structs that --cpp fields hold through
+    /// std::unique_ptr, as TYPE[=HEADER]. Headers forward-declare them, and a
+    /// <file>.cpp defining the members that need them includes HEADER, e.g.
+    /// Engine=engine.h, which types of the same OML file do without
+    #[arg(long, value_delimiter = ',')]
+    cpp_by_pointer: Vec<PointerType>,
+
+    /// Which --cpp fields get getters and setters; the others are public
+    #[arg(long, value_enum, default_value_t = AccessorMode::Private)]
+    cpp_accessors: AccessorMode,
+
+    /// File-scoped namespace for the C# generator
+    #[arg(long)]
+    csharp_namespace: Option<String>,
+
+    /// Make --csharp emit Unity-serializable types with public fields
+    #[arg(long)]
+    csharp_unity: bool,
+
+    /// Namespace for the F# generator; each file becomes a module otherwise
+    #[arg(long)]
+    fsharp_namespace: Option<String>,
+
+    /// Package for the Kotlin generator, e.g. `com.example.models`; files are
+    /// placed in the matching directories
+    #[arg(long)]
+    kotlin_package: Option<String>,
+
+    /// Make --kotlin annotate types with kotlinx.serialization's @Serializable
+    #[arg(long)]
+    kotlin_serializable: bool,
+
+    /// Make --kotlin expose private fields through public getters and setters
+    #[arg(long)]
+    kotlin_accessors: bool,
+
+    /// Make --kotlin emit data classes of read-only vals and Lists, ignoring
+    /// `mut`, with a withX() copy per field
+    #[arg(long)]
+    kotlin_immutable: bool,
+
+    /// Wrap the --openapi schemas in a skeleton OpenAPI 3.1 document
+    #[arg(long)]
+    openapi_full: bool,
+
+    /// Documentation pages, one per object
+    #[arg(long)]
+    docs: bool,
+
+    /// Format of the --docs pages
+    #[arg(long, value_enum, default_value_t = DocsFormat::Markdown)]
+    docs_format: DocsFormat,
+
+    /// Text put as a comment at the top of every generated file; it may use
+    /// the {file}, {object} and {version} placeholders
+    #[arg(long)]
+    header_file: Option<PathBuf>,
+
+    /// What the "generated from" comment at the top of every file includes
+    #[arg(long, value_enum, default_value_t = GeneratedComment::Plain)]
+    pub generated_comment: GeneratedComment,
+
+    /// How the "generated from" comment names the OML files: by file name,
+    /// or by path from the working directory
+    #[arg(long, value_enum, default_value_t = SourceNames::File)]
+    pub source_names: SourceNames,
+
+    /// Keep identifiers as written in OML or follow each target's conventions
+    #[arg(long, value_enum, default_value_t = NamingStyle::Preserve)]
+    naming: NamingStyle,
+
+    /// The order fields, and the constructor parameters and accessors made
+    /// from them, are declared in for every target
+    #[arg(long, value_enum, default_value_t = MemberOrder::Declared)]
+    member_order: MemberOrder,
+
+    /// Casing of type names in every target, overriding --naming
+    #[arg(long, value_enum)]
+    type_case: Option<Case>,
+
+    /// Casing of fields in every target, overriding --naming
+    #[arg(long, value_enum)]
+    field_case: Option<Case>,
+
+    /// Casing of static const fields in every target, overriding --naming
+    #[arg(long, value_enum)]
+    constant_case: Option<Case>,
+
+    /// Casing of enum values in every target, overriding --naming
+    #[arg(long, value_enum)]
+    enum_case: Option<Case>,
+
+    /// Give classes value equality in every target that lacks it by default:
+    /// operator== and std::hash for --cpp, equals and hashCode for --kotlin
+    /// regular classes and PartialEq for --rust structs. --python classes
+    /// always compare by value
+    #[arg(long)]
+    with_equality: bool,
+
+    /// Give classes a readable string form listing every field in every
+    /// target that lacks one by default: operator<< for --cpp and toString
+    /// for --kotlin regular classes. --python classes always have __repr__
+    /// and --rust types derive Debug
+    #[arg(long)]
+    with_tostring: bool,
+
+    /// Give classes a builder checking required fields in every target
+    /// that supports one: a nested Builder for --cpp, a builder class and
+    /// DSL function for --kotlin and a keyword-only factory for --python
+    #[arg(long)]
+    with_builder: bool,
+
+    /// Serialization to turn on in every target that supports it, instead of
+    /// --cpp-json, --kotlin-serializable and --python-dict
+    #[arg(long, value_enum)]
+    serde: Option<SerdeFormat>,
+
+    /// Leave out classes, structs and enums that declare nothing, in every
+    /// target. Without it they are generated as types with no fields or
+    /// values, which every target accepts
+    #[arg(long)]
+    pub skip_empty: bool,
+
+    /// Write every input's objects into one file per target, named after
+    /// `models` (`models.h`, `models.py`, ...), in dependency order
+    #[arg(long)]
+    pub single_file: bool,
+
+    /// Name the files written for each OML file after a template, as
+    /// [TARGET=]TEMPLATE with {name}, {snake_name} and {ext}, e.g.
+    /// {name}.gen.{ext} or python={snake_name}_generated.{ext}. TARGET is a
+    /// language flag such as cpp; without one the template applies to every
+    /// target. Files named after a type, such as test files, keep their name.
+    /// Repeatable
+    #[arg(long)]
+    file_name_template: Vec<FileNameTemplate>,
+
+    /// Replace an OML type in one target, as TARGET:OML_TYPE=TYPE[@INCLUDE],
+    /// e.g. cpp:string=QString@<QString>. The include is a header for --cpp,
+    /// a qualified name for --kotlin and a module for --python, and is added
+    /// to every file using the type; map an `@external` type to itself to
+    /// give it one, e.g. cpp:Money=Money@"money.h". Repeatable
+    #[arg(long)]
+    type_map: Vec<TypeMapping>,
+
+    /// Also write a test file per class and struct for --cpp (GoogleTest),
+    /// --kotlin (kotlin.test) and --python (pytest)
+    #[arg(long)]
+    with_tests: bool,
+
+    /// Give classes and structs a SCHEMA_HASH constant fingerprinting their
+    /// fields, for checking at runtime that two processes were generated
+    /// from the same model: static constexpr for --cpp, a companion const
+    /// for --kotlin and a class attribute for --python
+    #[arg(long)]
+    with_schema_hash: bool,
+
+    #[command(flatten)]
+    style: StyleArgs,
+}
+
+impl GeneratorArgs {
+    fn json(&self) -> bool {
+        self.serde == Some(SerdeFormat::Json)
+    }
+
+    /// The `--type-map` overrides given for `target`.
+    fn type_map_for(&self, target: &str) -> TypeMap {
+        TypeMap::new(self.type_map.iter().filter(|m| m.target == target))
+    }
+
+    pub fn generators(&self) -> Vec<Box<dyn Generate>> {
+        let mut generators: Vec<Box<dyn Generate>> = Vec::new();
+        let style = self.style.code_style();
+        let push = |generators: &mut Vec<Box<dyn Generate>>, target: &str, generator: Box<dyn Generate>| {
+            generators.push(self.templated(target, generator));
+        };
+
+        if self.c {
+            push(&mut generators, "c", Box::new(
+                CGenerator::new(self.c_strings, self.c_string_capacity).with_style(style),
+            ));
+        }
+        if self.cpp {
+            push(&mut generators, "cpp", Box::new(
+                CppGenerator::default()
+                    .with_unreal(self.cpp_unreal)
+                    .with_pragma_once(self.cpp_pragma_once)
+                    .with_guard_prefix(self.cpp_guard_prefix.as_deref())
+                    .with_guard_source(self.cpp_guard_from)
+                    .with_namespace(self.cpp_namespace.as_deref())
+                    .with_standard(self.cpp_std)
+                    .with_comparisons(self.cpp_comparisons || self.with_equality)
+                    .with_stream_operators(self.cpp_stream || self.with_tostring)
+                    .with_json(self.cpp_json || self.json())
+                    .with_hash(self.cpp_hash || self.with_equality)
+                    .with_builder(self.cpp_builder || self.with_builder)
+                    .with_enum_utilities(self.cpp_enum_utils)
+                    .with_move_only(&self.cpp_move_only)
+                    .with_by_pointer(&self.cpp_by_pointer)
+                    .with_accessors(self.cpp_accessors)
+                    .with_tests(self.with_tests)
+                    .with_schema_hash(self.with_schema_hash)
+                    .with_pybind11(self.cpp_pybind11)
+                    .with_jni(self.cpp_jni.then(|| JniBridge {
+                        package: self.kotlin_package.clone(),
+                        accessors: self.kotlin_accessors,
+                    }))
+                    .with_type_map(self.type_map_for("cpp"))
+                    .with_style(style),
+            ));
+        }
+
+        if self.python {
+            push(&mut generators, "python", Box::new(
+                PythonGenerator::new(self.use_data_class)
+                    .with_pydantic(self.python_pydantic)
+                    .with_attrs(self.python_attrs)
+                    .with_stubs(self.python_stubs)
+                    .with_dict_helpers(self.python_dict || self.json())
+                    .with_enum_style(self.python_enum)
+                    .with_unique_enums(self.python_enum_unique)
+                    .with_snake_case(self.python_snake_case)
+                    .with_slots(self.python_slots)
+                    .with_kw_only(self.python_kw_only)
+                    .with_frozen(self.python_frozen.as_deref())
+                    .with_validate(self.python_validate)
+                    .with_package_init(self.python_package)
+                    .with_accessors(self.python_accessors)
+                    .with_tests(self.with_tests)
+                    .with_builder(self.with_builder)
+                    .with_schema_hash(self.with_schema_hash)
+                    .with_type_map(self.type_map_for("python"))
+                    .with_style(style),
+            ));
+        }
+        if self.kotlin {
+            push(&mut generators, "kotlin", Box::new(
+                KotlinGenerator::new(self.use_data_class)
+                    .with_package(self.kotlin_package.as_deref())
+                    .with_serializable(self.kotlin_serializable || self.json())
+                    .with_accessors(self.kotlin_accessors)
+                    .with_immutable(self.kotlin_immutable)
+                    .with_tests(self.with_tests)
+                    .with_equality(self.with_equality)
+                    .with_to_string(self.with_tostring)
+                    .with_builder(self.with_builder)
+                    .with_schema_hash(self.with_schema_hash)
+                    .with_type_map(self.type_map_for("kotlin"))
+                    .with_style(style),
+            ));
+        }
+
+
+        if self.java {
+            push(&mut generators, "java", Box::new(JavaGenerator::default().with_style(style)));
+        }
+        if self.rust {
+            push(&mut generators, "rust", Box::new(RustGenerator::default().with_equality(self.with_equality).with_style(style)));
+        }
+        if self.typescript {
+            push(&mut generators, "typescript", Box::new(TypescriptGenerator::new(self.ts_interfaces).with_style(style)));
+        }
+        if self.sql {
+            push(&mut generators, "sql", Box::new(SqlGenerator::default().with_style(style)));
+        }
+        if self.go {
+            push(&mut generators, "go", Box::new(GoGenerator::new(&self.go_package).with_style(style)));
+        }
+        if self.csharp {
+            push(&mut generators, "csharp", Box::new(
+                CsharpGenerator::new(self.use_data_class)
+                    .with_namespace(self.csharp_namespace.as_deref())
+                    .with_unity(self.csharp_unity)
+                    .with_style(style),
+            ));
+        }
+        if self.dart {
+            push(&mut generators, "dart", Box::new(DartGenerator::default().with_style(style)));
+        }
+        if self.php {
+            push(&mut generators, "php", Box::new(PhpGenerator::default().with_style(style)));
+        }
+        if self.ruby {
+            push(&mut generators, "ruby", Box::new(RubyGenerator::default().with_style(style)));
+        }
+        if self.scala {
+            push(&mut generators, "scala", Box::new(ScalaGenerator::default().with_style(style)));
+        }
+        if self.haskell {
+            push(&mut generators, "haskell", Box::new(HaskellGenerator::default().with_style(style)));
+        }
+        if self.zig {
+            push(&mut generators, "zig", Box::new(ZigGenerator::default().with_style(style)));
+        }
+        if self.lua {
+            push(&mut generators, "lua", Box::new(LuaGenerator::default().with_style(style)));
+        }
+        if self.julia {
+            push(&mut generators, "julia", Box::new(JuliaGenerator::default().with_style(style)));
+        }
+        if self.elixir {
+            push(&mut generators, "elixir", Box::new(ElixirGenerator::default().with_style(style)));
+        }
+        if self.fsharp {
+            push(&mut generators, "fsharp", Box::new(
+                FsharpGenerator::default()
+                    .with_namespace(self.fsharp_namespace.as_deref())
+                    .with_style(style),
+            ));
+        }
+        if self.ocaml {
+            push(&mut generators, "ocaml", Box::new(OcamlGenerator::default().with_style(style)));
+        }
+        if self.proto {
+            push(&mut generators, "proto", Box::new(ProtoGenerator::default().with_style(style)));
+        }
+        if self.jsonschema {
+            push(&mut generators, "jsonschema", Box::new(JsonSchemaGenerator::default().with_style(style)));
+        }
+        if self.graphql {
+            push(&mut generators, "graphql", Box::new(GraphqlGenerator::default().with_style(style)));
+        }
+        if self.openapi {
+            push(&mut generators, "openapi", Box::new(
+                OpenapiGenerator::default()
+                    .with_full_spec(self.openapi_full)
+                    .with_style(style),
+            ));
+        }
+        if self.javascript {
+            push(&mut generators, "javascript", Box::new(JavascriptGenerator::default().with_style(style)));
+        }
+        if self.gdscript {
+            push(&mut generators, "gdscript", Box::new(GdscriptGenerator::default().with_style(style)));
+        }
+        if self.docs {
+            push(&mut generators, "docs", Box::new(DocsGenerator::new(self.docs_format)));
+        }
+
+        generators
+    }
+
+    /// The naming convention `generator` is given its objects in: its own
+    /// with `--naming idiomatic`, and the `--*-case` flags on top.
+    pub fn naming_for(&self, generator: &dyn Generate) -> NamingConvention {
+        let base = match self.naming {
+            NamingStyle::Preserve => NamingConvention::default(),
+            NamingStyle::Idiomatic => generator.naming(),
+        };
+        NamingConvention {
+            types: self.type_case.unwrap_or(base.types),
+            fields: self.field_case.unwrap_or(base.fields),
+            constants: self.constant_case.unwrap_or(base.constants),
+            enum_values: self.enum_case.unwrap_or(base.enum_values),
+        }
+    }
+
+    /// The last `--file-name-template` for the `target` flag, or else the
+    /// last one for all.
+    fn template_for(&self, target: &str) -> Option<&FileNameTemplate> {
+        self.file_name_template
+            .iter()
+            .rev()
+            .find(|t| t.target.as_deref() == Some(target))
+            .or_else(|| self.file_name_template.iter().rev().find(|t| t.target.is_none()))
+    }
+
+    /// `generator` for the `target` flag, following its `template_for`.
+    fn templated(&self, target: &str, generator: Box<dyn Generate>) -> Box<dyn Generate> {
+        match self.template_for(target) {
+            Some(template) => Box::new(Templated { generator, template: template.clone() }),
+            None => generator,
+        }
+    }
+
+    /// Rejects targets that would write the same files: `--c` and `--cpp`
+    /// both write `<file>.h`, unless `--file-name-template` names them apart.
+    pub fn check_targets(&self) -> Result<(), String> {
+        let template = |target| self.template_for(target).map(|t| t.template.as_str());
+        if self.c && self.cpp && template("c") == template("cpp") {
+            return Err(
+                "--c and --cpp both write <file>.h; run them with separate --output directories \
+                 or name one apart, e.g. --file-name-template c={name}_c.{ext}"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// `objects` as `generator` is given them: without empty ones under
+    /// `--skip-empty`, renamed by `naming_for`, with their fields in
+    /// `--member-order` and keywords of the target escaped.
+    pub fn objects_for(&self, generator: &dyn Generate, objects: &[OmlObject]) -> Vec<OmlObject> {
+        let mut objects = self.naming_for(generator).apply(objects);
+        if self.skip_empty {
+            objects.retain(|obj| !obj.is_empty());
+        }
+        escape_keywords(generator, self.member_order.apply(&objects))
+    }
+
+    /// The banner read from `--header-file`, if one was given.
+    pub fn banner(&self) -> std::io::Result<Option<Banner>> {
+        match &self.header_file {
+            Some(path) => Ok(Some(Banner::new(fs::read_to_string(path)?))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Formatting options for the generated code.
+#[derive(Args, Debug, Clone, Copy)]
+pub struct StyleArgs {
+    /// Indent with tabs or spaces
+    #[arg(long, value_enum, default_value_t = IndentKind::Tabs)]
+    indent: IndentKind,
+
+    /// Spaces per indentation level (also the column width of a tab)
+    #[arg(long, default_value_t = 4)]
+    indent_width: usize,
+
+    /// Put opening braces on the same line or the next line
+    #[arg(long, value_enum, default_value_t = BraceStyle::SameLine)]
+    brace_style: BraceStyle,
+
+    /// Column limit used when wrapping long declarations
+    #[arg(long, default_value_t = 120)]
+    max_line_length: usize,
+}
+
+impl StyleArgs {
+    pub fn code_style(&self) -> CodeStyle {
+        CodeStyle {
+            indent_kind: self.indent,
+            indent_width: self.indent_width,
+            brace_style: self.brace_style,
+            max_line_length: self.max_line_length,
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -83,29 +797,67 @@ pub enum Commands {
         #[arg(short, long, default_value = "./oml_output")]
         output: String,
 
-        #[arg(long)]
-        cpp: bool,
+        #[command(flatten)]
+        generator_args: Box<GeneratorArgs>,
+    },
 
-        #[arg(long)]
-        python: bool,
+    /// Compare two versions of a model set and report breaking changes.
+    /// Exits with status 1 when at least one breaking change is found.
+    Diff {
+        /// The old .oml file or directory
+        old: String,
 
-        #[arg(long)]
-        java: bool,
+        /// The new .oml file or directory
+        new: String,
 
-        #[arg(long)]
-        kotlin: bool,
+        #[arg(short, long, default_value_t = 3)]
+        depth: usize,
+    },
 
-        #[arg(long)]
-        rust: bool,
+    /// Type or paste OML and see what the chosen targets generate from it,
+    /// shown again each time an object is complete
+    Repl {
+        #[command(flatten)]
+        generator_args: Box<GeneratorArgs>,
+    },
 
-        #[arg(long)]
-        typescript: bool,
+    /// Write a new .oml file declaring one object, e.g.
+    /// `oml new class Person name:string age:int32 nickname:string?`
+    New {
+        /// The kind of object
+        #[arg(value_enum)]
+        kind: ObjectType,
+
+        /// The object's name; the file is named after it in snake_case
+        name: String,
 
+        /// Fields as NAME:TYPE, where TYPE may end in `?` for an optional
+        /// field, `[]` for a list or `[N]` for an array of N; for an enum,
+        /// variant names
+        members: Vec<String>,
+
+        /// Directory the file is written to
+        #[arg(short, long, default_value = ".")]
+        output: String,
+
+        /// Replace the file if it exists
         #[arg(long)]
-        sql: bool,
+        force: bool,
+    },
+
+    /// Print .oml files syntax-highlighted, for documentation sites and
+    /// review tools
+    Highlight {
+        /// The .oml files to highlight
+        files: Vec<String>,
+
+        #[arg(long, value_enum, default_value_t = HighlightFormat::Html)]
+        format: HighlightFormat,
 
+        /// With --format html, print a whole page with a stylesheet instead
+        /// of a `<pre>` block per file
         #[arg(long)]
-        use_data_class: bool,
+        standalone: bool,
     },
 }
 
@@ -120,81 +872,64 @@ impl OmlCli {
     }
 
     pub fn get_files(&self) -> Result<Vec<OmlFile>, errors::ParseError> {
-        let input_files = match &self.inputs {
-            Some(inputs) => inputs,
-            None => {
-                return Err(errors::ParseError::InvalidPath);
-            }
-        };
+        let input_files = self.inputs.as_ref().ok_or(errors::ParseError::InvalidPath)?;
 
         let mut files = Vec::new();
-
         for file_name in input_files {
-            let mut parsed = parse_dir_from_string(file_name.clone(), self.depth)?;
-            files.append(&mut parsed);
+            files.extend(parse_dir_from_string(file_name.clone(), self.depth)?);
         }
-
         Ok(files)
     }
 
-    pub fn get_generators(&self) -> Vec<Box<dyn Generate>> {
-        let mut generators: Vec<Box<dyn Generate>> = Vec::new();
+    /// Parses the input files in the order `get_files` returns them, handing
+    /// each to `each` as soon as it and those before it are parsed, or the
+    /// error saying why it does not parse. `each` can stop the parsing.
+    pub fn for_each_file(&self, mut each: impl FnMut(Result<OmlFile, Diagnostic>) -> ControlFlow<()>) -> Result<(), errors::ParseError> {
+        let input_files = self.inputs.as_ref().ok_or(errors::ParseError::InvalidPath)?;
 
-        if self.cpp {
-            generators.push(Box::new(CppGenerator));
+        let mut stopped = false;
+        for file_name in input_files {
+            parse_dir_each(file_name, self.depth, |parsed| {
+                let flow = each(parsed);
+                stopped = flow.is_break();
+                flow
+            })?;
+            if stopped {
+                break;
+            }
         }
 
-        if self.python {
-            generators.push(Box::new(PythonGenerator::new(self.use_data_class)));
-        }
-        if self.kotlin {
-            generators.push(Box::new(KotlinGenerator::new(self.use_data_class)));
-        }
+        Ok(())
+    }
 
+    /// The canonical paths of the input files, in the order `get_files`
+    /// returns them, without parsing them.
+    pub fn list_files(&self) -> Result<Vec<PathBuf>, errors::ParseError> {
+        let input_files = self.inputs.as_ref().ok_or(errors::ParseError::InvalidPath)?;
 
-        if self.java {
-            generators.push(Box::new(JavaGenerator));
-        }
-        if self.rust {
-            generators.push(Box::new(RustGenerator));
-        }
-        if self.typescript {
-            generators.push(Box::new(TypescriptGenerator));
-        }
-        if self.sql {
-            generators.push(Box::new(SqlGenerator));
+        let mut files = Vec::new();
+        for file_name in input_files {
+            files.extend(list_files(file_name, self.depth)?);
         }
 
-        generators
+        Ok(files)
     }
-}
 
-/// Builds generators from individual language flags (used by the Translate subcommand).
-pub fn get_generators_from_flags(
-    cpp: bool, python: bool, java: bool, kotlin: bool,
-    rust: bool, typescript: bool, sql: bool, use_data_class: bool,
-) -> Vec<Box<dyn Generate>> {
-    let mut generators: Vec<Box<dyn Generate>> = Vec::new();
-    if cpp { generators.push(Box::new(CppGenerator)); }
-    if python { generators.push(Box::new(PythonGenerator::new(use_data_class))); }
-    if kotlin { generators.push(Box::new(KotlinGenerator::new(use_data_class))); }
-    if java { generators.push(Box::new(JavaGenerator)); }
-    if rust { generators.push(Box::new(RustGenerator)); }
-    if typescript { generators.push(Box::new(TypescriptGenerator)); }
-    if sql { generators.push(Box::new(SqlGenerator)); }
-    generators
+    pub fn get_generators(&self) -> Vec<Box<dyn Generate>> {
+        self.generator_args.generators()
+    }
 }
 
 /// Returns the appropriate backwards generator for a file based on its extension.
 pub fn get_backwards_generator(extension: &str) -> Option<Box<dyn BackwardsGenerate>> {
     match extension {
-        "rs" => Some(Box::new(RustGenerator)),
+        "rs" => Some(Box::new(RustGenerator::default())),
         "kt" => Some(Box::new(KotlinGenerator::new(false))),
-        "cpp" | "h" => Some(Box::new(CppGenerator)),
+        "cpp" | "h" => Some(Box::new(CppGenerator::default())),
         "py" => Some(Box::new(PythonGenerator::new(false))),
-        "java" => Some(Box::new(JavaGenerator)),
-        "ts" => Some(Box::new(TypescriptGenerator)),
-        "sql" => Some(Box::new(SqlGenerator)),
+        "java" => Some(Box::new(JavaGenerator::default())),
+        "ts" => Some(Box::new(TypescriptGenerator::default())),
+        "sql" => Some(Box::new(SqlGenerator::default())),
         _ => None,
     }
 }
@@ -0,0 +1,236 @@
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+
+use crate::cli::oml::GeneratorArgs;
+use crate::core::generate::{Generate, validate_identifiers};
+use crate::core::oml_object::{ObjectType, OmlObject};
+
+/// What the output shown by `oml repl` is generated as, e.g. `repl.h`.
+const FILE_NAME: &str = "repl";
+
+const HELP: &str = "\
+Type or paste OML; the output is shown again each time an object is complete.
+Declaring an object again replaces it.
+  :show            show the output again
+  :remove NAME     forget an object
+  :clear           forget every object
+  :help            show this message
+  :quit            leave (as does end of input)";
+
+/// `oml repl`: the objects declared so far, and the lines of the one being
+/// typed.
+pub struct Repl<'a> {
+    args: &'a GeneratorArgs,
+    generators: Vec<Box<dyn Generate>>,
+    objects: Vec<OmlObject>,
+    pending: Vec<String>,
+    /// Braces opened and not yet closed in `pending`
+    depth: usize,
+    /// Whether `pending` has opened a body yet
+    opened: bool,
+}
+
+impl<'a> Repl<'a> {
+    pub fn new(args: &'a GeneratorArgs) -> Self {
+        Self { args, generators: args.generators(), objects: Vec::new(), pending: Vec::new(), depth: 0, opened: false }
+    }
+
+    /// Reads `input` to its end or `:quit`, writing the output of each
+    /// object as it is completed to `out`. With `prompt`, a prompt is shown
+    /// before each line, as for someone typing.
+    pub fn run(&mut self, input: impl BufRead, out: &mut impl Write, prompt: bool) -> io::Result<()> {
+        if self.generators.is_empty() {
+            writeln!(out, "error: No language flag specified (e.g. --cpp)")?;
+            return Ok(());
+        }
+        if prompt {
+            writeln!(out, "OML playground. :help lists the commands.")?;
+        }
+
+        let mut lines = input.lines();
+        loop {
+            if prompt {
+                write!(out, "{}", if self.pending.is_empty() { "oml> " } else { "...> " })?;
+                out.flush()?;
+            }
+            let Some(line) = lines.next().transpose()? else {
+                break;
+            };
+            if !self.line(&line, out)? {
+                return Ok(());
+            }
+        }
+
+        if !self.pending.is_empty() {
+            writeln!(out, "error: input ended inside an object; it was not added")?;
+        }
+        Ok(())
+    }
+
+    /// Takes one line of input. Returns false once asked to quit.
+    fn line(&mut self, line: &str, out: &mut impl Write) -> io::Result<bool> {
+        let trimmed = line.trim();
+        if self.pending.is_empty() && trimmed.starts_with(':') {
+            return self.command(trimmed, out);
+        }
+        if trimmed.is_empty() {
+            // a blank line ends text that never opened a body, e.g. a typo
+            if !self.pending.is_empty() && !self.opened {
+                self.complete(out)?;
+            }
+            return Ok(true);
+        }
+
+        let code = trimmed.split("//").next().unwrap_or_default();
+        for c in code.chars() {
+            match c {
+                '{' => {
+                    self.depth += 1;
+                    self.opened = true;
+                }
+                '}' => self.depth = self.depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        self.pending.push(line.to_string());
+        if self.opened && self.depth == 0 {
+            self.complete(out)?;
+        }
+        Ok(true)
+    }
+
+    fn command(&mut self, command: &str, out: &mut impl Write) -> io::Result<bool> {
+        let mut words = command.split_whitespace();
+        match (words.next().unwrap_or_default(), words.next()) {
+            (":quit" | ":q", None) => return Ok(false),
+            (":help", None) => writeln!(out, "{}", HELP)?,
+            (":show", None) => self.render(out)?,
+            (":clear", None) => {
+                self.objects.clear();
+                writeln!(out, "Cleared")?;
+            }
+            (":remove", Some(name)) => match self.objects.iter().position(|o| o.name == name) {
+                Some(index) => {
+                    self.objects.remove(index);
+                    self.render(out)?;
+                }
+                None => writeln!(out, "error: no object named '{}'", name)?,
+            },
+            _ => writeln!(out, "error: unknown command '{}'; :help lists the commands", command)?,
+        }
+        Ok(true)
+    }
+
+    /// Parses the lines typed since the last object, adds what they declare
+    /// and shows the output again.
+    fn complete(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let source = self.pending.join("\n");
+        self.pending.clear();
+        self.depth = 0;
+        self.opened = false;
+
+        let parsed = OmlObject::scan_file(source).and_then(|objects| {
+            OmlObject::validate_modifiers(&objects)?;
+            OmlObject::validate_enum_variants(&objects)?;
+            Ok(objects)
+        });
+        let objects = match parsed {
+            Ok(objects) if objects.is_empty() => {
+                writeln!(out, "error: nothing was declared: objects start with `class`, `struct` or `enum`, and their fields go on lines of their own")?;
+                return Ok(());
+            }
+            Ok(objects) => objects,
+            Err(e) => {
+                writeln!(out, "error: {}", e)?;
+                return Ok(());
+            }
+        };
+
+        for obj in objects {
+            match self.objects.iter_mut().find(|o| o.name == obj.name) {
+                Some(existing) => *existing = obj,
+                None => self.objects.push(obj),
+            }
+        }
+        self.render(out)
+    }
+
+    /// Writes the output of every target for the objects so far.
+    fn render(&self, out: &mut impl Write) -> io::Result<()> {
+        let declared: HashSet<&str> = self.objects.iter().map(|o| o.name.as_str()).collect();
+        let mut undeclared: Vec<&str> = Vec::new();
+        for obj in self.objects.iter().filter(|o| o.oml_type != ObjectType::ENUM) {
+            for var in obj.variables.iter().filter(|v| !v.external && !OmlObject::is_builtin_type(&v.var_type)) {
+                if !declared.contains(var.var_type.as_str()) && !undeclared.contains(&var.var_type.as_str()) {
+                    undeclared.push(&var.var_type);
+                }
+            }
+        }
+        for name in undeclared {
+            writeln!(out, "note: '{}' is not declared yet", name)?;
+        }
+
+        for generator in &self.generators {
+            let objects = self.args.objects_for(generator.as_ref(), &self.objects);
+            match validate_identifiers(generator.as_ref(), &objects).and_then(|()| generator.generate_files(&objects, FILE_NAME)) {
+                Ok(files) => {
+                    for file in files {
+                        writeln!(out, "==> {} <==", file.path.display())?;
+                        write!(out, "{}", file.content)?;
+                        if !file.content.ends_with('\n') {
+                            writeln!(out)?;
+                        }
+                    }
+                }
+                Err(e) => writeln!(out, "error: {} output: {}", generator.extension(), e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::oml::{Commands, OmlCli};
+    use clap::Parser;
+
+    fn session(flags: &[&str], input: &str) -> String {
+        let cli = OmlCli::parse_from(["oml", "repl"].iter().chain(flags));
+        let Some(Commands::Repl { generator_args }) = cli.command else {
+            panic!("not a repl command");
+        };
+        let mut out = Vec::new();
+        Repl::new(&generator_args).run(input.as_bytes(), &mut out, false).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_output_is_shown_for_each_complete_object() {
+        let output = session(&["--python"], "\
+class Car {
+    Engine engine;
+}
+:bogus
+struct Engine { int32 power; }
+enum Engine {
+    public Diesel;
+}
+");
+        let messages: Vec<&str> = output.lines().filter(|l| l.starts_with("error") || l.starts_with("note")).collect();
+        assert_eq!(messages, vec![
+            "note: 'Engine' is not declared yet",
+            "error: unknown command ':bogus'; :help lists the commands",
+            "error: nothing was declared: objects start with `class`, `struct` or `enum`, and their fields go on lines of their own",
+            "error: line 2: Error parsing line 'public Diesel;': An enum variant cannot be 'public': variants are plain names",
+        ]);
+        assert_eq!(output.matches("==> repl.py <==").count(), 1, "Got: {}", output);
+        assert!(output.contains("class Car:"), "Got: {}", output);
+
+        let output = session(&["--python"], "class Car {\n    int32 id;\n}\nenum Color {\n    Red;\n}\n:remove Car\n:remove Car\nclass Half {\n");
+        assert_eq!(output.matches("==> repl.py <==").count(), 3, "Got: {}", output);
+        assert!(output.ends_with("error: no object named 'Car'\nerror: input ended inside an object; it was not added\n"), "Got: {}", output);
+
+        assert_eq!(session(&[], "class Car {}\n"), "error: No language flag specified (e.g. --cpp)\n");
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use crate::core::backwards_converting::OmlGenerator;
+use crate::core::generate::Generate;
+use crate::core::oml_object::{ArrayKind, ObjectType, OmlObject, Variable, VariableModifier};
+use crate::core::utils::is_identifier;
+
+/// The object `oml new` writes: of `kind`, named `name`, with a field for
+/// each of `members` given as `NAME:TYPE`, or a variant for each name for
+/// an enum.
+///
+/// A type ending in `?` makes the field optional, `[]` a list and `[N]` an
+/// array of N, e.g. `nickname:string?` or `scores:int32[]`.
+pub fn scaffold(kind: ObjectType, name: &str, members: &[String]) -> Result<OmlObject, String> {
+    if !is_identifier(name) {
+        return Err(format!("'{}' is not a valid object name: names start with a letter or '_' and contain only letters, digits and '_'", name));
+    }
+
+    let mut variables: Vec<Variable> = Vec::new();
+    for member in members {
+        let var = match kind {
+            ObjectType::ENUM => parse_variant(member)?,
+            _ => parse_field(member)?,
+        };
+        if variables.iter().any(|v| v.name == var.name) {
+            return Err(format!("'{}' is given twice", var.name));
+        }
+        variables.push(var);
+    }
+
+    Ok(OmlObject { oml_type: kind, name: name.to_string(), variables, ..Default::default() })
+}
+
+/// `obj` as the content of an .oml file, checked to parse back to it.
+pub fn render(obj: &OmlObject) -> Result<String, String> {
+    let content = OmlGenerator.generate(std::slice::from_ref(obj), &obj.name).map_err(|e| e.to_string())?;
+    let parsed = OmlObject::scan_file(content.clone()).map_err(|e| format!("the scaffold does not parse: {}", e))?;
+    if parsed.len() != 1 || parsed[0].variables.len() != obj.variables.len() {
+        return Err("the scaffold does not parse back to the object given".to_string());
+    }
+    Ok(content)
+}
+
+/// The types `obj` uses that are not built in, which the file has to
+/// import.
+pub fn custom_types(obj: &OmlObject) -> Vec<&str> {
+    let mut seen = HashSet::new();
+    obj.variables
+        .iter()
+        .filter(|v| obj.oml_type != ObjectType::ENUM && !OmlObject::is_builtin_type(&v.var_type))
+        .map(|v| v.var_type.as_str())
+        .filter(|t| seen.insert(*t))
+        .collect()
+}
+
+fn parse_field(member: &str) -> Result<Variable, String> {
+    let Some((name, spec)) = member.split_once(':') else {
+        return Err(format!("'{}' has no type: fields are given as NAME:TYPE, e.g. age:int32", member));
+    };
+    if !is_identifier(name) {
+        return Err(format!("'{}' is not a valid field name", name));
+    }
+
+    let (spec, optional) = match spec.strip_suffix('?') {
+        Some(spec) => (spec, true),
+        None => (spec, false),
+    };
+    let (var_type, array_kind) = match spec.strip_suffix(']').and_then(|rest| rest.split_once('[')) {
+        Some((var_type, "")) => (var_type, ArrayKind::Dynamic),
+        Some((var_type, size)) => match size.parse::<u32>() {
+            Ok(size) if size > 0 => (var_type, ArrayKind::Static(size)),
+            _ => return Err(format!("'{}' has an invalid array size '{}': sizes are whole numbers above 0", member, size)),
+        },
+        None => (spec, ArrayKind::None),
+    };
+    if !is_identifier(var_type) {
+        return Err(format!("'{}' is not a valid type", var_type));
+    }
+
+    Ok(Variable {
+        var_mod: if optional { vec![VariableModifier::OPTIONAL] } else { Vec::new() },
+        var_type: var_type.to_string(),
+        array_kind,
+        name: name.to_string(),
+        ..Default::default()
+    })
+}
+
+fn parse_variant(member: &str) -> Result<Variable, String> {
+    if member.contains(':') {
+        return Err(format!("'{}' has a type, but enum variants are plain names", member));
+    }
+    if !is_identifier(member) {
+        return Err(format!("'{}' is not a valid variant name", member));
+    }
+    Ok(Variable { var_type: "string".to_string(), name: member.to_string(), ..Default::default() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members(specs: &[&str]) -> Vec<String> {
+        specs.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_scaffold_from_field_specs() {
+        let person = scaffold(
+            ObjectType::CLASS,
+            "Person",
+            &members(&["name:string", "age:int32", "nickname:string?", "scores:int32[]", "address:Address[2]"]),
+        )
+        .unwrap();
+        assert_eq!(
+            render(&person).unwrap(),
+            "class Person {\n    string name;\n    int32 age;\n    optional string nickname;\n    list int32 scores;\n    Address[2] address;\n}\n"
+        );
+        assert_eq!(custom_types(&person), vec!["Address"]);
+
+        let color = scaffold(ObjectType::ENUM, "Color", &members(&["Red", "Green"])).unwrap();
+        assert_eq!(render(&color).unwrap(), "enum Color {\n    string Red;\n    string Green;\n}\n");
+
+        for (kind, name, specs, error) in [
+            (ObjectType::CLASS, "Person", vec!["age"], "'age' has no type: fields are given as NAME:TYPE, e.g. age:int32"),
+            (ObjectType::CLASS, "Person", vec!["age:int32", "age:int64"], "'age' is given twice"),
+            (ObjectType::STRUCT, "Point", vec!["xs:float[0]"], "'xs:float[0]' has an invalid array size '0': sizes are whole numbers above 0"),
+            (ObjectType::ENUM, "Color", vec!["Red:int32"], "'Red:int32' has a type, but enum variants are plain names"),
+            (ObjectType::CLASS, "my-person", vec![], "'my-person' is not a valid object name: names start with a letter or '_' and contain only letters, digits and '_'"),
+        ] {
+            assert_eq!(scaffold(kind, name, &members(&specs)).unwrap_err(), error);
+        }
+    }
+}
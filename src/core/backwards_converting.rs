@@ -34,16 +34,32 @@ impl Generate for OmlGenerator {
     }
 }
 
+/// Writes `doc` as `///` comment lines at the given indentation.
+fn write_doc(doc: &Option<String>, indent: &str, out: &mut String) -> Result<(), std::fmt::Error> {
+    if let Some(doc) = doc {
+        for line in doc.lines() {
+            writeln!(out, "{}/// {}", indent, line)?;
+        }
+    }
+    Ok(())
+}
+
 fn generate_enum(obj: &OmlObject, out: &mut String) -> Result<(), std::fmt::Error> {
+    write_doc(&obj.doc, "", out)?;
     writeln!(out, "enum {} {{", obj.name)?;
     for var in &obj.variables {
-        writeln!(out, "    {} {};", var.var_type, var.name)?;
+        write_doc(&var.doc, "    ", out)?;
+        match &var.value {
+            Some(value) => writeln!(out, "    {} {} = {};", var.var_type, var.name, value)?,
+            None => writeln!(out, "    {} {};", var.var_type, var.name)?,
+        }
     }
     writeln!(out, "}}")?;
     Ok(())
 }
 
 fn generate_class(obj: &OmlObject, out: &mut String) -> Result<(), std::fmt::Error> {
+    write_doc(&obj.doc, "", out)?;
     writeln!(out, "class {} {{", obj.name)?;
     write_variables(obj, out)?;
     writeln!(out, "}}")?;
@@ -51,6 +67,7 @@ fn generate_class(obj: &OmlObject, out: &mut String) -> Result<(), std::fmt::Err
 }
 
 fn generate_struct(obj: &OmlObject, out: &mut String) -> Result<(), std::fmt::Error> {
+    write_doc(&obj.doc, "", out)?;
     writeln!(out, "struct {} {{", obj.name)?;
     write_variables(obj, out)?;
     writeln!(out, "}}")?;
@@ -59,6 +76,7 @@ fn generate_struct(obj: &OmlObject, out: &mut String) -> Result<(), std::fmt::Er
 
 fn write_variables(obj: &OmlObject, out: &mut String) -> Result<(), std::fmt::Error> {
     for var in &obj.variables {
+        write_doc(&var.doc, "    ", out)?;
         write!(out, "    ")?;
 
         // Visibility (private is default, omit it)
@@ -85,7 +103,10 @@ fn write_variables(obj: &OmlObject, out: &mut String) -> Result<(), std::fmt::Er
             ArrayKind::Dynamic => write!(out, "list {}", var.var_type)?,
         }
 
-        writeln!(out, " {};", var.name)?;
+        match &var.value {
+            Some(value) => writeln!(out, " {} = {};", var.name, value)?,
+            None => writeln!(out, " {};", var.name)?,
+        }
     }
     Ok(())
 }
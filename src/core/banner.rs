@@ -0,0 +1,135 @@
+use std::io::{self, Write};
+
+use crate::core::generate::CommentSyntax;
+use crate::core::oml_object::OmlObject;
+
+/// A license or copyright notice put at the top of every generated file.
+/// The template may use the `{file}`, `{object}` and `{version}`
+/// placeholders, which become the source .oml file, the names of the
+/// objects it declares and the version of oml.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Banner {
+    template: String,
+}
+
+impl Banner {
+    pub fn new(template: impl Into<String>) -> Self {
+        Self { template: template.into() }
+    }
+
+    /// The filled-in template wrapped in `syntax`, or `None` for formats
+    /// that have no comments.
+    pub fn render(&self, syntax: CommentSyntax, source: &str, objects: &[OmlObject]) -> Option<String> {
+        let names: Vec<&str> = objects.iter().map(|o| o.name.as_str()).collect();
+        let text = self.template
+            .replace("{file}", source)
+            .replace("{object}", &names.join(", "))
+            .replace("{version}", env!("CARGO_PKG_VERSION"));
+        let lines = text.trim_end().lines();
+
+        let mut banner = String::new();
+        match syntax {
+            CommentSyntax::Line(prefix) => {
+                for line in lines {
+                    banner.push_str(prefix);
+                    if !line.is_empty() {
+                        banner.push(' ');
+                        banner.push_str(line);
+                    }
+                    banner.push('\n');
+                }
+            }
+            CommentSyntax::Block(open, close) => {
+                banner.push_str(open);
+                banner.push('\n');
+                for line in lines {
+                    banner.push_str(line);
+                    banner.push('\n');
+                }
+                banner.push_str(close);
+                banner.push('\n');
+            }
+            CommentSyntax::None => return None,
+        }
+        Some(banner)
+    }
+
+    /// Puts the banner above `content`, below a first line that has to stay
+    /// first such as `<?php` or a doctype.
+    pub fn apply(&self, content: &str, syntax: CommentSyntax, source: &str, objects: &[OmlObject]) -> String {
+        let mut out = Vec::new();
+        self.write(content, syntax, source, objects, &mut out).expect("writing to a Vec cannot fail");
+        String::from_utf8(out).expect("banner and content are UTF-8")
+    }
+
+    /// Writes `content` to `out` with the banner put in as `apply` does,
+    /// without building the result in memory first.
+    pub fn write(
+        &self,
+        content: &str,
+        syntax: CommentSyntax,
+        source: &str,
+        objects: &[OmlObject],
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        let Some(banner) = self.render(syntax, source, objects) else {
+            return out.write_all(content.as_bytes());
+        };
+
+        let pinned = ["<?php", "<!DOCTYPE", "#!"].iter().any(|p| content.starts_with(p));
+        match content.split_once('\n') {
+            Some((first, rest)) if pinned => {
+                writeln!(out, "{}", first)?;
+                out.write_all(banner.as_bytes())?;
+                out.write_all(rest.as_bytes())
+            }
+            _ => {
+                out.write_all(banner.as_bytes())?;
+                out.write_all(content.as_bytes())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::oml_object::ObjectType;
+
+    fn objects() -> Vec<OmlObject> {
+        vec![
+            OmlObject { oml_type: ObjectType::CLASS, name: "Car".to_string(), ..Default::default() },
+            OmlObject { oml_type: ObjectType::ENUM, name: "Color".to_string(), ..Default::default() },
+        ]
+    }
+
+    #[test]
+    fn test_line_comment_placeholders() {
+        let banner = Banner::new("Copyright ACME\n\n{object} from {file}, oml {version}\n");
+        assert_eq!(
+            banner.render(CommentSyntax::Line("#"), "car.oml", &objects()).unwrap(),
+            format!("# Copyright ACME\n#\n# Car, Color from car.oml, oml {}\n", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn test_block_comment_and_no_comments() {
+        let banner = Banner::new("MIT License");
+        assert_eq!(banner.render(CommentSyntax::Block("(*", "*)"), "car.oml", &[]).unwrap(), "(*\nMIT License\n*)\n");
+        assert_eq!(banner.render(CommentSyntax::None, "car.oml", &[]), None);
+        assert_eq!(banner.apply("{}\n", CommentSyntax::None, "car.oml", &[]), "{}\n");
+    }
+
+    #[test]
+    fn test_banner_stays_below_pinned_first_line() {
+        let banner = Banner::new("MIT License");
+        assert_eq!(
+            banner.apply("<?php\n// body\n", CommentSyntax::Line("//"), "car.oml", &[]),
+            "<?php\n// MIT License\n// body\n"
+        );
+        assert_eq!(
+            banner.apply("// body\n", CommentSyntax::Line("//"), "car.oml", &[]),
+            "// MIT License\n// body\n"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::utils::fnv1a;
+
+/// What the last run writing to an output directory read and produced,
+/// kept in `<output>/.oml-cache`. A run with the same options, inputs and
+/// untouched outputs does nothing; otherwise only the modules whose sources
+/// changed are generated again.
+///
+/// Outputs are recorded relative to the output directory, inputs by their
+/// canonical path, and files by the FNV-1a hash of their content.
+#[derive(Debug, Default, PartialEq)]
+pub struct BuildCache {
+    /// Hash of everything besides the inputs that shapes the outputs: the
+    /// generator options, the header and the version of oml
+    pub options: u64,
+    /// The files given on the command line or found in given directories
+    pub roots: Vec<PathBuf>,
+    /// Every OML file read, imports included
+    pub inputs: Vec<(PathBuf, u64)>,
+    /// The outputs of each module, by the hash of what it is generated from
+    pub modules: HashMap<u64, Vec<(PathBuf, u64)>>,
+    /// The outputs generated from all modules together
+    pub package_outputs: Vec<(PathBuf, u64)>,
+}
+
+impl BuildCache {
+    pub const FILE_NAME: &'static str = ".oml-cache";
+    const HEADER: &'static str = "oml-cache 1";
+
+    /// The cache in `output_dir`, if there is a readable one.
+    pub fn load(output_dir: &Path) -> Option<Self> {
+        Self::parse(&fs::read_to_string(output_dir.join(Self::FILE_NAME)).ok()?)
+    }
+
+    pub fn save(&self, output_dir: &Path) -> std::io::Result<()> {
+        fs::write(output_dir.join(Self::FILE_NAME), self.render())
+    }
+
+    /// Whether the outputs recorded are still what `options` and `roots`
+    /// would produce: no input changed and no output was touched since.
+    pub fn is_fresh(&self, options: u64, roots: &[PathBuf], output_dir: &Path) -> bool {
+        self.options == options
+            && self.roots == roots
+            && self.inputs.iter().all(|(path, hash)| hash_file(path) == Some(*hash))
+            && self.modules.values().all(|outputs| outputs_intact(outputs, output_dir))
+            && outputs_intact(&self.package_outputs, output_dir)
+    }
+
+    /// The outputs of the module generated from `key`, if all of them are
+    /// still as they were written.
+    pub fn module_outputs(&self, key: u64, output_dir: &Path) -> Option<&[(PathBuf, u64)]> {
+        self.modules
+            .get(&key)
+            .filter(|outputs| outputs_intact(outputs, output_dir))
+            .map(Vec::as_slice)
+    }
+
+    /// Every output recorded, package outputs last.
+    pub fn outputs(&self) -> Vec<&Path> {
+        let mut outputs: Vec<&Path> = self.modules.values().flatten().map(|(path, _)| path.as_path()).collect();
+        outputs.sort();
+        outputs.extend(self.package_outputs.iter().map(|(path, _)| path.as_path()));
+        outputs
+    }
+
+    /// One `kind hash path` line per entry, paths last as they may hold
+    /// spaces; `output` lines belong to the `module` or `package` above.
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "{}", Self::HEADER);
+        let _ = writeln!(out, "options {:016x}", self.options);
+        for root in &self.roots {
+            let _ = writeln!(out, "root {}", root.display());
+        }
+        for (path, hash) in &self.inputs {
+            let _ = writeln!(out, "input {:016x} {}", hash, path.display());
+        }
+        let mut keys: Vec<&u64> = self.modules.keys().collect();
+        keys.sort();
+        for key in keys {
+            let _ = writeln!(out, "module {:016x}", key);
+            for (path, hash) in &self.modules[key] {
+                let _ = writeln!(out, "output {:016x} {}", hash, path.display());
+            }
+        }
+        let _ = writeln!(out, "package");
+        for (path, hash) in &self.package_outputs {
+            let _ = writeln!(out, "output {:016x} {}", hash, path.display());
+        }
+        out
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines();
+        if lines.next()? != Self::HEADER {
+            return None;
+        }
+        let mut cache = BuildCache::default();
+        // the module the `output` lines go to, `None` in the package section
+        let mut module = None;
+        for line in lines {
+            let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+            match kind {
+                "options" => cache.options = u64::from_str_radix(rest, 16).ok()?,
+                "root" => cache.roots.push(PathBuf::from(rest)),
+                "input" => cache.inputs.push(hashed_path(rest)?),
+                "module" => {
+                    let key = u64::from_str_radix(rest, 16).ok()?;
+                    cache.modules.insert(key, Vec::new());
+                    module = Some(key);
+                }
+                "package" => module = None,
+                "output" => match module {
+                    Some(key) => cache.modules.get_mut(&key)?.push(hashed_path(rest)?),
+                    None => cache.package_outputs.push(hashed_path(rest)?),
+                },
+                _ => return None,
+            }
+        }
+        Some(cache)
+    }
+}
+
+/// The content hash of the file at `path`, `None` if it cannot be read.
+pub fn hash_file(path: &Path) -> Option<u64> {
+    fs::read(path).ok().map(|content| fnv1a(&content))
+}
+
+fn outputs_intact(outputs: &[(PathBuf, u64)], output_dir: &Path) -> bool {
+    outputs.iter().all(|(path, hash)| hash_file(&output_dir.join(path)) == Some(*hash))
+}
+
+/// `<hash> <path>`
+fn hashed_path(s: &str) -> Option<(PathBuf, u64)> {
+    let (hash, path) = s.split_once(' ')?;
+    Some((PathBuf::from(path), u64::from_str_radix(hash, 16).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_and_parse_round_trip() {
+        let cache = BuildCache {
+            options: 0xabc,
+            roots: vec![PathBuf::from("/models/race car.oml")],
+            inputs: vec![(PathBuf::from("/models/race car.oml"), 1), (PathBuf::from("/shared/engine.oml"), 2)],
+            modules: HashMap::from([(7, vec![(PathBuf::from("race car.h"), 3)]), (8, vec![])]),
+            package_outputs: vec![(PathBuf::from("__init__.py"), 4)],
+        };
+        assert_eq!(BuildCache::parse(&cache.render()), Some(cache));
+        assert_eq!(BuildCache::parse("oml-cache 0\n"), None);
+        assert_eq!(BuildCache::parse("oml-cache 1\noptions xyz\n"), None);
+    }
+
+    #[test]
+    fn test_fresh_until_an_input_or_output_changes() {
+        let dir = std::env::temp_dir().join(format!("oml_build_cache_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("car.oml");
+        fs::write(&input, "class Car {\n}\n").unwrap();
+        fs::write(dir.join("car.h"), "struct Car {};\n").unwrap();
+
+        let cache = BuildCache {
+            options: 1,
+            roots: vec![input.clone()],
+            inputs: vec![(input.clone(), hash_file(&input).unwrap())],
+            modules: HashMap::from([(7, vec![(PathBuf::from("car.h"), hash_file(&dir.join("car.h")).unwrap())])]),
+            ..Default::default()
+        };
+        assert!(cache.is_fresh(1, std::slice::from_ref(&input), &dir));
+        assert!(!cache.is_fresh(2, std::slice::from_ref(&input), &dir));
+        assert!(!cache.is_fresh(1, &[], &dir));
+
+        fs::write(dir.join("car.h"), "struct Car { int x; };\n").unwrap();
+        assert!(!cache.is_fresh(1, std::slice::from_ref(&input), &dir));
+        assert_eq!(cache.module_outputs(7, &dir), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
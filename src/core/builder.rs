@@ -0,0 +1,44 @@
+use crate::core::oml_object::{OmlObject, Variable, VariableModifier};
+
+/// A field set through a builder. Required fields have to be given before
+/// the object can be built; optional ones default to empty.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BuilderField<'a> {
+    pub var: &'a Variable,
+    pub required: bool,
+}
+
+/// The fields every target's builder sets, in declaration order: all
+/// instance fields, required unless marked optional. Static fields belong to
+/// the type and are left out.
+pub fn builder_fields(oml_object: &OmlObject) -> Vec<BuilderField<'_>> {
+    oml_object.variables
+        .iter()
+        .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+        .map(|var| BuilderField { var, required: !var.var_mod.contains(&VariableModifier::OPTIONAL) })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::oml_object::ObjectType;
+
+    #[test]
+    fn test_builder_fields_skip_statics() {
+        let var = |name: &str, var_mod: Vec<VariableModifier>| Variable { name: name.to_string(), var_mod, ..Default::default() };
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("nickname", vec![VariableModifier::OPTIONAL]),
+                var("count", vec![VariableModifier::STATIC]),
+                var("name", vec![VariableModifier::CONST]),
+            ],
+            ..Default::default()
+        };
+
+        let fields: Vec<(&str, bool)> = builder_fields(&oml_object).iter().map(|f| (f.var.name.as_str(), f.required)).collect();
+        assert_eq!(fields, [("nickname", false), ("name", true)]);
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt::{self, Write};
+
+/// Characters used for one level of indentation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum IndentKind {
+    #[default]
+    Tabs,
+    Spaces,
+}
+
+/// Where the opening brace of a block goes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BraceStyle {
+    /// `class Foo {`
+    #[default]
+    SameLine,
+    /// `class Foo` followed by `{` on its own line
+    NextLine,
+}
+
+/// Formatting options shared by every generator. The default reproduces
+/// the historical output: tabs, braces on the same line, 120 columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeStyle {
+    pub indent_kind: IndentKind,
+    /// Number of spaces per level, also the column width of a tab.
+    pub indent_width: usize,
+    pub brace_style: BraceStyle,
+    pub max_line_length: usize,
+}
+
+impl Default for CodeStyle {
+    fn default() -> Self {
+        Self {
+            indent_kind: IndentKind::Tabs,
+            indent_width: 4,
+            brace_style: BraceStyle::SameLine,
+            max_line_length: 120,
+        }
+    }
+}
+
+impl CodeStyle {
+    /// The whitespace for `level` levels of indentation.
+    pub fn indent(&self, level: usize) -> String {
+        match self.indent_kind {
+            IndentKind::Tabs => "\t".repeat(level),
+            IndentKind::Spaces => " ".repeat(level * self.indent_width),
+        }
+    }
+
+    /// Display width of `line`, counting a tab as `indent_width` columns.
+    pub fn width(&self, line: &str) -> usize {
+        line.chars().map(|c| if c == '\t' { self.indent_width } else { 1 }).sum()
+    }
+}
+
+/// String buffer that generators write into. It implements `fmt::Write`, so
+/// `write!`/`writeln!` work as on a `String`, and adds the style-aware
+/// helpers for indentation, block braces and line length checks.
+///
+/// The writer also tracks the current nesting level: `begin_block`,
+/// `end_block` and `indented` move it, and `line` and `current_indent`
+/// follow it, so nested code does not have to count levels by hand.
+pub struct CodeWriter {
+    out: String,
+    style: CodeStyle,
+    level: usize,
+}
+
+impl CodeWriter {
+    pub fn new(style: CodeStyle) -> Self {
+        Self { out: String::new(), style, level: 0 }
+    }
+
+    /// The whitespace for `level` levels of indentation.
+    pub fn indent(&self, level: usize) -> String {
+        self.style.indent(level)
+    }
+
+    /// The whitespace for the current nesting level.
+    pub fn current_indent(&self) -> String {
+        self.style.indent(self.level)
+    }
+
+    /// Writes `text` as a line at the current nesting level. An empty text
+    /// gives a blank line without trailing whitespace.
+    pub fn line(&mut self, text: &str) -> fmt::Result {
+        if text.is_empty() {
+            return writeln!(self.out);
+        }
+        let indent = self.current_indent();
+        writeln!(self.out, "{}{}", indent, text)
+    }
+
+    /// Opens a block at the current level, like `open_block`, and nests
+    /// everything written until the matching `end_block`.
+    pub fn begin_block(&mut self, header: &str) -> fmt::Result {
+        self.open_block(self.level, header)?;
+        self.level += 1;
+        Ok(())
+    }
+
+    /// Leaves the innermost block and writes its closing line, e.g. `}` or `};`.
+    pub fn end_block(&mut self, closing: &str) -> fmt::Result {
+        self.level = self.level.saturating_sub(1);
+        self.line(closing)
+    }
+
+    /// Runs `f` one level deeper, for nesting without braces such as a
+    /// Python body or a continuation line.
+    pub fn indented<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.level += 1;
+        let result = f(self);
+        self.level -= 1;
+        result
+    }
+
+    /// Writes `header` followed by an opening brace, placed according to the
+    /// brace style, and ends the line.
+    pub fn open_block(&mut self, level: usize, header: &str) -> fmt::Result {
+        let indent = self.indent(level);
+        match self.style.brace_style {
+            BraceStyle::SameLine => writeln!(self.out, "{}{} {{", indent, header),
+            BraceStyle::NextLine => writeln!(self.out, "{}{}\n{}{{", indent, header, indent),
+        }
+    }
+
+    /// Whether `text` indented by `level` stays within the maximum line length.
+    pub fn fits(&self, level: usize, text: &str) -> bool {
+        self.style.width(&self.indent(level)) + self.style.width(text) <= self.style.max_line_length
+    }
+
+    /// Whether `text` at the current nesting level stays within the maximum
+    /// line length.
+    pub fn fits_line(&self, text: &str) -> bool {
+        self.fits(self.level, text)
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+impl fmt::Write for CodeWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.out.push_str(s);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_style_uses_tabs() {
+        let style = CodeStyle::default();
+        assert_eq!(style.indent(2), "\t\t");
+        assert_eq!(style.width("\tab"), 6);
+    }
+
+    #[test]
+    fn test_space_indentation() {
+        let style = CodeStyle { indent_kind: IndentKind::Spaces, indent_width: 2, ..Default::default() };
+        assert_eq!(style.indent(3), "      ");
+    }
+
+    #[test]
+    fn test_open_block_brace_styles() {
+        let mut same = CodeWriter::new(CodeStyle::default());
+        same.open_block(1, "class A").unwrap();
+        assert_eq!(same.finish(), "\tclass A {\n");
+
+        let style = CodeStyle { brace_style: BraceStyle::NextLine, ..Default::default() };
+        let mut next = CodeWriter::new(style);
+        next.open_block(1, "class A").unwrap();
+        assert_eq!(next.finish(), "\tclass A\n\t{\n");
+    }
+
+    #[test]
+    fn test_fits_counts_indentation() {
+        let style = CodeStyle { indent_kind: IndentKind::Spaces, max_line_length: 10, ..Default::default() };
+        let w = CodeWriter::new(style);
+        assert!(w.fits(0, "0123456789"));
+        assert!(!w.fits(1, "0123456789"));
+        assert!(w.fits(1, "012345"));
+    }
+
+    #[test]
+    fn test_automatic_nesting() {
+        let style = CodeStyle { indent_kind: IndentKind::Spaces, indent_width: 2, ..Default::default() };
+        let mut w = CodeWriter::new(style);
+        w.begin_block("class A").unwrap();
+        w.line("int x;").unwrap();
+        w.line("").unwrap();
+        w.begin_block("void f()").unwrap();
+        w.indented(|w| w.line("// deeper")).unwrap();
+        w.end_block("}").unwrap();
+        w.end_block("};").unwrap();
+        assert_eq!(w.finish(), "class A {\n  int x;\n\n  void f() {\n      // deeper\n  }\n};\n");
+    }
+
+    #[test]
+    fn test_writer_accepts_write_macros() {
+        let mut w = CodeWriter::new(CodeStyle::default());
+        writeln!(w, "{}x = {}", w.indent(1), 1).unwrap();
+        assert_eq!(w.finish(), "\tx = 1\n");
+    }
+}
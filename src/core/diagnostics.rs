@@ -0,0 +1,309 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::core::errors::SourceError;
+
+/// How diagnostics are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum MessageFormat {
+    /// `error: models/car.oml:12: message` on stderr
+    #[default]
+    Human,
+    /// GitHub Actions workflow commands (`::error file=...,line=...::message`)
+    /// on stdout, shown inline on pull requests
+    Github,
+}
+
+/// Whether diagnostics printed in the human format are colored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// When stderr is a terminal and NO_COLOR is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A message about the input, optionally pointing at a file and line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+static MESSAGE_FORMAT: OnceLock<MessageFormat> = OnceLock::new();
+static COLOR: OnceLock<bool> = OnceLock::new();
+static ERROR_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Selects the output format for every later `emit` call. Only the first
+/// call has an effect.
+pub fn set_message_format(format: MessageFormat) {
+    let _ = MESSAGE_FORMAT.set(format);
+}
+
+/// Selects whether later `emit` calls color their output. Only the first
+/// call has an effect.
+pub fn set_color(choice: ColorChoice) {
+    let _ = COLOR.set(choice.enabled());
+}
+
+/// Prints a diagnostic in the selected message format; in the human one
+/// with the source line it points at, underlined.
+pub fn emit(diagnostic: &Diagnostic) {
+    if diagnostic.severity == Severity::Error {
+        ERROR_COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+    let format = MESSAGE_FORMAT.get().copied().unwrap_or_default();
+    match format {
+        MessageFormat::Human => {
+            let color = COLOR.get().copied().unwrap_or(false);
+            eprint!("{}", diagnostic.render_terminal(diagnostic.source_line().as_deref(), color));
+        }
+        MessageFormat::Github => println!("{}", diagnostic.render(format)),
+    }
+}
+
+/// How many errors `emit` has printed so far.
+pub fn error_count() -> usize {
+    ERROR_COUNT.load(Ordering::Relaxed)
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { severity: Severity::Error, file: None, line: None, message: message.into() }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self { severity: Severity::Warning, file: None, line: None, message: message.into() }
+    }
+
+    /// Builds an error diagnostic from any error. A wrapped `Diagnostic` is
+    /// returned as is and a `SourceError` contributes its line number.
+    pub fn from_error(error: &(dyn Error + 'static)) -> Self {
+        if let Some(diagnostic) = error.downcast_ref::<Diagnostic>() {
+            return diagnostic.clone();
+        }
+        if let Some(source_error) = error.downcast_ref::<SourceError>() {
+            return Self::error(source_error.message.clone()).with_line(source_error.line);
+        }
+        Self::error(error.to_string())
+    }
+
+    pub fn with_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.file = Some(file.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets the line; 0 means "unknown" and is ignored.
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = (line > 0).then_some(line);
+        self
+    }
+
+    fn display_file(&self) -> Option<String> {
+        self.file.as_deref().map(display_path)
+    }
+
+    pub fn render(&self, format: MessageFormat) -> String {
+        match format {
+            MessageFormat::Human => self.render_human(),
+            MessageFormat::Github => self.render_github(),
+        }
+    }
+
+    fn render_human(&self) -> String {
+        let label = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match (self.display_file(), self.line) {
+            (Some(file), Some(line)) => format!("{}: {}:{}: {}", label, file, line, self.message),
+            (Some(file), None) => format!("{}: {}: {}", label, file, self.message),
+            _ => format!("{}: {}", label, self.message),
+        }
+    }
+
+    /// The human format with `source`, the text of the line pointed at,
+    /// underlined below it, and ANSI colors when `color` is set.
+    pub fn render_terminal(&self, source: Option<&str>, color: bool) -> String {
+        let paint = |code: &str, text: &str| if color { format!("\x1b[{}m{}\x1b[0m", code, text) } else { text.to_string() };
+        let (label, accent) = match self.severity {
+            Severity::Error => ("error", "1;31"),
+            Severity::Warning => ("warning", "1;33"),
+        };
+        let location = match (self.display_file(), self.line) {
+            (Some(file), Some(line)) => format!("{}:{}: ", file, line),
+            (Some(file), None) => format!("{}: ", file),
+            _ => String::new(),
+        };
+        let mut out = format!("{}{} {}{}\n", paint(accent, label), paint("1", ":"), location, paint("1", &self.message));
+
+        if let (Some(line), Some(source)) = (self.line, source.filter(|s| !s.trim().is_empty())) {
+            let number = line.to_string();
+            let gutter = " ".repeat(number.len());
+            let source = source.trim_end();
+            let indent = source.len() - source.trim_start().len();
+            let carets = "^".repeat(source.trim_start().chars().count());
+            let bar = paint("1;34", "|");
+            out.push_str(&format!("{} {}\n", gutter, bar));
+            out.push_str(&format!("{} {} {}\n", paint("1;34", &number), bar, source));
+            out.push_str(&format!("{} {} {}{}\n", gutter, bar, &source[..indent], paint(accent, &carets)));
+        }
+        out
+    }
+
+    /// The text of the line the diagnostic points at, if its file can be read.
+    fn source_line(&self) -> Option<String> {
+        let line = self.line?;
+        let content = fs::read_to_string(self.file.as_ref()?).ok()?;
+        content.lines().nth(line - 1).map(str::to_string)
+    }
+
+    fn render_github(&self) -> String {
+        let command = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let mut properties = Vec::new();
+        if let Some(file) = self.display_file() {
+            properties.push(format!("file={}", escape_property(&file)));
+        }
+        if let Some(line) = self.line {
+            properties.push(format!("line={}", line));
+        }
+
+        if properties.is_empty() {
+            format!("::{}::{}", command, escape_data(&self.message))
+        } else {
+            format!("::{} {}::{}", command, properties.join(","), escape_data(&self.message))
+        }
+    }
+}
+
+/// A file path as shown to the user: relative to the working directory
+/// when possible, since GitHub resolves annotation paths from the
+/// repository root.
+pub fn display_path(file: &Path) -> String {
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| file.strip_prefix(&cwd).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| file.to_path_buf());
+    let shown = relative.display().to_string();
+    shown.strip_prefix("./").map(str::to_string).unwrap_or(shown)
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render_human())
+    }
+}
+
+impl Error for Diagnostic {}
+
+/// Escapes a workflow command message.
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a workflow command property value.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_format_with_file_and_line() {
+        let diagnostic = Diagnostic::error("Unexpected token: foo").with_file("models/car.oml").with_line(12);
+        assert_eq!(
+            diagnostic.render(MessageFormat::Github),
+            "::error file=models/car.oml,line=12::Unexpected token: foo"
+        );
+    }
+
+    #[test]
+    fn test_github_format_escapes_special_characters() {
+        let diagnostic = Diagnostic::warning("50% done\nnext").with_file("a,b:c.oml");
+        assert_eq!(
+            diagnostic.render(MessageFormat::Github),
+            "::warning file=a%2Cb%3Ac.oml::50%25 done%0Anext"
+        );
+    }
+
+    #[test]
+    fn test_github_format_without_location() {
+        assert_eq!(Diagnostic::error("No .oml files found").render(MessageFormat::Github), "::error::No .oml files found");
+    }
+
+    #[test]
+    fn test_human_format() {
+        let diagnostic = Diagnostic::error("bad").with_file("./models/car.oml").with_line(3);
+        assert_eq!(diagnostic.render(MessageFormat::Human), "error: models/car.oml:3: bad");
+        assert_eq!(Diagnostic::warning("careful").render(MessageFormat::Human), "warning: careful");
+    }
+
+    #[test]
+    fn test_terminal_format_underlines_the_source_line() {
+        let diagnostic = Diagnostic::error("No variable name specified").with_file("models/car.oml").with_line(12);
+        assert_eq!(
+            diagnostic.render_terminal(Some("\tint32;  "), false),
+            "error: models/car.oml:12: No variable name specified\n   |\n12 | \tint32;\n   | \t^^^^^^\n"
+        );
+        assert_eq!(
+            Diagnostic::warning("careful").render_terminal(None, true),
+            "\x1b[1;33mwarning\x1b[0m\x1b[1m:\x1b[0m \x1b[1mcareful\x1b[0m\n"
+        );
+        assert_eq!(diagnostic.render_terminal(None, false), format!("{}\n", diagnostic.render(MessageFormat::Human)));
+    }
+
+    #[test]
+    fn test_from_error_picks_up_source_line() {
+        let error: Box<dyn Error> = SourceError::new(7, "No type specified").into();
+        let diagnostic = Diagnostic::from_error(error.as_ref());
+        assert_eq!(diagnostic.line, Some(7));
+        assert_eq!(diagnostic.message, "No type specified");
+
+        let error: Box<dyn Error> = "plain".into();
+        assert_eq!(Diagnostic::from_error(error.as_ref()).line, None);
+    }
+
+    #[test]
+    fn test_parse_errors_report_their_line() {
+        use crate::core::oml_object::OmlObject;
+
+        let content = "import \"other.oml\";\n\nclass Car {\n    string name;\n    int32;\n}\n";
+        let error = OmlObject::scan_file_with_imports(content.to_string()).unwrap_err();
+        assert_eq!(Diagnostic::from_error(error.as_ref()).line, Some(5));
+
+        let content = "class Car {\n    string name;\n    Wheel wheel;\n}\n";
+        let objects = OmlObject::scan_file(content.to_string()).unwrap();
+        let error = OmlObject::validate_custom_types(&objects, &Default::default()).unwrap_err();
+        assert_eq!(Diagnostic::from_error(error.as_ref()).line, Some(3));
+    }
+}
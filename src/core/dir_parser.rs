@@ -1,18 +1,83 @@
 use std::fs;
-use std::path::Path;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use crate::core::diagnostics::{self, Diagnostic, Severity};
 use crate::core::errors::ParseError;
 use crate::core::oml_object::{OmlFile, OmlObject};
+use crate::core::parallel::for_each_in_order;
 
+/// What walking the input finds, in directory order.
+enum Entry {
+    File(PathBuf),
+    /// An entry left out, with the warning saying why
+    Skipped(Diagnostic),
+}
+
+/// Parses the `.oml` files at or below `path`. Files are parsed in
+/// parallel; results and diagnostics are reported in directory order, with
+/// each directory's entries sorted by name, so every run prints the same.
 pub fn parse_path(
     path: &Path,
     max_depth: usize
 ) -> Result<Vec<OmlFile>, ParseError> {
+    let mut result = vec![];
+    let mut failed = false;
+    parse_path_each(path, max_depth, |parsed| match parsed {
+        Ok(file) => {
+            result.push(file);
+            ControlFlow::Continue(())
+        }
+        Err(diagnostic) => {
+            diagnostics::emit(&diagnostic);
+            // a file named on its own has to parse
+            failed = path.is_file();
+            if failed { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        }
+    })?;
+    if failed {
+        return Err(ParseError::InvalidPath);
+    }
+    Ok(result)
+}
+
+/// Parses the `.oml` files at or below `path` like `parse_path`, handing
+/// each to `each` as soon as it and the files before it are parsed, while
+/// the rest are still being parsed. A file that does not parse is handed on
+/// as the error saying why; `each` decides whether the walk goes on.
+pub fn parse_path_each(
+    path: &Path,
+    max_depth: usize,
+    mut each: impl FnMut(Result<OmlFile, Diagnostic>) -> ControlFlow<()>
+) -> Result<(), ParseError> {
+    let mut entries = Vec::new();
+    collect_entries(path, max_depth, &mut entries)?;
+
+    for_each_in_order(
+        entries.into_iter(),
+        |entry| match entry {
+            Entry::File(file_path) => parse_file(&file_path),
+            Entry::Skipped(diagnostic) => Err(diagnostic),
+        },
+        |parsed| match parsed {
+            Err(diagnostic) if diagnostic.severity == Severity::Warning => {
+                diagnostics::emit(&diagnostic);
+                ControlFlow::Continue(())
+            }
+            parsed => each(parsed),
+        },
+    );
+    Ok(())
+}
+
+fn collect_entries(
+    path: &Path,
+    max_depth: usize,
+    entries: &mut Vec<Entry>
+) -> Result<(), ParseError> {
     if max_depth == 0 {
         return Err(ParseError::MaxDepthExceeded);
     }
 
-    let mut result = vec![];
-
     let metadata = fs::symlink_metadata(path)?;
 
     if metadata.file_type().is_symlink() {
@@ -20,79 +85,62 @@ pub fn parse_path(
     }
 
     if path.is_file() {
-        if let Some(extension) = path.extension() {
-            if extension.to_string_lossy() != "oml" {
-                return Err(ParseError::InvalidPath);
-            }
-        } else {
+        if path.extension().is_none_or(|extension| extension.to_string_lossy() != "oml") {
             return Err(ParseError::InvalidPath);
         }
-
-        let file_name = path.file_stem()
-            .ok_or(ParseError::InvalidPath)?
-            .to_string_lossy()
-            .to_string();
-
-        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-        return match OmlObject::get_from_file(path) {
-            Ok((objects, imports)) => {
-                result.push(OmlFile { file_name, path: canonical, objects, imports });
-                Ok(result)
-            },
-            Err(_) => {
-                eprintln!("could not obtain oml objects from file {}", path.display());
-                Err(ParseError::InvalidPath)
-            }
-        }
+        entries.push(Entry::File(path.to_path_buf()));
+        return Ok(());
     }
 
     if !path.is_dir() {
         return Err(ParseError::InvalidPath);
     }
 
-    let dir = fs::read_dir(path)?;
+    let mut dir_entries: Vec<PathBuf> = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    dir_entries.sort();
 
-    for entry in dir {
-        let entry = entry?;
-        let entry_path = entry.path();
+    for entry_path in dir_entries {
         let metadata = fs::symlink_metadata(&entry_path)?;
 
         if metadata.file_type().is_symlink() {
-            eprintln!("Warning: Skipping symlink: {}", entry_path.display());
+            entries.push(Entry::Skipped(Diagnostic::warning("Skipping symlink").with_file(&entry_path)));
             continue;
         }
 
         if entry_path.is_file() {
             if let Some(extension) = entry_path.extension() {
                 if extension.to_string_lossy() != "oml" {
-                    eprintln!("Warning: Skipping non-oml file: {}", entry_path.display());
+                    entries.push(Entry::Skipped(Diagnostic::warning("Skipping non-oml file").with_file(&entry_path)));
                     continue;
                 }
             } else {
                 continue;
             }
 
-            let file_name = entry_path.file_stem()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_default();
-
-            let canonical = entry_path.canonicalize().unwrap_or_else(|_| entry_path.clone());
-            match OmlObject::get_from_file(&entry_path) {
-                Ok((objects, imports)) => result.push(OmlFile { file_name, path: canonical, objects, imports }),
-                Err(e) => {
-                    eprintln!("Warning: Failed to parse {}: {}", entry_path.display(), e);
-                }
-            }
+            entries.push(Entry::File(entry_path));
             continue;
         }
 
         if entry_path.is_dir() {
-            let mut sub_files = parse_path(&entry_path, max_depth - 1)?;
-            result.append(&mut sub_files);
+            collect_entries(&entry_path, max_depth - 1, entries)?;
         }
     }
 
-    Ok(result)
+    Ok(())
+}
+
+fn parse_file(path: &Path) -> Result<OmlFile, Diagnostic> {
+    let file_name = path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    match OmlObject::get_from_file(path) {
+        Ok((objects, imports)) => Ok(OmlFile { file_name, path: canonical, objects, imports }),
+        Err(e) => Err(Diagnostic::from_error(e.as_ref()).with_file(path)),
+    }
 }
 
 pub fn parse_dir_from_string(
@@ -108,6 +156,44 @@ pub fn parse_dir_from_string(
     parse_path(path, max_depth)
 }
 
+/// `parse_path_each` for the path `path_str` names.
+pub fn parse_dir_each(
+    path_str: &str,
+    max_depth: usize,
+    each: impl FnMut(Result<OmlFile, Diagnostic>) -> ControlFlow<()>
+) -> Result<(), ParseError> {
+    let path = Path::new(path_str);
+
+    if !path.exists() {
+        return Err(ParseError::InvalidPath);
+    }
+
+    parse_path_each(path, max_depth, each)
+}
+
+/// The canonical paths of the `.oml` files `parse_dir_from_string` would
+/// parse, in the same order, without reading them.
+pub fn list_files(
+    path_str: &str,
+    max_depth: usize
+) -> Result<Vec<PathBuf>, ParseError> {
+    let path = Path::new(path_str);
+
+    if !path.exists() {
+        return Err(ParseError::InvalidPath);
+    }
+
+    let mut entries = Vec::new();
+    collect_entries(path, max_depth, &mut entries)?;
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Entry::File(file_path) => Some(file_path.canonicalize().unwrap_or(file_path)),
+            Entry::Skipped(_) => None,
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,4 +212,34 @@ mod tests {
     fn test_max_depth_prevents_overflow() {
         let _result = parse_dir_from_string("./".to_string(), 5);
     }
+
+    #[test]
+    fn test_files_come_back_in_name_order() {
+        let files = parse_dir_from_string("src/core/test/oml_files".to_string(), 10).unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.file_name.as_str()).collect();
+        assert_eq!(names, ["car", "cycle_a", "cycle_b", "engine", "hello"]);
+        assert_eq!(files[0].objects, OmlObject::get_from_file(Path::new("src/core/test/oml_files/car.oml")).unwrap().0);
+    }
+
+    #[test]
+    fn test_files_that_do_not_parse_are_handed_on() {
+        let dir = std::env::temp_dir().join(format!("oml_dir_parser_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.oml"), "class A {\n int32 x;\n}\n").unwrap();
+        fs::write(dir.join("b.oml"), "class B {\n int32 x = ;\n}\n").unwrap();
+        fs::write(dir.join("c.oml"), "class C {\n int32 x;\n}\n").unwrap();
+
+        let mut seen = Vec::new();
+        parse_path_each(&dir, 2, |parsed| {
+            seen.push(match parsed {
+                Ok(file) => file.file_name,
+                Err(diagnostic) => format!("error in {}", diagnostic.file.unwrap().file_stem().unwrap().to_string_lossy()),
+            });
+            ControlFlow::Continue(())
+        }).unwrap();
+        assert_eq!(seen, ["a", "error in b", "c"]);
+
+        assert!(parse_path(&dir.join("b.oml"), 2).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
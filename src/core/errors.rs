@@ -14,4 +14,37 @@ impl From<std::io::Error> for ParseError {
     fn from(_: std::io::Error) -> Self {
         ParseError::Io()
     }
-}
\ No newline at end of file
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Io() => write!(f, "could not be read"),
+            ParseError::MaxDepthExceeded => write!(f, "nests deeper than the maximum depth"),
+            ParseError::InvalidPath => write!(f, "is not an .oml file or a directory"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An error tied to a line (1-based) of an .oml source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl SourceError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self { line, message: message.into() }
+    }
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for SourceError {}
@@ -0,0 +1,174 @@
+use crate::core::generate::{CommentSyntax, GeneratedFile, Generate};
+use crate::core::generated_comment::rename_source;
+use crate::core::naming::NamingConvention;
+use crate::core::oml_object::OmlObject;
+use crate::core::utils::to_snake_case;
+use crate::core::verify::VerifyTarget;
+use std::error::Error;
+use std::str::FromStr;
+
+/// One `--file-name-template` argument, `[TARGET=]TEMPLATE`, e.g.
+/// `{name}.gen.{ext}` or `python={snake_name}_generated.{ext}`. `{name}` is
+/// the OML file name, `{snake_name}` the same in snake_case and `{ext}` the
+/// target's extension, which has to end the template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileNameTemplate {
+    /// The language flag the template is for, such as `cpp`; every target
+    /// without one of its own when not given
+    pub target: Option<String>,
+    pub template: String,
+}
+
+impl FileNameTemplate {
+    const PLACEHOLDERS: [&'static str; 3] = ["{name}", "{snake_name}", "{ext}"];
+
+    /// The file name, without extension, that the output for `name` is
+    /// written under.
+    pub fn stem(&self, name: &str) -> String {
+        self.template
+            .trim_end_matches(".{ext}")
+            .replace("{snake_name}", &to_snake_case(name))
+            .replace("{name}", name)
+    }
+}
+
+impl FromStr for FileNameTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, template) = match s.split_once('=') {
+            Some((target, template)) => (Some(target.trim().to_string()), template.trim()),
+            None => (None, s.trim()),
+        };
+        if !template.ends_with(".{ext}") {
+            return Err(format!("the template has to end with `.{{ext}}`, got `{}`", template));
+        }
+        let mut rest = template.to_string();
+        for placeholder in Self::PLACEHOLDERS {
+            rest = rest.replace(placeholder, "");
+        }
+        if rest.contains(['{', '}']) {
+            return Err(format!("unknown placeholder in `{}`, expected {}", template, Self::PLACEHOLDERS.join(", ")));
+        }
+        if !template.contains("{name}") && !template.contains("{snake_name}") {
+            return Err(format!("the template has to contain {{name}} or {{snake_name}}, got `{}`", template));
+        }
+
+        Ok(FileNameTemplate { target, template: template.to_string() })
+    }
+}
+
+/// A generator whose files named after their OML input follow a template.
+/// The generator is given the templated name, so includes, guards and
+/// imports refer to the renamed files; the generated comment still names
+/// the OML file.
+pub struct Templated {
+    pub generator: Box<dyn Generate>,
+    pub template: FileNameTemplate,
+}
+
+impl Templated {
+    fn with_source(&self, content: &str, stem: &str, name: &str) -> String {
+        rename_source(content, self.comment_syntax(), &format!("{}.oml", stem), &format!("{}.oml", name))
+    }
+}
+
+impl Generate for Templated {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let stem = self.template.stem(file_name);
+        let content = self.generator.generate(oml_objects, &stem)?;
+        Ok(self.with_source(&content, &stem, file_name))
+    }
+
+    fn extension(&self) -> &str {
+        self.generator.extension()
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        self.generator.comment_syntax()
+    }
+
+    fn naming(&self) -> NamingConvention {
+        self.generator.naming()
+    }
+
+    fn is_identifier(&self, name: &str) -> bool {
+        self.generator.is_identifier(name)
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        self.generator.keywords()
+    }
+
+    fn escape_keyword(&self, keyword: &str) -> String {
+        self.generator.escape_keyword(keyword)
+    }
+
+    fn verify_target(&self) -> Option<VerifyTarget> {
+        self.generator.verify_target()
+    }
+
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let stem = self.template.stem(file_name);
+        let files = self.generator.generate_files(oml_objects, &stem)?;
+        Ok(files
+            .into_iter()
+            .map(|file| GeneratedFile { content: self.with_source(&file.content, &stem, file_name), ..file })
+            .collect())
+    }
+
+    fn generate_package_files(&self, modules: &[(&str, &[OmlObject])]) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let stems: Vec<String> = modules.iter().map(|(name, _)| self.template.stem(name)).collect();
+        let templated: Vec<(&str, &[OmlObject])> = stems.iter().zip(modules).map(|(stem, (_, objects))| (stem.as_str(), *objects)).collect();
+        let files = self.generator.generate_package_files(&templated)?;
+
+        let sources: Vec<String> = modules.iter().map(|(name, _)| format!("{}.oml", name)).collect();
+        let generated: Vec<String> = stems.iter().map(|stem| format!("{}.oml", stem)).collect();
+        Ok(files
+            .into_iter()
+            .map(|file| {
+                let content = rename_source(&file.content, self.comment_syntax(), &generated.join(", "), &sources.join(", "));
+                GeneratedFile { content, ..file }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::python::oml_python::PythonGenerator;
+    use crate::core::oml_object::ObjectType;
+
+    #[test]
+    fn test_parse_and_stem() {
+        let template: FileNameTemplate = "{name}.gen.{ext}".parse().unwrap();
+        assert_eq!(template.target, None);
+        assert_eq!(template.stem("RaceCar"), "RaceCar.gen");
+
+        let template: FileNameTemplate = "python={snake_name}_generated.{ext}".parse().unwrap();
+        assert_eq!(template.target.as_deref(), Some("python"));
+        assert_eq!(template.stem("RaceCar"), "race_car_generated");
+
+        for input in ["{name}.gen", "{name}.{kind}.{ext}", "models.{ext}"] {
+            assert!(input.parse::<FileNameTemplate>().is_err(), "Should have failed: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_templated_files_keep_naming_their_source() {
+        let objects = [OmlObject { oml_type: ObjectType::CLASS, name: "Car".to_string(), ..Default::default() }];
+        let templated = Templated {
+            generator: Box::new(PythonGenerator::new(false).with_package_init(true)),
+            template: "{name}_generated.{ext}".parse().unwrap(),
+        };
+
+        let files = templated.generate_files(&objects, "car").unwrap();
+        assert_eq!(files[0].path, std::path::PathBuf::from("car_generated.py"));
+        assert!(files[0].content.starts_with("# This file has been generated from car.oml\n"), "Got: {}", files[0].content);
+
+        let files = templated.generate_package_files(&[("car", &objects)]).unwrap();
+        assert!(files[0].content.starts_with("# This file has been generated from car.oml\n"), "Got: {}", files[0].content);
+        assert!(files[0].content.contains("from .car_generated import Car\n"), "Got: {}", files[0].content);
+    }
+}
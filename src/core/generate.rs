@@ -1,9 +1,71 @@
 use std::error::Error;
-use crate::core::oml_object::OmlObject;
+use std::path::PathBuf;
+use crate::core::naming::NamingConvention;
+use crate::core::errors::SourceError;
+use crate::core::oml_object::{ObjectType, OmlObject, Variable, VariableVisibility};
+use crate::core::utils;
+use crate::core::verify::VerifyTarget;
+
+/// A single file produced by a generator, with a path relative to the
+/// output directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+impl GeneratedFile {
+    pub fn new(path: impl Into<PathBuf>, content: String) -> Self {
+        Self { path: path.into(), content }
+    }
+}
+
+/// How a target language writes comments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommentSyntax {
+    /// A prefix put before every line, such as `//` or `#`
+    Line(&'static str),
+    /// Opening and closing delimiters around the whole comment
+    Block(&'static str, &'static str),
+    /// The format has no comments
+    None,
+}
+
+/// Which fields get getters and setters in targets that generate them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum AccessorMode {
+    /// Private fields only
+    #[default]
+    Private,
+    /// No field; every field is a plain member
+    None,
+    /// Every field, including public ones
+    All,
+}
+
+impl AccessorMode {
+    /// Whether `var` is kept behind a getter and setter.
+    pub fn has_accessors(self, var: &Variable) -> bool {
+        match self {
+            AccessorMode::Private => var.visibility == VariableVisibility::PRIVATE,
+            AccessorMode::None => false,
+            AccessorMode::All => true,
+        }
+    }
+}
+
+/// A serialization format turned on in every target through its own idiom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SerdeFormat {
+    /// nlohmann::json for C++, kotlinx.serialization for Kotlin and
+    /// to_dict/from_dict for Python (pydantic models already have it)
+    Json,
+}
 
 /// Trait that should be used to convert OML to a programming language.
 /// This is a must as the OML CLI uses the functions from this trait.
-pub trait Generate {
+/// Generators are shared between the threads that generate each input.
+pub trait Generate: Sync {
     /// Generate the code in the respective language given the OML objects and file name.
     /// All objects from the same .oml file are passed together so they can be
     /// emitted into a single output file.
@@ -12,6 +74,116 @@ pub trait Generate {
 
     /// Gives the file extension so that it can be saved correctly.
     fn extension(&self) -> &str;
+
+    /// The comment syntax of the generated files, used to wrap banners.
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("//")
+    }
+
+    /// The target's idiomatic spelling of identifiers, used with
+    /// `--naming idiomatic`. Names are kept as written by default.
+    fn naming(&self) -> NamingConvention {
+        NamingConvention::default()
+    }
+
+    /// Whether `name` can be written as is for a type, field or enum value
+    /// in the output. By default it must be an identifier in every language
+    /// target; formats that quote their names accept any.
+    fn is_identifier(&self, name: &str) -> bool {
+        utils::is_identifier(name)
+    }
+
+    /// Whether type names must also start with an uppercase letter, as Ruby
+    /// classes and Haskell types must. Not by default.
+    fn capitalised_types(&self) -> bool {
+        false
+    }
+
+    /// Words reserved in the target. Fields named after one are renamed with
+    /// `escape_keyword` before generating. None by default.
+    fn keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// `keyword` made usable as a name, by default with a trailing `_`.
+    fn escape_keyword(&self, keyword: &str) -> String {
+        format!("{}_", keyword)
+    }
+
+    /// The `--verify` check that covers the generated files, if any.
+    fn verify_target(&self) -> Option<VerifyTarget> {
+        None
+    }
+
+    /// Generates every file that should be written for one .oml input.
+    /// By default this is the output of `generate` saved as `<file_name>.<extension>`;
+    /// generators that emit several files per input override it.
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let content = self.generate(oml_objects, file_name)?;
+        Ok(vec![GeneratedFile::new(format!("{}.{}", file_name, self.extension()), content)])
+    }
+
+    /// Generates files that cover every input at once, such as a package
+    /// index, after `generate_files` has run for each of them. `modules`
+    /// pairs each input's file name with its objects. Nothing by default.
+    fn generate_package_files(&self, _modules: &[(&str, &[OmlObject])]) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        Ok(Vec::new())
+    }
+}
+
+/// `objects` with every field named after a keyword of `generator` escaped,
+/// and a line in its doc comment giving the name it has in OML. Enum values
+/// are left alone, as generators spell them in their own case.
+pub fn escape_keywords(generator: &dyn Generate, objects: Vec<OmlObject>) -> Vec<OmlObject> {
+    let keywords = generator.keywords();
+    objects
+        .into_iter()
+        .map(|mut obj| {
+            if obj.oml_type == ObjectType::ENUM {
+                return obj;
+            }
+            for var in obj.variables.iter_mut().filter(|var| keywords.contains(&var.name.as_str())) {
+                let note = format!("(`{}` in OML, a keyword here)", var.name);
+                var.doc = Some(match var.doc.take() {
+                    Some(doc) => format!("{}\n{}", doc, note),
+                    None => note,
+                });
+                var.name = generator.escape_keyword(&var.name);
+            }
+            obj
+        })
+        .collect()
+}
+
+/// Checks that the names of `objects`, as `generator` is given them, are
+/// identifiers in its output, so that nothing is written that would not
+/// compile.
+pub fn validate_identifiers(generator: &dyn Generate, objects: &[OmlObject]) -> Result<(), Box<dyn Error>> {
+    let rule = "names must start with a letter or '_' and contain only letters, digits and '_'";
+    for obj in objects {
+        if !generator.is_identifier(&obj.name) {
+            let message = format!("'{}' is not a valid type name in the .{} output: {}", obj.name, generator.extension(), rule);
+            return Err(SourceError::new(0, message).into());
+        }
+        if generator.capitalised_types() && !obj.name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            let message = format!(
+                "'{}' is not a valid type name in the .{} output: type names must start with an uppercase letter",
+                obj.name, generator.extension()
+            );
+            return Err(SourceError::new(0, message).into());
+        }
+        let kind = if obj.oml_type == ObjectType::ENUM { "variant" } else { "field" };
+        for var in &obj.variables {
+            if !generator.is_identifier(&var.name) {
+                return Err(SourceError::new(var.line, format!(
+                    "'{}' is not a valid {} name of '{}' in the .{} output: {}",
+                    var.name, kind, obj.name, generator.extension(), rule
+                )).into());
+            }
+        }
+    }
+
+    Ok(())
 }
 
 /// Trait for converting generated code back into OML objects.
@@ -21,3 +193,82 @@ pub trait BackwardsGenerate {
     /// Parse the given source content back into a list of OML objects.
     fn reverse(&self, content: &str) -> Result<Vec<OmlObject>, Box<dyn Error>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::cpp::oml_cpp::CppGenerator;
+    use crate::generators::csharp::oml_csharp::CsharpGenerator;
+    use crate::generators::dart::oml_dart::DartGenerator;
+    use crate::generators::docs::oml_docs::{DocsFormat, DocsGenerator};
+    use crate::generators::fsharp::oml_fsharp::FsharpGenerator;
+    use crate::generators::kotlin::oml_kotlin::KotlinGenerator;
+    use crate::generators::lua::oml_lua::LuaGenerator;
+    use crate::generators::python::oml_python::PythonGenerator;
+    use crate::generators::ruby::oml_ruby::RubyGenerator;
+
+    #[test]
+    fn test_names_must_be_identifiers_in_the_output() {
+        let field = |name: &str, line| Variable { var_type: "int32".to_string(), name: name.to_string(), line, ..Default::default() };
+        let object = |name: &str, variables| OmlObject { oml_type: ObjectType::CLASS, name: name.to_string(), variables, ..Default::default() };
+        let cpp = CppGenerator::default();
+
+        assert!(validate_identifiers(&cpp, &[object("_Car2", vec![field("max_speed", 2)])]).is_ok());
+
+        let error = validate_identifiers(&cpp, &[object("My_Class-123", Vec::new())]).unwrap_err();
+        assert!(error.to_string().contains("'My_Class-123' is not a valid type name in the .h output"), "Got: {}", error);
+
+        let objects = [object("Car", vec![field("speed", 2), field("2fast", 3)])];
+        let error = validate_identifiers(&cpp, &objects).unwrap_err();
+        assert_eq!(error.downcast_ref::<SourceError>().map(|e| e.line), Some(3));
+        assert!(validate_identifiers(&DocsGenerator::new(DocsFormat::Markdown), &objects).is_ok());
+
+        let objects = [object("car", Vec::new())];
+        assert!(validate_identifiers(&cpp, &objects).is_ok());
+        let error = validate_identifiers(&RubyGenerator::default(), &objects).unwrap_err();
+        assert!(error.to_string().contains("'car' is not a valid type name in the .rb output: type names must start with an uppercase letter"), "Got: {}", error);
+    }
+
+    #[test]
+    fn test_fields_named_after_keywords_are_escaped() {
+        let field = |name: &str, doc: Option<&str>| Variable { name: name.to_string(), doc: doc.map(str::to_string), ..Default::default() };
+        let objects = vec![
+            OmlObject { oml_type: ObjectType::CLASS, name: "Thing".to_string(), variables: vec![field("class", Some("What it is")), field("lambda", None), field("object", None)], ..Default::default() },
+            OmlObject { oml_type: ObjectType::ENUM, name: "Kind".to_string(), variables: vec![field("class", None)], ..Default::default() },
+        ];
+
+        let python = escape_keywords(&PythonGenerator::new(false), objects.clone());
+        let names: Vec<&str> = python[0].variables.iter().map(|var| var.name.as_str()).collect();
+        assert_eq!(names, ["class_", "lambda_", "object"]);
+        assert_eq!(python[0].variables[0].doc.as_deref(), Some("What it is\n(`class` in OML, a keyword here)"));
+        assert_eq!(python[0].variables[1].doc.as_deref(), Some("(`lambda` in OML, a keyword here)"));
+        assert_eq!(python[1], objects[1]);
+
+        let kotlin = KotlinGenerator::new(false);
+        let escaped = escape_keywords(&kotlin, objects);
+        let names: Vec<&str> = escaped[0].variables.iter().map(|var| var.name.as_str()).collect();
+        assert_eq!(names, ["`class`", "lambda", "`object`"]);
+        assert!(validate_identifiers(&kotlin, &escaped).is_ok());
+    }
+
+    #[test]
+    fn test_keywords_are_escaped_the_way_each_target_allows() {
+        let objects = vec![OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Thing".to_string(),
+            variables: ["class", "type", "end"].map(|name| Variable { name: name.to_string(), ..Default::default() }).to_vec(),
+            ..Default::default()
+        }];
+        let names = |generator: &dyn Generate| {
+            let escaped = escape_keywords(generator, objects.clone());
+            assert!(validate_identifiers(generator, &escaped).is_ok());
+            escaped[0].variables.iter().map(|var| var.name.clone()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(&CsharpGenerator::default()), ["class", "type", "end"]);
+        assert_eq!(names(&CsharpGenerator::default().with_unity(true)), ["@class", "type", "end"]);
+        assert_eq!(names(&DartGenerator::default()), ["class$", "type", "end"]);
+        assert_eq!(names(&FsharpGenerator::default()), ["``class``", "``type``", "``end``"]);
+        assert_eq!(names(&LuaGenerator::default()), ["class", "type", "end_"]);
+    }
+}
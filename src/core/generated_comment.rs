@@ -0,0 +1,167 @@
+use std::env;
+use std::path::Path;
+
+use crate::core::generate::CommentSyntax;
+use crate::core::oml_object::OmlFile;
+use crate::core::utils::fnv1a;
+
+/// What the "This file has been generated from X.oml" comment at the top of
+/// every generated file says. The default only names the source, so the
+/// output is the same on every machine and with every oml version.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum GeneratedComment {
+    /// Only the source file
+    #[default]
+    Plain,
+    /// The source file and the oml version
+    Version,
+    /// The source file, a hash of its contents and the oml version
+    Hash,
+    /// No comment at all
+    None,
+}
+
+impl GeneratedComment {
+    /// Rewrites the generated comment in `content` for the given `source`
+    /// (`car.oml`, or several names joined by `, `) whose text is `source_text`.
+    /// Files whose format has no comments are returned unchanged.
+    pub fn apply(self, content: &str, syntax: CommentSyntax, source: &str, source_text: &[u8]) -> String {
+        let line = |text: &str| comment_line(syntax, text);
+        let plain = format!("This file has been generated from {}", source);
+        let version = env!("CARGO_PKG_VERSION");
+        let replacement = match self {
+            GeneratedComment::Plain => return content.to_string(),
+            GeneratedComment::Version => line(&format!("{} by oml {}", plain, version)),
+            GeneratedComment::Hash => line(&format!("{} (fnv1a {:016x}) by oml {}", plain, fnv1a(source_text), version)),
+            GeneratedComment::None => String::new(),
+        };
+
+        let plain = line(&plain);
+        let found = content.find(&plain).filter(|&i| i == 0 || content[..i].ends_with('\n'));
+        let (Some(start), false) = (found, plain.is_empty()) else {
+            return content.to_string();
+        };
+
+        let mut end = start + plain.len();
+        // Without the comment, the blank line that separated it from the code goes too
+        if self == GeneratedComment::None && content[end..].starts_with('\n') {
+            end += 1;
+        }
+        format!("{}{}{}", &content[..start], replacement, &content[end..])
+    }
+}
+
+/// How the generated comment names the OML files an output was made from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SourceNames {
+    /// The file name, e.g. `car.oml`
+    #[default]
+    File,
+    /// The path from the working directory, e.g. `models/fleet/car.oml`,
+    /// which tells apart files of the same name in different directories
+    Path,
+}
+
+impl SourceNames {
+    /// What the generated comment calls `file`.
+    pub fn name(self, file: &OmlFile) -> String {
+        match self {
+            SourceNames::File => format!("{}.oml", file.file_name),
+            SourceNames::Path => {
+                let cwd = env::current_dir().and_then(|dir| dir.canonicalize());
+                relative_path(&file.path, cwd.as_deref().ok())
+            }
+        }
+    }
+}
+
+/// `path` below `base` with `/` between its parts on every platform, or as
+/// it is when not below `base`.
+fn relative_path(path: &Path, base: Option<&Path>) -> String {
+    match base.and_then(|base| path.strip_prefix(base).ok()) {
+        Some(relative) => relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        None => path.display().to_string(),
+    }
+}
+
+/// `content` with the generated comment naming `source` instead of
+/// `generated_name`, for output whose name is not that of its source.
+pub fn rename_source(content: &str, syntax: CommentSyntax, generated_name: &str, source: &str) -> String {
+    if generated_name == source {
+        return content.to_string();
+    }
+    let comment = |name: &str| comment_line(syntax, &format!("This file has been generated from {}", name));
+    let generated = comment(generated_name);
+    if generated.is_empty() {
+        return content.to_string();
+    }
+    content.replacen(&generated, &comment(source), 1)
+}
+
+/// `text` as a whole-line comment, or nothing in formats without comments.
+fn comment_line(syntax: CommentSyntax, text: &str) -> String {
+    match syntax {
+        CommentSyntax::Line(prefix) => format!("{} {}\n", prefix, text),
+        CommentSyntax::Block(open, close) => format!("{} {} {}\n", open, text, close),
+        CommentSyntax::None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PYTHON: &str = "# This file has been generated from car.oml\n\nfrom enum import Enum\n";
+
+    #[test]
+    fn test_plain_leaves_the_comment_alone() {
+        assert_eq!(GeneratedComment::Plain.apply(PYTHON, CommentSyntax::Line("#"), "car.oml", b"enum Color {}"), PYTHON);
+    }
+
+    #[test]
+    fn test_version_and_hash() {
+        let version = env!("CARGO_PKG_VERSION");
+        assert_eq!(
+            GeneratedComment::Version.apply(PYTHON, CommentSyntax::Line("#"), "car.oml", b""),
+            format!("# This file has been generated from car.oml by oml {}\n\nfrom enum import Enum\n", version)
+        );
+        assert_eq!(
+            GeneratedComment::Hash.apply("(* This file has been generated from car.oml *)\n", CommentSyntax::Block("(*", "*)"), "car.oml", b"a"),
+            format!("(* This file has been generated from car.oml (fnv1a af63dc4c8601ec8c) by oml {} *)\n", version)
+        );
+    }
+
+    #[test]
+    fn test_none_removes_the_comment_and_its_blank_line() {
+        assert_eq!(GeneratedComment::None.apply(PYTHON, CommentSyntax::Line("#"), "car.oml", b""), "from enum import Enum\n");
+        assert_eq!(
+            GeneratedComment::None.apply("<?php\n// This file has been generated from car.oml\n\ndeclare(strict_types=1);\n", CommentSyntax::Line("//"), "car.oml", b""),
+            "<?php\ndeclare(strict_types=1);\n"
+        );
+        assert_eq!(GeneratedComment::None.apply("{}\n", CommentSyntax::None, "car.oml", b""), "{}\n");
+    }
+
+    #[test]
+    fn test_rename_source() {
+        assert_eq!(
+            rename_source("# This file has been generated from models.oml\n", CommentSyntax::Line("#"), "models.oml", "car.oml, engine.oml"),
+            "# This file has been generated from car.oml, engine.oml\n"
+        );
+        assert_eq!(rename_source(PYTHON, CommentSyntax::Line("#"), "car.oml", "car.oml"), PYTHON);
+    }
+
+    #[test]
+    fn test_source_paths_are_relative_with_forward_slashes() {
+        let base = Path::new("/work");
+        let path = Path::new("/work").join("models").join("fleet").join("car.oml");
+        assert_eq!(relative_path(&path, Some(base)), "models/fleet/car.oml");
+        assert_eq!(relative_path(Path::new("/elsewhere/car.oml"), Some(base)), "/elsewhere/car.oml");
+
+        let file = OmlFile { file_name: "car".to_string(), path, objects: Vec::new(), imports: Vec::new() };
+        assert_eq!(SourceNames::File.name(&file), "car.oml");
+    }
+}
@@ -0,0 +1,120 @@
+use crate::core::lexer::{TokenKind, tokenize};
+
+/// What `oml highlight` writes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum HighlightFormat {
+    /// A `<pre class="oml">` block whose tokens are `<span>`s with classes
+    /// such as `oml-keyword`, for a stylesheet to color
+    #[default]
+    Html,
+    /// ANSI escape codes, for a terminal
+    Ansi,
+}
+
+/// Stylesheet for the classes of the HTML format, put in the pages written
+/// with `--standalone`.
+pub const STYLESHEET: &str = "\
+pre.oml { background: #fafafa; padding: 1em; }
+.oml-keyword { color: #a626a4; font-weight: bold; }
+.oml-visibility, .oml-modifier { color: #a626a4; }
+.oml-builtin-type, .oml-type { color: #0184bc; }
+.oml-annotation { color: #c18401; }
+.oml-number { color: #986801; }
+.oml-string { color: #50a14f; }
+.oml-comment { color: #a0a1a7; font-style: italic; }
+.oml-doc-comment { color: #50a14f; font-style: italic; }
+.oml-invalid { color: #e45649; text-decoration: underline wavy; }
+";
+
+/// `source` highlighted in `format`.
+pub fn highlight(source: &str, format: HighlightFormat) -> String {
+    let tokens = tokenize(source);
+    match format {
+        HighlightFormat::Html => {
+            let mut out = String::from("<pre class=\"oml\"><code>");
+            for token in tokens {
+                let text = escape_html(token.text);
+                match html_class(token.kind) {
+                    Some(class) => out.push_str(&format!("<span class=\"oml-{}\">{}</span>", class, text)),
+                    None => out.push_str(&text),
+                }
+            }
+            out.push_str("</code></pre>\n");
+            out
+        }
+        HighlightFormat::Ansi => {
+            let mut out = String::new();
+            for token in tokens {
+                match ansi_code(token.kind) {
+                    Some(code) => out.push_str(&format!("\x1b[{}m{}\x1b[0m", code, token.text)),
+                    None => out.push_str(token.text),
+                }
+            }
+            out
+        }
+    }
+}
+
+/// A whole HTML page titled `title` showing `blocks` of highlighted HTML,
+/// with the stylesheet inline.
+pub fn html_page(title: &str, blocks: &[String]) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        STYLESHEET,
+        blocks.concat()
+    )
+}
+
+fn html_class(kind: TokenKind) -> Option<&'static str> {
+    match kind {
+        TokenKind::Keyword => Some("keyword"),
+        TokenKind::Visibility => Some("visibility"),
+        TokenKind::Modifier => Some("modifier"),
+        TokenKind::BuiltinType => Some("builtin-type"),
+        TokenKind::Type => Some("type"),
+        TokenKind::Annotation => Some("annotation"),
+        TokenKind::Number => Some("number"),
+        TokenKind::String => Some("string"),
+        TokenKind::Comment => Some("comment"),
+        TokenKind::DocComment => Some("doc-comment"),
+        TokenKind::Invalid => Some("invalid"),
+        TokenKind::Identifier | TokenKind::Punctuation | TokenKind::Whitespace => None,
+    }
+}
+
+fn ansi_code(kind: TokenKind) -> Option<&'static str> {
+    match kind {
+        TokenKind::Keyword => Some("1;35"),
+        TokenKind::Visibility | TokenKind::Modifier => Some("35"),
+        TokenKind::BuiltinType | TokenKind::Type => Some("36"),
+        TokenKind::Annotation | TokenKind::Number => Some("33"),
+        TokenKind::String | TokenKind::DocComment => Some("32"),
+        TokenKind::Comment => Some("90"),
+        TokenKind::Invalid => Some("4;31"),
+        TokenKind::Identifier | TokenKind::Punctuation | TokenKind::Whitespace => None,
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_and_ansi() {
+        let source = "class Car {\n    Engine engine; // <main>\n}\n";
+        assert_eq!(
+            highlight(source, HighlightFormat::Html),
+            "<pre class=\"oml\"><code><span class=\"oml-keyword\">class</span> <span class=\"oml-type\">Car</span> {\n    \
+             <span class=\"oml-type\">Engine</span> engine; <span class=\"oml-comment\">// &lt;main&gt;</span>\n}\n</code></pre>\n"
+        );
+        assert_eq!(
+            highlight("enum Color {\n", HighlightFormat::Ansi),
+            "\x1b[1;35menum\x1b[0m \x1b[36mColor\x1b[0m {\n"
+        );
+    }
+}
@@ -1,102 +1,127 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use crate::core::diagnostics::Diagnostic;
 use crate::core::oml_object::{OmlFile, OmlObject};
 
+/// All discovered files plus, per file path, the object names imported into it.
+pub type ResolvedImports = (Vec<OmlFile>, HashMap<PathBuf, HashSet<String>>);
+
 /// Resolves all transitive imports for the given root files.
 /// Returns all discovered files and a map from each file's path to the set of
 /// object names imported into it.  Errors on missing files or circular imports.
 pub fn resolve_all(
     root_files: Vec<OmlFile>,
-) -> Result<(Vec<OmlFile>, HashMap<PathBuf, HashSet<String>>), Box<dyn std::error::Error>> {
-    let mut all_files: HashMap<PathBuf, OmlFile> = HashMap::new();
-
+) -> Result<ResolvedImports, Box<dyn std::error::Error>> {
+    let mut resolver = ImportResolver::default();
     for f in root_files {
-        all_files.insert(f.path.clone(), f);
+        resolver.add(f)?;
     }
 
-    // BFS: discover and parse every imported file not yet seen.
-    let mut queue: Vec<PathBuf> = all_files.keys().cloned().collect();
-
-    while let Some(current) = queue.pop() {
-        let imports = all_files[&current].imports.clone();
-        let parent = current
-            .parent()
-            .unwrap_or_else(|| Path::new("."))
-            .to_path_buf();
-
-        for import_str in imports {
-            let raw_path = parent.join(&import_str);
-            let canonical = raw_path.canonicalize().map_err(|_| {
-                format!(
-                    "Import '{}' not found (imported in '{}')",
-                    import_str,
-                    current.display()
-                )
-            })?;
-
-            if all_files.contains_key(&canonical) {
-                continue;
-            }
+    let paths: Vec<PathBuf> = resolver.files.keys().cloned().collect();
+    let names = paths
+        .into_iter()
+        .map(|path| {
+            let imported = resolver.imported_names(&path);
+            (path, imported)
+        })
+        .collect();
+    Ok((resolver.into_files(), names))
+}
 
-            let (objects, sub_imports) = OmlObject::get_from_file(&raw_path).map_err(|e| {
-                format!(
-                    "Failed to parse imported file '{}': {}",
-                    raw_path.display(),
-                    e
-                )
-            })?;
-
-            let file_name = raw_path
-                .file_stem()
-                .ok_or_else(|| format!("Invalid import path '{}'", import_str))?
-                .to_string_lossy()
-                .to_string();
-
-            let oml_file = OmlFile {
-                file_name,
-                path: canonical.clone(),
-                objects,
-                imports: sub_imports,
-            };
-
-            all_files.insert(canonical.clone(), oml_file);
-            queue.push(canonical);
+/// Resolves imports one root file at a time, so that each file can be
+/// checked as soon as it and everything it imports are parsed, before the
+/// files after it are.
+#[derive(Debug, Default)]
+pub struct ImportResolver {
+    files: HashMap<PathBuf, OmlFile>,
+    adj: HashMap<PathBuf, Vec<PathBuf>>,
+    /// Cycle detection: 0 = unvisited, 1 = in stack, 2 = done.
+    state: HashMap<PathBuf, u8>,
+    names_cache: HashMap<PathBuf, HashSet<String>>,
+}
+
+impl ImportResolver {
+    /// Adds `file` and parses what it imports, directly or not, that is not
+    /// known yet. Returns the paths of the files that are new, `file`'s
+    /// first; none if `file` itself was known. Errors on missing files or
+    /// circular imports.
+    pub fn add(&mut self, file: OmlFile) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+        if self.files.contains_key(&file.path) {
+            return Ok(vec![]);
         }
-    }
+        let root = file.path.clone();
+        self.files.insert(root.clone(), file);
+        let mut added = vec![root.clone()];
+
+        // BFS: discover and parse every imported file not yet seen.
+        let mut queue = vec![root.clone()];
+        while let Some(current) = queue.pop() {
+            let imports = self.files[&current].imports.clone();
+            let parent = current
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .to_path_buf();
+
+            let mut deps = Vec::new();
+            for import_str in imports {
+                let raw_path = parent.join(&import_str);
+                let canonical = raw_path.canonicalize().map_err(|_| {
+                    Diagnostic::error(format!("Import '{}' not found", import_str)).with_file(&current)
+                })?;
+                deps.push(canonical.clone());
+
+                if self.files.contains_key(&canonical) {
+                    continue;
+                }
 
-    let mut adj: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
-    for (path, file) in &all_files {
-        let parent = path
-            .parent()
-            .unwrap_or_else(|| Path::new("."))
-            .to_path_buf();
+                let (objects, sub_imports) = OmlObject::get_from_file(&raw_path)
+                    .map_err(|e| Diagnostic::from_error(e.as_ref()).with_file(&canonical))?;
+
+                let file_name = raw_path
+                    .file_stem()
+                    .ok_or_else(|| format!("Invalid import path '{}'", import_str))?
+                    .to_string_lossy()
+                    .to_string();
+
+                let oml_file = OmlFile {
+                    file_name,
+                    path: canonical.clone(),
+                    objects,
+                    imports: sub_imports,
+                };
+
+                self.files.insert(canonical.clone(), oml_file);
+                added.push(canonical.clone());
+                queue.push(canonical);
+            }
+            self.adj.insert(current, deps);
+        }
 
-        let deps: Vec<PathBuf> = file
-            .imports
-            .iter()
-            .filter_map(|imp| parent.join(imp).canonicalize().ok())
-            .collect();
+        // files known before cannot import the new ones, so only paths
+        // from `file` can close a cycle
+        dfs_detect_cycle(&root, &self.adj, &mut self.state)?;
+        Ok(added)
+    }
 
-        adj.insert(path.clone(), deps);
+    /// The file added at `path`, itself or through an import.
+    pub fn file(&self, path: &Path) -> Option<&OmlFile> {
+        self.files.get(path)
     }
 
-    // Cycle detection: 0 = unvisited, 1 = in stack, 2 = done.
-    let mut state: HashMap<PathBuf, u8> = HashMap::new();
-    for path in all_files.keys().cloned().collect::<Vec<_>>() {
-        if state.get(&path).copied().unwrap_or(0) == 0 {
-            dfs_detect_cycle(&path, &adj, &mut state)?;
-        }
+    /// The names of the objects imported into the file at `path`, directly or not.
+    pub fn imported_names(&mut self, path: &Path) -> HashSet<String> {
+        collect_imported_names(&path.to_path_buf(), &self.adj, &self.files, &mut self.names_cache)
     }
 
-    let mut names_cache: HashMap<PathBuf, HashSet<String>> = HashMap::new();
-    for path in all_files.keys().cloned().collect::<Vec<_>>() {
-        let names = collect_imported_names(&path, &adj, &all_files, &mut names_cache);
-        names_cache.insert(path, names);
+    /// Every file added, in no particular order.
+    pub fn files(&self) -> impl Iterator<Item = &OmlFile> {
+        self.files.values()
     }
 
-    let files: Vec<OmlFile> = all_files.into_values().collect();
-    Ok((files, names_cache))
+    pub fn into_files(self) -> Vec<OmlFile> {
+        self.files.into_values().collect()
+    }
 }
 
 /// DFS helper that errors on back-edges (cycles).
@@ -175,11 +200,20 @@ mod tests {
         let files = vec![empty_file("a", "/fake/a.oml"), empty_file("b", "/fake/b.oml")];
         let (all, names) = resolve_all(files).unwrap();
         assert_eq!(all.len(), 2);
-        for (_, set) in &names {
+        for set in names.values() {
             assert!(set.is_empty());
         }
     }
 
+    #[test]
+    fn test_resolver_adds_a_file_once() {
+        let mut resolver = ImportResolver::default();
+        let added = resolver.add(empty_file("a", "/fake/a.oml")).unwrap();
+        assert_eq!(added, vec![PathBuf::from("/fake/a.oml")]);
+        assert!(resolver.add(empty_file("a", "/fake/a.oml")).unwrap().is_empty());
+        assert_eq!(resolver.files().count(), 1);
+    }
+
     #[test]
     fn test_cycle_detection() {
         let a = PathBuf::from("/fake/a.oml");
@@ -0,0 +1,193 @@
+use crate::core::oml_object::OmlObject;
+
+/// What a piece of OML source is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// `class`, `struct`, `enum` and `import`
+    Keyword,
+    /// `public`, `private` and `protected`
+    Visibility,
+    /// `const`, `mut`, `static`, `optional` and `list`
+    Modifier,
+    /// A built-in type such as `int32`
+    BuiltinType,
+    /// A name used as a type: one declared by an object, or given to a field
+    Type,
+    /// Any other name, such as that of a field or variant
+    Identifier,
+    /// A word that is not a valid name, such as `_private` or `café`
+    Invalid,
+    /// An annotation such as `@tag` or `@external`
+    Annotation,
+    Number,
+    String,
+    Comment,
+    /// A `///` comment, which documents what follows
+    DocComment,
+    Punctuation,
+    Whitespace,
+}
+
+/// A piece of OML source, which the tokens of a text make up in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+}
+
+/// Splits `source` into tokens, including whitespace and comments, so that
+/// their texts joined give `source` back. Nothing is rejected: text the
+/// parser would not accept is still split up as well as it can be.
+pub fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        let (kind, len) = if c.is_whitespace() {
+            (TokenKind::Whitespace, rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len()))
+        } else if rest.starts_with("///") && !rest.starts_with("////") {
+            (TokenKind::DocComment, rest.find('\n').unwrap_or(rest.len()))
+        } else if rest.starts_with("//") {
+            (TokenKind::Comment, rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            (TokenKind::Comment, body.find("*/").map_or(rest.len(), |end| end + 4))
+        } else if c == '"' || c == '\'' {
+            // to the closing quote, or the end of the line without one
+            let len = match closing_quote(&rest[1..], c) {
+                Some(end) if rest[1 + end..].starts_with(c) => end + 2,
+                Some(end) => end + 1,
+                None => rest.len(),
+            };
+            (TokenKind::String, len)
+        } else if c == '@' {
+            (TokenKind::Annotation, 1 + word_len(&rest[1..]))
+        } else if c.is_ascii_digit() {
+            (TokenKind::Number, rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len()))
+        } else if c.is_alphanumeric() || c == '_' {
+            let len = word_len(rest);
+            (word_kind(&rest[..len]), len)
+        } else {
+            (TokenKind::Punctuation, c.len_utf8())
+        };
+        tokens.push(Token { kind, text: &rest[..len] });
+        rest = &rest[len..];
+    }
+
+    mark_types(&mut tokens);
+    tokens
+}
+
+/// The offset in `text` of the `quote` ending a literal, past escaped
+/// quotes, or of the newline cutting it short.
+fn closing_quote(text: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    text.char_indices()
+        .find(|&(_, c)| {
+            let end = !escaped && (c == quote || c == '\n');
+            escaped = !escaped && c == '\\';
+            end
+        })
+        .map(|(index, _)| index)
+}
+
+fn word_len(text: &str) -> usize {
+    text.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(text.len())
+}
+
+fn word_kind(word: &str) -> TokenKind {
+    match word {
+        "class" | "struct" | "enum" | "import" => TokenKind::Keyword,
+        "public" | "private" | "protected" => TokenKind::Visibility,
+        "const" | "mut" | "static" | "optional" | "list" => TokenKind::Modifier,
+        _ if OmlObject::is_builtin_type(word) => TokenKind::BuiltinType,
+        _ if is_name(word) => TokenKind::Identifier,
+        _ => TokenKind::Invalid,
+    }
+}
+
+/// Whether `text` is a valid name of an object, field or type: an ASCII
+/// letter followed by ASCII letters, digits, `_`, `.` or `-`. The parser
+/// checks every name with it, so it is kept to a single pass.
+pub fn is_name(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Marks as types the names declared after `class`, `struct` and `enum`,
+/// and those followed by another name or an array size, as in `Engine
+/// engine;` or `Wheel[4] wheels;`.
+fn mark_types(tokens: &mut [Token]) {
+    let significant: Vec<usize> = (0..tokens.len())
+        .filter(|&i| !matches!(tokens[i].kind, TokenKind::Whitespace | TokenKind::Comment | TokenKind::DocComment))
+        .collect();
+    for (n, &i) in significant.iter().enumerate() {
+        if tokens[i].kind != TokenKind::Identifier {
+            continue;
+        }
+        let previous = n.checked_sub(1).map(|p| tokens[significant[p]]);
+        let next = significant.get(n + 1).map(|&j| tokens[j]);
+        let declared = previous.is_some_and(|t| t.kind == TokenKind::Keyword && t.text != "import");
+        let typed = next.is_some_and(|t| t.kind == TokenKind::Identifier || t.text == "[");
+        if declared || typed {
+            tokens[i].kind = TokenKind::Type;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_make_up_the_source() {
+        let source = "import \"engine.oml\";\n\n/// A car\nclass Car {\n    @tag(2) public optional string name; // shown\n    Wheel[4] wheels;\n    /* spare */ list Wheel spares;\n}\n";
+        let tokens = tokenize(source);
+        assert_eq!(tokens.iter().map(|t| t.text).collect::<String>(), source);
+
+        let significant: Vec<(TokenKind, &str)> = tokens
+            .iter()
+            .filter(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Punctuation))
+            .map(|t| (t.kind, t.text))
+            .collect();
+        assert_eq!(significant, vec![
+            (TokenKind::Keyword, "import"), (TokenKind::String, "\"engine.oml\""),
+            (TokenKind::DocComment, "/// A car"),
+            (TokenKind::Keyword, "class"), (TokenKind::Type, "Car"),
+            (TokenKind::Annotation, "@tag"), (TokenKind::Number, "2"), (TokenKind::Visibility, "public"), (TokenKind::Modifier, "optional"), (TokenKind::BuiltinType, "string"),
+            (TokenKind::Identifier, "name"), (TokenKind::Comment, "// shown"),
+            (TokenKind::Type, "Wheel"), (TokenKind::Number, "4"), (TokenKind::Identifier, "wheels"),
+            (TokenKind::Comment, "/* spare */"), (TokenKind::Modifier, "list"), (TokenKind::Type, "Wheel"), (TokenKind::Identifier, "spares"),
+        ]);
+
+        // unterminated pieces run to the end of the text
+        let tokens = tokenize("/* open\n\"text");
+        assert_eq!(tokens, vec![Token { kind: TokenKind::Comment, text: "/* open\n\"text" }]);
+        let tokens = tokenize("\"text\nclass");
+        assert_eq!(tokens[0], Token { kind: TokenKind::String, text: "\"text" });
+
+        // values, with quotes escaped inside them
+        let values: Vec<&str> = tokenize("double r = 0.5; string s = \"a\\\"b\"; char c = '\\'';")
+            .into_iter()
+            .filter(|t| matches!(t.kind, TokenKind::Number | TokenKind::String))
+            .map(|t| t.text)
+            .collect();
+        assert_eq!(values, vec!["0.5", "\"a\\\"b\"", "'\\''"]);
+    }
+
+    #[test]
+    fn test_names() {
+        for name in ["myfile.txt", "variable_name", "Config", "test123", "my-file-name", "file.tar.gz", "a", "MyClass"] {
+            assert!(is_name(name), "{}", name);
+        }
+        for name in ["123file", "_private", "-file", "my file", "file@name", "my$var", "file/path", "", "café"] {
+            assert!(!is_name(name), "{}", name);
+        }
+
+        let kinds: Vec<(TokenKind, &str)> = tokenize("int32 _count; string café;")
+            .into_iter()
+            .filter(|t| matches!(t.kind, TokenKind::Identifier | TokenKind::Invalid))
+            .map(|t| (t.kind, t.text))
+            .collect();
+        assert_eq!(kinds, vec![(TokenKind::Invalid, "_count"), (TokenKind::Invalid, "café")]);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::core::oml_object::{OmlObject, ObjectType, Variable, VariableModifier, VariableVisibility};
+
+/// The order every target declares an object's fields in, and so the order
+/// of constructor parameters, accessors and the other members generated per
+/// field. Enum values always keep their declared order, since it gives them
+/// their numbers, and targets that default optional constructor parameters
+/// still take those last.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum MemberOrder {
+    /// As written in OML
+    #[default]
+    Declared,
+    /// Static fields first, then public, protected and private ones, each
+    /// group with its required fields before its optional ones and
+    /// otherwise as written
+    Grouped,
+    /// The groups of `grouped`, sorted by name within each group
+    Alphabetical,
+}
+
+impl MemberOrder {
+    /// Copies of `objects` with their fields in this order.
+    pub fn apply(self, objects: &[OmlObject]) -> Vec<OmlObject> {
+        let mut objects = objects.to_vec();
+        if self == MemberOrder::Declared {
+            return objects;
+        }
+        for obj in objects.iter_mut().filter(|o| o.oml_type != ObjectType::ENUM) {
+            // stable, so ties keep their declaration order
+            match self {
+                MemberOrder::Declared => {}
+                MemberOrder::Grouped => obj.variables.sort_by_key(group),
+                MemberOrder::Alphabetical => obj.variables.sort_by(|a, b| group(a).cmp(&group(b)).then_with(|| a.name.cmp(&b.name))),
+            }
+        }
+        objects
+    }
+}
+
+/// Statics, then visibility, then required before optional.
+fn group(var: &Variable) -> (bool, u8, bool) {
+    let visibility = match var.visibility {
+        VariableVisibility::PUBLIC => 0,
+        VariableVisibility::PROTECTED => 1,
+        VariableVisibility::PRIVATE => 2,
+    };
+    (
+        !var.var_mod.contains(&VariableModifier::STATIC),
+        visibility,
+        var.var_mod.contains(&VariableModifier::OPTIONAL),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(objects: &[OmlObject]) -> Vec<&str> {
+        objects[0].variables.iter().map(|v| v.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_orders() {
+        let var = |name: &str, visibility: VariableVisibility, var_mod: Vec<VariableModifier>| Variable {
+            name: name.to_string(),
+            visibility,
+            var_mod,
+            ..Default::default()
+        };
+        let objects = [OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("secret", VariableVisibility::PRIVATE, vec![]),
+                var("nickname", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL]),
+                var("name", VariableVisibility::PUBLIC, vec![]),
+                var("count", VariableVisibility::PRIVATE, vec![VariableModifier::STATIC]),
+                var("brand", VariableVisibility::PUBLIC, vec![]),
+            ],
+            ..Default::default()
+        }];
+
+        assert_eq!(names(&MemberOrder::Declared.apply(&objects)), ["secret", "nickname", "name", "count", "brand"]);
+        assert_eq!(names(&MemberOrder::Grouped.apply(&objects)), ["count", "name", "brand", "nickname", "secret"]);
+        assert_eq!(names(&MemberOrder::Alphabetical.apply(&objects)), ["count", "brand", "name", "nickname", "secret"]);
+    }
+
+    #[test]
+    fn test_enum_values_keep_their_order() {
+        let objects = [OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: ["Red", "Blue"].iter().map(|n| Variable { name: n.to_string(), ..Default::default() }).collect(),
+            ..Default::default()
+        }];
+        assert_eq!(names(&MemberOrder::Alphabetical.apply(&objects)), ["Red", "Blue"]);
+    }
+}
@@ -3,8 +3,26 @@ pub mod import_resolver;
 pub mod oml_object;
 pub mod errors;
 pub mod generate;
+pub mod code_writer;
+pub mod diagnostics;
 pub mod backwards_converting;
+pub mod schema_diff;
 pub mod utils;
+pub mod banner;
+pub mod generated_comment;
+pub mod naming;
+pub mod member_order;
+pub mod builder;
+pub mod type_map;
+pub mod file_name_template;
+pub mod schema_hash;
+pub mod build_cache;
+pub mod output_file;
+pub mod modifiers;
+pub mod verify;
+pub mod lexer;
+pub mod highlight;
+pub mod parallel;
 
 #[cfg(test)]
-mod test;
+pub(crate) mod test;
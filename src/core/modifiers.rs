@@ -0,0 +1,158 @@
+use crate::core::oml_object::{ObjectType, OmlObject, Variable, VariableModifier, VariableVisibility};
+
+/// Where a modifier may be written and what it cannot be combined with.
+pub struct ModifierRule {
+    pub modifier: VariableModifier,
+    /// Kinds of object whose members may carry it
+    pub kinds: &'static [ObjectType],
+    /// Visibilities of the members that may carry it
+    pub visibilities: &'static [VariableVisibility],
+    /// Modifiers it cannot be combined with
+    pub conflicts: &'static [VariableModifier],
+}
+
+const FIELDS: &[ObjectType] = &[ObjectType::CLASS, ObjectType::STRUCT];
+const ANY_VISIBILITY: &[VariableVisibility] = &[
+    VariableVisibility::PUBLIC,
+    VariableVisibility::PROTECTED,
+    VariableVisibility::PRIVATE,
+];
+
+/// Every modifier with the rules the validation pass enforces for it, so
+/// that generators can take any modifier they are given as legal. Enum
+/// variants are plain values and take none.
+pub static MODIFIER_RULES: [ModifierRule; 4] = [
+    ModifierRule {
+        modifier: VariableModifier::CONST,
+        kinds: FIELDS,
+        visibilities: ANY_VISIBILITY,
+        conflicts: &[VariableModifier::MUT],
+    },
+    ModifierRule {
+        modifier: VariableModifier::MUT,
+        kinds: FIELDS,
+        visibilities: ANY_VISIBILITY,
+        conflicts: &[VariableModifier::CONST],
+    },
+    ModifierRule {
+        modifier: VariableModifier::STATIC,
+        kinds: FIELDS,
+        visibilities: ANY_VISIBILITY,
+        conflicts: &[],
+    },
+    ModifierRule {
+        modifier: VariableModifier::OPTIONAL,
+        kinds: FIELDS,
+        visibilities: ANY_VISIBILITY,
+        conflicts: &[],
+    },
+];
+
+impl VariableModifier {
+    pub fn rule(&self) -> &'static ModifierRule {
+        MODIFIER_RULES
+            .iter()
+            .find(|rule| rule.modifier == *self)
+            .expect("every modifier has a row in MODIFIER_RULES")
+    }
+}
+
+/// The first two of `modifiers` that cannot be combined.
+pub fn find_conflict(modifiers: &[VariableModifier]) -> Option<(&VariableModifier, &VariableModifier)> {
+    modifiers.iter().enumerate().find_map(|(i, modifier)| {
+        modifiers[i + 1..]
+            .iter()
+            .find(|other| modifier.rule().conflicts.contains(other))
+            .map(|other| (modifier, other))
+    })
+}
+
+/// What is wrong with the modifiers of `var`, a member of `obj`, if
+/// anything.
+pub fn check(obj: &OmlObject, var: &Variable) -> Option<String> {
+    let kind = &obj.oml_type;
+    let member = format!("{} '{}' of '{}'", if *kind == ObjectType::ENUM { "Variant" } else { "Field" }, var.name, obj.name);
+    for (i, modifier) in var.var_mod.iter().enumerate() {
+        let rule = modifier.rule();
+        if var.var_mod[..i].contains(modifier) {
+            return Some(format!("{} is declared '{}' twice", member, modifier.keyword()));
+        }
+        if !rule.kinds.contains(kind) {
+            return Some(format!("{} cannot be '{}' in {}", member, modifier.keyword(), kind_name(kind)));
+        }
+        if !rule.visibilities.contains(&var.visibility) {
+            return Some(format!("{} cannot be both '{}' and '{}'", member, visibility_name(&var.visibility), modifier.keyword()));
+        }
+    }
+    find_conflict(&var.var_mod).map(|(first, second)| {
+        format!("{} cannot be both '{}' and '{}'", member, first.keyword(), second.keyword())
+    })
+}
+
+fn kind_name(kind: &ObjectType) -> &'static str {
+    match kind {
+        ObjectType::CLASS => "a class",
+        ObjectType::STRUCT => "a struct",
+        ObjectType::ENUM => "an enum",
+        ObjectType::UNDECIDED => "an object of no kind",
+    }
+}
+
+fn visibility_name(visibility: &VariableVisibility) -> &'static str {
+    match visibility {
+        VariableVisibility::PUBLIC => "public",
+        VariableVisibility::PROTECTED => "protected",
+        VariableVisibility::PRIVATE => "private",
+    }
+}
+
+impl Variable {
+    /// A read-only field. `mut` wins over `const` on objects built by hand,
+    /// which skip the validation keeping the two apart.
+    pub fn is_const(&self) -> bool {
+        self.var_mod.contains(&VariableModifier::CONST) && !self.var_mod.contains(&VariableModifier::MUT)
+    }
+
+    /// A field of the type rather than of each value.
+    pub fn is_static(&self) -> bool {
+        self.var_mod.contains(&VariableModifier::STATIC)
+    }
+
+    pub fn is_optional(&self) -> bool {
+        self.var_mod.contains(&VariableModifier::OPTIONAL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(var_mod: Vec<VariableModifier>) -> Variable {
+        Variable { name: "x".to_string(), var_mod, ..Default::default() }
+    }
+
+    fn object(oml_type: ObjectType) -> OmlObject {
+        OmlObject { oml_type, name: "T".to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn test_every_modifier_is_checked_against_its_row() {
+        for rule in &MODIFIER_RULES {
+            assert!(std::ptr::eq(rule.modifier.rule(), rule));
+            for conflict in rule.conflicts {
+                assert!(conflict.rule().conflicts.contains(&rule.modifier), "Conflicts go both ways");
+            }
+        }
+
+        let fine = member(vec![VariableModifier::STATIC, VariableModifier::CONST]);
+        assert_eq!(check(&object(ObjectType::CLASS), &fine), None);
+        assert!(fine.is_const() && fine.is_static() && !fine.is_optional());
+
+        let both = member(vec![VariableModifier::MUT, VariableModifier::STATIC, VariableModifier::CONST]);
+        assert_eq!(check(&object(ObjectType::STRUCT), &both).as_deref(), Some("Field 'x' of 'T' cannot be both 'mut' and 'const'"));
+        assert!(!both.is_const());
+
+        let variant = member(vec![VariableModifier::STATIC]);
+        assert_eq!(check(&object(ObjectType::ENUM), &variant).as_deref(), Some("Variant 'x' of 'T' cannot be 'static' in an enum"));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::core::oml_object::{OmlObject, ObjectType};
+use crate::core::utils::{to_camel_case, to_pascal_case, to_snake_case};
+
+/// Casing applied to one kind of identifier.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Case {
+    /// Keep the name as written in OML
+    #[default]
+    Preserve,
+    /// `maxSpeed`
+    Camel,
+    /// `MaxSpeed`
+    Pascal,
+    /// `max_speed`
+    Snake,
+    /// `MAX_SPEED`
+    ScreamingSnake,
+}
+
+impl Case {
+    pub fn apply(self, name: &str) -> String {
+        match self {
+            Case::Preserve => name.to_string(),
+            Case::Camel => to_camel_case(name),
+            Case::Pascal => to_pascal_case(name),
+            Case::Snake => to_snake_case(name),
+            Case::ScreamingSnake => to_snake_case(name).to_uppercase(),
+        }
+    }
+}
+
+/// Whether identifiers keep their OML spelling or follow each target's idiom.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum NamingStyle {
+    /// Keep every name as written in OML
+    #[default]
+    Preserve,
+    /// Follow each target's own style, e.g. PascalCase types and camelCase
+    /// fields in Java, snake_case fields in Rust
+    Idiomatic,
+}
+
+/// How a target spells each kind of identifier. Getters, setters and other
+/// derived names are built from the renamed fields, so they follow along.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NamingConvention {
+    /// Class, struct and enum names, and the types that refer to them
+    pub types: Case,
+    pub fields: Case,
+    /// Static fields that are const
+    pub constants: Case,
+    pub enum_values: Case,
+}
+
+impl NamingConvention {
+    /// Copies of `objects` with every identifier renamed.
+    pub fn apply(&self, objects: &[OmlObject]) -> Vec<OmlObject> {
+        if *self == NamingConvention::default() {
+            return objects.to_vec();
+        }
+
+        objects
+            .iter()
+            .map(|obj| {
+                let mut obj = obj.clone();
+                obj.name = self.types.apply(&obj.name);
+                for var in &mut obj.variables {
+                    let case = if obj.oml_type == ObjectType::ENUM {
+                        self.enum_values
+                    } else if var.is_static() && var.is_const() {
+                        self.constants
+                    } else {
+                        self.fields
+                    };
+                    var.name = case.apply(&var.name);
+                    if !OmlObject::is_builtin_type(&var.var_type) && !var.external {
+                        var.var_type = self.types.apply(&var.var_type);
+                    }
+                }
+                obj
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::oml_object::{Variable, VariableModifier};
+
+    fn var(name: &str, var_type: &str, var_mod: Vec<VariableModifier>) -> Variable {
+        Variable { name: name.to_string(), var_type: var_type.to_string(), var_mod, ..Default::default() }
+    }
+
+    #[test]
+    fn test_cases() {
+        assert_eq!(Case::Preserve.apply("max_Speed"), "max_Speed");
+        assert_eq!(Case::Camel.apply("max_speed"), "maxSpeed");
+        assert_eq!(Case::Pascal.apply("maxSpeed"), "MaxSpeed");
+        assert_eq!(Case::Snake.apply("MaxSpeed"), "max_speed");
+        assert_eq!(Case::ScreamingSnake.apply("maxSpeed"), "MAX_SPEED");
+    }
+
+    #[test]
+    fn test_convention_renames_by_kind() {
+        let objects = [
+            OmlObject {
+                oml_type: ObjectType::CLASS,
+                name: "race_car".to_string(),
+                variables: vec![
+                    var("max_speed", "int32", vec![]),
+                    var("limit", "int32", vec![VariableModifier::STATIC, VariableModifier::CONST]),
+                    var("count", "int32", vec![VariableModifier::STATIC]),
+                    var("paint", "car_color", vec![]),
+                ],
+                ..Default::default()
+            },
+            OmlObject {
+                oml_type: ObjectType::ENUM,
+                name: "car_color".to_string(),
+                variables: vec![var("darkRed", "string", vec![])],
+                ..Default::default()
+            },
+        ];
+        let convention = NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Camel,
+            constants: Case::ScreamingSnake,
+            enum_values: Case::ScreamingSnake,
+        };
+        let renamed = convention.apply(&objects);
+
+        assert_eq!(renamed[0].name, "RaceCar");
+        let names: Vec<&str> = renamed[0].variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["maxSpeed", "LIMIT", "count", "paint"]);
+        assert_eq!(renamed[0].variables[0].var_type, "int32");
+        assert_eq!(renamed[0].variables[3].var_type, "CarColor");
+        assert_eq!(renamed[1].name, "CarColor");
+        assert_eq!(renamed[1].variables[0].name, "DARK_RED");
+    }
+
+    #[test]
+    fn test_default_convention_changes_nothing() {
+        let objects = [OmlObject { oml_type: ObjectType::CLASS, name: "race_car".to_string(), ..Default::default() }];
+        assert_eq!(NamingConvention::default().apply(&objects), objects);
+    }
+}
@@ -1,20 +1,28 @@
 use std::cmp::PartialEq;
-use std::collections::HashSet;
-use std::fs;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use regex::Regex;
 
 use crate::core::errors;
+use crate::core::lexer;
+use crate::core::modifiers;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, clap::ValueEnum)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum ObjectType {
     ENUM,
     CLASS,
     STRUCT,
+    // Only produced by hand-built objects (e.g. in tests); generators reject it.
+    #[allow(dead_code)]
+    #[default]
+    #[value(skip)]
     UNDECIDED
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum VariableModifier {
     CONST,
     MUT,
@@ -22,38 +30,70 @@ pub enum VariableModifier {
     OPTIONAL,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl VariableModifier {
+    /// The modifier as written in OML.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            VariableModifier::CONST => "const",
+            VariableModifier::MUT => "mut",
+            VariableModifier::STATIC => "static",
+            VariableModifier::OPTIONAL => "optional",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum VariableVisibility {
+    #[default]
     PRIVATE,
     PUBLIC,
     PROTECTED
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum ArrayKind {
+    #[default]
     None,
     Static(u32),  // type[N] — N > 0 required
     Dynamic,       // list type
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Variable {
     pub var_mod: Vec<VariableModifier>,
     pub visibility: VariableVisibility,
     pub var_type: String,
     pub array_kind: ArrayKind,
     pub name: String,
+    /// Text of the `///` comment lines directly above the declaration.
+    pub doc: Option<String>,
+    /// 1-based source line of the declaration, 0 when not parsed from a file.
+    pub line: usize,
+    /// Wire field number from a `@tag(N)` annotation, used by schema
+    /// generators such as protobuf.
+    pub tag: Option<u32>,
+    /// Set by an `@external` annotation: the type is maintained outside OML
+    /// and passed through by name instead of being resolved.
+    pub external: bool,
+    /// The literal after `=` as written: a number, `true`/`false`, or a
+    /// quoted string or character; for an enum variant, its `int32` number.
+    pub value: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct OmlObject {
     pub oml_type: ObjectType,
     pub name: String,
-    pub variables: Vec<Variable>
+    pub variables: Vec<Variable>,
+    /// Text of the `///` comment lines directly above the object header.
+    pub doc: Option<String>,
+    /// 1-based source line of the object header, 0 when not parsed from a file.
+    pub line: usize,
 }
 
 /// Groups all OML objects parsed from a single file.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OmlFile {
     pub file_name: String,
     pub path: PathBuf,
@@ -61,6 +101,86 @@ pub struct OmlFile {
     pub imports: Vec<String>,
 }
 
+/// A field declaration as parsed, borrowing its type and name from the
+/// source line until it becomes a `Variable`.
+#[derive(Debug, PartialEq)]
+struct Declaration<'a> {
+    var_mod: Vec<VariableModifier>,
+    visibility: VariableVisibility,
+    var_type: &'a str,
+    array_kind: ArrayKind,
+    name: &'a str,
+    tag: Option<u32>,
+    external: bool,
+    value: Option<&'a str>,
+}
+
+impl Declaration<'_> {
+    fn into_variable(self, doc: Option<String>, line: usize) -> Variable {
+        Variable {
+            var_mod: self.var_mod,
+            visibility: self.visibility,
+            var_type: self.var_type.to_string(),
+            array_kind: self.array_kind,
+            name: self.name.to_string(),
+            doc,
+            line,
+            tag: self.tag,
+            external: self.external,
+            value: self.value.map(str::to_string),
+        }
+    }
+}
+
+/// Where an object is declared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectLocation {
+    pub name: String,
+    pub path: PathBuf,
+    /// 1-based line of the object header, 0 when not parsed from a file
+    pub line: usize,
+}
+
+/// An object with the name of one declared before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateObject {
+    pub duplicate: ObjectLocation,
+    pub first: ObjectLocation,
+}
+
+impl DuplicateObject {
+    /// Whether the names differ in case only, e.g. `Car` and `CAR`.
+    pub fn differs_in_case(&self) -> bool {
+        self.duplicate.name != self.first.name
+    }
+}
+
+/// The objects of the files added so far, by name. Names are compared
+/// ignoring case, since targets writing a file per object would write
+/// `Car` and `CAR` to one file on case-insensitive file systems.
+#[derive(Debug, Default)]
+pub struct DeclaredObjects {
+    seen: HashMap<String, ObjectLocation>,
+}
+
+impl DeclaredObjects {
+    /// Adds the objects of `file`, returning those that have the name of
+    /// one added before them.
+    pub fn add(&mut self, file: &OmlFile) -> Vec<DuplicateObject> {
+        let mut duplicates = Vec::new();
+        for obj in &file.objects {
+            let location = ObjectLocation { name: obj.name.clone(), path: file.path.clone(), line: obj.line };
+            match self.seen.get(&obj.name.to_ascii_uppercase()) {
+                Some(first) => duplicates.push(DuplicateObject { duplicate: location, first: first.clone() }),
+                None => {
+                    self.seen.insert(obj.name.to_ascii_uppercase(), location);
+                }
+            }
+        }
+        duplicates
+    }
+}
+
 impl OmlObject {
     const CLASS_NAME: &'static str = "class";
     const ENUM_NAME: &'static str = "enum";
@@ -76,6 +196,11 @@ impl OmlObject {
         Self::BUILTIN_TYPES.contains(&var_type)
     }
 
+    /// Whether the object declares no fields or values.
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
     /// Validates that any non-built-in type used as a variable type in these
     /// objects actually corresponds to another object defined in the same set
     /// OR is present in `imported_names` (types available via `import` statements).
@@ -92,14 +217,15 @@ impl OmlObject {
             }
             for var in &obj.variables {
                 if !var.var_type.is_empty()
+                    && !var.external
                     && !Self::is_builtin_type(&var.var_type)
                     && !object_names.contains(var.var_type.as_str())
                     && !imported_names.contains(&var.var_type)
                 {
-                    return Err(format!(
+                    return Err(errors::SourceError::new(var.line, format!(
                         "Type '{}' used in object '{}' is not a built-in type, is not defined in the same file, and has not been imported",
                         var.var_type, obj.name
-                    ).into());
+                    )).into());
                 }
             }
         }
@@ -107,54 +233,173 @@ impl OmlObject {
         Ok(())
     }
 
-    /// Parses an OML file and returns its objects and any `import` directives.
-    pub fn get_from_file(path: &Path) -> Result<(Vec<Self>, Vec<String>), Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(path)?;
-        Self::scan_file_with_imports(content)
+    /// Rejects fields whose type is one of `skipped`, the objects that
+    /// `--skip-empty` leaves out.
+    pub fn validate_skipped_types(objects: &[Self], skipped: &HashSet<&str>) -> Result<(), Box<dyn std::error::Error>> {
+        for obj in objects.iter().filter(|o| o.oml_type != ObjectType::ENUM) {
+            if let Some(var) = obj.variables.iter().find(|v| !v.external && skipped.contains(v.var_type.as_str())) {
+                return Err(errors::SourceError::new(var.line, format!(
+                    "Field '{}' of '{}' has type '{}', which declares nothing and is left out by --skip-empty",
+                    var.name, obj.name, var.var_type
+                )).into());
+            }
+        }
+
+        Ok(())
     }
 
-    /// Splits `content` into import declarations and the remaining OML source,
-    /// then parses the objects from the remainder.
-    pub fn scan_file_with_imports(content: String) -> Result<(Vec<Self>, Vec<String>), Box<dyn std::error::Error>> {
-        let mut imports: Vec<String> = Vec::new();
-        let mut rest = String::new();
+    /// Rejects modifiers that `modifiers::MODIFIER_RULES` does not allow
+    /// where they are written, and any given twice.
+    pub fn validate_modifiers(objects: &[Self]) -> Result<(), Box<dyn std::error::Error>> {
+        for obj in objects {
+            for var in &obj.variables {
+                if let Some(message) = modifiers::check(obj, var) {
+                    return Err(errors::SourceError::new(var.line, message).into());
+                }
+            }
+        }
 
-        for line in content.lines() {
-            let trimmed = line.trim();
-            if trimmed.starts_with("import ") {
-                let after_keyword = trimmed["import ".len()..].trim();
-                let raw_path = after_keyword
-                    .trim_end_matches(';')
-                    .trim()
-                    .trim_matches('"');
-                if !raw_path.is_empty() {
-                    imports.push(raw_path.to_string());
+        Ok(())
+    }
+
+    /// Rejects enums that declare the same variant twice, or number some of
+    /// their variants but not all or two alike. Names are compared ignoring
+    /// case, since generators upper-case them and `Red` and `RED` would
+    /// become one constant.
+    pub fn validate_enum_variants(objects: &[Self]) -> Result<(), Box<dyn std::error::Error>> {
+        for obj in objects.iter().filter(|o| o.oml_type == ObjectType::ENUM) {
+            let numbered = obj.variables.iter().find(|v| v.value.is_some());
+            let mut values: HashMap<i64, &str> = HashMap::new();
+            let mut seen: HashMap<String, &str> = HashMap::new();
+            for var in &obj.variables {
+                match (&var.value, numbered) {
+                    (None, Some(numbered)) => {
+                        let message = format!(
+                            "Variant '{}' of enum '{}' has no value, but '{}' has one: number every variant or none",
+                            var.name, obj.name, numbered.name
+                        );
+                        return Err(errors::SourceError::new(var.line, message).into());
+                    }
+                    (Some(value), _) => {
+                        if let Some(first) = values.insert(value.parse().unwrap_or_default(), &var.name) {
+                            let message = format!("Variants '{}' and '{}' of enum '{}' both have the value {}", first, var.name, obj.name, value);
+                            return Err(errors::SourceError::new(var.line, message).into());
+                        }
+                    }
+                    (None, None) => {}
+                }
+
+                if let Some(first) = seen.insert(var.name.to_ascii_uppercase(), &var.name) {
+                    let message = if first == var.name {
+                        format!("Variant '{}' is declared twice in enum '{}'", var.name, obj.name)
+                    } else {
+                        format!(
+                            "Variant '{}' of enum '{}' collides with '{}': variant names are compared ignoring case",
+                            var.name, obj.name, first
+                        )
+                    };
+                    return Err(errors::SourceError::new(var.line, message).into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `objects` reordered so that each comes after the objects whose types
+    /// its fields use, otherwise keeping their order. Objects on a cycle
+    /// stay in the order they were first reached.
+    pub fn dependency_order(objects: &[Self]) -> Vec<Self> {
+        fn visit(i: usize, objects: &[OmlObject], visited: &mut [bool], order: &mut Vec<usize>) {
+            if visited[i] {
+                return;
+            }
+            visited[i] = true;
+            if objects[i].oml_type != ObjectType::ENUM {
+                for var in objects[i].variables.iter().filter(|v| !v.external) {
+                    if let Some(dep) = objects.iter().position(|o| o.name == var.var_type) {
+                        visit(dep, objects, visited, order);
+                    }
                 }
-            } else {
-                rest.push_str(line);
-                rest.push('\n');
             }
+            order.push(i);
         }
 
-        let objects = Self::scan_file(rest)?;
+        let mut visited = vec![false; objects.len()];
+        let mut order = Vec::with_capacity(objects.len());
+        for i in 0..objects.len() {
+            visit(i, objects, &mut visited, &mut order);
+        }
+        order.into_iter().map(|i| objects[i].clone()).collect()
+    }
+
+    /// Parses an OML file and returns its objects and any `import` directives.
+    /// The file is read a line at a time, so only the object being parsed is
+    /// held in memory besides the ones already finished.
+    pub fn get_from_file(path: &Path) -> Result<(Vec<Self>, Vec<String>), Box<dyn std::error::Error>> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut imports: Vec<String> = Vec::new();
+        let objects = Self::scan_lines(reader.lines(), Some(&mut imports))?;
+        Ok((objects, imports))
+    }
+
+    /// Parses the objects in `content`, collecting its import declarations
+    /// apart from them.
+    pub fn scan_file_with_imports(content: String) -> Result<(Vec<Self>, Vec<String>), Box<dyn std::error::Error>> {
+        let mut imports: Vec<String> = Vec::new();
+        let objects = Self::scan_lines(content.lines().map(Ok), Some(&mut imports))?;
         Ok((objects, imports))
     }
 
     pub fn scan_file(content: String) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
-        let lines: Vec<&str> = content.lines().collect();
+        Self::scan_lines(content.lines().map(Ok), None)
+    }
+
+    /// Parses objects from `lines` as they come. With `imports`, `import`
+    /// lines anywhere are collected into it instead of being parsed.
+    fn scan_lines<S: AsRef<str>>(
+        lines: impl Iterator<Item = io::Result<S>>,
+        mut imports: Option<&mut Vec<String>>,
+    ) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
         let mut results: Vec<Self> = Vec::new();
 
         let mut current: Option<Self> = None;
         let mut inside_body = false;
         let mut commenting = false;
-        let mut body_lines: Vec<String> = Vec::new();
-
-        for line in lines {
-            let trimmed = line.trim();
+        // fields of the current object, parsed as their lines are read
+        let mut variables: Vec<Variable> = Vec::new();
+        // the first field that failed to parse, reported once the object closes
+        let mut body_error: Option<errors::SourceError> = None;
+        // `///` lines seen since the last declaration, waiting to be attached
+        let mut pending_doc: Vec<String> = Vec::new();
+
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            let line_no = index + 1;
+            let trimmed = line.as_ref().trim();
+
+            if let Some(imports) = imports.as_deref_mut()
+                && let Some(after_keyword) = trimmed.strip_prefix("import ")
+            {
+                let raw_path = after_keyword
+                    .trim()
+                    .trim_end_matches(';')
+                    .trim()
+                    .trim_matches('"');
+                if !raw_path.is_empty() {
+                    imports.push(raw_path.to_string());
+                }
+                continue;
+            }
             #[allow(unused_assignments)]
             let mut processed_line: String = String::new();
             let mut line_ref: &str = trimmed;
 
+            if !commenting && let Some(doc_line) = Self::parse_doc_line(trimmed) {
+                pending_doc.push(doc_line.to_string());
+                continue;
+            }
+
             if commenting {
                 if let Some(pos) = line_ref.find("*/") {
                     commenting = false;
@@ -167,14 +412,14 @@ impl OmlObject {
                 }
             }
 
-            if let Some(pos) = line_ref.find("//") {
+            if let Some(pos) = Self::find_unquoted(line_ref, "//") {
                 line_ref = line_ref[..pos].trim_end();
                 if line_ref.is_empty() {
                     continue;
                 }
             }
 
-            if let Some(pos) = line_ref.find("/*") {
+            if let Some(pos) = Self::find_unquoted(line_ref, "/*") {
                 let before_comment = line_ref[..pos].trim_end();
 
                 if let Some(end_pos) = line_ref[pos..].find("*/") {
@@ -192,12 +437,12 @@ impl OmlObject {
             }
 
             if !inside_body {
-                let tokens: Vec<&str> = line_ref.split_whitespace().collect();
-                if tokens.is_empty() {
+                let mut tokens = line_ref.split_whitespace();
+                let Some(keyword) = tokens.next() else {
                     continue;
-                }
+                };
 
-                let obj_type = match tokens[0] {
+                let obj_type = match keyword {
                     Self::CLASS_NAME => Some(ObjectType::CLASS),
                     Self::ENUM_NAME => Some(ObjectType::ENUM),
                     Self::STRUCT_NAME => Some(ObjectType::STRUCT),
@@ -209,9 +454,12 @@ impl OmlObject {
                         oml_type,
                         name: String::from("Nothing"),
                         variables: vec![],
+                        doc: Self::take_doc(&mut pending_doc),
+                        line: line_no,
                     };
-                    if tokens.len() > 1 {
-                        obj.assign_obj_name(tokens[1])?;
+                    if let Some(name) = tokens.next() {
+                        obj.assign_obj_name(name)
+                            .map_err(|e| errors::SourceError::new(line_no, e.to_string()))?;
                     }
                     current = Some(obj);
                 }
@@ -222,26 +470,40 @@ impl OmlObject {
                 continue;
             }
 
-            if line_ref.contains('}') {
+            if Self::find_unquoted(line_ref, "}").is_some() {
                 // finish the current object
                 if let Some(mut obj) = current.take() {
-                    if !body_lines.is_empty() {
-                        obj.variables = Self::extract_object_variables(body_lines.drain(..).collect())?;
+                    if let Some(error) = body_error {
+                        return Err(error.into());
                     }
+                    obj.variables = std::mem::take(&mut variables);
                     results.push(obj);
                 }
-                body_lines.clear();
+                variables.clear();
+                body_error = None;
+                pending_doc.clear();
                 inside_body = false;
                 continue;
             }
 
             if !line_ref.is_empty() {
-                let tokens: Vec<&str> = line_ref.split_whitespace().collect();
-                let has_type_and_name = tokens.iter().any(|&t| Self::is_type(t))
-                    && tokens.len() >= 2;
+                let has_type_and_name = line_ref.split_whitespace().nth(1).is_some()
+                    && line_ref.split_whitespace().any(Self::is_type);
 
                 if has_type_and_name || line_ref.ends_with(';') {
-                    body_lines.push(line_ref.to_string());
+                    let doc = Self::take_doc(&mut pending_doc);
+                    let cleaned = line_ref.trim_end_matches(';').trim();
+                    let declaration = match &current {
+                        Some(obj) if obj.oml_type == ObjectType::ENUM => Self::parse_enum_variant(cleaned),
+                        _ => Self::parse_variable_declaration(cleaned),
+                    };
+                    match declaration {
+                        Ok(declaration) => variables.push(declaration.into_variable(doc, line_no)),
+                        Err(e) if body_error.is_none() => {
+                            body_error = Some(errors::SourceError::new(line_no, format!("Error parsing line '{}': {}", line_ref, e)));
+                        }
+                        Err(_) => {}
+                    }
                 }
             }
         }
@@ -249,8 +511,25 @@ impl OmlObject {
         Ok(results)
     }
 
+    /// Returns the text of a `///` doc comment line, or `None` for any other
+    /// line (including `////` separator comments).
+    fn parse_doc_line(line: &str) -> Option<&str> {
+        let rest = line.strip_prefix("///")?;
+        if rest.starts_with('/') {
+            return None;
+        }
+        Some(rest.strip_prefix(' ').unwrap_or(rest).trim_end())
+    }
+
+    fn take_doc(pending_doc: &mut Vec<String>) -> Option<String> {
+        if pending_doc.is_empty() {
+            return None;
+        }
+        Some(std::mem::take(pending_doc).join("\n"))
+    }
+
     fn assign_obj_name(&mut self, name: &str) -> Result<(), errors::NameError> {
-        match Self::is_valid_name(name) {
+        match lexer::is_name(name) {
             true => self.name = name.to_string(),
             false => {
                 let message = format!("{} is not a valid obj name.", name);
@@ -290,7 +569,7 @@ impl OmlObject {
                 let base  = &token[..bracket_pos];
                 let inner = &token[bracket_pos + 1..token.len() - 1];
                 if inner.parse::<u32>().map(|n| n > 0).unwrap_or(false) {
-                    return Self::is_builtin_type(base) || Self::is_valid_name(base);
+                    return Self::is_builtin_type(base) || lexer::is_name(base);
                 }
             }
             return false; // malformed bracket → not a type
@@ -299,12 +578,12 @@ impl OmlObject {
             "int8" | "int16" | "int32" | "int64" |
             "uint8" | "uint16" | "uint32" | "uint64" |
             "float" | "double" | "bool" | "string" | "char"
-        ) || Self::is_valid_name(token)
+        ) || lexer::is_name(token)
     }
 
     /// Parses a `type[N]` token into `(base_type, N)`.  Returns `None` if the
     /// token does not match the pattern or if N is zero.
-    fn parse_array_type(token: &str) -> Option<(String, u32)> {
+    fn parse_array_type(token: &str) -> Option<(&str, u32)> {
         let bp = token.find('[')?;
         if !token.ends_with(']') {
             return None;
@@ -312,50 +591,52 @@ impl OmlObject {
         let base  = &token[..bp];
         let inner = &token[bp + 1..token.len() - 1];
         let size: u32 = inner.parse().ok().filter(|&n| n > 0)?;
-        if Self::is_builtin_type(base) || Self::is_valid_name(base) {
-            Some((base.to_string(), size))
+        if Self::is_builtin_type(base) || lexer::is_name(base) {
+            Some((base, size))
         } else {
             None
         }
     }
 
-    fn extract_object_variables(lines: Vec<String>) -> Result<Vec<Variable>, Box<dyn std::error::Error>> {
-        let mut vars: Vec<Variable> = Vec::new();
-
-        for line in lines {
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-
-            let cleaned = trimmed.trim_end_matches(|c| c == ';' || c == '\n').trim();
-
-            match Self::parse_variable_declaration(cleaned) {
-                Ok(var) => vars.push(var),
-                Err(e) => {
-                    return Err(format!("Error parsing line '{}': {}", line, e).into());
-                }
-            }
-        }
-
-        Ok(vars)
-    }
-
-    fn parse_variable_declaration(line: &str) -> Result<Variable, String> {
-        let tokens: Vec<&str> = line.split_whitespace().collect();
+    fn parse_variable_declaration(line: &str) -> Result<Declaration<'_>, String> {
+        let (line, value) = match line.split_once('=') {
+            Some((declaration, value)) => (declaration, Some(value.trim())),
+            None => (line, None),
+        };
+        let mut tokens = line.split_whitespace().peekable();
 
-        if tokens.is_empty() {
+        if tokens.peek().is_none() {
             return Err("Empty line".to_string());
         }
 
         let mut visibility: Option<VariableVisibility> = None;
         let mut modifiers: Vec<VariableModifier> = Vec::new();
-        let mut var_type: Option<String> = None;
-        let mut var_name: Option<String> = None;
+        let mut var_type: Option<&str> = None;
+        let mut var_name: Option<&str> = None;
         let mut array_kind = ArrayKind::None;
+        let mut tag: Option<u32> = None;
+        let mut external = false;
         let mut type_seen = false;
 
-        for token in &tokens {
+        for token in tokens {
+            if let Some(annotation) = token.strip_prefix('@') {
+                if type_seen {
+                    return Err(format!("Annotation '{}' cannot appear after type", token));
+                }
+                if annotation == "external" {
+                    if external {
+                        return Err("Multiple external annotations found".to_string());
+                    }
+                    external = true;
+                    continue;
+                }
+                if tag.is_some() {
+                    return Err("Multiple tag annotations found".to_string());
+                }
+                tag = Some(Self::parse_tag(annotation)?);
+                continue;
+            }
+
             if let Some(vis) = Self::parse_visibility(token) {
                 if type_seen {
                     return Err(format!(
@@ -383,7 +664,7 @@ impl OmlObject {
             }
 
             // "list" keyword → dynamic array; the next token will be the element type
-            if *token == "list" && !type_seen {
+            if token == "list" && !type_seen {
                 if array_kind != ArrayKind::None {
                     return Err("Multiple array kind specifiers".to_string());
                 }
@@ -407,26 +688,26 @@ impl OmlObject {
             }
 
             // "type[N]" → static array
-            if var_type.is_none() && !type_seen {
-                if let Some((base_type, size)) = Self::parse_array_type(token) {
-                    if array_kind == ArrayKind::Dynamic {
-                        return Err("Cannot combine 'list' with static array syntax 'type[N]'".to_string());
-                    }
-                    var_type = Some(base_type);
-                    array_kind = ArrayKind::Static(size);
-                    type_seen = true;
-                    continue;
+            if var_type.is_none() && !type_seen
+                && let Some((base_type, size)) = Self::parse_array_type(token)
+            {
+                if array_kind == ArrayKind::Dynamic {
+                    return Err("Cannot combine 'list' with static array syntax 'type[N]'".to_string());
                 }
+                var_type = Some(base_type);
+                array_kind = ArrayKind::Static(size);
+                type_seen = true;
+                continue;
             }
 
             if Self::is_type(token) && var_type.is_none() {
-                var_type = Some(token.to_string());
+                var_type = Some(token);
                 type_seen = true;
                 continue;
             }
 
             if var_type.is_some() && var_name.is_none() {
-                var_name = Some(token.to_string());
+                var_name = Some(token);
                 break;
             }
 
@@ -437,23 +718,180 @@ impl OmlObject {
         let final_name = var_name.ok_or("No variable name specified")?;
         let final_visibility = visibility.unwrap_or(VariableVisibility::PRIVATE);
 
-        if modifiers.contains(&VariableModifier::CONST) && modifiers.contains(&VariableModifier::MUT) {
-            return Err(format!("Const Error: variable {} cannot be const and mut simultaneously!", final_name));
+        if let Some((first, second)) = modifiers::find_conflict(&modifiers) {
+            return Err(format!("Field '{}' cannot be both '{}' and '{}'", final_name, first.keyword(), second.keyword()));
+        }
+        if let Some(value) = value {
+            Self::check_value(final_name, final_type, &array_kind, value)?;
         }
 
-        Ok(Variable {
+        Ok(Declaration {
             var_mod: modifiers,
             visibility: final_visibility,
             var_type: final_type,
             array_kind,
             name: final_name,
+            tag,
+            external,
+            value,
         })
     }
 
-    #[inline]
-    fn is_valid_name(name: &str) -> bool {
-        let re = Regex::new(r"^[a-zA-Z][a-zA-Z0-9_.-]*$").unwrap();
-        re.is_match(name)
+    /// Parses an enum variant: a name, written on its own or, as most files
+    /// do, after the type `string`, optionally after a `@tag(N)` and
+    /// optionally numbered with `= N`, an `int32`. Any other type, a
+    /// visibility or a modifier is an error rather than something
+    /// generators would drop.
+    fn parse_enum_variant(line: &str) -> Result<Declaration<'_>, String> {
+        let (line, value) = match line.split_once('=') {
+            Some((variant, value)) => (variant, Some(value.trim())),
+            None => (line, None),
+        };
+        let mut tag: Option<u32> = None;
+        let mut words: Vec<&str> = Vec::new();
+
+        for token in line.split_whitespace() {
+            if let Some(annotation) = token.strip_prefix('@') {
+                if annotation == "external" || !words.is_empty() {
+                    return Err(format!("Annotation '{}' is not allowed on an enum variant", token));
+                }
+                if tag.is_some() {
+                    return Err("Multiple tag annotations found".to_string());
+                }
+                tag = Some(Self::parse_tag(annotation)?);
+            } else if Self::parse_visibility(token).is_some() || Self::parse_modifier(token).is_some() {
+                return Err(format!("An enum variant cannot be '{}': variants are plain names", token));
+            } else {
+                words.push(token);
+            }
+        }
+
+        let name = match words.as_slice() {
+            [] => return Err("No variant name specified".to_string()),
+            [name] | ["string", name] => *name,
+            [var_type, name] => {
+                return Err(format!("Enum variant '{}' cannot have type '{}': variants are names, numbered with '= N' if need be", name, var_type));
+            }
+            [_, name, extra, ..] => {
+                return Err(format!("Unexpected token '{}' after variant '{}'", extra, name));
+            }
+        };
+        if name.contains(['<', '>', '[', ']']) {
+            return Err(format!("Enum variant '{}' cannot be a list or an array", name));
+        }
+        if let Some(value) = value {
+            Self::check_value(name, "int32", &ArrayKind::None, value)?;
+        }
+
+        Ok(Declaration {
+            var_mod: Vec::new(),
+            visibility: VariableVisibility::PRIVATE,
+            var_type: "string",
+            array_kind: ArrayKind::None,
+            name,
+            tag,
+            external: false,
+            value,
+        })
+    }
+
+    /// Parses the body of a `@tag(N)` annotation (without the `@`); N must be
+    /// a positive integer.
+    fn parse_tag(annotation: &str) -> Result<u32, String> {
+        let inner = annotation
+            .strip_prefix("tag(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| format!("Unknown annotation '@{}', expected '@tag(N)' or '@external'", annotation))?;
+        inner
+            .parse::<u32>()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("Tag must be a positive integer in '@{}'", annotation))
+    }
+
+    /// Checks that `value` is a literal of the built-in `var_type`: an
+    /// integer in its range, a decimal number for `float` and `double`,
+    /// `true` or `false`, or a quoted string or single character, in which
+    /// `\\`, `\"`, `\'`, `\n` and `\t` are the escapes.
+    fn check_value(name: &str, var_type: &str, array_kind: &ArrayKind, value: &str) -> Result<(), String> {
+        if value.is_empty() {
+            return Err(format!("No value given after '=' for '{}'", name));
+        }
+        if *array_kind != ArrayKind::None {
+            return Err(format!("List or array field '{}' cannot have a value", name));
+        }
+        let range = match var_type {
+            "int8" => Some((i8::MIN as i128, i8::MAX as i128)),
+            "int16" => Some((i16::MIN as i128, i16::MAX as i128)),
+            "int32" => Some((i32::MIN as i128, i32::MAX as i128)),
+            "int64" => Some((i64::MIN as i128, i64::MAX as i128)),
+            "uint8" => Some((0, u8::MAX as i128)),
+            "uint16" => Some((0, u16::MAX as i128)),
+            "uint32" => Some((0, u32::MAX as i128)),
+            "uint64" => Some((0, u64::MAX as i128)),
+            _ => None,
+        };
+        let number = value.strip_prefix('-').unwrap_or(value);
+        let valid = match (var_type, range) {
+            (_, Some((min, max))) => {
+                !number.is_empty()
+                    && number.bytes().all(|b| b.is_ascii_digit())
+                    && value.parse::<i128>().is_ok_and(|n| (min..=max).contains(&n))
+            }
+            ("float" | "double", None) => {
+                let (whole, fraction) = number.split_once('.').unwrap_or((number, "0"));
+                !whole.is_empty()
+                    && !fraction.is_empty()
+                    && whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit())
+            }
+            ("bool", None) => matches!(value, "true" | "false"),
+            ("string", None) => Self::quoted_len(value, '"').is_some(),
+            ("char", None) => Self::quoted_len(value, '\'') == Some(1),
+            _ => return Err(format!("Field '{}' of type '{}' cannot have a value: only built-in types can", name, var_type)),
+        };
+        if !valid {
+            return Err(format!("'{}' is not a valid {} value for '{}'", value, var_type, name));
+        }
+        Ok(())
+    }
+
+    /// The number of characters between the `quote`s that `literal` starts
+    /// and ends with, counting an escape as one; `None` if it is not quoted
+    /// or holds an unknown escape or an unescaped quote.
+    fn quoted_len(literal: &str, quote: char) -> Option<usize> {
+        let inner = literal.strip_prefix(quote)?.strip_suffix(quote)?;
+        let mut chars = inner.chars();
+        let mut len = 0;
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                if !matches!(chars.next(), Some('\\' | '"' | '\'' | 'n' | 't')) {
+                    return None;
+                }
+            } else if c == quote {
+                return None;
+            }
+            len += 1;
+        }
+        Some(len)
+    }
+
+    /// The byte offset of the first `pattern` in `line` outside a quoted
+    /// string, so that values may hold `//` or braces.
+    fn find_unquoted(line: &str, pattern: &str) -> Option<usize> {
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        for (index, c) in line.char_indices() {
+            match quote {
+                Some(_) if escaped => escaped = false,
+                Some(_) if c == '\\' => escaped = true,
+                Some(open) if c == open => quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None if line[index..].starts_with(pattern) => return Some(index),
+                None => {}
+            }
+        }
+        None
     }
 }
 
@@ -473,7 +911,7 @@ mod test {
         "MyClass"
     ];
 
-    const INVALID_NAMES: [&str; 8] = [
+    const INVALID_NAMES: [&str; 9] = [
         "123file",
         "_private",
         "-file",
@@ -482,25 +920,16 @@ mod test {
         "my$var",
         "file/path",
         "",
+        "café",
     ];
 
-    #[test]
-    fn test_name_validity() {
-        for valid_name in VALID_NAMES {
-            assert_eq!(OmlObject::is_valid_name(valid_name), true);
-        }
-
-        for valid_name in INVALID_NAMES {
-            assert_eq!(OmlObject::is_valid_name(valid_name), false);
-        }
-    }
-
     #[test]
     fn test_assign_name() {
         let mut oml_obj = OmlObject {
             oml_type: ObjectType::UNDECIDED,
             name: String::new(),
             variables: vec![],
+            ..Default::default()
         };
 
         for valid_name in VALID_NAMES {
@@ -741,6 +1170,219 @@ mod test {
         assert_eq!(vars[2].array_kind, ArrayKind::Dynamic);
     }
 
+    #[test]
+    fn test_doc_comments_attach_to_next_declaration() {
+        let content = r#"
+            /// A person.
+            /// Second line.
+            class Person {
+                /// Full name
+                string name;
+                // plain comment, not documentation
+                int32 age;
+                //// separator, not documentation
+                bool active;
+            }
+        "#;
+
+        let objects = OmlObject::scan_file(content.to_string()).unwrap();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].doc.as_deref(), Some("A person.\nSecond line."));
+        assert_eq!(objects[0].variables.len(), 3);
+        assert_eq!(objects[0].variables[0].doc.as_deref(), Some("Full name"));
+        assert_eq!(objects[0].variables[1].doc, None);
+        assert_eq!(objects[0].variables[2].doc, None);
+    }
+
+    #[test]
+    fn test_parse_tag_annotation() {
+        let var = OmlObject::parse_variable_declaration("@tag(3) public optional string nickname").unwrap();
+        assert_eq!(var.tag, Some(3));
+        assert_eq!(var.name, "nickname");
+        assert_eq!(var.visibility, VariableVisibility::PUBLIC);
+
+        assert_eq!(OmlObject::parse_variable_declaration("int32 x").unwrap().tag, None);
+
+        for input in ["@tag(0) int32 x", "@tag(x) int32 x", "@tag 1 int32 x", "@id(1) int32 x", "int32 @tag(1) x", "@tag(1) @tag(2) int32 x"] {
+            assert!(OmlObject::parse_variable_declaration(input).is_err(), "Should have failed: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_field_values() {
+        let objects = OmlObject::scan_file(concat!(
+            "class Config {\n",
+            "static const int32 MAX = -5;\n",
+            "double ratio = 0.5;\n",
+            "string url = \"http://x.y/{id}\"; // trailing\n",
+            "char sep = '\\'';\n",
+            "bool on=true;\n",
+            "uint8 level;\n",
+            "}\n",
+        ).to_string()).unwrap();
+        let values: Vec<_> = objects[0].variables.iter().map(|var| (var.name.as_str(), var.value.as_deref())).collect();
+        assert_eq!(values, vec![
+            ("MAX", Some("-5")),
+            ("ratio", Some("0.5")),
+            ("url", Some("\"http://x.y/{id}\"")),
+            ("sep", Some("'\\''")),
+            ("on", Some("true")),
+            ("level", None),
+        ]);
+
+        for (line, message) in [
+            ("int8 x = 128", "'128' is not a valid int8 value for 'x'"),
+            ("uint32 x = -1", "'-1' is not a valid uint32 value for 'x'"),
+            ("float x = 1.", "'1.' is not a valid float value for 'x'"),
+            ("bool x = 1", "'1' is not a valid bool value for 'x'"),
+            ("string x = \"a\\q\"", "'\"a\\q\"' is not a valid string value for 'x'"),
+            ("char x = 'ab'", "''ab'' is not a valid char value for 'x'"),
+            ("int32 x =", "No value given after '=' for 'x'"),
+            ("list int32 x = 1", "List or array field 'x' cannot have a value"),
+            ("Color x = Red", "Field 'x' of type 'Color' cannot have a value: only built-in types can"),
+        ] {
+            assert_eq!(OmlObject::parse_variable_declaration(line).unwrap_err(), message);
+        }
+    }
+
+    #[test]
+    fn test_dependency_order() {
+        let objects = OmlObject::scan_file("class Car {\nEngine engine;\nColor color;\n}\nenum Color {\nstring Red;\n}\nstruct Engine {\nPart[2] parts;\n}\nstruct Part {\nint32 id;\n}\n".to_string()).unwrap();
+        let names: Vec<String> = OmlObject::dependency_order(&objects).into_iter().map(|o| o.name).collect();
+        assert_eq!(names, ["Part", "Engine", "Color", "Car"]);
+
+        let objects = OmlObject::scan_file("class A {\nB b;\n}\nclass B {\nA a;\n}\n".to_string()).unwrap();
+        let names: Vec<String> = OmlObject::dependency_order(&objects).into_iter().map(|o| o.name).collect();
+        assert_eq!(names, ["B", "A"]);
+    }
+
+    #[test]
+    fn test_external_types_pass_validation() {
+        let var = OmlObject::parse_variable_declaration("@tag(2) @external public Money total").unwrap();
+        assert!(var.external);
+        assert_eq!(var.tag, Some(2));
+        assert_eq!(var.var_type, "Money");
+        assert!(OmlObject::parse_variable_declaration("@external @external Money total").is_err());
+
+        let objects = OmlObject::scan_file("class Order {\n@external Money total;\nMoney refund;\n}".to_string()).unwrap();
+        assert!(!objects[0].variables[1].external);
+        let error = OmlObject::validate_custom_types(&objects, &HashSet::new()).unwrap_err();
+        assert!(error.to_string().contains("Type 'Money'"), "Got: {}", error);
+
+        let objects = OmlObject::scan_file("class Order {\n@external Money total;\n}".to_string()).unwrap();
+        assert!(OmlObject::validate_custom_types(&objects, &HashSet::new()).is_ok());
+    }
+
+    #[test]
+    fn test_enum_variants_must_differ_ignoring_case() {
+        let objects = OmlObject::scan_file("enum Color {\nstring Red;\nstring Green;\n}\n".to_string()).unwrap();
+        assert!(OmlObject::validate_enum_variants(&objects).is_ok());
+
+        let objects = OmlObject::scan_file("enum Color {\nstring Red;\nstring Green;\nstring RED;\n}\n".to_string()).unwrap();
+        let error = OmlObject::validate_enum_variants(&objects).unwrap_err();
+        assert_eq!(error.to_string(), "line 4: Variant 'RED' of enum 'Color' collides with 'Red': variant names are compared ignoring case");
+
+        let objects = OmlObject::scan_file("enum Color {\nstring Red;\nstring Red;\n}\n".to_string()).unwrap();
+        let error = OmlObject::validate_enum_variants(&objects).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: Variant 'Red' is declared twice in enum 'Color'");
+
+        // only enums: fields of a class are told apart by their case
+        let objects = OmlObject::scan_file("class Car {\nint32 id;\nint32 ID;\n}\n".to_string()).unwrap();
+        assert!(OmlObject::validate_enum_variants(&objects).is_ok());
+    }
+
+    #[test]
+    fn test_enum_variants_are_numbered_all_or_none() {
+        let objects = OmlObject::scan_file("enum Status {\nOk = 200;\n@tag(2) string Missing = -404;\n}\n".to_string()).unwrap();
+        assert!(OmlObject::validate_enum_variants(&objects).is_ok());
+        let values: Vec<_> = objects[0].variables.iter().map(|v| (v.name.as_str(), v.value.as_deref(), v.tag)).collect();
+        assert_eq!(values, vec![("Ok", Some("200"), None), ("Missing", Some("-404"), Some(2))]);
+
+        let objects = OmlObject::scan_file("enum Status {\nOk = 200;\nMissing;\n}\n".to_string()).unwrap();
+        let error = OmlObject::validate_enum_variants(&objects).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: Variant 'Missing' of enum 'Status' has no value, but 'Ok' has one: number every variant or none");
+
+        let objects = OmlObject::scan_file("enum Status {\nOk = 200;\nFine = 0200;\n}\n".to_string()).unwrap();
+        let error = OmlObject::validate_enum_variants(&objects).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: Variants 'Ok' and 'Fine' of enum 'Status' both have the value 0200");
+    }
+
+    #[test]
+    fn test_contradictory_modifiers_are_rejected() {
+        let objects = OmlObject::scan_file("class Car {\nstatic const int32 wheels;\nstatic optional static string name;\n}\n".to_string()).unwrap();
+        let error = OmlObject::validate_modifiers(&objects).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: Field 'name' of 'Car' is declared 'static' twice");
+
+        let objects = OmlObject::scan_file("class Car {\nstatic const int32 wheels;\n}\nenum Color {\nstring Red;\n}\n".to_string()).unwrap();
+        assert!(OmlObject::validate_modifiers(&objects).is_ok());
+
+        let error = OmlObject::scan_file("class Car {\nconst mut int32 wheels;\n}\n".to_string()).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: Error parsing line 'const mut int32 wheels;': Field 'wheels' cannot be both 'const' and 'mut'");
+    }
+
+    #[test]
+    fn test_enum_variants_are_plain_names() {
+        let objects = OmlObject::scan_file("enum Color {\nRed;\nstring Green;\n@tag(7) Blue;\n}\n".to_string()).unwrap();
+        let variants: Vec<_> = objects[0].variables.iter().map(|var| (var.name.as_str(), var.var_type.as_str(), var.tag)).collect();
+        assert_eq!(variants, vec![("Red", "string", None), ("Green", "string", None), ("Blue", "string", Some(7))]);
+
+        for (line, message) in [
+            ("int32 Red;", "Enum variant 'Red' cannot have type 'int32': variants are names, numbered with '= N' if need be"),
+            ("list<string> Red;", "Enum variant 'Red' cannot have type 'list<string>': variants are names, numbered with '= N' if need be"),
+            ("Red = 1.5;", "'1.5' is not a valid int32 value for 'Red'"),
+            ("public string Red;", "An enum variant cannot be 'public': variants are plain names"),
+            ("optional Red;", "An enum variant cannot be 'optional': variants are plain names"),
+            ("@external Red;", "Annotation '@external' is not allowed on an enum variant"),
+            ("string Red Green;", "Unexpected token 'Green' after variant 'Red'"),
+        ] {
+            let error = OmlObject::scan_file(format!("enum Color {{\n{}\n}}\n", line)).unwrap_err();
+            assert_eq!(error.to_string(), format!("line 2: Error parsing line '{}': {}", line, message));
+        }
+    }
+
+    #[test]
+    fn test_objects_declared_twice_are_reported_at_both() {
+        let file = |name: &str, content: &str| OmlFile {
+            file_name: name.to_string(),
+            path: PathBuf::from(format!("/fake/{}.oml", name)),
+            objects: OmlObject::scan_file(content.to_string()).unwrap(),
+            imports: vec![],
+        };
+        let car = file("car", "class Car {\nint32 id;\n}\n/* enum Car {\n} */\nenum Color {\nRed;\n}\n");
+        let fleet = file("fleet", "// parts\nstruct CAR {\n}\nenum Color {\nBlue;\n}\n");
+        assert_eq!(car.objects.iter().map(|o| o.line).collect::<Vec<_>>(), vec![1, 6]);
+
+        let mut declared = DeclaredObjects::default();
+        assert!(declared.add(&car).is_empty());
+        let location = |name: &str, file: &OmlFile, line: usize| ObjectLocation { name: name.to_string(), path: file.path.clone(), line };
+        let duplicates = declared.add(&fleet);
+        assert_eq!(duplicates, vec![
+            DuplicateObject { duplicate: location("CAR", &fleet, 2), first: location("Car", &car, 1) },
+            DuplicateObject { duplicate: location("Color", &fleet, 4), first: location("Color", &car, 6) },
+        ]);
+        assert!(duplicates[0].differs_in_case() && !duplicates[1].differs_in_case());
+    }
+
+    #[test]
+    fn test_skipped_empty_types_cannot_be_used() {
+        let objects = OmlObject::scan_file("class Marker {\n}\nclass Car {\nint32 id;\nMarker tag;\n}\n".to_string()).unwrap();
+        assert!(objects[0].is_empty() && !objects[1].is_empty());
+
+        let skipped: HashSet<&str> = HashSet::from(["Marker"]);
+        let error = OmlObject::validate_skipped_types(&objects, &skipped).unwrap_err();
+        assert_eq!(error.to_string(), "line 5: Field 'tag' of 'Car' has type 'Marker', which declares nothing and is left out by --skip-empty");
+        assert!(OmlObject::validate_skipped_types(&objects, &HashSet::new()).is_ok());
+    }
+
+    #[test]
+    fn test_first_bad_field_is_reported_when_its_object_closes() {
+        let error = OmlObject::scan_file("class Car {\nint32 id;\nint32[0] wheels;\nstring;\n}\n".to_string()).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: Error parsing line 'int32[0] wheels;': Array size must be greater than 0 in 'int32[0]'");
+
+        // a body without an object header is skipped unparsed
+        assert!(OmlObject::scan_file("car {\nint32[0] wheels;\n}\n".to_string()).unwrap().is_empty());
+    }
+
     #[cfg(test)]
     mod comment_tests {
         use super::*;
@@ -766,8 +1408,8 @@ mod test {
             "#;
 
             let vars = vec![
-                Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: String::from("int64"), array_kind: ArrayKind::None, name: String::from("x") },
-                Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: String::from("int64"), array_kind: ArrayKind::None, name: String::from("y") },
+                Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: String::from("int64"), array_kind: ArrayKind::None, name: String::from("x"), line: 4, ..Default::default() },
+                Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: String::from("int64"), array_kind: ArrayKind::None, name: String::from("y"), line: 5, ..Default::default() },
             ];
 
             let result = OmlObject::scan_file(content.to_string());
@@ -804,8 +1446,8 @@ mod test {
             "#;
 
             let vars = vec![
-                Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: String::from("int64"), array_kind: ArrayKind::None, name: String::from("x") },
-                Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: String::from("int64"), array_kind: ArrayKind::None, name: String::from("y") },
+                Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: String::from("int64"), array_kind: ArrayKind::None, name: String::from("x"), line: 6, ..Default::default() },
+                Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: String::from("int64"), array_kind: ArrayKind::None, name: String::from("y"), line: 7, ..Default::default() },
             ];
 
             let result = OmlObject::scan_file(content.to_string());
@@ -0,0 +1,128 @@
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::core::utils::{FNV1A_OFFSET_BASIS, fnv1a_update};
+
+/// A generated file written through a `BufWriter` into a temporary file
+/// beside its destination, and compared chunk by chunk with the file already
+/// there as it goes. Neither version is ever held whole in memory.
+///
+/// `finish` moves the new file into place only if it differs, so an
+/// unchanged file keeps its mtime and incremental builds don't redo work;
+/// a run that fails midway leaves the old file as it was.
+pub struct OutputFile {
+    path: PathBuf,
+    temp_path: PathBuf,
+    writer: BufWriter<File>,
+    /// The file being replaced, while everything written so far matches it
+    existing: Option<BufReader<File>>,
+    hash: u64,
+}
+
+impl OutputFile {
+    /// Starts writing the file at `path`, creating any directories it needs.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut temp_name = path.file_name().map(OsString::from).unwrap_or_default();
+        temp_name.push(".oml-tmp");
+        let temp_path = path.with_file_name(temp_name);
+
+        Ok(Self {
+            writer: BufWriter::new(File::create(&temp_path)?),
+            existing: File::open(path).ok().map(BufReader::new),
+            path: path.to_path_buf(),
+            temp_path,
+            hash: FNV1A_OFFSET_BASIS,
+        })
+    }
+
+    /// Puts the file in place unless it is the same as the one already
+    /// there. Returns whether it was written, and the FNV-1a hash of its
+    /// content.
+    pub fn finish(mut self) -> io::Result<(bool, u64)> {
+        self.writer.flush()?;
+        let unchanged = match &mut self.existing {
+            Some(existing) => existing.fill_buf()?.is_empty(),
+            None => false,
+        };
+        if unchanged {
+            fs::remove_file(&self.temp_path)?;
+        } else {
+            fs::rename(&self.temp_path, &self.path)?;
+        }
+        Ok((!unchanged, self.hash))
+    }
+}
+
+impl Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.writer.write(buf)?;
+        let written = &buf[..n];
+        self.hash = fnv1a_update(self.hash, written);
+        if let Some(existing) = &mut self.existing
+            && !starts_with(existing, written).unwrap_or(false)
+        {
+            self.existing = None;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl Drop for OutputFile {
+    fn drop(&mut self) {
+        // Gone already after `finish`; left behind by a write that failed
+        let _ = fs::remove_file(&self.temp_path);
+    }
+}
+
+/// Whether the next bytes of `reader` are `bytes`, consuming them.
+fn starts_with(reader: &mut impl BufRead, mut bytes: &[u8]) -> io::Result<bool> {
+    while !bytes.is_empty() {
+        let available = reader.fill_buf()?;
+        let n = available.len().min(bytes.len());
+        if n == 0 || available[..n] != bytes[..n] {
+            return Ok(false);
+        }
+        reader.consume(n);
+        bytes = &bytes[n..];
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::utils::fnv1a;
+
+    fn write(path: &Path, pieces: &[&str]) -> (bool, u64) {
+        let mut file = OutputFile::create(path).unwrap();
+        for piece in pieces {
+            file.write_all(piece.as_bytes()).unwrap();
+        }
+        file.finish().unwrap()
+    }
+
+    #[test]
+    fn test_only_changed_content_is_written() {
+        let dir = std::env::temp_dir().join(format!("oml_output_file_{}", std::process::id()));
+        let path = dir.join("nested").join("car.h");
+
+        assert_eq!(write(&path, &["struct Car", " {};\n"]), (true, fnv1a(b"struct Car {};\n")));
+        assert_eq!(write(&path, &["struct Car {};", "\n"]), (false, fnv1a(b"struct Car {};\n")));
+        // a prefix of the old content, then a longer one
+        assert!(write(&path, &["struct Car"]).0);
+        assert!(write(&path, &["struct Car", " {};\n"]).0);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "struct Car {};\n");
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
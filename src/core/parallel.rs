@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::ops::ControlFlow;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Runs `work` on each of `items` on one worker thread per core and hands
+/// the results to `report` in the order of `items`, each as soon as those
+/// before it are in.
+///
+/// A worker takes the next item as soon as it is done with the last, so
+/// `items` can be the receiving end of a channel another thread is still
+/// filling. `report` can stop the run; items already taken are still
+/// finished and reported.
+pub fn for_each_in_order<T: Send, R: Send>(
+    items: impl Iterator<Item = T> + Send,
+    work: impl Fn(T) -> R + Sync,
+    mut report: impl FnMut(R) -> ControlFlow<()>,
+) {
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let items = Mutex::new(items.enumerate());
+    let stopped = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let (items, stopped, work) = (&items, &stopped, &work);
+            scope.spawn(move || {
+                while !stopped.load(Ordering::Relaxed) {
+                    // the lock is held only while waiting for the next item
+                    let next = items.lock().expect("another worker panicked").next();
+                    let Some((index, item)) = next else {
+                        break;
+                    };
+                    if stopped.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let _ = sender.send((index, work(item)));
+                }
+            });
+        }
+        drop(sender);
+
+        // results in ahead of those of earlier items
+        let mut waiting = BTreeMap::new();
+        let mut expected = 0;
+        for (index, result) in receiver {
+            waiting.insert(index, result);
+            while let Some(result) = waiting.remove(&expected) {
+                expected += 1;
+                if report(result).is_break() {
+                    stopped.store(true, Ordering::Relaxed);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_come_in_order_while_items_still_arrive() {
+        let (sender, receiver) = mpsc::channel();
+        let mut reported = Vec::new();
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                for i in 0..50u64 {
+                    sender.send(i).unwrap();
+                }
+            });
+            for_each_in_order(receiver.into_iter(), |i| {
+                // later items tend to finish first
+                thread::sleep(std::time::Duration::from_micros(50 - i));
+                i * 2
+            }, |result| {
+                reported.push(result);
+                ControlFlow::Continue(())
+            });
+        });
+        assert_eq!(reported, (0..50).map(|i| i * 2).collect::<Vec<_>>());
+
+        let mut reported = Vec::new();
+        for_each_in_order(0..1000, |i| i, |result| {
+            reported.push(result);
+            if result == 3 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        });
+        // items taken before the stop are still reported, in order
+        assert!(reported.len() >= 4);
+        assert!(reported.iter().copied().eq(0..reported.len()));
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+
+/// Whether a change can break consumers generated from the old schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+    Breaking,
+    Compatible,
+}
+
+/// One difference between two versions of the model set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaChange {
+    pub compatibility: Compatibility,
+    pub object: String,
+    pub field: Option<String>,
+    pub description: String,
+}
+
+impl SchemaChange {
+    fn new(compatibility: Compatibility, object: &str, field: Option<&str>, description: String) -> Self {
+        Self {
+            compatibility,
+            object: object.to_string(),
+            field: field.map(str::to_string),
+            description,
+        }
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility == Compatibility::Breaking
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = match self.compatibility {
+            Compatibility::Breaking => "BREAKING",
+            Compatibility::Compatible => "compatible",
+        };
+        match &self.field {
+            Some(field) => write!(f, "{:<10} {}.{}: {}", label, self.object, field, self.description),
+            None => write!(f, "{:<10} {}: {}", label, self.object, self.description),
+        }
+    }
+}
+
+/// Compares two model sets object by object (matched by name) and returns
+/// every added, removed or changed object and field. Objects keep the order
+/// of `old`, followed by the objects only present in `new`.
+pub fn diff_schemas(old: &[OmlObject], new: &[OmlObject]) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    let new_by_name: HashMap<&str, &OmlObject> = new.iter().map(|o| (o.name.as_str(), o)).collect();
+    let old_by_name: HashMap<&str, &OmlObject> = old.iter().map(|o| (o.name.as_str(), o)).collect();
+
+    for old_obj in old {
+        match new_by_name.get(old_obj.name.as_str()) {
+            Some(new_obj) => diff_object(old_obj, new_obj, &mut changes),
+            None => changes.push(SchemaChange::new(
+                Compatibility::Breaking,
+                &old_obj.name,
+                None,
+                format!("{} removed", kind_name(&old_obj.oml_type)),
+            )),
+        }
+    }
+
+    for new_obj in new {
+        if !old_by_name.contains_key(new_obj.name.as_str()) {
+            changes.push(SchemaChange::new(
+                Compatibility::Compatible,
+                &new_obj.name,
+                None,
+                format!("{} added", kind_name(&new_obj.oml_type)),
+            ));
+        }
+    }
+
+    changes
+}
+
+fn kind_name(oml_type: &ObjectType) -> &'static str {
+    match oml_type {
+        ObjectType::ENUM => "enum",
+        ObjectType::CLASS => "class",
+        ObjectType::STRUCT => "struct",
+        ObjectType::UNDECIDED => "object",
+    }
+}
+
+fn diff_object(old: &OmlObject, new: &OmlObject, changes: &mut Vec<SchemaChange>) {
+    if old.oml_type != new.oml_type {
+        changes.push(SchemaChange::new(
+            Compatibility::Breaking,
+            &old.name,
+            None,
+            format!("changed from {} to {}", kind_name(&old.oml_type), kind_name(&new.oml_type)),
+        ));
+        return;
+    }
+
+    if old.oml_type == ObjectType::ENUM {
+        diff_enum(old, new, changes);
+    } else {
+        diff_fields(old, new, changes);
+    }
+}
+
+fn diff_enum(old: &OmlObject, new: &OmlObject, changes: &mut Vec<SchemaChange>) {
+    let old_names: Vec<&str> = old.variables.iter().map(|v| v.name.as_str()).collect();
+    let new_names: Vec<&str> = new.variables.iter().map(|v| v.name.as_str()).collect();
+
+    for name in &old_names {
+        if !new_names.contains(name) {
+            changes.push(SchemaChange::new(Compatibility::Breaking, &old.name, Some(name), "variant removed".to_string()));
+        }
+    }
+    for name in &new_names {
+        if !old_names.contains(name) {
+            changes.push(SchemaChange::new(Compatibility::Compatible, &old.name, Some(name), "variant added".to_string()));
+        }
+    }
+
+    // A surviving variant keeps its number unless it is given another one,
+    // or its position changes where it has none.
+    let number = |var: Option<&Variable>| var.and_then(|v| v.value.clone()).unwrap_or_else(|| "its position".to_string());
+    for old_var in &old.variables {
+        let new_var = new.variables.iter().find(|v| v.name == old_var.name);
+        if new_var.is_some_and(|new_var| new_var.value != old_var.value) {
+            changes.push(SchemaChange::new(
+                Compatibility::Breaking,
+                &old.name,
+                Some(&old_var.name),
+                format!("number changed from {} to {}", number(Some(old_var)), number(new_var)),
+            ));
+        }
+    }
+
+    // Several generators number variants by position even where OML gives
+    // them numbers, so moving a surviving variant still changes its value.
+    let kept_old: Vec<&&str> = old_names.iter().filter(|n| new_names.contains(n)).collect();
+    let kept_new: Vec<&&str> = new_names.iter().filter(|n| old_names.contains(n)).collect();
+    if kept_old != kept_new {
+        changes.push(SchemaChange::new(
+            Compatibility::Breaking,
+            &old.name,
+            None,
+            "variants reordered (positional values change)".to_string(),
+        ));
+    }
+}
+
+fn diff_fields(old: &OmlObject, new: &OmlObject, changes: &mut Vec<SchemaChange>) {
+    for old_var in &old.variables {
+        match new.variables.iter().find(|v| v.name == old_var.name) {
+            Some(new_var) => diff_field(&old.name, old_var, new_var, changes),
+            None => changes.push(SchemaChange::new(
+                Compatibility::Breaking,
+                &old.name,
+                Some(&old_var.name),
+                "field removed".to_string(),
+            )),
+        }
+    }
+
+    for new_var in &new.variables {
+        if old.variables.iter().any(|v| v.name == new_var.name) {
+            continue;
+        }
+        // A new required instance field must be supplied by every existing
+        // constructor call; optional and static fields need nothing.
+        let (compatibility, description) = if new_var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            (Compatibility::Compatible, "optional field added")
+        } else if new_var.var_mod.contains(&VariableModifier::STATIC) {
+            (Compatibility::Compatible, "static field added")
+        } else {
+            (Compatibility::Breaking, "required field added")
+        };
+        changes.push(SchemaChange::new(compatibility, &old.name, Some(&new_var.name), description.to_string()));
+    }
+}
+
+fn type_string(var: &Variable) -> String {
+    match &var.array_kind {
+        ArrayKind::None => var.var_type.clone(),
+        ArrayKind::Static(n) => format!("{}[{}]", var.var_type, n),
+        ArrayKind::Dynamic => format!("list {}", var.var_type),
+    }
+}
+
+fn visibility_rank(visibility: &VariableVisibility) -> u8 {
+    match visibility {
+        VariableVisibility::PRIVATE => 0,
+        VariableVisibility::PROTECTED => 1,
+        VariableVisibility::PUBLIC => 2,
+    }
+}
+
+fn diff_field(object: &str, old: &Variable, new: &Variable, changes: &mut Vec<SchemaChange>) {
+    let field = Some(old.name.as_str());
+    let has = |var: &Variable, m: VariableModifier| var.var_mod.contains(&m);
+
+    let (old_type, new_type) = (type_string(old), type_string(new));
+    if old_type != new_type {
+        changes.push(SchemaChange::new(
+            Compatibility::Breaking,
+            object,
+            field,
+            format!("type changed from '{}' to '{}'", old_type, new_type),
+        ));
+    }
+
+    match (has(old, VariableModifier::OPTIONAL), has(new, VariableModifier::OPTIONAL)) {
+        (true, false) => changes.push(SchemaChange::new(Compatibility::Breaking, object, field, "became required".to_string())),
+        (false, true) => changes.push(SchemaChange::new(Compatibility::Breaking, object, field, "became optional (readers may now see no value)".to_string())),
+        _ => {}
+    }
+
+    if has(old, VariableModifier::STATIC) != has(new, VariableModifier::STATIC) {
+        let description = if has(new, VariableModifier::STATIC) { "became static" } else { "is no longer static" };
+        changes.push(SchemaChange::new(Compatibility::Breaking, object, field, description.to_string()));
+    }
+
+    let old_const = old.is_const();
+    let new_const = new.is_const();
+    match (old_const, new_const) {
+        (false, true) => changes.push(SchemaChange::new(Compatibility::Breaking, object, field, "became const (setters removed)".to_string())),
+        (true, false) => changes.push(SchemaChange::new(Compatibility::Compatible, object, field, "is no longer const".to_string())),
+        _ => {}
+    }
+
+    let (old_rank, new_rank) = (visibility_rank(&old.visibility), visibility_rank(&new.visibility));
+    if new_rank < old_rank {
+        changes.push(SchemaChange::new(Compatibility::Breaking, object, field, "visibility narrowed".to_string()));
+    } else if new_rank > old_rank {
+        changes.push(SchemaChange::new(Compatibility::Compatible, object, field, "visibility widened".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Vec<OmlObject> {
+        OmlObject::scan_file(content.to_string()).unwrap()
+    }
+
+    fn find<'a>(changes: &'a [SchemaChange], object: &str, field: Option<&str>) -> &'a SchemaChange {
+        changes
+            .iter()
+            .find(|c| c.object == object && c.field.as_deref() == field)
+            .unwrap_or_else(|| panic!("no change for {}.{:?} in {:?}", object, field, changes))
+    }
+
+    #[test]
+    fn test_identical_schemas_have_no_changes() {
+        let schema = "class Person {\n string name;\n optional int32 age;\n}\n";
+        assert!(diff_schemas(&parse(schema), &parse(schema)).is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed_objects() {
+        let old = parse("class A {\n string x;\n}\n");
+        let new = parse("class B {\n string x;\n}\n");
+        let changes = diff_schemas(&old, &new);
+
+        assert_eq!(changes.len(), 2);
+        assert!(find(&changes, "A", None).is_breaking());
+        assert!(!find(&changes, "B", None).is_breaking());
+    }
+
+    #[test]
+    fn test_field_additions_classified_by_requiredness() {
+        let old = parse("class Person {\n string name;\n}\n");
+        let new = parse("class Person {\n string name;\n int32 age;\n optional string nickname;\n static int32 count;\n}\n");
+        let changes = diff_schemas(&old, &new);
+
+        assert!(find(&changes, "Person", Some("age")).is_breaking());
+        assert!(!find(&changes, "Person", Some("nickname")).is_breaking());
+        assert!(!find(&changes, "Person", Some("count")).is_breaking());
+    }
+
+    #[test]
+    fn test_removed_and_retyped_fields_are_breaking() {
+        let old = parse("class Person {\n string name;\n int32 age;\n list string tags;\n}\n");
+        let new = parse("class Person {\n int64 age;\n string[2] tags;\n}\n");
+        let changes = diff_schemas(&old, &new);
+
+        assert!(find(&changes, "Person", Some("name")).is_breaking());
+        let age = find(&changes, "Person", Some("age"));
+        assert!(age.is_breaking());
+        assert!(age.description.contains("'int32' to 'int64'"), "Got: {}", age.description);
+        let tags = find(&changes, "Person", Some("tags"));
+        assert!(tags.description.contains("'list string' to 'string[2]'"), "Got: {}", tags.description);
+    }
+
+    #[test]
+    fn test_modifier_and_visibility_changes() {
+        let old = parse("class P {\n public const int32 a;\n int32 b;\n}\n");
+        let new = parse("class P {\n int32 a;\n public const int32 b;\n}\n");
+        let changes = diff_schemas(&old, &new);
+
+        let a: Vec<_> = changes.iter().filter(|c| c.field.as_deref() == Some("a")).collect();
+        assert!(a.iter().any(|c| c.description == "is no longer const" && !c.is_breaking()));
+        assert!(a.iter().any(|c| c.description == "visibility narrowed" && c.is_breaking()));
+
+        let b: Vec<_> = changes.iter().filter(|c| c.field.as_deref() == Some("b")).collect();
+        assert!(b.iter().any(|c| c.description.starts_with("became const") && c.is_breaking()));
+        assert!(b.iter().any(|c| c.description == "visibility widened" && !c.is_breaking()));
+    }
+
+    #[test]
+    fn test_enum_variant_changes() {
+        let old = parse("enum Color {\n string Red;\n string Green;\n string Blue;\n}\n");
+        let new = parse("enum Color {\n string Green;\n string Red;\n string Yellow;\n}\n");
+        let changes = diff_schemas(&old, &new);
+
+        assert!(find(&changes, "Color", Some("Blue")).is_breaking());
+        assert!(!find(&changes, "Color", Some("Yellow")).is_breaking());
+        assert!(find(&changes, "Color", None).description.contains("reordered"));
+    }
+
+    #[test]
+    fn test_enum_numbers_are_compared_as_well_as_positions() {
+        let old = parse("enum Status {\n string Ok = 200;\n string Missing = 404;\n string Gone = 410;\n}\n");
+        let new = parse("enum Status {\n string Ok = 200;\n string Missing = 404;\n string Gone = 401;\n}\n");
+        let changes = diff_schemas(&old, &new);
+
+        assert_eq!(changes.len(), 1, "Got: {:?}", changes);
+        assert_eq!(changes[0].to_string(), "BREAKING   Status.Gone: number changed from 410 to 401");
+
+        let new = parse("enum Status {\n string Missing = 404;\n string Ok = 200;\n string Gone = 410;\n}\n");
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(changes.len(), 1, "Got: {:?}", changes);
+        assert!(changes[0].is_breaking());
+        assert!(changes[0].description.contains("reordered"));
+
+        let new = parse("enum Status {\n string Ok;\n string Missing;\n string Gone;\n}\n");
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(find(&changes, "Status", Some("Ok")).description, "number changed from 200 to its position");
+    }
+
+    #[test]
+    fn test_kind_change_is_breaking() {
+        let old = parse("class Point {\n int32 x;\n}\n");
+        let new = parse("struct Point {\n int32 x;\n}\n");
+        let changes = diff_schemas(&old, &new);
+
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].is_breaking());
+        assert_eq!(changes[0].to_string(), "BREAKING   Point: changed from class to struct");
+    }
+}
@@ -0,0 +1,90 @@
+use crate::core::oml_object::{ArrayKind, ObjectType, OmlObject, VariableModifier};
+use crate::core::utils::{fnv1a, to_snake_case};
+
+/// The name of the constant generated classes hold their fingerprint in.
+pub const SCHEMA_HASH: &str = "SCHEMA_HASH";
+
+/// A fingerprint of an object's definition: its kind and name, then the
+/// name, type, array kind and `static`/`optional` modifiers of each field in
+/// order, and the number of each enum variant that has one. Processes generated from different versions of a model can compare
+/// it at runtime.
+///
+/// The hash is 64-bit FNV-1a over a canonical text, so it is the same on
+/// every platform and release. Names enter it in snake_case, which keeps it
+/// equal across targets whatever casing `--naming` gives them; docs, tags,
+/// visibility, `const`/`mut` and the values of fields are left out.
+pub fn schema_hash(object: &OmlObject) -> u64 {
+    let kind = match object.oml_type {
+        ObjectType::ENUM => "enum",
+        ObjectType::CLASS => "class",
+        ObjectType::STRUCT => "struct",
+        ObjectType::UNDECIDED => "object",
+    };
+    let mut canonical = format!("{} {}\n", kind, to_snake_case(&object.name));
+    for var in &object.variables {
+        if var.var_mod.contains(&VariableModifier::STATIC) {
+            canonical.push_str("static ");
+        }
+        if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            canonical.push_str("optional ");
+        }
+        let var_type = to_snake_case(&var.var_type);
+        let var_type = match var.array_kind {
+            ArrayKind::None => var_type,
+            ArrayKind::Static(n) => format!("{}[{}]", var_type, n),
+            ArrayKind::Dynamic => format!("list {}", var_type),
+        };
+        canonical.push_str(&format!("{} {}", var_type, to_snake_case(&var.name)));
+        if object.oml_type == ObjectType::ENUM && let Some(value) = &var.value {
+            canonical.push_str(&format!(" = {}", value));
+        }
+        canonical.push('\n');
+    }
+    fnv1a(canonical.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::oml_object::Variable;
+
+    fn car(fields: &[(&str, &str)]) -> OmlObject {
+        OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "RaceCar".to_string(),
+            variables: fields
+                .iter()
+                .map(|(var_type, name)| Variable { var_type: var_type.to_string(), name: name.to_string(), ..Default::default() })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hash_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(schema_hash(&car(&[("uint16", "year")])), fnv1a(b"class race_car\nuint16 year\n"));
+    }
+
+    #[test]
+    fn test_hash_follows_the_definition_not_the_casing() {
+        let hash = schema_hash(&car(&[("uint16", "topSpeed"), ("string", "name")]));
+        assert_eq!(hash, schema_hash(&OmlObject { name: "RACE_CAR".to_string(), ..car(&[("uint16", "top_speed"), ("string", "name")]) }));
+
+        assert_ne!(hash, schema_hash(&car(&[("string", "name"), ("uint16", "topSpeed")])));
+        assert_ne!(hash, schema_hash(&car(&[("uint32", "topSpeed"), ("string", "name")])));
+        assert_ne!(hash, schema_hash(&OmlObject { oml_type: ObjectType::STRUCT, ..car(&[("uint16", "topSpeed"), ("string", "name")]) }));
+    }
+
+    #[test]
+    fn test_hash_covers_variant_numbers_only() {
+        let numbered = |oml_type: ObjectType, value: Option<&str>| OmlObject {
+            oml_type,
+            variables: vec![Variable { var_type: "int32".to_string(), name: "red".to_string(), value: value.map(str::to_string), ..Default::default() }],
+            ..car(&[])
+        };
+        assert_ne!(schema_hash(&numbered(ObjectType::ENUM, None)), schema_hash(&numbered(ObjectType::ENUM, Some("1"))));
+        assert_eq!(schema_hash(&numbered(ObjectType::CLASS, None)), schema_hash(&numbered(ObjectType::CLASS, Some("1"))));
+    }
+}
@@ -0,0 +1,36 @@
+//! Builders for the objects the generator tests run on.
+
+use crate::core::generate::Generate;
+use crate::core::oml_object::{ArrayKind, OmlObject, Variable, VariableModifier, VariableVisibility};
+
+/// A field with the default visibility.
+pub fn var(name: &str, var_type: &str, var_mod: Vec<VariableModifier>, array_kind: ArrayKind) -> Variable {
+    Variable {
+        var_mod,
+        var_type: var_type.to_string(),
+        array_kind,
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+/// A field of the given visibility.
+pub fn member(
+    name: &str,
+    var_type: &str,
+    visibility: VariableVisibility,
+    var_mod: Vec<VariableModifier>,
+    array_kind: ArrayKind,
+) -> Variable {
+    Variable { visibility, ..var(name, var_type, var_mod, array_kind) }
+}
+
+/// What `generator` makes of `oml_object` alone, as `test.oml`.
+pub fn generated(generator: &impl Generate, oml_object: &OmlObject) -> String {
+    generated_all(generator, std::slice::from_ref(oml_object))
+}
+
+/// What `generator` makes of `oml_objects`, as `test.oml`.
+pub fn generated_all(generator: &impl Generate, oml_objects: &[OmlObject]) -> String {
+    generator.generate(oml_objects, "test").unwrap()
+}
@@ -1,2 +1,4 @@
+pub(crate) mod fixtures;
 mod test_oml;
-mod test_imports;
\ No newline at end of file
+mod test_imports;
+mod test_generate;
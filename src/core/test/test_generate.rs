@@ -0,0 +1,32 @@
+use clap::Parser;
+
+use crate::cli::oml::OmlCli;
+use crate::core::oml_object::{ObjectType, OmlObject};
+
+/// Every language flag, so that `generators()` returns one of each.
+const LANGUAGES: [&str; 28] = [
+    "--c", "--cpp", "--python", "--java", "--kotlin", "--rust", "--typescript", "--sql", "--go",
+    "--csharp", "--dart", "--php", "--ruby", "--scala", "--haskell", "--zig", "--lua", "--julia",
+    "--elixir", "--fsharp", "--ocaml", "--proto", "--jsonschema", "--graphql", "--openapi",
+    "--javascript", "--gdscript", "--docs",
+];
+
+// ── UNDECIDED objects ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_undecided_object_type_returns_error() {
+    let obj = OmlObject { oml_type: ObjectType::UNDECIDED, name: "Bad".to_string(), ..Default::default() };
+
+    for extra in [None, Some("--cpp-unreal")] {
+        let args = ["oml", "bad.oml"].into_iter().chain(LANGUAGES).chain(extra);
+        let cli = OmlCli::try_parse_from(args).unwrap();
+        let generators = cli.get_generators();
+        assert_eq!(generators.len(), LANGUAGES.len());
+        for generator in &generators {
+            assert!(
+                generator.generate(std::slice::from_ref(&obj), "bad").is_err(),
+                "{} generated an UNDECIDED object", generator.extension()
+            );
+        }
+    }
+}
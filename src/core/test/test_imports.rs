@@ -50,6 +50,16 @@ class Plain {
     assert!(imports.is_empty());
 }
 
+#[test]
+fn test_reading_a_file_matches_scanning_its_text() {
+    let path = Path::new("src/core/test/oml_files/car.oml");
+    let content = std::fs::read_to_string(path).unwrap();
+    assert_eq!(
+        OmlObject::get_from_file(path).unwrap(),
+        OmlObject::scan_file_with_imports(content).unwrap()
+    );
+}
+
 // ── validate_custom_types with imports ───────────────────────────────────────
 
 #[test]
@@ -0,0 +1,151 @@
+use crate::core::oml_object::OmlObject;
+use std::str::FromStr;
+
+/// The targets whose generators honour `--type-map`.
+pub const TYPE_MAP_TARGETS: [&str; 3] = ["cpp", "kotlin", "python"];
+
+/// A target type standing in for an OML type, with the include or import
+/// that brings it into scope: a header such as `<QString>` for C++, a fully
+/// qualified name for Kotlin and a module for Python.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeOverride {
+    pub target_type: String,
+    pub include: Option<String>,
+}
+
+/// One `--type-map` argument, `TARGET:OML_TYPE=TYPE[@INCLUDE]`, e.g.
+/// `cpp:string=QString@<QString>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMapping {
+    pub target: String,
+    pub oml_type: String,
+    pub type_override: TypeOverride,
+}
+
+impl FromStr for TypeMapping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected TARGET:OML_TYPE=TYPE[@INCLUDE], got `{}`", s);
+        let (target, rest) = s.split_once(':').ok_or_else(invalid)?;
+        let (oml_type, rest) = rest.split_once('=').ok_or_else(invalid)?;
+        let (target_type, include) = match rest.split_once('@') {
+            Some((target_type, include)) => (target_type, Some(include.trim().to_string())),
+            None => (rest, None),
+        };
+        let (target, oml_type, target_type) = (target.trim(), oml_type.trim(), target_type.trim());
+        if oml_type.is_empty() || target_type.is_empty() || include.as_deref() == Some("") {
+            return Err(invalid());
+        }
+        if !TYPE_MAP_TARGETS.contains(&target) {
+            return Err(format!("type maps are supported for {}, not `{}`", TYPE_MAP_TARGETS.join(", "), target));
+        }
+
+        Ok(TypeMapping {
+            target: target.to_string(),
+            oml_type: oml_type.to_string(),
+            type_override: TypeOverride { target_type: target_type.to_string(), include },
+        })
+    }
+}
+
+/// The type overrides of one target. A later override of the same OML type
+/// replaces an earlier one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypeMap {
+    overrides: Vec<(String, TypeOverride)>,
+}
+
+impl TypeMap {
+    pub fn new<'a>(mappings: impl IntoIterator<Item = &'a TypeMapping>) -> Self {
+        let mut map = TypeMap::default();
+        for mapping in mappings {
+            map.insert(&mapping.oml_type, mapping.type_override.clone());
+        }
+        map
+    }
+
+    pub fn insert(&mut self, oml_type: &str, type_override: TypeOverride) {
+        self.overrides.retain(|(t, _)| t != oml_type);
+        self.overrides.push((oml_type.to_string(), type_override));
+    }
+
+    pub fn get(&self, oml_type: &str) -> Option<&TypeOverride> {
+        self.overrides.iter().find(|(t, _)| t == oml_type).map(|(_, o)| o)
+    }
+
+    /// Copies of `objects` whose fields of a mapped type have the target
+    /// type instead, which generators pass through like any other type name.
+    pub fn apply(&self, objects: &[OmlObject]) -> Vec<OmlObject> {
+        let mut objects = objects.to_vec();
+        if self.overrides.is_empty() {
+            return objects;
+        }
+        for var in objects.iter_mut().flat_map(|o| &mut o.variables) {
+            if let Some(type_override) = self.get(&var.var_type) {
+                var.var_type = type_override.target_type.clone();
+            }
+        }
+        objects
+    }
+
+    /// The overrides `objects` use that need an include, in the order they
+    /// were given and each once.
+    pub fn used_includes(&self, objects: &[OmlObject]) -> Vec<&TypeOverride> {
+        self.overrides
+            .iter()
+            .filter(|(oml_type, _)| objects.iter().flat_map(|o| &o.variables).any(|v| &v.var_type == oml_type))
+            .map(|(_, type_override)| type_override)
+            .filter(|type_override| type_override.include.is_some())
+            .collect()
+    }
+
+    /// The target types standing in for OML types.
+    pub fn target_types(&self) -> impl Iterator<Item = &str> {
+        self.overrides.iter().map(|(_, o)| o.target_type.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::oml_object::{ObjectType, Variable};
+
+    #[test]
+    fn test_parse_mapping() {
+        let mapping: TypeMapping = "cpp:string=QString@<QString>".parse().unwrap();
+        assert_eq!(mapping.target, "cpp");
+        assert_eq!(mapping.oml_type, "string");
+        assert_eq!(mapping.type_override, TypeOverride { target_type: "QString".to_string(), include: Some("<QString>".to_string()) });
+
+        let mapping: TypeMapping = "python:double=float".parse().unwrap();
+        assert_eq!(mapping.type_override.include, None);
+
+        assert!("cpp:string".parse::<TypeMapping>().is_err());
+        assert!("cpp:string=QString@".parse::<TypeMapping>().is_err());
+        assert!("haskell:string=Text".parse::<TypeMapping>().unwrap_err().contains("cpp, kotlin, python"));
+    }
+
+    #[test]
+    fn test_apply_and_used_includes() {
+        let var = |name: &str, var_type: &str| Variable { name: name.to_string(), var_type: var_type.to_string(), ..Default::default() };
+        let objects = [OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Invoice".to_string(),
+            variables: vec![var("total", "double"), var("note", "string")],
+            ..Default::default()
+        }];
+        let mappings: Vec<TypeMapping> = ["cpp:double=Money@\"money.h\"", "cpp:int64=qint64@<QtGlobal>", "cpp:double=Decimal@<decimal>"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let map = TypeMap::new(&mappings);
+
+        let mapped = map.apply(&objects);
+        assert_eq!(mapped[0].variables[0].var_type, "Decimal");
+        assert_eq!(mapped[0].variables[1].var_type, "string");
+
+        let includes: Vec<&str> = map.used_includes(&objects).iter().filter_map(|o| o.include.as_deref()).collect();
+        assert_eq!(includes, ["<decimal>"]);
+    }
+}
@@ -31,4 +31,78 @@ macro_rules! data_struct_init {
             pub fn new(use_data_class: bool) -> Self { Self { use_data_class } }
         }
     };
-}
\ No newline at end of file
+}
+
+/// 64-bit FNV-1a, a hash that is the same on every platform and release,
+/// unlike `std`'s `DefaultHasher`.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    fnv1a_update(FNV1A_OFFSET_BASIS, bytes)
+}
+
+/// The FNV-1a hash of no bytes, where hashing in pieces starts.
+pub const FNV1A_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// `hash` carried on over `bytes`, so content written in pieces hashes the
+/// same as `fnv1a` of all of it.
+pub fn fnv1a_update(hash: u64, bytes: &[u8]) -> u64 {
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
+
+/// Whether `name` is an ASCII letter or `_` followed by letters, digits
+/// and `_`, an identifier in every language target.
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Turns `max_speed`, `maxSpeed` or `MAX_SPEED` into `MaxSpeed`, the
+/// casing Go, C# and similar targets use for public members.
+pub fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let part = if part.chars().all(|c| !c.is_lowercase()) {
+                part.to_lowercase()
+            } else {
+                part.to_string()
+            };
+            let mut chars = part.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(c) => c.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        })
+        .collect()
+}
+
+/// Turns `max_speed` or `MAX_SPEED` into `maxSpeed`.
+pub fn to_camel_case(name: &str) -> String {
+    let pascal = to_pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(c) => c.to_lowercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+/// Turns `MaxSpeed`, `maxSpeed` or `MAX_SPEED` into `max_speed`.
+pub fn to_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    let chars: Vec<char> = name.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let prev_upper = i > 0 && chars[i - 1].is_uppercase();
+            if !out.is_empty() && !out.ends_with('_') && (prev_lower || (prev_upper && next_lower)) {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,183 @@
+use std::env;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::core::diagnostics::{self, Diagnostic};
+
+/// An external check of generated files, chosen with `--verify`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum VerifyTarget {
+    /// Every --cpp header through `$CXX`, `clang++` or `g++` with
+    /// `-fsyntax-only`, adding the arguments in `$CXXFLAGS` and
+    /// --verify-flags
+    Cpp,
+}
+
+/// An error the check found, at a line of the checked file when it says.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckError {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// The checks asked for, with the tool found to run each and the extra
+/// arguments to give it.
+pub struct Verifier {
+    tools: Vec<(VerifyTarget, String)>,
+    flags: Vec<String>,
+}
+
+impl Verifier {
+    /// Looks for a tool for each of `targets`, warning about those that
+    /// have none; their files then go unchecked. `flags` are passed to every
+    /// tool, such as the include paths of the libraries the files use.
+    pub fn new(targets: &[VerifyTarget], flags: &str) -> Self {
+        let mut tools = Vec::new();
+        for &target in targets {
+            match target.find_tool() {
+                Some(tool) => tools.push((target, tool)),
+                None => diagnostics::emit(&Diagnostic::warning(format!(
+                    "--verify {}: no compiler found (set $CXX, or install clang++ or g++); its files are not checked",
+                    target.name()
+                ))),
+            }
+        }
+        Self { tools, flags: flags.split_whitespace().map(str::to_string).collect() }
+    }
+
+    /// Checks the file at `path`, returning what is wrong with it, or
+    /// `None` if `target` has no tool or does not cover the file.
+    pub fn check(&self, target: VerifyTarget, path: &Path) -> Option<io::Result<Vec<CheckError>>> {
+        let (_, tool) = self.tools.iter().find(|(t, _)| *t == target)?;
+        if !target.covers(path) {
+            return None;
+        }
+        Some(target.run(tool, &self.flags, path))
+    }
+}
+
+impl VerifyTarget {
+    fn name(self) -> &'static str {
+        match self {
+            VerifyTarget::Cpp => "cpp",
+        }
+    }
+
+    fn find_tool(self) -> Option<String> {
+        match self {
+            VerifyTarget::Cpp => env::var("CXX").ok().into_iter().chain(["clang++".to_string(), "g++".to_string()]).find(|tool| {
+                Command::new(tool).arg("--version").output().is_ok_and(|out| out.status.success())
+            }),
+        }
+    }
+
+    /// Whether files at `path` are checked; sources that need more than the
+    /// standard library, such as the gtest and pybind11 ones, are not.
+    fn covers(self, path: &Path) -> bool {
+        match self {
+            VerifyTarget::Cpp => path.extension().is_some_and(|ext| ext == "h"),
+        }
+    }
+
+    /// Pipes a translation unit including the header to the compiler, so
+    /// that it is checked the way a consumer sees it.
+    fn run(self, tool: &str, flags: &[String], path: &Path) -> io::Result<Vec<CheckError>> {
+        let path = path.canonicalize()?;
+        let mut command = Command::new(tool);
+        command.args(["-x", "c++", "-std=c++17", "-fsyntax-only"]);
+        if let Ok(flags) = env::var("CXXFLAGS") {
+            command.args(flags.split_whitespace());
+        }
+        command.args(flags);
+        let mut child = command
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "#include \"{}\"", path.display())?;
+        }
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        let mut errors = parse_compiler_errors(&String::from_utf8_lossy(&output.stderr), &path.to_string_lossy());
+        // a library header the file includes, such as nlohmann/json.hpp, is not on the include path
+        for error in &mut errors {
+            if error.message.ends_with("No such file or directory") || error.message.ends_with("file not found") {
+                error.message.push_str(" (give its include path with --verify-flags)");
+            }
+        }
+        if errors.is_empty() {
+            // failed without an error line we understand
+            let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Ok(vec![CheckError { line: None, message }]);
+        }
+        Ok(errors)
+    }
+}
+
+/// The `error:` lines of GCC or Clang output, with their line number when
+/// they are in the file at `path`.
+fn parse_compiler_errors(output: &str, path: &str) -> Vec<CheckError> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (location, message) = line.split_once(": error: ").or_else(|| line.split_once(": fatal error: "))?;
+            let line = location
+                .strip_prefix(path)
+                .and_then(|rest| rest.strip_prefix(':'))
+                .and_then(|rest| rest.split(':').next())
+                .and_then(|number| number.parse().ok());
+            Some(CheckError { line, message: message.to_string() })
+        })
+        .collect()
+}
+
+/// Which of `names` is declared last at or above `line` (1-based) of the
+/// C++ `content`, as the object an error on that line came from.
+pub fn declaring_object<'a>(content: &str, names: &[&'a str], line: usize) -> Option<&'a str> {
+    let mut object = None;
+    for text in content.lines().take(line) {
+        let text = text.trim_start();
+        let Some(rest) = ["enum class ", "enum ", "class ", "struct "].iter().find_map(|kind| text.strip_prefix(kind)) else {
+            continue;
+        };
+        if let Some(name) = names.iter().find(|name| {
+            rest.strip_prefix(**name).is_some_and(|after| !after.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_'))
+        }) {
+            object = Some(*name);
+        }
+    }
+    object
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_are_placed_in_the_object_declaring_them() {
+        let output = "\
+In file included from <stdin>:1:
+/out/car.h:9:14: error: unknown type name 'Engin'
+/out/car.h:9:14: note: did you mean 'Engine'?
+/usr/include/vector:3:1: error: somewhere else
+";
+        let errors = parse_compiler_errors(output, "/out/car.h");
+        assert_eq!(errors, vec![
+            CheckError { line: Some(9), message: "unknown type name 'Engin'".to_string() },
+            CheckError { line: None, message: "somewhere else".to_string() },
+        ]);
+
+        let content = "#pragma once\n\nenum class Color {\n\tRED\n};\n\nclass CarPart;\n\nclass Car {\n\tEngin engine;\n};\n";
+        let names = ["Color", "Car"];
+        assert_eq!(declaring_object(content, &names, 1), None);
+        assert_eq!(declaring_object(content, &names, 4), Some("Color"));
+        assert_eq!(declaring_object(content, &names, 8), Some("Color"));
+        assert_eq!(declaring_object(content, &names, 10), Some("Car"));
+    }
+}
@@ -0,0 +1,337 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_snake_case;
+use std::error::Error;
+use std::fmt::Write;
+
+/// How OML `string` fields are laid out in the generated structs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum CStringStrategy {
+    /// `char* name; size_t name_len;`
+    #[default]
+    PointerLength,
+    /// NUL-terminated `char* name;`
+    Pointer,
+    /// Inline `char name[N];` buffer, sized by the string capacity
+    Fixed,
+}
+
+pub struct CGenerator {
+    pub string_strategy: CStringStrategy,
+    /// Buffer size, including the terminating NUL, for `CStringStrategy::Fixed`
+    pub string_capacity: usize,
+    pub style: CodeStyle,
+}
+
+impl CGenerator {
+    pub fn new(string_strategy: CStringStrategy, string_capacity: usize) -> Self {
+        Self { string_strategy, string_capacity, style: CodeStyle::default() }
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Whether the field is represented by a pointer, in which case NULL
+    /// already means "absent" and no `has_` flag is needed.
+    fn is_pointer(&self, var: &Variable) -> bool {
+        match var.array_kind {
+            ArrayKind::Dynamic => true,
+            ArrayKind::Static(_) => false,
+            ArrayKind::None => var.var_type == "string" && self.string_strategy != CStringStrategy::Fixed,
+        }
+    }
+
+    /// The member declarations for one field; strings and dynamic arrays
+    /// take a second member holding their length.
+    fn members(&self, var: &Variable) -> Vec<String> {
+        let is_const = var.is_const();
+        let qualifier = if is_const { "const " } else { "" };
+        let name = &var.name;
+        let is_string = var.var_type == "string";
+        let elem = if is_string && var.array_kind != ArrayKind::None {
+            // arrays of strings always hold pointers
+            "char*".to_string()
+        } else {
+            convert_type(&var.var_type)
+        };
+
+        let mut members = Vec::new();
+        if var.var_mod.contains(&VariableModifier::OPTIONAL) && !self.is_pointer(var) {
+            members.push(format!("bool has_{};", name));
+        }
+
+        match (&var.array_kind, is_string) {
+            (ArrayKind::Static(n), _) => members.push(format!("{}{} {}[{}];", qualifier, elem, name, n)),
+            (ArrayKind::Dynamic, _) => {
+                members.push(format!("{}{}* {};", qualifier, elem, name));
+                members.push(format!("size_t {}_count;", name));
+            }
+            (ArrayKind::None, true) => match self.string_strategy {
+                CStringStrategy::PointerLength => {
+                    members.push(format!("{}char* {};", qualifier, name));
+                    members.push(format!("size_t {}_len;", name));
+                }
+                CStringStrategy::Pointer => members.push(format!("{}char* {};", qualifier, name)),
+                CStringStrategy::Fixed => {
+                    members.push(format!("{}char {}[{}];", qualifier, name, self.string_capacity))
+                }
+            },
+            (ArrayKind::None, false) => members.push(format!("{}{} {};", qualifier, elem, name)),
+        }
+
+        members
+    }
+}
+
+impl Default for CGenerator {
+    fn default() -> Self {
+        Self::new(CStringStrategy::default(), 64)
+    }
+}
+
+/// Words reserved in C, which fields are renamed away from.
+const KEYWORDS: [&str; 45] = [
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else", "enum",
+    "extern", "float", "for", "goto", "if", "inline", "int", "long", "register", "restrict", "return",
+    "short", "signed", "sizeof", "static", "struct", "switch", "typedef", "union", "unsigned", "void",
+    "volatile", "while", "bool", "true", "false", "alignas", "alignof", "constexpr", "nullptr",
+    "static_assert", "thread_local", "typeof", "typeof_unqual",
+];
+
+impl Generate for CGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut c_file = CodeWriter::new(self.style);
+        let header_guard = format!("{}_H", file_name.to_uppercase());
+
+        writeln!(c_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(c_file, "#ifndef {}", header_guard)?;
+        writeln!(c_file, "#define {}", header_guard)?;
+        writeln!(c_file)?;
+
+        let fields: Vec<&Variable> = oml_objects
+            .iter()
+            .filter(|o| o.oml_type != ObjectType::ENUM)
+            .flat_map(|o| &o.variables)
+            .collect();
+        let needs_bool = fields.iter().any(|v| {
+            v.var_type == "bool" || (v.var_mod.contains(&VariableModifier::OPTIONAL) && !self.is_pointer(v))
+        });
+        let needs_size = fields.iter().any(|v| {
+            v.array_kind == ArrayKind::Dynamic
+                || (v.var_type == "string" && self.string_strategy == CStringStrategy::PointerLength)
+        });
+        let needs_int = fields.iter().any(|v| v.var_type.starts_with("int") || v.var_type.starts_with("uint"));
+
+        if needs_bool { writeln!(c_file, "#include <stdbool.h>")?; }
+        if needs_size { writeln!(c_file, "#include <stddef.h>")?; }
+        if needs_int  { writeln!(c_file, "#include <stdint.h>")?; }
+        if needs_bool || needs_size || needs_int {
+            writeln!(c_file)?;
+        }
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut c_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => self.generate_struct(oml_object, &mut c_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(c_file)?;
+            }
+        }
+
+        writeln!(c_file)?;
+        writeln!(c_file, "#endif // {}", header_guard)?;
+
+        Ok(c_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "h"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+}
+
+impl CGenerator {
+    fn generate_struct(&self, oml_object: &OmlObject, c_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        // C structs have no static members; statics become extern globals
+        let (static_vars, field_vars): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+            .iter()
+            .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+        c_file.open_block(0, &format!("typedef struct {}", name))?;
+        if field_vars.is_empty() {
+            // an empty struct is a constraint violation in ISO C
+            writeln!(c_file, "{}char unused;", c_file.indent(1))?;
+        }
+        for var in &field_vars {
+            for member in self.members(var) {
+                writeln!(c_file, "{}{}", c_file.indent(1), member)?;
+            }
+        }
+        writeln!(c_file, "}} {};", name)?;
+
+        if !static_vars.is_empty() {
+            writeln!(c_file)?;
+            let prefix = to_snake_case(name);
+            for var in &static_vars {
+                for member in self.members(var) {
+                    writeln!(c_file, "extern {}", prefix_member(&member, &prefix))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Enum constants share one namespace in C, so they are prefixed with the
+/// enum name: `Color.dark_blue` becomes `COLOR_DARK_BLUE`.
+fn generate_enum(oml_object: &OmlObject, c_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let prefix = to_snake_case(&oml_object.name).to_uppercase();
+
+    c_file.open_block(0, &format!("typedef enum {}", oml_object.name))?;
+    let length = oml_object.variables.len();
+    for (i, var) in oml_object.variables.iter().enumerate() {
+        let separator = if i < length - 1 { "," } else { "" };
+        let constant = to_snake_case(&var.name).to_uppercase();
+        writeln!(c_file, "{}{}_{}{}", c_file.indent(1), prefix, constant, separator)?;
+    }
+    writeln!(c_file, "}} {};", oml_object.name)?;
+
+    Ok(())
+}
+
+/// Prefixes the declared name in a member declaration such as
+/// `size_t tags_count;` with `prefix_`.
+fn prefix_member(member: &str, prefix: &str) -> String {
+    let name_start = member
+        .rfind([' ', '*'])
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    format!("{}{}_{}", &member[..name_start], prefix, &member[name_start..])
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" => "int8_t".to_string(),
+        "int16" => "int16_t".to_string(),
+        "int32" => "int32_t".to_string(),
+        "int64" => "int64_t".to_string(),
+        "uint8" => "uint8_t".to_string(),
+        "uint16" => "uint16_t".to_string(),
+        "uint32" => "uint32_t".to_string(),
+        "uint64" => "uint64_t".to_string(),
+        "float" => "float".to_string(),
+        "double" => "double".to_string(),
+        "bool" => "bool".to_string(),
+        "string" => "char*".to_string(),
+        "char" => "char".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::var;
+
+    fn sensor() -> OmlObject {
+        OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Sensor".to_string(),
+            variables: vec![
+                var("id", "uint16", vec![VariableModifier::CONST], ArrayKind::None),
+                var("label", "string", vec![], ArrayKind::None),
+                var("readings", "float", vec![], ArrayKind::Static(8)),
+                var("history", "double", vec![], ArrayKind::Dynamic),
+                var("offset", "int32", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_struct_with_header_guard_and_includes() {
+        let out = CGenerator::default().generate(&[sensor()], "sensor").unwrap();
+        assert!(out.starts_with("// This file has been generated from sensor.oml\n#ifndef SENSOR_H\n#define SENSOR_H\n"));
+        assert!(out.contains("#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n"), "Got: {}", out);
+        assert!(out.contains(
+            "typedef struct Sensor {\n\
+             \tconst uint16_t id;\n\
+             \tchar* label;\n\
+             \tsize_t label_len;\n\
+             \tfloat readings[8];\n\
+             \tdouble* history;\n\
+             \tsize_t history_count;\n\
+             \tbool has_offset;\n\
+             \tint32_t offset;\n\
+             } Sensor;\n"
+        ), "Got: {}", out);
+        assert!(out.ends_with("#endif // SENSOR_H\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_string_strategies() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Tag".to_string(),
+            variables: vec![
+                var("name", "string", vec![], ArrayKind::None),
+                var("note", "string", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+
+        let out = CGenerator::new(CStringStrategy::Pointer, 64).generate(std::slice::from_ref(&obj), "t").unwrap();
+        assert!(out.contains("\tchar* name;\n\tchar* note;\n}"), "Got: {}", out);
+        assert!(!out.contains("#include"), "Got: {}", out);
+
+        let out = CGenerator::new(CStringStrategy::Fixed, 32).generate(std::slice::from_ref(&obj), "t").unwrap();
+        assert!(out.contains("\tchar name[32];\n\tbool has_note;\n\tchar note[32];\n}"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_constants_are_prefixed() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "GameState".to_string(),
+            variables: vec![
+                var("running", "string", vec![], ArrayKind::None),
+                var("GAME_OVER", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = CGenerator::default().generate(std::slice::from_ref(&obj), "state").unwrap();
+        assert!(out.contains(
+            "typedef enum GameState {\n\tGAME_STATE_RUNNING,\n\tGAME_STATE_GAME_OVER\n} GameState;\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_become_extern_globals() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "NetworkPacket".to_string(),
+            variables: vec![
+                var("max_size", "uint32", vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+                var("names", "string", vec![VariableModifier::STATIC], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        let out = CGenerator::default().generate(std::slice::from_ref(&obj), "packet").unwrap();
+        assert!(out.contains("typedef struct NetworkPacket {\n\tchar unused;\n} NetworkPacket;"), "Got: {}", out);
+        assert!(out.contains("extern const uint32_t network_packet_max_size;\n"), "Got: {}", out);
+        assert!(out.contains("extern char** network_packet_names;\nextern size_t network_packet_names_count;\n"), "Got: {}", out);
+    }
+}
@@ -0,0 +1,175 @@
+//! End-to-end check that generated code builds: every fixture OML file is
+//! generated for C++, Kotlin and Python and handed to the toolchains found on
+//! the machine. Behind the `compile-tests` feature, since it shells out:
+//!
+//! ```sh
+//! cargo test --features compile-tests compile_check -- --nocapture
+//! ```
+//!
+//! The C++ compiler is `$CXX` or the first of `c++`, `g++` and `clang++`;
+//! Kotlin needs `kotlinc` and Python `python3` or `python`. A missing
+//! toolchain skips its language. Failures list every fixture that did not
+//! compile with the compiler's output.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::core::generate::Generate;
+use crate::core::oml_object::OmlObject;
+use crate::generators::cpp::oml_cpp::CppGenerator;
+use crate::generators::kotlin::oml_kotlin::KotlinGenerator;
+use crate::generators::python::oml_python::PythonGenerator;
+
+const OUTPUT_DIR: &str = "test_results/compile";
+
+/// Directories whose `.oml` files are compiled.
+const FIXTURE_DIRS: [&str; 7] = [
+    "examples",
+    "src/generators/cpp/test_oml_files",
+    "src/generators/java/test_oml_files",
+    "src/generators/rust/test_oml_files",
+    "src/generators/sql/test_oml_files",
+    "src/generators/typescript/test_oml_files",
+    "src/core/test/oml_files",
+];
+
+/// A fixture with the objects of every file it imports, in dependency order
+/// so each target can hold them in one file.
+struct Fixture {
+    /// `<dir>_<stem>`, unique across the fixture directories
+    name: String,
+    objects: Vec<OmlObject>,
+}
+
+fn fixtures() -> Vec<Fixture> {
+    let mut fixtures = Vec::new();
+    for dir in FIXTURE_DIRS {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .unwrap_or_else(|e| panic!("Failed to read {}: {}", dir, e))
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "oml"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let mut objects = Vec::new();
+            if let Err(e) = collect_objects(&path, &mut objects, &mut Vec::new()) {
+                eprintln!("Skipping {}, which is not valid OML: {}", path.display(), e);
+                continue;
+            }
+            let dir_name = Path::new(dir).file_name().unwrap().to_string_lossy();
+            let stem = path.file_stem().unwrap().to_string_lossy();
+            fixtures.push(Fixture {
+                name: format!("{}_{}", dir_name, stem),
+                objects: OmlObject::dependency_order(&objects),
+            });
+        }
+    }
+    fixtures
+}
+
+/// Adds the objects of `path` and, first, of the files it imports.
+fn collect_objects(path: &Path, objects: &mut Vec<OmlObject>, seen: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let canonical = path.canonicalize()?;
+    if seen.contains(&canonical) {
+        return Ok(());
+    }
+    seen.push(canonical);
+
+    let (file_objects, imports) = OmlObject::get_from_file(path)?;
+    for import in imports {
+        collect_objects(&path.parent().unwrap().join(import), objects, seen)?;
+    }
+    objects.extend(file_objects);
+    Ok(())
+}
+
+/// The first of `candidates` that runs with `--version`.
+fn find_tool(candidates: &[&str]) -> Option<String> {
+    candidates
+        .iter()
+        .find(|tool| Command::new(tool).arg("--version").output().is_ok_and(|out| out.status.success()))
+        .map(|tool| tool.to_string())
+}
+
+/// Generates every fixture into `test_results/compile/<language>/<fixture>`
+/// and runs `compile` on each, panicking with the fixtures that failed.
+fn check(language: &str, generator: &dyn Generate, compile: impl Fn(&Path, &[PathBuf]) -> Command) {
+    let mut failures = Vec::new();
+    for fixture in fixtures() {
+        let dir = Path::new(OUTPUT_DIR).join(language).join(&fixture.name);
+        fs::create_dir_all(&dir).expect("Failed to create the output directory");
+
+        let files = match generator.generate_files(&fixture.objects, &fixture.name) {
+            Ok(files) => files,
+            Err(e) => {
+                failures.push(format!("{}: failed to generate: {}", fixture.name, e));
+                continue;
+            }
+        };
+        let mut paths = Vec::new();
+        for file in files {
+            let path = dir.join(&file.path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &file.content).unwrap();
+            paths.push(path);
+        }
+
+        let output = compile(&dir, &paths).output().expect("Failed to run the compiler");
+        if !output.status.success() {
+            failures.push(format!(
+                "{}:\n{}{}",
+                fixture.name,
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{} fixture(s) failed to compile as {}:\n\n{}", failures.len(), language, failures.join("\n"));
+}
+
+#[test]
+fn test_cpp_compiles() {
+    let compiler = match std::env::var("CXX").ok().or_else(|| find_tool(&["c++", "g++", "clang++"])) {
+        Some(compiler) => compiler,
+        None => return eprintln!("No C++ compiler found, skipping"),
+    };
+    check("cpp", &CppGenerator::default(), |dir, headers| {
+        // one translation unit including every header, so each is
+        // checked the way a consumer sees it
+        let includes: String = headers.iter().map(|h| format!("#include \"{}\"\n", h.file_name().unwrap().to_string_lossy())).collect();
+        let unit = dir.join("check.cpp");
+        fs::write(&unit, includes).unwrap();
+
+        let mut command = Command::new(&compiler);
+        command.args(["-std=c++17", "-fsyntax-only", "-Wall"]).arg(unit);
+        command
+    });
+}
+
+#[test]
+fn test_kotlin_compiles() {
+    let Some(kotlinc) = find_tool(&["kotlinc"]) else {
+        return eprintln!("kotlinc not found, skipping");
+    };
+    check("kotlin", &KotlinGenerator::new(false), |dir, sources| {
+        let mut command = Command::new(&kotlinc);
+        command.args(sources).arg("-d").arg(dir.join("classes"));
+        command
+    });
+}
+
+#[test]
+fn test_python_compiles() {
+    let Some(python) = find_tool(&["python3", "python"]) else {
+        return eprintln!("No Python found, skipping");
+    };
+    check("python", &PythonGenerator::new(false), |_, sources| {
+        let mut command = Command::new(&python);
+        command.args(["-m", "py_compile"]).args(sources);
+        command
+    });
+}
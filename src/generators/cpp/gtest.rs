@@ -0,0 +1,293 @@
+//! GoogleTest scaffolds for the standard C++ output: one test file per class
+//! and struct that constructs it from sample values, reads them back, runs
+//! the setters and, with `--cpp-json`, round-trips it through JSON.
+
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::code_writer::CodeWriter;
+use crate::core::generate::GeneratedFile;
+use crate::core::utils::to_snake_case;
+use super::oml_cpp::{CppGenerator, capitalize_first, type_annotation};
+use std::fmt::Write;
+
+impl CppGenerator {
+    /// `<object>_test.cpp` next to the header for every class and struct.
+    pub(super) fn generate_tests(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, std::fmt::Error> {
+        let objects = self.type_map.apply(&self.apply_accessors(oml_objects));
+        let mut files = Vec::new();
+
+        for oml_object in objects.iter().filter(|o| matches!(o.oml_type, ObjectType::CLASS | ObjectType::STRUCT)) {
+            let mut test_file = CodeWriter::new(self.style);
+            writeln!(test_file, "// This file has been generated from {}.oml", file_name)?;
+            writeln!(test_file)?;
+            writeln!(test_file, "#include <gtest/gtest.h>")?;
+            writeln!(test_file, "#include \"{}.h\"", file_name)?;
+            writeln!(test_file)?;
+            if let Some(namespace) = &self.namespace {
+                writeln!(test_file, "using namespace {};", namespace)?;
+                writeln!(test_file)?;
+            }
+            self.write_tests(oml_object, &objects, &mut test_file)?;
+
+            let path = format!("{}_test.cpp", to_snake_case(&oml_object.name));
+            files.push(GeneratedFile::new(path, test_file.finish()));
+        }
+
+        Ok(files)
+    }
+
+    fn write_tests(&self, oml_object: &OmlObject, siblings: &[OmlObject], test_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let fields: Vec<&Variable> = oml_object.variables
+            .iter()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+            .collect();
+        let optional = self.optional_type();
+        let args: Vec<String> = fields.iter().map(|v| sample(v, siblings, false, optional)).collect();
+        let construct = if fields.is_empty() {
+            format!("{} value;", name)
+        } else {
+            format!("{} value({});", name, args.join(", "))
+        };
+
+        test_file.begin_block(&format!("TEST({}Test, ConstructsFromEveryField)", name))?;
+        test_file.line(&construct)?;
+        let checked: Vec<&&Variable> = fields.iter().filter(|v| readable(v) && comparable(v, siblings)).collect();
+        if checked.is_empty() {
+            test_file.line("(void)value;")?;
+        }
+        for var in checked {
+            test_file.line(&format!("EXPECT_EQ({}, {});", read(var), expected(var, siblings, false, optional)))?;
+        }
+        test_file.end_block("}")?;
+
+        let settable: Vec<&&Variable> = fields
+            .iter()
+            .filter(|v| readable(v) && comparable(v, siblings) && !v.var_mod.contains(&VariableModifier::CONST))
+            .collect();
+        if !settable.is_empty() {
+            test_file.line("")?;
+            test_file.begin_block(&format!("TEST({}Test, SetsFields)", name))?;
+            test_file.line(&construct)?;
+            for var in settable {
+                let value = sample(var, siblings, true, optional);
+                if var.visibility == VariableVisibility::PRIVATE {
+                    test_file.line(&format!("value.set{}({});", capitalize_first(&var.name), value))?;
+                } else {
+                    test_file.line(&format!("value.{} = {};", var.name, expected(var, siblings, true, optional)))?;
+                }
+                test_file.line(&format!("EXPECT_EQ({}, {});", read(var), expected(var, siblings, true, optional)))?;
+            }
+            test_file.end_block("}")?;
+        }
+
+        // get<T>() also reads types without a default constructor, through
+        // the adl_serializer the header gives them
+        if self.json {
+            test_file.line("")?;
+            test_file.begin_block(&format!("TEST({}Test, RoundTripsJson)", name))?;
+            test_file.line(&construct)?;
+            test_file.line("const nlohmann::json json = value;")?;
+            test_file.line(&format!("EXPECT_EQ(nlohmann::json(json.get<{}>()), json);", name))?;
+            test_file.end_block("}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Private fields are read through their getter and public ones directly;
+/// protected ones are out of reach of a test.
+fn readable(var: &Variable) -> bool {
+    var.visibility != VariableVisibility::PROTECTED
+}
+
+fn read(var: &Variable) -> String {
+    match var.visibility {
+        VariableVisibility::PRIVATE => format!("value.get{}()", capitalize_first(&var.name)),
+        _ => format!("value.{}", var.name),
+    }
+}
+
+/// Whether `==` is known to exist for the field: builtins, enums of this
+/// file and containers of them. Other classes only have it with `--cpp-cmp`.
+fn comparable(var: &Variable, siblings: &[OmlObject]) -> bool {
+    OmlObject::is_builtin_type(&var.var_type) || local_enum(&var.var_type, siblings).is_some()
+}
+
+fn local_enum<'a>(var_type: &str, siblings: &'a [OmlObject]) -> Option<&'a OmlObject> {
+    siblings.iter().find(|o| o.name == var_type && o.oml_type == ObjectType::ENUM)
+}
+
+/// A value of the element type; `second` gives a different one for setters.
+fn sample_element(var_type: &str, siblings: &[OmlObject], second: bool) -> String {
+    let pick = |first: &str, other: &str| if second { other.to_string() } else { first.to_string() };
+    match var_type {
+        "int8" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" | "uint64" => pick("1", "2"),
+        "float" => pick("1.5f", "2.5f"),
+        "double" => pick("1.5", "2.5"),
+        "bool" => pick("true", "false"),
+        "string" => pick("\"text\"", "\"other\""),
+        "char" => pick("'a'", "'b'"),
+        other => match local_enum(other, siblings).and_then(|e| if second { e.variables.last() } else { e.variables.first() }) {
+            Some(variant) => format!("{}::{}", other, variant.name.to_uppercase()),
+            None => format!("{}{{}}", other),
+        },
+    }
+}
+
+/// A constructor or setter argument for `var`. An optional container is
+/// spelled out as `optional`, the optional template in use, since a braced
+/// list does not convert to one.
+fn sample(var: &Variable, siblings: &[OmlObject], second: bool, optional: &str) -> String {
+    let element = sample_element(&var.var_type, siblings, second);
+    let list = match var.array_kind {
+        ArrayKind::None => return element,
+        ArrayKind::Static(n) => format!("{{{}}}", vec![element; n as usize].join(", ")),
+        ArrayKind::Dynamic => format!("{{{}}}", element),
+    };
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        format!("{}<{}>{{{}}}", optional, type_annotation(&var.var_type, &var.array_kind), list)
+    } else {
+        list
+    }
+}
+
+/// The sample as a typed expression, since `EXPECT_EQ` cannot take a braced list.
+fn expected(var: &Variable, siblings: &[OmlObject], second: bool, optional: &str) -> String {
+    let value = sample(var, siblings, second, optional);
+    match var.array_kind {
+        ArrayKind::None => value,
+        _ if var.var_mod.contains(&VariableModifier::OPTIONAL) => format!("({})", value),
+        _ => format!("({}{})", type_annotation(&var.var_type, &var.array_kind), value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generate::Generate;
+
+    #[test]
+    fn test_test_file_per_class() {
+        let objects = [
+            OmlObject {
+                oml_type: ObjectType::ENUM,
+                name: "Color".to_string(),
+                variables: vec![
+                    Variable { name: "Red".to_string(), var_type: "string".to_string(), ..Default::default() },
+                    Variable { name: "Blue".to_string(), var_type: "string".to_string(), ..Default::default() },
+                ],
+                ..Default::default()
+            },
+            OmlObject {
+                oml_type: ObjectType::CLASS,
+                name: "RaceCar".to_string(),
+                variables: vec![
+                    Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), name: "name".to_string(), ..Default::default() },
+                    Variable { visibility: VariableVisibility::PUBLIC, var_type: "double".to_string(), array_kind: ArrayKind::Static(2), name: "wheels".to_string(), ..Default::default() },
+                    Variable { visibility: VariableVisibility::PRIVATE, var_type: "Color".to_string(), name: "color".to_string(), ..Default::default() },
+                    Variable { var_mod: vec![VariableModifier::STATIC], var_type: "int32".to_string(), name: "count".to_string(), ..Default::default() },
+                ],
+                ..Default::default()
+            },
+        ];
+
+        let files = CppGenerator::default().with_tests(true).with_json(true).generate_files(&objects, "cars").unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1].path, std::path::PathBuf::from("race_car_test.cpp"));
+        assert_eq!(
+            files[1].content,
+            "// This file has been generated from cars.oml\n\
+             \n\
+             #include <gtest/gtest.h>\n\
+             #include \"cars.h\"\n\
+             \n\
+             TEST(RaceCarTest, ConstructsFromEveryField) {\n\
+             \tRaceCar value(\"text\", {1.5, 1.5}, Color::RED);\n\
+             \tEXPECT_EQ(value.getName(), \"text\");\n\
+             \tEXPECT_EQ(value.wheels, (std::array<double, 2>{1.5, 1.5}));\n\
+             \tEXPECT_EQ(value.getColor(), Color::RED);\n\
+             }\n\
+             \n\
+             TEST(RaceCarTest, SetsFields) {\n\
+             \tRaceCar value(\"text\", {1.5, 1.5}, Color::RED);\n\
+             \tvalue.wheels = (std::array<double, 2>{2.5, 2.5});\n\
+             \tEXPECT_EQ(value.wheels, (std::array<double, 2>{2.5, 2.5}));\n\
+             \tvalue.setColor(Color::BLUE);\n\
+             \tEXPECT_EQ(value.getColor(), Color::BLUE);\n\
+             }\n\
+             \n\
+             TEST(RaceCarTest, RoundTripsJson) {\n\
+             \tRaceCar value(\"text\", {1.5, 1.5}, Color::RED);\n\
+             \tconst nlohmann::json json = value;\n\
+             \tEXPECT_EQ(nlohmann::json(json.get<RaceCar>()), json);\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_optional_containers_are_wrapped() {
+        let objects = [OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Tagged".to_string(),
+            variables: vec![
+                Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::Dynamic, name: "tags".to_string(), ..Default::default() },
+                Variable { visibility: VariableVisibility::PUBLIC, var_type: "int32".to_string(), array_kind: ArrayKind::Static(2), name: "scores".to_string(), ..Default::default() },
+                Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PUBLIC, var_type: "int32".to_string(), name: "rank".to_string(), ..Default::default() },
+            ],
+            ..Default::default()
+        }];
+
+        let files = CppGenerator::default().with_tests(true).generate_files(&objects, "tagged").unwrap();
+        assert_eq!(
+            files[1].content,
+            "// This file has been generated from tagged.oml\n\
+             \n\
+             #include <gtest/gtest.h>\n\
+             #include \"tagged.h\"\n\
+             \n\
+             TEST(TaggedTest, ConstructsFromEveryField) {\n\
+             \tTagged value(std::optional<std::vector<std::string>>{{\"text\"}}, {1, 1}, 1);\n\
+             \tEXPECT_EQ(value.tags, (std::optional<std::vector<std::string>>{{\"text\"}}));\n\
+             \tEXPECT_EQ(value.scores, (std::array<int32_t, 2>{1, 1}));\n\
+             \tEXPECT_EQ(value.rank, 1);\n\
+             }\n\
+             \n\
+             TEST(TaggedTest, SetsFields) {\n\
+             \tTagged value(std::optional<std::vector<std::string>>{{\"text\"}}, {1, 1}, 1);\n\
+             \tvalue.tags = (std::optional<std::vector<std::string>>{{\"other\"}});\n\
+             \tEXPECT_EQ(value.tags, (std::optional<std::vector<std::string>>{{\"other\"}}));\n\
+             \tvalue.scores = (std::array<int32_t, 2>{2, 2});\n\
+             \tEXPECT_EQ(value.scores, (std::array<int32_t, 2>{2, 2}));\n\
+             \tvalue.rank = 2;\n\
+             \tEXPECT_EQ(value.rank, 2);\n\
+             }\n"
+        );
+        let header = &files[0].content;
+        assert!(header.contains("std::optional<std::vector<std::string>> tags"), "Got: {}", header);
+    }
+
+    #[test]
+    fn test_types_without_default_constructor_round_trip_json() {
+        let objects = [OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Badge".to_string(),
+            variables: vec![
+                Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PUBLIC, var_type: "int32".to_string(), name: "id".to_string(), ..Default::default() },
+            ],
+            ..Default::default()
+        }];
+
+        let files = CppGenerator::default().with_tests(true).with_json(true).generate_files(&objects, "badge").unwrap();
+        assert!(files[1].content.ends_with(
+            "TEST(BadgeTest, RoundTripsJson) {\n\
+             \tBadge value(1);\n\
+             \tconst nlohmann::json json = value;\n\
+             \tEXPECT_EQ(nlohmann::json(json.get<Badge>()), json);\n\
+             }\n"
+        ), "Got: {}", files[1].content);
+        assert!(files[0].content.contains("struct adl_serializer<Badge> {\n"), "Got: {}", files[0].content);
+    }
+}
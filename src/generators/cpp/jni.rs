@@ -0,0 +1,517 @@
+//! JNI bridge between the standard C++ output and the `--kotlin` classes.
+//! `<file>_jni.h` specializes `oml_jni::Marshal` for every enum, class and
+//! struct of the file, converting values to the Kotlin objects and back.
+//! The specializations for strings, boxed builtins, optionals, arrays and
+//! lists they build on are in `oml_jni.h`, written once for all inputs.
+//!
+//! Kotlin objects are allocated without running their constructor and
+//! their backing fields set directly, which sidesteps the mangled
+//! constructors of unsigned parameters. Both targets are assumed to spell
+//! names alike, as they do without `--naming idiomatic`. Static fields are
+//! not per object and left out. Classes with protected fields make their
+//! `Marshal` a friend so those can be read too.
+
+use crate::core::oml_object::{OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind};
+use crate::core::code_writer::CodeWriter;
+use crate::core::generate::GeneratedFile;
+use super::oml_cpp::{CppGenerator, CppStandard, capitalize_first};
+use std::fmt::Write;
+
+/// The file with the specializations every `<file>_jni.h` builds on.
+const SHARED_HEADER: &str = "oml_jni.h";
+
+/// The Kotlin classes the bridge converts to, as `--kotlin` generates them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JniBridge {
+    /// The Kotlin package, e.g. `com.example.models`
+    pub package: Option<String>,
+    /// Whether private fields sit behind `_name` backing properties, as
+    /// with `--kotlin-accessors`
+    pub accessors: bool,
+}
+
+/// How a builtin is boxed on the JVM, for optionals, arrays and lists.
+struct Boxed {
+    cpp_type: &'static str,
+    jni_type: &'static str,
+    /// The JVM descriptor of the unboxed value
+    descriptor: &'static str,
+    /// The `Call<X>Method` unboxing it
+    call: &'static str,
+    class: &'static str,
+    box_method: &'static str,
+    unbox_method: &'static str,
+}
+
+const fn boxed(
+    cpp_type: &'static str,
+    (jni_type, descriptor, call): (&'static str, &'static str, &'static str),
+    (class, box_method, unbox_method): (&'static str, &'static str, &'static str),
+) -> Boxed {
+    Boxed { cpp_type, jni_type, descriptor, call, class, box_method, unbox_method }
+}
+
+const INT: (&str, &str, &str) = ("jint", "I", "Int");
+const LONG: (&str, &str, &str) = ("jlong", "J", "Long");
+const INTEGER: (&str, &str, &str) = ("java/lang/Integer", "valueOf", "intValue");
+const UINT: (&str, &str, &str) = ("kotlin/UInt", "box-impl", "unbox-impl");
+const ULONG: (&str, &str, &str) = ("kotlin/ULong", "box-impl", "unbox-impl");
+
+/// Every builtin but `string` as `--kotlin` types it: signed types up to 32
+/// bits are `Int`, unsigned ones `UInt`, which the JVM boxes as `kotlin.UInt`.
+const BOXED: [Boxed; 12] = [
+    boxed("int8_t", INT, INTEGER),
+    boxed("int16_t", INT, INTEGER),
+    boxed("int32_t", INT, INTEGER),
+    boxed("int64_t", LONG, ("java/lang/Long", "valueOf", "longValue")),
+    boxed("uint8_t", INT, UINT),
+    boxed("uint16_t", INT, UINT),
+    boxed("uint32_t", INT, UINT),
+    boxed("uint64_t", LONG, ULONG),
+    boxed("float", ("jfloat", "F", "Float"), ("java/lang/Float", "valueOf", "floatValue")),
+    boxed("double", ("jdouble", "D", "Double"), ("java/lang/Double", "valueOf", "doubleValue")),
+    boxed("bool", ("jboolean", "Z", "Boolean"), ("java/lang/Boolean", "valueOf", "booleanValue")),
+    boxed("char", ("jchar", "C", "Char"), ("java/lang/Character", "valueOf", "charValue")),
+];
+
+fn oml_boxed(var_type: &str) -> Option<&'static Boxed> {
+    let cpp_type = match var_type {
+        "int8" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" | "uint64" => format!("{}_t", var_type),
+        other => other.to_string(),
+    };
+    BOXED.iter().find(|b| b.cpp_type == cpp_type)
+}
+
+impl CppGenerator {
+    /// `oml_jni.h`, included by the `<file>_jni.h` of every input.
+    pub(super) fn generate_jni_shared(&self, file_names: &[&str]) -> Result<GeneratedFile, std::fmt::Error> {
+        let sources: Vec<String> = file_names.iter().map(|f| format!("{}.oml", f)).collect();
+        let optional = self.optional_type();
+
+        let mut cpp_file = CodeWriter::new(self.style);
+        writeln!(cpp_file, "// This file has been generated from {}", sources.join(", "))?;
+        self.write_guard_start("oml_jni", &mut cpp_file)?;
+        writeln!(cpp_file)?;
+        writeln!(cpp_file, "#include <jni.h>")?;
+        writeln!(cpp_file, "#include <array>")?;
+        writeln!(cpp_file, "#include <cstddef>")?;
+        writeln!(cpp_file, "#include <cstdint>")?;
+        writeln!(cpp_file, "#include {}", if self.standard >= CppStandard::Cpp17 { "<optional>" } else { "<tl/optional.hpp>" })?;
+        writeln!(cpp_file, "#include <string>")?;
+        writeln!(cpp_file, "#include <vector>")?;
+        writeln!(cpp_file)?;
+        cpp_file.line("namespace oml_jni {")?;
+        cpp_file.line("")?;
+        cpp_file.line("/// Converts a `T` to the Kotlin object it is generated as, and back.")?;
+        cpp_file.line("template <typename T>")?;
+        cpp_file.line("struct Marshal;")?;
+
+        cpp_file.line("")?;
+        cpp_file.line("template <>")?;
+        cpp_file.begin_block("struct Marshal<std::string>")?;
+        cpp_file.line("static constexpr const char* kotlinClass = \"java/lang/String\";")?;
+        cpp_file.line("")?;
+        cpp_file.begin_block("static jobject toKotlin(JNIEnv* env, const std::string& value)")?;
+        cpp_file.line("return env->NewStringUTF(value.c_str());")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("")?;
+        cpp_file.begin_block("static std::string fromKotlin(JNIEnv* env, jobject object)")?;
+        cpp_file.line("const char* chars = env->GetStringUTFChars(static_cast<jstring>(object), nullptr);")?;
+        cpp_file.line("std::string value(chars);")?;
+        cpp_file.line("env->ReleaseStringUTFChars(static_cast<jstring>(object), chars);")?;
+        cpp_file.line("return value;")?;
+        cpp_file.end_block("}")?;
+        cpp_file.end_block("};")?;
+
+        for boxed in &BOXED {
+            cpp_file.line("")?;
+            cpp_file.line("template <>")?;
+            cpp_file.begin_block(&format!("struct Marshal<{}>", boxed.cpp_type))?;
+            cpp_file.line(&format!("static constexpr const char* kotlinClass = \"{}\";", boxed.class))?;
+            cpp_file.line("")?;
+            cpp_file.begin_block(&format!("static jobject toKotlin(JNIEnv* env, {} value)", boxed.cpp_type))?;
+            cpp_file.line("jclass type = env->FindClass(kotlinClass);")?;
+            cpp_file.line(&format!(
+                "jmethodID box = env->GetStaticMethodID(type, \"{}\", \"({})L{};\");",
+                boxed.box_method, boxed.descriptor, boxed.class
+            ))?;
+            cpp_file.line(&format!("return env->CallStaticObjectMethod(type, box, static_cast<{}>(value));", boxed.jni_type))?;
+            cpp_file.end_block("}")?;
+            cpp_file.line("")?;
+            cpp_file.begin_block(&format!("static {} fromKotlin(JNIEnv* env, jobject object)", boxed.cpp_type))?;
+            cpp_file.line(&format!(
+                "jmethodID unbox = env->GetMethodID(env->GetObjectClass(object), \"{}\", \"(){}\");",
+                boxed.unbox_method, boxed.descriptor
+            ))?;
+            cpp_file.line(&format!("return static_cast<{}>(env->Call{}Method(object, unbox));", boxed.cpp_type, boxed.call))?;
+            cpp_file.end_block("}")?;
+            cpp_file.end_block("};")?;
+        }
+
+        cpp_file.line("")?;
+        cpp_file.line("/// Empty optionals are Kotlin `null`.")?;
+        cpp_file.line("template <typename T>")?;
+        cpp_file.begin_block(&format!("struct Marshal<{}<T>>", optional))?;
+        cpp_file.begin_block(&format!("static jobject toKotlin(JNIEnv* env, const {}<T>& value)", optional))?;
+        cpp_file.line("return value ? Marshal<T>::toKotlin(env, *value) : nullptr;")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("")?;
+        cpp_file.begin_block(&format!("static {}<T> fromKotlin(JNIEnv* env, jobject object)", optional))?;
+        cpp_file.begin_block("if (object == nullptr)")?;
+        cpp_file.line("return {};")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("return Marshal<T>::fromKotlin(env, object);")?;
+        cpp_file.end_block("}")?;
+        cpp_file.end_block("};")?;
+
+        cpp_file.line("")?;
+        cpp_file.line("/// Fixed-size arrays are Kotlin `Array`s of the boxed element.")?;
+        cpp_file.line("template <typename T, std::size_t N>")?;
+        cpp_file.begin_block("struct Marshal<std::array<T, N>>")?;
+        cpp_file.begin_block("static jobject toKotlin(JNIEnv* env, const std::array<T, N>& value)")?;
+        cpp_file.line("jobjectArray array = env->NewObjectArray(static_cast<jsize>(N), env->FindClass(Marshal<T>::kotlinClass), nullptr);")?;
+        cpp_file.begin_block("for (std::size_t i = 0; i < N; ++i)")?;
+        cpp_file.line("jobject item = Marshal<T>::toKotlin(env, value[i]);")?;
+        cpp_file.line("env->SetObjectArrayElement(array, static_cast<jsize>(i), item);")?;
+        cpp_file.line("env->DeleteLocalRef(item);")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("return array;")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("")?;
+        cpp_file.begin_block("static std::array<T, N> fromKotlin(JNIEnv* env, jobject object)")?;
+        cpp_file.line("std::array<T, N> value{};")?;
+        cpp_file.begin_block("for (std::size_t i = 0; i < N; ++i)")?;
+        cpp_file.line("jobject item = env->GetObjectArrayElement(static_cast<jobjectArray>(object), static_cast<jsize>(i));")?;
+        cpp_file.line("value[i] = Marshal<T>::fromKotlin(env, item);")?;
+        cpp_file.line("env->DeleteLocalRef(item);")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("return value;")?;
+        cpp_file.end_block("}")?;
+        cpp_file.end_block("};")?;
+
+        cpp_file.line("")?;
+        cpp_file.line("/// Vectors are Kotlin `MutableList`s, created as `java.util.ArrayList`.")?;
+        cpp_file.line("template <typename T>")?;
+        cpp_file.begin_block("struct Marshal<std::vector<T>>")?;
+        cpp_file.begin_block("static jobject toKotlin(JNIEnv* env, const std::vector<T>& value)")?;
+        cpp_file.line("jclass type = env->FindClass(\"java/util/ArrayList\");")?;
+        cpp_file.line("jobject list = env->NewObject(type, env->GetMethodID(type, \"<init>\", \"(I)V\"), static_cast<jint>(value.size()));")?;
+        cpp_file.line("jmethodID add = env->GetMethodID(type, \"add\", \"(Ljava/lang/Object;)Z\");")?;
+        cpp_file.begin_block("for (const T& element : value)")?;
+        cpp_file.line("jobject item = Marshal<T>::toKotlin(env, element);")?;
+        cpp_file.line("env->CallBooleanMethod(list, add, item);")?;
+        cpp_file.line("env->DeleteLocalRef(item);")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("return list;")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("")?;
+        cpp_file.begin_block("static std::vector<T> fromKotlin(JNIEnv* env, jobject object)")?;
+        cpp_file.line("jclass type = env->FindClass(\"java/util/List\");")?;
+        cpp_file.line("jint size = env->CallIntMethod(object, env->GetMethodID(type, \"size\", \"()I\"));")?;
+        cpp_file.line("jmethodID get = env->GetMethodID(type, \"get\", \"(I)Ljava/lang/Object;\");")?;
+        cpp_file.line("std::vector<T> value;")?;
+        cpp_file.line("value.reserve(static_cast<std::size_t>(size));")?;
+        cpp_file.begin_block("for (jint i = 0; i < size; ++i)")?;
+        cpp_file.line("jobject item = env->CallObjectMethod(object, get, i);")?;
+        cpp_file.line("value.push_back(Marshal<T>::fromKotlin(env, item));")?;
+        cpp_file.line("env->DeleteLocalRef(item);")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("return value;")?;
+        cpp_file.end_block("}")?;
+        cpp_file.end_block("};")?;
+
+        cpp_file.line("")?;
+        cpp_file.line("} // namespace oml_jni")?;
+        self.write_guard_end("oml_jni", &mut cpp_file)?;
+
+        Ok(GeneratedFile::new(SHARED_HEADER, cpp_file.finish()))
+    }
+
+    /// `<file>_jni.h` with a `Marshal` specialization per type of the file.
+    pub(super) fn generate_jni(&self, bridge: &JniBridge, oml_objects: &[OmlObject], file_name: &str) -> Result<GeneratedFile, std::fmt::Error> {
+        let objects = self.type_map.apply(&self.apply_accessors(oml_objects));
+        let guard = format!("{}_jni", file_name);
+
+        let mut cpp_file = CodeWriter::new(self.style);
+        writeln!(cpp_file, "// This file has been generated from {}.oml", file_name)?;
+        self.write_guard_start(&guard, &mut cpp_file)?;
+        writeln!(cpp_file)?;
+        writeln!(cpp_file, "#include \"{}\"", SHARED_HEADER)?;
+        writeln!(cpp_file, "#include \"{}.h\"", file_name)?;
+        writeln!(cpp_file)?;
+        cpp_file.line("namespace oml_jni {")?;
+        if let Some(namespace) = &self.namespace {
+            cpp_file.line("")?;
+            cpp_file.line(&format!("using namespace {};", namespace))?;
+        }
+
+        // the Kotlin field names follow the visibility as written
+        for (original, oml_object) in oml_objects.iter().zip(&objects) {
+            cpp_file.line("")?;
+            cpp_file.line("template <>")?;
+            match oml_object.oml_type {
+                ObjectType::ENUM => self.write_enum_marshal(bridge, oml_object, &mut cpp_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => self.write_class_marshal(bridge, original, oml_object, &mut cpp_file)?,
+                ObjectType::UNDECIDED => return Err(std::fmt::Error),
+            }
+        }
+
+        cpp_file.line("")?;
+        cpp_file.line("} // namespace oml_jni")?;
+        self.write_guard_end(&guard, &mut cpp_file)?;
+
+        Ok(GeneratedFile::new(format!("{}_jni.h", file_name), cpp_file.finish()))
+    }
+
+    fn write_guard_start(&self, name: &str, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        if self.pragma_once {
+            writeln!(cpp_file, "#pragma once")
+        } else {
+            let guard = self.header_guard(name);
+            writeln!(cpp_file, "#ifndef {}", guard)?;
+            writeln!(cpp_file, "#define {}", guard)
+        }
+    }
+
+    fn write_guard_end(&self, name: &str, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        if self.pragma_once {
+            return Ok(());
+        }
+        writeln!(cpp_file)?;
+        writeln!(cpp_file, "#endif // {}", self.header_guard(name))
+    }
+
+    /// Enum values are the constants of the same name, read back by ordinal.
+    fn write_enum_marshal(&self, bridge: &JniBridge, oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let class = kotlin_class(bridge, name);
+        let names: Vec<String> = oml_object.variables.iter().map(|v| format!("\"{}\"", v.name.to_uppercase())).collect();
+
+        cpp_file.begin_block(&format!("struct Marshal<{}>", name))?;
+        cpp_file.line(&format!("static constexpr const char* kotlinClass = \"{}\";", class))?;
+        cpp_file.line("")?;
+        cpp_file.begin_block(&format!("static jobject toKotlin(JNIEnv* env, {} value)", name))?;
+        if names.is_empty() {
+            cpp_file.line("(void)env;")?;
+            cpp_file.line("(void)value;")?;
+            cpp_file.line("return nullptr;")?;
+        } else {
+            cpp_file.line(&format!("static const char* const names[] = {{{}}};", names.join(", ")))?;
+            cpp_file.line("jclass type = env->FindClass(kotlinClass);")?;
+            cpp_file.line(&format!(
+                "jfieldID constant = env->GetStaticFieldID(type, names[static_cast<int>(value)], \"L{};\");",
+                class
+            ))?;
+            cpp_file.line("return env->GetStaticObjectField(type, constant);")?;
+        }
+        cpp_file.end_block("}")?;
+        cpp_file.line("")?;
+        cpp_file.begin_block(&format!("static {} fromKotlin(JNIEnv* env, jobject object)", name))?;
+        cpp_file.line("jmethodID ordinal = env->GetMethodID(env->GetObjectClass(object), \"ordinal\", \"()I\");")?;
+        cpp_file.line(&format!("return static_cast<{}>(env->CallIntMethod(object, ordinal));", name))?;
+        cpp_file.end_block("}")?;
+        cpp_file.end_block("};")
+    }
+
+    /// Classes go field by field; `original` has the visibility the Kotlin
+    /// output was generated from. Reading one back goes through the
+    /// constructor taking every field, which also sets const ones.
+    fn write_class_marshal(&self, bridge: &JniBridge, original: &OmlObject, oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let fields: Vec<(&Variable, &Variable)> = original.variables
+            .iter()
+            .zip(&oml_object.variables)
+            .filter(|(_, v)| !v.var_mod.contains(&VariableModifier::STATIC))
+            .collect();
+
+        cpp_file.begin_block(&format!("struct Marshal<{}>", name))?;
+        cpp_file.line(&format!("static constexpr const char* kotlinClass = \"{}\";", kotlin_class(bridge, name)))?;
+        cpp_file.line("")?;
+
+        cpp_file.begin_block(&format!("static jobject toKotlin(JNIEnv* env, const {}& value)", name))?;
+        cpp_file.line("jclass type = env->FindClass(kotlinClass);")?;
+        cpp_file.line("// the constructor is bypassed, the fields are set one by one")?;
+        cpp_file.line("jobject object = env->AllocObject(type);")?;
+        for (original, var) in &fields {
+            let field = self.field_id(bridge, original, var);
+            let read = self.read_field(var);
+            match self.direct_field(var) {
+                Some(boxed) => cpp_file.line(&format!(
+                    "env->Set{}Field(object, {}, static_cast<{}>({}));",
+                    boxed.call, field, boxed.jni_type, read
+                ))?,
+                None => cpp_file.line(&format!(
+                    "env->SetObjectField(object, {}, Marshal<{}>::toKotlin(env, {}));",
+                    field, self.get_full_type(var), read
+                ))?,
+            }
+        }
+        if fields.is_empty() {
+            cpp_file.line("(void)value;")?;
+        }
+        cpp_file.line("return object;")?;
+        cpp_file.end_block("}")?;
+        cpp_file.line("")?;
+
+        cpp_file.begin_block(&format!("static {} fromKotlin(JNIEnv* env, jobject object)", name))?;
+        if fields.is_empty() {
+            cpp_file.line("(void)env;")?;
+            cpp_file.line("(void)object;")?;
+            cpp_file.line(&format!("return {}();", name))?;
+        } else {
+            cpp_file.line("jclass type = env->GetObjectClass(object);")?;
+            cpp_file.line(&format!("return {}(", name))?;
+            cpp_file.indented(|cpp_file| {
+                for (i, (original, var)) in fields.iter().enumerate() {
+                    let field = self.field_id(bridge, original, var);
+                    let value = match self.direct_field(var) {
+                        Some(boxed) => format!("static_cast<{}>(env->Get{}Field(object, {}))", self.get_full_type(var), boxed.call, field),
+                        None => format!("Marshal<{}>::fromKotlin(env, env->GetObjectField(object, {}))", self.get_full_type(var), field),
+                    };
+                    let separator = if i + 1 == fields.len() { "" } else { "," };
+                    cpp_file.line(&format!("{}{}", value, separator))?;
+                }
+                Ok(())
+            })?;
+            cpp_file.line(");")?;
+        }
+        cpp_file.end_block("}")?;
+        cpp_file.end_block("};")
+    }
+
+    /// The builtin a field is stored as on the JVM when Kotlin keeps it
+    /// unboxed: plain, non-optional builtins other than strings.
+    fn direct_field(&self, var: &Variable) -> Option<&'static Boxed> {
+        if var.array_kind != ArrayKind::None || var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            return None;
+        }
+        oml_boxed(&var.var_type)
+    }
+
+    /// `GetFieldID` for the Kotlin backing field of `var`.
+    fn field_id(&self, bridge: &JniBridge, original: &Variable, var: &Variable) -> String {
+        let field_name = if bridge.accessors && original.visibility == VariableVisibility::PRIVATE {
+            format!("_{}", var.name)
+        } else {
+            var.name.clone()
+        };
+        let element = match (var.var_type.as_str(), oml_boxed(&var.var_type)) {
+            ("string", _) => "Ljava/lang/String;".to_string(),
+            (_, Some(boxed)) if self.direct_field(var).is_some() => boxed.descriptor.to_string(),
+            (_, Some(boxed)) => format!("L{};", boxed.class),
+            (other, None) => format!("L{};", kotlin_class(bridge, other)),
+        };
+        let descriptor = match var.array_kind {
+            ArrayKind::None => element,
+            ArrayKind::Static(_) => format!("[{}", element),
+            ArrayKind::Dynamic => "Ljava/util/List;".to_string(),
+        };
+        format!("env->GetFieldID(type, \"{}\", \"{}\")", field_name, descriptor)
+    }
+
+    /// Public and protected fields are read directly, the latter as a
+    /// friend, and private ones through their getter;
+    /// plain string getters return a view on C++17, copied back to a string.
+    fn read_field(&self, var: &Variable) -> String {
+        if var.visibility != VariableVisibility::PRIVATE {
+            return format!("value.{}", var.name);
+        }
+        let getter = format!("value.get{}()", capitalize_first(&var.name));
+        if self.standard >= CppStandard::Cpp17 && self.get_full_type(var) == "std::string" {
+            format!("std::string({})", getter)
+        } else {
+            getter
+        }
+    }
+}
+
+/// The JVM name of a generated Kotlin class, e.g. `com/example/Car`.
+fn kotlin_class(bridge: &JniBridge, name: &str) -> String {
+    match &bridge.package {
+        Some(package) => format!("{}/{}", package.replace('.', "/"), name),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generate::{AccessorMode, Generate};
+    use crate::core::test::fixtures::member;
+
+    fn objects() -> Vec<OmlObject> {
+        vec![
+            OmlObject {
+                oml_type: ObjectType::ENUM,
+                name: "Color".to_string(),
+                variables: vec![
+                    member("Red", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                    member("Blue", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                ],
+                ..Default::default()
+            },
+            OmlObject {
+                oml_type: ObjectType::CLASS,
+                name: "Car".to_string(),
+                variables: vec![
+                    member("year", "uint16", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                    member("name", "string", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+                    member("color", "Color", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                    member("wheels", "double", VariableVisibility::PUBLIC, vec![], ArrayKind::Static(4)),
+                    member("mileage", "int32", VariableVisibility::PROTECTED, vec![], ArrayKind::None),
+                    member("count", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+                ],
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_marshal_per_type() {
+        let bridge = JniBridge { package: Some("com.example".to_string()), accessors: true };
+        let files = CppGenerator::default()
+            .with_accessors(AccessorMode::Private)
+            .with_jni(Some(bridge))
+            .generate_files(&objects(), "car")
+            .unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1].path, std::path::PathBuf::from("car_jni.h"));
+        let out = &files[1].content;
+
+        assert!(out.starts_with("// This file has been generated from car.oml\n#ifndef CAR_JNI_H\n#define CAR_JNI_H\n\n#include \"oml_jni.h\"\n#include \"car.h\"\n"), "Got: {}", out);
+        assert!(out.contains("\t\tstatic const char* const names[] = {\"RED\", \"BLUE\"};\n"), "Got: {}", out);
+        assert!(out.contains("env->GetStaticFieldID(type, names[static_cast<int>(value)], \"Lcom/example/Color;\")"), "Got: {}", out);
+        assert!(out.contains(
+            "\t\tenv->SetIntField(object, env->GetFieldID(type, \"year\", \"I\"), static_cast<jint>(value.year));\n\
+             \t\tenv->SetObjectField(object, env->GetFieldID(type, \"_name\", \"Ljava/lang/String;\"), Marshal<std::string>::toKotlin(env, std::string(value.getName())));\n\
+             \t\tenv->SetObjectField(object, env->GetFieldID(type, \"color\", \"Lcom/example/Color;\"), Marshal<std::optional<Color>>::toKotlin(env, value.color));\n\
+             \t\tenv->SetObjectField(object, env->GetFieldID(type, \"wheels\", \"[Ljava/lang/Double;\"), Marshal<std::array<double, 4>>::toKotlin(env, value.wheels));\n\
+             \t\tenv->SetIntField(object, env->GetFieldID(type, \"mileage\", \"I\"), static_cast<jint>(value.mileage));\n\
+             \t\treturn object;\n"
+        ), "Got: {}", out);
+        assert!(out.contains(
+            "\t\treturn Car(\n\
+             \t\t\tstatic_cast<uint16_t>(env->GetIntField(object, env->GetFieldID(type, \"year\", \"I\"))),\n\
+             \t\t\tMarshal<std::string>::fromKotlin(env, env->GetObjectField(object, env->GetFieldID(type, \"_name\", \"Ljava/lang/String;\"))),\n"
+        ), "Got: {}", out);
+        assert!(!out.contains("count"), "Got: {}", out);
+
+        // protected fields are read as a friend
+        let header = &files[0].content;
+        assert!(header.contains("namespace oml_jni { template <typename T> struct Marshal; }\n"), "Got: {}", header);
+        assert!(header.contains("class Car {\npublic:\n\tfriend struct ::oml_jni::Marshal<Car>;\n"), "Got: {}", header);
+    }
+
+    #[test]
+    fn test_shared_header_once_for_all_inputs() {
+        let objects = objects();
+        let generator = CppGenerator::default().with_jni(Some(JniBridge::default()));
+        let files = generator.generate_package_files(&[("car", &objects), ("garage", &[])]).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, std::path::PathBuf::from("oml_jni.h"));
+        assert!(files[0].content.starts_with("// This file has been generated from car.oml, garage.oml\n"), "Got: {}", files[0].content);
+        assert!(files[0].content.contains("jmethodID box = env->GetStaticMethodID(type, \"box-impl\", \"(I)Lkotlin/UInt;\");"), "Got: {}", files[0].content);
+
+        assert!(CppGenerator::default().generate_package_files(&[("car", &objects)]).unwrap().is_empty());
+    }
+}
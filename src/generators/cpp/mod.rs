@@ -1,4 +1,9 @@
 pub mod oml_cpp;
+pub mod unreal;
+pub mod jni;
+mod gtest;
+mod pybind;
+mod source;
 
 #[cfg(test)]
 mod test;
@@ -1,11 +1,437 @@
 use crate::core::oml_object::{
     OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
 };
-use crate::core::generate::{Generate, BackwardsGenerate};
+use crate::core::generate::{AccessorMode, Generate, BackwardsGenerate, GeneratedFile};
+use crate::core::code_writer::{BraceStyle, CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
+use crate::core::builder::{builder_fields, BuilderField};
+use crate::core::type_map::TypeMap;
+use crate::core::schema_hash::{schema_hash, SCHEMA_HASH};
+use crate::core::utils::to_snake_case;
+use crate::core::verify::VerifyTarget;
+use crate::generators::cpp::unreal;
+use crate::generators::cpp::jni::JniBridge;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Write;
+use std::str::FromStr;
+
+/// The C++ standard the generated headers are written against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum CppStandard {
+    #[value(name = "11")]
+    Cpp11,
+    #[value(name = "14")]
+    Cpp14,
+    #[default]
+    #[value(name = "17")]
+    Cpp17,
+    #[value(name = "20")]
+    Cpp20,
+}
+
+/// What the include guard of a header is named after.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum GuardSource {
+    /// The OML file, e.g. `CAR_H` for car.oml
+    #[default]
+    File,
+    /// The objects the header declares, e.g. `CAR_ENGINE_H`, so that
+    /// headers from OML files of the same name do not share a guard
+    Objects,
+}
+
+/// A class or struct that fields hold through `std::unique_ptr`, given as
+/// `TYPE[=HEADER]`, e.g. `Engine=engine.h`. Headers only forward-declare
+/// it; the header declaring it is included by the generated source file,
+/// and may be left out for types declared in the same OML file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerType {
+    pub name: String,
+    /// `<engine.h>` or `"engine.h"`, which a bare file name is quoted into
+    pub header: Option<String>,
+}
+
+impl FromStr for PointerType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, header) = match s.split_once('=') {
+            Some((name, header)) => (name.trim(), Some(header.trim())),
+            None => (s.trim(), None),
+        };
+        if name.is_empty() || header == Some("") {
+            return Err(format!("expected TYPE[=HEADER], got `{}`", s));
+        }
+        let header = header.map(|header| match header.starts_with('<') || header.starts_with('"') {
+            true => header.to_string(),
+            false => format!("\"{}\"", header),
+        });
+        Ok(PointerType { name: name.to_string(), header })
+    }
+}
+
+#[derive(Default)]
+pub struct CppGenerator {
+    /// Emit Unreal Engine reflected types instead of standard C++
+    pub unreal: bool,
+    /// Use `#pragma once` instead of an `#ifndef` include guard
+    pub pragma_once: bool,
+    /// Prepended to include guards, e.g. the project name
+    pub guard_prefix: Option<String>,
+    pub guard_source: GuardSource,
+    /// Namespace wrapping the generated types, e.g. `myproj::models`;
+    /// ignored in Unreal mode, where reflected types must be global
+    pub namespace: Option<String>,
+    pub standard: CppStandard,
+    /// Generate member-wise `operator==`/`operator!=`, or defaulted `==`
+    /// and `<=>` on C++20
+    pub comparisons: bool,
+    /// Generate `operator<<` printing every field, for logging
+    pub stream_operators: bool,
+    /// Generate nlohmann::json `to_json`/`from_json` functions
+    pub json: bool,
+    /// Generate a `hash()` member and a `std::hash` specialization
+    pub hash: bool,
+    /// Generate a nested `Builder` with fluent setters
+    pub builder: bool,
+    /// Generate `to_string`, `from_string` and `enum_values` for enums
+    pub enum_utilities: bool,
+    /// Names of classes and structs that own unique resources; their copy
+    /// constructor and copy assignment are deleted
+    pub move_only: Vec<String>,
+    /// Types fields hold by pointer; classes and structs with such fields
+    /// define their special members in a generated `<file>.cpp`
+    pub by_pointer: Vec<PointerType>,
+    /// Fields given getters and setters; the others are public members
+    pub accessors: AccessorMode,
+    /// Also write a GoogleTest file per class and struct
+    pub tests: bool,
+    /// Also write pybind11 bindings exposing the header to Python
+    pub pybind11: bool,
+    /// Also write a JNI bridge converting the types to and from the
+    /// classes generated for Kotlin
+    pub jni: Option<JniBridge>,
+    /// Give classes and structs a `SCHEMA_HASH` constant fingerprinting
+    /// their definition
+    pub schema_hash: bool,
+    /// Types replacing OML ones, whose headers are included where used
+    pub type_map: TypeMap,
+    pub style: CodeStyle,
+}
+
+impl CppGenerator {
+    pub fn with_unreal(mut self, unreal: bool) -> Self {
+        self.unreal = unreal;
+        self
+    }
+
+    pub fn with_pragma_once(mut self, pragma_once: bool) -> Self {
+        self.pragma_once = pragma_once;
+        self
+    }
+
+    pub fn with_guard_prefix(mut self, guard_prefix: Option<&str>) -> Self {
+        self.guard_prefix = guard_prefix.map(str::to_string);
+        self
+    }
+
+    pub fn with_guard_source(mut self, guard_source: GuardSource) -> Self {
+        self.guard_source = guard_source;
+        self
+    }
+
+    pub fn with_namespace(mut self, namespace: Option<&str>) -> Self {
+        self.namespace = namespace.map(str::to_string);
+        self
+    }
+
+    pub fn with_standard(mut self, standard: CppStandard) -> Self {
+        self.standard = standard;
+        self
+    }
+
+    pub fn with_comparisons(mut self, comparisons: bool) -> Self {
+        self.comparisons = comparisons;
+        self
+    }
+
+    pub fn with_stream_operators(mut self, stream_operators: bool) -> Self {
+        self.stream_operators = stream_operators;
+        self
+    }
+
+    pub fn with_json(mut self, json: bool) -> Self {
+        self.json = json;
+        self
+    }
+
+    pub fn with_hash(mut self, hash: bool) -> Self {
+        self.hash = hash;
+        self
+    }
+
+    pub fn with_builder(mut self, builder: bool) -> Self {
+        self.builder = builder;
+        self
+    }
+
+    pub fn with_enum_utilities(mut self, enum_utilities: bool) -> Self {
+        self.enum_utilities = enum_utilities;
+        self
+    }
+
+    pub fn with_move_only(mut self, move_only: &[String]) -> Self {
+        self.move_only = move_only.to_vec();
+        self
+    }
+
+    pub fn with_by_pointer(mut self, by_pointer: &[PointerType]) -> Self {
+        self.by_pointer = by_pointer.to_vec();
+        self
+    }
+
+    pub fn with_accessors(mut self, accessors: AccessorMode) -> Self {
+        self.accessors = accessors;
+        self
+    }
+
+    pub fn with_tests(mut self, tests: bool) -> Self {
+        self.tests = tests;
+        self
+    }
+
+    pub fn with_pybind11(mut self, pybind11: bool) -> Self {
+        self.pybind11 = pybind11;
+        self
+    }
+
+    pub fn with_jni(mut self, jni: Option<JniBridge>) -> Self {
+        self.jni = jni;
+        self
+    }
+
+    pub fn with_schema_hash(mut self, schema_hash: bool) -> Self {
+        self.schema_hash = schema_hash;
+        self
+    }
+
+    pub fn with_type_map(mut self, type_map: TypeMap) -> Self {
+        self.type_map = type_map;
+        self
+    }
+
+    /// Copies of `oml_objects` in which private fields not given accessors
+    /// are public, since nothing else could reach them. Fields given
+    /// accessors keep their visibility.
+    pub(super) fn apply_accessors(&self, oml_objects: &[OmlObject]) -> Vec<OmlObject> {
+        oml_objects
+            .iter()
+            .map(|o| {
+                let mut o = o.clone();
+                for var in &mut o.variables {
+                    if !self.accessors.has_accessors(var) && var.visibility == VariableVisibility::PRIVATE {
+                        var.visibility = VariableVisibility::PUBLIC;
+                    }
+                }
+                o
+            })
+            .collect()
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// `std::optional` before C++17 is the drop-in `tl::optional`, which
+    /// keeps the constructors and accessors identical across standards.
+    pub(super) fn optional_type(&self) -> &'static str {
+        if self.standard >= CppStandard::Cpp17 { "std::optional" } else { "tl::optional" }
+    }
+
+    /// The namespaces to open, outermost first; nested namespace
+    /// definitions need C++17.
+    pub(super) fn namespaces(&self) -> Vec<&str> {
+        match &self.namespace {
+            Some(namespace) if self.standard >= CppStandard::Cpp17 => vec![namespace.as_str()],
+            Some(namespace) => namespace.split("::").collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// How fields hold `var_type`, if they hold it by pointer.
+    pub(super) fn pointer(&self, var_type: &str) -> Option<&PointerType> {
+        self.by_pointer.iter().find(|p| p.name == var_type)
+    }
+
+    /// Whether `oml_object` holds a field by pointer, which moves its
+    /// constructors, moves and destructor into the source file, where the
+    /// types pointed to are complete.
+    pub(super) fn holds_pointers(&self, oml_object: &OmlObject) -> bool {
+        oml_object.variables.iter().any(|v| self.pointer(&v.var_type).is_some())
+    }
+
+    /// Whether `var` is a static given a value the class cannot define before
+    /// C++17, which has no inline variables; the source file defines it.
+    pub(super) fn is_defined_out_of_line(&self, var: &Variable) -> bool {
+        let is_const = var.var_mod.contains(&VariableModifier::CONST);
+        let is_literal_type = var.var_type != "string" && !var.var_mod.contains(&VariableModifier::OPTIONAL);
+        var.var_mod.contains(&VariableModifier::STATIC)
+            && var.value.is_some()
+            && self.standard < CppStandard::Cpp17
+            && !(is_const && (is_literal_type || self.get_full_type(var) == "std::string"))
+    }
+
+    /// Rejects classes and structs holding fields by pointer that are given
+    /// members needing the types pointed to in the header, or that point to
+    /// a type of another file without a header to include for it.
+    fn check_pointers(&self, oml_objects: &[OmlObject]) -> Result<(), String> {
+        let features = [
+            (self.comparisons, "comparison operators"),
+            (self.stream_operators, "stream operators"),
+            (self.json, "JSON functions"),
+            (self.hash, "hashes"),
+            (self.builder, "builders"),
+            (self.tests, "GoogleTest files"),
+            (self.pybind11, "pybind11 bindings"),
+            (self.jni.is_some(), "a JNI bridge"),
+        ];
+        for oml_object in oml_objects.iter().filter(|o| self.holds_pointers(o)) {
+            if let Some((_, feature)) = features.iter().find(|(enabled, _)| *enabled) {
+                return Err(format!("'{}' holds fields by pointer, for which {} cannot be generated", oml_object.name, feature));
+            }
+            let foreign = oml_object.variables
+                .iter()
+                .filter_map(|v| self.pointer(&v.var_type))
+                .find(|p| p.header.is_none() && !oml_objects.iter().any(|o| o.name == p.name));
+            if let Some(pointer) = foreign {
+                return Err(format!(
+                    "'{}' holds '{}' by pointer, which is declared in another file; give its header as --cpp-by-pointer {}=HEADER",
+                    oml_object.name, pointer.name, pointer.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The `class X;` or `struct X;` declarations of the types fields hold
+    /// by pointer, in the order they are first used.
+    fn forward_declarations(&self, oml_objects: &[OmlObject]) -> Vec<String> {
+        let mut declarations = Vec::new();
+        for var in oml_objects.iter().flat_map(|o| &o.variables) {
+            if self.pointer(&var.var_type).is_none() {
+                continue;
+            }
+            // a type of another file is declared a class, which a struct accepts
+            let is_struct = oml_objects.iter().any(|o| o.oml_type == ObjectType::STRUCT && o.name == var.var_type);
+            let declaration = format!("{} {};", if is_struct { "struct" } else { "class" }, var.var_type);
+            if !declarations.contains(&declaration) {
+                declarations.push(declaration);
+            }
+        }
+        declarations
+    }
+
+    /// The headers the generated code uses, in a fixed order.
+    fn includes(&self, oml_objects: &[OmlObject]) -> Vec<&'static str> {
+        let types: Vec<&OmlObject> = oml_objects
+            .iter()
+            .filter(|o| o.oml_type == ObjectType::CLASS || o.oml_type == ObjectType::STRUCT)
+            .collect();
+        let vars = || types.iter().flat_map(|o| &o.variables);
+        let is_cpp17 = self.standard >= CppStandard::Cpp17;
+        let enum_utilities = self.enum_utilities && oml_objects.iter().any(|o| o.oml_type == ObjectType::ENUM);
+
+        let mut includes = Vec::new();
+        if vars().any(|v| v.var_type.starts_with("int") || v.var_type.starts_with("uint")) || (self.schema_hash && !types.is_empty()) {
+            includes.push("<cstdint>");
+        }
+        // before C++17 enum names are parsed from a std::string
+        if vars().any(|v| v.var_type == "string") || (enum_utilities && !is_cpp17) {
+            includes.push("<string>");
+        }
+        // plain strings with accessors get string_view getters
+        if is_cpp17 && (enum_utilities || vars().any(|v| {
+            v.var_type == "string"
+                && self.accessors.has_accessors(v)
+                && v.array_kind == ArrayKind::None
+                && !v.var_mod.contains(&VariableModifier::OPTIONAL)
+        })) {
+            includes.push("<string_view>");
+        }
+        let has_fields = vars().any(|v| !v.var_mod.contains(&VariableModifier::STATIC));
+        // builders keep required fields as optionals until they are set
+        // optional pointers are null when missing
+        let optional = |v: &Variable| v.var_mod.contains(&VariableModifier::OPTIONAL)
+            && !(self.pointer(&v.var_type).is_some() && v.array_kind == ArrayKind::None);
+        if vars().any(optional) || (self.builder && has_fields) || enum_utilities {
+            includes.push(if is_cpp17 { "<optional>" } else { "<tl/optional.hpp>" });
+        }
+        // constructors move their arguments into the members
+        if has_fields {
+            includes.push("<utility>");
+        }
+        if self.comparisons && self.standard >= CppStandard::Cpp20 && !types.is_empty() {
+            includes.push("<compare>");
+        }
+        if vars().any(|v| matches!(v.array_kind, ArrayKind::Static(_))) || enum_utilities {
+            includes.push("<array>");
+        }
+        if vars().any(|v| v.array_kind == ArrayKind::Dynamic) {
+            includes.push("<vector>");
+        }
+        if vars().any(|v| self.pointer(&v.var_type).is_some()) {
+            includes.push("<memory>");
+        }
+        if self.stream_operators && !oml_objects.is_empty() {
+            includes.push("<ostream>");
+        }
+        if self.json && !oml_objects.is_empty() {
+            includes.push("<nlohmann/json.hpp>");
+        }
+        if (self.hash && !types.is_empty()) || enum_utilities {
+            includes.push("<cstddef>");
+        }
+        if self.hash && !types.is_empty() {
+            includes.push("<functional>");
+        }
+        // enums are hashed through their underlying type before C++14
+        let is_enum = |name: &str| oml_objects.iter().any(|o| o.oml_type == ObjectType::ENUM && o.name == name);
+        if self.hash && self.standard < CppStandard::Cpp14 && vars().any(|v| is_enum(&v.var_type)) {
+            includes.push("<type_traits>");
+        }
+        if self.builder && has_fields {
+            includes.push("<stdexcept>");
+        }
+
+        includes
+    }
 
-pub struct CppGenerator;
+    /// The include guard for a header, `<PREFIX>_<FILE>_H`. Characters that
+    /// cannot appear in a macro name become underscores.
+    pub(super) fn header_guard(&self, file_name: &str) -> String {
+        let guard = match &self.guard_prefix {
+            Some(prefix) => format!("{}_{}_H", prefix, file_name),
+            None => format!("{}_H", file_name),
+        };
+        guard
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+            .collect()
+    }
+
+    /// The name the include guard of the header for `oml_objects`, written
+    /// as `file_name`, is made from.
+    fn guard_name(&self, oml_objects: &[OmlObject], file_name: &str) -> String {
+        match self.guard_source {
+            GuardSource::Objects if !oml_objects.is_empty() => {
+                oml_objects.iter().map(|o| to_snake_case(&o.name)).collect::<Vec<_>>().join("_")
+            }
+            _ => file_name.to_string(),
+        }
+    }
+}
 
 impl BackwardsGenerate for CppGenerator {
     fn reverse(&self, content: &str) -> Result<Vec<OmlObject>, Box<dyn Error>> {
@@ -20,7 +446,7 @@ impl BackwardsGenerate for CppGenerator {
                 let name = trimmed
                     .strip_prefix("enum class ")
                     .unwrap()
-                    .trim_end_matches(|c: char| c == '{' || c == ' ')
+                    .trim_end_matches(['{', ' '])
                     .to_string();
                 let mut vars = Vec::new();
                 i += 1;
@@ -35,6 +461,7 @@ impl BackwardsGenerate for CppGenerator {
                             var_type: "string".to_string(),
                             array_kind: ArrayKind::None,
                             name: variant,
+                            ..Default::default()
                         });
                     }
                     i += 1;
@@ -43,6 +470,7 @@ impl BackwardsGenerate for CppGenerator {
                     oml_type: ObjectType::ENUM,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
             } else if (trimmed.starts_with("class ") || trimmed.starts_with("struct "))
                 && trimmed.ends_with('{')
@@ -52,7 +480,7 @@ impl BackwardsGenerate for CppGenerator {
                 let name = trimmed
                     .strip_prefix(prefix)
                     .unwrap()
-                    .trim_end_matches(|c: char| c == '{' || c == ' ')
+                    .trim_end_matches(['{', ' '])
                     .to_string();
                 let mut vars = Vec::new();
                 let mut current_visibility = if is_struct {
@@ -74,11 +502,12 @@ impl BackwardsGenerate for CppGenerator {
                         && !line.starts_with("//")
                         && !line.contains('(')
                         && !line.contains('~')
+                        // the generated SCHEMA_HASH constant
+                        && !line.starts_with(&format!("static constexpr uint64_t {} ", SCHEMA_HASH))
                         && line.ends_with(';')
+                        && let Some(var) = parse_cpp_field(line, &current_visibility)
                     {
-                        if let Some(var) = parse_cpp_field(line, &current_visibility) {
-                            vars.push(var);
-                        }
+                        vars.push(var);
                     }
                     i += 1;
                 }
@@ -87,6 +516,7 @@ impl BackwardsGenerate for CppGenerator {
                     oml_type,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
             }
             i += 1;
@@ -109,14 +539,31 @@ fn reverse_cpp_type(cpp_type: &str) -> String {
         "float" => "float".to_string(),
         "double" => "double".to_string(),
         "bool" => "bool".to_string(),
-        "std::string" => "string".to_string(),
+        "std::string" | "char*" => "string".to_string(),
         "char" => "char".to_string(),
-        other => other.to_string(),
+        other => match other.strip_prefix("std::unique_ptr<").and_then(|t| t.strip_suffix('>')) {
+            Some(pointee) => pointee.trim().to_string(),
+            None => other.to_string(),
+        },
     }
 }
 
 fn parse_cpp_field(line: &str, default_vis: &VariableVisibility) -> Option<Variable> {
     let line = line.trim().trim_end_matches(';').trim();
+    let (declaration, value) = match line.split_once(" = ") {
+        Some((declaration, value)) => (declaration, Some(value.trim())),
+        None => (line, None),
+    };
+    let mut var = parse_cpp_declaration(declaration, default_vis)?;
+    var.value = value.map(|value| match var.var_type.as_str() {
+        "uint64" => value.trim_end_matches("ULL").to_string(),
+        _ => value.to_string(),
+    });
+    Some(var)
+}
+
+fn parse_cpp_declaration(line: &str, default_vis: &VariableVisibility) -> Option<Variable> {
+    let line = line.trim();
     if line.is_empty() { return None; }
 
     let mut var_mod = Vec::new();
@@ -127,8 +574,18 @@ fn parse_cpp_field(line: &str, default_vis: &VariableVisibility) -> Option<Varia
         rest = &rest[7..];
     }
 
-    if rest.starts_with("const ") {
+    // constants given a value in the class
+    if let Some(after) = rest.strip_prefix("constexpr ") {
         var_mod.push(VariableModifier::CONST);
+        rest = after;
+    } else if let Some(after) = rest.strip_prefix("inline ") {
+        rest = after;
+    }
+
+    if rest.starts_with("const ") {
+        if !var_mod.contains(&VariableModifier::CONST) {
+            var_mod.push(VariableModifier::CONST);
+        }
         rest = &rest[6..];
     }
 
@@ -150,6 +607,7 @@ fn parse_cpp_field(line: &str, default_vis: &VariableVisibility) -> Option<Varia
             var_type,
             array_kind,
             name,
+            ..Default::default()
         });
     }
 
@@ -164,6 +622,7 @@ fn parse_cpp_field(line: &str, default_vis: &VariableVisibility) -> Option<Varia
             var_type: reverse_cpp_type(inner.trim()),
             array_kind: ArrayKind::Dynamic,
             name,
+            ..Default::default()
         });
     }
 
@@ -182,6 +641,7 @@ fn parse_cpp_field(line: &str, default_vis: &VariableVisibility) -> Option<Varia
                     var_type: reverse_cpp_type(elem_type),
                     array_kind: ArrayKind::Static(size),
                     name,
+                    ..Default::default()
                 });
             }
         }
@@ -198,6 +658,7 @@ fn parse_cpp_field(line: &str, default_vis: &VariableVisibility) -> Option<Varia
             var_type: reverse_cpp_type(&cpp_type),
             array_kind: ArrayKind::None,
             name,
+            ..Default::default()
         });
     }
 
@@ -228,39 +689,117 @@ fn parse_cpp_type_and_name_inner(type_str: &str) -> (String, ArrayKind) {
     (reverse_cpp_type(type_str), ArrayKind::None)
 }
 
+/// Words reserved in C++, which fields are renamed away from.
+const KEYWORDS: [&str; 92] = [
+    "alignas", "alignof", "and", "and_eq", "asm", "auto", "bitand", "bitor", "bool", "break", "case",
+    "catch", "char", "char8_t", "char16_t", "char32_t", "class", "compl", "concept", "const", "consteval",
+    "constexpr", "constinit", "const_cast", "continue", "co_await", "co_return", "co_yield", "decltype",
+    "default", "delete", "do", "double", "dynamic_cast", "else", "enum", "explicit", "export", "extern",
+    "false", "float", "for", "friend", "goto", "if", "inline", "int", "long", "mutable", "namespace",
+    "new", "noexcept", "not", "not_eq", "nullptr", "operator", "or", "or_eq", "private", "protected",
+    "public", "register", "reinterpret_cast", "requires", "return", "short", "signed", "sizeof", "static",
+    "static_assert", "static_cast", "struct", "switch", "template", "this", "thread_local", "throw",
+    "true", "try", "typedef", "typeid", "typename", "union", "unsigned", "using", "virtual", "void",
+    "volatile", "wchar_t", "while", "xor", "xor_eq",
+];
+
 impl Generate for CppGenerator {
     fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
-        let mut cpp_file = String::new();
-        let header_guard = format!("{}_H", file_name.to_uppercase());
+        if self.unreal {
+            return unreal::generate(oml_objects, file_name, self.style);
+        }
+        self.check_pointers(oml_objects)?;
+
+        let mapped_includes: Vec<String> = self.type_map
+            .used_includes(oml_objects)
+            .iter()
+            .filter_map(|o| o.include.clone())
+            .collect();
+        // fingerprints are taken before the target's types are substituted
+        let schema_hashes: Vec<u64> = oml_objects.iter().map(schema_hash).collect();
+        let objects = self.type_map.apply(&self.apply_accessors(oml_objects));
+        let oml_objects = objects.as_slice();
+
+        let mut cpp_file = CodeWriter::new(self.style);
+        let header_guard = self.header_guard(&self.guard_name(oml_objects, file_name));
 
         writeln!(cpp_file, "// This file has been generated from {}.oml", file_name)?;
-        writeln!(cpp_file, "#ifndef {}", header_guard)?;
-        writeln!(cpp_file, "#define {}", header_guard)?;
+        // documented headers are picked up by Doxygen only with a @file block
+        let documented = oml_objects.iter().any(|o| o.doc.is_some() || o.variables.iter().any(|v| v.doc.is_some()));
+        if documented {
+            writeln!(cpp_file, "/**")?;
+            writeln!(cpp_file, " * @file {}.{}", file_name, self.extension())?;
+            writeln!(cpp_file, " */")?;
+        }
+        if self.pragma_once {
+            writeln!(cpp_file, "#pragma once")?;
+        } else {
+            writeln!(cpp_file, "#ifndef {}", header_guard)?;
+            writeln!(cpp_file, "#define {}", header_guard)?;
+        }
         writeln!(cpp_file)?;
 
         let has_class_or_struct = oml_objects.iter().any(|o|
             o.oml_type == ObjectType::CLASS || o.oml_type == ObjectType::STRUCT
         );
 
-        if has_class_or_struct {
-            writeln!(cpp_file, "#include <cstdint>")?;
-            writeln!(cpp_file, "#include <string>")?;
-            writeln!(cpp_file, "#include <optional>")?;
-            writeln!(cpp_file, "#include <utility>")?;
-
-            let has_static_array = oml_objects.iter().any(|o|
-                o.variables.iter().any(|v| matches!(v.array_kind, ArrayKind::Static(_))));
-            let has_dynamic_array = oml_objects.iter().any(|o|
-                o.variables.iter().any(|v| v.array_kind == ArrayKind::Dynamic));
-            if has_static_array  { writeln!(cpp_file, "#include <array>")?; }
-            if has_dynamic_array { writeln!(cpp_file, "#include <vector>")?; }
+        let mut includes: Vec<&str> = self.includes(oml_objects);
+        for include in &mapped_includes {
+            if !includes.contains(&include.as_str()) {
+                includes.push(include);
+            }
+        }
+        for include in &includes {
+            writeln!(cpp_file, "#include {}", include)?;
+        }
+        if !includes.is_empty() {
+            writeln!(cpp_file)?;
+        }
+
+        if oml_objects.iter().any(|o| self.reads_protected_over_jni(o)) {
+            writeln!(cpp_file, "namespace oml_jni {{ template <typename T> struct Marshal; }}")?;
+            writeln!(cpp_file)?;
+        }
+
+        // namespace contents are not indented, as is customary in C++
+        let namespaces = self.namespaces();
+        for namespace in &namespaces {
+            cpp_file.open_block(0, &format!("namespace {}", namespace))?;
+        }
+        if !namespaces.is_empty() {
+            writeln!(cpp_file)?;
+        }
+
+        let forward_declarations = self.forward_declarations(oml_objects);
+        for declaration in &forward_declarations {
+            writeln!(cpp_file, "{}", declaration)?;
+        }
+        if !forward_declarations.is_empty() {
             writeln!(cpp_file)?;
         }
 
+        let first_enum = oml_objects.iter().position(|o| o.oml_type == ObjectType::ENUM);
         for (i, oml_object) in oml_objects.iter().enumerate() {
             match &oml_object.oml_type {
-                ObjectType::ENUM => generate_enum(oml_object, &mut cpp_file)?,
-                ObjectType::CLASS | ObjectType::STRUCT => generate_class_or_struct(oml_object, &mut cpp_file)?,
+                ObjectType::ENUM => {
+                    generate_enum(oml_object, &mut cpp_file)?;
+                    if self.stream_operators {
+                        writeln!(cpp_file)?;
+                        generate_enum_stream_operator(oml_object, &mut cpp_file)?;
+                    }
+                    if self.json {
+                        writeln!(cpp_file)?;
+                        generate_enum_json(oml_object, &mut cpp_file)?;
+                    }
+                    if self.enum_utilities {
+                        writeln!(cpp_file)?;
+                        self.generate_enum_utilities(oml_object, first_enum == Some(i), &mut cpp_file)?;
+                    }
+                }
+                ObjectType::CLASS | ObjectType::STRUCT => {
+                    let schema_hash = self.schema_hash.then_some(schema_hashes[i]);
+                    self.generate_class_or_struct(oml_object, oml_objects, schema_hash, &mut cpp_file)?
+                }
                 ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
             }
             if i < oml_objects.len() - 1 {
@@ -268,351 +807,1482 @@ impl Generate for CppGenerator {
             }
         }
 
-        writeln!(cpp_file, "#endif // {}\n", header_guard)?;
-
-        Ok(cpp_file)
-    }
-
-    fn extension(&self) -> &str {
-        "h"
-    }
-}
+        if !namespaces.is_empty() {
+            writeln!(cpp_file)?;
+        }
+        for namespace in namespaces.iter().rev() {
+            writeln!(cpp_file, "}} // namespace {}", namespace)?;
+        }
 
-fn generate_enum(oml_object: &OmlObject, cpp_file: &mut String) -> Result<(), std::fmt::Error> {
-    writeln!(cpp_file, "enum class {} {{", oml_object.name)?;
-    let length = oml_object.variables.len();
+        if self.hash && has_class_or_struct {
+            writeln!(cpp_file)?;
+            self.generate_hash_specializations(oml_objects, &mut cpp_file)?;
+        }
+        if self.json && oml_objects.iter().any(|o| o.oml_type != ObjectType::ENUM && constructed_from_json(o, oml_objects)) {
+            writeln!(cpp_file)?;
+            self.generate_json_serializers(oml_objects, &mut cpp_file)?;
+        }
 
-    for (index, var) in oml_object.variables.iter().enumerate() {
-        write!(cpp_file, "\t{}", var.name.to_uppercase())?;
-        if index == length-1 {
-            writeln!(cpp_file, "")?;
-            continue
+        if !self.pragma_once {
+            writeln!(cpp_file, "#endif // {}\n", header_guard)?;
         }
-        writeln!(cpp_file, ",")?;
 
+        Ok(cpp_file.finish())
     }
 
-    writeln!(cpp_file, "}};")?;
-
-    Ok(())
-}
-
-fn generate_class_or_struct(
-    oml_object: &OmlObject,
-    cpp_file: &mut String
-) -> Result<(), std::fmt::Error> {
-    let oml_type = match &oml_object.oml_type {
-        ObjectType::CLASS => "class",
-        ObjectType::STRUCT => "struct",
-        _ => return Err(std::fmt::Error)
-    };
-
-    writeln!(cpp_file, "{} {} {{", oml_type, oml_object.name)?;
-
-    // Public section: constructors, special members, getters/setters, public vars
-    writeln!(cpp_file, "public:")?;
-    generate_constructors(oml_object, cpp_file)?;
-    writeln!(cpp_file)?;
-    generate_copy_move_and_destructor(oml_object, cpp_file)?;
-    writeln!(cpp_file)?;
-    generate_getters_and_setters(&oml_object.variables, cpp_file)?;
-
-    // Public member variables (after getters/setters)
-    generate_visibility_vars(&oml_object.variables, cpp_file, VariableVisibility::PUBLIC, false)?;
-
-    // Protected and private member variables
-    generate_visibility_vars(&oml_object.variables, cpp_file, VariableVisibility::PROTECTED, true)?;
-    generate_visibility_vars(&oml_object.variables, cpp_file, VariableVisibility::PRIVATE, true)?;
-
-    writeln!(cpp_file, "}};")?;
-
-    Ok(())
-}
-
-/// Writes variables of a given visibility. If `write_label` is true, emits the
-/// visibility label (e.g. `private:`) before the variables.
-fn generate_visibility_vars(
-    variables: &Vec<Variable>,
-    cpp_file: &mut String,
-    visibility: VariableVisibility,
-    write_label: bool,
-) -> Result<(), std::fmt::Error> {
-    let vars: Vec<_> = variables
-        .iter()
-        .filter(|v| v.visibility == visibility)
-        .collect();
-
-    if vars.is_empty() {
-        return Ok(());
+    fn extension(&self) -> &str {
+        "h"
     }
 
-    if write_label {
-        let label = match visibility {
-            VariableVisibility::PUBLIC => "public:",
-            VariableVisibility::PROTECTED => "protected:",
-            VariableVisibility::PRIVATE => "private:",
-        };
-        writeln!(cpp_file, "{}", label)?;
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
     }
 
-    for var in vars {
-        convert_modifiers_and_type(var, cpp_file)?;
+    fn verify_target(&self) -> Option<VerifyTarget> {
+        Some(VerifyTarget::Cpp)
     }
 
-    Ok(())
-}
-
-#[inline]
-fn convert_type(var_type: &str) -> String {
-    match var_type {
-        "int8" => "int8_t".to_string(),
-        "int16" => "int16_t".to_string(),
-        "int32" => "int32_t".to_string(),
-        "int64" => "int64_t".to_string(),
-        "uint8" => "uint8_t".to_string(),
-        "uint16" => "uint16_t".to_string(),
-        "uint32" => "uint32_t".to_string(),
-        "uint64" => "uint64_t".to_string(),
-        "float" => "float".to_string(),
-        "double" => "double".to_string(),
-        "bool" => "bool".to_string(),
-        "string" => "std::string".to_string(),
-        "char" => "char".to_string(),
-        other => other.to_string(),
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let mut files = vec![GeneratedFile::new(format!("{}.h", file_name), self.generate(oml_objects, file_name)?)];
+        let out_of_line = |o: &OmlObject| self.holds_pointers(o) || o.variables.iter().any(|v| self.is_defined_out_of_line(v));
+        if !self.unreal && oml_objects.iter().any(out_of_line) {
+            files.push(self.generate_source(oml_objects, file_name)?);
+        }
+        if self.tests && !self.unreal {
+            files.extend(self.generate_tests(oml_objects, file_name)?);
+        }
+        if self.pybind11 && !self.unreal {
+            files.push(self.generate_bindings(oml_objects, file_name)?);
+        }
+        if let Some(bridge) = self.jni.as_ref().filter(|_| !self.unreal) {
+            files.push(self.generate_jni(bridge, oml_objects, file_name)?);
+        }
+        Ok(files)
     }
-}
 
-fn type_annotation(var_type: &str, array_kind: &ArrayKind) -> String {
-    let base = convert_type(var_type);
-    match array_kind {
-        ArrayKind::None => base,
-        ArrayKind::Static(n) => format!("std::array<{}, {}>", base, n),
-        ArrayKind::Dynamic => format!("std::vector<{}>", base),
+    fn generate_package_files(&self, modules: &[(&str, &[OmlObject])]) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        if self.jni.is_none() || self.unreal {
+            return Ok(Vec::new());
+        }
+        let file_names: Vec<&str> = modules.iter().map(|(name, _)| *name).collect();
+        Ok(vec![self.generate_jni_shared(&file_names)?])
     }
-}
-
-fn convert_modifiers_and_type(
-    var: &Variable,
-    cpp_file: &mut String
-) -> Result<(), std::fmt::Error> {
-    write!(cpp_file, "\t")?;
 
-    if var.var_mod.contains(&VariableModifier::STATIC) {
-        write!(cpp_file, "static ")?;
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Preserve,
+            constants: Case::ScreamingSnake,
+            enum_values: Case::ScreamingSnake,
+        }
     }
+}
 
-    if var.var_mod.contains(&VariableModifier::CONST)
-        && !var.var_mod.contains(&VariableModifier::MUT) {
-        write!(cpp_file, "const ")?;
-    }
+impl CppGenerator {
+    /// Writes one class or struct; `siblings` are all objects of the header
+    /// and `schema_hash` the fingerprint to embed, if any.
+    fn generate_class_or_struct(
+        &self,
+        oml_object: &OmlObject,
+        siblings: &[OmlObject],
+        schema_hash: Option<u64>,
+        cpp_file: &mut CodeWriter
+    ) -> Result<(), std::fmt::Error> {
+        let oml_type = match &oml_object.oml_type {
+            ObjectType::CLASS => "class",
+            ObjectType::STRUCT => "struct",
+            _ => return Err(std::fmt::Error)
+        };
 
-    let var_type = get_full_type(var);
-    write!(cpp_file, "{}", var_type)?;
+        write_doc_block(&oml_object.doc, cpp_file)?;
+        cpp_file.open_block(0, &format!("{} {}", oml_type, oml_object.name))?;
 
-    writeln!(cpp_file, " {};", var.name)?;
+        // Public section: constructors, special members, getters/setters, public vars
+        writeln!(cpp_file, "public:")?;
+        if self.reads_protected_over_jni(oml_object) {
+            writeln!(cpp_file, "{}friend struct ::oml_jni::Marshal<{}>;", cpp_file.indent(1), oml_object.name)?;
+            writeln!(cpp_file)?;
+        }
+        if let Some(hash) = schema_hash {
+            writeln!(cpp_file, "{}static constexpr uint64_t {} = 0x{:016x}ULL;", cpp_file.indent(1), SCHEMA_HASH, hash)?;
+            writeln!(cpp_file)?;
+        }
+        self.generate_constructors(oml_object, cpp_file)?;
+        writeln!(cpp_file)?;
+        if self.holds_pointers(oml_object) {
+            self.declare_pointer_special_members(oml_object, cpp_file)?;
+        } else {
+            let move_only = self.move_only.contains(&oml_object.name);
+            generate_copy_move_and_destructor(oml_object, move_only, cpp_file)?;
+        }
+        writeln!(cpp_file)?;
+        if self.comparisons {
+            self.generate_comparison_operators(oml_object, cpp_file)?;
+            writeln!(cpp_file)?;
+        }
+        if self.stream_operators {
+            self.generate_stream_operator(oml_object, cpp_file)?;
+            writeln!(cpp_file)?;
+        }
+        if self.json {
+            self.generate_json_functions(oml_object, siblings, cpp_file)?;
+            writeln!(cpp_file)?;
+        }
+        if self.hash {
+            self.generate_hash_member(oml_object, siblings, cpp_file)?;
+            writeln!(cpp_file)?;
+        }
+        if self.builder {
+            self.generate_builder(oml_object, cpp_file)?;
+        }
+        self.generate_getters_and_setters(&oml_object.variables, cpp_file)?;
 
-    Ok(())
-}
+        // Public member variables (after getters/setters)
+        self.generate_visibility_vars(&oml_object.variables, cpp_file, VariableVisibility::PUBLIC, false)?;
 
-fn capitalize_first(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(c) => c.to_uppercase().to_string() + chars.as_str(),
-    }
-}
+        // Protected and private member variables
+        self.generate_visibility_vars(&oml_object.variables, cpp_file, VariableVisibility::PROTECTED, true)?;
+        self.generate_visibility_vars(&oml_object.variables, cpp_file, VariableVisibility::PRIVATE, true)?;
 
-fn generate_getters_and_setters(
-    variables: &Vec<Variable>,
-    cpp_file: &mut String,
-) -> Result<(), std::fmt::Error> {
-    let private_vars = variables
-        .iter()
-        .filter(|v| v.visibility == VariableVisibility::PRIVATE)
-        .collect::<Vec<_>>();
+        writeln!(cpp_file, "}};")?;
 
-    if private_vars.is_empty() {
-        return Ok(());
+        Ok(())
     }
 
-    for var in &private_vars {
-        let cpp_type = get_full_type(var);
-        let capitalized = capitalize_first(&var.name);
-
-        // Getter
-        writeln!(cpp_file, "\t{} get{}() const {{ return {}; }}", cpp_type, capitalized, var.name)?;
+    /// Whether the JNI bridge is made a friend of `oml_object` to read its
+    /// protected fields, which the Kotlin side needs set like any other.
+    fn reads_protected_over_jni(&self, oml_object: &OmlObject) -> bool {
+        self.jni.is_some()
+            && !self.unreal
+            && oml_object.variables.iter().any(|v| {
+                v.visibility == VariableVisibility::PROTECTED && !v.var_mod.contains(&VariableModifier::STATIC)
+            })
     }
 
-    writeln!(cpp_file)?;
+    /// Equality compares every instance member in declaration order. C++20
+    /// defaults both `==` and `<=>`, which need the member types, including
+    /// other generated types, to be comparable in turn.
+    fn generate_comparison_operators(
+        &self,
+        oml_object: &OmlObject,
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
 
-    for var in &private_vars {
-        // Skip setters for const variables
-        if var.var_mod.contains(&VariableModifier::CONST) {
-            continue;
+        if self.standard >= CppStandard::Cpp20 {
+            writeln!(cpp_file, "{}bool operator==(const {}& other) const = default;", cpp_file.indent(1), name)?;
+            writeln!(cpp_file, "{}auto operator<=>(const {}& other) const = default;", cpp_file.indent(1), name)?;
+            return Ok(());
         }
 
-        let cpp_type = get_full_type(var);
-        let capitalized = capitalize_first(&var.name);
+        let comparisons: Vec<String> = oml_object.variables
+            .iter()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+            .map(|v| format!("{} == other.{}", v.name, v.name))
+            .collect();
 
-        // Setter
+        cpp_file.open_block(1, &format!("bool operator==(const {}& other) const", name))?;
+        if comparisons.is_empty() {
+            writeln!(cpp_file, "{}return true;", cpp_file.indent(2))?;
+        } else {
+            let single_line = format!("return {};", comparisons.join(" && "));
+            if cpp_file.fits(2, &single_line) {
+                writeln!(cpp_file, "{}{}", cpp_file.indent(2), single_line)?;
+            } else {
+                write!(cpp_file, "{}return {}", cpp_file.indent(2), comparisons[0])?;
+                for comparison in &comparisons[1..] {
+                    write!(cpp_file, "\n{}&& {}", cpp_file.indent(3), comparison)?;
+                }
+                writeln!(cpp_file, ";")?;
+            }
+        }
+        writeln!(cpp_file, "{}}}", cpp_file.indent(1))?;
         writeln!(
             cpp_file,
-            "\tvoid set{}(const {}& value) {{ {} = value; }}",
-            capitalized, cpp_type, var.name
+            "{}bool operator!=(const {}& other) const {{ return !(*this == other); }}",
+            cpp_file.indent(1), name
         )?;
+
+        Ok(())
     }
 
-    Ok(())
-}
+    /// A friend `operator<<` printing `Name{field=value, ...}`. Empty
+    /// optionals print as `null`; element and field types without an
+    /// `operator<<` of their own must be given one, which generated types get
+    /// from the same option.
+    fn generate_stream_operator(
+        &self,
+        oml_object: &OmlObject,
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let fields: Vec<&Variable> = oml_object.variables
+            .iter()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+            .collect();
 
-fn get_full_type(var: &Variable) -> String {
-    let base_type = type_annotation(&var.var_type, &var.array_kind);
-    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
-        format!("std::optional<{}>", base_type)
-    } else {
-        base_type
+        cpp_file.open_block(1, &format!("friend std::ostream& operator<<(std::ostream& os, const {}& value)", name))?;
+        writeln!(cpp_file, "{}os << \"{}{{\";", cpp_file.indent(2), name)?;
+        for (i, var) in fields.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            writeln!(cpp_file, "{}os << \"{}{}=\";", cpp_file.indent(2), separator, var.name)?;
+
+            let member = format!("value.{}", var.name);
+            if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+                cpp_file.open_block(2, &format!("if ({})", member))?;
+                self.write_streamed_value(&format!("*{}", member), var, 3, cpp_file)?;
+                match self.style.brace_style {
+                    BraceStyle::SameLine => writeln!(cpp_file, "{}}} else {{", cpp_file.indent(2))?,
+                    BraceStyle::NextLine => writeln!(cpp_file, "{}}}\n{}else\n{}{{", cpp_file.indent(2), cpp_file.indent(2), cpp_file.indent(2))?,
+                }
+                writeln!(cpp_file, "{}os << \"null\";", cpp_file.indent(3))?;
+                writeln!(cpp_file, "{}}}", cpp_file.indent(2))?;
+            } else {
+                self.write_streamed_value(&member, var, 2, cpp_file)?;
+            }
+        }
+        writeln!(cpp_file, "{}return os << '}}';", cpp_file.indent(2))?;
+        writeln!(cpp_file, "{}}}", cpp_file.indent(1))?;
+
+        Ok(())
+    }
+
+    /// Friend `to_json`/`from_json` found by nlohmann::json through ADL.
+    /// Empty optionals are written as `null`, and missing or `null` keys
+    /// read back as empty. Objects that cannot be assigned field by field
+    /// get a static `fromJson` passing every field to the constructor
+    /// instead, which their `adl_serializer` reads them through.
+    fn generate_json_functions(
+        &self,
+        oml_object: &OmlObject,
+        siblings: &[OmlObject],
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let fields: Vec<&Variable> = oml_object.variables
+            .iter()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+            .collect();
+
+        cpp_file.open_block(1, &format!("friend void to_json(nlohmann::json& j, const {}& value)", name))?;
+        writeln!(cpp_file, "{}j = nlohmann::json::object();", cpp_file.indent(2))?;
+        for var in &fields {
+            if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+                writeln!(
+                    cpp_file,
+                    "{}j[\"{}\"] = value.{} ? nlohmann::json(*value.{}) : nlohmann::json(nullptr);",
+                    cpp_file.indent(2), var.name, var.name, var.name
+                )?;
+            } else {
+                writeln!(cpp_file, "{}j[\"{}\"] = value.{};", cpp_file.indent(2), var.name, var.name)?;
+            }
+        }
+        writeln!(cpp_file, "{}}}", cpp_file.indent(1))?;
+
+        if constructed_from_json(oml_object, siblings) {
+            cpp_file.open_block(1, &format!("static {} fromJson(const nlohmann::json& j)", name))?;
+            writeln!(cpp_file, "{}return {}(", cpp_file.indent(2), name)?;
+            for (i, var) in fields.iter().enumerate() {
+                let separator = if i + 1 < fields.len() { "," } else { "" };
+                writeln!(cpp_file, "{}{}{}", cpp_file.indent(3), self.json_value(var), separator)?;
+            }
+            writeln!(cpp_file, "{});", cpp_file.indent(2))?;
+            writeln!(cpp_file, "{}}}", cpp_file.indent(1))?;
+            return Ok(());
+        }
+
+        cpp_file.open_block(1, &format!("friend void from_json(const nlohmann::json& j, {}& value)", name))?;
+        for var in &fields {
+            if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+                writeln!(cpp_file, "{}value.{} = {};", cpp_file.indent(2), var.name, self.json_value(var))?;
+            } else {
+                writeln!(cpp_file, "{}j.at(\"{}\").get_to(value.{});", cpp_file.indent(2), var.name, var.name)?;
+            }
+        }
+        writeln!(cpp_file, "{}}}", cpp_file.indent(1))?;
+
+        Ok(())
     }
-}
 
-const MAX_LINE_LENGTH: usize = 120;
+    /// The value of `var` read from the JSON object `j`, empty for an
+    /// optional whose key is missing or `null`.
+    fn json_value(&self, var: &Variable) -> String {
+        if !var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            return format!("j.at(\"{}\").get<{}>()", var.name, self.get_full_type(var));
+        }
+        let optional = self.get_full_type(var);
+        let inner = type_annotation(&var.var_type, &var.array_kind);
+        format!(
+            "j.contains(\"{}\") && !j.at(\"{}\").is_null() ? {}(j.at(\"{}\").get<{}>()) : {}()",
+            var.name, var.name, optional, var.name, inner, optional
+        )
+    }
+
+    /// `nlohmann::adl_serializer` specializations for the objects read
+    /// through `fromJson`, which nlohmann::json needs for types without a
+    /// default constructor. Writing goes back to the friend `to_json`.
+    fn generate_json_serializers(
+        &self,
+        oml_objects: &[OmlObject],
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let types: Vec<String> = oml_objects
+            .iter()
+            .filter(|o| matches!(o.oml_type, ObjectType::CLASS | ObjectType::STRUCT) && constructed_from_json(o, oml_objects))
+            .map(|o| self.qualified_name(&o.name))
+            .collect();
 
-fn write_constructor(
-    cpp_file: &mut String,
-    prefix: &str,
-    name: &str,
-    params: &[String],
-    inits: &[String],
-) -> Result<(), std::fmt::Error> {
-    let params_str = params.join(", ");
-    let inits_str = inits.join(", ");
+        cpp_file.open_block(0, "namespace nlohmann")?;
+        for (i, ty) in types.iter().enumerate() {
+            writeln!(cpp_file, "template <>")?;
+            cpp_file.open_block(0, &format!("struct adl_serializer<{}>", ty))?;
+            writeln!(cpp_file, "{}static {} from_json(const json& j) {{ return {}::fromJson(j); }}", cpp_file.indent(1), ty, ty)?;
+            writeln!(
+                cpp_file,
+                "{}static void to_json(json& j, const {}& value) {{ ::nlohmann::to_json(j, value); }}",
+                cpp_file.indent(1), ty
+            )?;
+            writeln!(cpp_file, "}};")?;
+            if i < types.len() - 1 {
+                writeln!(cpp_file)?;
+            }
+        }
+        writeln!(cpp_file, "}} // namespace nlohmann")?;
+
+        Ok(())
+    }
 
-    let single_line = format!("\t{}{}({}) : {} {{}}", prefix, name, params_str, inits_str);
+    /// `name` qualified with the `--cpp-namespace`, for use outside it.
+    fn qualified_name(&self, name: &str) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}::{}", namespace, name),
+            None => name.to_string(),
+        }
+    }
 
-    if single_line.len() <= MAX_LINE_LENGTH {
-        writeln!(cpp_file, "{}", single_line)?;
-    } else {
-        // Signature on first line, initializers indented on following lines
-        writeln!(cpp_file, "\t{}{}({})", prefix, name, params_str)?;
-        write!(cpp_file, "\t\t: ")?;
+    /// Combines the hashes of every instance member, boost-style. Array
+    /// elements are hashed one by one, since the standard containers have no
+    /// `std::hash`. Types generated in the same header are hashed through
+    /// their `hash()` member, as their `std::hash` is only specialized at the
+    /// end of the header; types from other headers need a `std::hash`, which
+    /// enums have and generated types get from the same option.
+    fn generate_hash_member(
+        &self,
+        oml_object: &OmlObject,
+        siblings: &[OmlObject],
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let local_types: HashSet<&str> = siblings
+            .iter()
+            .filter(|o| o.oml_type == ObjectType::CLASS || o.oml_type == ObjectType::STRUCT)
+            .map(|o| o.name.as_str())
+            .collect();
+        // std::hash only covers enums from C++14 on
+        let enums_by_value: HashSet<&str> = siblings
+            .iter()
+            .filter(|o| o.oml_type == ObjectType::ENUM && self.standard < CppStandard::Cpp14)
+            .map(|o| o.name.as_str())
+            .collect();
 
-        // Try all inits on one line after the colon
-        let colon_line = format!("\t\t: {} {{}}", inits_str);
-        if colon_line.len() <= MAX_LINE_LENGTH {
-            writeln!(cpp_file, "{} {{}}", inits_str)?;
-        } else {
-            // Each initializer on its own line
-            for (i, init) in inits.iter().enumerate() {
-                if i == 0 {
-                    writeln!(cpp_file, "{}", init)?;
-                } else {
-                    writeln!(cpp_file, "\t\t, {}", init)?;
+        cpp_file.open_block(1, "std::size_t hash() const")?;
+        writeln!(cpp_file, "{}std::size_t seed = 0;", cpp_file.indent(2))?;
+        writeln!(
+            cpp_file,
+            "{}auto combine = [&seed](std::size_t h) {{ seed ^= h + 0x9e3779b9 + (seed << 6) + (seed >> 2); }};",
+            cpp_file.indent(2)
+        )?;
+        for var in oml_object.variables.iter().filter(|v| !v.var_mod.contains(&VariableModifier::STATIC)) {
+            let hash_of = |value: &str| if local_types.contains(var.var_type.as_str()) {
+                match value.strip_prefix('*') {
+                    Some(optional) => format!("{}->hash()", optional),
+                    None => format!("{}.hash()", value),
                 }
+            } else if enums_by_value.contains(var.var_type.as_str()) {
+                let underlying = format!("std::underlying_type<{}>::type", var.var_type);
+                format!("std::hash<{}>{{}}(static_cast<{}>({}))", underlying, underlying, value)
+            } else {
+                format!("std::hash<{}>{{}}({})", convert_type(&var.var_type), value)
+            };
+            let (member, guard) = if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+                writeln!(cpp_file, "{}combine({}.has_value());", cpp_file.indent(2), var.name)?;
+                (format!("*{}", var.name), format!("if ({}) ", var.name))
+            } else {
+                (var.name.clone(), String::new())
+            };
+            if var.array_kind == ArrayKind::None {
+                writeln!(cpp_file, "{}{}combine({});", cpp_file.indent(2), guard, hash_of(&member))?;
+            } else {
+                writeln!(
+                    cpp_file,
+                    "{}{}for (const auto& item : {}) combine({});",
+                    cpp_file.indent(2), guard, member, hash_of("item")
+                )?;
             }
-            writeln!(cpp_file, "\t{{}}")?;
         }
+        writeln!(cpp_file, "{}return seed;", cpp_file.indent(2))?;
+        writeln!(cpp_file, "{}}}", cpp_file.indent(1))?;
+
+        Ok(())
     }
 
-    Ok(())
-}
+    /// `std::hash` specializations forwarding to the `hash()` members; they
+    /// must live in `std`, outside any namespace of the generated types.
+    fn generate_hash_specializations(
+        &self,
+        oml_objects: &[OmlObject],
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let types: Vec<String> = oml_objects
+            .iter()
+            .filter(|o| o.oml_type == ObjectType::CLASS || o.oml_type == ObjectType::STRUCT)
+            .map(|o| self.qualified_name(&o.name))
+            .collect();
 
-fn generate_constructors(
-    oml_object: &OmlObject,
-    cpp_file: &mut String,
-) -> Result<(), std::fmt::Error> {
-    let all_vars: Vec<&Variable> = oml_object.variables.iter().collect();
+        cpp_file.open_block(0, "namespace std")?;
+        for (i, ty) in types.iter().enumerate() {
+            writeln!(cpp_file, "template <>")?;
+            cpp_file.open_block(0, &format!("struct hash<{}>", ty))?;
+            writeln!(
+                cpp_file,
+                "{}std::size_t operator()(const {}& value) const noexcept {{ return value.hash(); }}",
+                cpp_file.indent(1), ty
+            )?;
+            writeln!(cpp_file, "}};")?;
+            if i < types.len() - 1 {
+                writeln!(cpp_file)?;
+            }
+        }
+        writeln!(cpp_file, "}} // namespace std")?;
+
+        Ok(())
+    }
+
+    /// `to_string` overloads, plus `from_string<E>` and `enum_values<E>`
+    /// specializations; the primary templates are declared once per header,
+    /// before the first enum.
+    fn generate_enum_utilities(
+        &self,
+        oml_object: &OmlObject,
+        first: bool,
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let variants: Vec<String> = oml_object.variables.iter().map(|v| v.name.to_uppercase()).collect();
+        let optional = self.optional_type();
+        let (text, text_param) = if self.standard >= CppStandard::Cpp17 {
+            ("std::string_view", "std::string_view")
+        } else {
+            ("const char*", "const std::string&")
+        };
 
-    if all_vars.is_empty() {
-        writeln!(cpp_file, "\t{}() = default;", oml_object.name)?;
-        return Ok(());
-    }
+        if first {
+            writeln!(cpp_file, "template <typename E>")?;
+            writeln!(cpp_file, "{}<E> from_string({} name);", optional, text_param)?;
+            writeln!(cpp_file)?;
+            writeln!(cpp_file, "template <typename E>")?;
+            writeln!(cpp_file, "struct enum_values;")?;
+            writeln!(cpp_file)?;
+        }
+
+        cpp_file.open_block(0, &format!("inline {} to_string({} value)", text, name))?;
+        if !variants.is_empty() {
+            cpp_file.open_block(1, "switch (value)")?;
+            for variant in &variants {
+                writeln!(cpp_file, "{}case {}::{}: return \"{}\";", cpp_file.indent(2), name, variant, variant)?;
+            }
+            writeln!(cpp_file, "{}}}", cpp_file.indent(1))?;
+        }
+        writeln!(cpp_file, "{}return \"\";", cpp_file.indent(1))?;
+        writeln!(cpp_file, "}}")?;
+        writeln!(cpp_file)?;
+
+        writeln!(cpp_file, "template <>")?;
+        cpp_file.open_block(0, &format!("inline {}<{}> from_string<{}>({} name)", optional, name, name, text_param))?;
+        for variant in &variants {
+            writeln!(cpp_file, "{}if (name == \"{}\") return {}::{};", cpp_file.indent(1), variant, name, variant)?;
+        }
+        writeln!(cpp_file, "{}return {}<{}>();", cpp_file.indent(1), optional, name)?;
+        writeln!(cpp_file, "}}")?;
+        writeln!(cpp_file)?;
+
+        let all: Vec<String> = variants.iter().map(|v| format!("{}::{}", name, v)).collect();
+        writeln!(cpp_file, "template <>")?;
+        cpp_file.open_block(0, &format!("struct enum_values<{}>", name))?;
+        writeln!(cpp_file, "{}static constexpr std::size_t count = {};", cpp_file.indent(1), variants.len())?;
+        writeln!(
+            cpp_file,
+            "{}static constexpr std::array<{}, {}> all() {{ return {{{{{}}}}}; }}",
+            cpp_file.indent(1), name, variants.len(), all.join(", ")
+        )?;
+        writeln!(cpp_file, "}};")?;
+
+        Ok(())
+    }
+
+    /// A nested `Builder` with one fluent setter per instance field. `build()`
+    /// throws `std::logic_error` naming the first required field that was
+    /// never set, then calls the constructor taking every field.
+    fn generate_builder(
+        &self,
+        oml_object: &OmlObject,
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let fields = builder_fields(oml_object);
+        if fields.is_empty() {
+            return Ok(());
+        }
+
+        cpp_file.open_block(1, "class Builder")?;
+        writeln!(cpp_file, "{}public:", cpp_file.indent(1))?;
+        for BuilderField { var, .. } in &fields {
+            writeln!(
+                cpp_file,
+                "{}Builder& {}({} value) {{ {}_ = std::move(value); return *this; }}",
+                cpp_file.indent(2), var.name, type_annotation(&var.var_type, &var.array_kind), var.name
+            )?;
+        }
+        writeln!(cpp_file)?;
 
-    let required_vars: Vec<&&Variable> = all_vars
-        .iter()
-        .filter(|v| !v.var_mod.contains(&VariableModifier::OPTIONAL))
-        .collect();
+        cpp_file.open_block(2, &format!("{} build() const", name))?;
+        let mut args = Vec::new();
+        for BuilderField { var, required } in &fields {
+            if *required {
+                writeln!(
+                    cpp_file,
+                    "{}if (!{}_) throw std::logic_error(\"{}::Builder: {} is not set\");",
+                    cpp_file.indent(3), var.name, name, var.name
+                )?;
+                args.push(format!("*{}_", var.name));
+            } else {
+                args.push(format!("{}_", var.name));
+            }
+        }
+        writeln!(cpp_file, "{}return {}({});", cpp_file.indent(3), name, args.join(", "))?;
+        writeln!(cpp_file, "{}}}", cpp_file.indent(2))?;
+        writeln!(cpp_file)?;
 
-    let optional_vars: Vec<&&Variable> = all_vars
-        .iter()
-        .filter(|v| v.var_mod.contains(&VariableModifier::OPTIONAL))
-        .collect();
+        writeln!(cpp_file, "{}private:", cpp_file.indent(1))?;
+        for BuilderField { var, required } in &fields {
+            let storage = if *required {
+                format!("{}<{}>", self.optional_type(), type_annotation(&var.var_type, &var.array_kind))
+            } else {
+                self.get_full_type(var)
+            };
+            writeln!(cpp_file, "{}{} {}_;", cpp_file.indent(2), storage, var.name)?;
+        }
+        writeln!(cpp_file, "{}}};", cpp_file.indent(1))?;
+        writeln!(cpp_file)?;
 
-    // Default constructor
-    writeln!(cpp_file, "\t{}() = default;", oml_object.name)?;
+        Ok(())
+    }
+
+    /// Statements streaming `expr`, a value of the variable's (non-optional)
+    /// type; arrays print as `[a, b]`.
+    fn write_streamed_value(
+        &self,
+        expr: &str,
+        var: &Variable,
+        level: usize,
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        if var.array_kind == ArrayKind::None {
+            writeln!(cpp_file, "{}os << {};", cpp_file.indent(level), streamable(&var.var_type, expr))?;
+            return Ok(());
+        }
 
-    // Constructor with required params only (if there are optional vars, otherwise skip since
-    // the full constructor below would be identical)
-    if !required_vars.is_empty() && !optional_vars.is_empty() {
-        let params: Vec<String> = required_vars
+        writeln!(cpp_file, "{}os << '[';", cpp_file.indent(level))?;
+        cpp_file.open_block(level, &format!("for (std::size_t i = 0; i < ({}).size(); ++i)", expr))?;
+        writeln!(cpp_file, "{}if (i > 0) os << \", \";", cpp_file.indent(level + 1))?;
+        let element = format!("({})[i]", expr);
+        writeln!(cpp_file, "{}os << {};", cpp_file.indent(level + 1), streamable(&var.var_type, &element))?;
+        writeln!(cpp_file, "{}}}", cpp_file.indent(level))?;
+        writeln!(cpp_file, "{}os << ']';", cpp_file.indent(level))?;
+
+        Ok(())
+    }
+
+    /// Writes variables of a given visibility. If `write_label` is true, emits the
+    /// visibility label (e.g. `private:`) before the variables.
+    fn generate_visibility_vars(
+        &self,
+        variables: &[Variable],
+        cpp_file: &mut CodeWriter,
+        visibility: VariableVisibility,
+        write_label: bool,
+    ) -> Result<(), std::fmt::Error> {
+        let vars: Vec<_> = variables
             .iter()
-            .map(|v| format!("{} {}", get_full_type(v), v.name))
+            .filter(|v| v.visibility == visibility)
             .collect();
 
-        let inits: Vec<String> = required_vars
+        if vars.is_empty() {
+            return Ok(());
+        }
+
+        if write_label {
+            let label = match visibility {
+                VariableVisibility::PUBLIC => "public:",
+                VariableVisibility::PROTECTED => "protected:",
+                VariableVisibility::PRIVATE => "private:",
+            };
+            writeln!(cpp_file, "{}", label)?;
+        }
+
+        for var in vars {
+            self.convert_modifiers_and_type(var, cpp_file)?;
+        }
+
+        Ok(())
+    }
+
+    fn convert_modifiers_and_type(
+        &self,
+        var: &Variable,
+        cpp_file: &mut CodeWriter
+    ) -> Result<(), std::fmt::Error> {
+        write!(cpp_file, "{}", cpp_file.indent(1))?;
+
+        let var_type = self.get_full_type(var);
+        let is_static = var.var_mod.contains(&VariableModifier::STATIC);
+        let is_const = var.var_mod.contains(&VariableModifier::CONST);
+        let doc = trailing_doc(&var.doc);
+        // a static given a value is defined in the class where the standard
+        // allows it, and otherwise by the source file
+        if is_static && let Some(value) = &var.value {
+            let literal = cpp_literal(&var.var_type, value);
+            let is_literal_type = var.var_type != "string" && !var.var_mod.contains(&VariableModifier::OPTIONAL);
+            if is_const && is_literal_type {
+                return writeln!(cpp_file, "static constexpr {} {} = {};{}", var_type, var.name, literal, doc);
+            }
+            if self.standard >= CppStandard::Cpp17 {
+                let constness = if is_const { "const " } else { "" };
+                return writeln!(cpp_file, "static inline {}{} {} = {};{}", constness, var_type, var.name, literal, doc);
+            }
+            // there is no constexpr std::string before C++20
+            if is_const && var_type == "std::string" {
+                return writeln!(cpp_file, "static constexpr const char* {} = {};{}", var.name, literal, doc);
+            }
+        }
+
+        if is_static {
+            write!(cpp_file, "static ")?;
+        }
+
+        if var.is_const() {
+            write!(cpp_file, "const ")?;
+        }
+
+        write!(cpp_file, "{}", var_type)?;
+
+        writeln!(cpp_file, " {};{}", var.name, doc)?;
+
+        Ok(())
+    }
+
+    fn generate_getters_and_setters(
+        &self,
+        variables: &[Variable],
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let accessor_vars = variables
             .iter()
-            .map(|v| format!("{}(std::move({}))", v.name, v.name))
-            .collect();
+            .filter(|v| self.accessors.has_accessors(v))
+            .collect::<Vec<_>>();
+
+        if accessor_vars.is_empty() {
+            return Ok(());
+        }
+
+        for var in &accessor_vars {
+            let mut cpp_type = self.get_full_type(var);
+            let capitalized = capitalize_first(&var.name);
+
+            // pointers are lent out rather than copied
+            if self.pointer(&var.var_type).is_some() {
+                let (cpp_type, value) = match var.array_kind {
+                    ArrayKind::None => (format!("const {}*", var.var_type), format!("{}.get()", var.name)),
+                    _ => (format!("const {}&", cpp_type), var.name.clone()),
+                };
+                writeln!(cpp_file, "{}{} get{}() const {{ return {}; }}", cpp_file.indent(1), cpp_type, capitalized, value)?;
+                continue;
+            }
+
+            // Getter; plain strings are viewed rather than copied on C++17
+            if self.standard >= CppStandard::Cpp17 && cpp_type == "std::string" {
+                cpp_type = "std::string_view".to_string();
+            }
+            writeln!(cpp_file, "{}{} get{}() const {{ return {}; }}", cpp_file.indent(1), cpp_type, capitalized, var.name)?;
+        }
+
+        writeln!(cpp_file)?;
+
+        for var in &accessor_vars {
+            // Skip setters for const variables
+            if var.var_mod.contains(&VariableModifier::CONST) {
+                continue;
+            }
+
+            let cpp_type = self.get_full_type(var);
+            let capitalized = capitalize_first(&var.name);
+
+            // replacing a pointer destroys the old value, which the source file can
+            if self.pointer(&var.var_type).is_some() {
+                writeln!(cpp_file, "{}void set{}({} value);", cpp_file.indent(1), capitalized, cpp_type)?;
+                continue;
+            }
+
+            // Setter
+            writeln!(
+                cpp_file,
+                "{}void set{}(const {}& value) {{ {} = value; }}", cpp_file.indent(1),
+                capitalized, cpp_type, var.name
+            )?;
+        }
 
-        write_constructor(cpp_file, "explicit ", &oml_object.name, &params, &inits)?;
+        Ok(())
     }
 
-    // Constructor with all params
-    {
-        let params: Vec<String> = all_vars
+    pub(super) fn get_full_type(&self, var: &Variable) -> String {
+        if self.pointer(&var.var_type).is_some() {
+            let pointer = format!("std::unique_ptr<{}>", var.var_type);
+            return match var.array_kind {
+                // a null pointer already stands for a missing value
+                ArrayKind::None => pointer,
+                ArrayKind::Static(n) => format!("std::array<{}, {}>", pointer, n),
+                ArrayKind::Dynamic => format!("std::vector<{}>", pointer),
+            };
+        }
+        let base_type = type_annotation(&var.var_type, &var.array_kind);
+        if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            format!("{}<{}>", self.optional_type(), base_type)
+        } else {
+            base_type
+        }
+    }
+
+    /// The constructors of `oml_object`: the default one, one taking the
+    /// required fields if some are optional, and one taking every field.
+    /// Static members are not initialized per object.
+    pub(super) fn constructors(&self, oml_object: &OmlObject) -> Vec<Constructor> {
+        let all_vars: Vec<&Variable> = oml_object.variables
             .iter()
-            .map(|v| format!("{} {}", get_full_type(v), v.name))
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
             .collect();
+        let constructor = |explicit: bool, vars: &[&Variable]| Constructor {
+            explicit,
+            params: vars.iter().map(|v| format!("{} {}", self.get_full_type(v), v.name)).collect(),
+            inits: vars.iter().map(|v| format!("{}(std::move({}))", v.name, v.name)).collect(),
+        };
+
+        let mut constructors = vec![constructor(false, &[])];
+        if all_vars.is_empty() {
+            return constructors;
+        }
 
-        let inits: Vec<String> = all_vars
+        let required_vars: Vec<&Variable> = all_vars
             .iter()
-            .map(|v| format!("{}(std::move({}))", v.name, v.name))
+            .copied()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::OPTIONAL))
             .collect();
 
-        write_constructor(cpp_file, "", &oml_object.name, &params, &inits)?;
+        // Constructor with required params only (if there are optional vars, otherwise skip since
+        // the full constructor below would be identical)
+        if !required_vars.is_empty() && required_vars.len() < all_vars.len() {
+            constructors.push(constructor(true, &required_vars));
+        }
+
+        // Constructor with all params
+        constructors.push(constructor(false, &all_vars));
+        constructors
+    }
+
+    /// Writes the constructors, or only declares them for a class holding
+    /// fields by pointer, whose source file defines them.
+    fn generate_constructors(
+        &self,
+        oml_object: &OmlObject,
+        cpp_file: &mut CodeWriter,
+    ) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let out_of_line = self.holds_pointers(oml_object);
+        for constructor in self.constructors(oml_object) {
+            let prefix = if constructor.explicit { "explicit " } else { "" };
+            if constructor.params.is_empty() {
+                let definition = match out_of_line {
+                    true => declared(self.pointer_members(oml_object).default_constructor),
+                    false => " = default",
+                };
+                writeln!(cpp_file, "{}{}(){};", cpp_file.indent(1), name, definition)?;
+            } else if out_of_line {
+                writeln!(cpp_file, "{}{}{}({});", cpp_file.indent(1), prefix, name, constructor.params.join(", "))?;
+            } else {
+                write_constructor(cpp_file, 1, prefix, name, &constructor.params, &constructor.inits)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Which special members a class holding fields by pointer has: const
+    /// members must be initialized and cannot be assigned, and const
+    /// pointers cannot be moved from.
+    pub(super) fn pointer_members(&self, oml_object: &OmlObject) -> PointerMembers {
+        let consts = || oml_object.variables
+            .iter()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC) && v.var_mod.contains(&VariableModifier::CONST));
+        PointerMembers {
+            default_constructor: consts().next().is_none(),
+            move_constructor: !consts().any(|v| self.pointer(&v.var_type).is_some()),
+            move_assignment: consts().next().is_none(),
+        }
+    }
+
+    /// Declares the special members of a class holding fields by pointer:
+    /// pointers are not copied, and moving or destroying them needs the
+    /// types pointed to, so the source file defines those members.
+    fn declare_pointer_special_members(&self, oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let members = self.pointer_members(oml_object);
+        writeln!(cpp_file, "{}{}(const {}& other) = delete;", cpp_file.indent(1), name, name)?;
+        writeln!(cpp_file, "{}{}({}&& other) noexcept{};", cpp_file.indent(1), name, name, declared(members.move_constructor))?;
+        writeln!(cpp_file, "{}{}& operator=(const {}& other) = delete;", cpp_file.indent(1), name, name)?;
+        writeln!(cpp_file, "{}{}& operator=({}&& other) noexcept{};", cpp_file.indent(1), name, name, declared(members.move_assignment))?;
+        writeln!(cpp_file, "{}~{}();", cpp_file.indent(1), name)?;
+
+        Ok(())
+    }
+}
+
+/// The special members a class holding fields by pointer has; the others
+/// are deleted.
+pub(super) struct PointerMembers {
+    pub default_constructor: bool,
+    pub move_constructor: bool,
+    pub move_assignment: bool,
+}
+
+/// The end of the declaration of a special member the source file defines
+/// if it exists.
+fn declared(exists: bool) -> &'static str {
+    if exists { "" } else { " = delete" }
+}
+
+/// A constructor of a class or struct; the default one takes nothing.
+pub(super) struct Constructor {
+    pub explicit: bool,
+    pub params: Vec<String>,
+    pub inits: Vec<String>,
+}
+
+fn generate_enum(oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    write_doc_block(&oml_object.doc, cpp_file)?;
+    cpp_file.open_block(0, &format!("enum class {}", oml_object.name))?;
+    let length = oml_object.variables.len();
+
+    for (index, var) in oml_object.variables.iter().enumerate() {
+        write!(cpp_file, "{}{}", cpp_file.indent(1), var.name.to_uppercase())?;
+        if index == length-1 {
+            writeln!(cpp_file, "{}", trailing_doc(&var.doc))?;
+            continue
+        }
+        writeln!(cpp_file, ",{}", trailing_doc(&var.doc))?;
+
     }
 
+    writeln!(cpp_file, "}};")?;
+
     Ok(())
 }
 
-fn generate_copy_move_and_destructor(
-    oml_object: &OmlObject,
-    cpp_file: &mut String,
-) -> Result<(), std::fmt::Error> {
+/// A Doxygen `/** ... */` block for a top-level type, if it is documented.
+fn write_doc_block(doc: &Option<String>, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let Some(doc) = doc else {
+        return Ok(());
+    };
+    writeln!(cpp_file, "/**")?;
+    for line in doc.lines() {
+        if line.is_empty() {
+            writeln!(cpp_file, " *")?;
+        } else {
+            writeln!(cpp_file, " * {}", line)?;
+        }
+    }
+    writeln!(cpp_file, " */")?;
+    Ok(())
+}
+
+/// A trailing `///<` member comment with the doc lines joined, or nothing.
+fn trailing_doc(doc: &Option<String>) -> String {
+    match doc {
+        Some(doc) => format!(" ///< {}", doc.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ")),
+        None => String::new(),
+    }
+}
+
+fn generate_enum_stream_operator(oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
     let name = &oml_object.name;
+    cpp_file.open_block(0, &format!("inline std::ostream& operator<<(std::ostream& os, {} value)", name))?;
+    if !oml_object.variables.is_empty() {
+        cpp_file.open_block(1, "switch (value)")?;
+        for var in &oml_object.variables {
+            let variant = var.name.to_uppercase();
+            writeln!(cpp_file, "{}case {}::{}: return os << \"{}\";", cpp_file.indent(2), name, variant, variant)?;
+        }
+        writeln!(cpp_file, "{}}}", cpp_file.indent(1))?;
+    }
+    writeln!(cpp_file, "{}return os;", cpp_file.indent(1))?;
+    writeln!(cpp_file, "}}")?;
 
-    // Copy constructor
-    writeln!(cpp_file, "\t{}(const {}& other) = default;", name, name)?;
+    Ok(())
+}
 
-    // Move constructor
-    writeln!(cpp_file, "\t{}({}&& other) noexcept = default;", name, name)?;
+/// Serializes the enum by variant name rather than its underlying value.
+fn generate_enum_json(oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    writeln!(cpp_file, "NLOHMANN_JSON_SERIALIZE_ENUM({}, {{", name)?;
+    for var in &oml_object.variables {
+        let variant = var.name.to_uppercase();
+        writeln!(cpp_file, "{}{{{}::{}, \"{}\"}},", cpp_file.indent(1), name, variant, variant)?;
+    }
+    writeln!(cpp_file, "}})")?;
 
-    // Copy assignment operator
-    writeln!(cpp_file, "\t{}& operator=(const {}& other) = default;", name, name)?;
+    Ok(())
+}
 
-    // Move assignment operator
-    writeln!(cpp_file, "\t{}& operator=({}&& other) noexcept = default;", name, name)?;
+/// Whether `oml_object` is read from JSON through its constructor, having
+/// members that cannot be assigned: const ones, or ones of a type in
+/// `siblings` read that way, which has no default constructor either.
+fn constructed_from_json(oml_object: &OmlObject, siblings: &[OmlObject]) -> bool {
+    fn visit<'a>(oml_object: &'a OmlObject, siblings: &'a [OmlObject], seen: &mut Vec<&'a str>) -> bool {
+        if seen.contains(&oml_object.name.as_str()) {
+            return false;
+        }
+        seen.push(&oml_object.name);
+        oml_object.variables.iter().filter(|v| !v.is_static()).any(|v| {
+            v.is_const()
+                || (!v.is_optional()
+                    && v.array_kind != ArrayKind::Dynamic
+                    && siblings
+                        .iter()
+                        .find(|o| o.name == v.var_type && o.oml_type != ObjectType::ENUM)
+                        .is_some_and(|o| visit(o, siblings, seen)))
+        })
+    }
+    visit(oml_object, siblings, &mut Vec::new())
+}
 
-    // Destructor
-    writeln!(cpp_file, "\t~{}() = default;", name)?;
+/// `expr` in a form `operator<<` prints readably: 8-bit integers would
+/// otherwise print as characters and booleans as 0/1.
+fn streamable(var_type: &str, expr: &str) -> String {
+    match var_type {
+        "int8" | "uint8" => format!("static_cast<int>({})", expr),
+        "bool" => format!("({} ? \"true\" : \"false\")", expr),
+        _ => expr.to_string(),
+    }
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" => "int8_t".to_string(),
+        "int16" => "int16_t".to_string(),
+        "int32" => "int32_t".to_string(),
+        "int64" => "int64_t".to_string(),
+        "uint8" => "uint8_t".to_string(),
+        "uint16" => "uint16_t".to_string(),
+        "uint32" => "uint32_t".to_string(),
+        "uint64" => "uint64_t".to_string(),
+        "float" => "float".to_string(),
+        "double" => "double".to_string(),
+        "bool" => "bool".to_string(),
+        "string" => "std::string".to_string(),
+        "char" => "char".to_string(),
+        other => other.to_string(),
+    }
+}
+
+pub(super) fn type_annotation(var_type: &str, array_kind: &ArrayKind) -> String {
+    let base = convert_type(var_type);
+    match array_kind {
+        ArrayKind::None => base,
+        ArrayKind::Static(n) => format!("std::array<{}, {}>", base, n),
+        ArrayKind::Dynamic => format!("std::vector<{}>", base),
+    }
+}
+
+/// An OML `value` as a C++ literal of `var_type`; the two agree except for
+/// `uint64`, which would not fit a signed literal.
+pub(super) fn cpp_literal(var_type: &str, value: &str) -> String {
+    match var_type {
+        "uint64" => format!("{}ULL", value),
+        _ => value.to_string(),
+    }
+}
+
+pub(super) fn capitalize_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(c) => c.to_uppercase().to_string() + chars.as_str(),
+    }
+}
+
+/// Writes a constructor with its initializer list at `level`, wrapped to
+/// the line length.
+pub(super) fn write_constructor(
+    cpp_file: &mut CodeWriter,
+    level: usize,
+    prefix: &str,
+    name: &str,
+    params: &[String],
+    inits: &[String],
+) -> Result<(), std::fmt::Error> {
+    let params_str = params.join(", ");
+    let inits_str = inits.join(", ");
+
+    let single_line = format!("{}{}({}) : {} {{}}", prefix, name, params_str, inits_str);
+
+    if cpp_file.fits(level, &single_line) {
+        writeln!(cpp_file, "{}{}", cpp_file.indent(level), single_line)?;
+    } else {
+        // Signature on first line, initializers indented on following lines
+        writeln!(cpp_file, "{}{}{}({})", cpp_file.indent(level), prefix, name, params_str)?;
+        write!(cpp_file, "{}: ", cpp_file.indent(level + 1))?;
+
+        // Try all inits on one line after the colon
+        let colon_line = format!(": {} {{}}", inits_str);
+        if cpp_file.fits(level + 1, &colon_line) {
+            writeln!(cpp_file, "{} {{}}", inits_str)?;
+        } else {
+            // Each initializer on its own line
+            for (i, init) in inits.iter().enumerate() {
+                if i == 0 {
+                    writeln!(cpp_file, "{}", init)?;
+                } else {
+                    writeln!(cpp_file, "{}, {}", cpp_file.indent(level + 1), init)?;
+                }
+            }
+            writeln!(cpp_file, "{}{{}}", cpp_file.indent(level))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Move-only types delete the copy members and keep the defaulted moves.
+fn generate_copy_move_and_destructor(
+    oml_object: &OmlObject,
+    move_only: bool,
+    cpp_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let copy = if move_only { "delete" } else { "default" };
+
+    // Copy constructor
+    writeln!(cpp_file, "{}{}(const {}& other) = {};", cpp_file.indent(1), name, name, copy)?;
+
+    // Move constructor
+    writeln!(cpp_file, "{}{}({}&& other) noexcept = default;", cpp_file.indent(1), name, name)?;
+
+    // Copy assignment operator
+    writeln!(cpp_file, "{}{}& operator=(const {}& other) = {};", cpp_file.indent(1), name, name, copy)?;
+
+    // Move assignment operator
+    writeln!(cpp_file, "{}{}& operator=({}&& other) noexcept = default;", cpp_file.indent(1), name, name)?;
+
+    // Destructor
+    writeln!(cpp_file, "{}~{}() = default;", cpp_file.indent(1), name)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generate::Generate;
+    use crate::core::oml_object::{
+        OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+    };
+
+    fn oml_to_cpp(oml_object: &OmlObject, file_name: &str) -> Result<String, Box<dyn std::error::Error>> {
+        CppGenerator::default().generate(std::slice::from_ref(oml_object), file_name)
+    }
+
+    // ========== ENUM GENERATION TESTS ==========
+
+    #[test]
+    fn test_generate_enum_basic() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "Red".to_string(),
+                    ..Default::default()
+                },
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "Green".to_string(),
+                    ..Default::default()
+                },
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "Blue".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        generate_enum(&oml_object, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("enum class Color {"));
+        assert!(output.contains("\tRED,"));
+        assert!(output.contains("\tGREEN,"));
+        assert!(output.contains("\tBLUE"));
+        assert!(!output.contains("BLUE,"));
+        assert!(output.contains("};"));
+    }
+
+    #[test]
+    fn test_generate_enum_single_variant() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Status".to_string(),
+            variables: vec![
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "Active".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        generate_enum(&oml_object, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("enum class Status {"));
+        assert!(output.contains("\tACTIVE"));
+        assert!(!output.contains("ACTIVE,"));
+        assert!(output.contains("};"));
+    }
+
+    #[test]
+    fn test_generate_enum_empty() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Empty".to_string(),
+            variables: vec![],
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        generate_enum(&oml_object, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("enum class Empty {"));
+        assert!(output.contains("};"));
+    }
+
+    // ========== CLASS/STRUCT GENERATION TESTS ==========
+
+    #[test]
+    fn test_generate_class_with_all_visibility_levels() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "TestClass".to_string(),
+            variables: vec![
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "public_var".to_string(),
+                    ..Default::default()
+                },
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PRIVATE,
+                    var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "private_var".to_string(),
+                    ..Default::default()
+                },
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PROTECTED,
+                    var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "protected_var".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
+
+        println!("{}", output);
+
+        // assert!(output.contains("class TestClass {"));
+        // assert!(output.contains("private:"));
+        // assert!(output.contains("protected:"));
+        // assert!(output.contains("public:"));
+        // assert!(output.contains("};"));
+    }
+
+    #[test]
+    fn test_generate_struct() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Point".to_string(),
+            variables: vec![
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "float".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "x".to_string(),
+                    ..Default::default()
+                },
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "float".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "y".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("struct Point {"));
+        assert!(output.contains("float"));
+        assert!(output.contains("x"));
+        assert!(output.contains("y"));
+        assert!(output.contains("};"));
+    }
+
+    #[test]
+    fn test_generate_class_empty() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "EmptyClass".to_string(),
+            variables: vec![],
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("class EmptyClass {"));
+        assert!(output.contains("};"));
+    }
+
+    // ========== MODIFIER TESTS ==========
+
+    #[test]
+    fn test_static_modifier() {
+        let var = Variable {
+            var_mod: vec![VariableModifier::STATIC],
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+            name: "count".to_string(),
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().convert_modifiers_and_type(&var, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("static"));
+        assert!(output.contains("int32_t"));
+    }
+
+    #[test]
+    fn test_const_modifier() {
+        let var = Variable {
+            var_mod: vec![VariableModifier::CONST],
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+            name: "MAX_SIZE".to_string(),
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().convert_modifiers_and_type(&var, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("const"));
+        assert!(output.contains("int32_t"));
+    }
+
+    #[test]
+    fn test_const_static_modifiers_combined() {
+        let var = Variable {
+            var_mod: vec![VariableModifier::CONST, VariableModifier::STATIC],
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+            name: "MAX_VALUE".to_string(),
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().convert_modifiers_and_type(&var, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("static"));
+        assert!(output.contains("const"));
+        assert!(output.contains("int32_t"));
+
+        // Verify order: static should come before const
+        let static_pos = output.find("static").unwrap();
+        let const_pos = output.find("const").unwrap();
+        assert!(static_pos < const_pos);
+    }
+
+    #[test]
+    fn test_mut_modifier_overrides_const() {
+        let var = Variable {
+            var_mod: vec![VariableModifier::CONST, VariableModifier::MUT],
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+            name: "value".to_string(),
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().convert_modifiers_and_type(&var, &mut writer).unwrap();
+        let output = writer.finish();
+
+        // Should not contain const when mut is present
+        assert!(!output.contains("const"));
+        assert!(output.contains("int32_t"));
+    }
+
+    #[test]
+    fn test_optional_modifier() {
+        let var = Variable {
+            var_mod: vec![VariableModifier::OPTIONAL],
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "string".to_string(),
+                    array_kind: ArrayKind::None,
+            name: "nickname".to_string(),
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().convert_modifiers_and_type(&var, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("std::optional<std::string>"));
+    }
+
+    #[test]
+    fn test_optional_with_static() {
+        let var = Variable {
+            var_mod: vec![VariableModifier::OPTIONAL, VariableModifier::STATIC],
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+            name: "cache".to_string(),
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().convert_modifiers_and_type(&var, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("static"));
+        assert!(output.contains("std::optional<int32_t>"));
+    }
+
+    #[test]
+    fn test_optional_with_const() {
+        let var = Variable {
+            var_mod: vec![VariableModifier::OPTIONAL, VariableModifier::CONST],
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "string".to_string(),
+                    array_kind: ArrayKind::None,
+            name: "config".to_string(),
+            ..Default::default()
+        };
+
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().convert_modifiers_and_type(&var, &mut writer).unwrap();
+        let output = writer.finish();
+
+        assert!(output.contains("const"));
+        assert!(output.contains("std::optional<std::string>"));
+    }
+
+    // ========== TYPE CONVERSION TESTS ==========
+
+    #[test]
+    fn test_convert_all_integer_types() {
+        assert_eq!(convert_type("int8"), "int8_t");
+        assert_eq!(convert_type("int16"), "int16_t");
+        assert_eq!(convert_type("int32"), "int32_t");
+        assert_eq!(convert_type("int64"), "int64_t");
+        assert_eq!(convert_type("uint8"), "uint8_t");
+        assert_eq!(convert_type("uint16"), "uint16_t");
+        assert_eq!(convert_type("uint32"), "uint32_t");
+        assert_eq!(convert_type("uint64"), "uint64_t");
+    }
 
-    Ok(())
-}
+    #[test]
+    fn test_convert_floating_point_types() {
+        assert_eq!(convert_type("float"), "float");
+        assert_eq!(convert_type("double"), "double");
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core::generate::Generate;
-    use crate::core::oml_object::{
-        OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
-    };
+    #[test]
+    fn test_convert_other_basic_types() {
+        assert_eq!(convert_type("bool"), "bool");
+        assert_eq!(convert_type("char"), "char");
+        assert_eq!(convert_type("string"), "std::string");
+    }
 
-    fn oml_to_cpp(oml_object: &OmlObject, file_name: &str) -> Result<String, Box<dyn std::error::Error>> {
-        CppGenerator.generate(std::slice::from_ref(oml_object), file_name)
+    #[test]
+    fn test_convert_custom_type() {
+        assert_eq!(convert_type("CustomType"), "CustomType");
+        assert_eq!(convert_type("Address"), "Address");
     }
 
-    // ========== ENUM GENERATION TESTS ==========
+    // ========== FULL FILE GENERATION TESTS ==========
 
     #[test]
-    fn test_generate_enum_basic() {
+    fn test_oml_to_cpp_with_enum() {
         let oml_object = OmlObject {
             oml_type: ObjectType::ENUM,
             name: "Color".to_string(),
@@ -623,13 +2293,7 @@ mod tests {
                     var_type: "".to_string(),
                     array_kind: ArrayKind::None,
                     name: "Red".to_string(),
-                },
-                Variable {
-                    var_mod: vec![],
-                    visibility: VariableVisibility::PUBLIC,
-                    var_type: "".to_string(),
-                    array_kind: ArrayKind::None,
-                    name: "Green".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -637,401 +2301,897 @@ mod tests {
                     var_type: "".to_string(),
                     array_kind: ArrayKind::None,
                     name: "Blue".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_enum(&oml_object, &mut output).unwrap();
+        let result = oml_to_cpp(&oml_object, "Color").unwrap();
 
-        assert!(output.contains("enum class Color {"));
-        assert!(output.contains("\tRED,"));
-        assert!(output.contains("\tGREEN,"));
-        assert!(output.contains("\tBLUE"));
-        assert!(!output.contains("BLUE,"));
-        assert!(output.contains("};"));
+        // Check header guard
+        assert!(result.contains("#ifndef COLOR_H"));
+        assert!(result.contains("#define COLOR_H"));
+        assert!(result.contains("#endif // COLOR_H"));
+
+        // Check comment
+        assert!(result.contains("// This file has been generated from Color.oml"));
+
+        // Enum-only files should not have includes
+        assert!(!result.contains("#include"));
+
+        // Check enum content
+        assert!(result.contains("enum class Color {"));
     }
 
     #[test]
-    fn test_generate_enum_single_variant() {
+    fn test_oml_to_cpp_with_class() {
         let oml_object = OmlObject {
-            oml_type: ObjectType::ENUM,
-            name: "Status".to_string(),
+            oml_type: ObjectType::CLASS,
+            name: "Person".to_string(),
             variables: vec![
                 Variable {
                     var_mod: vec![],
                     visibility: VariableVisibility::PUBLIC,
-                    var_type: "".to_string(),
+                    var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
-                    name: "Active".to_string(),
+                    name: "name".to_string(),
+                    ..Default::default()
+                },
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PRIVATE,
+                    var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "age".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_enum(&oml_object, &mut output).unwrap();
+        let result = oml_to_cpp(&oml_object, "Person").unwrap();
 
-        assert!(output.contains("enum class Status {"));
-        assert!(output.contains("\tACTIVE"));
-        assert!(!output.contains("ACTIVE,"));
-        assert!(output.contains("};"));
+        assert!(result.contains("#ifndef PERSON_H"));
+        assert!(result.contains("#define PERSON_H"));
+        assert!(result.contains("class Person {"));
+        assert!(result.contains("std::string"));
+        assert!(result.contains("int32_t"));
+        assert!(result.contains("#endif // PERSON_H"));
     }
 
     #[test]
-    fn test_generate_enum_empty() {
+    fn test_oml_to_cpp_header_guard_uppercase() {
         let oml_object = OmlObject {
-            oml_type: ObjectType::ENUM,
-            name: "Empty".to_string(),
+            oml_type: ObjectType::CLASS,
+            name: "MyClass".to_string(),
+            variables: vec![],
+            ..Default::default()
+        };
+
+        let result = oml_to_cpp(&oml_object, "my_class").unwrap();
+
+        assert!(result.contains("#ifndef MY_CLASS_H"));
+        assert!(result.contains("#define MY_CLASS_H"));
+        assert!(result.contains("#endif // MY_CLASS_H"));
+    }
+
+    #[test]
+    fn test_header_guard_with_project_prefix() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "MyClass".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_enum(&oml_object, &mut output).unwrap();
+        let result = CppGenerator::default()
+            .with_guard_prefix(Some("acme-models"))
+            .generate(std::slice::from_ref(&oml_object), "my_class")
+            .unwrap();
 
-        assert!(output.contains("enum class Empty {"));
-        assert!(output.contains("};"));
+        assert!(result.contains("#ifndef ACME_MODELS_MY_CLASS_H\n#define ACME_MODELS_MY_CLASS_H\n"), "Got: {}", result);
+        assert!(result.contains("#endif // ACME_MODELS_MY_CLASS_H"), "Got: {}", result);
     }
 
-    // ========== CLASS/STRUCT GENERATION TESTS ==========
+    #[test]
+    fn test_header_guard_named_after_objects() {
+        let objects = [
+            OmlObject { oml_type: ObjectType::CLASS, name: "CarPart".to_string(), ..Default::default() },
+            OmlObject { oml_type: ObjectType::ENUM, name: "Color".to_string(), ..Default::default() },
+        ];
+
+        let result = CppGenerator::default()
+            .with_guard_prefix(Some("acme"))
+            .with_guard_source(GuardSource::Objects)
+            .generate(&objects, "car")
+            .unwrap();
+
+        assert!(result.contains("#ifndef ACME_CAR_PART_COLOR_H\n#define ACME_CAR_PART_COLOR_H\n"), "Got: {}", result);
+        assert!(result.contains("#endif // ACME_CAR_PART_COLOR_H"), "Got: {}", result);
+    }
 
     #[test]
-    fn test_generate_class_with_all_visibility_levels() {
+    fn test_pragma_once_replaces_guard() {
         let oml_object = OmlObject {
             oml_type: ObjectType::CLASS,
-            name: "TestClass".to_string(),
-            variables: vec![
-                Variable {
-                    var_mod: vec![],
-                    visibility: VariableVisibility::PUBLIC,
-                    var_type: "int32".to_string(),
-                    array_kind: ArrayKind::None,
-                    name: "public_var".to_string(),
-                },
-                Variable {
-                    var_mod: vec![],
-                    visibility: VariableVisibility::PRIVATE,
-                    var_type: "int32".to_string(),
-                    array_kind: ArrayKind::None,
-                    name: "private_var".to_string(),
-                },
-                Variable {
-                    var_mod: vec![],
-                    visibility: VariableVisibility::PROTECTED,
-                    var_type: "int32".to_string(),
-                    array_kind: ArrayKind::None,
-                    name: "protected_var".to_string(),
-                },
-            ],
+            name: "MyClass".to_string(),
+            variables: vec![],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let result = CppGenerator::default()
+            .with_pragma_once(true)
+            .generate(std::slice::from_ref(&oml_object), "my_class")
+            .unwrap();
 
-        println!("{}", output);
+        assert!(result.starts_with("// This file has been generated from my_class.oml\n#pragma once\n\n"), "Got: {}", result);
+        assert!(!result.contains("#ifndef"), "Got: {}", result);
+        assert!(!result.contains("#endif"), "Got: {}", result);
+    }
 
-        // assert!(output.contains("class TestClass {"));
-        // assert!(output.contains("private:"));
-        // assert!(output.contains("protected:"));
-        // assert!(output.contains("public:"));
-        // assert!(output.contains("};"));
+    #[test]
+    fn test_namespace_wraps_types() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![Variable {
+                var_mod: vec![],
+                visibility: VariableVisibility::PUBLIC,
+                var_type: "string".to_string(),
+                array_kind: ArrayKind::None,
+                name: "Red".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let result = CppGenerator::default()
+            .with_namespace(Some("acme::models"))
+            .generate(std::slice::from_ref(&oml_object), "color")
+            .unwrap();
+
+        assert!(result.contains(
+            "namespace acme::models {\n\
+             \n\
+             enum class Color {\n\
+             \tRED\n\
+             };\n\
+             \n\
+             } // namespace acme::models\n\
+             #endif // COLOR_H\n"
+        ), "Got: {}", result);
     }
 
     #[test]
-    fn test_generate_struct() {
+    fn test_cpp11_avoids_cpp17_features() {
         let oml_object = OmlObject {
-            oml_type: ObjectType::STRUCT,
-            name: "Point".to_string(),
+            oml_type: ObjectType::CLASS,
+            name: "User".to_string(),
             variables: vec![
                 Variable {
                     var_mod: vec![],
-                    visibility: VariableVisibility::PUBLIC,
-                    var_type: "float".to_string(),
+                    visibility: VariableVisibility::PRIVATE,
+                    var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
-                    name: "x".to_string(),
+                    name: "name".to_string(),
+                    ..Default::default()
                 },
                 Variable {
-                    var_mod: vec![],
-                    visibility: VariableVisibility::PUBLIC,
-                    var_type: "float".to_string(),
+                    var_mod: vec![VariableModifier::OPTIONAL],
+                    visibility: VariableVisibility::PRIVATE,
+                    var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
-                    name: "y".to_string(),
+                    name: "age".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let result = CppGenerator::default()
+            .with_standard(CppStandard::Cpp11)
+            .with_namespace(Some("acme::models"))
+            .generate(std::slice::from_ref(&oml_object), "user")
+            .unwrap();
 
-        assert!(output.contains("struct Point {"));
-        assert!(output.contains("float"));
-        assert!(output.contains("x"));
-        assert!(output.contains("y"));
-        assert!(output.contains("};"));
+        assert!(result.contains("#include <tl/optional.hpp>\n"), "Got: {}", result);
+        assert!(!result.contains("std::optional"), "Got: {}", result);
+        assert!(!result.contains("string_view"), "Got: {}", result);
+        assert!(result.contains("std::string getName() const { return name; }"), "Got: {}", result);
+        assert!(result.contains("tl::optional<int32_t> getAge() const { return age; }"), "Got: {}", result);
+        assert!(result.contains("namespace acme {\nnamespace models {\n\n"), "Got: {}", result);
+        assert!(result.contains("\n} // namespace models\n} // namespace acme\n"), "Got: {}", result);
     }
 
     #[test]
-    fn test_generate_class_empty() {
+    fn test_cpp17_string_view_getter() {
         let oml_object = OmlObject {
             oml_type: ObjectType::CLASS,
-            name: "EmptyClass".to_string(),
-            variables: vec![],
+            name: "User".to_string(),
+            variables: vec![Variable {
+                var_mod: vec![],
+                visibility: VariableVisibility::PRIVATE,
+                var_type: "string".to_string(),
+                array_kind: ArrayKind::None,
+                name: "name".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let result = oml_to_cpp(&oml_object, "user").unwrap();
 
-        assert!(output.contains("class EmptyClass {"));
-        assert!(output.contains("};"));
+        assert!(result.contains("#include <string>\n#include <string_view>\n#include <utility>\n"), "Got: {}", result);
+        assert!(result.contains("std::string_view getName() const { return name; }"), "Got: {}", result);
+        assert!(result.contains("void setName(const std::string& value) { name = value; }"), "Got: {}", result);
     }
 
-    // ========== MODIFIER TESTS ==========
-
-    #[test]
-    fn test_static_modifier() {
-        let var = Variable {
-            var_mod: vec![VariableModifier::STATIC],
+    fn point() -> OmlObject {
+        let member = |name: &str, var_mod: Vec<VariableModifier>| Variable {
+            var_mod,
             visibility: VariableVisibility::PUBLIC,
-            var_type: "int32".to_string(),
-                    array_kind: ArrayKind::None,
-            name: "count".to_string(),
+            var_type: "double".to_string(),
+            array_kind: ArrayKind::None,
+            name: name.to_string(),
+            ..Default::default()
         };
+        OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Point".to_string(),
+            variables: vec![
+                member("x", vec![]),
+                member("y", vec![]),
+                member("origin", vec![VariableModifier::STATIC]),
+            ],
+            ..Default::default()
+        }
+    }
 
-        let mut output = String::new();
-        convert_modifiers_and_type(&var, &mut output).unwrap();
+    #[test]
+    fn test_memberwise_comparison_operators() {
+        let result = CppGenerator::default()
+            .with_comparisons(true)
+            .generate(&[point()], "point")
+            .unwrap();
 
-        assert!(output.contains("static"));
-        assert!(output.contains("int32_t"));
+        assert!(result.contains(
+            "\tbool operator==(const Point& other) const {\n\
+             \t\treturn x == other.x && y == other.y;\n\
+             \t}\n\
+             \tbool operator!=(const Point& other) const { return !(*this == other); }\n"
+        ), "Got: {}", result);
+        assert!(!result.contains("<=>"), "Got: {}", result);
     }
 
     #[test]
-    fn test_const_modifier() {
-        let var = Variable {
-            var_mod: vec![VariableModifier::CONST],
-            visibility: VariableVisibility::PUBLIC,
-            var_type: "int32".to_string(),
-                    array_kind: ArrayKind::None,
-            name: "MAX_SIZE".to_string(),
-        };
-
-        let mut output = String::new();
-        convert_modifiers_and_type(&var, &mut output).unwrap();
+    fn test_cpp20_defaulted_comparisons() {
+        let result = CppGenerator::default()
+            .with_comparisons(true)
+            .with_standard(CppStandard::Cpp20)
+            .generate(&[point()], "point")
+            .unwrap();
 
-        assert!(output.contains("const"));
-        assert!(output.contains("int32_t"));
+        assert!(result.contains("#include <compare>\n"), "Got: {}", result);
+        assert!(result.contains(
+            "\tbool operator==(const Point& other) const = default;\n\
+             \tauto operator<=>(const Point& other) const = default;\n"
+        ), "Got: {}", result);
+        assert!(!result.contains("operator!="), "Got: {}", result);
     }
 
     #[test]
-    fn test_const_static_modifiers_combined() {
-        let var = Variable {
-            var_mod: vec![VariableModifier::CONST, VariableModifier::STATIC],
-            visibility: VariableVisibility::PUBLIC,
-            var_type: "int32".to_string(),
+    fn test_stream_operator_prints_fields() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                Variable {
+                    var_mod: vec![],
+                    visibility: VariableVisibility::PRIVATE,
+                    var_type: "uint8".to_string(),
                     array_kind: ArrayKind::None,
-            name: "MAX_VALUE".to_string(),
+                    name: "doors".to_string(),
+                    ..Default::default()
+                },
+                Variable {
+                    var_mod: vec![VariableModifier::OPTIONAL],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "string".to_string(),
+                    array_kind: ArrayKind::Dynamic,
+                    name: "tags".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        convert_modifiers_and_type(&var, &mut output).unwrap();
-
-        assert!(output.contains("static"));
-        assert!(output.contains("const"));
-        assert!(output.contains("int32_t"));
-
-        // Verify order: static should come before const
-        let static_pos = output.find("static").unwrap();
-        let const_pos = output.find("const").unwrap();
-        assert!(static_pos < const_pos);
+        let result = CppGenerator::default()
+            .with_stream_operators(true)
+            .generate(std::slice::from_ref(&oml_object), "car")
+            .unwrap();
+
+        assert!(result.contains("#include <ostream>\n"), "Got: {}", result);
+        assert!(result.contains(
+            "\tfriend std::ostream& operator<<(std::ostream& os, const Car& value) {\n\
+             \t\tos << \"Car{\";\n\
+             \t\tos << \"doors=\";\n\
+             \t\tos << static_cast<int>(value.doors);\n\
+             \t\tos << \", tags=\";\n\
+             \t\tif (value.tags) {\n\
+             \t\t\tos << '[';\n\
+             \t\t\tfor (std::size_t i = 0; i < (*value.tags).size(); ++i) {\n\
+             \t\t\t\tif (i > 0) os << \", \";\n\
+             \t\t\t\tos << (*value.tags)[i];\n\
+             \t\t\t}\n\
+             \t\t\tos << ']';\n\
+             \t\t} else {\n\
+             \t\t\tos << \"null\";\n\
+             \t\t}\n\
+             \t\treturn os << '}';\n\
+             \t}\n"
+        ), "Got: {}", result);
     }
 
     #[test]
-    fn test_mut_modifier_overrides_const() {
-        let var = Variable {
-            var_mod: vec![VariableModifier::CONST, VariableModifier::MUT],
-            visibility: VariableVisibility::PUBLIC,
-            var_type: "int32".to_string(),
-                    array_kind: ArrayKind::None,
-            name: "value".to_string(),
+    fn test_enum_stream_operator() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![Variable {
+                var_mod: vec![],
+                visibility: VariableVisibility::PUBLIC,
+                var_type: "string".to_string(),
+                array_kind: ArrayKind::None,
+                name: "Red".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        convert_modifiers_and_type(&var, &mut output).unwrap();
+        let result = CppGenerator::default()
+            .with_stream_operators(true)
+            .generate(std::slice::from_ref(&oml_object), "color")
+            .unwrap();
 
-        // Should not contain const when mut is present
-        assert!(!output.contains("const"));
-        assert!(output.contains("int32_t"));
+        assert!(result.contains("#define COLOR_H\n\n#include <ostream>\n\n"), "Got: {}", result);
+        assert!(result.contains(
+            "inline std::ostream& operator<<(std::ostream& os, Color value) {\n\
+             \tswitch (value) {\n\
+             \t\tcase Color::RED: return os << \"RED\";\n\
+             \t}\n\
+             \treturn os;\n\
+             }\n"
+        ), "Got: {}", result);
     }
 
     #[test]
-    fn test_optional_modifier() {
-        let var = Variable {
-            var_mod: vec![VariableModifier::OPTIONAL],
-            visibility: VariableVisibility::PUBLIC,
-            var_type: "string".to_string(),
-                    array_kind: ArrayKind::None,
-            name: "nickname".to_string(),
+    fn test_json_functions() {
+        let member = |name: &str, var_type: &str, var_mod: Vec<VariableModifier>| Variable {
+            var_mod,
+            visibility: VariableVisibility::PRIVATE,
+            var_type: var_type.to_string(),
+            array_kind: ArrayKind::None,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "User".to_string(),
+            variables: vec![
+                member("id", "uint64", vec![]),
+                member("name", "string", vec![]),
+                member("age", "int32", vec![VariableModifier::OPTIONAL]),
+                member("count", "int32", vec![VariableModifier::STATIC]),
+            ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        convert_modifiers_and_type(&var, &mut output).unwrap();
-
-        assert!(output.contains("std::optional<std::string>"));
+        let result = CppGenerator::default()
+            .with_json(true)
+            .generate(std::slice::from_ref(&oml_object), "user")
+            .unwrap();
+
+        assert!(result.contains("#include <nlohmann/json.hpp>\n"), "Got: {}", result);
+        assert!(result.contains(
+            "\tfriend void to_json(nlohmann::json& j, const User& value) {\n\
+             \t\tj = nlohmann::json::object();\n\
+             \t\tj[\"id\"] = value.id;\n\
+             \t\tj[\"name\"] = value.name;\n\
+             \t\tj[\"age\"] = value.age ? nlohmann::json(*value.age) : nlohmann::json(nullptr);\n\
+             \t}\n\
+             \tfriend void from_json(const nlohmann::json& j, User& value) {\n\
+             \t\tj.at(\"id\").get_to(value.id);\n\
+             \t\tj.at(\"name\").get_to(value.name);\n\
+             \t\tvalue.age = j.contains(\"age\") && !j.at(\"age\").is_null() \
+             ? std::optional<int32_t>(j.at(\"age\").get<int32_t>()) : std::optional<int32_t>();\n\
+             \t}\n"
+        ), "Got: {}", result);
+        assert!(!result.contains("adl_serializer"), "Got: {}", result);
+
+        // a const member leaves no default constructor, so the constructor
+        // reads every field, and so it does for a type holding this one
+        let user = OmlObject { variables: vec![member("id", "uint64", vec![VariableModifier::CONST]), member("age", "int32", vec![VariableModifier::OPTIONAL])], ..oml_object };
+        let account = OmlObject { name: "Account".to_string(), variables: vec![member("owner", "User", vec![])], ..user.clone() };
+        let result = CppGenerator::default()
+            .with_json(true)
+            .with_namespace(Some("acme"))
+            .generate(&[user, account], "user")
+            .unwrap();
+        assert!(result.contains(
+            "\tstatic User fromJson(const nlohmann::json& j) {\n\
+             \t\treturn User(\n\
+             \t\t\tj.at(\"id\").get<uint64_t>(),\n\
+             \t\t\tj.contains(\"age\") && !j.at(\"age\").is_null() \
+             ? std::optional<int32_t>(j.at(\"age\").get<int32_t>()) : std::optional<int32_t>()\n\
+             \t\t);\n\
+             \t}\n"
+        ), "Got: {}", result);
+        assert!(result.contains("\tstatic Account fromJson(const nlohmann::json& j) {\n\t\treturn Account(\n\t\t\tj.at(\"owner\").get<User>()\n"), "Got: {}", result);
+        assert!(result.ends_with(
+            "} // namespace acme\n\
+             \n\
+             namespace nlohmann {\n\
+             template <>\n\
+             struct adl_serializer<acme::User> {\n\
+             \tstatic acme::User from_json(const json& j) { return acme::User::fromJson(j); }\n\
+             \tstatic void to_json(json& j, const acme::User& value) { ::nlohmann::to_json(j, value); }\n\
+             };\n\
+             \n\
+             template <>\n\
+             struct adl_serializer<acme::Account> {\n\
+             \tstatic acme::Account from_json(const json& j) { return acme::Account::fromJson(j); }\n\
+             \tstatic void to_json(json& j, const acme::Account& value) { ::nlohmann::to_json(j, value); }\n\
+             };\n\
+             } // namespace nlohmann\n\
+             #endif // USER_H\n\n"
+        ), "Got: {}", result);
     }
 
     #[test]
-    fn test_optional_with_static() {
-        let var = Variable {
-            var_mod: vec![VariableModifier::OPTIONAL, VariableModifier::STATIC],
-            visibility: VariableVisibility::PUBLIC,
-            var_type: "int32".to_string(),
-                    array_kind: ArrayKind::None,
-            name: "cache".to_string(),
+    fn test_enum_json_by_name() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![Variable {
+                var_mod: vec![],
+                visibility: VariableVisibility::PUBLIC,
+                var_type: "string".to_string(),
+                array_kind: ArrayKind::None,
+                name: "Red".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        convert_modifiers_and_type(&var, &mut output).unwrap();
+        let result = CppGenerator::default()
+            .with_json(true)
+            .generate(std::slice::from_ref(&oml_object), "color")
+            .unwrap();
 
-        assert!(output.contains("static"));
-        assert!(output.contains("std::optional<int32_t>"));
+        assert!(result.contains("NLOHMANN_JSON_SERIALIZE_ENUM(Color, {\n\t{Color::RED, \"RED\"},\n})\n"), "Got: {}", result);
     }
 
     #[test]
-    fn test_optional_with_const() {
-        let var = Variable {
-            var_mod: vec![VariableModifier::OPTIONAL, VariableModifier::CONST],
+    fn test_hash_member_and_specialization() {
+        let member = |name: &str, var_type: &str, var_mod: Vec<VariableModifier>, array_kind: ArrayKind| Variable {
+            var_mod,
             visibility: VariableVisibility::PUBLIC,
-            var_type: "string".to_string(),
-                    array_kind: ArrayKind::None,
-            name: "config".to_string(),
+            var_type: var_type.to_string(),
+            array_kind,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let oml_object = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Key".to_string(),
+            variables: vec![
+                member("name", "string", vec![], ArrayKind::None),
+                member("parts", "uint16", vec![], ArrayKind::Dynamic),
+                member("shard", "int32", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("count", "int32", vec![VariableModifier::STATIC], ArrayKind::None),
+                member("parent", "Key", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+            ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        convert_modifiers_and_type(&var, &mut output).unwrap();
-
-        assert!(output.contains("const"));
-        assert!(output.contains("std::optional<std::string>"));
+        let result = CppGenerator::default()
+            .with_hash(true)
+            .with_namespace(Some("acme"))
+            .generate(std::slice::from_ref(&oml_object), "key")
+            .unwrap();
+
+        assert!(result.contains("#include <cstddef>\n#include <functional>\n"), "Got: {}", result);
+        assert!(result.contains(
+            "\tstd::size_t hash() const {\n\
+             \t\tstd::size_t seed = 0;\n\
+             \t\tauto combine = [&seed](std::size_t h) { seed ^= h + 0x9e3779b9 + (seed << 6) + (seed >> 2); };\n\
+             \t\tcombine(std::hash<std::string>{}(name));\n\
+             \t\tfor (const auto& item : parts) combine(std::hash<uint16_t>{}(item));\n\
+             \t\tcombine(shard.has_value());\n\
+             \t\tif (shard) combine(std::hash<int32_t>{}(*shard));\n\
+             \t\tcombine(parent.has_value());\n\
+             \t\tif (parent) combine(parent->hash());\n\
+             \t\treturn seed;\n\
+             \t}\n"
+        ), "Got: {}", result);
+        assert!(result.contains(
+            "} // namespace acme\n\
+             \n\
+             namespace std {\n\
+             template <>\n\
+             struct hash<acme::Key> {\n\
+             \tstd::size_t operator()(const acme::Key& value) const noexcept { return value.hash(); }\n\
+             };\n\
+             } // namespace std\n"
+        ), "Got: {}", result);
     }
 
-    // ========== TYPE CONVERSION TESTS ==========
-
     #[test]
-    fn test_convert_all_integer_types() {
-        assert_eq!(convert_type("int8"), "int8_t");
-        assert_eq!(convert_type("int16"), "int16_t");
-        assert_eq!(convert_type("int32"), "int32_t");
-        assert_eq!(convert_type("int64"), "int64_t");
-        assert_eq!(convert_type("uint8"), "uint8_t");
-        assert_eq!(convert_type("uint16"), "uint16_t");
-        assert_eq!(convert_type("uint32"), "uint32_t");
-        assert_eq!(convert_type("uint64"), "uint64_t");
+    fn test_cpp11_hashes_enums_through_their_underlying_type() {
+        let color = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![Variable { var_type: "string".to_string(), name: "RED".to_string(), ..Default::default() }],
+            ..Default::default()
+        };
+        let paint = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Paint".to_string(),
+            variables: vec![Variable {
+                visibility: VariableVisibility::PUBLIC,
+                var_type: "Color".to_string(),
+                name: "color".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let objects = [color, paint];
+
+        let result = CppGenerator::default()
+            .with_hash(true)
+            .with_standard(CppStandard::Cpp11)
+            .generate(&objects, "paint")
+            .unwrap();
+        assert!(result.contains("#include <functional>\n#include <type_traits>\n"), "Got: {}", result);
+        assert!(result.contains(
+            "\t\tcombine(std::hash<std::underlying_type<Color>::type>{}(static_cast<std::underlying_type<Color>::type>(color)));\n"
+        ), "Got: {}", result);
+
+        let result = CppGenerator::default()
+            .with_hash(true)
+            .with_standard(CppStandard::Cpp14)
+            .generate(&objects, "paint")
+            .unwrap();
+        assert!(!result.contains("<type_traits>"), "Got: {}", result);
+        assert!(result.contains("\t\tcombine(std::hash<Color>{}(color));\n"), "Got: {}", result);
     }
 
     #[test]
-    fn test_convert_floating_point_types() {
-        assert_eq!(convert_type("float"), "float");
-        assert_eq!(convert_type("double"), "double");
-    }
+    fn test_only_needed_includes() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Point".to_string(),
+            variables: vec![Variable {
+                var_mod: vec![],
+                visibility: VariableVisibility::PUBLIC,
+                var_type: "double".to_string(),
+                array_kind: ArrayKind::None,
+                name: "x".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
 
-    #[test]
-    fn test_convert_other_basic_types() {
-        assert_eq!(convert_type("bool"), "bool");
-        assert_eq!(convert_type("char"), "char");
-        assert_eq!(convert_type("string"), "std::string");
+        let result = oml_to_cpp(&oml_object, "point").unwrap();
+        assert!(result.contains("#define POINT_H\n\n#include <utility>\n\nstruct Point {"), "Got: {}", result);
+
+        let empty = OmlObject { oml_type: ObjectType::STRUCT, name: "Marker".to_string(), ..Default::default() };
+        let result = oml_to_cpp(&empty, "marker").unwrap();
+        assert!(!result.contains("#include"), "Got: {}", result);
     }
 
     #[test]
-    fn test_convert_custom_type() {
-        assert_eq!(convert_type("CustomType"), "CustomType");
-        assert_eq!(convert_type("Address"), "Address");
-    }
+    fn test_builder_validates_required_fields() {
+        let member = |name: &str, var_type: &str, var_mod: Vec<VariableModifier>| Variable {
+            var_mod,
+            visibility: VariableVisibility::PRIVATE,
+            var_type: var_type.to_string(),
+            array_kind: ArrayKind::None,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "User".to_string(),
+            variables: vec![
+                member("count", "int32", vec![VariableModifier::STATIC]),
+                member("name", "string", vec![]),
+                member("age", "int32", vec![VariableModifier::OPTIONAL]),
+            ],
+            ..Default::default()
+        };
 
-    // ========== FULL FILE GENERATION TESTS ==========
+        let result = CppGenerator::default()
+            .with_builder(true)
+            .generate(std::slice::from_ref(&oml_object), "user")
+            .unwrap();
+
+        assert!(result.contains("#include <stdexcept>\n"), "Got: {}", result);
+        assert!(result.contains("\tUser(std::string name, std::optional<int32_t> age) : "), "Got: {}", result);
+        assert!(result.contains(
+            "\tclass Builder {\n\
+             \tpublic:\n\
+             \t\tBuilder& name(std::string value) { name_ = std::move(value); return *this; }\n\
+             \t\tBuilder& age(int32_t value) { age_ = std::move(value); return *this; }\n\
+             \n\
+             \t\tUser build() const {\n\
+             \t\t\tif (!name_) throw std::logic_error(\"User::Builder: name is not set\");\n\
+             \t\t\treturn User(*name_, age_);\n\
+             \t\t}\n\
+             \n\
+             \tprivate:\n\
+             \t\tstd::optional<std::string> name_;\n\
+             \t\tstd::optional<int32_t> age_;\n\
+             \t};\n"
+        ), "Got: {}", result);
+    }
 
     #[test]
-    fn test_oml_to_cpp_with_enum() {
+    fn test_enum_utilities() {
+        let variant = |name: &str| Variable {
+            var_mod: vec![],
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "string".to_string(),
+            array_kind: ArrayKind::None,
+            name: name.to_string(),
+            ..Default::default()
+        };
         let oml_object = OmlObject {
             oml_type: ObjectType::ENUM,
             name: "Color".to_string(),
-            variables: vec![
-                Variable {
-                    var_mod: vec![],
-                    visibility: VariableVisibility::PUBLIC,
-                    var_type: "".to_string(),
-                    array_kind: ArrayKind::None,
-                    name: "Red".to_string(),
-                },
-                Variable {
-                    var_mod: vec![],
-                    visibility: VariableVisibility::PUBLIC,
-                    var_type: "".to_string(),
-                    array_kind: ArrayKind::None,
-                    name: "Blue".to_string(),
-                },
-            ],
+            variables: vec![variant("Red"), variant("Green")],
+            ..Default::default()
         };
 
-        let result = oml_to_cpp(&oml_object, "Color").unwrap();
+        let result = CppGenerator::default()
+            .with_enum_utilities(true)
+            .generate(std::slice::from_ref(&oml_object), "color")
+            .unwrap();
+
+        assert!(result.contains("#include <string_view>\n#include <optional>\n#include <array>\n#include <cstddef>\n"), "Got: {}", result);
+        assert!(result.contains(
+            "template <typename E>\n\
+             std::optional<E> from_string(std::string_view name);\n\
+             \n\
+             template <typename E>\n\
+             struct enum_values;\n\
+             \n\
+             inline std::string_view to_string(Color value) {\n\
+             \tswitch (value) {\n\
+             \t\tcase Color::RED: return \"RED\";\n\
+             \t\tcase Color::GREEN: return \"GREEN\";\n\
+             \t}\n\
+             \treturn \"\";\n\
+             }\n\
+             \n\
+             template <>\n\
+             inline std::optional<Color> from_string<Color>(std::string_view name) {\n\
+             \tif (name == \"RED\") return Color::RED;\n\
+             \tif (name == \"GREEN\") return Color::GREEN;\n\
+             \treturn std::optional<Color>();\n\
+             }\n\
+             \n\
+             template <>\n\
+             struct enum_values<Color> {\n\
+             \tstatic constexpr std::size_t count = 2;\n\
+             \tstatic constexpr std::array<Color, 2> all() { return {{Color::RED, Color::GREEN}}; }\n\
+             };\n"
+        ), "Got: {}", result);
+    }
 
-        // Check header guard
-        assert!(result.contains("#ifndef COLOR_H"));
-        assert!(result.contains("#define COLOR_H"));
-        assert!(result.contains("#endif // COLOR_H"));
+    #[test]
+    fn test_move_only_deletes_copy_members() {
+        let handle = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Handle".to_string(),
+            ..Default::default()
+        };
+        let plain = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Plain".to_string(),
+            ..Default::default()
+        };
 
-        // Check comment
-        assert!(result.contains("// This file has been generated from Color.oml"));
+        let result = CppGenerator::default()
+            .with_move_only(&["Handle".to_string()])
+            .generate(&[handle, plain], "handle")
+            .unwrap();
 
-        // Enum-only files should not have includes
-        assert!(!result.contains("#include"));
+        assert!(result.contains(
+            "\tHandle(const Handle& other) = delete;\n\
+             \tHandle(Handle&& other) noexcept = default;\n\
+             \tHandle& operator=(const Handle& other) = delete;\n\
+             \tHandle& operator=(Handle&& other) noexcept = default;\n\
+             \t~Handle() = default;\n"
+        ), "Got: {}", result);
+        assert!(result.contains("\tPlain(const Plain& other) = default;\n"), "Got: {}", result);
+        assert!(result.contains("\tPlain& operator=(const Plain& other) = default;\n"), "Got: {}", result);
+    }
 
-        // Check enum content
-        assert!(result.contains("enum class Color {"));
+    #[test]
+    fn test_pointer_fields_are_forward_declared_and_defined_in_the_source() {
+        let car = || OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                Variable { var_type: "Engine".to_string(), name: "engine".to_string(), ..Default::default() },
+                Variable { var_type: "Wheel".to_string(), array_kind: ArrayKind::Dynamic, name: "wheels".to_string(), ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let wheel = || OmlObject { oml_type: ObjectType::STRUCT, name: "Wheel".to_string(), ..Default::default() };
+        let by_pointer: Vec<PointerType> = ["Engine=engine.h", "Wheel"].iter().map(|p| p.parse().unwrap()).collect();
+        let generator = CppGenerator::default().with_by_pointer(&by_pointer);
+
+        let files = generator.generate_files(&[car(), wheel()], "car").unwrap();
+        let (header, source) = (&files[0].content, &files[1].content);
+        assert_eq!(files[1].path, std::path::PathBuf::from("car.cpp"));
+        assert!(header.contains("#include <memory>\n"), "Got: {}", header);
+        assert!(header.contains("class Engine;\nstruct Wheel;\n"), "Got: {}", header);
+        assert!(header.contains(
+            "\tCar();\n\
+             \tCar(std::unique_ptr<Engine> engine, std::vector<std::unique_ptr<Wheel>> wheels);\n\
+             \n\
+             \tCar(const Car& other) = delete;\n\
+             \tCar(Car&& other) noexcept;\n\
+             \tCar& operator=(const Car& other) = delete;\n\
+             \tCar& operator=(Car&& other) noexcept;\n\
+             \t~Car();\n"
+        ), "Got: {}", header);
+        assert!(header.contains("\tconst Engine* getEngine() const { return engine.get(); }\n"), "Got: {}", header);
+        assert!(header.contains("\tvoid setEngine(std::unique_ptr<Engine> value);\n"), "Got: {}", header);
+        assert!(header.contains("\tWheel() = default;\n"), "Got: {}", header);
+
+        assert_eq!(source, "// This file has been generated from car.oml\n\
+             \n\
+             #include \"car.h\"\n\
+             \n\
+             #include \"engine.h\"\n\
+             \n\
+             Car::Car() = default;\n\
+             Car::Car(std::unique_ptr<Engine> engine, std::vector<std::unique_ptr<Wheel>> wheels)\n\
+             \t: engine(std::move(engine)), wheels(std::move(wheels)) {}\n\
+             Car::Car(Car&& other) noexcept = default;\n\
+             Car& Car::operator=(Car&& other) noexcept = default;\n\
+             Car::~Car() = default;\n\
+             \n\
+             void Car::setEngine(std::unique_ptr<Engine> value) { engine = std::move(value); }\n\
+             void Car::setWheels(std::vector<std::unique_ptr<Wheel>> value) { wheels = std::move(value); }\n");
+
+        // a type of another file needs its header, and no member may need the type complete
+        let error = CppGenerator::default().with_by_pointer(&["Engine".parse().unwrap()]).generate(&[car()], "car").unwrap_err();
+        assert!(error.to_string().contains("give its header as --cpp-by-pointer Engine=HEADER"), "Got: {}", error);
+        let error = CppGenerator::default().with_by_pointer(&by_pointer).with_json(true).generate(&[car(), wheel()], "car").unwrap_err();
+        assert_eq!(error.to_string(), "'Car' holds fields by pointer, for which JSON functions cannot be generated");
     }
 
     #[test]
-    fn test_oml_to_cpp_with_class() {
-        let oml_object = OmlObject {
+    fn test_accessor_modes() {
+        let obj = OmlObject {
             oml_type: ObjectType::CLASS,
-            name: "Person".to_string(),
+            name: "Point".to_string(),
             variables: vec![
-                Variable {
-                    var_mod: vec![],
-                    visibility: VariableVisibility::PUBLIC,
-                    var_type: "string".to_string(),
-                    array_kind: ArrayKind::None,
-                    name: "name".to_string(),
-                },
-                Variable {
-                    var_mod: vec![],
-                    visibility: VariableVisibility::PRIVATE,
-                    var_type: "int32".to_string(),
-                    array_kind: ArrayKind::None,
-                    name: "age".to_string(),
-                },
+                Variable { visibility: VariableVisibility::PUBLIC, var_type: "int32".to_string(), name: "x".to_string(), ..Default::default() },
+                Variable { visibility: VariableVisibility::PRIVATE, var_type: "int32".to_string(), name: "y".to_string(), ..Default::default() },
             ],
+            ..Default::default()
         };
 
-        let result = oml_to_cpp(&oml_object, "Person").unwrap();
+        let all = CppGenerator::default()
+            .with_accessors(AccessorMode::All)
+            .generate(std::slice::from_ref(&obj), "point")
+            .unwrap();
+        assert!(all.contains("\tint32_t getX() const { return x; }\n\tint32_t getY() const { return y; }\n"), "Got: {}", all);
+        // public fields stay public, with the accessors alongside
+        assert!(all.contains("\tvoid setY(const int32_t& value) { y = value; }\n\tint32_t x;\n"), "Got: {}", all);
+        assert!(all.contains("private:\n\tint32_t y;\n"), "Got: {}", all);
+
+        let strukt = OmlObject { oml_type: ObjectType::STRUCT, ..obj.clone() };
+        let all = CppGenerator::default()
+            .with_accessors(AccessorMode::All)
+            .generate(std::slice::from_ref(&strukt), "point")
+            .unwrap();
+        assert!(all.contains("\tint32_t getX() const { return x; }\n"), "Got: {}", all);
+        assert!(all.contains("\tint32_t x;\n"), "Got: {}", all);
+
+        let none = CppGenerator::default()
+            .with_accessors(AccessorMode::None)
+            .generate(std::slice::from_ref(&obj), "point")
+            .unwrap();
+        assert!(!none.contains("get") && !none.contains("private:"), "Got: {}", none);
+        assert!(none.contains("\tint32_t x;\n\tint32_t y;\n"), "Got: {}", none);
+    }
 
-        assert!(result.contains("#ifndef PERSON_H"));
-        assert!(result.contains("#define PERSON_H"));
-        assert!(result.contains("class Person {"));
-        assert!(result.contains("std::string"));
-        assert!(result.contains("int32_t"));
-        assert!(result.contains("#endif // PERSON_H"));
+    #[test]
+    fn test_type_map_replaces_types_and_adds_includes() {
+        use crate::core::type_map::{TypeMap, TypeMapping};
+
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Label".to_string(),
+            variables: vec![Variable { visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), name: "text".to_string(), ..Default::default() }],
+            ..Default::default()
+        };
+        let mappings: Vec<TypeMapping> = ["cpp:string=QString@<QString>", "cpp:double=Money@\"money.h\""]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        let result = CppGenerator::default()
+            .with_type_map(TypeMap::new(&mappings))
+            .generate(std::slice::from_ref(&obj), "label")
+            .unwrap();
+        assert!(result.contains("#include <utility>\n#include <QString>\n\n"), "Got: {}", result);
+        assert!(result.contains("\tQString text;\n"), "Got: {}", result);
+        assert!(!result.contains("<string>") && !result.contains("money.h"), "Got: {}", result);
     }
 
     #[test]
-    fn test_oml_to_cpp_header_guard_uppercase() {
-        let oml_object = OmlObject {
-            oml_type: ObjectType::CLASS,
-            name: "MyClass".to_string(),
-            variables: vec![],
+    fn test_doxygen_comments() {
+        let variant = |name: &str, doc: Option<&str>| Variable {
+            var_mod: vec![],
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "string".to_string(),
+            array_kind: ArrayKind::None,
+            name: name.to_string(),
+            doc: doc.map(str::to_string),
+            ..Default::default()
+        };
+        let color = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![variant("RED", Some("Stop.")), variant("GREEN", None), variant("BLUE", Some("Calm."))],
+            doc: Some("Paint colors.".to_string()),
+            ..Default::default()
+        };
+        let car = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Car".to_string(),
+            variables: vec![Variable {
+                var_mod: vec![],
+                visibility: VariableVisibility::PUBLIC,
+                var_type: "int32".to_string(),
+                array_kind: ArrayKind::None,
+                name: "doors".to_string(),
+                doc: Some("Number of doors,\nincluding the trunk.".to_string()),
+                ..Default::default()
+            }],
+            doc: Some("A car.\n\nSecond paragraph.".to_string()),
+            ..Default::default()
         };
 
-        let result = oml_to_cpp(&oml_object, "my_class").unwrap();
+        let result = CppGenerator::default().with_pragma_once(true).generate(&[color, car], "car").unwrap();
 
-        assert!(result.contains("#ifndef MY_CLASS_H"));
-        assert!(result.contains("#define MY_CLASS_H"));
-        assert!(result.contains("#endif // MY_CLASS_H"));
+        assert!(result.starts_with(
+            "// This file has been generated from car.oml\n/**\n * @file car.h\n */\n#pragma once\n"
+        ), "Got: {}", result);
+        assert!(result.contains(
+            "/**\n * Paint colors.\n */\nenum class Color {\n\tRED, ///< Stop.\n\tGREEN,\n\tBLUE ///< Calm.\n};\n"
+        ), "Got: {}", result);
+        assert!(result.contains("/**\n * A car.\n *\n * Second paragraph.\n */\nstruct Car {\n"), "Got: {}", result);
+        assert!(result.contains("\tint32_t doors; ///< Number of doors, including the trunk.\n"), "Got: {}", result);
+    }
+
+    #[test]
+    fn test_undocumented_header_has_no_file_block() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Plain".to_string(),
+            ..Default::default()
+        };
+        let result = oml_to_cpp(&oml_object, "plain").unwrap();
+        assert!(!result.contains("@file"), "Got: {}", result);
     }
 
     #[test]
@@ -1040,6 +3200,7 @@ mod tests {
             oml_type: ObjectType::UNDECIDED,
             name: "Test".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "Test");
@@ -1061,6 +3222,7 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "pub1".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -1068,6 +3230,7 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "priv1".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -1075,12 +3238,15 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "pub2".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
 
         // Verify public section comes before private section
         let public_pos = output.find("public:").unwrap();
@@ -1105,6 +3271,7 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "var1".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -1112,12 +3279,15 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "var2".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(output.contains("private:"));
         // public: is now always present for constructors/getters/setters
@@ -1137,12 +3307,15 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "var1".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(!output.contains("private:"));
         assert!(!output.contains("protected:"));
@@ -1162,6 +3335,7 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "MAX_SIZE".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![VariableModifier::OPTIONAL],
@@ -1169,6 +3343,7 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "nickname".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -1176,8 +3351,10 @@ mod tests {
                     var_type: "float".to_string(),
                     array_kind: ArrayKind::None,
                     name: "value".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "ComplexClass").unwrap();
@@ -1201,6 +3378,7 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "var1".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -1208,6 +3386,7 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "var2".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -1215,12 +3394,15 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "var3".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(output.contains("var1"));
         assert!(output.contains("var2"));
@@ -1233,19 +3415,23 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "MyClass".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
         let struct_obj = OmlObject {
             oml_type: ObjectType::STRUCT,
             name: "MyStruct".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
-        let mut class_output = String::new();
-        let mut struct_output = String::new();
+        let mut class_writer = CodeWriter::new(CodeStyle::default());
+        let mut struct_writer = CodeWriter::new(CodeStyle::default());
 
-        generate_class_or_struct(&class_obj, &mut class_output).unwrap();
-        generate_class_or_struct(&struct_obj, &mut struct_output).unwrap();
+        CppGenerator::default().generate_class_or_struct(&class_obj, &[], None, &mut class_writer).unwrap();
+        CppGenerator::default().generate_class_or_struct(&struct_obj, &[], None, &mut struct_writer).unwrap();
+        let class_output = class_writer.finish();
+        let struct_output = struct_writer.finish();
 
         assert!(class_output.contains("class MyClass"));
         assert!(struct_output.contains("struct MyStruct"));
@@ -1265,10 +3451,12 @@ mod tests {
             var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
             name: "value".to_string(),
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        convert_modifiers_and_type(&var, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().convert_modifiers_and_type(&var, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(output.contains("static"));
         assert!(output.contains("const"));
@@ -1287,8 +3475,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "Test");
@@ -1303,10 +3493,12 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "My_Class-123".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(output.contains("class My_Class-123 {"));
     }
@@ -1325,12 +3517,15 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: long_name.to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(output.contains(long_name));
     }
@@ -1349,12 +3544,15 @@ mod tests {
                     var_type: "".to_string(),
                     array_kind: ArrayKind::None,
                     name: "Value".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_enum(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        generate_enum(&oml_object, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(output.contains("\tVALUE"));
     }
@@ -1364,7 +3562,15 @@ mod tests {
         let oml_object = OmlObject {
             oml_type: ObjectType::CLASS,
             name: "Test".to_string(),
-            variables: vec![],
+            variables: vec![Variable {
+                var_mod: vec![],
+                visibility: VariableVisibility::PRIVATE,
+                var_type: "int32".to_string(),
+                array_kind: ArrayKind::None,
+                name: "id".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "Test").unwrap();
@@ -1390,10 +3596,12 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "Test".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(output.contains("};"));
     }
@@ -1404,10 +3612,12 @@ mod tests {
             oml_type: ObjectType::ENUM,
             name: "Test".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_enum(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        generate_enum(&oml_object, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(output.contains("};"));
     }
@@ -1420,7 +3630,15 @@ mod tests {
         let oml_object = OmlObject {
             oml_type: ObjectType::CLASS,
             name: "Test".to_string(),
-            variables: vec![],
+            variables: vec![Variable {
+                var_mod: vec![],
+                visibility: VariableVisibility::PRIVATE,
+                var_type: "int32".to_string(),
+                array_kind: ArrayKind::None,
+                name: "id".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "Test").unwrap();
@@ -1445,8 +3663,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "value".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "Test").unwrap();
@@ -1469,12 +3689,15 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "prot_var".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_class_or_struct(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        CppGenerator::default().generate_class_or_struct(&oml_object, &[], None, &mut writer).unwrap();
+        let output = writer.finish();
 
         // With current implementation, protected vars are output but no label is shown
         // This test documents current behavior
@@ -1499,6 +3722,7 @@ mod tests {
                 var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                 name: format!("var{}", i),
+                ..Default::default()
             });
         }
 
@@ -1506,6 +3730,7 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "LargeClass".to_string(),
             variables,
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "LargeClass");
@@ -1526,6 +3751,7 @@ mod tests {
                 var_type: "".to_string(),
                     array_kind: ArrayKind::None,
                 name: format!("Variant{}", i),
+                ..Default::default()
             });
         }
 
@@ -1533,10 +3759,12 @@ mod tests {
             oml_type: ObjectType::ENUM,
             name: "LargeEnum".to_string(),
             variables,
+            ..Default::default()
         };
 
-        let mut output = String::new();
-        generate_enum(&oml_object, &mut output).unwrap();
+        let mut writer = CodeWriter::new(CodeStyle::default());
+        generate_enum(&oml_object, &mut writer).unwrap();
+        let output = writer.finish();
 
         assert!(output.contains("VARIANT0,"));
         assert!(output.contains("VARIANT49"));
@@ -1547,7 +3775,7 @@ mod tests {
 
     #[test]
     fn test_all_integer_types_in_class() {
-        let types = vec!["int8", "int16", "int32", "int64", "uint8", "uint16", "uint32", "uint64"];
+        let types = ["int8", "int16", "int32", "int64", "uint8", "uint16", "uint32", "uint64"];
         let mut variables = vec![];
 
         for (i, type_name) in types.iter().enumerate() {
@@ -1557,6 +3785,7 @@ mod tests {
                 var_type: type_name.to_string(),
                     array_kind: ArrayKind::None,
                 name: format!("var{}", i),
+                ..Default::default()
             });
         }
 
@@ -1564,6 +3793,7 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "AllTypes".to_string(),
             variables,
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "AllTypes").unwrap();
@@ -1590,8 +3820,10 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "text".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "StringTest").unwrap();
@@ -1612,6 +3844,7 @@ mod tests {
                     var_type: "bool".to_string(),
                     array_kind: ArrayKind::None,
                     name: "flag".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -1619,8 +3852,10 @@ mod tests {
                     var_type: "char".to_string(),
                     array_kind: ArrayKind::None,
                     name: "letter".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let result = oml_to_cpp(&oml_object, "BasicTypes").unwrap();
@@ -1628,6 +3863,93 @@ mod tests {
         assert!(result.contains("bool"));
         assert!(result.contains("char"));
     }
+
+    #[test]
+    fn test_schema_hash_constant() {
+        let color = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![Variable { var_type: "string".to_string(), name: "Red".to_string(), ..Default::default() }],
+            ..Default::default()
+        };
+        let car = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![Variable { var_type: "string".to_string(), name: "name".to_string(), ..Default::default() }],
+            ..Default::default()
+        };
+
+        let result = CppGenerator::default()
+            .with_schema_hash(true)
+            .generate(&[color, car.clone()], "car")
+            .unwrap();
+        assert!(result.contains("#include <cstdint>\n"), "Got: {}", result);
+        assert!(result.contains(&format!(
+            "class Car {{\npublic:\n\tstatic constexpr uint64_t SCHEMA_HASH = 0x{:016x}ULL;\n\n\tCar() = default;\n",
+            schema_hash(&car)
+        )), "Got: {}", result);
+        assert_eq!(result.matches("SCHEMA_HASH").count(), 1, "Got: {}", result);
+        assert_eq!(CppGenerator::default().reverse(&result).unwrap()[1].variables.len(), 1);
+    }
+
+    #[test]
+    fn test_static_constants_are_defined_in_the_class() {
+        let objects = OmlObject::scan_file(concat!(
+            "class Limits {\n",
+            "public static const int32 MAX = 10;\n",
+            "public static const uint64 MASK = 18446744073709551615;\n",
+            "public static const string UNIT = \"km/h\";\n",
+            "public static int32 created = 0;\n",
+            "public static const int32 LEGACY;\n",
+            "}\n",
+        ).to_string()).unwrap();
+
+        let result = CppGenerator::default().with_standard(CppStandard::Cpp17).generate(&objects, "limits").unwrap();
+        assert!(result.contains(concat!(
+            "\tstatic constexpr int32_t MAX = 10;\n",
+            "\tstatic constexpr uint64_t MASK = 18446744073709551615ULL;\n",
+            "\tstatic inline const std::string UNIT = \"km/h\";\n",
+            "\tstatic inline int32_t created = 0;\n",
+            "\tstatic const int32_t LEGACY;\n",
+        )), "Got: {}", result);
+
+        let result = CppGenerator::default().with_standard(CppStandard::Cpp11).generate(&objects, "limits").unwrap();
+        assert!(result.contains(concat!(
+            "\tstatic constexpr int32_t MAX = 10;\n",
+            "\tstatic constexpr uint64_t MASK = 18446744073709551615ULL;\n",
+            "\tstatic constexpr const char* UNIT = \"km/h\";\n",
+            "\tstatic int32_t created;\n",
+        )), "Got: {}", result);
+
+        // the constants read back with their values
+        let reversed = CppGenerator::default().reverse(&result).unwrap();
+        let constants: Vec<_> = reversed[0].variables.iter()
+            .map(|v| (v.name.as_str(), v.var_type.as_str(), v.var_mod.contains(&VariableModifier::CONST), v.value.as_deref()))
+            .collect();
+        assert_eq!(constants, vec![
+            ("MAX", "int32", true, Some("10")),
+            ("MASK", "uint64", true, Some("18446744073709551615")),
+            ("UNIT", "string", true, Some("\"km/h\"")),
+            ("created", "int32", false, None),
+            ("LEGACY", "int32", true, None),
+        ]);
+    }
+
+    #[test]
+    fn test_statics_with_values_are_defined_in_the_source_before_cpp17() {
+        let objects = OmlObject::scan_file(
+            "class Limits {\npublic static int32 created = 0;\npublic static string label = \"car\";\npublic static const int32 MAX = 10;\n}\n".to_string()
+        ).unwrap();
+
+        let files = CppGenerator::default().with_standard(CppStandard::Cpp14).generate_files(&objects, "limits").unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files[1].content.contains(
+            "#include \"limits.h\"\n\nint32_t Limits::created = 0;\nstd::string Limits::label = \"car\";\n"
+        ), "Got: {}", files[1].content);
+
+        let files = CppGenerator::default().with_standard(CppStandard::Cpp17).generate_files(&objects, "limits").unwrap();
+        assert_eq!(files.len(), 1);
+    }
 }
 
 #[cfg(test)]
@@ -1636,7 +3958,7 @@ mod array_tests {
     use crate::core::oml_object::{OmlObject, ObjectType, Variable, VariableVisibility, ArrayKind};
 
     fn to_cpp(oml_object: &OmlObject) -> String {
-        CppGenerator.generate(std::slice::from_ref(oml_object), "test").unwrap()
+        CppGenerator::default().generate(std::slice::from_ref(oml_object), "test").unwrap()
     }
 
     fn array_var(name: &str, ty: &str, kind: ArrayKind) -> Variable {
@@ -1646,6 +3968,7 @@ mod array_tests {
             var_type: ty.to_string(),
             array_kind: kind,
             name: name.to_string(),
+            ..Default::default()
         }
     }
 
@@ -1655,6 +3978,7 @@ mod array_tests {
             oml_type: ObjectType::CLASS,
             name: "Arr".to_string(),
             variables: vec![array_var("scores", "uint16", ArrayKind::Static(4))],
+            ..Default::default()
         };
         let out = to_cpp(&obj);
         assert!(out.contains("std::array<uint16_t, 4>"), "Got: {}", out);
@@ -1667,6 +3991,7 @@ mod array_tests {
             oml_type: ObjectType::CLASS,
             name: "Lst".to_string(),
             variables: vec![array_var("tags", "string", ArrayKind::Dynamic)],
+            ..Default::default()
         };
         let out = to_cpp(&obj);
         assert!(out.contains("std::vector<std::string>"), "Got: {}", out);
@@ -1679,9 +4004,48 @@ mod array_tests {
             oml_type: ObjectType::CLASS,
             name: "Plain".to_string(),
             variables: vec![array_var("x", "int32", ArrayKind::None)],
+            ..Default::default()
         };
         let out = to_cpp(&obj);
         assert!(!out.contains("#include <array>"), "Got: {}", out);
         assert!(!out.contains("#include <vector>"), "Got: {}", out);
     }
+
+    #[test]
+    fn test_code_style_spaces_and_next_line_braces() {
+        use crate::core::code_writer::{BraceStyle, IndentKind};
+
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Point".to_string(),
+            variables: vec![array_var("x", "int32", ArrayKind::None)],
+            ..Default::default()
+        };
+        let style = CodeStyle {
+            indent_kind: IndentKind::Spaces,
+            indent_width: 2,
+            brace_style: BraceStyle::NextLine,
+            ..Default::default()
+        };
+        let out = CppGenerator::default().with_style(style).generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.contains("class Point\n{\n"), "Got: {}", out);
+        assert!(out.contains("\n  Point() = default;"), "Got: {}", out);
+        assert!(!out.contains('\t'), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_max_line_length_controls_constructor_wrapping() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Point".to_string(),
+            variables: vec![array_var("x", "int32", ArrayKind::None), array_var("y", "int32", ArrayKind::None)],
+            ..Default::default()
+        };
+        let wide = to_cpp(&obj);
+        assert!(wide.contains("Point(int32_t x, int32_t y) : x(std::move(x)), y(std::move(y)) {}"), "Got: {}", wide);
+
+        let style = CodeStyle { max_line_length: 40, ..Default::default() };
+        let narrow = CppGenerator::default().with_style(style).generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(narrow.contains("\tPoint(int32_t x, int32_t y)\n\t\t: x(std::move(x))\n\t\t, y(std::move(y))\n\t{}"), "Got: {}", narrow);
+    }
 }
@@ -0,0 +1,192 @@
+//! pybind11 bindings for the standard C++ output: `<file>_bindings.cpp`
+//! defines a Python module named after the OML file exposing its enums,
+//! classes and structs with their constructors, fields and accessors.
+
+use crate::core::oml_object::{OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier};
+use crate::core::code_writer::CodeWriter;
+use crate::core::generate::GeneratedFile;
+use super::oml_cpp::{CppGenerator, capitalize_first};
+use std::fmt::Write;
+
+impl CppGenerator {
+    /// `<file>_bindings.cpp`, built into a Python extension module `<file>`.
+    pub(super) fn generate_bindings(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<GeneratedFile, std::fmt::Error> {
+        let objects = self.type_map.apply(&self.apply_accessors(oml_objects));
+
+        let mut cpp_file = CodeWriter::new(self.style);
+        writeln!(cpp_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(cpp_file)?;
+        writeln!(cpp_file, "#include <pybind11/pybind11.h>")?;
+        // converts optionals, vectors and arrays to and from Python
+        writeln!(cpp_file, "#include <pybind11/stl.h>")?;
+        writeln!(cpp_file, "#include \"{}.h\"", file_name)?;
+        writeln!(cpp_file)?;
+        writeln!(cpp_file, "namespace py = pybind11;")?;
+        if let Some(namespace) = &self.namespace {
+            writeln!(cpp_file, "using namespace {};", namespace)?;
+        }
+        writeln!(cpp_file)?;
+
+        cpp_file.begin_block(&format!("PYBIND11_MODULE({}, m)", file_name))?;
+        for (i, oml_object) in objects.iter().enumerate() {
+            if i > 0 {
+                cpp_file.line("")?;
+            }
+            match oml_object.oml_type {
+                ObjectType::ENUM => write_enum(oml_object, &mut cpp_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => self.write_class(oml_object, &mut cpp_file)?,
+                ObjectType::UNDECIDED => return Err(std::fmt::Error),
+            }
+        }
+        cpp_file.end_block("}")?;
+
+        Ok(GeneratedFile::new(format!("{}_bindings.cpp", file_name), cpp_file.finish()))
+    }
+
+    /// `py::class_` with the constructors of the header, the public fields
+    /// and a property per private field with accessors. Protected fields are
+    /// out of reach.
+    fn write_class(&self, oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let fields: Vec<&Variable> = oml_object.variables
+            .iter()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+            .collect();
+        let required: Vec<&Variable> = fields.iter().copied().filter(|v| !v.var_mod.contains(&VariableModifier::OPTIONAL)).collect();
+
+        let mut calls = Vec::new();
+        // a defaulted constructor is deleted while a const field is left
+        // uninitialized
+        if !fields.iter().any(|v| v.is_const()) {
+            calls.push("def(py::init<>())".to_string());
+        }
+        if !required.is_empty() && required.len() < fields.len() {
+            calls.push(self.init(&required));
+        }
+        if !fields.is_empty() {
+            calls.push(self.init(&fields));
+        }
+        for var in &oml_object.variables {
+            let is_static = var.var_mod.contains(&VariableModifier::STATIC);
+            let read_only = var.is_const();
+            let suffix = if is_static { "_static" } else { "" };
+            match var.visibility {
+                VariableVisibility::PUBLIC => {
+                    let def = if read_only { "def_readonly" } else { "def_readwrite" };
+                    calls.push(format!("{}{}(\"{}\", &{}::{})", def, suffix, var.name, name, var.name));
+                }
+                VariableVisibility::PRIVATE if read_only => {
+                    calls.push(format!("def_property_readonly(\"{}\", &{}::get{})", var.name, name, capitalize_first(&var.name)));
+                }
+                VariableVisibility::PRIVATE => {
+                    let accessor = capitalize_first(&var.name);
+                    calls.push(format!("def_property(\"{}\", &{}::get{}, &{}::set{})", var.name, name, accessor, name, accessor));
+                }
+                VariableVisibility::PROTECTED => {}
+            }
+        }
+
+        write_chain(&format!("py::class_<{}>(m, \"{}\")", name, name), &calls, cpp_file)
+    }
+
+    /// A `py::init` overload taking `vars` in order, as keywords as well.
+    fn init(&self, vars: &[&Variable]) -> String {
+        let types: Vec<String> = vars.iter().map(|v| self.get_full_type(v)).collect();
+        let args: Vec<String> = vars.iter().map(|v| format!("py::arg(\"{}\")", v.name)).collect();
+        format!("def(py::init<{}>(), {})", types.join(", "), args.join(", "))
+    }
+}
+
+fn write_enum(oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let calls: Vec<String> = oml_object.variables
+        .iter()
+        .map(|v| format!("value(\"{}\", {}::{})", v.name.to_uppercase(), name, v.name.to_uppercase()))
+        .collect();
+    write_chain(&format!("py::enum_<{}>(m, \"{}\")", name, name), &calls, cpp_file)
+}
+
+/// `head` followed by one `.call` per line.
+fn write_chain(head: &str, calls: &[String], cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    if calls.is_empty() {
+        return cpp_file.line(&format!("{};", head));
+    }
+    cpp_file.line(head)?;
+    cpp_file.indented(|cpp_file| {
+        for (i, call) in calls.iter().enumerate() {
+            let end = if i + 1 == calls.len() { ";" } else { "" };
+            cpp_file.line(&format!(".{}{}", call, end))?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generate::{AccessorMode, Generate};
+    use crate::core::oml_object::ArrayKind;
+
+    #[test]
+    fn test_bindings_module() {
+        let var = |name: &str, var_type: &str, visibility: VariableVisibility, var_mod: Vec<VariableModifier>| Variable {
+            name: name.to_string(),
+            var_type: var_type.to_string(),
+            visibility,
+            var_mod,
+            ..Default::default()
+        };
+        let objects = [
+            OmlObject {
+                oml_type: ObjectType::ENUM,
+                name: "Color".to_string(),
+                variables: vec![var("Red", "string", VariableVisibility::PUBLIC, vec![]), var("Blue", "string", VariableVisibility::PUBLIC, vec![])],
+                ..Default::default()
+            },
+            OmlObject {
+                oml_type: ObjectType::CLASS,
+                name: "Car".to_string(),
+                variables: vec![
+                    var("name", "string", VariableVisibility::PUBLIC, vec![VariableModifier::CONST]),
+                    var("color", "Color", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL]),
+                    Variable { array_kind: ArrayKind::Dynamic, ..var("wheels", "double", VariableVisibility::PRIVATE, vec![]) },
+                    var("count", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC]),
+                    var("secret", "int32", VariableVisibility::PROTECTED, vec![]),
+                ],
+                ..Default::default()
+            },
+        ];
+
+        let files = CppGenerator::default()
+            .with_accessors(AccessorMode::Private)
+            .with_pybind11(true)
+            .generate_files(&objects, "car")
+            .unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1].path, std::path::PathBuf::from("car_bindings.cpp"));
+        assert_eq!(
+            files[1].content,
+            "// This file has been generated from car.oml\n\
+             \n\
+             #include <pybind11/pybind11.h>\n\
+             #include <pybind11/stl.h>\n\
+             #include \"car.h\"\n\
+             \n\
+             namespace py = pybind11;\n\
+             \n\
+             PYBIND11_MODULE(car, m) {\n\
+             \tpy::enum_<Color>(m, \"Color\")\n\
+             \t\t.value(\"RED\", Color::RED)\n\
+             \t\t.value(\"BLUE\", Color::BLUE);\n\
+             \n\
+             \tpy::class_<Car>(m, \"Car\")\n\
+             \t\t.def(py::init<std::string, std::vector<double>, int32_t>(), py::arg(\"name\"), py::arg(\"wheels\"), py::arg(\"secret\"))\n\
+             \t\t.def(py::init<std::string, std::optional<Color>, std::vector<double>, int32_t>(), py::arg(\"name\"), py::arg(\"color\"), py::arg(\"wheels\"), py::arg(\"secret\"))\n\
+             \t\t.def_readonly(\"name\", &Car::name)\n\
+             \t\t.def_readwrite(\"color\", &Car::color)\n\
+             \t\t.def_property(\"wheels\", &Car::getWheels, &Car::setWheels)\n\
+             \t\t.def_readwrite_static(\"count\", &Car::count);\n\
+             }\n"
+        );
+    }
+}
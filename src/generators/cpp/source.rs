@@ -0,0 +1,135 @@
+//! The source file of the standard C++ output: `<file>.cpp` includes the
+//! headers of the types fields hold by pointer, which the header only
+//! forward-declares, and defines the members that need those types
+//! complete: constructors, moves, destructors and pointer setters. Before
+//! C++17 it also defines the statics given a value that the class cannot.
+
+use crate::core::oml_object::{OmlObject, ObjectType, VariableModifier};
+use crate::core::code_writer::CodeWriter;
+use crate::core::generate::GeneratedFile;
+use super::oml_cpp::{CppGenerator, capitalize_first, cpp_literal, write_constructor};
+use std::fmt::Write;
+
+impl CppGenerator {
+    /// `<file>.cpp`, defining the members the header declares for classes
+    /// and structs holding fields by pointer, and the statics defined out of
+    /// line.
+    pub(super) fn generate_source(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<GeneratedFile, std::fmt::Error> {
+        let objects = self.type_map.apply(&self.apply_accessors(oml_objects));
+        let defining: Vec<&OmlObject> = objects
+            .iter()
+            .filter(|o| matches!(o.oml_type, ObjectType::CLASS | ObjectType::STRUCT))
+            .filter(|o| self.holds_pointers(o) || o.variables.iter().any(|v| self.is_defined_out_of_line(v)))
+            .collect();
+
+        let mut cpp_file = CodeWriter::new(self.style);
+        writeln!(cpp_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(cpp_file)?;
+        writeln!(cpp_file, "#include \"{}.h\"", file_name)?;
+        writeln!(cpp_file)?;
+
+        let mut headers: Vec<&str> = Vec::new();
+        for var in defining.iter().flat_map(|o| &o.variables) {
+            if let Some(header) = self.pointer(&var.var_type).and_then(|p| p.header.as_deref())
+                && !objects.iter().any(|o| o.name == var.var_type)
+                && !headers.contains(&header)
+            {
+                headers.push(header);
+            }
+        }
+        for header in &headers {
+            writeln!(cpp_file, "#include {}", header)?;
+        }
+        if !headers.is_empty() {
+            writeln!(cpp_file)?;
+        }
+
+        let namespaces = self.namespaces();
+        for namespace in &namespaces {
+            cpp_file.open_block(0, &format!("namespace {}", namespace))?;
+        }
+        if !namespaces.is_empty() {
+            writeln!(cpp_file)?;
+        }
+        for (i, oml_object) in defining.iter().enumerate() {
+            if i > 0 {
+                writeln!(cpp_file)?;
+            }
+            self.define_statics(oml_object, &mut cpp_file)?;
+            if self.holds_pointers(oml_object) {
+                self.define_pointer_members(oml_object, &mut cpp_file)?;
+            }
+        }
+        if !namespaces.is_empty() {
+            writeln!(cpp_file)?;
+        }
+        for namespace in namespaces.iter().rev() {
+            writeln!(cpp_file, "}} // namespace {}", namespace)?;
+        }
+
+        Ok(GeneratedFile::new(format!("{}.cpp", file_name), cpp_file.finish()))
+    }
+
+    /// The statics of a class the header declares but cannot define.
+    fn define_statics(&self, oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let statics: Vec<_> = oml_object.variables
+            .iter()
+            .filter(|v| self.is_defined_out_of_line(v))
+            .filter_map(|v| Some((v, v.value.as_deref()?)))
+            .collect();
+        for (var, value) in &statics {
+            let constness = if var.var_mod.contains(&VariableModifier::CONST) { "const " } else { "" };
+            writeln!(
+                cpp_file,
+                "{}{} {}::{} = {};",
+                constness, self.get_full_type(var), oml_object.name, var.name, cpp_literal(&var.var_type, value)
+            )?;
+        }
+        if !statics.is_empty() && self.holds_pointers(oml_object) {
+            writeln!(cpp_file)?;
+        }
+
+        Ok(())
+    }
+
+    /// The constructors, moves, destructor and pointer setters of a class
+    /// the header only declares them for.
+    fn define_pointer_members(&self, oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let name = &oml_object.name;
+        let qualified = format!("{}::{}", name, name);
+        let members = self.pointer_members(oml_object);
+        for constructor in self.constructors(oml_object) {
+            if !constructor.params.is_empty() {
+                write_constructor(cpp_file, 0, "", &qualified, &constructor.params, &constructor.inits)?;
+            } else if members.default_constructor {
+                writeln!(cpp_file, "{}() = default;", qualified)?;
+            }
+        }
+
+        if members.move_constructor {
+            writeln!(cpp_file, "{}({}&& other) noexcept = default;", qualified, name)?;
+        }
+        if members.move_assignment {
+            writeln!(cpp_file, "{}& {}::operator=({}&& other) noexcept = default;", name, name, name)?;
+        }
+        writeln!(cpp_file, "{}::~{}() = default;", name, name)?;
+
+        let setters: Vec<_> = oml_object.variables
+            .iter()
+            .filter(|v| self.pointer(&v.var_type).is_some() && self.accessors.has_accessors(v))
+            .filter(|v| !v.var_mod.contains(&VariableModifier::CONST))
+            .collect();
+        if !setters.is_empty() {
+            writeln!(cpp_file)?;
+        }
+        for var in setters {
+            writeln!(
+                cpp_file,
+                "void {}::set{}({} value) {{ {} = std::move(value); }}",
+                name, capitalize_first(&var.name), self.get_full_type(var), var.name
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use std::path::Path;
 
 use crate::core::generate::Generate;
 use crate::core::oml_object::{
-    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+    OmlObject, ObjectType, Variable, VariableVisibility, ArrayKind
 };
 use crate::generators::cpp::oml_cpp::CppGenerator;
 
@@ -16,18 +16,18 @@ fn ensure_test_results_dir() {
 fn generate_and_write(oml_path: &str, file_name: &str) -> String {
     ensure_test_results_dir();
 
-    let generator = CppGenerator;
+    let generator = CppGenerator::default();
 
     let path = Path::new(oml_path);
     let (oml_objects, _imports) = OmlObject::get_from_file(path)
-        .expect(&format!("Failed to parse OML file: {}", oml_path));
+        .unwrap_or_else(|_| panic!("Failed to parse OML file: {}", oml_path));
 
     let cpp_output = generator.generate(&oml_objects, file_name)
-        .expect(&format!("Failed to generate C++ for: {}", file_name));
+        .unwrap_or_else(|_| panic!("Failed to generate C++ for: {}", file_name));
 
     let output_path = format!("{}/{}.{}", TEST_RESULTS_DIR, file_name, generator.extension());
     fs::write(&output_path, &cpp_output)
-        .expect(&format!("Failed to write output file: {}", output_path));
+        .unwrap_or_else(|_| panic!("Failed to write output file: {}", output_path));
 
     cpp_output
 }
@@ -71,7 +71,7 @@ fn test_person_class_generates_cpp_file() {
     assert!(output.contains("~Person() = default;"));
 
     // Getters
-    assert!(output.contains("std::string getName() const { return name; }"));
+    assert!(output.contains("std::string_view getName() const { return name; }"));
     assert!(output.contains("int32_t getAge() const { return age; }"));
     assert!(output.contains("std::optional<std::string> getNickname() const { return nickname; }"));
 
@@ -138,14 +138,15 @@ fn test_color_enum_generates_cpp_file() {
         oml_type: ObjectType::ENUM,
         name: "Color".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Red".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Green".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Blue".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Yellow".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Red".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Green".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Blue".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Yellow".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let generator = CppGenerator;
+    let generator = CppGenerator::default();
     let output = generator.generate(std::slice::from_ref(&oml_object), "Color").unwrap();
 
     let output_path = format!("{}/Color.h", TEST_RESULTS_DIR);
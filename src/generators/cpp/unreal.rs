@@ -0,0 +1,307 @@
+//! Unreal Engine flavour of the C++ generator: reflected `USTRUCT`/`UCLASS`
+//! declarations with `UPROPERTY` fields, using the engine's naming
+//! prefixes (`F` structs, `U` objects, `E` enums) and container types.
+
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_pascal_case;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Write;
+
+pub fn generate(oml_objects: &[OmlObject], file_name: &str, style: CodeStyle) -> Result<String, Box<dyn Error>> {
+    let mut cpp_file = CodeWriter::new(style);
+
+    // types declared in this file; anything else is assumed to be a struct
+    let kinds: HashMap<&str, &ObjectType> = oml_objects
+        .iter()
+        .map(|o| (o.name.as_str(), &o.oml_type))
+        .collect();
+
+    writeln!(cpp_file, "// This file has been generated from {}.oml", file_name)?;
+    writeln!(cpp_file)?;
+    writeln!(cpp_file, "#pragma once")?;
+    writeln!(cpp_file)?;
+    writeln!(cpp_file, "#include \"CoreMinimal.h\"")?;
+    if oml_objects.iter().any(|o| o.oml_type == ObjectType::CLASS) {
+        writeln!(cpp_file, "#include \"UObject/Object.h\"")?;
+    }
+    // must stay the last include
+    writeln!(cpp_file, "#include \"{}.generated.h\"", file_name)?;
+    writeln!(cpp_file)?;
+
+    for (i, oml_object) in oml_objects.iter().enumerate() {
+        match &oml_object.oml_type {
+            ObjectType::ENUM => generate_enum(oml_object, &mut cpp_file)?,
+            ObjectType::CLASS | ObjectType::STRUCT => generate_type(oml_object, &kinds, &mut cpp_file)?,
+            ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+        }
+        if i < oml_objects.len() - 1 {
+            writeln!(cpp_file)?;
+        }
+    }
+
+    Ok(cpp_file.finish())
+}
+
+/// The engine name of an OML object: `F` for structs, `U` for classes and
+/// `E` for enums.
+fn engine_name(name: &str, oml_type: &ObjectType) -> String {
+    let prefix = match oml_type {
+        ObjectType::CLASS => "U",
+        ObjectType::ENUM => "E",
+        ObjectType::STRUCT | ObjectType::UNDECIDED => "F",
+    };
+    format!("{}{}", prefix, name)
+}
+
+/// Reflected enums must be backed by `uint8`.
+fn generate_enum(oml_object: &OmlObject, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(cpp_file, "UENUM(BlueprintType)")?;
+    cpp_file.open_block(0, &format!("enum class {} : uint8", engine_name(&oml_object.name, &ObjectType::ENUM)))?;
+    for var in &oml_object.variables {
+        let name = to_pascal_case(&var.name);
+        writeln!(cpp_file, "{}{} UMETA(DisplayName = \"{}\"),", cpp_file.indent(1), name, name)?;
+    }
+    writeln!(cpp_file, "}};")?;
+
+    Ok(())
+}
+
+/// Structs become `USTRUCT`s and classes `UObject` subclasses. Statics
+/// cannot be reflected, so they are plain inline members.
+fn generate_type(
+    oml_object: &OmlObject,
+    kinds: &HashMap<&str, &ObjectType>,
+    cpp_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    let name = engine_name(&oml_object.name, &oml_object.oml_type);
+    match oml_object.oml_type {
+        ObjectType::CLASS => {
+            writeln!(cpp_file, "UCLASS(BlueprintType)")?;
+            cpp_file.open_block(0, &format!("class {} : public UObject", name))?;
+        }
+        _ => {
+            writeln!(cpp_file, "USTRUCT(BlueprintType)")?;
+            cpp_file.open_block(0, &format!("struct {}", name))?;
+        }
+    }
+    writeln!(cpp_file, "{}GENERATED_BODY()", cpp_file.indent(1))?;
+
+    for visibility in [VariableVisibility::PUBLIC, VariableVisibility::PROTECTED, VariableVisibility::PRIVATE] {
+        let vars: Vec<&Variable> = oml_object.variables.iter().filter(|v| v.visibility == visibility).collect();
+        if vars.is_empty() {
+            continue;
+        }
+
+        writeln!(cpp_file)?;
+        let label = match visibility {
+            VariableVisibility::PUBLIC => "public:",
+            VariableVisibility::PROTECTED => "protected:",
+            VariableVisibility::PRIVATE => "private:",
+        };
+        writeln!(cpp_file, "{}", label)?;
+        for var in vars {
+            write_member(var, kinds, cpp_file)?;
+        }
+    }
+
+    writeln!(cpp_file, "}};")?;
+
+    Ok(())
+}
+
+fn write_member(var: &Variable, kinds: &HashMap<&str, &ObjectType>, cpp_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let ty = unreal_type(var, kinds);
+    let name = to_pascal_case(&var.name);
+    let read_only = var.is_const();
+
+    if var.var_mod.contains(&VariableModifier::STATIC) {
+        let constness = if read_only { "const " } else { "" };
+        writeln!(cpp_file, "{}static inline {}{} {}{{}};", cpp_file.indent(1), constness, ty, name)?;
+        return Ok(());
+    }
+
+    if var.var_type == "char" {
+        // TCHAR is not a reflected type
+        writeln!(cpp_file, "{}{} {};", cpp_file.indent(1), ty, name)?;
+        return Ok(());
+    }
+
+    let mut specifiers = vec![if read_only { "VisibleAnywhere" } else { "EditAnywhere" }];
+    if is_blueprint_type(var, kinds) {
+        specifiers.push(if read_only { "BlueprintReadOnly" } else { "BlueprintReadWrite" });
+        if var.visibility != VariableVisibility::PUBLIC {
+            specifiers.push("meta = (AllowPrivateAccess = \"true\")");
+        }
+    }
+    writeln!(cpp_file, "{}UPROPERTY({})", cpp_file.indent(1), specifiers.join(", "))?;
+
+    match &var.array_kind {
+        ArrayKind::Static(n) => writeln!(cpp_file, "{}{} {}[{}];", cpp_file.indent(1), ty, name, n)?,
+        _ => writeln!(cpp_file, "{}{} {};", cpp_file.indent(1), ty, name)?,
+    }
+
+    Ok(())
+}
+
+/// Blueprints only understand a subset of the reflected types: no narrow or
+/// unsigned integers besides `uint8`, no fixed-size arrays and no
+/// `TOptional`.
+fn is_blueprint_type(var: &Variable, kinds: &HashMap<&str, &ObjectType>) -> bool {
+    if matches!(var.array_kind, ArrayKind::Static(_)) {
+        return false;
+    }
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) && kinds.get(var.var_type.as_str()) != Some(&&ObjectType::CLASS) {
+        return false;
+    }
+    !matches!(var.var_type.as_str(), "int8" | "int16" | "uint16" | "uint32" | "uint64" | "char")
+}
+
+#[inline]
+fn convert_type(var_type: &str, kinds: &HashMap<&str, &ObjectType>) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" | "int64" => var_type.to_string(),
+        "uint8" | "uint16" | "uint32" | "uint64" => var_type.to_string(),
+        "float" => "float".to_string(),
+        "double" => "double".to_string(),
+        "bool" => "bool".to_string(),
+        "string" => "FString".to_string(),
+        "char" => "TCHAR".to_string(),
+        other => match kinds.get(other) {
+            Some(ObjectType::CLASS) => format!("TObjectPtr<{}>", engine_name(other, &ObjectType::CLASS)),
+            Some(oml_type) => engine_name(other, oml_type),
+            None => engine_name(other, &ObjectType::STRUCT),
+        },
+    }
+}
+
+/// Dynamic arrays become `TArray`; optional values are wrapped in
+/// `TOptional`, except object pointers which are already nullable.
+fn unreal_type(var: &Variable, kinds: &HashMap<&str, &ObjectType>) -> String {
+    let base = convert_type(&var.var_type, kinds);
+    let ty = match &var.array_kind {
+        ArrayKind::Dynamic => format!("TArray<{}>", base),
+        _ => base,
+    };
+    let nullable = var.array_kind == ArrayKind::None && kinds.get(var.var_type.as_str()) == Some(&&ObjectType::CLASS);
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) && !nullable {
+        format!("TOptional<{}>", ty)
+    } else {
+        ty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::generate::Generate;
+    use crate::core::oml_object::{OmlObject, ObjectType, VariableVisibility, VariableModifier, ArrayKind};
+    use crate::generators::cpp::oml_cpp::CppGenerator;
+    use crate::core::test::fixtures::member;
+
+    fn to_unreal(oml_objects: &[OmlObject]) -> String {
+        CppGenerator::default().with_unreal(true).generate(oml_objects, "vehicle").unwrap()
+    }
+
+    fn color() -> OmlObject {
+        OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                member("RED", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("dark_blue", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_includes_end_with_generated_header() {
+        let out = to_unreal(&[color()]);
+        assert!(out.contains("#pragma once\n\n#include \"CoreMinimal.h\"\n#include \"vehicle.generated.h\"\n\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_uenum() {
+        assert!(to_unreal(&[color()]).contains(
+            "UENUM(BlueprintType)\n\
+             enum class EColor : uint8 {\n\
+             \tRed UMETA(DisplayName = \"Red\"),\n\
+             \tDarkBlue UMETA(DisplayName = \"DarkBlue\"),\n\
+             };\n"
+        ));
+    }
+
+    #[test]
+    fn test_ustruct_properties() {
+        let wheel = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Wheel".to_string(),
+            variables: vec![
+                member("radius", "float", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("label", "string", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::None),
+                member("color", "Color", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("tags", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::Dynamic),
+                member("pressure", "double", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("bolts", "uint16", VariableVisibility::PRIVATE, vec![], ArrayKind::Static(5)),
+            ],
+            ..Default::default()
+        };
+        let out = to_unreal(&[color(), wheel]);
+        assert!(out.contains(
+            "USTRUCT(BlueprintType)\n\
+             struct FWheel {\n\
+             \tGENERATED_BODY()\n\
+             \n\
+             public:\n\
+             \tUPROPERTY(EditAnywhere, BlueprintReadWrite)\n\
+             \tfloat Radius;\n\
+             \tUPROPERTY(VisibleAnywhere, BlueprintReadOnly)\n\
+             \tFString Label;\n\
+             \tUPROPERTY(EditAnywhere, BlueprintReadWrite)\n\
+             \tEColor Color;\n\
+             \tUPROPERTY(EditAnywhere, BlueprintReadWrite)\n\
+             \tTArray<FString> Tags;\n\
+             \tUPROPERTY(EditAnywhere)\n\
+             \tTOptional<double> Pressure;\n\
+             \n\
+             private:\n\
+             \tUPROPERTY(EditAnywhere)\n\
+             \tuint16 Bolts[5];\n\
+             };\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_uclass_with_object_pointers_and_statics() {
+        let car = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                member("owner", "Car", VariableVisibility::PRIVATE, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("wheel", "Wheel", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("count", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = to_unreal(std::slice::from_ref(&car));
+        assert!(out.contains("#include \"UObject/Object.h\"\n"), "Got: {}", out);
+        assert!(out.contains(
+            "UCLASS(BlueprintType)\n\
+             class UCar : public UObject {\n\
+             \tGENERATED_BODY()\n\
+             \n\
+             public:\n\
+             \tUPROPERTY(EditAnywhere, BlueprintReadWrite)\n\
+             \tFWheel Wheel;\n\
+             \tstatic inline int32 Count{};\n\
+             \n\
+             private:\n\
+             \tUPROPERTY(EditAnywhere, BlueprintReadWrite, meta = (AllowPrivateAccess = \"true\"))\n\
+             \tTObjectPtr<UCar> Owner;\n\
+             };\n"
+        ), "Got: {}", out);
+    }
+}
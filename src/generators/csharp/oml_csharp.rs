@@ -0,0 +1,565 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
+use crate::core::utils::{self, to_camel_case, to_pascal_case};
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct CsharpGenerator {
+    /// Emit OML classes as `record` instead of `class`
+    pub use_data_class: bool,
+    /// File-scoped namespace for the generated types, if any
+    pub namespace: Option<String>,
+    /// Emit `[Serializable]` types with plain fields for Unity's serializer
+    pub unity: bool,
+    pub style: CodeStyle,
+}
+
+impl CsharpGenerator {
+    pub fn new(use_data_class: bool) -> Self {
+        Self { use_data_class, ..Default::default() }
+    }
+
+    pub fn with_namespace(mut self, namespace: Option<&str>) -> Self {
+        self.namespace = namespace.map(str::to_string);
+        self
+    }
+
+    pub fn with_unity(mut self, unity: bool) -> Self {
+        self.unity = unity;
+        self
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Unity's compiler predates file-scoped namespaces, `required` and
+    /// records, and its serializer only sees fields, so this flavour writes
+    /// plain `[Serializable]` classes and structs with public fields and
+    /// `[SerializeField]` on the non-public ones.
+    fn generate_unity(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut cs_file = CodeWriter::new(self.style);
+
+        writeln!(cs_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(cs_file)?;
+
+        let has_types = oml_objects.iter().any(|o| o.oml_type != ObjectType::ENUM);
+        let variables = || oml_objects.iter().filter(|o| o.oml_type != ObjectType::ENUM).flat_map(|o| &o.variables);
+        if has_types {
+            writeln!(cs_file, "using System;")?;
+        }
+        if variables().any(|v| v.array_kind == ArrayKind::Dynamic) {
+            writeln!(cs_file, "using System.Collections.Generic;")?;
+        }
+        if variables().any(|v| is_serialized(v) && v.visibility != VariableVisibility::PUBLIC) {
+            writeln!(cs_file, "using UnityEngine;")?;
+        }
+        if has_types {
+            writeln!(cs_file)?;
+        }
+
+        let level = match &self.namespace {
+            Some(namespace) => {
+                cs_file.open_block(0, &format!("namespace {}", namespace))?;
+                1
+            }
+            None => 0,
+        };
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_unity_enum(oml_object, level, &mut cs_file)?,
+                ObjectType::CLASS => generate_unity_type(oml_object, "class", level, &mut cs_file)?,
+                ObjectType::STRUCT => generate_unity_type(oml_object, "struct", level, &mut cs_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(cs_file)?;
+            }
+        }
+
+        if self.namespace.is_some() {
+            writeln!(cs_file, "}}")?;
+        }
+
+        Ok(cs_file.finish())
+    }
+}
+
+/// Words reserved in C#, which fields are renamed away from.
+const KEYWORDS: [&str; 77] = [
+    "abstract", "as", "base", "bool", "break", "byte", "case", "catch", "char", "checked", "class",
+    "const", "continue", "decimal", "default", "delegate", "do", "double", "else", "enum", "event",
+    "explicit", "extern", "false", "finally", "fixed", "float", "for", "foreach", "goto", "if",
+    "implicit", "in", "int", "interface", "internal", "is", "lock", "long", "namespace", "new",
+    "null", "object", "operator", "out", "override", "params", "private", "protected", "public",
+    "readonly", "ref", "return", "sbyte", "sealed", "short", "sizeof", "stackalloc", "static",
+    "string", "struct", "switch", "this", "throw", "true", "try", "typeof", "uint", "ulong",
+    "unchecked", "unsafe", "ushort", "using", "virtual", "void", "volatile", "while",
+];
+
+impl Generate for CsharpGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        if self.unity {
+            return self.generate_unity(oml_objects, file_name);
+        }
+
+        let mut cs_file = CodeWriter::new(self.style);
+
+        writeln!(cs_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(cs_file)?;
+        // `T?` on reference types is only an annotation inside a nullable context
+        writeln!(cs_file, "#nullable enable")?;
+        writeln!(cs_file)?;
+
+        let uses_lists = oml_objects
+            .iter()
+            .flat_map(|o| &o.variables)
+            .any(|v| v.array_kind == ArrayKind::Dynamic);
+        if uses_lists {
+            writeln!(cs_file, "using System.Collections.Generic;")?;
+            writeln!(cs_file)?;
+        }
+
+        if let Some(namespace) = &self.namespace {
+            writeln!(cs_file, "namespace {};", namespace)?;
+            writeln!(cs_file)?;
+        }
+
+        // Enums and record structs declared in this file are value types and
+        // need no initializer to satisfy the nullable analysis
+        let value_types: Vec<&str> = oml_objects
+            .iter()
+            .filter(|o| matches!(o.oml_type, ObjectType::ENUM | ObjectType::STRUCT))
+            .map(|o| o.name.as_str())
+            .collect();
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut cs_file)?,
+                ObjectType::CLASS => {
+                    let keyword = if self.use_data_class { "record" } else { "class" };
+                    generate_type(oml_object, keyword, &value_types, &mut cs_file)?
+                }
+                ObjectType::STRUCT => generate_type(oml_object, "record struct", &value_types, &mut cs_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(cs_file)?;
+            }
+        }
+
+        Ok(cs_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "cs"
+    }
+
+    /// Properties are PascalCase, which no keyword is; Unity fields are
+    /// camelCase.
+    fn keywords(&self) -> &'static [&'static str] {
+        if self.unity { &KEYWORDS } else { &[] }
+    }
+
+    /// C#'s verbatim prefix, which lets a keyword be used as a name.
+    fn escape_keyword(&self, keyword: &str) -> String {
+        format!("@{}", keyword)
+    }
+
+    fn is_identifier(&self, name: &str) -> bool {
+        utils::is_identifier(name.strip_prefix('@').unwrap_or(name))
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Pascal,
+            constants: Case::Pascal,
+            enum_values: Case::Pascal,
+        }
+    }
+}
+
+fn generate_enum(oml_object: &OmlObject, cs_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    cs_file.open_block(0, &format!("public enum {}", oml_object.name))?;
+    for var in &oml_object.variables {
+        writeln!(cs_file, "{}{},", cs_file.indent(1), to_pascal_case(&var.name))?;
+    }
+    writeln!(cs_file, "}}")?;
+
+    Ok(())
+}
+
+fn generate_type(
+    oml_object: &OmlObject,
+    keyword: &str,
+    value_types: &[&str],
+    cs_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    cs_file.open_block(0, &format!("public {} {}", keyword, oml_object.name))?;
+    for var in &oml_object.variables {
+        writeln!(cs_file, "{}{}", cs_file.indent(1), property(var, value_types))?;
+    }
+    writeln!(cs_file, "}}")?;
+
+    Ok(())
+}
+
+/// Renders one member as an auto-property.
+///
+/// Non-optional public instance members are `required`, so object
+/// initializers must set them. Members that cannot be `required` (statics
+/// and non-public members) get a `default!` initializer instead when their
+/// type is a reference type.
+fn property(var: &Variable, value_types: &[&str]) -> String {
+    let is_static = var.var_mod.contains(&VariableModifier::STATIC);
+    let is_const = var.is_const();
+    let is_optional = var.var_mod.contains(&VariableModifier::OPTIONAL);
+    let is_public = var.visibility == VariableVisibility::PUBLIC;
+
+    let mut decl = String::from(convert_visibility(&var.visibility));
+    if is_static {
+        decl.push_str(" static");
+    }
+    let is_required = is_public && !is_static && !is_optional && var.value.is_none();
+    if is_required {
+        decl.push_str(" required");
+    }
+
+    let accessors = match (is_const, is_static) {
+        (true, true) => "{ get; }",
+        (true, false) => "{ get; init; }",
+        (false, _) => "{ get; set; }",
+    };
+
+    let _ = write!(decl, " {} {} {}", cs_type(var), to_pascal_case(&var.name), accessors);
+
+    if let Some(value) = &var.value {
+        let _ = write!(decl, " = {};", cs_literal(&var.var_type, value));
+    } else if !is_required && !is_optional && !is_value_type(var, value_types) {
+        decl.push_str(" = default!;");
+    }
+
+    decl
+}
+
+fn generate_unity_enum(oml_object: &OmlObject, level: usize, cs_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    cs_file.open_block(level, &format!("public enum {}", oml_object.name))?;
+    for var in &oml_object.variables {
+        writeln!(cs_file, "{}{},", cs_file.indent(level + 1), to_pascal_case(&var.name))?;
+    }
+    writeln!(cs_file, "{}}}", cs_file.indent(level))?;
+
+    Ok(())
+}
+
+fn generate_unity_type(
+    oml_object: &OmlObject,
+    keyword: &str,
+    level: usize,
+    cs_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    writeln!(cs_file, "{}[Serializable]", cs_file.indent(level))?;
+    cs_file.open_block(level, &format!("public {} {}", keyword, oml_object.name))?;
+    for var in &oml_object.variables {
+        writeln!(cs_file, "{}{}", cs_file.indent(level + 1), unity_field(var))?;
+    }
+    writeln!(cs_file, "{}}}", cs_file.indent(level))?;
+
+    Ok(())
+}
+
+/// Renders one member as a field named in Unity's camelCase style. The
+/// serializer cannot store null or read-only fields, so optional and const
+/// instance members are plain fields; static consts become `static
+/// readonly`, and statics are never serialized.
+fn unity_field(var: &Variable) -> String {
+    let mut decl = String::new();
+    if is_serialized(var) && var.visibility != VariableVisibility::PUBLIC {
+        decl.push_str("[SerializeField] ");
+    }
+    decl.push_str(convert_visibility(&var.visibility));
+    if var.var_mod.contains(&VariableModifier::STATIC) {
+        decl.push_str(" static");
+        if var.is_const() {
+            decl.push_str(" readonly");
+        }
+    }
+
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::Dynamic => format!("List<{}>", base),
+        ArrayKind::Static(_) => format!("{}[]", base),
+        ArrayKind::None => base,
+    };
+    let _ = write!(decl, " {} {}", ty, to_camel_case(&var.name));
+    if let Some(value) = &var.value {
+        let _ = write!(decl, " = {}", cs_literal(&var.var_type, value));
+    }
+    decl.push(';');
+
+    decl
+}
+
+#[inline]
+fn is_serialized(var: &Variable) -> bool {
+    !var.var_mod.contains(&VariableModifier::STATIC)
+}
+
+/// An OML literal in C#, suffixed to the width of `var_type`.
+fn cs_literal(var_type: &str, value: &str) -> String {
+    match var_type {
+        "uint32" => format!("{}u", value),
+        "int64" => format!("{}L", value),
+        "uint64" => format!("{}UL", value),
+        "float" => format!("{}f", value),
+        _ => value.to_string(),
+    }
+}
+
+#[inline]
+fn convert_visibility(visibility: &VariableVisibility) -> &'static str {
+    match visibility {
+        VariableVisibility::PUBLIC => "public",
+        VariableVisibility::PRIVATE => "private",
+        VariableVisibility::PROTECTED => "protected",
+    }
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" => "sbyte".to_string(),
+        "int16" => "short".to_string(),
+        "int32" => "int".to_string(),
+        "int64" => "long".to_string(),
+        "uint8" => "byte".to_string(),
+        "uint16" => "ushort".to_string(),
+        "uint32" => "uint".to_string(),
+        "uint64" => "ulong".to_string(),
+        "float" => "float".to_string(),
+        "double" => "double".to_string(),
+        "bool" => "bool".to_string(),
+        "string" => "string".to_string(),
+        "char" => "char".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// C# arrays carry no length in their type, so static arrays become `T[]`.
+fn cs_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::Dynamic => format!("List<{}>", base),
+        ArrayKind::Static(_) => format!("{}[]", base),
+        ArrayKind::None => base,
+    };
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        format!("{}?", ty)
+    } else {
+        ty
+    }
+}
+
+fn is_value_type(var: &Variable, value_types: &[&str]) -> bool {
+    if var.array_kind != ArrayKind::None {
+        return false;
+    }
+    match var.var_type.as_str() {
+        "string" => false,
+        "int8" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" | "uint64"
+        | "float" | "double" | "bool" | "char" => true,
+        other => value_types.contains(&other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{member, generated};
+
+    #[test]
+    fn test_class_properties_are_required_or_nullable() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                member("name", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("max_speed", "double", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::None),
+                member("nickname", "string", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("owner", "string", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+                member("doors", "int32", VariableVisibility::PROTECTED, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&CsharpGenerator::default(), &obj);
+        assert!(out.contains("#nullable enable"), "Got: {}", out);
+        assert!(out.contains("public class Car {\n"), "Got: {}", out);
+        assert!(out.contains("\tpublic required string Name { get; set; }\n"), "Got: {}", out);
+        assert!(out.contains("\tpublic required double MaxSpeed { get; init; }\n"), "Got: {}", out);
+        assert!(out.contains("\tpublic string? Nickname { get; set; }\n"), "Got: {}", out);
+        assert!(out.contains("\tprivate string Owner { get; set; } = default!;\n"), "Got: {}", out);
+        assert!(out.contains("\tprotected int Doors { get; set; }\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_and_static_consts() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Counter".to_string(),
+            variables: vec![
+                member("count", "int64", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("label", "string", VariableVisibility::PUBLIC,
+                    vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&CsharpGenerator::default(), &obj);
+        assert!(out.contains("\tpublic static long Count { get; set; }\n"), "Got: {}", out);
+        assert!(out.contains("\tpublic static string Label { get; } = default!;\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_arrays_lists_and_using() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Garage".to_string(),
+            variables: vec![
+                member("slots", "uint8", VariableVisibility::PUBLIC, vec![], ArrayKind::Static(4)),
+                member("cars", "Car", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&CsharpGenerator::default(), &obj);
+        assert!(out.contains("using System.Collections.Generic;"), "Got: {}", out);
+        assert!(out.contains("public required byte[] Slots { get; set; }"), "Got: {}", out);
+        assert!(out.contains("public List<Car>? Cars { get; set; }"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_records_enums_and_value_types() {
+        let color = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                member("RED", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("dark_blue", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let point = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Point".to_string(),
+            variables: vec![
+                member("color", "Color", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let shape = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Shape".to_string(),
+            variables: vec![
+                member("origin", "Point", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = CsharpGenerator::new(true).generate(&[color, point, shape], "test").unwrap();
+        assert!(out.contains("public enum Color {\n\tRed,\n\tDarkBlue,\n}"), "Got: {}", out);
+        assert!(out.contains("public record struct Point {\n\tprivate Color Color { get; set; }\n}"), "Got: {}", out);
+        assert!(out.contains("public record Shape {\n\tprivate Point Origin { get; set; }\n}"), "Got: {}", out);
+        assert!(!out.contains("using"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_namespace_and_brace_style() {
+        use crate::core::code_writer::BraceStyle;
+
+        let obj = OmlObject { oml_type: ObjectType::CLASS, name: "Empty".to_string(), ..Default::default() };
+        let style = CodeStyle { brace_style: BraceStyle::NextLine, ..Default::default() };
+        let out = CsharpGenerator::default()
+            .with_namespace(Some("Acme.Models"))
+            .with_style(style)
+            .generate(std::slice::from_ref(&obj), "test")
+            .unwrap();
+        assert!(out.contains("\nnamespace Acme.Models;\n\npublic class Empty\n{\n}\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_unity_serializable_fields() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Enemy".to_string(),
+            variables: vec![
+                member("MaxHealth", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::None),
+                member("loot", "Item", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::Dynamic),
+                member("spawn_point", "float", VariableVisibility::PRIVATE, vec![], ArrayKind::Static(3)),
+                member("count", "int32", VariableVisibility::PRIVATE, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("tag", "string", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = CsharpGenerator::default().with_unity(true).generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(!out.contains("#nullable"), "Got: {}", out);
+        assert!(out.contains("\nusing System;\nusing System.Collections.Generic;\nusing UnityEngine;\n\n"), "Got: {}", out);
+        assert!(out.contains(
+            "[Serializable]\n\
+             public class Enemy {\n\
+             \tpublic int maxHealth;\n\
+             \tpublic List<Item> loot;\n\
+             \t[SerializeField] private float[] spawnPoint;\n\
+             \tprivate static int count;\n\
+             \tpublic static readonly string tag;\n\
+             }\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_unity_block_namespace_and_struct() {
+        let color = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![member("RED", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None)],
+            ..Default::default()
+        };
+        let point = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Point".to_string(),
+            variables: vec![member("x", "double", VariableVisibility::PUBLIC, vec![], ArrayKind::None)],
+            ..Default::default()
+        };
+        let out = CsharpGenerator::default()
+            .with_unity(true)
+            .with_namespace(Some("Game.Data"))
+            .generate(&[color, point], "test")
+            .unwrap();
+        assert!(out.contains(
+            "namespace Game.Data {\n\
+             \tpublic enum Color {\n\
+             \t\tRed,\n\
+             \t}\n\
+             \n\
+             \t[Serializable]\n\
+             \tpublic struct Point {\n\
+             \t\tpublic double x;\n\
+             \t}\n\
+             }\n"
+        ), "Got: {}", out);
+        assert!(!out.contains("UnityEngine"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static string label = \"$5 #1\";\n public static char mark = '\\'';\n}\n".to_string()
+        ).unwrap();
+        let out = CsharpGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("\tpublic static long MaxSpeed { get; } = 120L;\n"), "Got: {}", out);
+    }
+}
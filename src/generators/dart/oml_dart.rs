@@ -0,0 +1,293 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
+use crate::core::utils::{self, to_camel_case};
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct DartGenerator {
+    pub style: CodeStyle,
+}
+
+impl DartGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Words reserved in Dart, which fields are renamed away from.
+const KEYWORDS: [&str; 33] = [
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
+impl Generate for DartGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut dart_file = CodeWriter::new(self.style);
+
+        writeln!(dart_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(dart_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut dart_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_class(oml_object, &mut dart_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(dart_file)?;
+            }
+        }
+
+        Ok(dart_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "dart"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    /// A trailing `$`: Dart has no way to escape a keyword, and fields are
+    /// camelCased, which would drop a trailing `_`.
+    fn escape_keyword(&self, keyword: &str) -> String {
+        format!("{}$", keyword)
+    }
+
+    fn is_identifier(&self, name: &str) -> bool {
+        utils::is_identifier(name.strip_suffix('$').unwrap_or(name))
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Camel,
+            constants: Case::Camel,
+            enum_values: Case::Camel,
+        }
+    }
+}
+
+fn generate_enum(oml_object: &OmlObject, dart_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    dart_file.open_block(0, &format!("enum {}", oml_object.name))?;
+    for var in &oml_object.variables {
+        writeln!(dart_file, "{}{},", dart_file.indent(1), to_camel_case(&var.name))?;
+    }
+    writeln!(dart_file, "}}")?;
+
+    Ok(())
+}
+
+fn generate_class(oml_object: &OmlObject, dart_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    dart_file.open_block(0, &format!("class {}", oml_object.name))?;
+
+    for var in &oml_object.variables {
+        writeln!(dart_file, "{}{}", dart_file.indent(1), field(var))?;
+    }
+
+    let instance_vars: Vec<&Variable> = oml_object.variables
+        .iter()
+        .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+        .collect();
+
+    if !instance_vars.is_empty() {
+        writeln!(dart_file)?;
+        generate_constructor(&oml_object.name, &instance_vars, dart_file)?;
+    }
+
+    writeln!(dart_file, "}}")?;
+
+    Ok(())
+}
+
+/// Named-parameter constructor: non-optional fields are `required`, optional
+/// ones default to null. Private fields cannot be initializing formals of
+/// named parameters, so they are assigned in the initializer list instead.
+fn generate_constructor(
+    class_name: &str,
+    instance_vars: &[&Variable],
+    dart_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    let mut initializers = Vec::new();
+
+    writeln!(dart_file, "{}{}({{", dart_file.indent(1), class_name)?;
+    for var in instance_vars {
+        let required = if is_optional(var) { "" } else { "required " };
+        let name = to_camel_case(&var.name);
+        if var.visibility == VariableVisibility::PRIVATE {
+            writeln!(dart_file, "{}{}{} {},", dart_file.indent(2), required, dart_type(var), name)?;
+            initializers.push(format!("_{} = {}", name, name));
+        } else {
+            writeln!(dart_file, "{}{}this.{},", dart_file.indent(2), required, name)?;
+        }
+    }
+
+    if initializers.is_empty() {
+        writeln!(dart_file, "{}}});", dart_file.indent(1))?;
+    } else {
+        writeln!(dart_file, "{}}}) : {};", dart_file.indent(1), initializers.join(", "))?;
+    }
+
+    Ok(())
+}
+
+/// Dart has no `protected`; such fields stay public. Non-optional statics
+/// without a value are `late` since Dart requires non-nullable statics to be
+/// initialized.
+fn field(var: &Variable) -> String {
+    let is_static = var.var_mod.contains(&VariableModifier::STATIC);
+    let is_const = var.is_const();
+    let value = var.value.as_ref().filter(|_| is_static);
+
+    let mut decl = String::new();
+    if is_static {
+        decl.push_str("static ");
+        if !is_optional(var) && value.is_none() {
+            decl.push_str("late ");
+        }
+    }
+    if is_const {
+        decl.push_str("final ");
+    }
+
+    let prefix = if var.visibility == VariableVisibility::PRIVATE { "_" } else { "" };
+    let _ = write!(decl, "{} {}{}", dart_type(var), prefix, to_camel_case(&var.name));
+    if let Some(value) = value {
+        // `$` interpolates in a Dart string
+        let _ = write!(decl, " = {}", value.replace('$', "\\$"));
+    }
+    decl.push(';');
+
+    decl
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" | "int64" => "int".to_string(),
+        "uint8" | "uint16" | "uint32" | "uint64" => "int".to_string(),
+        "float" | "double" => "double".to_string(),
+        "bool" => "bool".to_string(),
+        "string" | "char" => "String".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Dart lists carry no length in their type, so both array kinds become `List<T>`.
+fn dart_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::Dynamic | ArrayKind::Static(_) => format!("List<{}>", base),
+        ArrayKind::None => base,
+    };
+    if is_optional(var) {
+        format!("{}?", ty)
+    } else {
+        ty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{member, generated};
+
+    #[test]
+    fn test_class_fields_and_named_constructor() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                member("name", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("max_speed", "double", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::None),
+                member("nickname", "string", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("tags", "string", VariableVisibility::PROTECTED, vec![], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&DartGenerator::default(), &obj);
+        assert!(out.contains(
+            "class Car {\n\tString name;\n\tfinal double maxSpeed;\n\tString? nickname;\n\tList<String> tags;\n\n"
+        ), "Got: {}", out);
+        assert!(out.contains(
+            "\tCar({\n\t\trequired this.name,\n\t\trequired this.maxSpeed,\n\t\tthis.nickname,\n\t\trequired this.tags,\n\t});\n}"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_private_fields_use_initializer_list() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Account".to_string(),
+            variables: vec![
+                member("owner_id", "uint64", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+                member("note", "string", VariableVisibility::PRIVATE, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&DartGenerator::default(), &obj);
+        assert!(out.contains("\tint _ownerId;\n\tString? _note;\n"), "Got: {}", out);
+        assert!(out.contains(
+            "\tAccount({\n\t\trequired int ownerId,\n\t\tString? note,\n\t}) : _ownerId = ownerId, _note = note;\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_are_late_and_skip_constructor() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Registry".to_string(),
+            variables: vec![
+                member("count", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("label", "char", VariableVisibility::PUBLIC,
+                    vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+                member("last", "string", VariableVisibility::PUBLIC,
+                    vec![VariableModifier::STATIC, VariableModifier::OPTIONAL], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&DartGenerator::default(), &obj);
+        assert!(out.contains("\tstatic late int count;\n"), "Got: {}", out);
+        assert!(out.contains("\tstatic late final String label;\n"), "Got: {}", out);
+        assert!(out.contains("\tstatic String? last;\n}"), "Got: {}", out);
+        assert!(!out.contains("Registry("), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_values_are_camel_case() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                member("RED", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("DARK_BLUE", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&DartGenerator::default(), &obj).contains("enum Color {\n\tred,\n\tdarkBlue,\n}"));
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static string label = \"$5 #1\";\n public static char mark = '\\'';\n}\n".to_string()
+        ).unwrap();
+        let out = DartGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("\tstatic final int maxSpeed = 120;\n"), "Got: {}", out);
+        assert!(out.contains("\tstatic String label = \"\\$5 #1\";\n"), "Got: {}", out);
+    }
+}
@@ -0,0 +1,463 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate, GeneratedFile};
+use std::error::Error;
+use std::fmt::Write;
+
+/// Output flavour of the documentation pages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum DocsFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+/// Generator that turns OML objects into API documentation pages: one page
+/// per object listing its fields, modifiers and doc comments, with links to
+/// the pages of referenced types and a dependency diagram.
+pub struct DocsGenerator {
+    pub format: DocsFormat,
+}
+
+impl DocsGenerator {
+    pub fn new(format: DocsFormat) -> Self {
+        Self { format }
+    }
+
+    fn render_page(&self, oml_object: &OmlObject, siblings: &[OmlObject], file_name: &str) -> Result<String, std::fmt::Error> {
+        let mut page = String::new();
+        match self.format {
+            DocsFormat::Markdown => write_markdown_page(oml_object, siblings, file_name, self.extension(), &mut page)?,
+            DocsFormat::Html => write_html_page(oml_object, siblings, file_name, self.extension(), &mut page)?,
+        }
+        Ok(page)
+    }
+}
+
+impl Generate for DocsGenerator {
+    /// Returns the pages of every object in the file concatenated together;
+    /// `generate_files` writes them as separate pages instead.
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut docs = String::new();
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            if oml_object.oml_type == ObjectType::UNDECIDED {
+                return Err("Cannot generate documentation for UNDECIDED object type".into());
+            }
+            docs.push_str(&self.render_page(oml_object, oml_objects, file_name)?);
+            if i < oml_objects.len() - 1 {
+                writeln!(docs)?;
+            }
+        }
+
+        Ok(docs)
+    }
+
+    fn extension(&self) -> &str {
+        match self.format {
+            DocsFormat::Markdown => "md",
+            DocsFormat::Html => "html",
+        }
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Block("<!--", "-->")
+    }
+
+    /// Names are only ever prose in the docs.
+    fn is_identifier(&self, _name: &str) -> bool {
+        true
+    }
+
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let mut files = Vec::new();
+
+        for oml_object in oml_objects {
+            if oml_object.oml_type == ObjectType::UNDECIDED {
+                return Err("Cannot generate documentation for UNDECIDED object type".into());
+            }
+            let page = self.render_page(oml_object, oml_objects, file_name)?;
+            files.push(GeneratedFile::new(format!("{}.{}", oml_object.name, self.extension()), page));
+        }
+
+        Ok(files)
+    }
+}
+
+fn kind_name(oml_type: &ObjectType) -> &'static str {
+    match oml_type {
+        ObjectType::ENUM => "enum",
+        ObjectType::CLASS => "class",
+        ObjectType::STRUCT => "struct",
+        ObjectType::UNDECIDED => "undecided",
+    }
+}
+
+fn visibility_name(visibility: &VariableVisibility) -> &'static str {
+    match visibility {
+        VariableVisibility::PUBLIC => "public",
+        VariableVisibility::PROTECTED => "protected",
+        VariableVisibility::PRIVATE => "private",
+    }
+}
+
+fn modifier_names(var: &Variable) -> Vec<&'static str> {
+    var.var_mod.iter().map(VariableModifier::keyword).collect()
+}
+
+/// Wraps the (already rendered) base type in the OML array notation.
+fn with_array_kind(base: &str, array_kind: &ArrayKind) -> String {
+    match array_kind {
+        ArrayKind::None => base.to_string(),
+        ArrayKind::Static(n) => format!("{}[{}]", base, n),
+        ArrayKind::Dynamic => format!("list {}", base),
+    }
+}
+
+/// Types defined in OML; external types have no page to link to.
+fn is_custom_type(var: &Variable) -> bool {
+    !var.var_type.is_empty() && !var.external && !OmlObject::is_builtin_type(&var.var_type)
+}
+
+/// A variant with the number it is given in OML, if any.
+fn variant_label(var: &Variable) -> String {
+    match &var.value {
+        Some(value) => format!("{} = {}", var.name, value),
+        None => var.name.clone(),
+    }
+}
+
+/// Edges `(from, field, to)` of the dependency diagram for `oml_object`:
+/// the custom types it references, plus the objects of the same file that
+/// reference it.
+fn dependency_edges(oml_object: &OmlObject, siblings: &[OmlObject]) -> Vec<(String, String, String)> {
+    let mut edges = Vec::new();
+
+    if oml_object.oml_type != ObjectType::ENUM {
+        for var in &oml_object.variables {
+            if is_custom_type(var) {
+                edges.push((oml_object.name.clone(), var.name.clone(), var.var_type.clone()));
+            }
+        }
+    }
+
+    for other in siblings {
+        if other.name == oml_object.name || other.oml_type == ObjectType::ENUM {
+            continue;
+        }
+        for var in &other.variables {
+            if var.var_type == oml_object.name {
+                edges.push((other.name.clone(), var.name.clone(), oml_object.name.clone()));
+            }
+        }
+    }
+
+    edges
+}
+
+fn write_mermaid_graph(edges: &[(String, String, String)], out: &mut String) -> Result<(), std::fmt::Error> {
+    writeln!(out, "graph LR")?;
+    for (from, field, to) in edges {
+        writeln!(out, "    {} -->|{}| {}", from, field, to)?;
+    }
+    Ok(())
+}
+
+// ── Markdown ─────────────────────────────────────────────────────────────────
+
+fn markdown_type(var: &Variable, extension: &str) -> String {
+    let base = if is_custom_type(var) {
+        format!("[`{0}`]({0}.{1})", var.var_type, extension)
+    } else {
+        format!("`{}`", var.var_type)
+    };
+    with_array_kind(&base, &var.array_kind)
+}
+
+/// Doc comments may span several lines; table cells may not.
+fn markdown_cell(doc: &Option<String>) -> String {
+    match doc {
+        Some(text) => text.replace('\n', " ").replace('|', "\\|"),
+        None => String::new(),
+    }
+}
+
+fn write_markdown_page(
+    oml_object: &OmlObject,
+    siblings: &[OmlObject],
+    file_name: &str,
+    extension: &str,
+    out: &mut String,
+) -> Result<(), std::fmt::Error> {
+    writeln!(out, "# {}", oml_object.name)?;
+    writeln!(out)?;
+    writeln!(out, "_{}_ defined in `{}.oml`", kind_name(&oml_object.oml_type), file_name)?;
+    writeln!(out)?;
+
+    if let Some(doc) = &oml_object.doc {
+        writeln!(out, "{}", doc)?;
+        writeln!(out)?;
+    }
+
+    if oml_object.oml_type == ObjectType::ENUM {
+        writeln!(out, "## Variants")?;
+        writeln!(out)?;
+        if oml_object.variables.is_empty() {
+            writeln!(out, "_This enum has no variants._")?;
+        }
+        for var in &oml_object.variables {
+            match &var.doc {
+                Some(doc) => writeln!(out, "- `{}` — {}", variant_label(var), doc.replace('\n', " "))?,
+                None => writeln!(out, "- `{}`", variant_label(var))?,
+            }
+        }
+    } else {
+        writeln!(out, "## Fields")?;
+        writeln!(out)?;
+        if oml_object.variables.is_empty() {
+            writeln!(out, "_This {} has no fields._", kind_name(&oml_object.oml_type))?;
+        } else {
+            writeln!(out, "| Name | Type | Visibility | Modifiers | Value | Description |")?;
+            writeln!(out, "|------|------|------------|-----------|-------|-------------|")?;
+            for var in &oml_object.variables {
+                writeln!(
+                    out,
+                    "| `{}` | {} | {} | {} | {} | {} |",
+                    var.name,
+                    markdown_type(var, extension),
+                    visibility_name(&var.visibility),
+                    modifier_names(var).join(", "),
+                    var.value.as_deref().map(|v| format!("`{}`", v.replace('|', "\\|"))).unwrap_or_default(),
+                    markdown_cell(&var.doc),
+                )?;
+            }
+        }
+    }
+
+    let edges = dependency_edges(oml_object, siblings);
+    if !edges.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "## Dependencies")?;
+        writeln!(out)?;
+        writeln!(out, "```mermaid")?;
+        write_mermaid_graph(&edges, out)?;
+        writeln!(out, "```")?;
+    }
+
+    Ok(())
+}
+
+// ── HTML ─────────────────────────────────────────────────────────────────────
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_type(var: &Variable, extension: &str) -> String {
+    let base = if is_custom_type(var) {
+        format!("<a href=\"{0}.{1}\"><code>{0}</code></a>", escape_html(&var.var_type), extension)
+    } else {
+        format!("<code>{}</code>", escape_html(&var.var_type))
+    };
+    with_array_kind(&base, &var.array_kind)
+}
+
+fn write_html_page(
+    oml_object: &OmlObject,
+    siblings: &[OmlObject],
+    file_name: &str,
+    extension: &str,
+    out: &mut String,
+) -> Result<(), std::fmt::Error> {
+    let name = escape_html(&oml_object.name);
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html lang=\"en\">")?;
+    writeln!(out, "<head>")?;
+    writeln!(out, "<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>{}</title>", name)?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<h1>{}</h1>", name)?;
+    writeln!(
+        out,
+        "<p><em>{}</em> defined in <code>{}.oml</code></p>",
+        kind_name(&oml_object.oml_type),
+        escape_html(file_name)
+    )?;
+
+    if let Some(doc) = &oml_object.doc {
+        writeln!(out, "<p>{}</p>", escape_html(doc).replace('\n', "<br>"))?;
+    }
+
+    if oml_object.oml_type == ObjectType::ENUM {
+        writeln!(out, "<h2>Variants</h2>")?;
+        if oml_object.variables.is_empty() {
+            writeln!(out, "<p><em>This enum has no variants.</em></p>")?;
+        } else {
+            writeln!(out, "<ul>")?;
+            for var in &oml_object.variables {
+                let label = escape_html(&variant_label(var));
+                match &var.doc {
+                    Some(doc) => writeln!(out, "<li><code>{}</code> — {}</li>", label, escape_html(doc))?,
+                    None => writeln!(out, "<li><code>{}</code></li>", label)?,
+                }
+            }
+            writeln!(out, "</ul>")?;
+        }
+    } else {
+        writeln!(out, "<h2>Fields</h2>")?;
+        if oml_object.variables.is_empty() {
+            writeln!(out, "<p><em>This {} has no fields.</em></p>", kind_name(&oml_object.oml_type))?;
+        } else {
+            writeln!(out, "<table>")?;
+            writeln!(out, "<tr><th>Name</th><th>Type</th><th>Visibility</th><th>Modifiers</th><th>Value</th><th>Description</th></tr>")?;
+            for var in &oml_object.variables {
+                writeln!(
+                    out,
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&var.name),
+                    html_type(var, extension),
+                    visibility_name(&var.visibility),
+                    modifier_names(var).join(", "),
+                    var.value.as_deref().map(|v| format!("<code>{}</code>", escape_html(v))).unwrap_or_default(),
+                    var.doc.as_deref().map(escape_html).unwrap_or_default(),
+                )?;
+            }
+            writeln!(out, "</table>")?;
+        }
+    }
+
+    let edges = dependency_edges(oml_object, siblings);
+    if !edges.is_empty() {
+        writeln!(out, "<h2>Dependencies</h2>")?;
+        writeln!(out, "<pre class=\"mermaid\">")?;
+        write_mermaid_graph(&edges, out)?;
+        writeln!(out, "</pre>")?;
+        writeln!(out, "<script type=\"module\">")?;
+        writeln!(out, "import mermaid from \"https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs\";")?;
+        writeln!(out, "mermaid.initialize({{ startOnLoad: true }});")?;
+        writeln!(out, "</script>")?;
+    }
+
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn car_and_engine() -> Vec<OmlObject> {
+        let content = r#"
+            /// A drivable vehicle.
+            class Car {
+                /// Display name
+                public string name;
+                public optional Engine engine;
+                list string tags;
+            }
+
+            struct Engine {
+                public int32 horsepower;
+            }
+        "#;
+        OmlObject::scan_file(content.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_one_markdown_page_per_object() {
+        let objects = car_and_engine();
+        let files = DocsGenerator::new(DocsFormat::Markdown).generate_files(&objects, "car").unwrap();
+
+        let paths: Vec<String> = files.iter().map(|f| f.path.display().to_string()).collect();
+        assert_eq!(paths, vec!["Car.md", "Engine.md"]);
+    }
+
+    #[test]
+    fn test_markdown_page_contents() {
+        let objects = car_and_engine();
+        let files = DocsGenerator::new(DocsFormat::Markdown).generate_files(&objects, "car").unwrap();
+        let car = &files[0].content;
+
+        assert!(car.contains("# Car"), "Got: {}", car);
+        assert!(car.contains("_class_ defined in `car.oml`"), "Got: {}", car);
+        assert!(car.contains("A drivable vehicle."), "Got: {}", car);
+        assert!(car.contains("| `name` | `string` | public |  |  | Display name |"), "Got: {}", car);
+        assert!(car.contains("| `engine` | [`Engine`](Engine.md) | public | optional |  |  |"), "Got: {}", car);
+        assert!(car.contains("| `tags` | list `string` | private |"), "Got: {}", car);
+        assert!(car.contains("Car -->|engine| Engine"), "Got: {}", car);
+    }
+
+    #[test]
+    fn test_referenced_object_shows_incoming_edges() {
+        let objects = car_and_engine();
+        let files = DocsGenerator::new(DocsFormat::Markdown).generate_files(&objects, "car").unwrap();
+        let engine = &files[1].content;
+
+        assert!(engine.contains("_struct_ defined in `car.oml`"), "Got: {}", engine);
+        assert!(engine.contains("Car -->|engine| Engine"), "Got: {}", engine);
+    }
+
+    #[test]
+    fn test_html_pages_escape_and_link() {
+        let objects = car_and_engine();
+        let generator = DocsGenerator::new(DocsFormat::Html);
+        let files = generator.generate_files(&objects, "car").unwrap();
+
+        assert_eq!(generator.extension(), "html");
+        assert_eq!(files[0].path.display().to_string(), "Car.html");
+        let car = &files[0].content;
+        assert!(car.contains("<h1>Car</h1>"), "Got: {}", car);
+        assert!(car.contains("<a href=\"Engine.html\"><code>Engine</code></a>"), "Got: {}", car);
+        assert!(car.contains("<pre class=\"mermaid\">"), "Got: {}", car);
+    }
+
+    #[test]
+    fn test_enum_variants_listed() {
+        let content = r#"
+            enum Color {
+                /// The colour of blood
+                string Red;
+                string Green;
+            }
+        "#;
+        let objects = OmlObject::scan_file(content.to_string()).unwrap();
+        let out = DocsGenerator::new(DocsFormat::Markdown).generate(&objects, "color").unwrap();
+
+        assert!(out.contains("## Variants"), "Got: {}", out);
+        assert!(out.contains("- `Red` — The colour of blood"), "Got: {}", out);
+        assert!(out.contains("- `Green`"), "Got: {}", out);
+        assert!(!out.contains("## Dependencies"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_variant_numbers_shown() {
+        let content = "enum Status {\n string Ok = 200;\n /// Not there\n string Missing = 404;\n}\n";
+        let objects = OmlObject::scan_file(content.to_string()).unwrap();
+
+        let out = DocsGenerator::new(DocsFormat::Markdown).generate(&objects, "status").unwrap();
+        assert!(out.contains("- `Ok = 200`\n- `Missing = 404` — Not there\n"), "Got: {}", out);
+
+        let out = DocsGenerator::new(DocsFormat::Html).generate(&objects, "status").unwrap();
+        assert!(out.contains("<li><code>Ok = 200</code></li>"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_field_values_shown() {
+        let content = "class Car {\n public static const int32 MAX_SPEED = 120;\n public string name;\n}\n";
+        let objects = OmlObject::scan_file(content.to_string()).unwrap();
+
+        let out = DocsGenerator::new(DocsFormat::Markdown).generate(&objects, "car").unwrap();
+        assert!(out.contains("| `MAX_SPEED` | `int32` | public | static, const | `120` |  |\n| `name` | `string` | public |  |  |  |\n"), "Got: {}", out);
+
+        let out = DocsGenerator::new(DocsFormat::Html).generate(&objects, "car").unwrap();
+        assert!(out.contains("<td>static, const</td><td><code>120</code></td>"), "Got: {}", out);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_snake_case;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct ElixirGenerator {
+    pub style: CodeStyle,
+}
+
+impl ElixirGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Words reserved in Elixir, which fields are renamed away from.
+const KEYWORDS: [&str; 15] = [
+    "after", "and", "catch", "do", "else", "end", "false", "fn", "in", "nil", "not", "or", "rescue",
+    "true", "when",
+];
+
+impl Generate for ElixirGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut ex_file = CodeWriter::new(self.style);
+
+        writeln!(ex_file, "# This file has been generated from {}.oml", file_name)?;
+        writeln!(ex_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut ex_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_struct(oml_object, &mut ex_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(ex_file)?;
+            }
+        }
+
+        Ok(ex_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "ex"
+    }
+
+    fn capitalised_types(&self) -> bool {
+        true
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("#")
+    }
+}
+
+/// A module with one function per variant returning its atom, a union
+/// type of the atoms and a `values/0` listing them.
+fn generate_enum(oml_object: &OmlObject, ex_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let atoms: Vec<String> = oml_object.variables.iter().map(|v| to_snake_case(&v.name)).collect();
+
+    writeln!(ex_file, "defmodule {} do", oml_object.name)?;
+    if atoms.is_empty() {
+        writeln!(ex_file, "{}@type t :: none()", ex_file.indent(1))?;
+    } else {
+        let union: Vec<String> = atoms.iter().map(|a| format!(":{}", a)).collect();
+        writeln!(ex_file, "{}@type t :: {}", ex_file.indent(1), union.join(" | "))?;
+        writeln!(ex_file)?;
+        for atom in &atoms {
+            writeln!(ex_file, "{}def {}, do: :{}", ex_file.indent(1), atom, atom)?;
+        }
+    }
+    writeln!(ex_file)?;
+    writeln!(ex_file, "{}@spec values() :: [t()]", ex_file.indent(1))?;
+    let list: Vec<String> = atoms.iter().map(|a| format!(":{}", a)).collect();
+    writeln!(ex_file, "{}def values, do: [{}]", ex_file.indent(1), list.join(", "))?;
+    writeln!(ex_file, "end")?;
+
+    Ok(())
+}
+
+/// `defstruct` with `@enforce_keys` for the required fields and a `@type t`
+/// in which optional fields admit `nil`. Statics have no place in a struct
+/// and are noted in a comment.
+fn generate_struct(oml_object: &OmlObject, ex_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    writeln!(ex_file, "defmodule {} do", oml_object.name)?;
+
+    for var in &static_vars {
+        writeln!(ex_file, "{}# static {} is not representable in a struct", ex_file.indent(1), var.name)?;
+    }
+
+    let required: Vec<String> = fields
+        .iter()
+        .filter(|v| !is_optional(v))
+        .map(|v| format!(":{}", to_snake_case(&v.name)))
+        .collect();
+    if !required.is_empty() {
+        writeln!(ex_file, "{}@enforce_keys [{}]", ex_file.indent(1), required.join(", "))?;
+    }
+    let keys: Vec<String> = fields.iter().map(|v| format!(":{}", to_snake_case(&v.name))).collect();
+    writeln!(ex_file, "{}defstruct [{}]", ex_file.indent(1), keys.join(", "))?;
+    writeln!(ex_file)?;
+
+    if fields.is_empty() {
+        writeln!(ex_file, "{}@type t :: %__MODULE__{{}}", ex_file.indent(1))?;
+    } else {
+        writeln!(ex_file, "{}@type t :: %__MODULE__{{", ex_file.indent(1))?;
+        let length = fields.len();
+        for (i, var) in fields.iter().enumerate() {
+            let separator = if i < length - 1 { "," } else { "" };
+            writeln!(
+                ex_file,
+                "{}{}: {}{}",
+                ex_file.indent(2),
+                to_snake_case(&var.name),
+                elixir_type(var),
+                separator,
+            )?;
+        }
+        writeln!(ex_file, "{}}}", ex_file.indent(1))?;
+    }
+
+    writeln!(ex_file, "end")?;
+
+    Ok(())
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" | "int64" => "integer()".to_string(),
+        "uint8" | "uint16" | "uint32" | "uint64" => "non_neg_integer()".to_string(),
+        "float" | "double" => "float()".to_string(),
+        "bool" => "boolean()".to_string(),
+        "string" => "String.t()".to_string(),
+        "char" => "char()".to_string(),
+        other => format!("{}.t()", other),
+    }
+}
+
+fn elixir_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        _ => format!("[{}]", base),
+    };
+    if is_optional(var) {
+        format!("{} | nil", ty)
+    } else {
+        ty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{var, generated};
+
+    #[test]
+    fn test_struct_with_enforced_keys_and_typespec() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("name", "string", vec![], ArrayKind::None),
+                var("maxSpeed", "double", vec![], ArrayKind::None),
+                var("engine", "Engine", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                var("tags", "string", vec![], ArrayKind::Dynamic),
+                var("doors", "uint8", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&ElixirGenerator::default(), &obj).contains(
+            "defmodule Car do\n\
+             \t@enforce_keys [:name, :max_speed, :tags]\n\
+             \tdefstruct [:name, :max_speed, :engine, :tags, :doors]\n\
+             \n\
+             \t@type t :: %__MODULE__{\n\
+             \t\tname: String.t(),\n\
+             \t\tmax_speed: float(),\n\
+             \t\tengine: Engine.t() | nil,\n\
+             \t\ttags: [String.t()],\n\
+             \t\tdoors: non_neg_integer() | nil\n\
+             \t}\n\
+             end\n"
+        ));
+    }
+
+    #[test]
+    fn test_statics_are_noted() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Registry".to_string(),
+            variables: vec![var("count", "int32", vec![VariableModifier::STATIC], ArrayKind::None)],
+            ..Default::default()
+        };
+        assert!(generated(&ElixirGenerator::default(), &obj).contains(
+            "defmodule Registry do\n\
+             \t# static count is not representable in a struct\n\
+             \tdefstruct []\n\
+             \n\
+             \t@type t :: %__MODULE__{}\n\
+             end\n"
+        ));
+    }
+
+    #[test]
+    fn test_enum_module_with_atoms() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                var("RED", "string", vec![], ArrayKind::None),
+                var("DARK_BLUE", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&ElixirGenerator::default(), &obj).contains(
+            "defmodule Color do\n\
+             \t@type t :: :red | :dark_blue\n\
+             \n\
+             \tdef red, do: :red\n\
+             \tdef dark_blue, do: :dark_blue\n\
+             \n\
+             \t@spec values() :: [t()]\n\
+             \tdef values, do: [:red, :dark_blue]\n\
+             end\n"
+        ));
+    }
+}
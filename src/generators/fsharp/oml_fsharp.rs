@@ -0,0 +1,304 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter, IndentKind};
+use crate::core::utils::{self, to_camel_case, to_pascal_case};
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct FsharpGenerator {
+    /// Namespace for the generated types; without one each file becomes a
+    /// top-level module named after the .oml file
+    pub namespace: Option<String>,
+    pub style: CodeStyle,
+}
+
+impl FsharpGenerator {
+    pub fn with_namespace(mut self, namespace: Option<&str>) -> Self {
+        self.namespace = namespace.map(str::to_string);
+        self
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Words reserved in F#, which fields are renamed away from.
+const KEYWORDS: [&str; 66] = [
+    "abstract", "and", "as", "assert", "base", "begin", "class", "default", "delegate", "do",
+    "done", "downcast", "downto", "elif", "else", "end", "exception", "extern", "false", "finally",
+    "fixed", "for", "fun", "function", "global", "if", "in", "inherit", "inline", "interface",
+    "internal", "lazy", "let", "match", "member", "module", "mutable", "namespace", "new", "not",
+    "null", "of", "open", "or", "override", "private", "public", "rec", "return", "select", "sig",
+    "static", "struct", "then", "to", "true", "try", "type", "upcast", "use", "val", "void", "when",
+    "while", "with", "yield",
+];
+
+impl Generate for FsharpGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        // the F# compiler rejects tab characters, so tabs fall back to spaces
+        let style = CodeStyle { indent_kind: IndentKind::Spaces, ..self.style };
+        let mut fs_file = CodeWriter::new(style);
+
+        writeln!(fs_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(fs_file)?;
+        match &self.namespace {
+            Some(namespace) => writeln!(fs_file, "namespace {}", namespace)?,
+            None => writeln!(fs_file, "module {}", to_pascal_case(file_name))?,
+        }
+        writeln!(fs_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_union(oml_object, &mut fs_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_record(oml_object, &mut fs_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(fs_file)?;
+            }
+        }
+
+        Ok(fs_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "fs"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    /// Double backticks, which let F# use a keyword as a name.
+    fn escape_keyword(&self, keyword: &str) -> String {
+        format!("``{}``", keyword)
+    }
+
+    fn is_identifier(&self, name: &str) -> bool {
+        let unescaped = name.strip_prefix("``").and_then(|name| name.strip_suffix("``"));
+        utils::is_identifier(unescaped.unwrap_or(name))
+    }
+}
+
+/// A discriminated union with one case per variant.
+fn generate_union(oml_object: &OmlObject, fs_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(fs_file, "type {} =", oml_object.name)?;
+    for var in &oml_object.variables {
+        writeln!(fs_file, "{}| {}", fs_file.indent(1), to_pascal_case(&var.name))?;
+    }
+
+    Ok(())
+}
+
+/// A record, marked `[<Struct>]` for OML structs. Record fields cannot be
+/// private, so visibility is not carried over. Statics go into a module of
+/// the same name, which F# compiles as `<Name>Module`.
+fn generate_record(oml_object: &OmlObject, fs_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    if oml_object.oml_type == ObjectType::STRUCT {
+        writeln!(fs_file, "[<Struct>]")?;
+    }
+
+    if fields.is_empty() {
+        // records need at least one field
+        let kind = if oml_object.oml_type == ObjectType::STRUCT { "struct" } else { "class" };
+        writeln!(fs_file, "type {} = {} end", name, kind)?;
+    } else {
+        writeln!(fs_file, "type {} =", name)?;
+        writeln!(fs_file, "{}{{", fs_file.indent(1))?;
+        for var in &fields {
+            let mutable = if is_const(var) { "" } else { "mutable " };
+            writeln!(fs_file, "{}{}{}: {}", fs_file.indent(2), mutable, to_pascal_case(&var.name), fsharp_type(var))?;
+        }
+        writeln!(fs_file, "{}}}", fs_file.indent(1))?;
+    }
+
+    if !static_vars.is_empty() {
+        writeln!(fs_file)?;
+        writeln!(fs_file, "module {} =", name)?;
+        for var in &static_vars {
+            let mutable = if is_const(var) { "" } else { "mutable " };
+            writeln!(
+                fs_file,
+                "{}let {}{}: {} = {}",
+                fs_file.indent(1),
+                mutable,
+                to_camel_case(&var.name),
+                fsharp_type(var),
+                default_value(var),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn is_const(var: &Variable) -> bool {
+    var.is_const()
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" => "sbyte".to_string(),
+        "int16" => "int16".to_string(),
+        "int32" => "int".to_string(),
+        "int64" => "int64".to_string(),
+        "uint8" => "byte".to_string(),
+        "uint16" => "uint16".to_string(),
+        "uint32" => "uint32".to_string(),
+        "uint64" => "uint64".to_string(),
+        "float" => "float32".to_string(),
+        "double" => "float".to_string(),
+        "bool" => "bool".to_string(),
+        "string" => "string".to_string(),
+        "char" => "char".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Static arrays become arrays and dynamic ones immutable lists.
+fn fsharp_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        ArrayKind::Static(_) => format!("{}[]", base),
+        ArrayKind::Dynamic => format!("{} list", base),
+    };
+    if is_optional(var) {
+        format!("{} option", ty)
+    } else {
+        ty
+    }
+}
+
+fn default_value(var: &Variable) -> String {
+    if let Some(value) = &var.value {
+        let literal = fsharp_literal(&var.var_type, value);
+        return if is_optional(var) { format!("Some {}", literal) } else { literal };
+    }
+    if is_optional(var) {
+        return "None".to_string();
+    }
+    match &var.array_kind {
+        ArrayKind::Static(n) => format!("Array.zeroCreate {}", n),
+        ArrayKind::Dynamic => "[]".to_string(),
+        ArrayKind::None => match var.var_type.as_str() {
+            "string" => "\"\"".to_string(),
+            "bool" => "false".to_string(),
+            _ => "Unchecked.defaultof<_>".to_string(),
+        },
+    }
+}
+
+/// An OML literal in F#, suffixed to the width of `var_type`.
+fn fsharp_literal(var_type: &str, value: &str) -> String {
+    let decimal = if value.contains('.') { value.to_string() } else { format!("{}.0", value) };
+    match var_type {
+        "int8" => format!("{}y", value),
+        "uint8" => format!("{}uy", value),
+        "int16" => format!("{}s", value),
+        "uint16" => format!("{}us", value),
+        "uint32" => format!("{}u", value),
+        "int64" => format!("{}L", value),
+        "uint64" => format!("{}UL", value),
+        "float" => format!("{}f", decimal),
+        "double" => decimal,
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{var, generated};
+
+    #[test]
+    fn test_module_or_namespace_header() {
+        let obj = OmlObject { oml_type: ObjectType::CLASS, name: "Empty".to_string(), ..Default::default() };
+        let out = FsharpGenerator::default().generate(std::slice::from_ref(&obj), "game_entity").unwrap();
+        assert!(out.contains("\nmodule GameEntity\n\ntype Empty = class end\n"), "Got: {}", out);
+
+        let out = FsharpGenerator::default()
+            .with_namespace(Some("Acme.Models"))
+            .generate(std::slice::from_ref(&obj), "game_entity")
+            .unwrap();
+        assert!(out.contains("\nnamespace Acme.Models\n\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_record_with_options_and_collections() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("name", "string", vec![VariableModifier::CONST], ArrayKind::None),
+                var("max_speed", "double", vec![], ArrayKind::None),
+                var("nickname", "string", vec![VariableModifier::CONST, VariableModifier::OPTIONAL], ArrayKind::None),
+                var("wheels", "Wheel", vec![VariableModifier::CONST], ArrayKind::Static(4)),
+                var("tags", "string", vec![VariableModifier::CONST, VariableModifier::OPTIONAL], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&FsharpGenerator::default(), &obj).contains(
+            "type Car =\n    {\n        Name: string\n        mutable MaxSpeed: float\n        Nickname: string option\n        Wheels: Wheel[]\n        Tags: string list option\n    }\n"
+        ));
+    }
+
+    #[test]
+    fn test_struct_record_and_static_module() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Counter".to_string(),
+            variables: vec![
+                var("value", "uint8", vec![VariableModifier::CONST], ArrayKind::None),
+                var("total", "int64", vec![VariableModifier::STATIC], ArrayKind::None),
+                var("unit_name", "string", vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&FsharpGenerator::default(), &obj);
+        assert!(out.contains("[<Struct>]\ntype Counter =\n    {\n        Value: byte\n    }\n"), "Got: {}", out);
+        assert!(out.contains(
+            "module Counter =\n    let mutable total: int64 = Unchecked.defaultof<_>\n    let unitName: string = \"\"\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_union() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                var("RED", "string", vec![], ArrayKind::None),
+                var("dark_blue", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&FsharpGenerator::default(), &obj).contains("type Color =\n    | Red\n    | DarkBlue\n"));
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static const float ratio = 2;\n}\n".to_string()
+        ).unwrap();
+        let out = FsharpGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("    let maxSpeed: int64 = 120L\n    let ratio: float32 = 2.0f\n"), "Got: {}", out);
+    }
+}
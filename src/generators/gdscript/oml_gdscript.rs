@@ -0,0 +1,325 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate, GeneratedFile};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_snake_case;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct GdscriptGenerator {
+    pub style: CodeStyle,
+}
+
+impl GdscriptGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// One script per object, since Godot allows a single `class_name` per
+    /// file. Enums declared in `siblings` are referred to as `Name.Value`.
+    fn render_script(&self, oml_object: &OmlObject, siblings: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut gd_file = CodeWriter::new(self.style);
+
+        let enums: HashSet<&str> = siblings
+            .iter()
+            .filter(|o| o.oml_type == ObjectType::ENUM)
+            .map(|o| o.name.as_str())
+            .collect();
+
+        writeln!(gd_file, "# This file has been generated from {}.oml", file_name)?;
+        match &oml_object.oml_type {
+            ObjectType::ENUM => generate_enum(oml_object, &mut gd_file)?,
+            ObjectType::CLASS | ObjectType::STRUCT => generate_class(oml_object, &enums, &mut gd_file)?,
+            ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+        }
+
+        Ok(gd_file.finish())
+    }
+}
+
+/// Words reserved in GDScript, which fields are renamed away from.
+const KEYWORDS: [&str; 37] = [
+    "and", "as", "assert", "await", "break", "breakpoint", "class", "class_name", "const",
+    "continue", "elif", "else", "enum", "extends", "false", "for", "func", "if", "in", "is",
+    "match", "not", "null", "or", "pass", "preload", "return", "self", "signal", "static", "super",
+    "true", "var", "void", "when", "while", "yield",
+];
+
+impl Generate for GdscriptGenerator {
+    /// Returns the scripts of every object in the file concatenated together;
+    /// `generate_files` writes them as separate scripts instead.
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut scripts = String::new();
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            scripts.push_str(&self.render_script(oml_object, oml_objects, file_name)?);
+            if i < oml_objects.len() - 1 {
+                writeln!(scripts)?;
+            }
+        }
+
+        Ok(scripts)
+    }
+
+    fn extension(&self) -> &str {
+        "gd"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("#")
+    }
+
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let mut files = Vec::new();
+
+        for oml_object in oml_objects {
+            let script = self.render_script(oml_object, oml_objects, file_name)?;
+            files.push(GeneratedFile::new(format!("{}.gd", to_snake_case(&oml_object.name)), script));
+        }
+
+        Ok(files)
+    }
+}
+
+/// Godot enums live inside a class, so an OML enum becomes a class holding
+/// a single `Value` enum.
+fn generate_enum(oml_object: &OmlObject, gd_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(gd_file, "class_name {}", oml_object.name)?;
+    writeln!(gd_file, "extends RefCounted")?;
+    writeln!(gd_file)?;
+    if oml_object.variables.is_empty() {
+        writeln!(gd_file, "enum Value {{}}")?;
+        return Ok(());
+    }
+
+    writeln!(gd_file, "enum Value {{")?;
+    for var in &oml_object.variables {
+        writeln!(gd_file, "{}{},", gd_file.indent(1), to_snake_case(&var.name).to_uppercase())?;
+    }
+    writeln!(gd_file, "}}")?;
+
+    Ok(())
+}
+
+/// Classes extend `RefCounted`; structs extend `Resource` with `@export`
+/// fields so they can be edited and saved as data assets. Static consts
+/// become `const`, other statics `static var` (Godot 4.1+). GDScript has no
+/// per-instance constants or access control, so const instance fields are
+/// plain `var`s and non-public names get the conventional `_` prefix.
+fn generate_class(oml_object: &OmlObject, enums: &HashSet<&str>, gd_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let resource = oml_object.oml_type == ObjectType::STRUCT;
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    writeln!(gd_file, "class_name {}", oml_object.name)?;
+    writeln!(gd_file, "extends {}", if resource { "Resource" } else { "RefCounted" })?;
+
+    if !static_vars.is_empty() {
+        writeln!(gd_file)?;
+    }
+    for var in &static_vars {
+        let ty = declared_type(var, enums);
+        let value = default_value(var, enums);
+        if is_const(var) {
+            writeln!(gd_file, "const {}: {} = {}", to_snake_case(&var.name).to_uppercase(), ty, value)?;
+        } else {
+            writeln!(gd_file, "static var {}: {} = {}", field_name(var), ty, value)?;
+        }
+    }
+
+    if !fields.is_empty() {
+        writeln!(gd_file)?;
+    }
+    for var in &fields {
+        let export = if resource && var.visibility == VariableVisibility::PUBLIC { "@export " } else { "" };
+        let ty = declared_type(var, enums);
+        if ty == "Variant" {
+            writeln!(gd_file, "{}var {}: Variant = null", export, field_name(var))?;
+        } else {
+            writeln!(gd_file, "{}var {}: {}", export, field_name(var), ty)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn field_name(var: &Variable) -> String {
+    let name = to_snake_case(&var.name);
+    match var.visibility {
+        VariableVisibility::PUBLIC => name,
+        VariableVisibility::PRIVATE | VariableVisibility::PROTECTED => format!("_{}", name),
+    }
+}
+
+#[inline]
+fn is_const(var: &Variable) -> bool {
+    var.is_const()
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+/// Whether the variable's GDScript type is a value type rather than an
+/// object reference; enum values count as ints.
+#[inline]
+fn is_builtin(var: &Variable, enums: &HashSet<&str>) -> bool {
+    var.array_kind != ArrayKind::None
+        || OmlObject::is_builtin_type(&var.var_type)
+        || enums.contains(var.var_type.as_str())
+}
+
+#[inline]
+fn convert_type(var_type: &str, enums: &HashSet<&str>) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" | "int64" => "int".to_string(),
+        "uint8" | "uint16" | "uint32" | "uint64" => "int".to_string(),
+        "float" | "double" => "float".to_string(),
+        "bool" => "bool".to_string(),
+        "string" | "char" => "String".to_string(),
+        other if enums.contains(other) => format!("{}.Value", other),
+        other => other.to_string(),
+    }
+}
+
+/// Arrays of either kind become typed arrays.
+fn gdscript_type(var: &Variable, enums: &HashSet<&str>) -> String {
+    let base = convert_type(&var.var_type, enums);
+    match &var.array_kind {
+        ArrayKind::None => base,
+        _ => format!("Array[{}]", base),
+    }
+}
+
+/// The declared type, or `Variant` for optional value types since those
+/// cannot hold null.
+fn declared_type(var: &Variable, enums: &HashSet<&str>) -> String {
+    if is_optional(var) && is_builtin(var, enums) {
+        "Variant".to_string()
+    } else {
+        gdscript_type(var, enums)
+    }
+}
+
+fn default_value(var: &Variable, enums: &HashSet<&str>) -> String {
+    if let Some(value) = &var.value {
+        return value.clone();
+    }
+    if is_optional(var) {
+        return "null".to_string();
+    }
+    if var.array_kind != ArrayKind::None {
+        return "[]".to_string();
+    }
+    match var.var_type.as_str() {
+        "int8" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" | "uint64" => "0".to_string(),
+        "float" | "double" => "0.0".to_string(),
+        "bool" => "false".to_string(),
+        "string" | "char" => "\"\"".to_string(),
+        other if enums.contains(other) => "0".to_string(),
+        _ => "null".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::member;
+
+    fn color() -> OmlObject {
+        OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Team".to_string(),
+            variables: vec![
+                member("RED", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("darkBlue", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn player() -> OmlObject {
+        OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Player".to_string(),
+            variables: vec![
+                member("maxHealth", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+                member("count", "int32", VariableVisibility::PRIVATE, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("name", "string", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::None),
+                member("speed", "double", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+                member("team", "Team", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("weapon", "Weapon", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("nickname", "string", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("rival", "Team", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("scores", "uint16", VariableVisibility::PUBLIC, vec![], ArrayKind::Static(3)),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_class_script() {
+        let out = GdscriptGenerator::default().generate(&[player(), color()], "game").unwrap();
+        assert!(out.contains(
+            "class_name Player\n\
+             extends RefCounted\n\
+             \n\
+             const MAX_HEALTH: int = 0\n\
+             static var _count: int = 0\n\
+             \n\
+             var name: String\n\
+             var _speed: float\n\
+             var team: Team.Value\n\
+             var weapon: Weapon\n\
+             var nickname: Variant = null\n\
+             var rival: Variant = null\n\
+             var scores: Array[int]\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_struct_is_an_exported_resource() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "ItemData".to_string(),
+            variables: vec![
+                member("label", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("seed", "uint32", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = GdscriptGenerator::default().generate(std::slice::from_ref(&obj), "items").unwrap();
+        assert!(out.contains("class_name ItemData\nextends Resource\n\n@export var label: String\nvar _seed: int\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_script_and_one_file_per_object() {
+        let files = GdscriptGenerator::default().generate_files(&[player(), color()], "game").unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, std::path::PathBuf::from("player.gd"));
+        assert_eq!(files[1].path, std::path::PathBuf::from("team.gd"));
+        assert_eq!(
+            files[1].content,
+            "# This file has been generated from game.oml\nclass_name Team\nextends RefCounted\n\nenum Value {\n\tRED,\n\tDARK_BLUE,\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static const float ratio = 2;\n}\n".to_string()
+        ).unwrap();
+        let out = GdscriptGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("const MAX_SPEED: int = 120\nconst RATIO: float = 2\n"), "Got: {}", out);
+    }
+}
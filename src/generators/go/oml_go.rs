@@ -0,0 +1,325 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
+use crate::core::utils::to_pascal_case;
+use std::error::Error;
+use std::fmt::Write;
+
+pub struct GoGenerator {
+    /// Name used in the `package` clause of every generated file
+    pub package: String,
+    pub style: CodeStyle,
+}
+
+impl GoGenerator {
+    pub fn new(package: &str) -> Self {
+        Self { package: package.to_string(), style: CodeStyle::default() }
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Default for GoGenerator {
+    fn default() -> Self {
+        Self::new("models")
+    }
+}
+
+impl Generate for GoGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut go_file = CodeWriter::new(self.style);
+
+        writeln!(go_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(go_file)?;
+        writeln!(go_file, "package {}", self.package)?;
+        writeln!(go_file)?;
+
+        // String() on enums falls back to fmt.Sprintf for unknown values
+        if oml_objects.iter().any(|o| o.oml_type == ObjectType::ENUM) {
+            writeln!(go_file, "import \"fmt\"")?;
+            writeln!(go_file)?;
+        }
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut go_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_struct(oml_object, &mut go_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(go_file)?;
+            }
+        }
+
+        Ok(go_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "go"
+    }
+
+    /// Fields are exported, so PascalCase, which no keyword is.
+    fn keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Pascal,
+            constants: Case::Pascal,
+            enum_values: Case::Pascal,
+        }
+    }
+}
+
+// Braces are written inline rather than through `open_block`: Go's automatic
+// semicolon insertion makes a brace on the next line a syntax error.
+
+fn generate_enum(oml_object: &OmlObject, go_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let constants: Vec<String> = oml_object.variables
+        .iter()
+        .map(|v| format!("{}{}", name, to_pascal_case(&v.name)))
+        .collect();
+
+    writeln!(go_file, "type {} int", name)?;
+
+    if !constants.is_empty() {
+        writeln!(go_file)?;
+        writeln!(go_file, "const (")?;
+        for (index, constant) in constants.iter().enumerate() {
+            if index == 0 {
+                writeln!(go_file, "{}{} {} = iota", go_file.indent(1), constant, name)?;
+            } else {
+                writeln!(go_file, "{}{}", go_file.indent(1), constant)?;
+            }
+        }
+        writeln!(go_file, ")")?;
+    }
+
+    writeln!(go_file)?;
+    writeln!(go_file, "func (v {}) String() string {{", name)?;
+    writeln!(go_file, "{}switch v {{", go_file.indent(1))?;
+    for (var, constant) in oml_object.variables.iter().zip(&constants) {
+        writeln!(go_file, "{}case {}:", go_file.indent(1), constant)?;
+        writeln!(go_file, "{}return \"{}\"", go_file.indent(2), var.name)?;
+    }
+    writeln!(go_file, "{}default:", go_file.indent(1))?;
+    writeln!(go_file, "{}return fmt.Sprintf(\"{}(%d)\", int(v))", go_file.indent(2), name)?;
+    writeln!(go_file, "{}}}", go_file.indent(1))?;
+    writeln!(go_file, "}}")?;
+
+    Ok(())
+}
+
+fn generate_struct(
+    oml_object: &OmlObject,
+    go_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    // Go has no static members; statics become package-level variables
+    let (static_vars, field_vars): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    if field_vars.is_empty() {
+        writeln!(go_file, "type {} struct{{}}", oml_object.name)?;
+    } else {
+        writeln!(go_file, "type {} struct {{", oml_object.name)?;
+        write_aligned(go_file, field_vars.iter().map(|v| (to_pascal_case(&v.name), go_type(v), None)))?;
+        writeln!(go_file, "}}")?;
+    }
+
+    if !static_vars.is_empty() {
+        writeln!(go_file)?;
+        writeln!(go_file, "var (")?;
+        write_aligned(go_file, static_vars.iter().map(|v| {
+            // a pointer cannot point at a literal
+            let value = v.value.clone().filter(|_| !v.var_mod.contains(&VariableModifier::OPTIONAL));
+            (format!("{}{}", oml_object.name, to_pascal_case(&v.name)), go_type(v), value)
+        }))?;
+        writeln!(go_file, ")")?;
+    }
+
+    Ok(())
+}
+
+/// Writes `name type` pairs, or `name type = value`, one level deep with
+/// the types and values lined up in columns, the way gofmt lays out struct
+/// fields and var blocks.
+fn write_aligned(
+    go_file: &mut CodeWriter,
+    entries: impl Iterator<Item = (String, String, Option<String>)>,
+) -> Result<(), std::fmt::Error> {
+    let entries: Vec<(String, String, Option<String>)> = entries.collect();
+    let width = entries.iter().map(|(name, _, _)| name.len()).max().unwrap_or(0);
+    let type_width = entries.iter().filter(|(_, _, value)| value.is_some()).map(|(_, ty, _)| ty.len()).max().unwrap_or(0);
+
+    for (name, ty, value) in &entries {
+        match value {
+            Some(value) => writeln!(
+                go_file,
+                "{}{:<width$} {:<type_width$} = {}",
+                go_file.indent(1), name, ty, value, width = width, type_width = type_width
+            )?,
+            None => writeln!(go_file, "{}{:<width$} {}", go_file.indent(1), name, ty, width = width)?,
+        }
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" => "int8".to_string(),
+        "int16" => "int16".to_string(),
+        "int32" => "int32".to_string(),
+        "int64" => "int64".to_string(),
+        "uint8" => "uint8".to_string(),
+        "uint16" => "uint16".to_string(),
+        "uint32" => "uint32".to_string(),
+        "uint64" => "uint64".to_string(),
+        "float" => "float32".to_string(),
+        "double" => "float64".to_string(),
+        "bool" => "bool".to_string(),
+        "string" => "string".to_string(),
+        "char" => "rune".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Optional values become pointers. Optional slices stay plain slices,
+/// since a nil slice already means "absent".
+fn go_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let optional = var.var_mod.contains(&VariableModifier::OPTIONAL);
+    match &var.array_kind {
+        ArrayKind::Dynamic => format!("[]{}", base),
+        ArrayKind::Static(n) if optional => format!("*[{}]{}", n, base),
+        ArrayKind::Static(n) => format!("[{}]{}", n, base),
+        ArrayKind::None if optional => format!("*{}", base),
+        ArrayKind::None => base,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{var, generated};
+
+    #[test]
+    fn test_package_clause_is_configurable() {
+        let obj = OmlObject { oml_type: ObjectType::STRUCT, name: "Empty".to_string(), ..Default::default() };
+        let out = GoGenerator::new("vehicles").generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.contains("\npackage vehicles\n"), "Got: {}", out);
+        assert!(out.contains("type Empty struct{}"), "Got: {}", out);
+        assert!(!out.contains("import"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_struct_fields_are_exported_and_aligned() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("name", "string", vec![], ArrayKind::None),
+                var("max_speed", "double", vec![], ArrayKind::None),
+                var("wheels", "Wheel", vec![], ArrayKind::Static(4)),
+                var("tags", "string", vec![], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&GoGenerator::default(), &obj);
+        assert!(out.contains(
+            "type Car struct {\n\tName     string\n\tMaxSpeed float64\n\tWheels   [4]Wheel\n\tTags     []string\n}"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_optional_fields_use_pointers() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "User".to_string(),
+            variables: vec![
+                var("nickname", "string", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                var("aliases", "string", vec![VariableModifier::OPTIONAL], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&GoGenerator::default(), &obj);
+        assert!(out.contains("Nickname *string"), "Got: {}", out);
+        assert!(out.contains("Aliases  []string"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_become_package_variables() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Counter".to_string(),
+            variables: vec![
+                var("count", "int32", vec![VariableModifier::STATIC], ArrayKind::None),
+                var("label", "char", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&GoGenerator::default(), &obj);
+        assert!(out.contains("type Counter struct {\n\tLabel rune\n}"), "Got: {}", out);
+        assert!(out.contains("var (\n\tCounterCount int32\n)"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_package_variables_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n static const int32 maxSpeed = 120;\n static string label = \"car\";\n static int64 count;\n}\n".to_string()
+        ).unwrap();
+        let out = GoGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains(
+            "var (\n\
+             \tCarMaxSpeed int32  = 120\n\
+             \tCarLabel    string = \"car\"\n\
+             \tCarCount    int64\n\
+             )"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_typed_constants_and_stringer() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                var("RED", "string", vec![], ArrayKind::None),
+                var("dark_blue", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&GoGenerator::default(), &obj);
+        assert!(out.contains("import \"fmt\""), "Got: {}", out);
+        assert!(out.contains("type Color int\n\nconst (\n\tColorRed Color = iota\n\tColorDarkBlue\n)"), "Got: {}", out);
+        assert!(out.contains("func (v Color) String() string {"), "Got: {}", out);
+        assert!(out.contains("\tcase ColorDarkBlue:\n\t\treturn \"dark_blue\""), "Got: {}", out);
+        assert!(out.contains("return fmt.Sprintf(\"Color(%d)\", int(v))"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_next_line_brace_style_is_ignored() {
+        use crate::core::code_writer::BraceStyle;
+
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "P".to_string(),
+            variables: vec![var("x", "int32", vec![], ArrayKind::None)],
+            ..Default::default()
+        };
+        let style = CodeStyle { brace_style: BraceStyle::NextLine, ..Default::default() };
+        let out = GoGenerator::default().with_style(style).generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.contains("type P struct {\n"), "Got: {}", out);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::{to_camel_case, to_snake_case};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct GraphqlGenerator {
+    pub style: CodeStyle,
+}
+
+impl GraphqlGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Generate for GraphqlGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut gql_file = CodeWriter::new(self.style);
+
+        writeln!(gql_file, "# This file has been generated from {}.oml", file_name)?;
+        writeln!(gql_file)?;
+
+        // enums are shared between output and input types, objects are not
+        let enums: HashSet<&str> = oml_objects
+            .iter()
+            .filter(|o| o.oml_type == ObjectType::ENUM)
+            .map(|o| o.name.as_str())
+            .collect();
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut gql_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => {
+                    generate_type(oml_object, "type", &oml_object.name, &enums, &mut gql_file)?;
+                    writeln!(gql_file)?;
+                    generate_type(oml_object, "input", &input_name(&oml_object.name), &enums, &mut gql_file)?;
+                }
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(gql_file)?;
+            }
+        }
+
+        Ok(gql_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "graphql"
+    }
+
+    /// GraphQL reserves no field names, only the `__` prefix of introspection.
+    fn keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("#")
+    }
+}
+
+#[inline]
+fn input_name(name: &str) -> String {
+    format!("{}Input", name)
+}
+
+fn generate_enum(oml_object: &OmlObject, gql_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    if oml_object.variables.is_empty() {
+        writeln!(gql_file, "enum {}", oml_object.name)?;
+        return Ok(());
+    }
+
+    writeln!(gql_file, "enum {} {{", oml_object.name)?;
+    for var in &oml_object.variables {
+        writeln!(gql_file, "{}{}", gql_file.indent(1), to_snake_case(&var.name).to_uppercase())?;
+    }
+    writeln!(gql_file, "}}")?;
+
+    Ok(())
+}
+
+/// An output `type` or its `input` counterpart. Input types may only refer
+/// to other inputs, so object-typed fields point at `<Name>Input` there;
+/// types not defined in this file are assumed to be objects. Statics have
+/// no place in a schema and are noted in a comment.
+fn generate_type(
+    oml_object: &OmlObject,
+    keyword: &str,
+    name: &str,
+    enums: &HashSet<&str>,
+    gql_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    // a type without fields is written without braces, as `{}` is invalid
+    if fields.is_empty() {
+        for var in &static_vars {
+            writeln!(gql_file, "# static {} is not representable in a {}", var.name, keyword)?;
+        }
+        writeln!(gql_file, "{} {}", keyword, name)?;
+        return Ok(());
+    }
+
+    writeln!(gql_file, "{} {} {{", keyword, name)?;
+    for var in &static_vars {
+        writeln!(gql_file, "{}# static {} is not representable in a {}", gql_file.indent(1), var.name, keyword)?;
+    }
+    let input = keyword == "input";
+    for var in &fields {
+        writeln!(gql_file, "{}{}: {}", gql_file.indent(1), to_camel_case(&var.name), graphql_type(var, input, enums))?;
+    }
+    writeln!(gql_file, "}}")?;
+
+    Ok(())
+}
+
+/// GraphQL's `Int` is a signed 32-bit integer, so `uint32` widens to
+/// `Float` (exact up to 2^53) and the 64-bit types travel as `String`.
+/// A field named `id` is typed `ID`.
+#[inline]
+fn convert_type(var: &Variable, input: bool, enums: &HashSet<&str>) -> String {
+    if var.name == "id" && var.array_kind == ArrayKind::None {
+        return "ID".to_string();
+    }
+    match var.var_type.as_str() {
+        "int8" | "int16" | "int32" | "uint8" | "uint16" => "Int".to_string(),
+        "uint32" | "float" | "double" => "Float".to_string(),
+        "int64" | "uint64" => "String".to_string(),
+        "bool" => "Boolean".to_string(),
+        "string" | "char" => "String".to_string(),
+        other if input && !enums.contains(other) => input_name(other),
+        other => other.to_string(),
+    }
+}
+
+/// Non-null unless optional; list elements are always non-null.
+fn graphql_type(var: &Variable, input: bool, enums: &HashSet<&str>) -> String {
+    let base = convert_type(var, input, enums);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        _ => format!("[{}!]", base),
+    };
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        ty
+    } else {
+        format!("{}!", ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{var, generated, generated_all};
+
+    fn car() -> OmlObject {
+        OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("id", "uint64", vec![VariableModifier::CONST], ArrayKind::None),
+                var("max_speed", "double", vec![], ArrayKind::None),
+                var("doors", "uint8", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                var("mileage", "int64", vec![], ArrayKind::None),
+                var("engine", "Engine", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                var("color", "Color", vec![], ArrayKind::None),
+                var("tags", "string", vec![], ArrayKind::Dynamic),
+                var("count", "int32", vec![VariableModifier::STATIC], ArrayKind::None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn color() -> OmlObject {
+        OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                var("Red", "string", vec![], ArrayKind::None),
+                var("darkBlue", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_type_and_input() {
+        let out = generated_all(&GraphqlGenerator::default(), &[car(), color()]);
+        assert!(out.contains(
+            "type Car {\n\
+             \t# static count is not representable in a type\n\
+             \tid: ID!\n\
+             \tmaxSpeed: Float!\n\
+             \tdoors: Int\n\
+             \tmileage: String!\n\
+             \tengine: Engine\n\
+             \tcolor: Color!\n\
+             \ttags: [String!]!\n\
+             }\n"
+        ), "Got: {}", out);
+        assert!(out.contains("input CarInput {\n"), "Got: {}", out);
+        assert!(out.contains("\tengine: EngineInput\n\tcolor: Color!\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum() {
+        assert!(generated_all(&GraphqlGenerator::default(), &[color()]).contains("enum Color {\n\tRED\n\tDARK_BLUE\n}\n"));
+    }
+
+    #[test]
+    fn test_empty_type_has_no_body() {
+        let obj = OmlObject { oml_type: ObjectType::STRUCT, name: "Marker".to_string(), ..Default::default() };
+        assert!(generated(&GraphqlGenerator::default(), &obj).contains("type Marker\n\ninput MarkerInput\n"));
+    }
+}
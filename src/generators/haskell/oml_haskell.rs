@@ -0,0 +1,286 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate, GeneratedFile};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::{to_camel_case, to_pascal_case};
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct HaskellGenerator {
+    pub style: CodeStyle,
+}
+
+impl HaskellGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Generate for HaskellGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut hs_file = CodeWriter::new(self.style);
+
+        writeln!(hs_file, "-- This file has been generated from {}.oml", file_name)?;
+        writeln!(hs_file)?;
+        writeln!(hs_file, "{{-# LANGUAGE DeriveGeneric #-}}")?;
+        writeln!(hs_file)?;
+        writeln!(hs_file, "module {} where", to_pascal_case(file_name))?;
+        writeln!(hs_file)?;
+
+        for import in collect_imports(oml_objects) {
+            writeln!(hs_file, "{}", import)?;
+        }
+        writeln!(hs_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut hs_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_record(oml_object, &mut hs_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(hs_file)?;
+            }
+        }
+
+        Ok(hs_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "hs"
+    }
+
+    fn capitalised_types(&self) -> bool {
+        true
+    }
+
+    /// Record fields are prefixed with their type's name and statics are
+    /// left out, so no field is spelled like a keyword.
+    fn keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("--")
+    }
+
+    /// GHC looks up a module `NetworkPacket` in `NetworkPacket.hs`, so the
+    /// file is named after the module rather than the .oml input.
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let content = self.generate(oml_objects, file_name)?;
+        Ok(vec![GeneratedFile::new(format!("{}.{}", to_pascal_case(file_name), self.extension()), content)])
+    }
+}
+
+fn collect_imports(oml_objects: &[OmlObject]) -> Vec<String> {
+    let fields: Vec<&Variable> = oml_objects
+        .iter()
+        .filter(|o| o.oml_type != ObjectType::ENUM)
+        .flat_map(|o| &o.variables)
+        .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+        .collect();
+
+    let mut ints: Vec<String> = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    for bits in ["8", "16", "32", "64"] {
+        if fields.iter().any(|v| v.var_type == format!("int{}", bits)) {
+            ints.push(format!("Int{}", bits));
+        }
+        if fields.iter().any(|v| v.var_type == format!("uint{}", bits)) {
+            words.push(format!("Word{}", bits));
+        }
+    }
+
+    let mut imports = Vec::new();
+    if !ints.is_empty() {
+        imports.push(format!("import Data.Int ({})", ints.join(", ")));
+    }
+    if fields.iter().any(|v| v.var_type == "string") {
+        imports.push("import Data.Text (Text)".to_string());
+    }
+    if !words.is_empty() {
+        imports.push(format!("import Data.Word ({})", words.join(", ")));
+    }
+    imports.push("import GHC.Generics (Generic)".to_string());
+    imports
+}
+
+/// A sum type with one nullary constructor per variant.
+fn generate_enum(oml_object: &OmlObject, hs_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(hs_file, "data {}", oml_object.name)?;
+    // deriving on an empty data type needs EmptyDataDeriving
+    if oml_object.variables.is_empty() {
+        return Ok(());
+    }
+    for (i, var) in oml_object.variables.iter().enumerate() {
+        let separator = if i == 0 { "=" } else { "|" };
+        writeln!(hs_file, "{}{} {}", hs_file.indent(1), separator, to_pascal_case(&var.name))?;
+    }
+    writeln!(hs_file, "{}deriving (Show, Eq, Ord, Enum, Bounded, Generic)", hs_file.indent(1))?;
+
+    Ok(())
+}
+
+/// A record whose field names are prefixed with the type name, since
+/// record fields share one namespace per module. Statics have no place in
+/// a record and are noted in a comment.
+fn generate_record(oml_object: &OmlObject, hs_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let prefix = to_camel_case(name);
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    for var in &static_vars {
+        writeln!(hs_file, "-- static {} is not representable in a record", var.name)?;
+    }
+
+    writeln!(hs_file, "data {} = {}", name, name)?;
+    if !fields.is_empty() {
+        for (i, var) in fields.iter().enumerate() {
+            let separator = if i == 0 { "{" } else { "," };
+            writeln!(
+                hs_file,
+                "{}{} {}{} :: {}",
+                hs_file.indent(1),
+                separator,
+                prefix,
+                to_pascal_case(&var.name),
+                haskell_type(var),
+            )?;
+        }
+        writeln!(hs_file, "{}}}", hs_file.indent(1))?;
+    }
+    writeln!(hs_file, "{}deriving (Show, Eq, Generic)", hs_file.indent(1))?;
+
+    Ok(())
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" => "Int8".to_string(),
+        "int16" => "Int16".to_string(),
+        "int32" => "Int32".to_string(),
+        "int64" => "Int64".to_string(),
+        "uint8" => "Word8".to_string(),
+        "uint16" => "Word16".to_string(),
+        "uint32" => "Word32".to_string(),
+        "uint64" => "Word64".to_string(),
+        "float" => "Float".to_string(),
+        "double" => "Double".to_string(),
+        "bool" => "Bool".to_string(),
+        "string" => "Text".to_string(),
+        "char" => "Char".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Both array kinds become lists.
+fn haskell_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        _ => format!("[{}]", base),
+    };
+    if !var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        ty
+    } else if ty.contains(' ') {
+        format!("Maybe ({})", ty)
+    } else {
+        format!("Maybe {}", ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::var;
+
+    #[test]
+    fn test_module_header_and_imports() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Packet".to_string(),
+            variables: vec![
+                var("id", "uint32", vec![], ArrayKind::None),
+                var("ttl", "int8", vec![], ArrayKind::None),
+                var("payload", "uint8", vec![], ArrayKind::Dynamic),
+                var("source", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = HaskellGenerator::default().generate(std::slice::from_ref(&obj), "network_packet").unwrap();
+        assert!(out.starts_with(
+            "-- This file has been generated from network_packet.oml\n\n\
+             {-# LANGUAGE DeriveGeneric #-}\n\n\
+             module NetworkPacket where\n\n\
+             import Data.Int (Int8)\n\
+             import Data.Text (Text)\n\
+             import Data.Word (Word8, Word32)\n\
+             import GHC.Generics (Generic)\n\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_record_with_maybe_and_lists() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "GameEntity".to_string(),
+            variables: vec![
+                var("name", "string", vec![], ArrayKind::None),
+                var("max_health", "double", vec![], ArrayKind::None),
+                var("nickname", "string", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                var("tags", "string", vec![VariableModifier::OPTIONAL], ArrayKind::Static(3)),
+                var("count", "int32", vec![VariableModifier::STATIC], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = HaskellGenerator::default().generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.contains(
+            "-- static count is not representable in a record\n\
+             data GameEntity = GameEntity\n\
+             \t{ gameEntityName :: Text\n\
+             \t, gameEntityMaxHealth :: Double\n\
+             \t, gameEntityNickname :: Maybe Text\n\
+             \t, gameEntityTags :: Maybe [Text]\n\
+             \t}\n\
+             \tderiving (Show, Eq, Generic)\n"
+        ), "Got: {}", out);
+        assert!(!out.contains("Data.Int"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_file_is_named_after_module() {
+        let obj = OmlObject { oml_type: ObjectType::CLASS, name: "Unit".to_string(), ..Default::default() };
+        let files = HaskellGenerator::default().generate_files(std::slice::from_ref(&obj), "game_entity").unwrap();
+        assert_eq!(files[0].path, std::path::PathBuf::from("GameEntity.hs"));
+    }
+
+    #[test]
+    fn test_empty_record() {
+        let obj = OmlObject { oml_type: ObjectType::CLASS, name: "Unit".to_string(), ..Default::default() };
+        let out = HaskellGenerator::default().generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.ends_with("data Unit = Unit\n\tderiving (Show, Eq, Generic)\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_sum_type() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                var("RED", "string", vec![], ArrayKind::None),
+                var("dark_blue", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = HaskellGenerator::default().generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.contains(
+            "data Color\n\t= Red\n\t| DarkBlue\n\tderiving (Show, Eq, Ord, Enum, Bounded, Generic)\n"
+        ), "Got: {}", out);
+    }
+}
@@ -2,10 +2,22 @@ use crate::core::oml_object::{
     OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
 };
 use crate::core::generate::{Generate, BackwardsGenerate};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
 use std::error::Error;
 use std::fmt::Write;
 
-pub struct JavaGenerator;
+#[derive(Default)]
+pub struct JavaGenerator {
+    pub style: CodeStyle,
+}
+
+impl JavaGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
 
 impl BackwardsGenerate for JavaGenerator {
     fn reverse(&self, content: &str) -> Result<Vec<OmlObject>, Box<dyn Error>> {
@@ -20,14 +32,14 @@ impl BackwardsGenerate for JavaGenerator {
                 let name = trimmed
                     .strip_prefix("public enum ")
                     .unwrap()
-                    .trim_end_matches(|c: char| c == '{' || c == ' ')
+                    .trim_end_matches(['{', ' '])
                     .to_string();
                 let mut vars = Vec::new();
                 i += 1;
                 while i < lines.len() {
                     let line = lines[i].trim();
                     if line == "}" { break; }
-                    let variant = line.trim_end_matches(|c: char| c == ',' || c == ';').trim().to_string();
+                    let variant = line.trim_end_matches([',', ';']).trim().to_string();
                     if !variant.is_empty() {
                         vars.push(Variable {
                             var_mod: vec![],
@@ -35,6 +47,7 @@ impl BackwardsGenerate for JavaGenerator {
                             var_type: "string".to_string(),
                             array_kind: ArrayKind::None,
                             name: variant,
+                            ..Default::default()
                         });
                     }
                     i += 1;
@@ -43,12 +56,13 @@ impl BackwardsGenerate for JavaGenerator {
                     oml_type: ObjectType::ENUM,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
             } else if trimmed.starts_with("public class ") && trimmed.ends_with('{') {
                 let name = trimmed
                     .strip_prefix("public class ")
                     .unwrap()
-                    .trim_end_matches(|c: char| c == '{' || c == ' ')
+                    .trim_end_matches(['{', ' '])
                     .to_string();
                 let mut vars = Vec::new();
                 i += 1;
@@ -56,10 +70,10 @@ impl BackwardsGenerate for JavaGenerator {
                     let line = lines[i].trim();
                     if line == "}" { break; }
                     // Only parse field declarations (before constructor)
-                    if line.ends_with(';') && !line.contains('(') && !line.contains("return") {
-                        if let Some(var) = parse_java_field(line) {
-                            vars.push(var);
-                        }
+                    if line.ends_with(';') && !line.contains('(') && !line.contains("return")
+                        && let Some(var) = parse_java_field(line)
+                    {
+                        vars.push(var);
                     }
                     // Stop parsing fields when we hit the constructor
                     if line.contains(&format!("public {}(", name)) { break; }
@@ -77,6 +91,7 @@ impl BackwardsGenerate for JavaGenerator {
                     oml_type: ObjectType::CLASS,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
                 continue;
             }
@@ -139,12 +154,11 @@ fn parse_java_field(line: &str) -> Option<Variable> {
     if type_token.starts_with("List<") && type_token.ends_with('>') {
         let inner = &type_token[5..type_token.len() - 1];
         let oml_type = reverse_java_boxed_type(inner);
-        return Some(Variable { var_mod, visibility, var_type: oml_type, array_kind: ArrayKind::Dynamic, name });
+        return Some(Variable { var_mod, visibility, var_type: oml_type, array_kind: ArrayKind::Dynamic, name, ..Default::default() });
     }
 
     // Handle arrays: type[] /* [N] */
-    if type_token.ends_with("[]") {
-        let base = &type_token[..type_token.len() - 2];
+    if let Some(base) = type_token.strip_suffix("[]") {
         // Check for size comment
         let remaining = tokens[idx + 1..].join(" ");
         let size = if let Some(start) = remaining.find("/* [") {
@@ -158,7 +172,7 @@ fn parse_java_field(line: &str) -> Option<Variable> {
             Some(n) => ArrayKind::Static(n),
             None => ArrayKind::Dynamic,
         };
-        return Some(Variable { var_mod, visibility, var_type: reverse_java_type(base), array_kind, name: name_str });
+        return Some(Variable { var_mod, visibility, var_type: reverse_java_type(base), array_kind, name: name_str, ..Default::default() });
     }
 
     Some(Variable {
@@ -167,6 +181,7 @@ fn parse_java_field(line: &str) -> Option<Variable> {
         var_type: reverse_java_type(type_token),
         array_kind: ArrayKind::None,
         name,
+        ..Default::default()
     })
 }
 
@@ -185,9 +200,19 @@ fn reverse_java_boxed_type(boxed: &str) -> String {
     }
 }
 
+/// Words reserved in Java, which fields are renamed away from.
+const KEYWORDS: [&str; 54] = [
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const",
+    "continue", "default", "do", "double", "else", "enum", "extends", "false", "final", "finally",
+    "float", "for", "goto", "if", "implements", "import", "instanceof", "int", "interface", "long",
+    "native", "new", "null", "package", "private", "protected", "public", "return", "short", "static",
+    "strictfp", "super", "switch", "synchronized", "this", "throw", "throws", "transient", "true", "try",
+    "void", "volatile", "while", "_",
+];
+
 impl Generate for JavaGenerator {
     fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
-        let mut java_file = String::new();
+        let mut java_file = CodeWriter::new(self.style);
 
         writeln!(java_file, "// This file has been generated from {}.oml", file_name)?;
         writeln!(java_file)?;
@@ -212,12 +237,25 @@ impl Generate for JavaGenerator {
             }
         }
 
-        Ok(java_file)
+        Ok(java_file.finish())
     }
 
     fn extension(&self) -> &str {
         "java"
     }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Camel,
+            constants: Case::ScreamingSnake,
+            enum_values: Case::ScreamingSnake,
+        }
+    }
 }
 
 fn collect_imports(oml_objects: &[OmlObject]) -> Vec<String> {
@@ -236,12 +274,12 @@ fn collect_imports(oml_objects: &[OmlObject]) -> Vec<String> {
     imports
 }
 
-fn generate_enum(oml_object: &OmlObject, java_file: &mut String) -> Result<(), std::fmt::Error> {
-    writeln!(java_file, "public enum {} {{", oml_object.name)?;
+fn generate_enum(oml_object: &OmlObject, java_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    java_file.open_block(0, &format!("public enum {}", oml_object.name))?;
     let length = oml_object.variables.len();
 
     for (index, var) in oml_object.variables.iter().enumerate() {
-        write!(java_file, "\t{}", var.name.to_uppercase())?;
+        write!(java_file, "{}{}", java_file.indent(1), var.name.to_uppercase())?;
         if index == length - 1 {
             writeln!(java_file, ";")?;
         } else {
@@ -256,9 +294,9 @@ fn generate_enum(oml_object: &OmlObject, java_file: &mut String) -> Result<(), s
 
 fn generate_class(
     oml_object: &OmlObject,
-    java_file: &mut String,
+    java_file: &mut CodeWriter,
 ) -> Result<(), std::fmt::Error> {
-    writeln!(java_file, "public class {} {{", oml_object.name)?;
+    java_file.open_block(0, &format!("public class {}", oml_object.name))?;
 
     if oml_object.variables.is_empty() {
         writeln!(java_file, "}}")?;
@@ -292,27 +330,27 @@ fn generate_class(
         let total = required.len() + optional.len();
         let mut index = 0;
 
-        writeln!(java_file, "\tpublic {}(", oml_object.name)?;
+        writeln!(java_file, "{}public {}(", java_file.indent(1), oml_object.name)?;
         for var in &required {
             let java_type = type_annotation(&var.var_type, &var.array_kind, false);
-            write!(java_file, "\t\t{} {}", java_type, var.name)?;
+            write!(java_file, "{}{} {}", java_file.indent(2), java_type, var.name)?;
             index += 1;
             if index < total { writeln!(java_file, ",")?; } else { writeln!(java_file)?; }
         }
         for var in &optional {
             let java_type = type_annotation(&var.var_type, &var.array_kind, false);
-            write!(java_file, "\t\t{} {}", java_type, var.name)?;
+            write!(java_file, "{}{} {}", java_file.indent(2), java_type, var.name)?;
             index += 1;
             if index < total { writeln!(java_file, ",")?; } else { writeln!(java_file)?; }
         }
 
-        writeln!(java_file, "\t) {{")?;
+        java_file.open_block(1, ")")?;
 
         for var in required.iter().chain(optional.iter()) {
-            writeln!(java_file, "\t\tthis.{} = {};", var.name, var.name)?;
+            writeln!(java_file, "{}this.{} = {};", java_file.indent(2), var.name, var.name)?;
         }
 
-        writeln!(java_file, "\t}}")?;
+        writeln!(java_file, "{}}}", java_file.indent(1))?;
     }
 
     // Getters and setters for non-static, non-const fields
@@ -328,9 +366,7 @@ fn generate_class(
             }
             write_getter(var, java_file)?;
             // No setter for const (final) fields
-            if !var.var_mod.contains(&VariableModifier::CONST)
-                || var.var_mod.contains(&VariableModifier::MUT)
-            {
+            if !var.is_const() {
                 write_setter(var, java_file)?;
             }
         }
@@ -342,8 +378,8 @@ fn generate_class(
 }
 
 /// Writes a single class field declaration.
-fn write_field(var: &Variable, java_file: &mut String) -> Result<(), std::fmt::Error> {
-    write!(java_file, "\t")?;
+fn write_field(var: &Variable, java_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    write!(java_file, "{}", java_file.indent(1))?;
 
     // Visibility
     match var.visibility {
@@ -358,30 +394,44 @@ fn write_field(var: &Variable, java_file: &mut String) -> Result<(), std::fmt::E
     }
 
     // final for const (without mut override)
-    if var.var_mod.contains(&VariableModifier::CONST)
-        && !var.var_mod.contains(&VariableModifier::MUT)
-    {
+    if var.is_const() {
         write!(java_file, "final ")?;
     }
 
     let java_type = type_annotation(&var.var_type, &var.array_kind, var.var_mod.contains(&VariableModifier::OPTIONAL));
 
-    writeln!(java_file, "{} {};", java_type, var.name)?;
+    // Instance fields are set by the constructor, statics by their value
+    match &var.value {
+        Some(value) if var.var_mod.contains(&VariableModifier::STATIC) => {
+            writeln!(java_file, "{} {} = {};", java_type, var.name, java_literal(&var.var_type, value))?;
+        }
+        _ => writeln!(java_file, "{} {};", java_type, var.name)?,
+    }
 
     Ok(())
 }
 
-fn write_getter(var: &Variable, java_file: &mut String) -> Result<(), std::fmt::Error> {
+/// An OML literal in Java, suffixed to the width of `var_type`.
+fn java_literal(var_type: &str, value: &str) -> String {
+    match var_type {
+        "int64" | "uint32" | "uint64" => format!("{}L", value),
+        "float" => format!("{}f", value),
+        "double" if !value.contains('.') => format!("{}.0", value),
+        _ => value.to_string(),
+    }
+}
+
+fn write_getter(var: &Variable, java_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
     let java_type = type_annotation(&var.var_type, &var.array_kind, var.var_mod.contains(&VariableModifier::OPTIONAL));
     let getter_name = format!("get{}", capitalise(&var.name));
-    writeln!(java_file, "\tpublic {} {}() {{ return {}; }}", java_type, getter_name, var.name)?;
+    writeln!(java_file, "{}public {} {}() {{ return {}; }}", java_file.indent(1), java_type, getter_name, var.name)?;
     Ok(())
 }
 
-fn write_setter(var: &Variable, java_file: &mut String) -> Result<(), std::fmt::Error> {
+fn write_setter(var: &Variable, java_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
     let java_type = type_annotation(&var.var_type, &var.array_kind, var.var_mod.contains(&VariableModifier::OPTIONAL));
     let setter_name = format!("set{}", capitalise(&var.name));
-    writeln!(java_file, "\tpublic void {}({} value) {{ this.{} = value; }}", setter_name, java_type, var.name)?;
+    writeln!(java_file, "{}public void {}({} value) {{ this.{} = value; }}", java_file.indent(1), setter_name, java_type, var.name)?;
     Ok(())
 }
 
@@ -16,18 +16,18 @@ fn ensure_test_results_dir() {
 fn generate_and_write(oml_path: &str, file_name: &str) -> String {
     ensure_test_results_dir();
 
-    let generator = JavaGenerator;
+    let generator = JavaGenerator::default();
 
     let path = Path::new(oml_path);
     let (oml_objects, _imports) = OmlObject::get_from_file(path)
-        .expect(&format!("Failed to parse OML file: {}", oml_path));
+        .unwrap_or_else(|_| panic!("Failed to parse OML file: {}", oml_path));
 
     let java_output = generator.generate(&oml_objects, file_name)
-        .expect(&format!("Failed to generate Java for: {}", file_name));
+        .unwrap_or_else(|_| panic!("Failed to generate Java for: {}", file_name));
 
     let output_path = format!("{}/{}.{}", TEST_RESULTS_DIR, file_name, generator.extension());
     fs::write(&output_path, &java_output)
-        .expect(&format!("Failed to write output file: {}", output_path));
+        .unwrap_or_else(|_| panic!("Failed to write output file: {}", output_path));
 
     java_output
 }
@@ -150,11 +150,12 @@ fn test_enum_single_variant_ends_with_semicolon() {
         oml_type: ObjectType::ENUM,
         name: "Single".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Only".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Only".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = JavaGenerator.generate(std::slice::from_ref(&oml_object), "Single").unwrap();
+    let output = JavaGenerator::default().generate(std::slice::from_ref(&oml_object), "Single").unwrap();
     assert!(output.contains("\tONLY;"));
     assert!(!output.contains("ONLY,"));
 }
@@ -165,11 +166,12 @@ fn test_const_field_generates_final_no_setter() {
         oml_type: ObjectType::CLASS,
         name: "Config".to_string(),
         variables: vec![
-            Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "version".to_string() },
+            Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "version".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = JavaGenerator.generate(std::slice::from_ref(&oml_object), "Config").unwrap();
+    let output = JavaGenerator::default().generate(std::slice::from_ref(&oml_object), "Config").unwrap();
     assert!(output.contains("private final String version;"));
     // No setter for final fields
     assert!(!output.contains("setVersion("));
@@ -183,12 +185,13 @@ fn test_static_field_not_in_constructor() {
         oml_type: ObjectType::CLASS,
         name: "Counter".to_string(),
         variables: vec![
-            Variable { var_mod: vec![VariableModifier::STATIC], visibility: VariableVisibility::PRIVATE, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "count".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string() },
+            Variable { var_mod: vec![VariableModifier::STATIC], visibility: VariableVisibility::PRIVATE, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "count".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = JavaGenerator.generate(std::slice::from_ref(&oml_object), "Counter").unwrap();
+    let output = JavaGenerator::default().generate(std::slice::from_ref(&oml_object), "Counter").unwrap();
     assert!(output.contains("private static int count;"));
     assert!(!output.contains("this.count"));
 }
@@ -199,12 +202,13 @@ fn test_optional_params_come_after_required_in_constructor() {
         oml_type: ObjectType::CLASS,
         name: "Mixed".to_string(),
         variables: vec![
-            Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "opt_first".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "required".to_string() },
+            Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "opt_first".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "required".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = JavaGenerator.generate(std::slice::from_ref(&oml_object), "Mixed").unwrap();
+    let output = JavaGenerator::default().generate(std::slice::from_ref(&oml_object), "Mixed").unwrap();
     // Search within the constructor block to avoid matching the field declarations above it
     let constructor_start = output.find("public Mixed(").unwrap();
     let constructor_region = &output[constructor_start..];
@@ -219,11 +223,12 @@ fn test_dynamic_list_generates_list_type_and_import() {
         oml_type: ObjectType::CLASS,
         name: "Container".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::Dynamic, name: "tags".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::Dynamic, name: "tags".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = JavaGenerator.generate(std::slice::from_ref(&oml_object), "Container").unwrap();
+    let output = JavaGenerator::default().generate(std::slice::from_ref(&oml_object), "Container").unwrap();
     assert!(output.contains("import java.util.List;"));
     assert!(output.contains("public List<String> tags;"));
 }
@@ -234,11 +239,12 @@ fn test_static_array_expands_with_size_comment() {
         oml_type: ObjectType::CLASS,
         name: "Matrix".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "float".to_string(), array_kind: ArrayKind::Static(4), name: "data".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "float".to_string(), array_kind: ArrayKind::Static(4), name: "data".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = JavaGenerator.generate(std::slice::from_ref(&oml_object), "Matrix").unwrap();
+    let output = JavaGenerator::default().generate(std::slice::from_ref(&oml_object), "Matrix").unwrap();
     assert!(output.contains("public float[] /* [4] */ data;"));
 }
 
@@ -257,10 +263,11 @@ fn test_all_builtin_types_convert_to_java() {
         var_type: oml_type.to_string(),
         array_kind: ArrayKind::None,
         name: format!("field_{}", i),
+        ..Default::default()
     }).collect();
 
-    let oml_object = OmlObject { oml_type: ObjectType::CLASS, name: "AllTypes".to_string(), variables };
-    let output = JavaGenerator.generate(std::slice::from_ref(&oml_object), "AllTypes").unwrap();
+    let oml_object = OmlObject { oml_type: ObjectType::CLASS, name: "AllTypes".to_string(), variables, ..Default::default() };
+    let output = JavaGenerator::default().generate(std::slice::from_ref(&oml_object), "AllTypes").unwrap();
 
     for (i, (_, expected)) in pairs.iter().enumerate() {
         let expected_field = format!("{} field_{};", expected, i);
@@ -270,11 +277,23 @@ fn test_all_builtin_types_convert_to_java() {
 
 #[test]
 fn test_undecided_object_type_returns_error() {
-    let oml_object = OmlObject { oml_type: ObjectType::UNDECIDED, name: "Bad".to_string(), variables: vec![] };
-    assert!(JavaGenerator.generate(std::slice::from_ref(&oml_object), "Bad").is_err());
+    let oml_object = OmlObject { oml_type: ObjectType::UNDECIDED, name: "Bad".to_string(), variables: vec![], ..Default::default() };
+    assert!(JavaGenerator::default().generate(std::slice::from_ref(&oml_object), "Bad").is_err());
 }
 
 #[test]
 fn test_extension_is_java() {
-    assert_eq!(JavaGenerator.extension(), "java");
+    assert_eq!(JavaGenerator::default().extension(), "java");
+}
+
+#[test]
+fn test_statics_start_from_their_values() {
+    let oml_objects = OmlObject::scan_file(
+        "class Config {\n public static const double ratio = 2;\n public static int64 count = 5;\n public const int32 max = 3;\n}\n".to_string()
+    ).unwrap();
+
+    let output = JavaGenerator::default().generate(&oml_objects, "Config").unwrap();
+    assert!(output.contains("\tpublic static final double ratio = 2.0;\n"), "Got: {}", output);
+    assert!(output.contains("\tpublic static long count = 5L;\n"), "Got: {}", output);
+    assert!(output.contains("\tpublic final int max;\n"), "Got: {}", output);
 }
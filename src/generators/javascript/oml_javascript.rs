@@ -0,0 +1,304 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct JavascriptGenerator {
+    pub style: CodeStyle,
+}
+
+impl JavascriptGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Words reserved in JavaScript, which fields are renamed away from.
+const KEYWORDS: [&str; 46] = [
+    "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do",
+    "else", "enum", "export", "extends", "false", "finally", "for", "function", "if", "import",
+    "in", "instanceof", "new", "null", "return", "super", "switch", "this", "throw", "true", "try",
+    "typeof", "var", "void", "while", "with", "implements", "interface", "let", "package",
+    "private", "protected", "public", "static", "yield", "await",
+];
+
+impl Generate for JavascriptGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut js_file = CodeWriter::new(self.style);
+
+        writeln!(js_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(js_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut js_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_class(oml_object, &mut js_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(js_file)?;
+            }
+        }
+
+        Ok(js_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "js"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+}
+
+/// A frozen object of string constants, named like the TypeScript enums.
+fn generate_enum(oml_object: &OmlObject, js_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(js_file, "/** @enum {{string}} */")?;
+    writeln!(js_file, "export const {} = Object.freeze({{", oml_object.name)?;
+    for var in &oml_object.variables {
+        let name = var.name.to_uppercase();
+        writeln!(js_file, "{}{}: \"{}\",", js_file.indent(1), name, name)?;
+    }
+    writeln!(js_file, "}});")?;
+
+    Ok(())
+}
+
+/// A class with JSDoc-typed fields. The constructor takes a single object
+/// described by a `<Name>Init` typedef, in which optional fields and fields
+/// with a value may be left out and default to `null` or that value.
+fn generate_class(oml_object: &OmlObject, js_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    if !fields.is_empty() {
+        writeln!(js_file, "/**")?;
+        writeln!(js_file, " * @typedef {{object}} {}Init", name)?;
+        for var in &fields {
+            if is_optional(var) || var.value.is_some() {
+                writeln!(js_file, " * @property {{{}}} [{}]", js_type(var), var.name)?;
+            } else {
+                writeln!(js_file, " * @property {{{}}} {}", js_type(var), var.name)?;
+            }
+        }
+        writeln!(js_file, " */")?;
+        writeln!(js_file)?;
+    }
+
+    js_file.open_block(0, &format!("export class {}", name))?;
+
+    for var in &static_vars {
+        let (ty, value) = static_default(var);
+        writeln!(js_file, "{}/** {}@type {{{}}} */", js_file.indent(1), tags(var), ty)?;
+        writeln!(js_file, "{}static {} = {};", js_file.indent(1), var.name, value)?;
+    }
+    if !static_vars.is_empty() && !fields.is_empty() {
+        writeln!(js_file)?;
+    }
+
+    for var in &fields {
+        writeln!(js_file, "{}/** {}@type {{{}}} */", js_file.indent(1), tags(var), js_type(var))?;
+        writeln!(js_file, "{}{};", js_file.indent(1), var.name)?;
+    }
+
+    if !fields.is_empty() {
+        writeln!(js_file)?;
+        writeln!(js_file, "{}/** @param {{{}Init}} init */", js_file.indent(1), name)?;
+        js_file.open_block(1, "constructor(init)")?;
+        for var in &fields {
+            let fallback = match &var.value {
+                Some(value) => format!(" ?? {}", js_literal(&var.var_type, value)),
+                None if is_optional(var) => " ?? null".to_string(),
+                None => String::new(),
+            };
+            writeln!(js_file, "{}this.{} = init.{}{};", js_file.indent(2), var.name, var.name, fallback)?;
+        }
+        writeln!(js_file, "{}}}", js_file.indent(1))?;
+    }
+
+    writeln!(js_file, "}}")?;
+
+    Ok(())
+}
+
+/// JSDoc tags for access and mutability, each followed by a space.
+fn tags(var: &Variable) -> String {
+    let mut tags = String::new();
+    match var.visibility {
+        VariableVisibility::PRIVATE => tags.push_str("@private "),
+        VariableVisibility::PROTECTED => tags.push_str("@protected "),
+        VariableVisibility::PUBLIC => {}
+    }
+    if var.is_const() {
+        tags.push_str("@readonly ");
+    }
+    tags
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" | "int16" | "int32"
+        | "uint8" | "uint16" | "uint32"
+        | "float" | "double" => "number".to_string(),
+        // 64-bit integers exceed Number.MAX_SAFE_INTEGER
+        "int64" | "uint64" => "bigint".to_string(),
+        "bool" => "boolean".to_string(),
+        "string" | "char" => "string".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn js_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        _ => format!("{}[]", base),
+    };
+    if is_optional(var) {
+        format!("{} | null", ty)
+    } else {
+        ty
+    }
+}
+
+/// The type and initial value of a static field: its value, if it has one.
+/// Object-typed statics have no value to start from, so they start as `null`.
+fn static_default(var: &Variable) -> (String, String) {
+    let ty = js_type(var);
+    if let Some(value) = &var.value {
+        return (ty, js_literal(&var.var_type, value));
+    }
+    if is_optional(var) {
+        return (ty, "null".to_string());
+    }
+    if var.array_kind != ArrayKind::None {
+        return (ty, "[]".to_string());
+    }
+    let value = match var.var_type.as_str() {
+        "int8" | "int16" | "int32" | "uint8" | "uint16" | "uint32" | "float" | "double" => "0",
+        "int64" | "uint64" => "0n",
+        "bool" => "false",
+        "string" | "char" => "\"\"",
+        _ => return (format!("{} | null", ty), "null".to_string()),
+    };
+    (ty, value.to_string())
+}
+
+/// An OML `value` as a JavaScript literal; 64-bit integers are `BigInt`s.
+fn js_literal(var_type: &str, value: &str) -> String {
+    match var_type {
+        "int64" | "uint64" => format!("{}n", value),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{member, generated};
+
+    #[test]
+    fn test_class_with_typedef_and_constructor() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                member("name", "string", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::None),
+                member("mileage", "uint64", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+                member("engine", "Engine", VariableVisibility::PROTECTED, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("tags", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&JavascriptGenerator::default(), &obj).contains(
+            "/**\n\
+             \x20* @typedef {object} CarInit\n\
+             \x20* @property {string} name\n\
+             \x20* @property {bigint} mileage\n\
+             \x20* @property {Engine | null} [engine]\n\
+             \x20* @property {string[]} tags\n\
+             \x20*/\n\
+             \n\
+             export class Car {\n\
+             \t/** @readonly @type {string} */\n\
+             \tname;\n\
+             \t/** @private @type {bigint} */\n\
+             \tmileage;\n\
+             \t/** @protected @type {Engine | null} */\n\
+             \tengine;\n\
+             \t/** @type {string[]} */\n\
+             \ttags;\n\
+             \n\
+             \t/** @param {CarInit} init */\n\
+             \tconstructor(init) {\n\
+             \t\tthis.name = init.name;\n\
+             \t\tthis.mileage = init.mileage;\n\
+             \t\tthis.engine = init.engine ?? null;\n\
+             \t\tthis.tags = init.tags;\n\
+             \t}\n\
+             }\n"
+        ));
+    }
+
+    #[test]
+    fn test_static_fields() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Registry".to_string(),
+            variables: vec![
+                member("count", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("main", "Registry", VariableVisibility::PRIVATE, vec![VariableModifier::STATIC], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&JavascriptGenerator::default(), &obj).contains(
+            "export class Registry {\n\
+             \t/** @type {number} */\n\
+             \tstatic count = 0;\n\
+             \t/** @private @type {Registry | null} */\n\
+             \tstatic main = null;\n\
+             }\n"
+        ));
+    }
+
+    #[test]
+    fn test_values_are_initial_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int32 MAX_SPEED = 120;\n public int64 mileage = 5;\n public string name;\n}\n".to_string()
+        ).unwrap();
+        let out = JavascriptGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("\tstatic MAX_SPEED = 120;\n"), "Got: {}", out);
+        assert!(out.contains(" * @property {bigint} [mileage]\n"), "Got: {}", out);
+        assert!(out.contains("\t\tthis.mileage = init.mileage ?? 5n;\n\t\tthis.name = init.name;\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_frozen_enum() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                member("Red", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("GREEN", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&JavascriptGenerator::default(), &obj).contains(
+            "/** @enum {string} */\nexport const Color = Object.freeze({\n\tRED: \"RED\",\n\tGREEN: \"GREEN\",\n});\n"
+        ));
+    }
+}
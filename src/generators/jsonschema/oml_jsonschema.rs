@@ -0,0 +1,257 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate, GeneratedFile};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use std::error::Error;
+use std::fmt::Write;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+#[derive(Default)]
+pub struct JsonSchemaGenerator {
+    pub style: CodeStyle,
+}
+
+impl JsonSchemaGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// One standalone schema document. Its `$id` is the file name it is
+    /// written to, so references to other objects resolve to the sibling
+    /// `<Name>.schema.json` files.
+    fn render_schema(&self, oml_object: &OmlObject, file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut schema = CodeWriter::new(self.style);
+
+        writeln!(schema, "{{")?;
+        writeln!(schema, "{}\"$schema\": \"{}\",", schema.indent(1), DRAFT)?;
+        writeln!(schema, "{}\"$id\": \"{}\",", schema.indent(1), schema_file(&oml_object.name))?;
+        writeln!(schema, "{}\"$comment\": \"This file has been generated from {}.oml\",", schema.indent(1), file_name)?;
+        writeln!(schema, "{}\"title\": \"{}\",", schema.indent(1), oml_object.name)?;
+        match &oml_object.oml_type {
+            ObjectType::ENUM => generate_enum(oml_object, &mut schema)?,
+            ObjectType::CLASS | ObjectType::STRUCT => generate_object(oml_object, &mut schema)?,
+            ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+        }
+        writeln!(schema, "}}")?;
+
+        Ok(schema.finish())
+    }
+}
+
+impl Generate for JsonSchemaGenerator {
+    /// Returns the schemas of every object in the file concatenated together;
+    /// `generate_files` writes one document per object instead.
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut schemas = String::new();
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            schemas.push_str(&self.render_schema(oml_object, file_name)?);
+            if i < oml_objects.len() - 1 {
+                writeln!(schemas)?;
+            }
+        }
+
+        Ok(schemas)
+    }
+
+    fn extension(&self) -> &str {
+        "schema.json"
+    }
+
+    /// Property names are JSON strings, so no word is reserved.
+    fn keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::None
+    }
+
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let mut files = Vec::new();
+
+        for oml_object in oml_objects {
+            let schema = self.render_schema(oml_object, file_name)?;
+            files.push(GeneratedFile::new(schema_file(&oml_object.name), schema));
+        }
+
+        Ok(files)
+    }
+}
+
+#[inline]
+fn schema_file(name: &str) -> String {
+    format!("{}.schema.json", name)
+}
+
+/// Enum values are the variant names as written in the OML file.
+fn generate_enum(oml_object: &OmlObject, schema: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let values: Vec<String> = oml_object.variables.iter().map(|v| format!("\"{}\"", v.name)).collect();
+    writeln!(schema, "{}\"type\": \"string\",", schema.indent(1))?;
+    writeln!(schema, "{}\"enum\": [{}]", schema.indent(1), values.join(", "))?;
+
+    Ok(())
+}
+
+/// A closed object schema: every non-optional field is required and no
+/// other properties are allowed. Statics are not part of an instance and
+/// are left out.
+fn generate_object(oml_object: &OmlObject, schema: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let fields: Vec<&Variable> = oml_object.variables
+        .iter()
+        .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+        .collect();
+
+    writeln!(schema, "{}\"type\": \"object\",", schema.indent(1))?;
+    if fields.is_empty() {
+        writeln!(schema, "{}\"properties\": {{}},", schema.indent(1))?;
+    } else {
+        writeln!(schema, "{}\"properties\": {{", schema.indent(1))?;
+        let length = fields.len();
+        for (i, var) in fields.iter().enumerate() {
+            let separator = if i < length - 1 { "," } else { "" };
+            writeln!(schema, "{}\"{}\": {}{}", schema.indent(2), var.name, property_schema(var), separator)?;
+        }
+        writeln!(schema, "{}}},", schema.indent(1))?;
+    }
+
+    let required: Vec<String> = fields
+        .iter()
+        .filter(|v| !v.var_mod.contains(&VariableModifier::OPTIONAL))
+        .map(|v| format!("\"{}\"", v.name))
+        .collect();
+    writeln!(schema, "{}\"required\": [{}],", schema.indent(1), required.join(", "))?;
+    writeln!(schema, "{}\"additionalProperties\": false", schema.indent(1))?;
+
+    Ok(())
+}
+
+/// The inline schema of one property, with `readOnly` for const fields and
+/// fixed item counts for static arrays.
+fn property_schema(var: &Variable) -> String {
+    let item = type_schema(&var.var_type);
+    let mut keywords = match &var.array_kind {
+        ArrayKind::None => item,
+        ArrayKind::Static(n) => vec![
+            "\"type\": \"array\"".to_string(),
+            format!("\"items\": {{ {} }}", item.join(", ")),
+            format!("\"minItems\": {}", n),
+            format!("\"maxItems\": {}", n),
+        ],
+        ArrayKind::Dynamic => vec![
+            "\"type\": \"array\"".to_string(),
+            format!("\"items\": {{ {} }}", item.join(", ")),
+        ],
+    };
+    if var.is_const() {
+        keywords.push("\"readOnly\": true".to_string());
+    }
+
+    format!("{{ {} }}", keywords.join(", "))
+}
+
+/// Sized integers carry their range, except the 64-bit bounds which JSON
+/// numbers cannot represent exactly. Other objects are referenced by file.
+fn type_schema(var_type: &str) -> Vec<String> {
+    let integer = |min: i64, max: Option<i64>| {
+        let mut keywords = vec!["\"type\": \"integer\"".to_string(), format!("\"minimum\": {}", min)];
+        if let Some(max) = max {
+            keywords.push(format!("\"maximum\": {}", max));
+        }
+        keywords
+    };
+
+    match var_type {
+        "int8" => integer(i8::MIN.into(), Some(i8::MAX.into())),
+        "int16" => integer(i16::MIN.into(), Some(i16::MAX.into())),
+        "int32" => integer(i32::MIN.into(), Some(i32::MAX.into())),
+        "int64" => vec!["\"type\": \"integer\"".to_string()],
+        "uint8" => integer(0, Some(u8::MAX.into())),
+        "uint16" => integer(0, Some(u16::MAX.into())),
+        "uint32" => integer(0, Some(u32::MAX.into())),
+        "uint64" => integer(0, None),
+        "float" | "double" => vec!["\"type\": \"number\"".to_string()],
+        "bool" => vec!["\"type\": \"boolean\"".to_string()],
+        "string" => vec!["\"type\": \"string\"".to_string()],
+        "char" => vec![
+            "\"type\": \"string\"".to_string(),
+            "\"minLength\": 1".to_string(),
+            "\"maxLength\": 1".to_string(),
+        ],
+        other => vec![format!("\"$ref\": \"{}\"", schema_file(other))],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::var;
+
+    #[test]
+    fn test_object_schema() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("name", "string", vec![VariableModifier::CONST], ArrayKind::None),
+                var("speed", "double", vec![], ArrayKind::None),
+                var("wheels", "uint8", vec![], ArrayKind::Static(4)),
+                var("engine", "Engine", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                var("tags", "string", vec![VariableModifier::OPTIONAL], ArrayKind::Dynamic),
+                var("count", "int32", vec![VariableModifier::STATIC], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = JsonSchemaGenerator::default().generate(std::slice::from_ref(&obj), "car").unwrap();
+        assert_eq!(
+            out,
+            "{\n\
+             \t\"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n\
+             \t\"$id\": \"Car.schema.json\",\n\
+             \t\"$comment\": \"This file has been generated from car.oml\",\n\
+             \t\"title\": \"Car\",\n\
+             \t\"type\": \"object\",\n\
+             \t\"properties\": {\n\
+             \t\t\"name\": { \"type\": \"string\", \"readOnly\": true },\n\
+             \t\t\"speed\": { \"type\": \"number\" },\n\
+             \t\t\"wheels\": { \"type\": \"array\", \"items\": { \"type\": \"integer\", \"minimum\": 0, \"maximum\": 255 }, \"minItems\": 4, \"maxItems\": 4 },\n\
+             \t\t\"engine\": { \"$ref\": \"Engine.schema.json\" },\n\
+             \t\t\"tags\": { \"type\": \"array\", \"items\": { \"type\": \"string\" } }\n\
+             \t},\n\
+             \t\"required\": [\"name\", \"speed\", \"wheels\"],\n\
+             \t\"additionalProperties\": false\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_enum_schema() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                var("RED", "string", vec![], ArrayKind::None),
+                var("GREEN", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = JsonSchemaGenerator::default().generate(std::slice::from_ref(&obj), "color").unwrap();
+        assert!(out.contains("\t\"type\": \"string\",\n\t\"enum\": [\"RED\", \"GREEN\"]\n}\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_one_file_per_object() {
+        let objects = [
+            OmlObject { oml_type: ObjectType::STRUCT, name: "Point".to_string(), ..Default::default() },
+            OmlObject { oml_type: ObjectType::ENUM, name: "Axis".to_string(), ..Default::default() },
+        ];
+        let files = JsonSchemaGenerator::default().generate_files(&objects, "geometry").unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, std::path::PathBuf::from("Point.schema.json"));
+        assert!(files[0].content.contains("\t\"properties\": {},\n\t\"required\": [],\n"), "Got: {}", files[0].content);
+        assert_eq!(files[1].path, std::path::PathBuf::from("Axis.schema.json"));
+    }
+}
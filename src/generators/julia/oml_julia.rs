@@ -0,0 +1,263 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_snake_case;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct JuliaGenerator {
+    pub style: CodeStyle,
+}
+
+impl JuliaGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Words reserved in Julia, which fields are renamed away from.
+const KEYWORDS: [&str; 29] = [
+    "baremodule", "begin", "break", "catch", "const", "continue", "do", "else", "elseif", "end",
+    "export", "false", "finally", "for", "function", "global", "if", "import", "let", "local",
+    "macro", "module", "quote", "return", "struct", "true", "try", "using", "while",
+];
+
+impl Generate for JuliaGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut jl_file = CodeWriter::new(self.style);
+
+        writeln!(jl_file, "# This file has been generated from {}.oml", file_name)?;
+        writeln!(jl_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut jl_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_struct(oml_object, &mut jl_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(jl_file)?;
+            }
+        }
+
+        Ok(jl_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "jl"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("#")
+    }
+}
+
+fn generate_enum(oml_object: &OmlObject, jl_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(jl_file, "@enum {} begin", oml_object.name)?;
+    for var in &oml_object.variables {
+        writeln!(jl_file, "{}{}", jl_file.indent(1), var.name)?;
+    }
+    writeln!(jl_file, "end")?;
+
+    Ok(())
+}
+
+/// A `struct` when every field is const, otherwise a `mutable struct` with
+/// `const` on the fields that are (Julia 1.8+). Optional fields default to
+/// `nothing` through `Base.@kwdef`. Statics become typed globals named
+/// after the struct.
+fn generate_struct(oml_object: &OmlObject, jl_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    let mutable = fields.iter().any(|v| !is_const(v));
+    let kwdef = if fields.iter().any(|v| is_optional(v)) { "Base.@kwdef " } else { "" };
+    let keyword = if mutable { "mutable struct" } else { "struct" };
+
+    writeln!(jl_file, "{}{} {}", kwdef, keyword, name)?;
+    for var in &fields {
+        let qualifier = if mutable && is_const(var) { "const " } else { "" };
+        let default = if is_optional(var) { " = nothing" } else { "" };
+        writeln!(jl_file, "{}{}{}::{}{}", jl_file.indent(1), qualifier, var.name, julia_type(var), default)?;
+    }
+    writeln!(jl_file, "end")?;
+
+    if !static_vars.is_empty() {
+        writeln!(jl_file)?;
+        let prefix = to_snake_case(name);
+        for var in &static_vars {
+            let qualifier = if is_const(var) { "const " } else { "" };
+            writeln!(
+                jl_file,
+                "{}{}_{}::{} = {}",
+                qualifier,
+                prefix,
+                var.name,
+                julia_type(var),
+                default_value(var),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn is_const(var: &Variable) -> bool {
+    var.is_const()
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" => "Int8".to_string(),
+        "int16" => "Int16".to_string(),
+        "int32" => "Int32".to_string(),
+        "int64" => "Int64".to_string(),
+        "uint8" => "UInt8".to_string(),
+        "uint16" => "UInt16".to_string(),
+        "uint32" => "UInt32".to_string(),
+        "uint64" => "UInt64".to_string(),
+        "float" => "Float32".to_string(),
+        "double" => "Float64".to_string(),
+        "bool" => "Bool".to_string(),
+        "string" => "String".to_string(),
+        "char" => "Char".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Static arrays become `NTuple`s, which keep their length in the type and
+/// stay inline in memory.
+fn julia_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        ArrayKind::Static(n) => format!("NTuple{{{}, {}}}", n, base),
+        ArrayKind::Dynamic => format!("Vector{{{}}}", base),
+    };
+    if is_optional(var) {
+        format!("Union{{{}, Nothing}}", ty)
+    } else {
+        ty
+    }
+}
+
+fn default_value(var: &Variable) -> String {
+    if let Some(value) = &var.value {
+        // `$` interpolates in a Julia string
+        return value.replace('$', "\\$");
+    }
+    if is_optional(var) {
+        return "nothing".to_string();
+    }
+    let base = convert_type(&var.var_type);
+    match &var.array_kind {
+        ArrayKind::Static(n) => format!("ntuple(_ -> zero({}), {})", base, n),
+        ArrayKind::Dynamic => format!("{}[]", base),
+        ArrayKind::None => match var.var_type.as_str() {
+            "string" => "\"\"".to_string(),
+            "char" => "'\\0'".to_string(),
+            "bool" => "false".to_string(),
+            "int8" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" | "uint64"
+            | "float" | "double" => format!("zero({})", base),
+            _ => "nothing".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{var, generated};
+
+    #[test]
+    fn test_all_const_fields_make_an_immutable_struct() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Point".to_string(),
+            variables: vec![
+                var("x", "double", vec![VariableModifier::CONST], ArrayKind::None),
+                var("rgba", "uint8", vec![VariableModifier::CONST], ArrayKind::Static(4)),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&JuliaGenerator::default(), &obj).contains("struct Point\n\tx::Float64\n\trgba::NTuple{4, UInt8}\nend\n"));
+    }
+
+    #[test]
+    fn test_mutable_struct_with_const_and_optional_fields() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Sensor".to_string(),
+            variables: vec![
+                var("id", "string", vec![VariableModifier::CONST], ArrayKind::None),
+                var("readings", "float", vec![], ArrayKind::Dynamic),
+                var("label", "string", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&JuliaGenerator::default(), &obj).contains(
+            "Base.@kwdef mutable struct Sensor\n\
+             \tconst id::String\n\
+             \treadings::Vector{Float32}\n\
+             \tlabel::Union{String, Nothing} = nothing\n\
+             end\n"
+        ));
+    }
+
+    #[test]
+    fn test_statics_become_globals() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "GameEntity".to_string(),
+            variables: vec![
+                var("max_count", "int32", vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+                var("names", "string", vec![VariableModifier::STATIC], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&JuliaGenerator::default(), &obj);
+        assert!(out.contains("struct GameEntity\nend\n\n"), "Got: {}", out);
+        assert!(out.contains("const game_entity_max_count::Int32 = zero(Int32)\n"), "Got: {}", out);
+        assert!(out.contains("game_entity_names::Vector{String} = String[]\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_block() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                var("RED", "string", vec![], ArrayKind::None),
+                var("GREEN", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&JuliaGenerator::default(), &obj).contains("@enum Color begin\n\tRED\n\tGREEN\nend\n"));
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static const float ratio = 2;\n}\n".to_string()
+        ).unwrap();
+        let out = JuliaGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("const car_max_speed::Int64 = 120\nconst car_ratio::Float32 = 2\n"), "Got: {}", out);
+    }
+}
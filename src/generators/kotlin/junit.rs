@@ -0,0 +1,272 @@
+//! kotlin.test (JUnit) scaffolds: one test class per class and struct that
+//! constructs it from sample values, reads back the public properties, runs
+//! the setters and, with `--kotlin-serializable`, round-trips it through JSON.
+
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::code_writer::CodeWriter;
+use crate::core::generate::GeneratedFile;
+use super::oml_kotlin::{KotlinGenerator, constructor_order, has_default, is_read_only};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// How deep sample objects of this file's types are nested before giving up.
+const MAX_SAMPLE_DEPTH: usize = 3;
+
+impl KotlinGenerator {
+    /// `<Object>Test.kt` in the package directory for every class and struct.
+    pub(super) fn generate_tests(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, std::fmt::Error> {
+        let objects = self.type_map.apply(oml_objects);
+        let oml_objects = objects.as_slice();
+        let mut files = Vec::new();
+
+        for oml_object in oml_objects.iter().filter(|o| matches!(o.oml_type, ObjectType::CLASS | ObjectType::STRUCT)) {
+            let mut body = CodeWriter::new(self.style);
+            let arrays = self.write_tests(oml_object, oml_objects, &mut body)?;
+
+            let mut kt_file = CodeWriter::new(self.style);
+            writeln!(kt_file, "// This file has been generated from {}.oml", file_name)?;
+            writeln!(kt_file)?;
+            if let Some(package) = &self.package {
+                writeln!(kt_file, "package {}", package)?;
+                writeln!(kt_file)?;
+            }
+            if self.serializable {
+                writeln!(kt_file, "import kotlinx.serialization.decodeFromString")?;
+                writeln!(kt_file, "import kotlinx.serialization.encodeToString")?;
+                writeln!(kt_file, "import kotlinx.serialization.json.Json")?;
+            }
+            writeln!(kt_file, "import kotlin.test.Test")?;
+            if arrays {
+                writeln!(kt_file, "import kotlin.test.assertContentEquals")?;
+            }
+            writeln!(kt_file, "import kotlin.test.assertEquals")?;
+            writeln!(kt_file)?;
+            write!(kt_file, "{}", body.finish())?;
+
+            let mut path = PathBuf::new();
+            if let Some(package) = &self.package {
+                path.extend(package.split('.'));
+            }
+            path.push(format!("{}Test.kt", oml_object.name));
+            files.push(GeneratedFile::new(path, kt_file.finish()));
+        }
+
+        Ok(files)
+    }
+
+    /// Writes the test class, returning whether it compares arrays.
+    fn write_tests(&self, oml_object: &OmlObject, siblings: &[OmlObject], kt_file: &mut CodeWriter) -> Result<bool, std::fmt::Error> {
+        let name = &oml_object.name;
+        let construct = format!("val value = {}", construct(oml_object, siblings, true, 0));
+        let checked: Vec<&Variable> = oml_object.variables
+            .iter()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC) && self.is_visible(v) && comparable(v, siblings))
+            .collect();
+        let settable: Vec<&&Variable> = checked.iter().filter(|v| !is_read_only(v, self.immutable)).collect();
+        let arrays = checked.iter().any(|v| matches!(v.array_kind, ArrayKind::Static(_)));
+
+        kt_file.begin_block(&format!("class {}Test", name))?;
+
+        kt_file.line("@Test")?;
+        kt_file.begin_block("fun constructsFromEveryField()")?;
+        kt_file.line(&construct)?;
+        for var in &checked {
+            kt_file.line(&assertion(var, &sample(var, siblings, false, 0)))?;
+        }
+        kt_file.end_block("}")?;
+
+        if !settable.is_empty() {
+            kt_file.line("")?;
+            kt_file.line("@Test")?;
+            kt_file.begin_block("fun setsFields()")?;
+            kt_file.line(&construct)?;
+            for var in settable {
+                let value = sample(var, siblings, true, 0);
+                kt_file.line(&format!("value.{} = {}", var.name, value))?;
+                kt_file.line(&assertion(var, &value))?;
+            }
+            kt_file.end_block("}")?;
+        }
+
+        if self.serializable {
+            kt_file.line("")?;
+            kt_file.line("@Test")?;
+            kt_file.begin_block("fun roundTripsJson()")?;
+            kt_file.line(&construct)?;
+            kt_file.line("val json = Json.encodeToString(value)")?;
+            kt_file.line(&format!("assertEquals(json, Json.encodeToString(Json.decodeFromString<{}>(json)))", name))?;
+            kt_file.end_block("}")?;
+        }
+
+        kt_file.end_block("}")?;
+
+        Ok(arrays)
+    }
+
+    /// Public fields and, with accessors, the properties in front of
+    /// private ones.
+    fn is_visible(&self, var: &Variable) -> bool {
+        match var.visibility {
+            VariableVisibility::PUBLIC => true,
+            VariableVisibility::PRIVATE => self.accessors,
+            VariableVisibility::PROTECTED => false,
+        }
+    }
+}
+
+fn assertion(var: &Variable, expected: &str) -> String {
+    // arrays compare by reference
+    let assert = if matches!(var.array_kind, ArrayKind::Static(_)) { "assertContentEquals" } else { "assertEquals" };
+    format!("{}({}, value.{})", assert, expected, var.name)
+}
+
+/// Builtins and enums of this file, whose values compare by content.
+fn comparable(var: &Variable, siblings: &[OmlObject]) -> bool {
+    OmlObject::is_builtin_type(&var.var_type)
+        || siblings.iter().any(|o| o.name == var.var_type && o.oml_type == ObjectType::ENUM)
+}
+
+/// A constructor call in parameter order; nested objects leave the params
+/// with a default (a value, or null when optional) out.
+fn construct(oml_object: &OmlObject, siblings: &[OmlObject], with_defaults: bool, depth: usize) -> String {
+    let fields: Vec<&Variable> = oml_object.variables
+        .iter()
+        .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+        .collect();
+    let args: Vec<String> = constructor_order(&fields)
+        .into_iter()
+        .filter(|v| with_defaults || !has_default(v))
+        .map(|v| sample(v, siblings, false, depth))
+        .collect();
+    format!("{}({})", oml_object.name, args.join(", "))
+}
+
+/// A value of the element type; `second` gives a different one for setters.
+fn sample_element(var_type: &str, siblings: &[OmlObject], second: bool, depth: usize) -> String {
+    let pick = |first: &str, other: &str| if second { other.to_string() } else { first.to_string() };
+    match var_type {
+        "int8" | "int16" | "int32" => pick("1", "2"),
+        "int64" => pick("1L", "2L"),
+        "uint8" | "uint16" | "uint32" => pick("1u", "2u"),
+        "uint64" => pick("1uL", "2uL"),
+        "float" => pick("1.5f", "2.5f"),
+        "double" => pick("1.5", "2.5"),
+        "bool" => pick("true", "false"),
+        "string" => pick("\"text\"", "\"other\""),
+        "char" => pick("'a'", "'b'"),
+        other => match siblings.iter().find(|o| o.name == other) {
+            Some(obj) if obj.oml_type == ObjectType::ENUM => {
+                match if second { obj.variables.last() } else { obj.variables.first() } {
+                    Some(variant) => format!("{}.{}", other, variant.name.to_uppercase()),
+                    None => format!("TODO(\"{} has no values\")", other),
+                }
+            }
+            Some(obj) if depth < MAX_SAMPLE_DEPTH => construct(obj, siblings, false, depth + 1),
+            _ => format!("TODO(\"sample {}\")", other),
+        },
+    }
+}
+
+fn sample(var: &Variable, siblings: &[OmlObject], second: bool, depth: usize) -> String {
+    let element = sample_element(&var.var_type, siblings, second, depth);
+    match var.array_kind {
+        ArrayKind::None => element,
+        ArrayKind::Static(n) => format!("arrayOf({})", vec![element; n as usize].join(", ")),
+        ArrayKind::Dynamic => format!("mutableListOf({})", element),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generate::Generate;
+
+    #[test]
+    fn test_test_class_per_class() {
+        let field = |name: &str, var_type: &str, visibility: VariableVisibility, var_mod: Vec<VariableModifier>, array_kind: ArrayKind| Variable {
+            name: name.to_string(),
+            var_type: var_type.to_string(),
+            visibility,
+            var_mod,
+            array_kind,
+            ..Default::default()
+        };
+        let objects = [
+            OmlObject {
+                oml_type: ObjectType::STRUCT,
+                name: "Engine".to_string(),
+                variables: vec![field("hp", "uint16", VariableVisibility::PUBLIC, vec![], ArrayKind::None)],
+                ..Default::default()
+            },
+            OmlObject {
+                oml_type: ObjectType::CLASS,
+                name: "Car".to_string(),
+                variables: vec![
+                    field("name", "string", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::None),
+                    field("nickname", "string", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                    field("wheels", "double", VariableVisibility::PUBLIC, vec![], ArrayKind::Static(2)),
+                    field("engine", "Engine", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                    field("secret", "int32", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+                ],
+                ..Default::default()
+            },
+        ];
+
+        let files = KotlinGenerator::new(false)
+            .with_package(Some("com.example"))
+            .with_tests(true)
+            .generate_files(&objects, "cars")
+            .unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[2].path, ["com", "example", "CarTest.kt"].iter().collect::<PathBuf>());
+        assert_eq!(
+            files[2].content,
+            "// This file has been generated from cars.oml\n\
+             \n\
+             package com.example\n\
+             \n\
+             import kotlin.test.Test\n\
+             import kotlin.test.assertContentEquals\n\
+             import kotlin.test.assertEquals\n\
+             \n\
+             class CarTest {\n\
+             \t@Test\n\
+             \tfun constructsFromEveryField() {\n\
+             \t\tval value = Car(\"text\", arrayOf(1.5, 1.5), Engine(1u), 1, \"text\")\n\
+             \t\tassertEquals(\"text\", value.name)\n\
+             \t\tassertEquals(\"text\", value.nickname)\n\
+             \t\tassertContentEquals(arrayOf(1.5, 1.5), value.wheels)\n\
+             \t}\n\
+             \n\
+             \t@Test\n\
+             \tfun setsFields() {\n\
+             \t\tval value = Car(\"text\", arrayOf(1.5, 1.5), Engine(1u), 1, \"text\")\n\
+             \t\tvalue.nickname = \"other\"\n\
+             \t\tassertEquals(\"other\", value.nickname)\n\
+             \t\tvalue.wheels = arrayOf(2.5, 2.5)\n\
+             \t\tassertContentEquals(arrayOf(2.5, 2.5), value.wheels)\n\
+             \t}\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_valued_fields_are_passed_last() {
+        let objects = OmlObject::scan_file(
+            "class Settings {\n public int32 volume = 5;\n public string name;\n public optional string theme;\n}\n\
+             class Profile {\n Settings settings;\n}\n".to_string()
+        ).unwrap();
+
+        let files = KotlinGenerator::new(false)
+            .with_tests(true)
+            .generate_files(&objects, "settings")
+            .unwrap();
+        let settings = files.iter().find(|f| f.path.ends_with("SettingsTest.kt")).unwrap();
+        assert!(settings.content.contains("\t\tval value = Settings(\"text\", 1, \"text\")\n"), "Got: {}", settings.content);
+        assert!(settings.content.contains("\t\tassertEquals(1, value.volume)\n"), "Got: {}", settings.content);
+        let profile = files.iter().find(|f| f.path.ends_with("ProfileTest.kt")).unwrap();
+        assert!(profile.content.contains("\t\tval value = Profile(Settings(\"text\"))\n"), "Got: {}", profile.content);
+    }
+}
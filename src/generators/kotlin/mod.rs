@@ -1 +1,2 @@
 pub mod oml_kotlin;
+mod junit;
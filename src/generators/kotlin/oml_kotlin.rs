@@ -1,12 +1,45 @@
 use crate::core::oml_object::{
     OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
 };
-use crate::core::generate::{Generate, BackwardsGenerate};
+use crate::core::generate::{Generate, BackwardsGenerate, GeneratedFile};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
+use crate::core::builder::{builder_fields, BuilderField};
+use crate::core::utils::{self, to_camel_case, to_pascal_case};
+use crate::core::type_map::TypeMap;
+use crate::core::schema_hash::{schema_hash, SCHEMA_HASH};
 use std::error::Error;
 use std::fmt::Write;
 
 pub struct KotlinGenerator {
     pub use_data_class: bool,
+    /// Package of the generated file, e.g. `com.example.models`
+    pub package: Option<String>,
+    /// Annotate the types with kotlinx.serialization's `@Serializable`
+    pub serializable: bool,
+    /// Keep private fields in `_name` backing properties exposed through
+    /// public getters and, unless read-only, setters
+    pub accessors: bool,
+    /// Emit every class as a data class of `val`s and read-only `List`s,
+    /// ignoring `mut`; `copy()` and `withName()` take the place of setters
+    pub immutable: bool,
+    /// Also write a kotlin.test class per class and struct
+    pub tests: bool,
+    /// Give regular classes `equals` and `hashCode` over their fields, as
+    /// data classes have
+    pub equality: bool,
+    /// Give regular classes a `toString` listing their fields, as data
+    /// classes have
+    pub to_string: bool,
+    /// Follow each class with a `<Name>Builder` and a `name { ... }` DSL
+    /// function building it
+    pub builder: bool,
+    /// Give classes a `SCHEMA_HASH` companion constant fingerprinting their
+    /// definition
+    pub schema_hash: bool,
+    /// Types replacing OML ones, imported where used
+    pub type_map: TypeMap,
+    pub style: CodeStyle,
 }
 
 impl BackwardsGenerate for KotlinGenerator {
@@ -19,24 +52,38 @@ impl BackwardsGenerate for KotlinGenerator {
             let trimmed = lines[i].trim();
 
             if trimmed.starts_with("enum class ") && trimmed.ends_with('{') {
-                let name = trimmed
-                    .strip_prefix("enum class ")
-                    .unwrap()
-                    .trim_end_matches(|c: char| c == '{' || c == ' ')
-                    .to_string();
+                let after = trimmed.strip_prefix("enum class ").unwrap();
+                let name = after[..after.find(['(', '{', ' ']).unwrap_or(after.len())].to_string();
                 let mut vars = Vec::new();
+                // the variants end at a `;` where members such as fromValue() follow
+                let mut variants_done = false;
+                let mut depth = 0usize;
                 i += 1;
                 while i < lines.len() {
                     let line = lines[i].trim();
-                    if line == "}" { break; }
-                    let variant = line.trim_end_matches(',').trim().to_string();
+                    if line == "}" && depth == 0 { break; }
+                    if variants_done {
+                        depth += line.matches('{').count();
+                        depth = depth.saturating_sub(line.matches('}').count());
+                        i += 1;
+                        continue;
+                    }
+                    variants_done = line.ends_with(';');
+                    let variant = line.trim_end_matches([',', ';']).trim();
+                    // RED(1) for a numbered variant
+                    let (variant, value) = match variant.split_once('(') {
+                        Some((variant, value)) => (variant, value.strip_suffix(')').map(str::to_string)),
+                        None => (variant, None),
+                    };
                     if !variant.is_empty() {
                         vars.push(Variable {
                             var_mod: vec![],
                             visibility: VariableVisibility::PUBLIC,
                             var_type: "string".to_string(),
                             array_kind: ArrayKind::None,
-                            name: variant,
+                            name: variant.to_string(),
+                            value,
+                            ..Default::default()
                         });
                     }
                     i += 1;
@@ -45,6 +92,7 @@ impl BackwardsGenerate for KotlinGenerator {
                     oml_type: ObjectType::ENUM,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
             } else if (trimmed.starts_with("data class ") || trimmed.starts_with("class "))
                 && (trimmed.contains('(') || trimmed.ends_with('{'))
@@ -52,7 +100,7 @@ impl BackwardsGenerate for KotlinGenerator {
                 let is_data = trimmed.starts_with("data class ");
                 let prefix = if is_data { "data class " } else { "class " };
                 let after = trimmed.strip_prefix(prefix).unwrap();
-                let name_end = after.find(|c: char| c == '(' || c == '{' || c == ' ').unwrap_or(after.len());
+                let name_end = after.find(['(', '{', ' ']).unwrap_or(after.len());
                 let name = after[..name_end].trim().to_string();
 
                 let mut vars = Vec::new();
@@ -87,19 +135,22 @@ impl BackwardsGenerate for KotlinGenerator {
                         i += 1;
                         continue;
                     }
-                    if in_companion {
-                        if let Some(var) = parse_kotlin_companion_var(line) {
-                            vars.push(var);
-                        }
+                    if in_companion
+                        // the generated SCHEMA_HASH constant
+                        && !line.starts_with(&format!("const val {}:", SCHEMA_HASH))
+                        && let Some(var) = parse_kotlin_companion_var(line)
+                    {
+                        vars.push(var);
                     }
                     i += 1;
                 }
 
-                let oml_type = if is_data { ObjectType::CLASS } else { ObjectType::CLASS };
+                let oml_type = ObjectType::CLASS;
                 objects.push(OmlObject {
                     oml_type,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
             }
             i += 1;
@@ -128,10 +179,10 @@ fn parse_kotlin_type_annotation(type_str: &str) -> (String, ArrayKind, bool) {
     let type_str = type_str.trim();
 
     // Optional: "Type? = null" or "Type?"
-    let (type_str, is_optional) = if type_str.ends_with("? = null") {
-        (&type_str[..type_str.len() - 8], true)
-    } else if type_str.ends_with('?') {
-        (&type_str[..type_str.len() - 1], true)
+    let (type_str, is_optional) = if let Some(stripped) = type_str.strip_suffix("? = null") {
+        (stripped, true)
+    } else if let Some(stripped) = type_str.strip_suffix('?') {
+        (stripped, true)
     } else {
         (type_str, false)
     };
@@ -142,9 +193,12 @@ fn parse_kotlin_type_annotation(type_str: &str) -> (String, ArrayKind, bool) {
         return (reverse_kotlin_type(inner), ArrayKind::Static(0), is_optional);
     }
 
-    // MutableList<T>
-    if type_str.starts_with("MutableList<") && type_str.ends_with('>') {
-        let inner = &type_str[12..type_str.len() - 1];
+    // MutableList<T>, or List<T> from --kotlin-immutable
+    if let Some(inner) = type_str
+        .strip_prefix("MutableList<")
+        .or_else(|| type_str.strip_prefix("List<"))
+        .and_then(|rest| rest.strip_suffix('>'))
+    {
         return (reverse_kotlin_type(inner), ArrayKind::Dynamic, is_optional);
     }
 
@@ -155,6 +209,12 @@ fn parse_kotlin_param(line: &str) -> Option<Variable> {
     let line = line.trim().trim_end_matches(',');
     if line.is_empty() { return None; }
 
+    // a backing property's @SerialName is the field's own name
+    let (serial_name, line) = match line.strip_prefix("@SerialName(\"").and_then(|r| r.split_once("\") ")) {
+        Some((serial_name, rest)) => (Some(serial_name), rest),
+        None => (None, line),
+    };
+
     let mut visibility = VariableVisibility::PUBLIC;
     let mut rest = line;
 
@@ -180,10 +240,10 @@ fn parse_kotlin_param(line: &str) -> Option<Variable> {
     }
     let _ = is_val;
 
-    // "name: Type" or "name: Type? = null"
+    // "name: Type", "name: Type? = null" or "name: Type = value"
     let colon_pos = rest.find(':')?;
-    let name = rest[..colon_pos].trim().to_string();
-    let type_str = rest[colon_pos + 1..].trim();
+    let name = serial_name.unwrap_or(rest[..colon_pos].trim()).to_string();
+    let (type_str, value) = split_default(rest[colon_pos + 1..].trim());
 
     let (var_type, array_kind, is_optional) = parse_kotlin_type_annotation(type_str);
     if is_optional {
@@ -193,18 +253,38 @@ fn parse_kotlin_param(line: &str) -> Option<Variable> {
     Some(Variable {
         var_mod,
         visibility,
+        value: value.map(|value| oml_value(&var_type, value)),
         var_type,
         array_kind,
         name,
+        ..Default::default()
     })
 }
 
+/// Splits `Type = value` into the type and the value, if it is not `null`.
+fn split_default(type_str: &str) -> (&str, Option<&str>) {
+    match type_str.split_once(" = ") {
+        Some((type_str, "null")) => (type_str, None),
+        Some((type_str, value)) => (type_str, Some(value)),
+        None => (type_str, None),
+    }
+}
+
+/// A Kotlin literal written by `kotlin_literal` as the OML value.
+fn oml_value(var_type: &str, literal: &str) -> String {
+    match var_type {
+        "int64" | "uint32" | "uint64" | "float" => literal.trim_end_matches(['L', 'u', 'f']).to_string(),
+        "string" => literal.replace("\\$", "$"),
+        _ => literal.to_string(),
+    }
+}
+
 fn parse_kotlin_companion_var(line: &str) -> Option<Variable> {
     let line = line.trim();
     if line.is_empty() { return None; }
 
     let mut var_mod = vec![VariableModifier::STATIC];
-    let mut rest = line;
+    let mut rest = line.strip_prefix("const ").unwrap_or(line);
 
     if rest.starts_with("val ") {
         var_mod.push(VariableModifier::CONST);
@@ -217,7 +297,7 @@ fn parse_kotlin_companion_var(line: &str) -> Option<Variable> {
 
     let colon_pos = rest.find(':')?;
     let name = rest[..colon_pos].trim().to_string();
-    let type_str = rest[colon_pos + 1..].trim();
+    let (type_str, value) = split_default(rest[colon_pos + 1..].trim());
 
     let (var_type, array_kind, is_optional) = parse_kotlin_type_annotation(type_str);
     if is_optional {
@@ -227,74 +307,257 @@ fn parse_kotlin_companion_var(line: &str) -> Option<Variable> {
     Some(Variable {
         var_mod,
         visibility: VariableVisibility::PRIVATE,
+        value: value.map(|value| oml_value(&var_type, value)),
         var_type,
         array_kind,
         name,
+        ..Default::default()
     })
 }
 
 impl KotlinGenerator {
     pub fn new(use_data_class: bool) -> Self {
-        Self { use_data_class }
+        Self { use_data_class, package: None, serializable: false, accessors: false, immutable: false, tests: false, equality: false, to_string: false, builder: false, schema_hash: false, type_map: TypeMap::default(), style: CodeStyle::default() }
+    }
+
+    pub fn with_package(mut self, package: Option<&str>) -> Self {
+        self.package = package.map(str::to_string);
+        self
+    }
+
+    pub fn with_serializable(mut self, serializable: bool) -> Self {
+        self.serializable = serializable;
+        self
+    }
+
+    pub fn with_accessors(mut self, accessors: bool) -> Self {
+        self.accessors = accessors;
+        self
+    }
+
+    pub fn with_immutable(mut self, immutable: bool) -> Self {
+        self.immutable = immutable;
+        self
+    }
+
+    pub fn with_tests(mut self, tests: bool) -> Self {
+        self.tests = tests;
+        self
+    }
+
+    pub fn with_equality(mut self, equality: bool) -> Self {
+        self.equality = equality;
+        self
+    }
+
+    pub fn with_to_string(mut self, to_string: bool) -> Self {
+        self.to_string = to_string;
+        self
+    }
+
+    pub fn with_builder(mut self, builder: bool) -> Self {
+        self.builder = builder;
+        self
+    }
+
+    pub fn with_schema_hash(mut self, schema_hash: bool) -> Self {
+        self.schema_hash = schema_hash;
+        self
+    }
+
+    pub fn with_type_map(mut self, type_map: TypeMap) -> Self {
+        self.type_map = type_map;
+        self
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
     }
 }
 
+/// `name` without the backticks escaping a keyword, for the names built
+/// from it and the text that mentions it.
+fn unescaped(name: &str) -> &str {
+    name.trim_matches('`')
+}
+
+/// Words reserved in Kotlin, which fields are renamed away from.
+const KEYWORDS: [&str; 28] = [
+    "as", "break", "class", "continue", "do", "else", "false", "for", "fun", "if", "in", "interface",
+    "is", "null", "object", "package", "return", "super", "this", "throw", "true", "try", "typealias",
+    "typeof", "val", "var", "when", "while",
+];
+
 impl Generate for KotlinGenerator {
     fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
-        let mut kt_file = String::new();
+        let mut kt_file = CodeWriter::new(self.style);
 
         writeln!(kt_file, "// This file has been generated from {}.oml", file_name)?;
         writeln!(kt_file)?;
+        if let Some(package) = &self.package {
+            writeln!(kt_file, "package {}", package)?;
+            writeln!(kt_file)?;
+        }
+        let mut imports: Vec<String> = Vec::new();
+        if self.serializable && !oml_objects.is_empty() {
+            let backed = |v: &Variable| v.visibility == VariableVisibility::PRIVATE && !v.var_mod.contains(&VariableModifier::STATIC);
+            if self.accessors && oml_objects.iter().any(|o| o.oml_type != ObjectType::ENUM && o.variables.iter().any(backed)) {
+                imports.push("kotlinx.serialization.SerialName".to_string());
+            }
+            imports.push("kotlinx.serialization.Serializable".to_string());
+        }
+        for type_override in self.type_map.used_includes(oml_objects) {
+            imports.extend(type_override.include.clone());
+        }
+        for import in &imports {
+            writeln!(kt_file, "import {}", import)?;
+        }
+        if !imports.is_empty() {
+            writeln!(kt_file)?;
+        }
+
+        // fingerprints are taken before the target's types are substituted
+        let schema_hashes: Vec<u64> = oml_objects.iter().map(schema_hash).collect();
+        let objects = self.type_map.apply(oml_objects);
+        let oml_objects = objects.as_slice();
 
         for (i, oml_object) in oml_objects.iter().enumerate() {
+            let schema_hash = self.schema_hash.then_some(schema_hashes[i]);
+            // optional fields already default to null, which the plugin
+            // treats as optional when decoding
+            if self.serializable && oml_object.oml_type != ObjectType::UNDECIDED {
+                writeln!(kt_file, "@Serializable")?;
+            }
             match &oml_object.oml_type {
                 ObjectType::ENUM => generate_enum(oml_object, &mut kt_file)?,
-                ObjectType::CLASS => generate_class(oml_object, &mut kt_file, self.use_data_class)?,
-                ObjectType::STRUCT => generate_class(oml_object, &mut kt_file, true)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_class(
+                    oml_object,
+                    schema_hash,
+                    &mut kt_file,
+                    self.use_data_class || oml_object.oml_type == ObjectType::STRUCT,
+                    self.accessors,
+                    self.immutable,
+                    self.serializable,
+                    self.equality,
+                    self.to_string,
+                )?,
                 ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
             }
+            if self.builder && oml_object.oml_type != ObjectType::ENUM {
+                write_builder(oml_object, &mut kt_file, self.accessors, self.immutable)?;
+            }
             if i < oml_objects.len() - 1 {
                 writeln!(kt_file)?;
             }
         }
 
-        Ok(kt_file)
+        Ok(kt_file.finish())
     }
 
     fn extension(&self) -> &str {
         "kt"
     }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    /// Backticks, which let Kotlin use a keyword as a name.
+    fn escape_keyword(&self, keyword: &str) -> String {
+        format!("`{}`", keyword)
+    }
+
+    fn is_identifier(&self, name: &str) -> bool {
+        utils::is_identifier(unescaped(name))
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Camel,
+            constants: Case::ScreamingSnake,
+            enum_values: Case::ScreamingSnake,
+        }
+    }
+
+    /// Packaged files go in the directory tree matching the package, as
+    /// Gradle and the Kotlin style guide expect.
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let content = self.generate(oml_objects, file_name)?;
+        let mut path = std::path::PathBuf::new();
+        if let Some(package) = &self.package {
+            path.extend(package.split('.'));
+        }
+        path.push(format!("{}.{}", file_name, self.extension()));
+        let mut files = vec![GeneratedFile::new(path, content)];
+        if self.tests {
+            files.extend(self.generate_tests(oml_objects, file_name)?);
+        }
+        Ok(files)
+    }
 }
 
-fn generate_enum(oml_object: &OmlObject, kt_file: &mut String) -> Result<(), std::fmt::Error> {
-    writeln!(kt_file, "enum class {} {{", oml_object.name)?;
+fn generate_enum(oml_object: &OmlObject, kt_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    // numbered variants carry their number and can be looked up by it
+    let numbered = oml_object.variables.iter().any(|v| v.value.is_some());
+    if numbered {
+        kt_file.begin_block(&format!("enum class {}(val value: Int)", oml_object.name))?;
+    } else {
+        kt_file.begin_block(&format!("enum class {}", oml_object.name))?;
+    }
     let length = oml_object.variables.len();
 
     for (index, var) in oml_object.variables.iter().enumerate() {
-        write!(kt_file, "\t{}", var.name.to_uppercase())?;
-        if index == length - 1 {
-            writeln!(kt_file)?;
-        } else {
-            writeln!(kt_file, ",")?;
+        let separator = match (index == length - 1, numbered) {
+            (false, _) => ",",
+            (true, true) => ";",
+            (true, false) => "",
+        };
+        match &var.value {
+            Some(value) => kt_file.line(&format!("{}({}){}", var.name.to_uppercase(), value, separator))?,
+            None => kt_file.line(&format!("{}{}", var.name.to_uppercase(), separator))?,
         }
     }
 
-    writeln!(kt_file, "}}")?;
+    if numbered {
+        kt_file.line("")?;
+        kt_file.begin_block("companion object")?;
+        kt_file.line(&format!(
+            "fun fromValue(value: Int): {}? = entries.firstOrNull {{ it.value == value }}",
+            oml_object.name
+        ))?;
+        kt_file.end_block("}")?;
+    }
+
+    kt_file.end_block("}")?;
 
     Ok(())
 }
 
+/// Whether `var` is a `val`: everything with `--kotlin-immutable`, otherwise
+/// what is `const` and not `mut`.
+pub(super) fn is_read_only(var: &Variable, immutable: bool) -> bool {
+    immutable || var.is_const()
+}
+
+/// Writes one class; `schema_hash` is the fingerprint to embed, if any.
+#[allow(clippy::too_many_arguments)]
 fn generate_class(
     oml_object: &OmlObject,
-    kt_file: &mut String,
+    schema_hash: Option<u64>,
+    kt_file: &mut CodeWriter,
     use_data_class: bool,
+    accessors: bool,
+    immutable: bool,
+    serializable: bool,
+    equality: bool,
+    to_string: bool,
 ) -> Result<(), std::fmt::Error> {
-    let class_keyword = if use_data_class { "data class" } else { "class" };
-
     let all_vars: Vec<&Variable> = oml_object.variables.iter().collect();
 
-    if all_vars.is_empty() {
-        writeln!(kt_file, "{} {}", class_keyword, oml_object.name)?;
+    if all_vars.is_empty() && schema_hash.is_none() {
+        writeln!(kt_file, "class {}", oml_object.name)?;
         return Ok(());
     }
 
@@ -311,67 +574,309 @@ fn generate_class(
         .copied()
         .collect();
 
-    if instance_vars.is_empty() && !static_vars.is_empty() {
+    // a data class needs a property in its primary constructor
+    let is_data_class = (use_data_class || immutable) && !instance_vars.is_empty();
+    let class_keyword = if is_data_class { "data class" } else { "class" };
+
+    let accessor_vars: Vec<&Variable> = instance_vars
+        .iter()
+        .filter(|v| accessors && v.visibility == VariableVisibility::PRIVATE)
+        .copied()
+        .collect();
+    let equality = equality && !is_data_class && !instance_vars.is_empty();
+    let to_string = to_string && !is_data_class && !instance_vars.is_empty();
+    let copy_with = immutable && !instance_vars.is_empty();
+    let has_companion = !static_vars.is_empty() || schema_hash.is_some();
+    let has_body = has_companion || !accessor_vars.is_empty() || equality || to_string || copy_with;
+
+    if copy_with {
+        kt_file.line("// Immutable: derive changed values with copy() or the with...() functions")?;
+    }
+    if instance_vars.is_empty() && has_companion {
         // Only static vars, no primary constructor params
-        writeln!(kt_file, "{} {} {{", class_keyword, oml_object.name)?;
+        kt_file.begin_block(&format!("{} {}", class_keyword, oml_object.name))?;
     } else {
         // Write class header with primary constructor
-        writeln!(kt_file, "{}{}(", class_keyword, format!(" {}", oml_object.name))?;
-        write_constructor_params(&instance_vars, kt_file)?;
-        write!(kt_file, ")")?;
+        kt_file.line(&format!("{} {}(", class_keyword, oml_object.name))?;
+        kt_file.indented(|kt_file| write_constructor_params(&instance_vars, kt_file, accessors, immutable, serializable))?;
 
-        if static_vars.is_empty() {
-            writeln!(kt_file)?;
+        if has_body {
+            kt_file.begin_block(")")?;
         } else {
-            writeln!(kt_file, " {{")?;
+            kt_file.line(")")?;
         }
     }
 
+    // Sections of the body are separated by blank lines
+    let mut separate = false;
+    for var in &accessor_vars {
+        write_accessor_property(var, kt_file, immutable)?;
+        separate = true;
+    }
+
+    if copy_with {
+        if separate {
+            kt_file.line("")?;
+        }
+        write_copy_with(&oml_object.name, &instance_vars, kt_file, accessors, immutable)?;
+        separate = true;
+    }
+
+    if equality {
+        if separate {
+            kt_file.line("")?;
+        }
+        write_equality(&oml_object.name, &instance_vars, kt_file, accessors)?;
+        separate = true;
+    }
+
+    if to_string {
+        if separate {
+            kt_file.line("")?;
+        }
+        write_to_string(&oml_object.name, &instance_vars, kt_file, accessors)?;
+        separate = true;
+    }
+
     // Companion object for static vars
-    if !static_vars.is_empty() {
-        writeln!(kt_file, "\tcompanion object {{")?;
+    if has_companion {
+        if separate {
+            kt_file.line("")?;
+        }
+        kt_file.begin_block("companion object")?;
+        if let Some(hash) = schema_hash {
+            kt_file.line(&format!("const val {}: ULong = 0x{:016x}uL", SCHEMA_HASH, hash))?;
+        }
         for var in &static_vars {
-            write_static_property(var, kt_file)?;
+            write_static_property(var, kt_file, immutable)?;
         }
-        writeln!(kt_file, "\t}}")?;
-        writeln!(kt_file, "}}")?;
+        kt_file.end_block("}")?;
+    }
+
+    if has_body {
+        kt_file.end_block("}")?;
     }
 
     Ok(())
 }
 
-fn write_constructor_params(
+/// The public face of a private field kept in its `_name` backing property,
+/// mirroring the getters and setters of the C++ generator.
+fn write_accessor_property(
+    var: &Variable,
+    kt_file: &mut CodeWriter,
+    immutable: bool,
+) -> Result<(), std::fmt::Error> {
+    let read_only = is_read_only(var, immutable);
+    let mut kt_type = type_annotation(&var.var_type, &var.array_kind, immutable);
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        kt_type.push('?');
+    }
+
+    let keyword = if read_only { "val" } else { "var" };
+    kt_file.line(&format!("{} {}: {}", keyword, var.name, kt_type))?;
+    kt_file.indented(|kt_file| {
+        kt_file.line(&format!("get() = _{}", unescaped(&var.name)))?;
+        if !read_only {
+            kt_file.line(&format!("set(value) {{ _{} = value }}", unescaped(&var.name)))?;
+        }
+        Ok(())
+    })
+}
+
+/// `withName(value)` for every field of an immutable class, returning a
+/// copy with that one field changed.
+fn write_copy_with(
+    name: &str,
     vars: &[&Variable],
-    kt_file: &mut String,
+    kt_file: &mut CodeWriter,
+    accessors: bool,
+    immutable: bool,
 ) -> Result<(), std::fmt::Error> {
-    let required_vars: Vec<&&Variable> = vars
-        .iter()
-        .filter(|v| !v.var_mod.contains(&VariableModifier::OPTIONAL))
-        .collect();
+    for var in vars {
+        let mut kt_type = type_annotation(&var.var_type, &var.array_kind, immutable);
+        if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            kt_type.push('?');
+        }
+        kt_file.line(&format!(
+            "fun with{}(value: {}): {} = copy({} = value)",
+            to_pascal_case(unescaped(&var.name)), kt_type, name, property_name(var, accessors)
+        ))?;
+    }
+    Ok(())
+}
 
-    let optional_vars: Vec<&&Variable> = vars
-        .iter()
-        .filter(|v| v.var_mod.contains(&VariableModifier::OPTIONAL))
-        .collect();
+/// A mutable builder whose required properties `build()` checks with
+/// `requireNotNull`, passing everything to the constructor by name, and
+/// a DSL function applying a block to it.
+fn write_builder(
+    oml_object: &OmlObject,
+    kt_file: &mut CodeWriter,
+    accessors: bool,
+    immutable: bool,
+) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let fields = builder_fields(oml_object);
+    if fields.is_empty() {
+        return Ok(());
+    }
 
-    // Required params first, then optional params (with defaults)
-    let total = required_vars.len() + optional_vars.len();
-    let mut index = 0;
+    kt_file.line("")?;
+    kt_file.begin_block(&format!("class {}Builder", name))?;
+    for BuilderField { var, .. } in &fields {
+        // fields with a value start from it rather than unset
+        match &var.value {
+            Some(value) => kt_file.line(&format!(
+                "var {}: {} = {}",
+                var.name, declared_type(var, immutable), kotlin_literal(&var.var_type, value)
+            ))?,
+            None => kt_file.line(&format!("var {}: {}? = null", var.name, type_annotation(&var.var_type, &var.array_kind, immutable)))?,
+        }
+    }
+    kt_file.line("")?;
+    kt_file.line(&format!("fun build(): {} = {}(", name, name))?;
+    kt_file.indented(|kt_file| {
+        for BuilderField { var, required } in &fields {
+            let value = if *required && var.value.is_none() {
+                format!("requireNotNull({}) {{ \"{}.{} is not set\" }}", var.name, name, unescaped(&var.name))
+            } else {
+                var.name.clone()
+            };
+            kt_file.line(&format!("{} = {},", property_name(var, accessors), value))?;
+        }
+        Ok(())
+    })?;
+    kt_file.line(")")?;
+    kt_file.end_block("}")?;
+    kt_file.line("")?;
+    kt_file.line(&format!(
+        "fun {}(block: {}Builder.() -> Unit): {} = {}Builder().apply(block).build()",
+        to_camel_case(name), name, name, name
+    ))
+}
 
-    for var in &required_vars {
-        write_property_param(var, kt_file, false)?;
-        index += 1;
-        if index < total {
-            writeln!(kt_file, ",")?;
-        } else {
-            writeln!(kt_file)?;
+/// The constructor property holding `var`, which is the `_name` backing
+/// property for private fields with accessors.
+fn property_name(var: &Variable, accessors: bool) -> String {
+    if accessors && var.visibility == VariableVisibility::PRIVATE {
+        format!("_{}", unescaped(&var.name))
+    } else {
+        var.name.clone()
+    }
+}
+
+/// `toString` in the format of data classes, `Name(field=value, ...)`,
+/// printing arrays by content.
+fn write_to_string(
+    name: &str,
+    vars: &[&Variable],
+    kt_file: &mut CodeWriter,
+    accessors: bool,
+) -> Result<(), std::fmt::Error> {
+    let fields: Vec<String> = vars.iter().map(|var| {
+        let property = property_name(var, accessors);
+        match var.array_kind {
+            ArrayKind::Static(_) => format!("{}=${{{}.contentToString()}}", unescaped(&var.name), property),
+            _ => format!("{}=${{{}}}", unescaped(&var.name), property),
+        }
+    }).collect();
+
+    let single_line = format!("override fun toString(): String = \"{}({})\"", name, fields.join(", "));
+    if kt_file.fits_line(&single_line) {
+        return kt_file.line(&single_line);
+    }
+    kt_file.line(&format!("override fun toString(): String = \"{}(\" +", name))?;
+    kt_file.indented(|kt_file| {
+        for (i, field) in fields.iter().enumerate() {
+            let separator = if i + 1 < fields.len() { ", " } else { "" };
+            kt_file.line(&format!("\"{}{}\" +", field, separator))?;
+        }
+        kt_file.line("\")\"")
+    })
+}
+
+/// `equals` and `hashCode` over every instance field, comparing arrays
+/// by content.
+fn write_equality(
+    name: &str,
+    vars: &[&Variable],
+    kt_file: &mut CodeWriter,
+    accessors: bool,
+) -> Result<(), std::fmt::Error> {
+    let property = |var: &Variable| property_name(var, accessors);
+
+
+    let comparisons: Vec<String> = vars.iter().map(|var| {
+        let property = property(var);
+        match var.array_kind {
+            ArrayKind::Static(_) => format!("{}.contentEquals(other.{})", property, property),
+            _ => format!("{} == other.{}", property, property),
         }
+    }).collect();
+    kt_file.begin_block("override fun equals(other: Any?): Boolean")?;
+    kt_file.line("if (this === other) return true")?;
+    kt_file.line(&format!("if (other !is {}) return false", name))?;
+    let single_line = format!("return {}", comparisons.join(" && "));
+    if kt_file.fits_line(&single_line) {
+        kt_file.line(&single_line)?;
+    } else {
+        // a line break before `&&` would end the statement
+        kt_file.line(&format!("return {} &&", comparisons[0]))?;
+        kt_file.indented(|kt_file| {
+            for (i, comparison) in comparisons[1..].iter().enumerate() {
+                let and = if i + 2 < comparisons.len() { " &&" } else { "" };
+                kt_file.line(&format!("{}{}", comparison, and))?;
+            }
+            Ok(())
+        })?;
     }
+    kt_file.end_block("}")?;
+    kt_file.line("")?;
+
+    let hashed: Vec<String> = vars.iter().map(|var| {
+        let property = property(var);
+        match var.array_kind {
+            ArrayKind::Static(_) => format!("{}.contentHashCode()", property),
+            _ => property,
+        }
+    }).collect();
+    let single_line = format!("override fun hashCode(): Int = listOf({}).hashCode()", hashed.join(", "));
+    if kt_file.fits_line(&single_line) {
+        return kt_file.line(&single_line);
+    }
+    kt_file.line("override fun hashCode(): Int = listOf(")?;
+    kt_file.indented(|kt_file| {
+        for hash in &hashed {
+            kt_file.line(&format!("{},", hash))?;
+        }
+        Ok(())
+    })?;
+    kt_file.line(").hashCode()")
+}
+
+/// The primary constructor's params: those with a default (a value, or null
+/// when optional) come last so that callers can leave them out.
+pub(super) fn constructor_order<'a>(vars: &[&'a Variable]) -> Vec<&'a Variable> {
+    let (required, defaulted): (Vec<&Variable>, Vec<&Variable>) = vars.iter().partition(|v| !has_default(v));
+    required.into_iter().chain(defaulted).collect()
+}
+
+/// Whether the constructor param of `var` can be left out.
+pub(super) fn has_default(var: &Variable) -> bool {
+    var.value.is_some() || var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
 
-    for var in &optional_vars {
-        write_property_param(var, kt_file, true)?;
-        index += 1;
-        if index < total {
+fn write_constructor_params(
+    vars: &[&Variable],
+    kt_file: &mut CodeWriter,
+    accessors: bool,
+    immutable: bool,
+    serializable: bool,
+) -> Result<(), std::fmt::Error> {
+    let params = constructor_order(vars);
+    for (index, var) in params.iter().enumerate() {
+        write_property_param(var, kt_file, accessors, immutable, serializable)?;
+        if index + 1 < params.len() {
             writeln!(kt_file, ",")?;
         } else {
             writeln!(kt_file)?;
@@ -383,10 +888,18 @@ fn write_constructor_params(
 
 fn write_property_param(
     var: &Variable,
-    kt_file: &mut String,
-    is_optional: bool,
+    kt_file: &mut CodeWriter,
+    accessors: bool,
+    immutable: bool,
+    serializable: bool,
 ) -> Result<(), std::fmt::Error> {
-    write!(kt_file, "\t")?;
+    write!(kt_file, "{}", kt_file.current_indent())?;
+
+    // backing properties are serialized under the field's own name
+    let property = property_name(var, accessors);
+    if serializable && property != var.name {
+        write!(kt_file, "@SerialName(\"{}\") ", unescaped(&var.name))?;
+    }
 
     // Visibility modifier (public is default, so we omit it)
     match var.visibility {
@@ -396,21 +909,15 @@ fn write_property_param(
     }
 
     // val for const, var for mutable
-    if var.var_mod.contains(&VariableModifier::CONST)
-        && !var.var_mod.contains(&VariableModifier::MUT) {
+    if is_read_only(var, immutable) {
         write!(kt_file, "val ")?;
     } else {
         write!(kt_file, "var ")?;
     }
 
-    let kt_type = type_annotation(&var.var_type, &var.array_kind);
-
-    write!(kt_file, "{}: ", var.name)?;
-
-    if is_optional {
-        write!(kt_file, "{}? = null", kt_type)?;
-    } else {
-        write!(kt_file, "{}", kt_type)?;
+    write!(kt_file, "{}: {}", property, declared_type(var, immutable))?;
+    if let Some(default) = default_value(var) {
+        write!(kt_file, " = {}", default)?;
     }
 
     Ok(())
@@ -418,27 +925,59 @@ fn write_property_param(
 
 fn write_static_property(
     var: &Variable,
-    kt_file: &mut String,
+    kt_file: &mut CodeWriter,
+    immutable: bool,
 ) -> Result<(), std::fmt::Error> {
-    write!(kt_file, "\t\t")?;
+    write!(kt_file, "{}", kt_file.current_indent())?;
 
-    // Static const → const val, static mutable → var
-    if var.var_mod.contains(&VariableModifier::CONST)
-        && !var.var_mod.contains(&VariableModifier::MUT) {
+    // Static const → const val when its value is known, static mutable → var
+    let read_only = is_read_only(var, immutable);
+    if read_only && var.value.is_some() && !var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        write!(kt_file, "const val ")?;
+    } else if read_only {
         write!(kt_file, "val ")?;
     } else {
         write!(kt_file, "var ")?;
     }
 
-    let kt_type = type_annotation(&var.var_type, &var.array_kind);
+    write!(kt_file, "{}: {}", var.name, declared_type(var, immutable))?;
+    match default_value(var) {
+        Some(default) => writeln!(kt_file, " = {}", default),
+        None => writeln!(kt_file),
+    }
+}
 
+/// The type of the property holding `var`, nullable when it is optional.
+fn declared_type(var: &Variable, immutable: bool) -> String {
+    let kt_type = type_annotation(&var.var_type, &var.array_kind, immutable);
     if var.var_mod.contains(&VariableModifier::OPTIONAL) {
-        writeln!(kt_file, "{}: {}? = null", var.name, kt_type)?;
+        format!("{}?", kt_type)
     } else {
-        writeln!(kt_file, "{}: {}", var.name, kt_type)?;
+        kt_type
     }
+}
 
-    Ok(())
+/// What `var` starts as when not given: its value, or null when optional.
+fn default_value(var: &Variable) -> Option<String> {
+    match &var.value {
+        Some(value) => Some(kotlin_literal(&var.var_type, value)),
+        None if var.var_mod.contains(&VariableModifier::OPTIONAL) => Some("null".to_string()),
+        None => None,
+    }
+}
+
+/// An OML `value` as a Kotlin literal of the type `var_type` converts to:
+/// numbers take the suffix of their type and `$` is escaped in strings.
+fn kotlin_literal(var_type: &str, value: &str) -> String {
+    match var_type {
+        "int64" => format!("{}L", value),
+        "uint8" | "uint16" | "uint32" => format!("{}u", value),
+        "uint64" => format!("{}uL", value),
+        "float" => format!("{}f", value),
+        "double" if !value.contains('.') => format!("{}.0", value),
+        "string" => value.replace('$', "\\$"),
+        _ => value.to_string(),
+    }
 }
 
 #[inline]
@@ -457,11 +996,14 @@ fn convert_type(var_type: &str) -> String {
     }
 }
 
-fn type_annotation(var_type: &str, array_kind: &ArrayKind) -> String {
+/// The Kotlin type of a field; lists are read-only `List`s with
+/// `--kotlin-immutable`.
+fn type_annotation(var_type: &str, array_kind: &ArrayKind, immutable: bool) -> String {
     let base = convert_type(var_type);
     match array_kind {
         ArrayKind::None => base,
         ArrayKind::Static(_) => format!("Array<{}>", base),
+        ArrayKind::Dynamic if immutable => format!("List<{}>", base),
         ArrayKind::Dynamic => format!("MutableList<{}>", base),
     }
 }
@@ -496,6 +1038,7 @@ mod tests {
                     var_type: "".to_string(),
                     array_kind: ArrayKind::None,
                     name: "Red".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -503,6 +1046,7 @@ mod tests {
                     var_type: "".to_string(),
                     array_kind: ArrayKind::None,
                     name: "Green".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -510,8 +1054,10 @@ mod tests {
                     var_type: "".to_string(),
                     array_kind: ArrayKind::None,
                     name: "Blue".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Color").unwrap();
@@ -535,8 +1081,10 @@ mod tests {
                     var_type: "".to_string(),
                     array_kind: ArrayKind::None,
                     name: "Only".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Single").unwrap();
@@ -551,6 +1099,7 @@ mod tests {
             oml_type: ObjectType::ENUM,
             name: "Empty".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Empty").unwrap();
@@ -572,6 +1121,7 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "name".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -579,8 +1129,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "age".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Person").unwrap();
@@ -601,6 +1153,7 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "name".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -608,8 +1161,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "age".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin_no_data(&oml_object, "Person").unwrap();
@@ -631,6 +1186,7 @@ mod tests {
                     var_type: "double".to_string(),
                     array_kind: ArrayKind::None,
                     name: "x".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -638,8 +1194,10 @@ mod tests {
                     var_type: "double".to_string(),
                     array_kind: ArrayKind::None,
                     name: "y".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         // Even with no-data-class, structs should be data class
@@ -653,10 +1211,12 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "Empty".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Empty").unwrap();
-        assert!(output.contains("data class Empty"));
+        assert!(output.contains("class Empty"));
+        assert!(!output.contains("data class"));
         assert!(!output.contains("("));
     }
 
@@ -672,6 +1232,7 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "name".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![VariableModifier::OPTIONAL],
@@ -679,6 +1240,7 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "email".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![VariableModifier::OPTIONAL],
@@ -686,8 +1248,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "age".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "User").unwrap();
@@ -708,6 +1272,7 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "optional_first".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -715,8 +1280,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "required".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Mixed").unwrap();
@@ -738,8 +1305,10 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "name".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Config").unwrap();
@@ -759,8 +1328,10 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "name".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Config").unwrap();
@@ -779,8 +1350,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "value".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Config").unwrap();
@@ -800,6 +1373,7 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "name".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![VariableModifier::STATIC],
@@ -807,8 +1381,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "count".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Config").unwrap();
@@ -828,8 +1404,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "MAX".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Constants").unwrap();
@@ -849,8 +1427,10 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "instance".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Config").unwrap();
@@ -872,8 +1452,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "x".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Foo").unwrap();
@@ -894,8 +1476,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "x".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Foo").unwrap();
@@ -914,8 +1498,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "x".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Foo").unwrap();
@@ -934,6 +1520,7 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "pub_val".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -941,6 +1528,7 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "prot_val".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -948,8 +1536,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "priv_val".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Mixed").unwrap();
@@ -1003,11 +1593,12 @@ mod tests {
             oml_type: ObjectType::ENUM,
             name: "Direction".to_string(),
             variables: vec![
-                Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "North".to_string() },
-                Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "South".to_string() },
-                Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "East".to_string() },
-                Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "West".to_string() },
+                Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "North".to_string(), ..Default::default() },
+                Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "South".to_string(), ..Default::default() },
+                Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "East".to_string(), ..Default::default() },
+                Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "West".to_string(), ..Default::default() },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Direction").unwrap();
@@ -1033,8 +1624,10 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "bar".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Foo").unwrap();
@@ -1050,6 +1643,7 @@ mod tests {
             oml_type: ObjectType::UNDECIDED,
             name: "Bad".to_string(),
             variables: vec![],
+            ..Default::default()
         };
 
         let result = oml_to_kotlin(&oml_object, "Bad");
@@ -1068,6 +1662,7 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "id".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![],
@@ -1075,6 +1670,7 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "count".to_string(),
+                    ..Default::default()
                 },
                 Variable {
                     var_mod: vec![VariableModifier::OPTIONAL],
@@ -1082,8 +1678,10 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "description".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Example").unwrap();
@@ -1110,6 +1708,7 @@ mod tests {
                 var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                 name: format!("var_{}", i),
+                ..Default::default()
             });
         }
 
@@ -1117,6 +1716,7 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "ManyVars".to_string(),
             variables,
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "ManyVars").unwrap();
@@ -1135,12 +1735,14 @@ mod tests {
             var_type: "".to_string(),
                     array_kind: ArrayKind::None,
             name: format!("Variant{}", i),
+            ..Default::default()
         }).collect();
 
         let oml_object = OmlObject {
             oml_type: ObjectType::ENUM,
             name: "BigEnum".to_string(),
             variables,
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "BigEnum").unwrap();
@@ -1166,6 +1768,7 @@ mod tests {
                 var_type: oml_type.to_string(),
                     array_kind: ArrayKind::None,
                 name: format!("field_{}", i),
+                ..Default::default()
             }
         }).collect();
 
@@ -1173,6 +1776,7 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "AllTypes".to_string(),
             variables,
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "AllTypes").unwrap();
@@ -1195,8 +1799,10 @@ mod tests {
                     var_type: "string".to_string(),
                     array_kind: ArrayKind::None,
                     name: "value".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Foo").unwrap();
@@ -1215,12 +1821,462 @@ mod tests {
                     var_type: "int32".to_string(),
                     array_kind: ArrayKind::None,
                     name: "everything".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = oml_to_kotlin(&oml_object, "Full").unwrap();
         assert!(output.contains("companion object {"));
         assert!(output.contains("val everything: Int? = null"));
     }
+
+    #[test]
+    fn test_package_declaration_and_directory() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Person".to_string(),
+            ..Default::default()
+        };
+
+        let files = KotlinGenerator::new(true)
+            .with_package(Some("com.example.models"))
+            .generate_files(std::slice::from_ref(&oml_object), "person")
+            .unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, std::path::PathBuf::from("com/example/models/person.kt"));
+        assert!(files[0].content.starts_with(
+            "// This file has been generated from person.oml\n\npackage com.example.models\n\nclass Person\n"
+        ), "Got: {}", files[0].content);
+    }
+
+    #[test]
+    fn test_no_package_stays_flat() {
+        let files = KotlinGenerator::new(true).generate_files(&[], "person").unwrap();
+        assert_eq!(files[0].path, std::path::PathBuf::from("person.kt"));
+        assert!(!files[0].content.contains("package"));
+    }
+
+    #[test]
+    fn test_serializable_annotations() {
+        let objects = [
+            OmlObject {
+                oml_type: ObjectType::ENUM,
+                name: "Color".to_string(),
+                variables: vec![Variable { name: "RED".to_string(), ..Default::default() }],
+                ..Default::default()
+            },
+            OmlObject {
+                oml_type: ObjectType::CLASS,
+                name: "Car".to_string(),
+                variables: vec![
+                    Variable {
+                        var_mod: vec![VariableModifier::CONST],
+                        var_type: "string".to_string(),
+                        name: "name".to_string(),
+                        ..Default::default()
+                    },
+                    Variable {
+                        var_mod: vec![VariableModifier::OPTIONAL],
+                        var_type: "Color".to_string(),
+                        name: "color".to_string(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+        ];
+
+        let result = KotlinGenerator::new(true).with_serializable(true).generate(&objects, "car").unwrap();
+
+        assert_eq!(
+            result,
+            "// This file has been generated from car.oml\n\
+             \n\
+             import kotlinx.serialization.Serializable\n\
+             \n\
+             @Serializable\n\
+             enum class Color {\n\
+             \tRED\n\
+             }\n\
+             \n\
+             @Serializable\n\
+             data class Car(\n\
+             \tprivate val name: String,\n\
+             \tprivate var color: Color? = null\n\
+             )\n"
+        );
+    }
+
+    #[test]
+    fn test_accessors_for_private_fields() {
+        let field = |name: &str, visibility: VariableVisibility, var_mod: Vec<VariableModifier>| Variable {
+            var_mod,
+            visibility,
+            var_type: "int32".to_string(),
+            array_kind: ArrayKind::None,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Account".to_string(),
+            variables: vec![
+                field("id", VariableVisibility::PRIVATE, vec![VariableModifier::CONST]),
+                field("balance", VariableVisibility::PRIVATE, vec![]),
+                field("owner", VariableVisibility::PUBLIC, vec![]),
+                field("note", VariableVisibility::PRIVATE, vec![VariableModifier::OPTIONAL]),
+                field("count", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC]),
+            ],
+            ..Default::default()
+        };
+
+        let result = KotlinGenerator::new(false)
+            .with_accessors(true)
+            .generate(std::slice::from_ref(&oml_object), "account")
+            .unwrap();
+
+        assert!(result.contains(
+            "class Account(\n\
+             \tprivate val _id: Int,\n\
+             \tprivate var _balance: Int,\n\
+             \tvar owner: Int,\n\
+             \tprivate var _note: Int? = null\n\
+             ) {\n\
+             \tval id: Int\n\
+             \t\tget() = _id\n\
+             \tvar balance: Int\n\
+             \t\tget() = _balance\n\
+             \t\tset(value) { _balance = value }\n\
+             \tvar note: Int?\n\
+             \t\tget() = _note\n\
+             \t\tset(value) { _note = value }\n\
+             \n\
+             \tcompanion object {\n\
+             \t\tvar count: Int\n\
+             \t}\n\
+             }\n"
+        ), "Got: {}", result);
+
+        // the backing properties keep the field names on the wire
+        let result = KotlinGenerator::new(false)
+            .with_accessors(true)
+            .with_serializable(true)
+            .generate(std::slice::from_ref(&oml_object), "account")
+            .unwrap();
+        assert!(result.contains("import kotlinx.serialization.SerialName\n"), "Got: {}", result);
+        assert!(result.contains(
+            "class Account(\n\
+             \t@SerialName(\"id\") private val _id: Int,\n\
+             \t@SerialName(\"balance\") private var _balance: Int,\n\
+             \tvar owner: Int,\n\
+             \t@SerialName(\"note\") private var _note: Int? = null\n\
+             ) {\n"
+        ), "Got: {}", result);
+        let reverted = KotlinGenerator::new(false).reverse(&result).unwrap();
+        let names: Vec<&str> = reverted[0].variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["id", "balance", "owner", "note", "count"]);
+    }
+
+    #[test]
+    fn test_immutable_mode() {
+        let field = |name: &str, var_mod: Vec<VariableModifier>| Variable {
+            var_mod,
+            visibility: VariableVisibility::PUBLIC,
+            var_type: "double".to_string(),
+            array_kind: ArrayKind::None,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Point".to_string(),
+            variables: vec![
+                field("x", vec![]),
+                field("y", vec![VariableModifier::CONST, VariableModifier::MUT]),
+                field("origin", vec![VariableModifier::STATIC]),
+                Variable { array_kind: ArrayKind::Dynamic, ..field("path", vec![VariableModifier::OPTIONAL]) },
+            ],
+            ..Default::default()
+        };
+
+        let result = KotlinGenerator::new(false)
+            .with_immutable(true)
+            .generate(std::slice::from_ref(&oml_object), "point")
+            .unwrap();
+
+        assert!(result.contains(
+            "// Immutable: derive changed values with copy() or the with...() functions\n\
+             data class Point(\n\
+             \tval x: Double,\n\
+             \tval y: Double,\n\
+             \tval path: List<Double>? = null\n\
+             ) {\n\
+             \tfun withX(value: Double): Point = copy(x = value)\n\
+             \tfun withY(value: Double): Point = copy(y = value)\n\
+             \tfun withPath(value: List<Double>?): Point = copy(path = value)\n\
+             \n\
+             \tcompanion object {\n\
+             \t\tval origin: Double\n\
+             \t}\n\
+             }\n"
+        ), "Got: {}", result);
+        assert!(!result.contains("MutableList"), "Got: {}", result);
+    }
+
+    #[test]
+    fn test_code_style_applies_to_companion_object() {
+        use crate::core::code_writer::{BraceStyle, CodeStyle, IndentKind};
+
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Counter".to_string(),
+            variables: vec![
+                Variable {
+                    var_mod: vec![VariableModifier::STATIC],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "int32".to_string(),
+                    array_kind: ArrayKind::None,
+                    name: "count".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let style = CodeStyle {
+            indent_kind: IndentKind::Spaces,
+            indent_width: 4,
+            brace_style: BraceStyle::NextLine,
+            ..Default::default()
+        };
+
+        let output = KotlinGenerator::new(false)
+            .with_style(style)
+            .generate(std::slice::from_ref(&oml_object), "Counter")
+            .unwrap();
+        assert!(output.contains("class Counter\n{\n    companion object\n    {\n        var count: Int\n    }\n}"), "Got: {}", output);
+    }
+
+    #[test]
+    fn test_equality_for_regular_classes() {
+        let field = |name: &str, visibility: VariableVisibility, var_mod: Vec<VariableModifier>, array_kind: ArrayKind| Variable {
+            var_mod,
+            visibility,
+            var_type: "double".to_string(),
+            array_kind,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Track".to_string(),
+            variables: vec![
+                field("length", VariableVisibility::PRIVATE, vec![], ArrayKind::None),
+                field("laps", VariableVisibility::PUBLIC, vec![], ArrayKind::Static(3)),
+                field("record", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+
+        let result = KotlinGenerator::new(false)
+            .with_accessors(true)
+            .with_equality(true)
+            .generate(std::slice::from_ref(&oml_object), "track")
+            .unwrap();
+        assert!(result.ends_with(
+            "\tvar length: Double\n\
+             \t\tget() = _length\n\
+             \t\tset(value) { _length = value }\n\
+             \n\
+             \toverride fun equals(other: Any?): Boolean {\n\
+             \t\tif (this === other) return true\n\
+             \t\tif (other !is Track) return false\n\
+             \t\treturn _length == other._length && laps.contentEquals(other.laps)\n\
+             \t}\n\
+             \n\
+             \toverride fun hashCode(): Int = listOf(_length, laps.contentHashCode()).hashCode()\n\
+             \n\
+             \tcompanion object {\n\
+             \t\tvar record: Double\n\
+             \t}\n\
+             }\n"
+        ), "Got: {}", result);
+
+        let data = KotlinGenerator::new(true)
+            .with_equality(true)
+            .generate(std::slice::from_ref(&oml_object), "track")
+            .unwrap();
+        assert!(!data.contains("override fun equals"), "Got: {}", data);
+    }
+
+    #[test]
+    fn test_to_string_for_regular_classes() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Track".to_string(),
+            variables: vec![
+                Variable { visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), name: "name".to_string(), ..Default::default() },
+                Variable {
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "double".to_string(),
+                    array_kind: ArrayKind::Static(3),
+                    name: "laps".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let result = KotlinGenerator::new(false)
+            .with_equality(true)
+            .with_to_string(true)
+            .generate(std::slice::from_ref(&oml_object), "track")
+            .unwrap();
+        assert!(result.ends_with(
+            "\toverride fun hashCode(): Int = listOf(name, laps.contentHashCode()).hashCode()\n\
+             \n\
+             \toverride fun toString(): String = \"Track(name=${name}, laps=${laps.contentToString()})\"\n\
+             }\n"
+        ), "Got: {}", result);
+    }
+
+    #[test]
+    fn test_builder_and_dsl_function() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "RaceCar".to_string(),
+            variables: vec![
+                Variable { visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), name: "name".to_string(), ..Default::default() },
+                Variable { var_type: "int32".to_string(), name: "secret".to_string(), ..Default::default() },
+                Variable {
+                    var_mod: vec![VariableModifier::OPTIONAL],
+                    visibility: VariableVisibility::PUBLIC,
+                    var_type: "string".to_string(),
+                    name: "nickname".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let result = KotlinGenerator::new(false)
+            .with_accessors(true)
+            .with_builder(true)
+            .generate(std::slice::from_ref(&oml_object), "race_car")
+            .unwrap();
+        assert!(result.ends_with(
+            "class RaceCarBuilder {\n\
+             \tvar name: String? = null\n\
+             \tvar secret: Int? = null\n\
+             \tvar nickname: String? = null\n\
+             \n\
+             \tfun build(): RaceCar = RaceCar(\n\
+             \t\tname = requireNotNull(name) { \"RaceCar.name is not set\" },\n\
+             \t\t_secret = requireNotNull(secret) { \"RaceCar.secret is not set\" },\n\
+             \t\tnickname = nickname,\n\
+             \t)\n\
+             }\n\
+             \n\
+             fun raceCar(block: RaceCarBuilder.() -> Unit): RaceCar = RaceCarBuilder().apply(block).build()\n"
+        ), "Got: {}", result);
+    }
+
+    #[test]
+    fn test_schema_hash_in_companion_object() {
+        let oml_object = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                Variable { var_type: "string".to_string(), name: "name".to_string(), ..Default::default() },
+                Variable { var_mod: vec![VariableModifier::STATIC], var_type: "int32".to_string(), name: "count".to_string(), ..Default::default() },
+            ],
+            ..Default::default()
+        };
+
+        let result = KotlinGenerator::new(false)
+            .with_schema_hash(true)
+            .generate(std::slice::from_ref(&oml_object), "car")
+            .unwrap();
+        assert!(result.ends_with(&format!(
+            "\tcompanion object {{\n\t\tconst val SCHEMA_HASH: ULong = 0x{:016x}uL\n\t\tvar count: Int\n\t}}\n}}\n",
+            schema_hash(&oml_object)
+        )), "Got: {}", result);
+        assert_eq!(KotlinGenerator::new(false).reverse(&result).unwrap()[0].variables.len(), 2);
+    }
+
+    #[test]
+    fn test_values_become_defaults() {
+        let objects = OmlObject::scan_file(concat!(
+            "class Trip {\n",
+            "public const optional int64 budget = 100;\n",
+            "public float speed = 1.5;\n",
+            "public const string name;\n",
+            "public const double ratio = 2;\n",
+            "public static const string CURRENCY = \"$\";\n",
+            "public static uint32 trips = 0;\n",
+            "}\n",
+        ).to_string()).unwrap();
+
+        let result = KotlinGenerator::new(true).with_builder(true).generate(&objects, "trip").unwrap();
+        assert!(result.contains(concat!(
+            "data class Trip(\n",
+            "\tval name: String,\n",
+            "\tval budget: Long? = 100L,\n",
+            "\tvar speed: Float = 1.5f,\n",
+            "\tval ratio: Double = 2.0\n",
+            ") {\n",
+            "\tcompanion object {\n",
+            "\t\tconst val CURRENCY: String = \"\\$\"\n",
+            "\t\tvar trips: UInt = 0u\n",
+        )), "Got: {}", result);
+        assert!(result.contains(concat!(
+            "\tvar budget: Long? = 100L\n",
+            "\tvar speed: Float = 1.5f\n",
+            "\tvar name: String? = null\n",
+            "\tvar ratio: Double = 2.0\n",
+        )), "Got: {}", result);
+        assert!(result.contains("\t\tspeed = speed,\n"), "Got: {}", result);
+
+        // the values read back as written in OML, but for the added fraction
+        let reversed = KotlinGenerator::new(true).reverse(&result).unwrap();
+        let values: Vec<_> = reversed[0].variables.iter().map(|v| (v.name.as_str(), v.value.as_deref())).collect();
+        assert_eq!(values, vec![
+            ("name", None),
+            ("budget", Some("100")),
+            ("speed", Some("1.5")),
+            ("ratio", Some("2.0")),
+            ("CURRENCY", Some("\"$\"")),
+            ("trips", Some("0")),
+        ]);
+    }
+
+    #[test]
+    fn test_numbered_enum_carries_its_values() {
+        let objects = OmlObject::scan_file("enum Status {\nOk = 200;\nMissing = 404;\n}\nenum Color {\nRed;\n}\n".to_string()).unwrap();
+
+        let result = KotlinGenerator::new(true).generate(&objects, "status").unwrap();
+        assert!(result.contains(concat!(
+            "enum class Status(val value: Int) {\n",
+            "\tOK(200),\n",
+            "\tMISSING(404);\n",
+            "\n",
+            "\tcompanion object {\n",
+            "\t\tfun fromValue(value: Int): Status? = entries.firstOrNull { it.value == value }\n",
+            "\t}\n",
+            "}\n",
+            "\n",
+            "enum class Color {\n",
+            "\tRED\n",
+            "}\n",
+        )), "Got: {}", result);
+
+        let reversed = KotlinGenerator::new(true).reverse(&result).unwrap();
+        let variants: Vec<_> = reversed.iter()
+            .flat_map(|o| o.variables.iter().map(move |v| (o.name.as_str(), v.name.as_str(), v.value.as_deref())))
+            .collect();
+        assert_eq!(variants, vec![("Status", "OK", Some("200")), ("Status", "MISSING", Some("404")), ("Color", "RED", None)]);
+    }
 }
+
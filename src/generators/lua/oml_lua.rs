@@ -0,0 +1,268 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_snake_case;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct LuaGenerator {
+    pub style: CodeStyle,
+}
+
+impl LuaGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Words reserved in Lua, which fields are renamed away from.
+const KEYWORDS: [&str; 22] = [
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+impl Generate for LuaGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut lua_file = CodeWriter::new(self.style);
+
+        writeln!(lua_file, "-- This file has been generated from {}.oml", file_name)?;
+        writeln!(lua_file)?;
+
+        for oml_object in oml_objects {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut lua_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_class(oml_object, &mut lua_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            writeln!(lua_file)?;
+        }
+
+        // the module table
+        let exports: Vec<String> = oml_objects.iter().map(|o| format!("{} = {}", o.name, o.name)).collect();
+        writeln!(lua_file, "return {{ {} }}", exports.join(", "))?;
+
+        Ok(lua_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "lua"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("--")
+    }
+}
+
+/// A table of string constants annotated with `---@enum`, holding the
+/// variant names as written in the OML file.
+fn generate_enum(oml_object: &OmlObject, lua_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(lua_file, "---@enum {}", oml_object.name)?;
+    writeln!(lua_file, "local {} = {{", oml_object.name)?;
+    for var in &oml_object.variables {
+        let key = to_snake_case(&var.name).to_uppercase();
+        writeln!(lua_file, "{}{} = \"{}\",", lua_file.indent(1), key, var.name)?;
+    }
+    writeln!(lua_file, "}}")?;
+
+    Ok(())
+}
+
+/// A metatable-backed class: `---@class`/`---@field` annotations, statics
+/// as fields on the class table and a `new` constructor that takes a table
+/// of field values.
+fn generate_class(oml_object: &OmlObject, lua_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    writeln!(lua_file, "---@class {}", name)?;
+    for var in &fields {
+        writeln!(lua_file, "---@field {}{} {}", field_scope(&var.visibility), var.name, lua_type(var))?;
+    }
+    writeln!(lua_file, "local {} = {{}}", name)?;
+    writeln!(lua_file, "{}.__index = {}", name, name)?;
+
+    if !static_vars.is_empty() {
+        writeln!(lua_file)?;
+        for var in &static_vars {
+            writeln!(lua_file, "---@type {}", lua_type(var))?;
+            writeln!(lua_file, "{}.{} = {}", name, var.name, default_value(var))?;
+        }
+    }
+
+    writeln!(lua_file)?;
+    if fields.is_empty() {
+        writeln!(lua_file, "---@return {}", name)?;
+        writeln!(lua_file, "function {}.new()", name)?;
+        writeln!(lua_file, "{}return setmetatable({{}}, {})", lua_file.indent(1), name)?;
+        writeln!(lua_file, "end")?;
+        return Ok(());
+    }
+
+    let params: Vec<String> = fields
+        .iter()
+        .map(|v| {
+            let optional = if is_optional(v) { "?" } else { "" };
+            format!("{}{}: {}", v.name, optional, lua_type(v).trim_end_matches('?'))
+        })
+        .collect();
+    writeln!(lua_file, "---@param fields {{ {} }}", params.join(", "))?;
+    writeln!(lua_file, "---@return {}", name)?;
+    writeln!(lua_file, "function {}.new(fields)", name)?;
+    writeln!(lua_file, "{}local self = setmetatable({{}}, {})", lua_file.indent(1), name)?;
+    for var in &fields {
+        writeln!(lua_file, "{}self.{} = fields.{}", lua_file.indent(1), var.name, var.name)?;
+    }
+    writeln!(lua_file, "{}return self", lua_file.indent(1))?;
+    writeln!(lua_file, "end")?;
+
+    Ok(())
+}
+
+#[inline]
+fn field_scope(visibility: &VariableVisibility) -> &'static str {
+    match visibility {
+        VariableVisibility::PUBLIC => "",
+        VariableVisibility::PRIVATE => "private ",
+        VariableVisibility::PROTECTED => "protected ",
+    }
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" | "int64" => "integer".to_string(),
+        "uint8" | "uint16" | "uint32" | "uint64" => "integer".to_string(),
+        "float" | "double" => "number".to_string(),
+        "bool" => "boolean".to_string(),
+        "string" | "char" => "string".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn lua_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        _ => format!("{}[]", base),
+    };
+    if is_optional(var) {
+        format!("{}?", ty)
+    } else {
+        ty
+    }
+}
+
+fn default_value(var: &Variable) -> String {
+    if let Some(value) = &var.value {
+        return value.clone();
+    }
+    if is_optional(var) {
+        return "nil".to_string();
+    }
+    if var.array_kind != ArrayKind::None {
+        return "{}".to_string();
+    }
+    match var.var_type.as_str() {
+        "int8" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" | "uint64" => "0".to_string(),
+        "float" | "double" => "0.0".to_string(),
+        "bool" => "false".to_string(),
+        "string" | "char" => "\"\"".to_string(),
+        _ => "nil".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{member, generated};
+
+    #[test]
+    fn test_class_annotations_and_constructor() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                member("name", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("max_speed", "double", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("owner", "Person", VariableVisibility::PRIVATE, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("tags", "string", VariableVisibility::PROTECTED, vec![], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&LuaGenerator::default(), &obj);
+        assert!(out.contains(
+            "---@class Car\n\
+             ---@field name string\n\
+             ---@field max_speed number\n\
+             ---@field private owner Person?\n\
+             ---@field protected tags string[]\n\
+             local Car = {}\n\
+             Car.__index = Car\n\n"
+        ), "Got: {}", out);
+        assert!(out.contains(
+            "---@param fields { name: string, max_speed: number, owner?: Person, tags: string[] }\n\
+             ---@return Car\n\
+             function Car.new(fields)\n\
+             \tlocal self = setmetatable({}, Car)\n\
+             \tself.name = fields.name\n"
+        ), "Got: {}", out);
+        assert!(out.contains("\tself.tags = fields.tags\n\treturn self\nend\n"), "Got: {}", out);
+        assert!(out.ends_with("\nreturn { Car = Car }\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_live_on_the_class_table() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Registry".to_string(),
+            variables: vec![
+                member("count", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("names", "string", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&LuaGenerator::default(), &obj);
+        assert!(out.contains("---@type integer\nRegistry.count = 0\n---@type string[]\nRegistry.names = {}\n"), "Got: {}", out);
+        assert!(out.contains("function Registry.new()\n\treturn setmetatable({}, Registry)\nend\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_table() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                member("RED", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("darkBlue", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&LuaGenerator::default(), &obj).contains(
+            "---@enum Color\nlocal Color = {\n\tRED = \"RED\",\n\tDARK_BLUE = \"darkBlue\",\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static const float ratio = 2;\n}\n".to_string()
+        ).unwrap();
+        let out = LuaGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("Car.max_speed = 120\n---@type number\nCar.ratio = 2\n"), "Got: {}", out);
+    }
+}
@@ -1,7 +1,31 @@
+pub mod c;
 pub mod cpp;
+pub mod csharp;
+pub mod dart;
+pub mod docs;
+pub mod elixir;
+pub mod fsharp;
+pub mod gdscript;
+pub mod go;
+pub mod graphql;
+pub mod haskell;
 pub mod java;
+pub mod javascript;
+pub mod jsonschema;
+pub mod julia;
 pub mod kotlin;
+pub mod lua;
+pub mod ocaml;
+pub mod openapi;
+pub mod php;
+pub mod proto;
 pub mod python;
+pub mod ruby;
 pub mod rust;
+pub mod scala;
 pub mod sql;
 pub mod typescript;
+pub mod zig;
+
+#[cfg(all(test, feature = "compile-tests"))]
+mod compile_check;
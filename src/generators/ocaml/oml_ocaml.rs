@@ -0,0 +1,309 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate, GeneratedFile};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_snake_case;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct OcamlGenerator {
+    pub style: CodeStyle,
+}
+
+impl OcamlGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Renders the implementation, or with `interface` set the `.mli`
+    /// signature. Both expose the same type definitions; statics are `let`
+    /// bindings in the former and `val` specifications in the latter.
+    fn render(&self, oml_objects: &[OmlObject], file_name: &str, interface: bool) -> Result<String, Box<dyn Error>> {
+        let mut ml_file = CodeWriter::new(self.style);
+
+        writeln!(ml_file, "(* This file has been generated from {}.oml *)", file_name)?;
+
+        // One recursive group, so types may refer to each other in any order
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            let keyword = if i == 0 { "type" } else { "and" };
+            writeln!(ml_file)?;
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_variant(oml_object, keyword, &mut ml_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_record(oml_object, keyword, &mut ml_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+        }
+
+        let statics: Vec<(&OmlObject, &Variable)> = oml_objects
+            .iter()
+            .filter(|o| o.oml_type != ObjectType::ENUM)
+            .flat_map(|o| o.variables.iter().map(move |v| (o, v)))
+            .filter(|(_, v)| v.var_mod.contains(&VariableModifier::STATIC))
+            .collect();
+
+        if !statics.is_empty() {
+            writeln!(ml_file)?;
+        }
+        for (oml_object, var) in statics {
+            let name = format!("{}_{}", to_snake_case(&oml_object.name), to_snake_case(&var.name));
+            // mutable statics are refs
+            let (ty, value) = if is_const(var) {
+                (ocaml_type(var), default_value(var))
+            } else {
+                (format!("{} ref", ocaml_type(var)), format!("ref {}", default_value(var)))
+            };
+            if interface {
+                writeln!(ml_file, "val {} : {}", name, ty)?;
+            } else {
+                writeln!(ml_file, "let {} : {} = {}", name, ty, value)?;
+            }
+        }
+
+        Ok(ml_file.finish())
+    }
+}
+
+/// Words reserved in OCaml, which fields are renamed away from.
+const KEYWORDS: [&str; 56] = [
+    "and", "as", "assert", "asr", "begin", "class", "constraint", "do", "done", "downto", "else",
+    "end", "exception", "external", "false", "for", "fun", "function", "functor", "if", "in",
+    "include", "inherit", "initializer", "land", "lazy", "let", "lor", "lsl", "lsr", "lxor",
+    "match", "method", "mod", "module", "mutable", "new", "nonrec", "object", "of", "open", "or",
+    "private", "rec", "sig", "struct", "then", "to", "true", "try", "type", "val", "virtual",
+    "when", "while", "with",
+];
+
+impl Generate for OcamlGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        self.render(oml_objects, file_name, false)
+    }
+
+    fn extension(&self) -> &str {
+        "ml"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Block("(*", "*)")
+    }
+
+    /// Writes the `.ml` implementation together with its `.mli` interface.
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        Ok(vec![
+            GeneratedFile::new(format!("{}.ml", file_name), self.render(oml_objects, file_name, false)?),
+            GeneratedFile::new(format!("{}.mli", file_name), self.render(oml_objects, file_name, true)?),
+        ])
+    }
+}
+
+/// A variant type with one constant constructor per OML variant:
+/// `DARK_BLUE` becomes `Dark_blue`.
+fn generate_variant(oml_object: &OmlObject, keyword: &str, ml_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = to_snake_case(&oml_object.name);
+    if oml_object.variables.is_empty() {
+        writeln!(ml_file, "{} {} = |", keyword, name)?;
+        return Ok(());
+    }
+
+    writeln!(ml_file, "{} {} =", keyword, name)?;
+    for var in &oml_object.variables {
+        writeln!(ml_file, "{}| {}", ml_file.indent(1), constructor_name(&var.name))?;
+    }
+
+    Ok(())
+}
+
+/// A record type; statics are emitted separately as top-level bindings.
+/// OCaml has no empty records, so a type without fields is `unit`.
+fn generate_record(oml_object: &OmlObject, keyword: &str, ml_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = to_snake_case(&oml_object.name);
+    let fields: Vec<&Variable> = oml_object.variables
+        .iter()
+        .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+        .collect();
+
+    if fields.is_empty() {
+        writeln!(ml_file, "{} {} = unit", keyword, name)?;
+        return Ok(());
+    }
+
+    writeln!(ml_file, "{} {} = {{", keyword, name)?;
+    for var in &fields {
+        let mutable = if is_const(var) { "" } else { "mutable " };
+        writeln!(ml_file, "{}{}{} : {};", ml_file.indent(1), mutable, to_snake_case(&var.name), ocaml_type(var))?;
+    }
+    writeln!(ml_file, "}}")?;
+
+    Ok(())
+}
+
+fn constructor_name(name: &str) -> String {
+    let snake = to_snake_case(name).to_lowercase();
+    let mut chars = snake.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(c) => c.to_uppercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+#[inline]
+fn is_const(var: &Variable) -> bool {
+    var.is_const()
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+/// OCaml's native `int` is 63 bits wide, so only the 64-bit types need
+/// the boxed `int64`.
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" => "int".to_string(),
+        "uint8" | "uint16" | "uint32" => "int".to_string(),
+        "int64" | "uint64" => "int64".to_string(),
+        "float" | "double" => "float".to_string(),
+        "bool" => "bool".to_string(),
+        "string" => "string".to_string(),
+        "char" => "char".to_string(),
+        other => to_snake_case(other),
+    }
+}
+
+/// Static arrays become arrays and dynamic ones lists.
+fn ocaml_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        ArrayKind::Static(_) => format!("{} array", base),
+        ArrayKind::Dynamic => format!("{} list", base),
+    };
+    if is_optional(var) {
+        format!("{} option", ty)
+    } else {
+        ty
+    }
+}
+
+fn default_value(var: &Variable) -> String {
+    if let Some(value) = &var.value {
+        let literal = ocaml_literal(&var.var_type, value);
+        return if is_optional(var) { format!("Some {}", literal) } else { literal };
+    }
+    if is_optional(var) {
+        return "None".to_string();
+    }
+    match &var.array_kind {
+        ArrayKind::Static(_) => "[||]".to_string(),
+        ArrayKind::Dynamic => "[]".to_string(),
+        ArrayKind::None => match var.var_type.as_str() {
+            "int8" | "int16" | "int32" | "uint8" | "uint16" | "uint32" => "0".to_string(),
+            "int64" | "uint64" => "0L".to_string(),
+            "float" | "double" => "0.0".to_string(),
+            "bool" => "false".to_string(),
+            "string" => "\"\"".to_string(),
+            "char" => "'\\000'".to_string(),
+            _ => "Obj.magic ()".to_string(),
+        },
+    }
+}
+
+/// An OML literal in OCaml: `L` on 64-bit integers, a `.` on floats.
+fn ocaml_literal(var_type: &str, value: &str) -> String {
+    match var_type {
+        "int64" | "uint64" => format!("{}L", value),
+        "float" | "double" if !value.contains('.') => format!("{}.", value),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::var;
+
+    fn car() -> OmlObject {
+        OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("name", "string", vec![VariableModifier::CONST], ArrayKind::None),
+                var("maxSpeed", "double", vec![], ArrayKind::None),
+                var("engine", "GasEngine", vec![VariableModifier::CONST, VariableModifier::OPTIONAL], ArrayKind::None),
+                var("tags", "string", vec![VariableModifier::CONST], ArrayKind::Dynamic),
+                var("count", "int64", vec![VariableModifier::STATIC], ArrayKind::None),
+                var("wheels", "uint8", vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn color() -> OmlObject {
+        OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                var("RED", "string", vec![], ArrayKind::None),
+                var("DARK_BLUE", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_record_and_variant_in_one_group() {
+        let out = OcamlGenerator::default().generate(&[car(), color()], "car").unwrap();
+        assert!(out.contains(
+            "\ntype car = {\n\
+             \tname : string;\n\
+             \tmutable max_speed : float;\n\
+             \tengine : gas_engine option;\n\
+             \ttags : string list;\n\
+             }\n\
+             \n\
+             and color =\n\
+             \t| Red\n\
+             \t| Dark_blue\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_become_bindings() {
+        let out = OcamlGenerator::default().generate(&[car()], "car").unwrap();
+        assert!(out.ends_with("\nlet car_count : int64 ref = ref 0L\nlet car_wheels : int = 0\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_interface_file() {
+        let files = OcamlGenerator::default().generate_files(&[car()], "car").unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, std::path::PathBuf::from("car.ml"));
+        assert_eq!(files[1].path, std::path::PathBuf::from("car.mli"));
+        assert!(files[1].content.contains("type car = {\n"), "Got: {}", files[1].content);
+        assert!(files[1].content.ends_with("\nval car_count : int64 ref\nval car_wheels : int\n"), "Got: {}", files[1].content);
+    }
+
+    #[test]
+    fn test_empty_record_is_unit() {
+        let obj = OmlObject { oml_type: ObjectType::STRUCT, name: "Marker".to_string(), ..Default::default() };
+        let out = OcamlGenerator::default().generate(std::slice::from_ref(&obj), "marker").unwrap();
+        assert!(out.contains("\ntype marker = unit\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static const float ratio = 2;\n}\n".to_string()
+        ).unwrap();
+        let out = OcamlGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("let car_max_speed : int64 = 120L\nlet car_ratio : float = 2.\n"), "Got: {}", out);
+    }
+}
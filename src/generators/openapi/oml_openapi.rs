@@ -0,0 +1,287 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate};
+use crate::core::code_writer::{CodeStyle, CodeWriter, IndentKind};
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct OpenapiGenerator {
+    /// Wrap the schemas in a skeleton OpenAPI document instead of emitting
+    /// only the `components` fragment
+    pub full_spec: bool,
+    pub style: CodeStyle,
+}
+
+impl OpenapiGenerator {
+    pub fn with_full_spec(mut self, full_spec: bool) -> Self {
+        self.full_spec = full_spec;
+        self
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Generate for OpenapiGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        // YAML does not allow tabs in indentation
+        let style = CodeStyle { indent_kind: IndentKind::Spaces, ..self.style };
+        let mut yaml_file = CodeWriter::new(style);
+
+        writeln!(yaml_file, "# This file has been generated from {}.oml", file_name)?;
+        writeln!(yaml_file)?;
+
+        if self.full_spec {
+            writeln!(yaml_file, "openapi: 3.1.0")?;
+            writeln!(yaml_file, "info:")?;
+            writeln!(yaml_file, "{}title: {}", yaml_file.indent(1), file_name)?;
+            writeln!(yaml_file, "{}version: 1.0.0", yaml_file.indent(1))?;
+            writeln!(yaml_file, "paths: {{}}")?;
+        }
+
+        writeln!(yaml_file, "components:")?;
+        if oml_objects.is_empty() {
+            writeln!(yaml_file, "{}schemas: {{}}", yaml_file.indent(1))?;
+            return Ok(yaml_file.finish());
+        }
+
+        writeln!(yaml_file, "{}schemas:", yaml_file.indent(1))?;
+        for oml_object in oml_objects {
+            writeln!(yaml_file, "{}{}:", yaml_file.indent(2), oml_object.name)?;
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut yaml_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_object(oml_object, &mut yaml_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+        }
+
+        Ok(yaml_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "yaml"
+    }
+
+    /// Property names are JSON strings, so no word is reserved.
+    fn keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("#")
+    }
+}
+
+/// Values are quoted so names like `YES` or `null` stay strings.
+fn generate_enum(oml_object: &OmlObject, yaml_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(yaml_file, "{}type: string", yaml_file.indent(3))?;
+    if oml_object.variables.is_empty() {
+        writeln!(yaml_file, "{}enum: []", yaml_file.indent(3))?;
+        return Ok(());
+    }
+
+    writeln!(yaml_file, "{}enum:", yaml_file.indent(3))?;
+    for var in &oml_object.variables {
+        writeln!(yaml_file, "{}- '{}'", yaml_file.indent(4), var.name)?;
+    }
+
+    Ok(())
+}
+
+/// A closed object schema mirroring the JSON Schema generator: optional
+/// fields are left out of `required`, const fields are `readOnly` and
+/// statics are not part of the payload.
+fn generate_object(oml_object: &OmlObject, yaml_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let fields: Vec<&Variable> = oml_object.variables
+        .iter()
+        .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+        .collect();
+
+    writeln!(yaml_file, "{}type: object", yaml_file.indent(3))?;
+    if fields.is_empty() {
+        writeln!(yaml_file, "{}properties: {{}}", yaml_file.indent(3))?;
+    } else {
+        writeln!(yaml_file, "{}properties:", yaml_file.indent(3))?;
+        for var in &fields {
+            writeln!(yaml_file, "{}{}:", yaml_file.indent(4), var.name)?;
+            generate_property(var, yaml_file)?;
+        }
+    }
+
+    let required: Vec<&&Variable> = fields
+        .iter()
+        .filter(|v| !v.var_mod.contains(&VariableModifier::OPTIONAL))
+        .collect();
+    if !required.is_empty() {
+        writeln!(yaml_file, "{}required:", yaml_file.indent(3))?;
+        for var in required {
+            writeln!(yaml_file, "{}- {}", yaml_file.indent(4), var.name)?;
+        }
+    }
+    writeln!(yaml_file, "{}additionalProperties: false", yaml_file.indent(3))?;
+
+    Ok(())
+}
+
+fn generate_property(var: &Variable, yaml_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    match &var.array_kind {
+        ArrayKind::None => generate_type(&var.var_type, 5, yaml_file)?,
+        kind => {
+            writeln!(yaml_file, "{}type: array", yaml_file.indent(5))?;
+            writeln!(yaml_file, "{}items:", yaml_file.indent(5))?;
+            generate_type(&var.var_type, 6, yaml_file)?;
+            if let ArrayKind::Static(n) = kind {
+                writeln!(yaml_file, "{}minItems: {}", yaml_file.indent(5), n)?;
+                writeln!(yaml_file, "{}maxItems: {}", yaml_file.indent(5), n)?;
+            }
+        }
+    }
+    if var.is_const() {
+        writeln!(yaml_file, "{}readOnly: true", yaml_file.indent(5))?;
+    }
+
+    Ok(())
+}
+
+/// Inclusive minimum and optional maximum of an integer type.
+type Bounds = (i64, Option<i64>);
+
+/// Integers use the `int32`/`int64` formats OpenAPI defines, with bounds
+/// for the narrower and unsigned types. Other objects are referenced
+/// within the same components section.
+fn generate_type(var_type: &str, level: usize, yaml_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let indent = yaml_file.indent(level);
+    let (ty, format, bounds): (&str, Option<&str>, Option<Bounds>) = match var_type {
+        "int8" => ("integer", Some("int32"), Some((i8::MIN.into(), Some(i8::MAX.into())))),
+        "int16" => ("integer", Some("int32"), Some((i16::MIN.into(), Some(i16::MAX.into())))),
+        "int32" => ("integer", Some("int32"), None),
+        "int64" => ("integer", Some("int64"), None),
+        "uint8" => ("integer", Some("int32"), Some((0, Some(u8::MAX.into())))),
+        "uint16" => ("integer", Some("int32"), Some((0, Some(u16::MAX.into())))),
+        "uint32" => ("integer", Some("int64"), Some((0, Some(u32::MAX.into())))),
+        "uint64" => ("integer", None, Some((0, None))),
+        "float" => ("number", Some("float"), None),
+        "double" => ("number", Some("double"), None),
+        "bool" => ("boolean", None, None),
+        "string" => ("string", None, None),
+        "char" => {
+            writeln!(yaml_file, "{}type: string", indent)?;
+            writeln!(yaml_file, "{}minLength: 1", indent)?;
+            writeln!(yaml_file, "{}maxLength: 1", indent)?;
+            return Ok(());
+        }
+        other => {
+            writeln!(yaml_file, "{}$ref: '#/components/schemas/{}'", indent, other)?;
+            return Ok(());
+        }
+    };
+
+    writeln!(yaml_file, "{}type: {}", indent, ty)?;
+    if let Some(format) = format {
+        writeln!(yaml_file, "{}format: {}", indent, format)?;
+    }
+    if let Some((min, max)) = bounds {
+        writeln!(yaml_file, "{}minimum: {}", indent, min)?;
+        if let Some(max) = max {
+            writeln!(yaml_file, "{}maximum: {}", indent, max)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::var;
+
+    fn style() -> CodeStyle {
+        CodeStyle { indent_width: 2, ..Default::default() }
+    }
+
+    #[test]
+    fn test_components_fragment() {
+        let objects = [
+            OmlObject {
+                oml_type: ObjectType::CLASS,
+                name: "Car".to_string(),
+                variables: vec![
+                    var("name", "string", vec![VariableModifier::CONST], ArrayKind::None),
+                    var("doors", "uint8", vec![], ArrayKind::None),
+                    var("engine", "Engine", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                    var("wheels", "double", vec![], ArrayKind::Static(4)),
+                    var("count", "int32", vec![VariableModifier::STATIC], ArrayKind::None),
+                ],
+                ..Default::default()
+            },
+            OmlObject {
+                oml_type: ObjectType::ENUM,
+                name: "Color".to_string(),
+                variables: vec![var("RED", "string", vec![], ArrayKind::None), var("NO", "string", vec![], ArrayKind::None)],
+                ..Default::default()
+            },
+        ];
+        let out = OpenapiGenerator::default().with_style(style()).generate(&objects, "car").unwrap();
+        assert_eq!(
+            out,
+            "# This file has been generated from car.oml\n\
+             \n\
+             components:\n\
+             \x20 schemas:\n\
+             \x20   Car:\n\
+             \x20     type: object\n\
+             \x20     properties:\n\
+             \x20       name:\n\
+             \x20         type: string\n\
+             \x20         readOnly: true\n\
+             \x20       doors:\n\
+             \x20         type: integer\n\
+             \x20         format: int32\n\
+             \x20         minimum: 0\n\
+             \x20         maximum: 255\n\
+             \x20       engine:\n\
+             \x20         $ref: '#/components/schemas/Engine'\n\
+             \x20       wheels:\n\
+             \x20         type: array\n\
+             \x20         items:\n\
+             \x20           type: number\n\
+             \x20           format: double\n\
+             \x20         minItems: 4\n\
+             \x20         maxItems: 4\n\
+             \x20     required:\n\
+             \x20       - name\n\
+             \x20       - doors\n\
+             \x20       - wheels\n\
+             \x20     additionalProperties: false\n\
+             \x20   Color:\n\
+             \x20     type: string\n\
+             \x20     enum:\n\
+             \x20       - 'RED'\n\
+             \x20       - 'NO'\n"
+        );
+    }
+
+    #[test]
+    fn test_full_spec_skeleton() {
+        let obj = OmlObject { oml_type: ObjectType::STRUCT, name: "Marker".to_string(), ..Default::default() };
+        let out = OpenapiGenerator::default()
+            .with_full_spec(true)
+            .with_style(style())
+            .generate(std::slice::from_ref(&obj), "marker")
+            .unwrap();
+        assert!(out.contains(
+            "openapi: 3.1.0\ninfo:\n  title: marker\n  version: 1.0.0\npaths: {}\ncomponents:\n  schemas:\n    Marker:\n      type: object\n      properties: {}\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_tabs_fall_back_to_spaces() {
+        let obj = OmlObject { oml_type: ObjectType::STRUCT, name: "Marker".to_string(), ..Default::default() };
+        let out = OpenapiGenerator::default().generate(std::slice::from_ref(&obj), "marker").unwrap();
+        assert!(!out.contains('\t'), "Got: {}", out);
+    }
+}
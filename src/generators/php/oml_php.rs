@@ -0,0 +1,317 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
+use crate::core::utils::{to_camel_case, to_pascal_case};
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct PhpGenerator {
+    pub style: CodeStyle,
+}
+
+impl PhpGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// PHP variables carry a `$`, so keywords are fine as property names;
+/// only `$this` is taken.
+const KEYWORDS: [&str; 1] = [
+    "this",
+];
+
+impl Generate for PhpGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut php_file = CodeWriter::new(self.style);
+
+        writeln!(php_file, "<?php")?;
+        writeln!(php_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(php_file)?;
+        writeln!(php_file, "declare(strict_types=1);")?;
+        writeln!(php_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut php_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_class(oml_object, &mut php_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(php_file)?;
+            }
+        }
+
+        Ok(php_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "php"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Camel,
+            constants: Case::Camel,
+            enum_values: Case::Pascal,
+        }
+    }
+}
+
+/// String-backed enum whose values are the variant names as written in the
+/// OML file, so serialized values stay stable across languages.
+fn generate_enum(oml_object: &OmlObject, php_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    php_file.open_block(0, &format!("enum {}: string", oml_object.name))?;
+    for var in &oml_object.variables {
+        writeln!(php_file, "{}case {} = '{}';", php_file.indent(1), to_pascal_case(&var.name), var.name)?;
+    }
+    writeln!(php_file, "}}")?;
+
+    Ok(())
+}
+
+fn generate_class(oml_object: &OmlObject, php_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let (static_vars, instance_vars): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    php_file.open_block(0, &format!("class {}", oml_object.name))?;
+
+    // Static properties cannot be promoted, and PHP has no readonly statics
+    for var in &static_vars {
+        let comment = if is_const(var) { " // read-only" } else { "" };
+        let value = var.value.as_deref().map(|v| format!(" = {}", php_literal(v))).unwrap_or_default();
+        writeln!(
+            php_file,
+            "{}{} static {} ${}{};{}",
+            php_file.indent(1),
+            convert_visibility(&var.visibility),
+            php_type(var),
+            to_camel_case(&var.name),
+            value,
+            comment,
+        )?;
+    }
+
+    if !instance_vars.is_empty() {
+        if !static_vars.is_empty() {
+            writeln!(php_file)?;
+        }
+        generate_constructor(&instance_vars, php_file)?;
+    }
+
+    writeln!(php_file, "}}")?;
+
+    Ok(())
+}
+
+/// Constructor with promoted properties. Optional parameters default to
+/// null and go last, since PHP deprecates optional parameters before
+/// required ones.
+fn generate_constructor(instance_vars: &[&Variable], php_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let (optional, required): (Vec<&Variable>, Vec<&Variable>) = instance_vars
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::OPTIONAL));
+    let params: Vec<&Variable> = required.into_iter().chain(optional).collect();
+
+    // `array` says nothing about the elements, so describe them for static analysers
+    let array_params: Vec<&&Variable> = params.iter().filter(|v| v.array_kind != ArrayKind::None).collect();
+    if !array_params.is_empty() {
+        writeln!(php_file, "{}/**", php_file.indent(1))?;
+        for var in array_params {
+            writeln!(
+                php_file,
+                "{} * @param {} ${}",
+                php_file.indent(1),
+                array_doc_type(var),
+                to_camel_case(&var.name),
+            )?;
+        }
+        writeln!(php_file, "{} */", php_file.indent(1))?;
+    }
+
+    writeln!(php_file, "{}public function __construct(", php_file.indent(1))?;
+    for var in &params {
+        let readonly = if is_const(var) { "readonly " } else { "" };
+        let default = if var.var_mod.contains(&VariableModifier::OPTIONAL) { " = null" } else { "" };
+        writeln!(
+            php_file,
+            "{}{} {}{} ${}{},",
+            php_file.indent(2),
+            convert_visibility(&var.visibility),
+            readonly,
+            php_type(var),
+            to_camel_case(&var.name),
+            default,
+        )?;
+    }
+    writeln!(php_file, "{}) {{}}", php_file.indent(1))?;
+
+    Ok(())
+}
+
+#[inline]
+fn is_const(var: &Variable) -> bool {
+    var.is_const()
+}
+
+/// An OML literal in PHP. Chars become double-quoted strings, since single
+/// quotes would leave their escapes as written, and `$` is escaped so it
+/// does not interpolate.
+fn php_literal(value: &str) -> String {
+    match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        Some(c) => format!("\"{}\"", c.replace("\\'", "'").replace("\\\"", "\"").replace('"', "\\\"").replace('$', "\\$")),
+        None if value.starts_with('"') => value.replace('$', "\\$"),
+        None => value.to_string(),
+    }
+}
+
+#[inline]
+fn convert_visibility(visibility: &VariableVisibility) -> &'static str {
+    match visibility {
+        VariableVisibility::PUBLIC => "public",
+        VariableVisibility::PRIVATE => "private",
+        VariableVisibility::PROTECTED => "protected",
+    }
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" | "int64" => "int".to_string(),
+        "uint8" | "uint16" | "uint32" | "uint64" => "int".to_string(),
+        "float" | "double" => "float".to_string(),
+        "bool" => "bool".to_string(),
+        "string" | "char" => "string".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn php_type(var: &Variable) -> String {
+    let ty = match &var.array_kind {
+        ArrayKind::None => convert_type(&var.var_type),
+        _ => "array".to_string(),
+    };
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        format!("?{}", ty)
+    } else {
+        ty
+    }
+}
+
+fn array_doc_type(var: &Variable) -> String {
+    let list = format!("list<{}>", convert_type(&var.var_type));
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        format!("{}|null", list)
+    } else {
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{member, generated};
+
+    #[test]
+    fn test_file_header() {
+        let obj = OmlObject { oml_type: ObjectType::CLASS, name: "Empty".to_string(), ..Default::default() };
+        let out = generated(&PhpGenerator::default(), &obj);
+        assert!(out.starts_with("<?php\n// This file has been generated from test.oml\n\ndeclare(strict_types=1);\n\n"));
+        assert!(out.ends_with("class Empty {\n}\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_constructor_property_promotion() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                member("nickname", "string", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("name", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("max_speed", "double", VariableVisibility::PRIVATE, vec![VariableModifier::CONST], ArrayKind::None),
+                member("doors", "int32", VariableVisibility::PROTECTED,
+                    vec![VariableModifier::CONST, VariableModifier::MUT], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&PhpGenerator::default(), &obj);
+        assert!(out.contains(
+            "\tpublic function __construct(\n\
+             \t\tpublic string $name,\n\
+             \t\tprivate readonly float $maxSpeed,\n\
+             \t\tprotected int $doors,\n\
+             \t\tpublic ?string $nickname = null,\n\
+             \t) {}\n"
+        ), "Got: {}", out);
+        assert!(!out.contains("/**"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_arrays_get_element_types_in_docblock() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Image".to_string(),
+            variables: vec![
+                member("pixels", "uint8", VariableVisibility::PUBLIC, vec![], ArrayKind::Dynamic),
+                member("tags", "string", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::Static(4)),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&PhpGenerator::default(), &obj);
+        assert!(out.contains("\t/**\n\t * @param list<int> $pixels\n\t * @param list<string>|null $tags\n\t */\n"), "Got: {}", out);
+        assert!(out.contains("\t\tpublic array $pixels,\n\t\tpublic ?array $tags = null,\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_are_declared_before_constructor() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Counter".to_string(),
+            variables: vec![
+                member("total", "int64", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("unit_name", "string", VariableVisibility::PRIVATE,
+                    vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+                member("value", "int32", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&PhpGenerator::default(), &obj);
+        assert!(out.contains(
+            "class Counter {\n\tpublic static int $total;\n\tprivate static string $unitName; // read-only\n\n\tpublic function __construct(\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_backed_enum() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                member("RED", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("dark_blue", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&PhpGenerator::default(), &obj).contains("enum Color: string {\n\tcase Red = 'RED';\n\tcase DarkBlue = 'dark_blue';\n}"));
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static string label = \"$5 #1\";\n public static char mark = '\\'';\n}\n".to_string()
+        ).unwrap();
+        let out = PhpGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("\tpublic static int $maxSpeed = 120; // read-only\n\tpublic static string $label = \"\\$5 #1\";\n\tpublic static string $mark = \"'\";\n"), "Got: {}", out);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_snake_case;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Write;
+
+/// Largest field number protobuf accepts.
+const MAX_FIELD_NUMBER: u32 = 536_870_911;
+/// Field numbers reserved for the protobuf implementation.
+const RESERVED_FIELD_NUMBERS: std::ops::RangeInclusive<u32> = 19_000..=19_999;
+
+#[derive(Default)]
+pub struct ProtoGenerator {
+    pub style: CodeStyle,
+}
+
+impl ProtoGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Generate for ProtoGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut proto_file = CodeWriter::new(self.style);
+
+        writeln!(proto_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(proto_file)?;
+        writeln!(proto_file, "syntax = \"proto3\";")?;
+        writeln!(proto_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut proto_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_message(oml_object, &mut proto_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(proto_file)?;
+            }
+        }
+
+        Ok(proto_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "proto"
+    }
+
+    /// protoc reads keywords as field names where a name is expected.
+    fn keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// proto3 enums must start at zero, so every enum gets an `_UNSPECIFIED`
+/// default and the OML variants are numbered from one. Values are prefixed
+/// with the enum name since they share the package scope.
+fn generate_enum(oml_object: &OmlObject, proto_file: &mut CodeWriter) -> Result<(), Box<dyn Error>> {
+    let prefix = to_snake_case(&oml_object.name).to_uppercase();
+    let numbers = field_numbers(oml_object, &oml_object.variables.iter().collect::<Vec<_>>())?;
+
+    writeln!(proto_file, "enum {} {{", oml_object.name)?;
+    writeln!(proto_file, "{}{}_UNSPECIFIED = 0;", proto_file.indent(1), prefix)?;
+    for (var, number) in oml_object.variables.iter().zip(numbers) {
+        let value = to_snake_case(&var.name).to_uppercase();
+        writeln!(proto_file, "{}{}_{} = {};", proto_file.indent(1), prefix, value, number)?;
+    }
+    writeln!(proto_file, "}}")?;
+
+    Ok(())
+}
+
+/// A message with one field per instance variable. Arrays of either kind
+/// become `repeated`, which proto3 does not allow to be `optional`; an empty
+/// list stands in for a missing one. Statics have no wire representation.
+fn generate_message(oml_object: &OmlObject, proto_file: &mut CodeWriter) -> Result<(), Box<dyn Error>> {
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+    let numbers = field_numbers(oml_object, &fields)?;
+
+    writeln!(proto_file, "message {} {{", oml_object.name)?;
+    for var in &static_vars {
+        writeln!(proto_file, "{}// static {} is not representable in a message", proto_file.indent(1), var.name)?;
+    }
+    for (var, number) in fields.iter().zip(numbers) {
+        let label = if var.array_kind != ArrayKind::None {
+            "repeated "
+        } else if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            "optional "
+        } else {
+            ""
+        };
+        writeln!(
+            proto_file,
+            "{}{}{} {} = {};",
+            proto_file.indent(1),
+            label,
+            convert_type(&var.var_type),
+            to_snake_case(&var.name),
+            number,
+        )?;
+    }
+    writeln!(proto_file, "}}")?;
+
+    Ok(())
+}
+
+/// Assigns a field number to each variable: its `@tag(N)` when present,
+/// otherwise the lowest number not yet taken, counting from one. Numbers
+/// must be unique within the object and outside protobuf's reserved range.
+fn field_numbers(oml_object: &OmlObject, vars: &[&Variable]) -> Result<Vec<u32>, Box<dyn Error>> {
+    let mut taken: HashSet<u32> = HashSet::new();
+    for var in vars {
+        if let Some(tag) = var.tag {
+            if tag > MAX_FIELD_NUMBER || RESERVED_FIELD_NUMBERS.contains(&tag) {
+                return Err(format!(
+                    "Tag {} on '{}.{}' is not a valid protobuf field number",
+                    tag, oml_object.name, var.name
+                ).into());
+            }
+            if !taken.insert(tag) {
+                return Err(format!("Tag {} is used more than once in '{}'", tag, oml_object.name).into());
+            }
+        }
+    }
+
+    let mut next = 1;
+    let mut numbers = Vec::with_capacity(vars.len());
+    for var in vars {
+        let number = match var.tag {
+            Some(tag) => tag,
+            None => {
+                while taken.contains(&next) || RESERVED_FIELD_NUMBERS.contains(&next) {
+                    next += 1;
+                }
+                taken.insert(next);
+                next
+            }
+        };
+        numbers.push(number);
+    }
+
+    Ok(numbers)
+}
+
+/// Narrow integers widen to the smallest protobuf scalar that holds them.
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" => "int32".to_string(),
+        "int64" => "int64".to_string(),
+        "uint8" | "uint16" | "uint32" => "uint32".to_string(),
+        "uint64" => "uint64".to_string(),
+        "float" => "float".to_string(),
+        "double" => "double".to_string(),
+        "bool" => "bool".to_string(),
+        "string" | "char" => "string".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{var, generated};
+
+    fn tagged(name: &str, var_type: &str, var_mod: Vec<VariableModifier>, array_kind: ArrayKind, tag: Option<u32>) -> Variable {
+        Variable { tag, ..var(name, var_type, var_mod, array_kind) }
+    }
+
+    #[test]
+    fn test_message_fields() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("name", "string", vec![VariableModifier::CONST], ArrayKind::None),
+                var("maxSpeed", "double", vec![], ArrayKind::None),
+                var("nickname", "string", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                var("engine", "Engine", vec![], ArrayKind::None),
+                var("tags", "string", vec![], ArrayKind::Dynamic),
+                var("wheels", "uint8", vec![VariableModifier::OPTIONAL], ArrayKind::Static(4)),
+                var("count", "int32", vec![VariableModifier::STATIC], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&ProtoGenerator::default(), &obj);
+        assert!(out.contains("\nsyntax = \"proto3\";\n"), "Got: {}", out);
+        assert!(out.contains(
+            "message Car {\n\
+             \t// static count is not representable in a message\n\
+             \tstring name = 1;\n\
+             \tdouble max_speed = 2;\n\
+             \toptional string nickname = 3;\n\
+             \tEngine engine = 4;\n\
+             \trepeated string tags = 5;\n\
+             \trepeated uint32 wheels = 6;\n\
+             }\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_tags_set_field_numbers() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Packet".to_string(),
+            variables: vec![
+                tagged("id", "uint64", vec![], ArrayKind::None, Some(2)),
+                var("payload", "uint8", vec![], ArrayKind::Dynamic),
+                tagged("checksum", "uint32", vec![], ArrayKind::None, Some(10)),
+                var("flags", "uint16", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&ProtoGenerator::default(), &obj).contains(
+            "message Packet {\n\
+             \tuint64 id = 2;\n\
+             \trepeated uint32 payload = 1;\n\
+             \tuint32 checksum = 10;\n\
+             \tuint32 flags = 3;\n\
+             }\n"
+        ));
+    }
+
+    #[test]
+    fn test_invalid_tags_return_error() {
+        for tags in [[Some(1), Some(1)], [Some(19_500), None], [Some(MAX_FIELD_NUMBER + 1), None]] {
+            let obj = OmlObject {
+                oml_type: ObjectType::CLASS,
+                name: "Bad".to_string(),
+                variables: vec![
+                    tagged("a", "int32", vec![], ArrayKind::None, tags[0]),
+                    tagged("b", "int32", vec![], ArrayKind::None, tags[1]),
+                ],
+                ..Default::default()
+            };
+            assert!(ProtoGenerator::default().generate(std::slice::from_ref(&obj), "bad").is_err(), "Accepted {:?}", tags);
+        }
+    }
+
+    #[test]
+    fn test_enum_with_unspecified_default() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "TrafficLight".to_string(),
+            variables: vec![
+                var("RED", "string", vec![], ArrayKind::None),
+                tagged("darkBlue", "string", vec![], ArrayKind::None, Some(5)),
+                var("GREEN", "string", vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&ProtoGenerator::default(), &obj).contains(
+            "enum TrafficLight {\n\
+             \tTRAFFIC_LIGHT_UNSPECIFIED = 0;\n\
+             \tTRAFFIC_LIGHT_RED = 1;\n\
+             \tTRAFFIC_LIGHT_DARK_BLUE = 5;\n\
+             \tTRAFFIC_LIGHT_GREEN = 2;\n\
+             }\n"
+        ));
+    }
+}
@@ -1 +1,2 @@
-pub mod oml_python;
\ No newline at end of file
+pub mod oml_python;
+mod pytest;
@@ -1,12 +1,74 @@
 use crate::core::oml_object::{
     OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
 };
-use crate::core::generate::{Generate, BackwardsGenerate};
+use crate::core::generate::{AccessorMode, CommentSyntax, Generate, BackwardsGenerate, GeneratedFile};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
+use crate::core::builder::{builder_fields, BuilderField};
+use crate::core::type_map::TypeMap;
+use crate::core::schema_hash::{schema_hash, SCHEMA_HASH};
+use crate::core::utils::to_snake_case;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Write;
 
+/// How enum variants get their values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum PythonEnumStyle {
+    /// `Enum` with values counting up from 0 in declaration order
+    #[default]
+    Positional,
+    /// `Enum` with `auto()` values
+    Auto,
+    /// `IntEnum` with values counting up from 0, comparable to plain ints
+    Int,
+    /// `StrEnum` whose values are the variant names (Python 3.11+)
+    Str,
+}
+
 pub struct PythonGenerator {
     pub use_data_class: bool,
+    /// Emit pydantic `BaseModel` subclasses for classes and structs
+    pub pydantic: bool,
+    /// Emit `attrs` classes for classes and structs
+    pub attrs: bool,
+    /// Also write a `.pyi` stub next to each module
+    pub stubs: bool,
+    /// Generate `to_dict`/`from_dict` and `to_json`/`from_json` methods;
+    /// pydantic models have `model_dump` and `model_validate` instead
+    pub dict_helpers: bool,
+    pub enum_style: PythonEnumStyle,
+    /// Decorate enums with `@unique`
+    pub unique_enums: bool,
+    /// Name fields in snake_case and static constants in SCREAMING_SNAKE_CASE;
+    /// `to_dict` keys keep the OML names
+    pub snake_case: bool,
+    /// Give dataclasses `__slots__` via `slots=True` (Python 3.10+)
+    pub slots: bool,
+    /// Make dataclass fields keyword-only via `kw_only=True` (Python 3.10+)
+    pub kw_only: bool,
+    /// Names of the dataclasses to freeze; when unset, dataclasses whose
+    /// fields are all const are frozen
+    pub frozen: Option<Vec<String>>,
+    /// Check the ranges and lengths implied by the OML types in setters,
+    /// `__init__` and dataclass `__post_init__`
+    pub validate: bool,
+    /// Also write an `__init__.py` re-exporting every generated type
+    pub package_init: bool,
+    /// Fields of regular classes kept in `_name` behind a property; the
+    /// others are plain attributes
+    pub accessors: AccessorMode,
+    /// Also write a pytest module per class and struct
+    pub tests: bool,
+    /// Follow each class with a `make_<name>` factory taking every field
+    /// as a keyword argument
+    pub builder: bool,
+    /// Give classes a `SCHEMA_HASH` class attribute fingerprinting their
+    /// definition
+    pub schema_hash: bool,
+    /// Types replacing OML ones, imported from their module where used
+    pub type_map: TypeMap,
+    pub style: CodeStyle,
 }
 
 impl BackwardsGenerate for PythonGenerator {
@@ -47,10 +109,10 @@ impl BackwardsGenerate for PythonGenerator {
                             if let Some(var) = parse_python_dataclass_field(line, true, is_frozen) {
                                 vars.push(var);
                             }
-                        } else if line.contains(": ") && !line.starts_with("def ") && !line.starts_with("@") {
-                            if let Some(var) = parse_python_dataclass_field(line, false, is_frozen) {
-                                vars.push(var);
-                            }
+                        } else if line.contains(": ") && !line.starts_with("def ") && !line.starts_with("@")
+                            && let Some(var) = parse_python_dataclass_field(line, false, is_frozen)
+                        {
+                            vars.push(var);
                         }
                         i += 1;
                     }
@@ -58,6 +120,7 @@ impl BackwardsGenerate for PythonGenerator {
                         oml_type: ObjectType::CLASS,
                         name,
                         variables: vars,
+                        ..Default::default()
                     });
                     continue;
                 }
@@ -90,6 +153,7 @@ impl BackwardsGenerate for PythonGenerator {
                             var_type: "string".to_string(),
                             array_kind: ArrayKind::None,
                             name: variant_name,
+                            ..Default::default()
                         });
                     }
                     i += 1;
@@ -98,6 +162,7 @@ impl BackwardsGenerate for PythonGenerator {
                     oml_type: ObjectType::ENUM,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
                 continue;
             }
@@ -128,6 +193,7 @@ impl BackwardsGenerate for PythonGenerator {
                                 var_type,
                                 array_kind,
                                 name: pname,
+                                ..Default::default()
                             });
                         }
                     }
@@ -141,6 +207,7 @@ impl BackwardsGenerate for PythonGenerator {
                     oml_type: ObjectType::CLASS,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
                 continue;
             }
@@ -189,6 +256,7 @@ fn parse_python_classvar(line: &str) -> Option<Variable> {
         var_type,
         array_kind,
         name,
+        ..Default::default()
     })
 }
 
@@ -215,6 +283,7 @@ fn parse_python_dataclass_field(line: &str, is_optional: bool, is_frozen: bool)
             var_type,
             array_kind,
             name,
+            ..Default::default()
         });
     }
 
@@ -225,6 +294,7 @@ fn parse_python_dataclass_field(line: &str, is_optional: bool, is_frozen: bool)
         var_type,
         array_kind,
         name,
+        ..Default::default()
     })
 }
 
@@ -259,19 +329,254 @@ fn extract_python_init_params(line: &str) -> Vec<(String, String, bool)> {
 }
 impl PythonGenerator {
     pub fn new(use_data_class: bool) -> Self {
-        Self { use_data_class }
+        Self {
+            use_data_class,
+            pydantic: false,
+            attrs: false,
+            stubs: false,
+            dict_helpers: false,
+            enum_style: PythonEnumStyle::Positional,
+            unique_enums: false,
+            snake_case: false,
+            slots: false,
+            kw_only: false,
+            frozen: None,
+            validate: false,
+            package_init: false,
+            accessors: AccessorMode::All,
+            tests: false,
+            builder: false,
+            schema_hash: false,
+            type_map: TypeMap::default(),
+            style: CodeStyle::default(),
+        }
+    }
+
+    pub fn with_pydantic(mut self, pydantic: bool) -> Self {
+        self.pydantic = pydantic;
+        self
+    }
+
+    pub fn with_attrs(mut self, attrs: bool) -> Self {
+        self.attrs = attrs;
+        self
+    }
+
+    pub fn with_stubs(mut self, stubs: bool) -> Self {
+        self.stubs = stubs;
+        self
+    }
+
+    pub fn with_dict_helpers(mut self, dict_helpers: bool) -> Self {
+        self.dict_helpers = dict_helpers;
+        self
+    }
+
+    pub fn with_enum_style(mut self, enum_style: PythonEnumStyle) -> Self {
+        self.enum_style = enum_style;
+        self
+    }
+
+    pub fn with_unique_enums(mut self, unique_enums: bool) -> Self {
+        self.unique_enums = unique_enums;
+        self
+    }
+
+    pub fn with_snake_case(mut self, snake_case: bool) -> Self {
+        self.snake_case = snake_case;
+        self
+    }
+
+    pub fn with_slots(mut self, slots: bool) -> Self {
+        self.slots = slots;
+        self
+    }
+
+    pub fn with_kw_only(mut self, kw_only: bool) -> Self {
+        self.kw_only = kw_only;
+        self
+    }
+
+    pub fn with_frozen(mut self, frozen: Option<&[String]>) -> Self {
+        self.frozen = frozen.map(<[String]>::to_vec);
+        self
+    }
+
+    pub fn with_validate(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    pub fn with_package_init(mut self, package_init: bool) -> Self {
+        self.package_init = package_init;
+        self
+    }
+
+    pub fn with_accessors(mut self, accessors: AccessorMode) -> Self {
+        self.accessors = accessors;
+        self
+    }
+
+    pub fn with_tests(mut self, tests: bool) -> Self {
+        self.tests = tests;
+        self
+    }
+
+    pub fn with_builder(mut self, builder: bool) -> Self {
+        self.builder = builder;
+        self
+    }
+
+    pub fn with_schema_hash(mut self, schema_hash: bool) -> Self {
+        self.schema_hash = schema_hash;
+        self
+    }
+
+    pub fn with_type_map(mut self, type_map: TypeMap) -> Self {
+        self.type_map = type_map;
+        self
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
     }
 }
 
+/// Words reserved in Python, which fields are renamed away from.
+const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in",
+    "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
 impl Generate for PythonGenerator {
     fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
-        let mut py_file = String::new();
+        self.render(oml_objects, file_name, false)
+    }
+
+    fn extension(&self) -> &str { "py" }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn generate_files(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        let mut files = vec![GeneratedFile::new(format!("{}.py", file_name), self.generate(oml_objects, file_name)?)];
+        if self.stubs {
+            files.push(GeneratedFile::new(format!("{}.pyi", file_name), self.render(oml_objects, file_name, true)?));
+        }
+        if self.tests {
+            files.extend(self.generate_tests(oml_objects, file_name)?);
+        }
+        Ok(files)
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Snake,
+            constants: Case::ScreamingSnake,
+            enum_values: Case::ScreamingSnake,
+        }
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("#")
+    }
+
+    /// An `__init__.py` importing the types of every module relative to the
+    /// package and listing them in `__all__`.
+    fn generate_package_files(&self, modules: &[(&str, &[OmlObject])]) -> Result<Vec<GeneratedFile>, Box<dyn Error>> {
+        if !self.package_init || modules.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut py_file = CodeWriter::new(self.style);
+        let sources: Vec<String> = modules.iter().map(|(name, _)| format!("{}.oml", name)).collect();
+        writeln!(py_file, "# This file has been generated from {}", sources.join(", "))?;
+        writeln!(py_file)?;
+
+        // A type declared by several modules is exported from the first one
+        let mut exported = HashSet::new();
+        let mut exports = Vec::new();
+        for (module, objects) in modules {
+            let names: Vec<&str> = objects
+                .iter()
+                .map(|o| o.name.as_str())
+                .filter(|name| exported.insert(*name))
+                .collect();
+            if !names.is_empty() {
+                writeln!(py_file, "from .{} import {}", module, names.join(", "))?;
+                exports.extend(names);
+            }
+        }
+        writeln!(py_file)?;
+
+        writeln!(py_file, "__all__ = [")?;
+        for name in exports {
+            writeln!(py_file, "{}\"{}\",", py_file.indent(1), name)?;
+        }
+        writeln!(py_file, "]")?;
+
+        Ok(vec![GeneratedFile::new("__init__.py", py_file.finish())])
+    }
+}
+
+impl PythonGenerator {
+    /// The module, or its stub when `stub` is set. Only regular classes
+    /// differ: their properties are declared without bodies, while the
+    /// declarative dataclass, attrs and pydantic classes are already precise.
+    fn render(&self, oml_objects: &[OmlObject], file_name: &str, stub: bool) -> Result<String, Box<dyn Error>> {
+        let mut py_file = CodeWriter::new(self.style);
 
         writeln!(py_file, "# This file has been generated from {}.oml", file_name)?;
         writeln!(py_file)?;
 
+        let mapped_imports: Vec<String> = self.type_map
+            .used_includes(oml_objects)
+            .iter()
+            .filter_map(|o| o.include.as_ref().map(|module| format!("from {} import {}", module, o.target_type)))
+            .collect();
+        // fingerprints are taken before the target's types are substituted
+        let schema_hashes: Vec<u64> = oml_objects.iter().map(schema_hash).collect();
+        let objects = self.type_map.apply(oml_objects);
+        let oml_objects = objects.as_slice();
+
         // Collect imports needed across all objects
-        let imports = collect_imports(oml_objects, self.use_data_class);
+        let mut imports = if self.pydantic {
+            collect_pydantic_imports(oml_objects)
+        } else if self.attrs {
+            collect_attrs_imports(oml_objects)
+        } else {
+            collect_imports(oml_objects, self.use_data_class)
+        };
+        if let Some(import) = imports.iter_mut().find(|i| *i == "from enum import Enum") {
+            *import = self.enum_import();
+        }
+        if self.schema_hash && oml_objects.iter().any(|o| o.oml_type != ObjectType::ENUM) {
+            add_typing_import(&mut imports, "ClassVar");
+        }
+        let dict_helpers = self.dict_helpers && !self.pydantic;
+        if dict_helpers && oml_objects.iter().any(|o| o.oml_type != ObjectType::ENUM) {
+            let future = imports.iter().take_while(|i| i.starts_with("from __future__")).count();
+            imports.insert(future, "import json".to_string());
+        }
+        for import in mapped_imports {
+            if !imports.contains(&import) {
+                imports.push(import);
+            }
+        }
+        let enums: HashSet<&str> = oml_objects
+            .iter()
+            .filter(|o| o.oml_type == ObjectType::ENUM)
+            .map(|o| o.name.as_str())
+            .collect();
+        // mapped and external types are stored as they are, like builtins
+        let plain: HashSet<&str> = self.type_map
+            .target_types()
+            .chain(oml_objects.iter().flat_map(|o| &o.variables).filter(|v| v.external).map(|v| v.var_type.as_str()))
+            .collect();
         if !imports.is_empty() {
             for import in &imports {
                 writeln!(py_file, "{}", import)?;
@@ -279,22 +584,156 @@ impl Generate for PythonGenerator {
             writeln!(py_file)?;
         }
 
-        for (i, oml_object) in oml_objects.iter().enumerate() {
+        let renamed = self.renamed(oml_objects);
+
+        for (i, (original, oml_object)) in oml_objects.iter().zip(&renamed).enumerate() {
+            let schema_hash = self.schema_hash.then_some(schema_hashes[i]);
+            let empty = oml_object.variables.is_empty() && schema_hash.is_none();
             match &oml_object.oml_type {
-                ObjectType::ENUM => generate_enum(oml_object, &mut py_file)?,
-                ObjectType::CLASS => generate_class(oml_object, &mut py_file, self.use_data_class)?,
-                ObjectType::STRUCT => generate_class(oml_object, &mut py_file, true)?,
+                ObjectType::ENUM => self.generate_enum(oml_object, &mut py_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT if self.pydantic => generate_pydantic_model(oml_object, schema_hash, &mut py_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT if self.attrs => generate_attrs_class(oml_object, schema_hash, &mut py_file)?,
+                ObjectType::CLASS if stub && !self.use_data_class => generate_regular_class_stub(oml_object, schema_hash, self.accessors, &mut py_file)?,
+                ObjectType::CLASS => self.generate_class(oml_object, schema_hash, self.use_data_class, &mut py_file)?,
+                ObjectType::STRUCT => self.generate_class(oml_object, schema_hash, true, &mut py_file)?,
                 ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
             }
+            if dict_helpers && oml_object.oml_type != ObjectType::ENUM {
+                // regular classes end on a blank line unless they are empty
+                let regular = oml_object.oml_type == ObjectType::CLASS && !self.use_data_class && !self.attrs;
+                if stub && regular {
+                    generate_dict_helper_stubs(oml_object, &mut py_file)?;
+                } else {
+                    let separate = !regular || empty;
+                    generate_dict_helpers(oml_object, original, &enums, &plain, separate, &mut py_file)?;
+                }
+            }
+            if self.builder && oml_object.oml_type != ObjectType::ENUM {
+                // non-empty regular classes already end on a blank line
+                let regular = oml_object.oml_type == ObjectType::CLASS && !self.use_data_class && !self.attrs && !self.pydantic;
+                if !regular || stub || dict_helpers || empty {
+                    writeln!(py_file)?;
+                }
+                generate_factory(oml_object, stub, &mut py_file)?;
+            }
             if i < oml_objects.len() - 1 {
                 writeln!(py_file)?;
             }
         }
 
-        Ok(py_file)
+        Ok(py_file.finish())
     }
 
-    fn extension(&self) -> &str { "py" }
+    /// The objects with their fields renamed as they appear in the module;
+    /// enum variants are already upper case, and static constants become
+    /// SCREAMING_SNAKE_CASE as PEP 8 has them.
+    pub(super) fn renamed(&self, oml_objects: &[OmlObject]) -> Vec<OmlObject> {
+        oml_objects
+            .iter()
+            .map(|o| {
+                let mut o = o.clone();
+                if self.snake_case && o.oml_type != ObjectType::ENUM {
+                    for var in &mut o.variables {
+                        var.name = match var.is_static() && var.is_const() {
+                            true => Case::ScreamingSnake.apply(&var.name),
+                            false => to_snake_case(&var.name),
+                        };
+                    }
+                }
+                o
+            })
+            .collect()
+    }
+
+    fn generate_class(&self, oml_object: &OmlObject, schema_hash: Option<u64>, use_data_class: bool, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        if use_data_class {
+            generate_data_class(oml_object, schema_hash, &self.dataclass_args(oml_object), self.validate, py_file)
+        } else {
+            generate_regular_class(oml_object, schema_hash, self.accessors, self.validate, py_file)
+        }
+    }
+
+    pub(super) fn dataclass_args(&self, oml_object: &OmlObject) -> DataclassArgs {
+        let frozen = match &self.frozen {
+            Some(names) => names.contains(&oml_object.name),
+            None => {
+                let mut fields = oml_object.variables.iter().filter(|v| !v.var_mod.contains(&VariableModifier::STATIC)).peekable();
+                fields.peek().is_some() && fields.all(|v| v.var_mod.contains(&VariableModifier::CONST))
+            }
+        };
+        DataclassArgs { frozen, slots: self.slots, kw_only: self.kw_only }
+    }
+
+    fn enum_import(&self) -> String {
+        let mut names = vec![match self.enum_style {
+            PythonEnumStyle::Positional | PythonEnumStyle::Auto => "Enum",
+            PythonEnumStyle::Int => "IntEnum",
+            PythonEnumStyle::Str => "StrEnum",
+        }];
+        if self.enum_style == PythonEnumStyle::Auto {
+            names.push("auto");
+        }
+        if self.unique_enums {
+            names.push("unique");
+        }
+        format!("from enum import {}", names.join(", "))
+    }
+
+    fn generate_enum(&self, oml_object: &OmlObject, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let base = match self.enum_style {
+            PythonEnumStyle::Positional | PythonEnumStyle::Auto => "Enum",
+            PythonEnumStyle::Int => "IntEnum",
+            PythonEnumStyle::Str => "StrEnum",
+        };
+        if self.unique_enums {
+            writeln!(py_file, "@unique")?;
+        }
+        writeln!(py_file, "class {}({}):", oml_object.name, base)?;
+
+        if oml_object.variables.is_empty() {
+            writeln!(py_file, "{}pass", py_file.indent(1))?;
+        } else {
+            for (index, var) in oml_object.variables.iter().enumerate() {
+                let name = var.name.to_uppercase();
+                let value = match self.enum_style {
+                    PythonEnumStyle::Positional | PythonEnumStyle::Int => index.to_string(),
+                    PythonEnumStyle::Auto => "auto()".to_string(),
+                    PythonEnumStyle::Str => format!("\"{}\"", name),
+                };
+                writeln!(py_file, "{}{} = {}", py_file.indent(1), name, value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds `name` to the `from typing import` line, keeping the names sorted,
+/// or adds the line before the third-party imports.
+fn add_typing_import(imports: &mut Vec<String>, name: &str) {
+    if let Some(import) = imports.iter_mut().find(|i| i.starts_with("from typing import ")) {
+        let mut names: Vec<&str> = import["from typing import ".len()..].split(", ").collect();
+        if !names.contains(&name) {
+            names.push(name);
+            names.sort_unstable();
+            *import = format!("from typing import {}", names.join(", "));
+        }
+        return;
+    }
+    let position = imports
+        .iter()
+        .position(|i| i.starts_with("from pydantic") || i.starts_with("from attrs"))
+        .unwrap_or(imports.len());
+    imports.insert(position, format!("from typing import {}", name));
+}
+
+/// `SCHEMA_HASH`, first in the class body, when the class embeds its
+/// fingerprint.
+fn write_schema_hash(schema_hash: Option<u64>, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    if let Some(hash) = schema_hash {
+        writeln!(py_file, "{}{}: ClassVar[int] = 0x{:016x}", py_file.indent(1), SCHEMA_HASH, hash)?;
+    }
+    Ok(())
 }
 
 fn collect_imports(oml_objects: &[OmlObject], use_data_class: bool) -> Vec<String> {
@@ -337,35 +776,90 @@ fn collect_imports(oml_objects: &[OmlObject], use_data_class: bool) -> Vec<Strin
     imports
 }
 
-fn generate_enum(oml_object: &OmlObject, py_file: &mut String) -> Result<(), std::fmt::Error> {
-    writeln!(py_file, "class {}(Enum):", oml_object.name)?;
+fn collect_pydantic_imports(oml_objects: &[OmlObject]) -> Vec<String> {
+    let mut imports: Vec<String> = Vec::new();
+    let vars = || oml_objects.iter().filter(|o| o.oml_type != ObjectType::ENUM).flat_map(|o| &o.variables);
 
-    if oml_object.variables.is_empty() {
-        writeln!(py_file, "\tpass")?;
-    } else {
-        for (index, var) in oml_object.variables.iter().enumerate() {
-            writeln!(py_file, "\t{} = {}", var.name.to_uppercase(), index)?;
-        }
+    // models may refer to ones defined further down; pydantic resolves the
+    // postponed annotations once the module is loaded
+    if oml_objects.iter().any(|o| o.oml_type != ObjectType::ENUM) {
+        imports.push("from __future__ import annotations".to_string());
+    }
+    if oml_objects.iter().any(|o| o.oml_type == ObjectType::ENUM) {
+        imports.push("from enum import Enum".to_string());
     }
 
-    Ok(())
+    let mut typing_imports: Vec<&str> = Vec::new();
+    if vars().any(|v| v.array_kind != ArrayKind::None && !value_constraints(&v.var_type).is_empty()) {
+        typing_imports.push("Annotated");
+    }
+    if vars().any(|v| v.var_mod.contains(&VariableModifier::STATIC)) {
+        typing_imports.push("ClassVar");
+    }
+    if vars().any(|v| v.var_mod.contains(&VariableModifier::OPTIONAL)) {
+        typing_imports.push("Optional");
+    }
+    if !typing_imports.is_empty() {
+        imports.push(format!("from typing import {}", typing_imports.join(", ")));
+    }
+
+    if oml_objects.iter().any(|o| o.oml_type != ObjectType::ENUM) {
+        imports.push("from pydantic import BaseModel, Field".to_string());
+    }
+
+    imports
 }
 
-fn generate_class(
-    oml_object: &OmlObject,
-    py_file: &mut String,
-    use_data_class: bool,
-) -> Result<(), std::fmt::Error> {
-    if use_data_class {
-        generate_data_class(oml_object, py_file)
-    } else {
-        generate_regular_class(oml_object, py_file)
+fn collect_attrs_imports(oml_objects: &[OmlObject]) -> Vec<String> {
+    let mut imports: Vec<String> = Vec::new();
+    let has_types = oml_objects.iter().any(|o| o.oml_type != ObjectType::ENUM);
+    let vars = || oml_objects.iter().filter(|o| o.oml_type != ObjectType::ENUM).flat_map(|o| &o.variables);
+
+    // attrs evaluates annotations eagerly otherwise, so classes could not
+    // refer to ones defined further down
+    if has_types {
+        imports.push("from __future__ import annotations".to_string());
+    }
+    if oml_objects.iter().any(|o| o.oml_type == ObjectType::ENUM) {
+        imports.push("from enum import Enum".to_string());
+    }
+
+    let mut typing_imports: Vec<&str> = Vec::new();
+    if vars().any(|v| v.var_mod.contains(&VariableModifier::STATIC)) {
+        typing_imports.push("ClassVar");
+    }
+    if vars().any(|v| v.var_mod.contains(&VariableModifier::OPTIONAL)) {
+        typing_imports.push("Optional");
+    }
+    if !typing_imports.is_empty() {
+        imports.push(format!("from typing import {}", typing_imports.join(", ")));
     }
+
+    if vars().any(|v| v.var_mod.contains(&VariableModifier::OPTIONAL) && !v.var_mod.contains(&VariableModifier::STATIC)) {
+        imports.push("from attrs import define, field".to_string());
+    } else if has_types {
+        imports.push("from attrs import define".to_string());
+    }
+
+    imports
 }
 
 // ── dataclass ────────────────────────────────────────────────────────────────
 
-fn generate_data_class(oml_object: &OmlObject, py_file: &mut String) -> Result<(), std::fmt::Error> {
+/// Keyword arguments of the `@dataclass` decorator.
+pub(super) struct DataclassArgs {
+    pub(super) frozen: bool,
+    slots: bool,
+    kw_only: bool,
+}
+
+fn generate_data_class(
+    oml_object: &OmlObject,
+    schema_hash: Option<u64>,
+    args: &DataclassArgs,
+    validate: bool,
+    py_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
     let vars = &oml_object.variables;
 
     let static_vars: Vec<&Variable> = vars.iter()
@@ -376,25 +870,28 @@ fn generate_data_class(oml_object: &OmlObject, py_file: &mut String) -> Result<(
         .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
         .collect();
 
-    let all_const = !instance_vars.is_empty() && instance_vars.iter()
-        .all(|v| v.var_mod.contains(&VariableModifier::CONST));
+    let flags: Vec<&str> = [(args.frozen, "frozen=True"), (args.slots, "slots=True"), (args.kw_only, "kw_only=True")]
+        .into_iter()
+        .filter_map(|(set, flag)| set.then_some(flag))
+        .collect();
 
-    if all_const {
-        writeln!(py_file, "@dataclass(frozen=True)")?;
-    } else {
+    if flags.is_empty() {
         writeln!(py_file, "@dataclass")?;
+    } else {
+        writeln!(py_file, "@dataclass({})", flags.join(", "))?;
     }
     writeln!(py_file, "class {}:", oml_object.name)?;
 
-    if vars.is_empty() {
-        writeln!(py_file, "\tpass")?;
+    if vars.is_empty() && schema_hash.is_none() {
+        writeln!(py_file, "{}pass", py_file.indent(1))?;
         return Ok(());
     }
+    write_schema_hash(schema_hash, py_file)?;
 
     // Static (ClassVar) fields first
     for var in &static_vars {
         let py_type = type_annotation(&var.var_type, &var.array_kind);
-        writeln!(py_file, "\t{}: ClassVar[{}]", var.name, py_type)?;
+        writeln!(py_file, "{}{}: ClassVar[{}]{}", py_file.indent(1), var.name, py_type, initialiser(var))?;
     }
 
     // Required instance fields (non-optional, non-static) — required first
@@ -408,20 +905,55 @@ fn generate_data_class(oml_object: &OmlObject, py_file: &mut String) -> Result<(
 
     for var in &required {
         let py_type = type_annotation(&var.var_type, &var.array_kind);
-        writeln!(py_file, "\t{}: {}", var.name, py_type)?;
+        writeln!(py_file, "{}{}: {}", py_file.indent(1), var.name, py_type)?;
     }
 
     for var in &optional {
         let py_type = type_annotation(&var.var_type, &var.array_kind);
-        writeln!(py_file, "\t{}: Optional[{}] = None", var.name, py_type)?;
+        writeln!(py_file, "{}{}: Optional[{}] = None", py_file.indent(1), var.name, py_type)?;
+    }
+
+    let checked: Vec<&&Variable> = instance_vars.iter().filter(|v| has_checks(v)).collect();
+    if validate && !checked.is_empty() {
+        writeln!(py_file)?;
+        writeln!(py_file, "{}def __post_init__(self) -> None:", py_file.indent(1))?;
+        for var in checked {
+            write_checks(var, &format!("self.{}", var.name), 2, py_file)?;
+        }
     }
 
     Ok(())
 }
 
+/// ` = <value>` for a field declaring one, in Python's spelling, or nothing.
+fn initialiser(var: &Variable) -> String {
+    match var.value.as_deref() {
+        Some("true") => " = True".to_string(),
+        Some("false") => " = False".to_string(),
+        Some(value) => format!(" = {}", value),
+        None => String::new(),
+    }
+}
+
 // ── regular class ─────────────────────────────────────────────────────────────
 
-fn generate_regular_class(oml_object: &OmlObject, py_file: &mut String) -> Result<(), std::fmt::Error> {
+/// The instance attribute holding `var`: `_name` behind a property, or the
+/// plain `name`.
+fn attribute(var: &Variable, accessors: AccessorMode) -> String {
+    if accessors.has_accessors(var) {
+        format!("_{}", var.name)
+    } else {
+        var.name.clone()
+    }
+}
+
+fn generate_regular_class(
+    oml_object: &OmlObject,
+    schema_hash: Option<u64>,
+    accessors: AccessorMode,
+    validate: bool,
+    py_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
     let vars = &oml_object.variables;
 
     let static_vars: Vec<&Variable> = vars.iter()
@@ -434,30 +966,31 @@ fn generate_regular_class(oml_object: &OmlObject, py_file: &mut String) -> Resul
 
     writeln!(py_file, "class {}:", oml_object.name)?;
 
-    if vars.is_empty() {
-        writeln!(py_file, "\tpass")?;
+    if vars.is_empty() && schema_hash.is_none() {
+        writeln!(py_file, "{}pass", py_file.indent(1))?;
         return Ok(());
     }
 
     // Class-level static variables
+    write_schema_hash(schema_hash, py_file)?;
     for var in &static_vars {
         let py_type = type_annotation(&var.var_type, &var.array_kind);
-        if var.var_mod.contains(&VariableModifier::CONST) {
-            writeln!(py_file, "\t{}: {} = ...", var.name, py_type)?;
+        if var.value.is_none() && var.var_mod.contains(&VariableModifier::CONST) {
+            writeln!(py_file, "{}{}: {} = ...", py_file.indent(1), var.name, py_type)?;
         } else {
-            writeln!(py_file, "\t{}: {}", var.name, py_type)?;
+            writeln!(py_file, "{}{}: {}{}", py_file.indent(1), var.name, py_type, initialiser(var))?;
         }
     }
 
-    if !static_vars.is_empty() {
+    if !static_vars.is_empty() || schema_hash.is_some() {
         writeln!(py_file)?;
     }
 
     // __slots__
     if !instance_vars.is_empty() {
-        write!(py_file, "\t__slots__ = (")?;
+        write!(py_file, "{}__slots__ = (", py_file.indent(1))?;
         for var in &instance_vars {
-            write!(py_file, "'_{}', ", var.name)?;
+            write!(py_file, "'{}', ", attribute(var, accessors))?;
         }
         writeln!(py_file, ")")?;
         writeln!(py_file)?;
@@ -473,7 +1006,7 @@ fn generate_regular_class(oml_object: &OmlObject, py_file: &mut String) -> Resul
         .collect();
 
     if !instance_vars.is_empty() {
-        write!(py_file, "\tdef __init__(self")?;
+        write!(py_file, "{}def __init__(self", py_file.indent(1))?;
         for var in &required {
             let py_type = type_annotation(&var.var_type, &var.array_kind);
             write!(py_file, ", {}: {}", var.name, py_type)?;
@@ -484,14 +1017,19 @@ fn generate_regular_class(oml_object: &OmlObject, py_file: &mut String) -> Resul
         }
         writeln!(py_file, "):")?;
 
+        if validate {
+            for var in &instance_vars {
+                write_checks(var, &var.name, 2, py_file)?;
+            }
+        }
         for var in &instance_vars {
-            writeln!(py_file, "\t\tself._{} = {}", var.name, var.name)?;
+            writeln!(py_file, "{}self.{} = {}", py_file.indent(2), attribute(var, accessors), var.name)?;
         }
         writeln!(py_file)?;
     }
 
     // Properties (getters + setters)
-    for var in &instance_vars {
+    for var in instance_vars.iter().filter(|v| accessors.has_accessors(v)) {
         let py_type = type_annotation(&var.var_type, &var.array_kind);
         let is_const = var.var_mod.contains(&VariableModifier::CONST);
         let is_optional = var.var_mod.contains(&VariableModifier::OPTIONAL);
@@ -503,100 +1041,591 @@ fn generate_regular_class(oml_object: &OmlObject, py_file: &mut String) -> Resul
         };
 
         // getter
-        writeln!(py_file, "\t@property")?;
-        writeln!(py_file, "\tdef {}(self) -> {}:", var.name, return_type)?;
-        writeln!(py_file, "\t\treturn self._{}", var.name)?;
+        writeln!(py_file, "{}@property", py_file.indent(1))?;
+        writeln!(py_file, "{}def {}(self) -> {}:", py_file.indent(1), var.name, return_type)?;
+        writeln!(py_file, "{}return self._{}", py_file.indent(2), var.name)?;
 
         // setter — only for non-const
         if !is_const {
-            writeln!(py_file, "\t@{}.setter", var.name)?;
-            writeln!(py_file, "\tdef {}(self, value: {}):", var.name, return_type)?;
-            writeln!(py_file, "\t\tself._{} = value", var.name)?;
+            writeln!(py_file, "{}@{}.setter", py_file.indent(1), var.name)?;
+            writeln!(py_file, "{}def {}(self, value: {}):", py_file.indent(1), var.name, return_type)?;
+            if validate {
+                write_checks(var, "value", 2, py_file)?;
+            }
+            writeln!(py_file, "{}self._{} = value", py_file.indent(2), var.name)?;
         }
 
         writeln!(py_file)?;
     }
 
+    if !instance_vars.is_empty() {
+        generate_dunder_methods(oml_object, &instance_vars, accessors, py_file)?;
+    }
+
     Ok(())
 }
 
-#[inline]
-fn convert_type(var_type: &str) -> String {
-    match var_type {
-        "int8" | "int16" | "int32" | "int64" => "int",
-        "uint8" | "uint16" | "uint32" | "uint64" => "int",
-        "float" | "double" => "float",
-        "bool" => "bool",
-        "string" | "char" => "str",
-        _ => var_type,
-    }.to_string()
-}
+/// `__eq__` and `__repr__` over the slots, as a dataclass would have, and
+/// `__hash__` when every field is const; otherwise defining `__eq__` leaves
+/// the class unhashable. Lists are hashed as tuples.
+fn generate_dunder_methods(
+    oml_object: &OmlObject,
+    instance_vars: &[&Variable],
+    accessors: AccessorMode,
+    py_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let tuple = |prefix: &str| {
+        let items: Vec<String> = instance_vars.iter().map(|v| format!("{}.{}", prefix, attribute(v, accessors))).collect();
+        if items.len() == 1 {
+            format!("({},)", items[0])
+        } else {
+            format!("({})", items.join(", "))
+        }
+    };
 
-fn type_annotation(var_type: &str, array_kind: &ArrayKind) -> String {
-    let base = convert_type(var_type);
-    match array_kind {
-        ArrayKind::None => base,
-        ArrayKind::Static(_) | ArrayKind::Dynamic => format!("list[{}]", base),
+    writeln!(py_file, "{}def __eq__(self, other: object) -> bool:", py_file.indent(1))?;
+    writeln!(py_file, "{}if not isinstance(other, {}):", py_file.indent(2), name)?;
+    writeln!(py_file, "{}return NotImplemented", py_file.indent(3))?;
+    writeln!(py_file, "{}return {} == {}", py_file.indent(2), tuple("self"), tuple("other"))?;
+    writeln!(py_file)?;
+
+    let fields: Vec<String> = instance_vars.iter().map(|v| format!("{}={{self.{}!r}}", v.name, attribute(v, accessors))).collect();
+    writeln!(py_file, "{}def __repr__(self) -> str:", py_file.indent(1))?;
+    writeln!(py_file, "{}return f\"{}({})\"", py_file.indent(2), name, fields.join(", "))?;
+    writeln!(py_file)?;
+
+    if instance_vars.iter().all(|v| v.var_mod.contains(&VariableModifier::CONST)) {
+        let items: Vec<String> = instance_vars.iter().map(|v| {
+            let attribute = attribute(v, accessors);
+            match (&v.array_kind, v.var_mod.contains(&VariableModifier::OPTIONAL)) {
+                (ArrayKind::None, _) => format!("self.{}", attribute),
+                (_, false) => format!("tuple(self.{})", attribute),
+                (_, true) => format!("None if self.{} is None else tuple(self.{})", attribute, attribute),
+            }
+        }).collect();
+        let hashed = if items.len() == 1 { format!("({},)", items[0]) } else { format!("({})", items.join(", ")) };
+        writeln!(py_file, "{}def __hash__(self) -> int:", py_file.indent(1))?;
+        writeln!(py_file, "{}return hash({})", py_file.indent(2), hashed)?;
+        writeln!(py_file)?;
     }
+
+    Ok(())
 }
 
+/// Whether the OML type of `var` implies anything to check beyond its
+/// Python type: an integer range, a single character or a fixed length.
+fn has_checks(var: &Variable) -> bool {
+    matches!(var.array_kind, ArrayKind::Static(_)) || !value_constraints(&var.var_type).is_empty()
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core::oml_object::{ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind};
+/// Raises `ValueError` when the value of `expr` breaks the constraints of
+/// `var`, skipping `None` for optional fields.
+fn write_checks(var: &Variable, expr: &str, level: usize, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &var.name;
+    let mut checks: Vec<(String, String)> = Vec::new();
 
-    fn to_python(oml_object: &OmlObject, use_data_class: bool) -> String {
-        PythonGenerator::new(use_data_class)
-            .generate(std::slice::from_ref(oml_object), "test")
-            .unwrap()
+    if let ArrayKind::Static(n) = var.array_kind {
+        checks.push((format!("len({}) != {}", expr, n), format!("{} must have {} items, got {{len({})}}", name, n, expr)));
     }
-
-    // ── helpers ──────────────────────────────────────────────────────────────
-
-    fn var(name: &str, ty: &str, mods: Vec<VariableModifier>) -> Variable {
-        Variable {
-            var_mod: mods,
-            visibility: VariableVisibility::PRIVATE,
-            var_type: ty.to_string(),
-            array_kind: ArrayKind::None,
-            name: name.to_string(),
+    let single = var.array_kind == ArrayKind::None;
+    if let Some((min, max)) = int_range(&var.var_type) {
+        if single {
+            checks.push((
+                format!("not {} <= {} <= {}", min, expr, max),
+                format!("{} must be between {} and {}, got {{{}!r}}", name, min, max, expr),
+            ));
+        } else {
+            checks.push((
+                format!("any(not {} <= x <= {} for x in {})", min, max, expr),
+                format!("{} items must be between {} and {}", name, min, max),
+            ));
+        }
+    } else if var.var_type == "char" {
+        if single {
+            checks.push((format!("len({}) != 1", expr), format!("{} must be a single character, got {{{}!r}}", name, expr)));
+        } else {
+            checks.push((format!("any(len(x) != 1 for x in {})", expr), format!("{} items must be single characters", name)));
         }
     }
 
-    // ── enum ─────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn test_enum_basic() {
-        let obj = OmlObject {
-            oml_type: ObjectType::ENUM,
-            name: "Color".to_string(),
-            variables: vec![
-                var("Red", "", vec![]),
-                var("Green", "", vec![]),
-                var("Blue", "", vec![]),
-            ],
-        };
-        let out = to_python(&obj, false);
-        assert!(out.contains("from enum import Enum"));
-        assert!(out.contains("class Color(Enum):"));
-        assert!(out.contains("\tRED = 0"));
-        assert!(out.contains("\tGREEN = 1"));
-        assert!(out.contains("\tBLUE = 2"));
+    if checks.is_empty() {
+        return Ok(());
+    }
+    let mut level = level;
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        writeln!(py_file, "{}if {} is not None:", py_file.indent(level), expr)?;
+        level += 1;
+    }
+    for (condition, message) in checks {
+        writeln!(py_file, "{}if {}:", py_file.indent(level), condition)?;
+        let prefix = if message.contains('{') { "f" } else { "" };
+        writeln!(py_file, "{}raise ValueError({}\"{}\")", py_file.indent(level + 1), prefix, message)?;
     }
 
-    #[test]
+    Ok(())
+}
+
+/// The typed surface of `generate_regular_class`: statics, the `__init__`
+/// signature and the properties, with `...` bodies.
+fn generate_regular_class_stub(oml_object: &OmlObject, schema_hash: Option<u64>, accessors: AccessorMode, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let (static_vars, instance_vars): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    if oml_object.variables.is_empty() && schema_hash.is_none() {
+        writeln!(py_file, "class {}: ...", oml_object.name)?;
+        return Ok(());
+    }
+    writeln!(py_file, "class {}:", oml_object.name)?;
+    write_schema_hash(schema_hash, py_file)?;
+
+    for var in &static_vars {
+        let py_type = type_annotation(&var.var_type, &var.array_kind);
+        writeln!(py_file, "{}{}: {}", py_file.indent(1), var.name, py_type)?;
+    }
+    if instance_vars.is_empty() {
+        return Ok(());
+    }
+    if !static_vars.is_empty() || schema_hash.is_some() {
+        writeln!(py_file)?;
+    }
+
+    let (optional, required): (Vec<&&Variable>, Vec<&&Variable>) = instance_vars
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::OPTIONAL));
+
+    write!(py_file, "{}def __init__(self", py_file.indent(1))?;
+    for var in &required {
+        write!(py_file, ", {}: {}", var.name, type_annotation(&var.var_type, &var.array_kind))?;
+    }
+    for var in &optional {
+        write!(py_file, ", {}: Optional[{}] = ...", var.name, type_annotation(&var.var_type, &var.array_kind))?;
+    }
+    writeln!(py_file, ") -> None: ...")?;
+
+    for var in &instance_vars {
+        let mut py_type = type_annotation(&var.var_type, &var.array_kind);
+        if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            py_type = format!("Optional[{}]", py_type);
+        }
+        if !accessors.has_accessors(var) {
+            writeln!(py_file, "{}{}: {}", py_file.indent(1), var.name, py_type)?;
+            continue;
+        }
+        writeln!(py_file, "{}@property", py_file.indent(1))?;
+        writeln!(py_file, "{}def {}(self) -> {}: ...", py_file.indent(1), var.name, py_type)?;
+        if !var.var_mod.contains(&VariableModifier::CONST) {
+            writeln!(py_file, "{}@{}.setter", py_file.indent(1), var.name)?;
+            writeln!(py_file, "{}def {}(self, value: {}) -> None: ...", py_file.indent(1), var.name, py_type)?;
+        }
+    }
+
+    writeln!(py_file, "{}def __eq__(self, other: object) -> bool: ...", py_file.indent(1))?;
+    writeln!(py_file, "{}def __repr__(self) -> str: ...", py_file.indent(1))?;
+    if instance_vars.iter().all(|v| v.var_mod.contains(&VariableModifier::CONST)) {
+        writeln!(py_file, "{}def __hash__(self) -> int: ...", py_file.indent(1))?;
+    }
+
+    Ok(())
+}
+
+// ── dict helpers ─────────────────────────────────────────────────────────────
+
+/// Methods converting an instance to and from plain JSON data. Statics are
+/// left out, enums are stored by name and other OML types are expected to
+/// have the same helpers. Keys are the field names of `original`, before
+/// any renaming.
+fn generate_dict_helpers(
+    oml_object: &OmlObject,
+    original: &OmlObject,
+    enums: &HashSet<&str>,
+    plain: &HashSet<&str>,
+    separate: bool,
+    py_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let fields: Vec<(&Variable, &str)> = oml_object.variables
+        .iter()
+        .zip(&original.variables)
+        .filter(|(v, _)| !v.var_mod.contains(&VariableModifier::STATIC))
+        .map(|(v, o)| (v, o.name.as_str()))
+        .collect();
+
+    if separate {
+        writeln!(py_file)?;
+    }
+
+    writeln!(py_file, "{}def to_dict(self) -> dict:", py_file.indent(1))?;
+    if fields.is_empty() {
+        writeln!(py_file, "{}return {{}}", py_file.indent(2))?;
+    } else {
+        writeln!(py_file, "{}return {{", py_file.indent(2))?;
+        for (var, key) in &fields {
+            let value = dict_value(var, &format!("self.{}", var.name), enums, plain, true);
+            writeln!(py_file, "{}\"{}\": {},", py_file.indent(3), key, value)?;
+        }
+        writeln!(py_file, "{}}}", py_file.indent(2))?;
+    }
+    writeln!(py_file)?;
+
+    writeln!(py_file, "{}@classmethod", py_file.indent(1))?;
+    writeln!(py_file, "{}def from_dict(cls, data: dict) -> \"{}\":", py_file.indent(1), name)?;
+    if fields.is_empty() {
+        writeln!(py_file, "{}return cls()", py_file.indent(2))?;
+    } else {
+        writeln!(py_file, "{}return cls(", py_file.indent(2))?;
+        for (var, key) in &fields {
+            let raw = if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+                format!("data.get(\"{}\")", key)
+            } else {
+                format!("data[\"{}\"]", key)
+            };
+            writeln!(py_file, "{}{}={},", py_file.indent(3), var.name, dict_value(var, &raw, enums, plain, false))?;
+        }
+        writeln!(py_file, "{})", py_file.indent(2))?;
+    }
+    writeln!(py_file)?;
+
+    writeln!(py_file, "{}def to_json(self) -> str:", py_file.indent(1))?;
+    writeln!(py_file, "{}return json.dumps(self.to_dict())", py_file.indent(2))?;
+    writeln!(py_file)?;
+
+    writeln!(py_file, "{}@classmethod", py_file.indent(1))?;
+    writeln!(py_file, "{}def from_json(cls, text: str) -> \"{}\":", py_file.indent(1), name)?;
+    writeln!(py_file, "{}return cls.from_dict(json.loads(text))", py_file.indent(2))?;
+
+    Ok(())
+}
+
+fn generate_dict_helper_stubs(oml_object: &OmlObject, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    writeln!(py_file, "{}def to_dict(self) -> dict: ...", py_file.indent(1))?;
+    writeln!(py_file, "{}@classmethod", py_file.indent(1))?;
+    writeln!(py_file, "{}def from_dict(cls, data: dict) -> {}: ...", py_file.indent(1), name)?;
+    writeln!(py_file, "{}def to_json(self) -> str: ...", py_file.indent(1))?;
+    writeln!(py_file, "{}@classmethod", py_file.indent(1))?;
+    writeln!(py_file, "{}def from_json(cls, text: str) -> {}: ...", py_file.indent(1), name)?;
+    Ok(())
+}
+
+/// `expr` converted for `to_dict` (`encode`) or from the data passed to
+/// `from_dict`, element-wise for lists and passing `None` through. Types
+/// in `plain` are stored as they are.
+fn dict_value(var: &Variable, expr: &str, enums: &HashSet<&str>, plain: &HashSet<&str>, encode: bool) -> String {
+    let element = |x: &str| -> Option<String> {
+        match var.var_type.as_str() {
+            t if OmlObject::is_builtin_type(t) || plain.contains(t) => None,
+            t if enums.contains(t) && encode => Some(format!("{}.name", x)),
+            t if enums.contains(t) => Some(format!("{}[{}]", t, x)),
+            _ if encode => Some(format!("{}.to_dict()", x)),
+            t => Some(format!("{}.from_dict({})", t, x)),
+        }
+    };
+    let converted = match (&var.array_kind, element("x")) {
+        (_, None) => return expr.to_string(),
+        (ArrayKind::None, Some(_)) => element(expr).unwrap_or_default(),
+        (_, Some(item)) => format!("[{} for x in {}]", item, expr),
+    };
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        format!("None if {} is None else {}", expr, converted)
+    } else {
+        converted
+    }
+}
+
+// ── factory ──────────────────────────────────────────────────────────────────
+
+/// `make_<name>`, taking every instance field as a keyword-only argument
+/// with optional ones defaulting to `None`, so callers cannot mix up
+/// positions. Stubs get the signature alone.
+fn generate_factory(oml_object: &OmlObject, stub: bool, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let fields = builder_fields(oml_object);
+    let mut params = Vec::new();
+    if !fields.is_empty() {
+        params.push("*".to_string());
+    }
+    for BuilderField { var, required } in &fields {
+        let py_type = type_annotation(&var.var_type, &var.array_kind);
+        if *required {
+            params.push(format!("{}: {}", var.name, py_type));
+        } else {
+            params.push(format!("{}: Optional[{}] = None", var.name, py_type));
+        }
+    }
+    let args: Vec<String> = fields.iter().map(|f| format!("{}={}", f.var.name, f.var.name)).collect();
+
+    let function = format!("make_{}", to_snake_case(name));
+    let body = if stub { " ..." } else { "" };
+    let signature = format!("def {}({}) -> {}:{}", function, params.join(", "), name, body);
+    if py_file.fits(0, &signature) {
+        writeln!(py_file, "{}", signature)?;
+    } else {
+        writeln!(py_file, "def {}(", function)?;
+        for param in &params {
+            writeln!(py_file, "{}{},", py_file.indent(1), param)?;
+        }
+        writeln!(py_file, ") -> {}:{}", name, body)?;
+    }
+    if stub {
+        return Ok(());
+    }
+
+    let call = format!("return {}({})", name, args.join(", "));
+    if py_file.fits(1, &call) {
+        writeln!(py_file, "{}{}", py_file.indent(1), call)?;
+    } else {
+        writeln!(py_file, "{}return {}(", py_file.indent(1), name)?;
+        for arg in &args {
+            writeln!(py_file, "{}{},", py_file.indent(2), arg)?;
+        }
+        writeln!(py_file, "{})", py_file.indent(1))?;
+    }
+
+    Ok(())
+}
+
+// ── attrs ────────────────────────────────────────────────────────────────────
+
+/// Laid out like the dataclass: statics as `ClassVar`, then required fields,
+/// then optional ones defaulting to `None`, frozen when every field is const.
+fn generate_attrs_class(oml_object: &OmlObject, schema_hash: Option<u64>, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let (static_vars, instance_vars): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    let all_const = !instance_vars.is_empty() && instance_vars.iter()
+        .all(|v| v.var_mod.contains(&VariableModifier::CONST));
+
+    if all_const {
+        writeln!(py_file, "@define(frozen=True)")?;
+    } else {
+        writeln!(py_file, "@define")?;
+    }
+    writeln!(py_file, "class {}:", oml_object.name)?;
+
+    if oml_object.variables.is_empty() && schema_hash.is_none() {
+        writeln!(py_file, "{}pass", py_file.indent(1))?;
+        return Ok(());
+    }
+    write_schema_hash(schema_hash, py_file)?;
+
+    for var in &static_vars {
+        let py_type = type_annotation(&var.var_type, &var.array_kind);
+        writeln!(py_file, "{}{}: ClassVar[{}]{}", py_file.indent(1), var.name, py_type, initialiser(var))?;
+    }
+
+    let (optional, required): (Vec<&Variable>, Vec<&Variable>) = instance_vars
+        .into_iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::OPTIONAL));
+
+    for var in &required {
+        let py_type = type_annotation(&var.var_type, &var.array_kind);
+        writeln!(py_file, "{}{}: {}", py_file.indent(1), var.name, py_type)?;
+    }
+
+    for var in &optional {
+        let py_type = type_annotation(&var.var_type, &var.array_kind);
+        writeln!(py_file, "{}{}: Optional[{}] = field(default=None)", py_file.indent(1), var.name, py_type)?;
+    }
+
+    Ok(())
+}
+
+// ── pydantic ─────────────────────────────────────────────────────────────────
+
+/// Every instance field gets a `Field(...)`, carrying the constraints OML
+/// types imply: integer ranges, single-character strings and fixed array
+/// lengths. Constraints on array elements go into `Annotated`. Const fields
+/// are frozen individually, so models mixing const and mutable fields still
+/// validate on assignment.
+fn generate_pydantic_model(oml_object: &OmlObject, schema_hash: Option<u64>, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(py_file, "class {}(BaseModel):", oml_object.name)?;
+
+    if oml_object.variables.is_empty() && schema_hash.is_none() {
+        writeln!(py_file, "{}pass", py_file.indent(1))?;
+        return Ok(());
+    }
+    write_schema_hash(schema_hash, py_file)?;
+
+    for var in &oml_object.variables {
+        let py_type = pydantic_type(var);
+        if var.var_mod.contains(&VariableModifier::STATIC) {
+            writeln!(py_file, "{}{}: ClassVar[{}]", py_file.indent(1), var.name, py_type)?;
+            continue;
+        }
+
+        let mut args = Vec::new();
+        if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            args.push("default=None".to_string());
+        } else {
+            args.push("...".to_string());
+        }
+        if var.is_const() {
+            args.push("frozen=True".to_string());
+        }
+        match &var.array_kind {
+            ArrayKind::None => args.extend(value_constraints(&var.var_type)),
+            ArrayKind::Static(n) => args.push(format!("min_length={}, max_length={}", n, n)),
+            ArrayKind::Dynamic => {}
+        }
+
+        if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+            writeln!(py_file, "{}{}: Optional[{}] = Field({})", py_file.indent(1), var.name, py_type, args.join(", "))?;
+        } else {
+            writeln!(py_file, "{}{}: {} = Field({})", py_file.indent(1), var.name, py_type, args.join(", "))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn pydantic_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    match &var.array_kind {
+        ArrayKind::None => base,
+        _ => {
+            let constraints = value_constraints(&var.var_type);
+            if constraints.is_empty() {
+                format!("list[{}]", base)
+            } else {
+                format!("list[Annotated[{}, Field({})]]", base, constraints.join(", "))
+            }
+        }
+    }
+}
+
+/// The `Field` constraints a single value of an OML type must satisfy.
+fn value_constraints(var_type: &str) -> Vec<String> {
+    if var_type == "char" {
+        return vec!["min_length=1".to_string(), "max_length=1".to_string()];
+    }
+    match int_range(var_type) {
+        Some((min, max)) => vec![format!("ge={}", min), format!("le={}", max)],
+        None => Vec::new(),
+    }
+}
+
+/// Inclusive bounds of an OML integer type.
+fn int_range(var_type: &str) -> Option<(i128, i128)> {
+    match var_type {
+        "int8" => Some((i8::MIN.into(), i8::MAX.into())),
+        "int16" => Some((i16::MIN.into(), i16::MAX.into())),
+        "int32" => Some((i32::MIN.into(), i32::MAX.into())),
+        "int64" => Some((i64::MIN.into(), i64::MAX.into())),
+        "uint8" => Some((0, u8::MAX.into())),
+        "uint16" => Some((0, u16::MAX.into())),
+        "uint32" => Some((0, u32::MAX.into())),
+        "uint64" => Some((0, u64::MAX.into())),
+        _ => None,
+    }
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" | "int64" => "int",
+        "uint8" | "uint16" | "uint32" | "uint64" => "int",
+        "float" | "double" => "float",
+        "bool" => "bool",
+        "string" | "char" => "str",
+        _ => var_type,
+    }.to_string()
+}
+
+fn type_annotation(var_type: &str, array_kind: &ArrayKind) -> String {
+    let base = convert_type(var_type);
+    match array_kind {
+        ArrayKind::None => base,
+        ArrayKind::Static(_) | ArrayKind::Dynamic => format!("list[{}]", base),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::oml_object::{ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind};
+
+    fn to_python(oml_object: &OmlObject, use_data_class: bool) -> String {
+        PythonGenerator::new(use_data_class)
+            .generate(std::slice::from_ref(oml_object), "test")
+            .unwrap()
+    }
+
+    // ── helpers ──────────────────────────────────────────────────────────────
+
+    fn var(name: &str, ty: &str, mods: Vec<VariableModifier>) -> Variable {
+        Variable {
+            var_mod: mods,
+            visibility: VariableVisibility::PRIVATE,
+            var_type: ty.to_string(),
+            array_kind: ArrayKind::None,
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // ── enum ─────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_enum_basic() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                var("Red", "", vec![]),
+                var("Green", "", vec![]),
+                var("Blue", "", vec![]),
+            ],
+            ..Default::default()
+        };
+        let out = to_python(&obj, false);
+        assert!(out.contains("from enum import Enum"));
+        assert!(out.contains("class Color(Enum):"));
+        assert!(out.contains("\tRED = 0"));
+        assert!(out.contains("\tGREEN = 1"));
+        assert!(out.contains("\tBLUE = 2"));
+    }
+
+    #[test]
     fn test_enum_empty() {
         let obj = OmlObject {
             oml_type: ObjectType::ENUM,
             name: "Empty".to_string(),
             variables: vec![],
+            ..Default::default()
         };
         let out = to_python(&obj, false);
         assert!(out.contains("class Empty(Enum):"));
         assert!(out.contains("\tpass"));
     }
 
+    #[test]
+    fn test_enum_styles() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![var("Red", "string", vec![]), var("Green", "string", vec![])],
+            ..Default::default()
+        };
+        let generate = |style: PythonEnumStyle, unique: bool| {
+            PythonGenerator::new(false)
+                .with_enum_style(style)
+                .with_unique_enums(unique)
+                .generate(std::slice::from_ref(&obj), "color")
+                .unwrap()
+        };
+
+        let out = generate(PythonEnumStyle::Auto, true);
+        assert!(out.contains("from enum import Enum, auto, unique\n\n@unique\nclass Color(Enum):\n\tRED = auto()\n\tGREEN = auto()\n"), "Got: {}", out);
+        let out = generate(PythonEnumStyle::Int, false);
+        assert!(out.contains("from enum import IntEnum\n\nclass Color(IntEnum):\n\tRED = 0\n\tGREEN = 1\n"), "Got: {}", out);
+        let out = generate(PythonEnumStyle::Str, false);
+        assert!(out.contains("from enum import StrEnum\n\nclass Color(StrEnum):\n\tRED = \"RED\"\n\tGREEN = \"GREEN\"\n"), "Got: {}", out);
+    }
+
     // ── regular class ─────────────────────────────────────────────────────────
 
     #[test]
@@ -608,6 +1637,7 @@ mod tests {
                 var("name", "string", vec![]),
                 var("age", "int32", vec![]),
             ],
+            ..Default::default()
         };
         let out = to_python(&obj, false);
         assert!(out.contains("class Person:"));
@@ -629,6 +1659,7 @@ mod tests {
             variables: vec![
                 var("max_size", "int64", vec![VariableModifier::CONST]),
             ],
+            ..Default::default()
         };
         let out = to_python(&obj, false);
         assert!(out.contains("def max_size(self) -> int:"));
@@ -644,6 +1675,7 @@ mod tests {
                 var("name", "string", vec![]),
                 var("nickname", "string", vec![VariableModifier::OPTIONAL]),
             ],
+            ..Default::default()
         };
         let out = to_python(&obj, false);
         assert!(out.contains("from typing import Optional"));
@@ -661,6 +1693,7 @@ mod tests {
                 var("count", "int32", vec![VariableModifier::STATIC]),
                 var("name", "string", vec![]),
             ],
+            ..Default::default()
         };
         let out = to_python(&obj, false);
         // static goes at class level
@@ -675,6 +1708,7 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "Empty".to_string(),
             variables: vec![],
+            ..Default::default()
         };
         let out = to_python(&obj, false);
         assert!(out.contains("class Empty:"));
@@ -682,6 +1716,83 @@ mod tests {
         assert!(!out.contains("__init__"));
     }
 
+    #[test]
+    fn test_regular_class_dunder_methods() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Tag".to_string(),
+            variables: vec![
+                var("name", "string", vec![VariableModifier::CONST]),
+                Variable { array_kind: ArrayKind::Dynamic, ..var("ids", "int32", vec![VariableModifier::CONST]) },
+            ],
+            ..Default::default()
+        };
+        let out = to_python(&obj, false);
+        assert!(out.contains(
+            "\tdef __eq__(self, other: object) -> bool:\n\
+             \t\tif not isinstance(other, Tag):\n\
+             \t\t\treturn NotImplemented\n\
+             \t\treturn (self._name, self._ids) == (other._name, other._ids)\n\
+             \n\
+             \tdef __repr__(self) -> str:\n\
+             \t\treturn f\"Tag(name={self._name!r}, ids={self._ids!r})\"\n\
+             \n\
+             \tdef __hash__(self) -> int:\n\
+             \t\treturn hash((self._name, tuple(self._ids)))\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_regular_class_mutable_is_not_hashed() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Counter".to_string(),
+            variables: vec![var("value", "int32", vec![])],
+            ..Default::default()
+        };
+        let out = to_python(&obj, false);
+        assert!(out.contains("return (self._value,) == (other._value,)"), "Got: {}", out);
+        assert!(!out.contains("__hash__"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_regular_class_properties_for_private_fields_only() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Point".to_string(),
+            variables: vec![
+                Variable { visibility: VariableVisibility::PUBLIC, ..var("x", "int32", vec![]) },
+                var("label", "string", vec![VariableModifier::CONST]),
+            ],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false)
+            .with_accessors(AccessorMode::Private)
+            .generate(std::slice::from_ref(&obj), "test")
+            .unwrap();
+        assert!(out.contains(
+            "\t__slots__ = ('x', '_label', )\n\
+             \n\
+             \tdef __init__(self, x: int, label: str):\n\
+             \t\tself.x = x\n\
+             \t\tself._label = label\n\
+             \n\
+             \t@property\n\
+             \tdef label(self) -> str:\n\
+             \t\treturn self._label\n\
+             \n\
+             \tdef __eq__"
+        ), "Got: {}", out);
+        assert!(out.contains("return (self.x, self._label) == (other.x, other._label)"), "Got: {}", out);
+
+        let plain = PythonGenerator::new(false)
+            .with_accessors(AccessorMode::None)
+            .generate(std::slice::from_ref(&obj), "test")
+            .unwrap();
+        assert!(!plain.contains("@property"), "Got: {}", plain);
+        assert!(plain.contains("\t\tself.label = label\n"), "Got: {}", plain);
+    }
+
     // ── dataclass ─────────────────────────────────────────────────────────────
 
     #[test]
@@ -693,6 +1804,7 @@ mod tests {
                 var("name", "string", vec![]),
                 var("age", "int32", vec![]),
             ],
+            ..Default::default()
         };
         let out = to_python(&obj, true);
         assert!(out.contains("from dataclasses import dataclass, field"));
@@ -712,6 +1824,7 @@ mod tests {
                 var("x", "float", vec![VariableModifier::CONST]),
                 var("y", "float", vec![VariableModifier::CONST]),
             ],
+            ..Default::default()
         };
         let out = to_python(&obj, true);
         assert!(out.contains("@dataclass(frozen=True)"));
@@ -726,6 +1839,7 @@ mod tests {
                 var("name", "string", vec![]),
                 var("email", "string", vec![VariableModifier::OPTIONAL]),
             ],
+            ..Default::default()
         };
         let out = to_python(&obj, true);
         assert!(out.contains("from typing import Optional"));
@@ -746,6 +1860,7 @@ mod tests {
                 var("count", "int32", vec![VariableModifier::STATIC]),
                 var("name", "string", vec![]),
             ],
+            ..Default::default()
         };
         let out = to_python(&obj, true);
         assert!(out.contains("from typing import ClassVar"));
@@ -759,6 +1874,7 @@ mod tests {
             oml_type: ObjectType::CLASS,
             name: "Empty".to_string(),
             variables: vec![],
+            ..Default::default()
         };
         let out = to_python(&obj, true);
         assert!(out.contains("@dataclass"));
@@ -766,6 +1882,89 @@ mod tests {
         assert!(out.contains("\tpass"));
     }
 
+    #[test]
+    fn test_dataclass_options() {
+        let objects = [
+            OmlObject {
+                oml_type: ObjectType::STRUCT,
+                name: "Point".to_string(),
+                variables: vec![var("x", "double", vec![])],
+                ..Default::default()
+            },
+            OmlObject {
+                oml_type: ObjectType::STRUCT,
+                name: "Id".to_string(),
+                variables: vec![var("value", "int32", vec![VariableModifier::CONST])],
+                ..Default::default()
+            },
+        ];
+        let out = PythonGenerator::new(true)
+            .with_slots(true)
+            .with_kw_only(true)
+            .with_frozen(Some(&["Point".to_string()]))
+            .generate(&objects, "geo")
+            .unwrap();
+
+        assert!(out.contains("@dataclass(frozen=True, slots=True, kw_only=True)\nclass Point:\n"), "Got: {}", out);
+        assert!(out.contains("@dataclass(slots=True, kw_only=True)\nclass Id:\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_validation_in_setters_and_init() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("doors", "uint8", vec![]),
+                var("grade", "char", vec![VariableModifier::OPTIONAL, VariableModifier::CONST]),
+                var("name", "string", vec![]),
+            ],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false).with_validate(true).generate(std::slice::from_ref(&obj), "car").unwrap();
+
+        assert!(out.contains(
+            "\tdef __init__(self, doors: int, name: str, grade: Optional[str] = None):\n\
+             \t\tif not 0 <= doors <= 255:\n\
+             \t\t\traise ValueError(f\"doors must be between 0 and 255, got {doors!r}\")\n\
+             \t\tif grade is not None:\n\
+             \t\t\tif len(grade) != 1:\n\
+             \t\t\t\traise ValueError(f\"grade must be a single character, got {grade!r}\")\n\
+             \t\tself._doors = doors\n"
+        ), "Got: {}", out);
+        assert!(out.contains(
+            "\tdef doors(self, value: int):\n\
+             \t\tif not 0 <= value <= 255:\n\
+             \t\t\traise ValueError(f\"doors must be between 0 and 255, got {value!r}\")\n\
+             \t\tself._doors = value\n"
+        ), "Got: {}", out);
+        assert!(out.contains("\tdef name(self, value: str):\n\t\tself._name = value\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_validation_in_dataclass_post_init() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Sample".to_string(),
+            variables: vec![
+                Variable { array_kind: ArrayKind::Static(3), ..var("levels", "int8", vec![]) },
+                var("label", "string", vec![]),
+            ],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(true).with_validate(true).generate(std::slice::from_ref(&obj), "sample").unwrap();
+
+        assert!(out.ends_with(
+            "\tlabel: str\n\
+             \n\
+             \tdef __post_init__(self) -> None:\n\
+             \t\tif len(self.levels) != 3:\n\
+             \t\t\traise ValueError(f\"levels must have 3 items, got {len(self.levels)}\")\n\
+             \t\tif any(not -128 <= x <= 127 for x in self.levels):\n\
+             \t\t\traise ValueError(\"levels items must be between -128 and 127\")\n"
+        ), "Got: {}", out);
+    }
+
     #[test]
     fn test_struct_always_dataclass() {
         let obj = OmlObject {
@@ -775,6 +1974,7 @@ mod tests {
                 var("x", "double", vec![]),
                 var("y", "double", vec![]),
             ],
+            ..Default::default()
         };
         // even with use_data_class=false, STRUCT → dataclass
         let out = to_python(&obj, false);
@@ -795,16 +1995,380 @@ mod tests {
         assert_eq!(convert_type("MyType"), "MyType");
     }
 
+    // ── pydantic ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pydantic_model_fields() {
+        let mut wheels = var("wheels", "double", vec![]);
+        wheels.array_kind = ArrayKind::Static(4);
+        let mut scores = var("scores", "uint8", vec![]);
+        scores.array_kind = ArrayKind::Dynamic;
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                var("count", "int32", vec![VariableModifier::STATIC]),
+                var("name", "string", vec![VariableModifier::CONST]),
+                var("doors", "uint8", vec![]),
+                var("grade", "char", vec![VariableModifier::OPTIONAL]),
+                wheels,
+                scores,
+                var("engine", "Engine", vec![VariableModifier::OPTIONAL]),
+            ],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false).with_pydantic(true).generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.contains("from __future__ import annotations\nfrom typing import Annotated, ClassVar, Optional\nfrom pydantic import BaseModel, Field\n"), "Got: {}", out);
+        assert!(!out.contains("dataclass"), "Got: {}", out);
+        assert!(out.contains(
+            "class Car(BaseModel):\n\
+             \tcount: ClassVar[int]\n\
+             \tname: str = Field(..., frozen=True)\n\
+             \tdoors: int = Field(..., ge=0, le=255)\n\
+             \tgrade: Optional[str] = Field(default=None, min_length=1, max_length=1)\n\
+             \twheels: list[float] = Field(..., min_length=4, max_length=4)\n\
+             \tscores: list[Annotated[int, Field(ge=0, le=255)]] = Field(...)\n\
+             \tengine: Optional[Engine] = Field(default=None)\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_pydantic_struct_and_empty_model() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Marker".to_string(),
+            variables: vec![],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(true).with_pydantic(true).generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.contains("from pydantic import BaseModel, Field\n\nclass Marker(BaseModel):\n\tpass\n"), "Got: {}", out);
+    }
+
+    // ── stubs ─────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_stub_for_regular_class() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Person".to_string(),
+            variables: vec![
+                var("count", "int32", vec![VariableModifier::STATIC]),
+                var("name", "string", vec![VariableModifier::CONST]),
+                var("nick", "string", vec![VariableModifier::OPTIONAL]),
+            ],
+            ..Default::default()
+        };
+        let files = PythonGenerator::new(false)
+            .with_stubs(true)
+            .generate_files(std::slice::from_ref(&obj), "person")
+            .unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, std::path::PathBuf::from("person.py"));
+        assert_eq!(files[1].path, std::path::PathBuf::from("person.pyi"));
+        assert_eq!(
+            files[1].content,
+            "# This file has been generated from person.oml\n\
+             \n\
+             from typing import Optional\n\
+             \n\
+             class Person:\n\
+             \tcount: int\n\
+             \n\
+             \tdef __init__(self, name: str, nick: Optional[str] = ...) -> None: ...\n\
+             \t@property\n\
+             \tdef name(self) -> str: ...\n\
+             \t@property\n\
+             \tdef nick(self) -> Optional[str]: ...\n\
+             \t@nick.setter\n\
+             \tdef nick(self, value: Optional[str]) -> None: ...\n\
+             \tdef __eq__(self, other: object) -> bool: ...\n\
+             \tdef __repr__(self) -> str: ...\n"
+        );
+    }
+
+    #[test]
+    fn test_stub_for_dataclass_matches_module() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Point".to_string(),
+            variables: vec![var("x", "double", vec![])],
+            ..Default::default()
+        };
+        let files = PythonGenerator::new(false)
+            .with_stubs(true)
+            .generate_files(std::slice::from_ref(&obj), "point")
+            .unwrap();
+        assert_eq!(files[0].content, files[1].content);
+        assert_eq!(PythonGenerator::new(false).generate_files(std::slice::from_ref(&obj), "point").unwrap().len(), 1);
+    }
+
+    // ── dict helpers ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_dict_helpers_convert_nested_types() {
+        let color = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![var("RED", "", vec![])],
+            ..Default::default()
+        };
+        let car = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Car".to_string(),
+            variables: vec![
+                var("count", "int32", vec![VariableModifier::STATIC]),
+                var("name", "string", vec![]),
+                var("color", "Color", vec![]),
+                var("engine", "Engine", vec![VariableModifier::OPTIONAL]),
+                Variable { array_kind: ArrayKind::Dynamic, ..var("wheels", "Wheel", vec![]) },
+            ],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false).with_dict_helpers(true).generate(&[color, car], "car").unwrap();
+
+        assert!(out.contains("import json\nfrom enum import Enum\n"), "Got: {}", out);
+        assert!(out.contains(
+            "\tdef to_dict(self) -> dict:\n\
+             \t\treturn {\n\
+             \t\t\t\"name\": self.name,\n\
+             \t\t\t\"color\": self.color.name,\n\
+             \t\t\t\"engine\": None if self.engine is None else self.engine.to_dict(),\n\
+             \t\t\t\"wheels\": [x.to_dict() for x in self.wheels],\n\
+             \t\t}\n\
+             \n\
+             \t@classmethod\n\
+             \tdef from_dict(cls, data: dict) -> \"Car\":\n\
+             \t\treturn cls(\n\
+             \t\t\tname=data[\"name\"],\n\
+             \t\t\tcolor=Color[data[\"color\"]],\n\
+             \t\t\tengine=None if data.get(\"engine\") is None else Engine.from_dict(data.get(\"engine\")),\n\
+             \t\t\twheels=[Wheel.from_dict(x) for x in data[\"wheels\"]],\n\
+             \t\t)\n\
+             \n\
+             \tdef to_json(self) -> str:\n\
+             \t\treturn json.dumps(self.to_dict())\n\
+             \n\
+             \t@classmethod\n\
+             \tdef from_json(cls, text: str) -> \"Car\":\n\
+             \t\treturn cls.from_dict(json.loads(text))\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_snake_case_keeps_dict_keys() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Player".to_string(),
+            variables: vec![
+                var("maxHealth", "int32", vec![VariableModifier::CONST]),
+                var("HTTPPort", "int32", vec![]),
+                var("maxLives", "int32", vec![VariableModifier::STATIC, VariableModifier::CONST]),
+            ],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false)
+            .with_snake_case(true)
+            .with_dict_helpers(true)
+            .generate(std::slice::from_ref(&obj), "player")
+            .unwrap();
+
+        assert!(out.contains("\tMAX_LIVES: int = ...\n"), "Got: {}", out);
+        assert!(out.contains("def __init__(self, max_health: int, http_port: int):"), "Got: {}", out);
+        assert!(out.contains("\tdef max_health(self) -> int:\n\t\treturn self._max_health\n"), "Got: {}", out);
+        assert!(out.contains("\t\t\t\"maxHealth\": self.max_health,\n"), "Got: {}", out);
+        assert!(out.contains("\t\t\thttp_port=data[\"HTTPPort\"],\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_dict_helpers_skip_pydantic() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Point".to_string(),
+            variables: vec![var("x", "double", vec![])],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false)
+            .with_pydantic(true)
+            .with_dict_helpers(true)
+            .generate(std::slice::from_ref(&obj), "point")
+            .unwrap();
+        assert!(!out.contains("to_dict"), "Got: {}", out);
+    }
+
+    // ── package ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_package_init_exports_every_type() {
+        let car = [
+            OmlObject { oml_type: ObjectType::CLASS, name: "Car".to_string(), ..Default::default() },
+            OmlObject { oml_type: ObjectType::ENUM, name: "Color".to_string(), ..Default::default() },
+        ];
+        let person = [
+            OmlObject { oml_type: ObjectType::STRUCT, name: "Person".to_string(), ..Default::default() },
+            OmlObject { oml_type: ObjectType::ENUM, name: "Color".to_string(), ..Default::default() },
+        ];
+        let modules: [(&str, &[OmlObject]); 2] = [("car", &car), ("person", &person)];
+
+        let files = PythonGenerator::new(false).with_package_init(true).generate_package_files(&modules).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, std::path::PathBuf::from("__init__.py"));
+        assert_eq!(
+            files[0].content,
+            "# This file has been generated from car.oml, person.oml\n\
+             \n\
+             from .car import Car, Color\n\
+             from .person import Person\n\
+             \n\
+             __all__ = [\n\
+             \t\"Car\",\n\
+             \t\"Color\",\n\
+             \t\"Person\",\n\
+             ]\n"
+        );
+        assert!(PythonGenerator::new(false).generate_package_files(&modules).unwrap().is_empty());
+    }
+
+    // ── attrs ─────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_attrs_class_orders_required_first() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "User".to_string(),
+            variables: vec![
+                var("nickname", "string", vec![VariableModifier::OPTIONAL]),
+                var("count", "int32", vec![VariableModifier::STATIC]),
+                var("name", "string", vec![]),
+            ],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false).with_attrs(true).generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.contains(
+            "from __future__ import annotations\nfrom typing import ClassVar, Optional\nfrom attrs import define, field\n"
+        ), "Got: {}", out);
+        assert!(out.contains(
+            "@define\n\
+             class User:\n\
+             \tcount: ClassVar[int]\n\
+             \tname: str\n\
+             \tnickname: Optional[str] = field(default=None)\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_attrs_frozen_when_all_const() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Point".to_string(),
+            variables: vec![
+                var("x", "double", vec![VariableModifier::CONST]),
+                var("y", "double", vec![VariableModifier::CONST]),
+            ],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false).with_attrs(true).generate(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(out.contains("from attrs import define\n"), "Got: {}", out);
+        assert!(out.contains("@define(frozen=True)\nclass Point:\n\tx: float\n\ty: float\n"), "Got: {}", out);
+    }
+
     #[test]
     fn test_undecided_returns_error() {
         let obj = OmlObject {
             oml_type: ObjectType::UNDECIDED,
             name: "Bad".to_string(),
             variables: vec![],
+            ..Default::default()
         };
         let result = PythonGenerator::new(false).generate(std::slice::from_ref(&obj), "test");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_type_map_imports_and_stores_mapped_types_as_is() {
+        use crate::core::type_map::{TypeMap, TypeMapping};
+
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Invoice".to_string(),
+            variables: vec![var("total", "double", vec![])],
+            ..Default::default()
+        };
+        let mapping: TypeMapping = "python:double=Decimal@decimal".parse().unwrap();
+        let out = PythonGenerator::new(false)
+            .with_dict_helpers(true)
+            .with_type_map(TypeMap::new([&mapping]))
+            .generate(std::slice::from_ref(&obj), "test")
+            .unwrap();
+        assert!(out.contains("from dataclasses import dataclass, field\nfrom decimal import Decimal\n"), "Got: {}", out);
+        assert!(out.contains("\ttotal: Decimal\n"), "Got: {}", out);
+        assert!(out.contains("\"total\": self.total,"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_external_types_are_stored_as_is() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Order".to_string(),
+            variables: vec![Variable { external: true, ..var("total", "Money", vec![]) }],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false)
+            .with_dict_helpers(true)
+            .generate(std::slice::from_ref(&obj), "test")
+            .unwrap();
+        assert!(out.contains("\ttotal: Money\n"), "Got: {}", out);
+        assert!(out.contains("\"total\": self.total,"), "Got: {}", out);
+        assert!(out.contains("total=data[\"total\"],"), "Got: {}", out);
+    }
+
+    // ── factory ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_factory_takes_keyword_arguments() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "RaceCar".to_string(),
+            variables: vec![
+                var("name", "string", vec![]),
+                var("count", "int32", vec![VariableModifier::STATIC]),
+                var("nickname", "string", vec![VariableModifier::OPTIONAL]),
+            ],
+            ..Default::default()
+        };
+        let generator = PythonGenerator::new(false).with_builder(true).with_stubs(true);
+        let files = generator.generate_files(std::slice::from_ref(&obj), "test").unwrap();
+        assert!(files[0].content.ends_with(
+            "\tnickname: Optional[str] = None\n\
+             \n\
+             def make_race_car(*, name: str, nickname: Optional[str] = None) -> RaceCar:\n\
+             \treturn RaceCar(name=name, nickname=nickname)\n"
+        ), "Got: {}", files[0].content);
+        assert!(files[1].content.ends_with(
+            "\ndef make_race_car(*, name: str, nickname: Optional[str] = None) -> RaceCar: ...\n"
+        ), "Got: {}", files[1].content);
+    }
+
+    #[test]
+    fn test_schema_hash_class_attribute() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Car".to_string(),
+            variables: vec![var("nickname", "string", vec![VariableModifier::OPTIONAL])],
+            ..Default::default()
+        };
+        let out = PythonGenerator::new(false).with_schema_hash(true).generate(std::slice::from_ref(&obj), "car").unwrap();
+        assert!(out.contains("from typing import ClassVar, Optional\n"), "Got: {}", out);
+        assert!(out.contains(&format!(
+            "class Car:\n\tSCHEMA_HASH: ClassVar[int] = 0x{:016x}\n\tnickname: Optional[str] = None\n",
+            schema_hash(&obj)
+        )), "Got: {}", out);
+
+        let empty = OmlObject { oml_type: ObjectType::CLASS, name: "Empty".to_string(), ..Default::default() };
+        let out = PythonGenerator::new(false).with_schema_hash(true).with_pydantic(true).generate(std::slice::from_ref(&empty), "empty").unwrap();
+        assert!(out.contains("from typing import ClassVar\nfrom pydantic import BaseModel, Field\n"), "Got: {}", out);
+        assert!(out.ends_with(&format!("class Empty(BaseModel):\n\tSCHEMA_HASH: ClassVar[int] = 0x{:016x}\n", schema_hash(&empty))), "Got: {}", out);
+    }
 }
 
 #[cfg(test)]
@@ -825,6 +2389,7 @@ mod array_tests {
             var_type: ty.to_string(),
             array_kind: kind,
             name: name.to_string(),
+            ..Default::default()
         }
     }
 
@@ -834,6 +2399,7 @@ mod array_tests {
             oml_type: ObjectType::CLASS,
             name: "Arr".to_string(),
             variables: vec![array_var("scores", "uint16", ArrayKind::Static(4))],
+            ..Default::default()
         };
         let out = to_python(&obj, true);
         assert!(out.contains("scores: list[int]"), "Got: {}", out);
@@ -845,6 +2411,7 @@ mod array_tests {
             oml_type: ObjectType::CLASS,
             name: "Lst".to_string(),
             variables: vec![array_var("tags", "string", ArrayKind::Dynamic)],
+            ..Default::default()
         };
         let out = to_python(&obj, true);
         assert!(out.contains("tags: list[str]"), "Got: {}", out);
@@ -856,6 +2423,7 @@ mod array_tests {
             oml_type: ObjectType::CLASS,
             name: "Arr".to_string(),
             variables: vec![array_var("ids", "int32", ArrayKind::Static(10))],
+            ..Default::default()
         };
         let out = to_python(&obj, false);
         assert!(out.contains("ids: list[int]"), "Got: {}", out);
@@ -872,9 +2440,22 @@ mod array_tests {
                 var_type: "string".to_string(),
                 array_kind: ArrayKind::Dynamic,
                 name: "tags".to_string(),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         let out = to_python(&obj, true);
         assert!(out.contains("tags: Optional[list[str]] = None"), "Got: {}", out);
     }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int32 MAX_SPEED = 120;\n public static bool ELECTRIC = true;\n public static const string BRAND;\n}\n".to_string()
+        ).unwrap();
+        let out = PythonGenerator::new(false).generate(&objects, "car").unwrap();
+        assert!(out.contains("\tMAX_SPEED: int = 120\n\tELECTRIC: bool = True\n\tBRAND: str = ...\n"), "Got: {}", out);
+        let out = PythonGenerator::new(true).generate(&objects, "car").unwrap();
+        assert!(out.contains("\tMAX_SPEED: ClassVar[int] = 120\n"), "Got: {}", out);
+    }
 }
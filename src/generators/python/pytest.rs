@@ -0,0 +1,241 @@
+//! pytest scaffolds: one `test_<object>.py` per class and struct that
+//! constructs it from sample values, reads them back, assigns the mutable
+//! fields and round-trips it through the dict helpers or pydantic.
+
+use crate::core::oml_object::{OmlObject, ObjectType, Variable, VariableModifier, ArrayKind};
+use crate::core::code_writer::CodeWriter;
+use crate::core::generate::GeneratedFile;
+use crate::core::utils::to_snake_case;
+use super::oml_python::PythonGenerator;
+use std::fmt::Write;
+
+/// How deep sample objects of this module's types are nested before giving up.
+const MAX_SAMPLE_DEPTH: usize = 3;
+
+impl PythonGenerator {
+    /// `test_<object>.py` next to the module for every class and struct.
+    pub(super) fn generate_tests(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<Vec<GeneratedFile>, std::fmt::Error> {
+        let objects = self.renamed(&self.type_map.apply(oml_objects));
+        let mut files = Vec::new();
+
+        for oml_object in objects.iter().filter(|o| matches!(o.oml_type, ObjectType::CLASS | ObjectType::STRUCT)) {
+            let mut samples = Samples { siblings: &objects, used: vec![oml_object.name.clone()] };
+            let mut body = CodeWriter::new(self.style);
+            self.write_tests(oml_object, &mut samples, &mut body)?;
+
+            let mut py_file = CodeWriter::new(self.style);
+            writeln!(py_file, "# This file has been generated from {}.oml", file_name)?;
+            writeln!(py_file)?;
+            writeln!(py_file, "from {} import {}", file_name, samples.used.join(", "))?;
+            writeln!(py_file)?;
+            writeln!(py_file)?;
+            write!(py_file, "{}", body.finish())?;
+
+            let path = format!("test_{}.py", to_snake_case(&oml_object.name));
+            files.push(GeneratedFile::new(path, py_file.finish()));
+        }
+
+        Ok(files)
+    }
+
+    fn write_tests(&self, oml_object: &OmlObject, samples: &mut Samples, py_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+        let test_name = to_snake_case(&oml_object.name);
+        let fields: Vec<&Variable> = oml_object.variables
+            .iter()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+            .collect();
+        let construct = format!("value = {}", samples.construct(oml_object, true, 0));
+
+        py_file.line(&format!("def test_{}_constructs():", test_name))?;
+        py_file.indented(|py_file| {
+            py_file.line(&construct)?;
+            for var in &fields {
+                py_file.line(&format!("assert value.{} == {}", var.name, samples.sample(var, false, 0)))?;
+            }
+            Ok(())
+        })?;
+
+        // frozen dataclasses reject every assignment, pydantic and attrs
+        // only those to const fields
+        let dataclass = !self.pydantic && !self.attrs
+            && (oml_object.oml_type == ObjectType::STRUCT || self.use_data_class);
+        let frozen = dataclass && self.dataclass_args(oml_object).frozen;
+        let settable: Vec<&&Variable> = fields.iter().filter(|v| !v.var_mod.contains(&VariableModifier::CONST)).collect();
+        if !frozen && !settable.is_empty() {
+            py_file.line("")?;
+            py_file.line("")?;
+            py_file.line(&format!("def test_{}_setters():", test_name))?;
+            py_file.indented(|py_file| {
+                py_file.line(&construct)?;
+                for var in settable {
+                    let value = samples.sample(var, true, 0);
+                    py_file.line(&format!("value.{} = {}", var.name, value))?;
+                    py_file.line(&format!("assert value.{} == {}", var.name, value))?;
+                }
+                Ok(())
+            })?;
+        }
+
+        let round_trip = if self.pydantic {
+            Some(format!("{}.model_validate(value.model_dump())", oml_object.name))
+        } else if self.dict_helpers {
+            Some(format!("{}.from_dict(value.to_dict())", oml_object.name))
+        } else {
+            None
+        };
+        if let Some(round_trip) = round_trip {
+            py_file.line("")?;
+            py_file.line("")?;
+            py_file.line(&format!("def test_{}_round_trip():", test_name))?;
+            py_file.indented(|py_file| {
+                py_file.line(&construct)?;
+                py_file.line(&format!("assert {} == value", round_trip))
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Sample values for the fields of a module, remembering which of its types
+/// they mention so the test can import them.
+struct Samples<'a> {
+    siblings: &'a [OmlObject],
+    used: Vec<String>,
+}
+
+impl Samples<'_> {
+    fn use_name(&mut self, name: &str) {
+        if !self.used.iter().any(|n| n == name) {
+            self.used.push(name.to_string());
+        }
+    }
+
+    /// A keyword-argument constructor call; nested objects leave their
+    /// optional fields at `None`.
+    fn construct(&mut self, oml_object: &OmlObject, with_optional: bool, depth: usize) -> String {
+        let args: Vec<String> = oml_object.variables
+            .iter()
+            .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
+            .filter(|v| with_optional || !v.var_mod.contains(&VariableModifier::OPTIONAL))
+            .map(|v| format!("{}={}", v.name, self.sample(v, false, depth)))
+            .collect();
+        format!("{}({})", oml_object.name, args.join(", "))
+    }
+
+    /// A value of the element type; `second` gives a different one for setters.
+    fn sample_element(&mut self, var_type: &str, second: bool, depth: usize) -> String {
+        let pick = |first: &str, other: &str| if second { other.to_string() } else { first.to_string() };
+        match var_type {
+            "int8" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" | "uint64" => pick("1", "2"),
+            "float" | "double" => pick("1.5", "2.5"),
+            "bool" => pick("True", "False"),
+            "string" => pick("\"text\"", "\"other\""),
+            "char" => pick("\"a\"", "\"b\""),
+            other => match self.siblings.iter().find(|o| o.name == other) {
+                Some(obj) if obj.oml_type == ObjectType::ENUM => {
+                    match if second { obj.variables.last() } else { obj.variables.first() } {
+                        Some(variant) => {
+                            self.use_name(other);
+                            format!("{}.{}", other, variant.name.to_uppercase())
+                        }
+                        None => "None".to_string(),
+                    }
+                }
+                Some(obj) if depth < MAX_SAMPLE_DEPTH => {
+                    self.use_name(other);
+                    self.construct(obj, false, depth + 1)
+                }
+                _ => "None".to_string(),
+            },
+        }
+    }
+
+    fn sample(&mut self, var: &Variable, second: bool, depth: usize) -> String {
+        let element = self.sample_element(&var.var_type, second, depth);
+        match var.array_kind {
+            ArrayKind::None => element,
+            ArrayKind::Static(n) => format!("[{}]", vec![element; n as usize].join(", ")),
+            ArrayKind::Dynamic => format!("[{}]", element),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generate::Generate;
+    use crate::core::test::fixtures::var;
+
+    fn objects() -> Vec<OmlObject> {
+        vec![
+            OmlObject {
+                oml_type: ObjectType::ENUM,
+                name: "Color".to_string(),
+                variables: vec![var("Red", "string", vec![], ArrayKind::None), var("Blue", "string", vec![], ArrayKind::None)],
+                ..Default::default()
+            },
+            OmlObject {
+                oml_type: ObjectType::CLASS,
+                name: "RaceCar".to_string(),
+                variables: vec![
+                    var("carName", "string", vec![VariableModifier::CONST], ArrayKind::None),
+                    var("wheels", "double", vec![], ArrayKind::Static(2)),
+                    var("color", "Color", vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                    var("count", "int32", vec![VariableModifier::STATIC], ArrayKind::None),
+                ],
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_test_module_per_class() {
+        let files = PythonGenerator::new(false)
+            .with_snake_case(true)
+            .with_dict_helpers(true)
+            .with_tests(true)
+            .generate_files(&objects(), "cars")
+            .unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1].path, std::path::PathBuf::from("test_race_car.py"));
+        assert_eq!(
+            files[1].content,
+            "# This file has been generated from cars.oml\n\
+             \n\
+             from cars import RaceCar, Color\n\
+             \n\
+             \n\
+             def test_race_car_constructs():\n\
+             \tvalue = RaceCar(car_name=\"text\", wheels=[1.5, 1.5], color=Color.RED)\n\
+             \tassert value.car_name == \"text\"\n\
+             \tassert value.wheels == [1.5, 1.5]\n\
+             \tassert value.color == Color.RED\n\
+             \n\
+             \n\
+             def test_race_car_setters():\n\
+             \tvalue = RaceCar(car_name=\"text\", wheels=[1.5, 1.5], color=Color.RED)\n\
+             \tvalue.wheels = [2.5, 2.5]\n\
+             \tassert value.wheels == [2.5, 2.5]\n\
+             \tvalue.color = Color.BLUE\n\
+             \tassert value.color == Color.BLUE\n\
+             \n\
+             \n\
+             def test_race_car_round_trip():\n\
+             \tvalue = RaceCar(car_name=\"text\", wheels=[1.5, 1.5], color=Color.RED)\n\
+             \tassert RaceCar.from_dict(value.to_dict()) == value\n"
+        );
+    }
+
+    #[test]
+    fn test_frozen_dataclass_has_no_setter_test() {
+        let files = PythonGenerator::new(true)
+            .with_frozen(Some(&["RaceCar".to_string()]))
+            .with_tests(true)
+            .generate_files(&objects(), "cars")
+            .unwrap();
+        assert!(files[1].content.contains("def test_race_car_constructs():"));
+        assert!(!files[1].content.contains("def test_race_car_setters():"));
+        assert!(!files[1].content.contains("round_trip"));
+    }
+}
@@ -0,0 +1 @@
+pub mod oml_ruby;
@@ -0,0 +1,335 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::{CommentSyntax, Generate};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_snake_case;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct RubyGenerator {
+    pub style: CodeStyle,
+}
+
+impl RubyGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Words reserved in Ruby, which fields are renamed away from.
+const KEYWORDS: [&str; 35] = [
+    "alias", "and", "begin", "break", "case", "class", "def", "do", "else", "elsif", "end",
+    "ensure", "false", "for", "if", "in", "module", "next", "nil", "not", "or", "redo", "rescue",
+    "retry", "return", "self", "super", "then", "true", "undef", "unless", "until", "when", "while",
+    "yield",
+];
+
+impl Generate for RubyGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut rb_file = CodeWriter::new(self.style);
+
+        writeln!(rb_file, "# frozen_string_literal: true")?;
+        writeln!(rb_file)?;
+        writeln!(rb_file, "# This file has been generated from {}.oml", file_name)?;
+        writeln!(rb_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut rb_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_class(oml_object, &mut rb_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(rb_file)?;
+            }
+        }
+
+        Ok(rb_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "rb"
+    }
+
+    fn capitalised_types(&self) -> bool {
+        true
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("#")
+    }
+}
+
+/// A module of string constants holding the variant names as written in
+/// the OML file, plus a frozen `ALL` list.
+fn generate_enum(oml_object: &OmlObject, rb_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    writeln!(rb_file, "module {}", oml_object.name)?;
+
+    let constants: Vec<String> = oml_object.variables
+        .iter()
+        .map(|v| to_snake_case(&v.name).to_uppercase())
+        .collect();
+    for (var, constant) in oml_object.variables.iter().zip(&constants) {
+        writeln!(rb_file, "{}{} = '{}'", rb_file.indent(1), constant, var.name)?;
+    }
+    if !constants.is_empty() {
+        writeln!(rb_file)?;
+    }
+    writeln!(rb_file, "{}ALL = [{}].freeze", rb_file.indent(1), constants.join(", "))?;
+
+    writeln!(rb_file, "end")?;
+
+    Ok(())
+}
+
+fn generate_class(oml_object: &OmlObject, rb_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let (static_vars, instance_vars): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    writeln!(rb_file, "class {}", oml_object.name)?;
+
+    let mut wrote_section = false;
+
+    if !static_vars.is_empty() {
+        writeln!(rb_file, "{}class << self", rb_file.indent(1))?;
+        write_attributes(&static_vars, 2, rb_file)?;
+        writeln!(rb_file, "{}end", rb_file.indent(1))?;
+        // The singleton accessors read the class's own instance variables
+        for (var, value) in static_vars.iter().filter_map(|v| Some((v, v.value.as_deref()?))) {
+            writeln!(rb_file, "{}@{} = {}", rb_file.indent(1), var.name, ruby_literal(value))?;
+        }
+        wrote_section = true;
+    }
+
+    if !instance_vars.is_empty() {
+        if wrote_section {
+            writeln!(rb_file)?;
+        }
+        write_attributes(&instance_vars, 1, rb_file)?;
+        writeln!(rb_file)?;
+        generate_initializer(&instance_vars, rb_file)?;
+    }
+
+    writeln!(rb_file, "end")?;
+
+    Ok(())
+}
+
+/// `attr_reader` for const fields and `attr_accessor` for the rest, grouped
+/// under `protected`/`private` sections according to visibility.
+fn write_attributes(vars: &[&Variable], level: usize, rb_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let sections = [
+        (VariableVisibility::PUBLIC, None),
+        (VariableVisibility::PROTECTED, Some("protected")),
+        (VariableVisibility::PRIVATE, Some("private")),
+    ];
+
+    let mut first = true;
+    for (visibility, keyword) in sections {
+        let section: Vec<&&Variable> = vars.iter().filter(|v| v.visibility == visibility).collect();
+        if section.is_empty() {
+            continue;
+        }
+        if let Some(keyword) = keyword {
+            if !first {
+                writeln!(rb_file)?;
+            }
+            writeln!(rb_file, "{}{}", rb_file.indent(level), keyword)?;
+        }
+        first = false;
+
+        let (readers, accessors): (Vec<&&Variable>, Vec<&&Variable>) = section.into_iter().partition(|v| is_const(v));
+        for (method, group) in [("attr_reader", readers), ("attr_accessor", accessors)] {
+            if group.is_empty() {
+                continue;
+            }
+            let names: Vec<String> = group.iter().map(|v| format!(":{}", to_snake_case(&v.name))).collect();
+            writeln!(rb_file, "{}{} {}", rb_file.indent(level), method, names.join(", "))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Keyword-argument initializer: required fields first, then optional ones
+/// defaulting to nil. The YARD tags record the OML types.
+fn generate_initializer(instance_vars: &[&Variable], rb_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let (optional, required): (Vec<&Variable>, Vec<&Variable>) = instance_vars
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::OPTIONAL));
+    let params: Vec<&Variable> = required.into_iter().chain(optional).collect();
+
+    for var in &params {
+        writeln!(rb_file, "{}# @param {} [{}]", rb_file.indent(1), to_snake_case(&var.name), yard_type(var))?;
+    }
+
+    let args: Vec<String> = params
+        .iter()
+        .map(|v| {
+            let name = to_snake_case(&v.name);
+            if v.var_mod.contains(&VariableModifier::OPTIONAL) {
+                format!("{}: nil", name)
+            } else {
+                format!("{}:", name)
+            }
+        })
+        .collect();
+    writeln!(rb_file, "{}def initialize({})", rb_file.indent(1), args.join(", "))?;
+    for var in &params {
+        let name = to_snake_case(&var.name);
+        writeln!(rb_file, "{}@{} = {}", rb_file.indent(2), name, name)?;
+    }
+    writeln!(rb_file, "{}end", rb_file.indent(1))?;
+
+    Ok(())
+}
+
+#[inline]
+fn is_const(var: &Variable) -> bool {
+    var.is_const()
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" | "int16" | "int32" | "int64" => "Integer".to_string(),
+        "uint8" | "uint16" | "uint32" | "uint64" => "Integer".to_string(),
+        "float" | "double" => "Float".to_string(),
+        "bool" => "Boolean".to_string(),
+        "string" | "char" => "String".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn yard_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        _ => format!("Array<{}>", base),
+    };
+    if var.var_mod.contains(&VariableModifier::OPTIONAL) {
+        format!("{}, nil", ty)
+    } else {
+        ty
+    }
+}
+
+/// An OML literal in Ruby. Chars become double-quoted strings, since single
+/// quotes would leave their escapes as written, and `#` is escaped so it
+/// does not interpolate.
+fn ruby_literal(value: &str) -> String {
+    match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        Some(c) => format!("\"{}\"", c.replace("\\'", "'").replace("\\\"", "\"").replace('"', "\\\"").replace('#', "\\#")),
+        None if value.starts_with('"') => value.replace('#', "\\#"),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{member, generated};
+
+    #[test]
+    fn test_readers_accessors_and_initializer() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Car".to_string(),
+            variables: vec![
+                member("nickname", "string", VariableVisibility::PUBLIC, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+                member("name", "string", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::None),
+                member("maxSpeed", "double", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("tags", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::Dynamic),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&RubyGenerator::default(), &obj);
+        assert!(out.starts_with("# frozen_string_literal: true\n\n# This file has been generated from test.oml\n\n"));
+        assert!(out.contains(
+            "class Car\n\
+             \tattr_reader :name\n\
+             \tattr_accessor :nickname, :max_speed, :tags\n\
+             \n\
+             \t# @param name [String]\n\
+             \t# @param max_speed [Float]\n\
+             \t# @param tags [Array<String>]\n\
+             \t# @param nickname [String, nil]\n\
+             \tdef initialize(name:, max_speed:, tags:, nickname: nil)\n\
+             \t\t@name = name\n\
+             \t\t@max_speed = max_speed\n\
+             \t\t@tags = tags\n\
+             \t\t@nickname = nickname\n\
+             \tend\n\
+             end\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_visibility_sections() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Account".to_string(),
+            variables: vec![
+                member("secret", "string", VariableVisibility::PRIVATE, vec![VariableModifier::CONST], ArrayKind::None),
+                member("balance", "int64", VariableVisibility::PROTECTED, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&RubyGenerator::default(), &obj);
+        assert!(out.contains(
+            "class Account\n\tprotected\n\tattr_accessor :balance\n\n\tprivate\n\tattr_reader :secret\n\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_use_singleton_class() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Registry".to_string(),
+            variables: vec![
+                member("count", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("label", "string", VariableVisibility::PUBLIC,
+                    vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&RubyGenerator::default(), &obj);
+        assert!(out.contains(
+            "class Registry\n\tclass << self\n\t\tattr_reader :label\n\t\tattr_accessor :count\n\tend\nend\n"
+        ), "Got: {}", out);
+        assert!(!out.contains("def initialize"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_module_with_frozen_constants() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                member("RED", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("darkBlue", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&RubyGenerator::default(), &obj).contains(
+            "module Color\n\tRED = 'RED'\n\tDARK_BLUE = 'darkBlue'\n\n\tALL = [RED, DARK_BLUE].freeze\nend\n"
+        ));
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static string label = \"$5 #1\";\n public static char mark = '\\'';\n}\n".to_string()
+        ).unwrap();
+        let out = RubyGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("\tend\n\t@max_speed = 120\n\t@label = \"$5 \\#1\"\n\t@mark = \"'\"\n"), "Got: {}", out);
+    }
+}
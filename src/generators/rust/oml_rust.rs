@@ -2,10 +2,29 @@ use crate::core::oml_object::{
     OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
 };
 use crate::core::generate::{Generate, BackwardsGenerate};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
 use std::error::Error;
 use std::fmt::Write;
 
-pub struct RustGenerator;
+#[derive(Default)]
+pub struct RustGenerator {
+    /// Derive `PartialEq` for structs as well as enums
+    pub equality: bool,
+    pub style: CodeStyle,
+}
+
+impl RustGenerator {
+    pub fn with_equality(mut self, equality: bool) -> Self {
+        self.equality = equality;
+        self
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
 
 impl BackwardsGenerate for RustGenerator {
     fn reverse(&self, content: &str) -> Result<Vec<OmlObject>, Box<dyn Error>> {
@@ -20,7 +39,7 @@ impl BackwardsGenerate for RustGenerator {
                 let name = trimmed
                     .strip_prefix("pub enum ")
                     .unwrap()
-                    .trim_end_matches(|c: char| c == '{' || c == ' ')
+                    .trim_end_matches(['{', ' '])
                     .to_string();
                 let mut vars = Vec::new();
                 i += 1;
@@ -35,6 +54,7 @@ impl BackwardsGenerate for RustGenerator {
                             var_type: "string".to_string(),
                             array_kind: ArrayKind::None,
                             name: variant,
+                            ..Default::default()
                         });
                     }
                     i += 1;
@@ -43,12 +63,13 @@ impl BackwardsGenerate for RustGenerator {
                     oml_type: ObjectType::ENUM,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
             } else if trimmed.starts_with("pub struct ") && trimmed.ends_with('{') {
                 let name = trimmed
                     .strip_prefix("pub struct ")
                     .unwrap()
-                    .trim_end_matches(|c: char| c == '{' || c == ' ')
+                    .trim_end_matches(['{', ' '])
                     .to_string();
                 let mut vars = Vec::new();
                 i += 1;
@@ -83,6 +104,7 @@ impl BackwardsGenerate for RustGenerator {
                     oml_type: ObjectType::STRUCT,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
             }
             i += 1;
@@ -105,7 +127,7 @@ fn reverse_rust_type(rs_type: &str) -> String {
         "f32" => "float".to_string(),
         "f64" => "double".to_string(),
         "bool" => "bool".to_string(),
-        "String" => "string".to_string(),
+        "String" | "&str" => "string".to_string(),
         "char" => "char".to_string(),
         other => other.to_string(),
     }
@@ -144,6 +166,7 @@ fn parse_rust_field(line: &str) -> Option<Variable> {
         var_type,
         array_kind,
         name,
+        ..Default::default()
     })
 }
 
@@ -206,12 +229,18 @@ fn parse_rust_associated_const(line: &str) -> Option<Variable> {
         return None;
     }
 
-    // "NAME: Type = todo!();"
+    // "NAME: Type = value;", or "= todo!();" when the OML gave no value
     let colon_pos = rest.find(':')?;
     let name = rest[..colon_pos].trim().to_lowercase();
     let after_colon = rest[colon_pos + 1..].trim();
     let eq_pos = after_colon.find('=')?;
     let type_str = after_colon[..eq_pos].trim();
+    let value = after_colon[eq_pos + 1..].trim().trim_end_matches(';').trim();
+    let value = value
+        .strip_prefix("Some(")
+        .and_then(|v| v.strip_suffix(')'))
+        .unwrap_or(value);
+    let value = (value != "todo!()" && value != "None").then(|| value.to_string());
 
     let (var_type, array_kind, is_optional) = parse_rust_type_annotation(type_str);
     if is_optional {
@@ -224,12 +253,23 @@ fn parse_rust_associated_const(line: &str) -> Option<Variable> {
         var_type,
         array_kind,
         name,
+        value,
+        ..Default::default()
     })
 }
 
+/// Words reserved in Rust, which fields are renamed away from.
+const KEYWORDS: [&str; 52] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+];
+
 impl Generate for RustGenerator {
     fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
-        let mut rs_file = String::new();
+        let mut rs_file = CodeWriter::new(self.style);
 
         writeln!(rs_file, "// This file has been generated from {}.oml", file_name)?;
         writeln!(rs_file)?;
@@ -242,7 +282,7 @@ impl Generate for RustGenerator {
         for (i, oml_object) in oml_objects.iter().enumerate() {
             match &oml_object.oml_type {
                 ObjectType::ENUM => generate_enum(oml_object, &mut rs_file)?,
-                ObjectType::CLASS | ObjectType::STRUCT => generate_struct(oml_object, &mut rs_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_struct(oml_object, self.equality, &mut rs_file)?,
                 ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
             }
             if i < oml_objects.len() - 1 {
@@ -250,22 +290,35 @@ impl Generate for RustGenerator {
             }
         }
 
-        Ok(rs_file)
+        Ok(rs_file.finish())
     }
 
     fn extension(&self) -> &str {
         "rs"
     }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Snake,
+            constants: Case::ScreamingSnake,
+            enum_values: Case::Pascal,
+        }
+    }
 }
 
-fn generate_enum(oml_object: &OmlObject, rs_file: &mut String) -> Result<(), std::fmt::Error> {
+fn generate_enum(oml_object: &OmlObject, rs_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
     writeln!(rs_file, "#[derive(Debug, Clone, PartialEq)]")?;
-    writeln!(rs_file, "pub enum {} {{", oml_object.name)?;
+    rs_file.open_block(0, &format!("pub enum {}", oml_object.name))?;
 
     for var in &oml_object.variables {
         // Capitalise first letter to match Rust enum variant convention
         let name = capitalise(&var.name);
-        writeln!(rs_file, "\t{},", name)?;
+        writeln!(rs_file, "{}{},", rs_file.indent(1), name)?;
     }
 
     writeln!(rs_file, "}}")?;
@@ -275,7 +328,8 @@ fn generate_enum(oml_object: &OmlObject, rs_file: &mut String) -> Result<(), std
 
 fn generate_struct(
     oml_object: &OmlObject,
-    rs_file: &mut String,
+    equality: bool,
+    rs_file: &mut CodeWriter,
 ) -> Result<(), std::fmt::Error> {
     // Separate static (associated-const) vars from regular fields
     let static_vars: Vec<&Variable> = oml_object.variables
@@ -288,8 +342,12 @@ fn generate_struct(
         .filter(|v| !v.var_mod.contains(&VariableModifier::STATIC))
         .collect();
 
-    writeln!(rs_file, "#[derive(Debug, Clone)]")?;
-    writeln!(rs_file, "pub struct {} {{", oml_object.name)?;
+    if equality {
+        writeln!(rs_file, "#[derive(Debug, Clone, PartialEq)]")?;
+    } else {
+        writeln!(rs_file, "#[derive(Debug, Clone)]")?;
+    }
+    rs_file.open_block(0, &format!("pub struct {}", oml_object.name))?;
 
     for var in &field_vars {
         write_field(var, rs_file)?;
@@ -300,7 +358,7 @@ fn generate_struct(
     // Emit associated constants in an impl block for static vars
     if !static_vars.is_empty() {
         writeln!(rs_file)?;
-        writeln!(rs_file, "impl {} {{", oml_object.name)?;
+        rs_file.open_block(0, &format!("impl {}", oml_object.name))?;
         for var in &static_vars {
             write_associated_const(var, rs_file)?;
         }
@@ -311,8 +369,8 @@ fn generate_struct(
 }
 
 /// Writes a single struct field.
-fn write_field(var: &Variable, rs_file: &mut String) -> Result<(), std::fmt::Error> {
-    write!(rs_file, "\t")?;
+fn write_field(var: &Variable, rs_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    write!(rs_file, "{}", rs_file.indent(1))?;
 
     // In Rust, `pub` / `pub(crate)` / (private) map to PUBLIC / PROTECTED / PRIVATE
     match var.visibility {
@@ -329,28 +387,46 @@ fn write_field(var: &Variable, rs_file: &mut String) -> Result<(), std::fmt::Err
 }
 
 /// Emits a static variable as an associated constant in an `impl` block.
-fn write_associated_const(var: &Variable, rs_file: &mut String) -> Result<(), std::fmt::Error> {
+fn write_associated_const(var: &Variable, rs_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
     let vis = match var.visibility {
         VariableVisibility::PUBLIC => "pub ",
         VariableVisibility::PROTECTED => "pub(crate) ",
         VariableVisibility::PRIVATE => "",
     };
 
-    let rs_type = type_annotation(&var.var_type, &var.array_kind, var.var_mod.contains(&VariableModifier::OPTIONAL));
+    let optional = var.var_mod.contains(&VariableModifier::OPTIONAL);
+    let mut rs_type = type_annotation(&var.var_type, &var.array_kind, optional);
+    // A `String` cannot be built in a constant, so a string with a value is a `&str`.
+    if var.value.is_some() && var.var_type == "string" {
+        rs_type = rs_type.replace("String", "&str");
+    }
+    let value = match var.value.as_deref() {
+        Some(value) if optional => format!("Some({})", rust_literal(&var.var_type, value)),
+        Some(value) => rust_literal(&var.var_type, value),
+        None => "todo!()".to_string(),
+    };
 
     // Const fields use `const`, mutable statics use `static mut` (unsafe in Rust).
-    // We default to a placeholder comment when the value is unknown.
-    if var.var_mod.contains(&VariableModifier::CONST) && !var.var_mod.contains(&VariableModifier::MUT) {
-        writeln!(rs_file, "\t{}const {}: {} = todo!();", vis, var.name.to_uppercase(), rs_type)?;
+    // We default to a `todo!()` placeholder when the OML gives no value.
+    if var.is_const() {
+        writeln!(rs_file, "{}{}const {}: {} = {};", rs_file.indent(1), vis, var.name.to_uppercase(), rs_type, value)?;
     } else {
         // Static mutable fields are inherently unsafe in Rust; emit a warning comment.
-        writeln!(rs_file, "\t// SAFETY: mutable static — initialise before use")?;
-        writeln!(rs_file, "\t{}static mut {}: {} = todo!();", vis, var.name.to_uppercase(), rs_type)?;
+        writeln!(rs_file, "{}// SAFETY: mutable static — initialise before use", rs_file.indent(1))?;
+        writeln!(rs_file, "{}{}static mut {}: {} = {};", rs_file.indent(1), vis, var.name.to_uppercase(), rs_type, value)?;
     }
 
     Ok(())
 }
 
+/// An OML literal in Rust: floating-point values always carry a `.`.
+fn rust_literal(var_type: &str, value: &str) -> String {
+    match var_type {
+        "float" | "double" if !value.contains('.') => format!("{}.0", value),
+        _ => value.to_string(),
+    }
+}
+
 #[inline]
 fn convert_type(var_type: &str) -> String {
     match var_type {
@@ -16,18 +16,18 @@ fn ensure_test_results_dir() {
 fn generate_and_write(oml_path: &str, file_name: &str) -> String {
     ensure_test_results_dir();
 
-    let generator = RustGenerator;
+    let generator = RustGenerator::default();
 
     let path = Path::new(oml_path);
     let (oml_objects, _imports) = OmlObject::get_from_file(path)
-        .expect(&format!("Failed to parse OML file: {}", oml_path));
+        .unwrap_or_else(|_| panic!("Failed to parse OML file: {}", oml_path));
 
     let rs_output = generator.generate(&oml_objects, file_name)
-        .expect(&format!("Failed to generate Rust for: {}", file_name));
+        .unwrap_or_else(|_| panic!("Failed to generate Rust for: {}", file_name));
 
     let output_path = format!("{}/{}.{}", TEST_RESULTS_DIR, file_name, generator.extension());
     fs::write(&output_path, &rs_output)
-        .expect(&format!("Failed to write output file: {}", output_path));
+        .unwrap_or_else(|_| panic!("Failed to write output file: {}", output_path));
 
     rs_output
 }
@@ -111,12 +111,13 @@ fn test_enum_capitalises_variant_names() {
         oml_type: ObjectType::ENUM,
         name: "Direction".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "north".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "south".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "north".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "south".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = RustGenerator.generate(std::slice::from_ref(&oml_object), "Direction").unwrap();
+    let output = RustGenerator::default().generate(std::slice::from_ref(&oml_object), "Direction").unwrap();
     assert!(output.contains("\tNorth,"));
     assert!(output.contains("\tSouth,"));
 }
@@ -127,12 +128,13 @@ fn test_optional_field_wraps_in_option() {
         oml_type: ObjectType::CLASS,
         name: "User".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string() },
-            Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "email".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string(), ..Default::default() },
+            Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "email".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = RustGenerator.generate(std::slice::from_ref(&oml_object), "User").unwrap();
+    let output = RustGenerator::default().generate(std::slice::from_ref(&oml_object), "User").unwrap();
     assert!(output.contains("\tpub name: String,"));
     assert!(output.contains("\tpub email: Option<String>,"));
 }
@@ -143,11 +145,12 @@ fn test_protected_visibility_maps_to_pub_crate() {
         oml_type: ObjectType::STRUCT,
         name: "Foo".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PROTECTED, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "value".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PROTECTED, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "value".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = RustGenerator.generate(std::slice::from_ref(&oml_object), "Foo").unwrap();
+    let output = RustGenerator::default().generate(std::slice::from_ref(&oml_object), "Foo").unwrap();
     assert!(output.contains("\tpub(crate) value: i32,"));
 }
 
@@ -157,12 +160,13 @@ fn test_static_const_generates_impl_block_with_associated_const() {
         oml_type: ObjectType::CLASS,
         name: "Config".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string() },
-            Variable { var_mod: vec![VariableModifier::STATIC, VariableModifier::CONST], visibility: VariableVisibility::PUBLIC, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "max".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string(), ..Default::default() },
+            Variable { var_mod: vec![VariableModifier::STATIC, VariableModifier::CONST], visibility: VariableVisibility::PUBLIC, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "max".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = RustGenerator.generate(std::slice::from_ref(&oml_object), "Config").unwrap();
+    let output = RustGenerator::default().generate(std::slice::from_ref(&oml_object), "Config").unwrap();
     assert!(output.contains("impl Config {"));
     assert!(output.contains("pub const MAX: i32"));
     // Static field must NOT appear inside the struct body
@@ -175,11 +179,12 @@ fn test_static_array_generates_fixed_size_array_type() {
         oml_type: ObjectType::STRUCT,
         name: "Matrix".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "float".to_string(), array_kind: ArrayKind::Static(4), name: "data".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "float".to_string(), array_kind: ArrayKind::Static(4), name: "data".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = RustGenerator.generate(std::slice::from_ref(&oml_object), "Matrix").unwrap();
+    let output = RustGenerator::default().generate(std::slice::from_ref(&oml_object), "Matrix").unwrap();
     assert!(output.contains("\tpub data: [f32; 4],"));
 }
 
@@ -189,11 +194,12 @@ fn test_dynamic_list_generates_vec() {
         oml_type: ObjectType::CLASS,
         name: "Container".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::Dynamic, name: "tags".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::Dynamic, name: "tags".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = RustGenerator.generate(std::slice::from_ref(&oml_object), "Container").unwrap();
+    let output = RustGenerator::default().generate(std::slice::from_ref(&oml_object), "Container").unwrap();
     assert!(output.contains("\tpub tags: Vec<String>,"));
 }
 
@@ -212,10 +218,11 @@ fn test_all_builtin_types_convert_to_rs() {
         var_type: oml_type.to_string(),
         array_kind: ArrayKind::None,
         name: format!("field_{}", i),
+        ..Default::default()
     }).collect();
 
-    let oml_object = OmlObject { oml_type: ObjectType::STRUCT, name: "AllTypes".to_string(), variables };
-    let output = RustGenerator.generate(std::slice::from_ref(&oml_object), "AllTypes").unwrap();
+    let oml_object = OmlObject { oml_type: ObjectType::STRUCT, name: "AllTypes".to_string(), variables, ..Default::default() };
+    let output = RustGenerator::default().generate(std::slice::from_ref(&oml_object), "AllTypes").unwrap();
 
     for (i, (_, expected)) in pairs.iter().enumerate() {
         let expected_field = format!("field_{}: {},", i, expected);
@@ -225,11 +232,33 @@ fn test_all_builtin_types_convert_to_rs() {
 
 #[test]
 fn test_undecided_object_type_returns_error() {
-    let oml_object = OmlObject { oml_type: ObjectType::UNDECIDED, name: "Bad".to_string(), variables: vec![] };
-    assert!(RustGenerator.generate(std::slice::from_ref(&oml_object), "Bad").is_err());
+    let oml_object = OmlObject { oml_type: ObjectType::UNDECIDED, name: "Bad".to_string(), variables: vec![], ..Default::default() };
+    assert!(RustGenerator::default().generate(std::slice::from_ref(&oml_object), "Bad").is_err());
 }
 
 #[test]
 fn test_extension_is_rs() {
-    assert_eq!(RustGenerator.extension(), "rs");
+    assert_eq!(RustGenerator::default().extension(), "rs");
+}
+
+#[test]
+fn test_equality_derives_partial_eq_for_structs() {
+    let oml_object = OmlObject { oml_type: ObjectType::STRUCT, name: "Point".to_string(), variables: vec![], ..Default::default() };
+    let plain = RustGenerator::default().generate(std::slice::from_ref(&oml_object), "Point").unwrap();
+    assert!(plain.contains("#[derive(Debug, Clone)]\npub struct Point"), "Got: {}", plain);
+
+    let output = RustGenerator::default().with_equality(true).generate(std::slice::from_ref(&oml_object), "Point").unwrap();
+    assert!(output.contains("#[derive(Debug, Clone, PartialEq)]\npub struct Point"), "Got: {}", output);
+}
+
+#[test]
+fn test_associated_consts_start_from_their_values() {
+    let oml_objects = OmlObject::scan_file(
+        "class Config {\n public static const float RATIO = 2;\n public static const string NAME = \"car\";\n public static const int32 MAX;\n}\n".to_string()
+    ).unwrap();
+
+    let output = RustGenerator::default().generate(&oml_objects, "Config").unwrap();
+    assert!(output.contains("\tpub const RATIO: f32 = 2.0;\n"), "Got: {}", output);
+    assert!(output.contains("\tpub const NAME: &str = \"car\";\n"), "Got: {}", output);
+    assert!(output.contains("\tpub const MAX: i32 = todo!();\n"), "Got: {}", output);
 }
@@ -0,0 +1,331 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct ScalaGenerator {
+    pub style: CodeStyle,
+}
+
+impl ScalaGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Words reserved in Scala, which fields are renamed away from.
+const KEYWORDS: [&str; 43] = [
+    "abstract", "case", "catch", "class", "def", "do", "else", "enum", "export", "extends", "false",
+    "final", "finally", "for", "forSome", "given", "if", "implicit", "import", "lazy", "match",
+    "new", "null", "object", "override", "package", "private", "protected", "return", "sealed",
+    "super", "then", "this", "throw", "trait", "true", "try", "type", "val", "var", "while", "with",
+    "yield",
+];
+
+impl Generate for ScalaGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut scala_file = CodeWriter::new(self.style);
+
+        writeln!(scala_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(scala_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut scala_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_case_class(oml_object, &mut scala_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(scala_file)?;
+            }
+        }
+
+        Ok(scala_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "scala"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Camel,
+            constants: Case::Pascal,
+            enum_values: Case::Pascal,
+        }
+    }
+}
+
+/// A sealed trait with one case object per variant, and a `values` list in
+/// the companion object.
+fn generate_enum(oml_object: &OmlObject, scala_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+
+    writeln!(scala_file, "sealed trait {}", name)?;
+    writeln!(scala_file)?;
+    scala_file.open_block(0, &format!("object {}", name))?;
+    for var in &oml_object.variables {
+        writeln!(scala_file, "{}case object {} extends {}", scala_file.indent(1), var.name, name)?;
+    }
+    if !oml_object.variables.is_empty() {
+        writeln!(scala_file)?;
+    }
+    let variants: Vec<&str> = oml_object.variables.iter().map(|v| v.name.as_str()).collect();
+    writeln!(scala_file, "{}val values: Seq[{}] = Seq({})", scala_file.indent(1), name, variants.join(", "))?;
+    writeln!(scala_file, "}}")?;
+
+    Ok(())
+}
+
+fn generate_case_class(oml_object: &OmlObject, scala_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let name = &oml_object.name;
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    if fields.is_empty() {
+        writeln!(scala_file, "final case class {}()", name)?;
+    } else {
+        writeln!(scala_file, "final case class {}(", name)?;
+        let length = fields.len();
+        for (i, var) in fields.iter().enumerate() {
+            let separator = if i < length - 1 { "," } else { "" };
+            let keyword = if is_const(var) { "" } else { "var " };
+            let default = if is_optional(var) { " = None" } else { "" };
+            writeln!(
+                scala_file,
+                "{}{}{}{}: {}{}{}",
+                scala_file.indent(1),
+                visibility_prefix(&var.visibility, is_const(var)),
+                keyword,
+                var.name,
+                scala_type(var),
+                default,
+                separator,
+            )?;
+        }
+        writeln!(scala_file, ")")?;
+    }
+
+    // Statics live in the companion object
+    if !static_vars.is_empty() {
+        writeln!(scala_file)?;
+        scala_file.open_block(0, &format!("object {}", name))?;
+        for var in &static_vars {
+            let keyword = if is_const(var) { "val" } else { "var" };
+            writeln!(
+                scala_file,
+                "{}{}{} {}: {} = {}",
+                scala_file.indent(1),
+                visibility_prefix(&var.visibility, false),
+                keyword,
+                var.name,
+                scala_type(var),
+                default_value(var),
+            )?;
+        }
+        writeln!(scala_file, "}}")?;
+    }
+
+    Ok(())
+}
+
+/// Case class parameters are public vals by default; a modifier on an
+/// immutable parameter needs an explicit `val` to stay a member.
+fn visibility_prefix(visibility: &VariableVisibility, needs_val: bool) -> &'static str {
+    match (visibility, needs_val) {
+        (VariableVisibility::PUBLIC, _) => "",
+        (VariableVisibility::PRIVATE, true) => "private val ",
+        (VariableVisibility::PRIVATE, false) => "private ",
+        (VariableVisibility::PROTECTED, true) => "protected val ",
+        (VariableVisibility::PROTECTED, false) => "protected ",
+    }
+}
+
+#[inline]
+fn is_const(var: &Variable) -> bool {
+    var.is_const()
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+/// Scala has no unsigned integers, so each unsigned type widens to the next
+/// signed one.
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" => "Byte".to_string(),
+        "int16" => "Short".to_string(),
+        "int32" => "Int".to_string(),
+        "int64" => "Long".to_string(),
+        "uint8" => "Short".to_string(),
+        "uint16" => "Int".to_string(),
+        "uint32" => "Long".to_string(),
+        "uint64" => "BigInt".to_string(),
+        "float" => "Float".to_string(),
+        "double" => "Double".to_string(),
+        "bool" => "Boolean".to_string(),
+        "string" => "String".to_string(),
+        "char" => "Char".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Both array kinds become `Seq[T]`: `Array` would break case class
+/// equality, and Spark encodes `Seq` natively.
+fn scala_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        _ => format!("Seq[{}]", base),
+    };
+    if is_optional(var) {
+        format!("Option[{}]", ty)
+    } else {
+        ty
+    }
+}
+
+/// Initial value for companion object members, which Scala requires.
+fn default_value(var: &Variable) -> String {
+    if let Some(value) = &var.value {
+        let literal = scala_literal(&var.var_type, value);
+        return if is_optional(var) { format!("Some({})", literal) } else { literal };
+    }
+    if is_optional(var) {
+        return "None".to_string();
+    }
+    if var.array_kind != ArrayKind::None {
+        return "Seq.empty".to_string();
+    }
+    match var.var_type.as_str() {
+        "int8" | "int16" | "int32" | "uint8" | "uint16" => "0".to_string(),
+        "int64" | "uint32" => "0L".to_string(),
+        "uint64" => "BigInt(0)".to_string(),
+        "float" => "0.0f".to_string(),
+        "double" => "0.0".to_string(),
+        "bool" => "false".to_string(),
+        "string" => "\"\"".to_string(),
+        "char" => "'\\u0000'".to_string(),
+        _ => "null".to_string(),
+    }
+}
+
+/// An OML literal in Scala, suffixed to the width of `var_type`.
+fn scala_literal(var_type: &str, value: &str) -> String {
+    match var_type {
+        "int64" | "uint32" => format!("{}L", value),
+        "uint64" => format!("BigInt(\"{}\")", value),
+        "float" => format!("{}f", value),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{member, generated};
+
+    #[test]
+    fn test_case_class_with_options_and_seqs() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Event".to_string(),
+            variables: vec![
+                member("id", "uint64", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::None),
+                member("score", "double", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("tags", "string", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::Dynamic),
+                member("note", "string", VariableVisibility::PUBLIC,
+                    vec![VariableModifier::CONST, VariableModifier::OPTIONAL], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&ScalaGenerator::default(), &obj);
+        assert!(out.contains(
+            "final case class Event(\n\
+             \tid: BigInt,\n\
+             \tvar score: Double,\n\
+             \ttags: Seq[String],\n\
+             \tnote: Option[String] = None\n\
+             )\n"
+        ), "Got: {}", out);
+        assert!(!out.contains("object Event"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_non_public_parameters() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Account".to_string(),
+            variables: vec![
+                member("secret", "string", VariableVisibility::PRIVATE, vec![VariableModifier::CONST], ArrayKind::None),
+                member("balance", "int64", VariableVisibility::PROTECTED, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&ScalaGenerator::default(), &obj);
+        assert!(out.contains("\tprivate val secret: String,\n\tprotected var balance: Long\n"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_statics_go_to_companion_object() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Registry".to_string(),
+            variables: vec![
+                member("count", "int32", VariableVisibility::PUBLIC, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("label", "string", VariableVisibility::PRIVATE,
+                    vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&ScalaGenerator::default(), &obj);
+        assert!(out.contains("final case class Registry()\n\nobject Registry {\n"), "Got: {}", out);
+        assert!(out.contains("\tvar count: Int = 0\n\tprivate val label: String = \"\"\n}"), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_sealed_trait_enum() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Color".to_string(),
+            variables: vec![
+                member("RED", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("GREEN", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&ScalaGenerator::default(), &obj).contains(
+            "sealed trait Color\n\n\
+             object Color {\n\
+             \tcase object RED extends Color\n\
+             \tcase object GREEN extends Color\n\
+             \n\
+             \tval values: Seq[Color] = Seq(RED, GREEN)\n\
+             }\n"
+        ));
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static const float ratio = 2;\n}\n".to_string()
+        ).unwrap();
+        let out = ScalaGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("\tval max_speed: Long = 120L\n\tval ratio: Float = 2f\n"), "Got: {}", out);
+    }
+}
@@ -1,11 +1,22 @@
 use crate::core::oml_object::{
     OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
 };
-use crate::core::generate::{Generate, BackwardsGenerate};
+use crate::core::generate::{CommentSyntax, Generate, BackwardsGenerate};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
 use std::error::Error;
 use std::fmt::Write;
 
-pub struct SqlGenerator;
+#[derive(Default)]
+pub struct SqlGenerator {
+    pub style: CodeStyle,
+}
+
+impl SqlGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
 
 impl BackwardsGenerate for SqlGenerator {
     fn reverse(&self, content: &str) -> Result<Vec<OmlObject>, Box<dyn Error>> {
@@ -20,7 +31,7 @@ impl BackwardsGenerate for SqlGenerator {
                 let name = trimmed
                     .strip_prefix("CREATE TABLE ")
                     .unwrap()
-                    .trim_end_matches(|c: char| c == '(' || c == ' ')
+                    .trim_end_matches(['(', ' '])
                     .to_string();
 
                 // Check if this is an enum (lookup table) by looking for INSERT with name values
@@ -58,6 +69,7 @@ impl BackwardsGenerate for SqlGenerator {
                                     var_type: "string".to_string(),
                                     array_kind: ArrayKind::None,
                                     name: clean.to_string(),
+                                    ..Default::default()
                                 });
                             }
                         }
@@ -70,6 +82,7 @@ impl BackwardsGenerate for SqlGenerator {
                         oml_type: ObjectType::ENUM,
                         name,
                         variables: vars,
+                        ..Default::default()
                     });
                 } else {
                     // Parse as struct from columns
@@ -82,6 +95,7 @@ impl BackwardsGenerate for SqlGenerator {
                         oml_type: ObjectType::STRUCT,
                         name,
                         variables: vars,
+                        ..Default::default()
                     });
                 }
                 continue;
@@ -153,12 +167,13 @@ fn parse_sql_column(line: &str) -> Option<Variable> {
         var_type: reverse_sql_type(&sql_type_str),
         array_kind: ArrayKind::None,
         name,
+        ..Default::default()
     })
 }
 
 impl Generate for SqlGenerator {
     fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
-        let mut sql_file = String::new();
+        let mut sql_file = CodeWriter::new(self.style);
 
         writeln!(sql_file, "-- This file has been generated from {}.oml", file_name)?;
         writeln!(sql_file)?;
@@ -175,12 +190,16 @@ impl Generate for SqlGenerator {
             }
         }
 
-        Ok(sql_file)
+        Ok(sql_file.finish())
     }
 
     fn extension(&self) -> &str {
         "sql"
     }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::Line("--")
+    }
 }
 
 /// Generates a simple lookup table for an OML enum.
@@ -193,10 +212,10 @@ impl Generate for SqlGenerator {
 /// );
 /// INSERT INTO Color (name) VALUES ('RED'), ('GREEN'), ('BLUE');
 /// ```
-fn generate_enum_table(oml_object: &OmlObject, sql_file: &mut String) -> Result<(), std::fmt::Error> {
+fn generate_enum_table(oml_object: &OmlObject, sql_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
     writeln!(sql_file, "CREATE TABLE {} (", oml_object.name)?;
-    writeln!(sql_file, "\tid   INT          NOT NULL AUTO_INCREMENT PRIMARY KEY,")?;
-    writeln!(sql_file, "\tname VARCHAR(255) NOT NULL")?;
+    writeln!(sql_file, "{}id   INT          NOT NULL AUTO_INCREMENT PRIMARY KEY,", sql_file.indent(1))?;
+    writeln!(sql_file, "{}name VARCHAR(255) NOT NULL", sql_file.indent(1))?;
     writeln!(sql_file, ");")?;
 
     if !oml_object.variables.is_empty() {
@@ -221,7 +240,7 @@ fn generate_enum_table(oml_object: &OmlObject, sql_file: &mut String) -> Result<
 /// Static arrays (`T[N]`) produce N individual columns (e.g. `col_0`, `col_1`, …).
 fn generate_table(
     oml_object: &OmlObject,
-    sql_file: &mut String,
+    sql_file: &mut CodeWriter,
 ) -> Result<(), std::fmt::Error> {
     // Collect inline columns (non-dynamic-array fields)
     let inline_vars: Vec<&Variable> = oml_object.variables
@@ -236,7 +255,7 @@ fn generate_table(
         .collect();
 
     writeln!(sql_file, "CREATE TABLE {} (", oml_object.name)?;
-    writeln!(sql_file, "\tid INT NOT NULL AUTO_INCREMENT PRIMARY KEY,")?;
+    writeln!(sql_file, "{}id INT NOT NULL AUTO_INCREMENT PRIMARY KEY,", sql_file.indent(1))?;
 
     for var in &inline_vars {
         match &var.array_kind {
@@ -247,7 +266,7 @@ fn generate_table(
                     let null_str = if is_optional { "NULL" } else { "NOT NULL" };
                     writeln!(
                         sql_file,
-                        "\t{}_{} {} {},",
+                        "{}{}_{} {} {},", sql_file.indent(1),
                         var.name, i,
                         convert_type(&var.var_type),
                         null_str
@@ -259,7 +278,7 @@ fn generate_table(
                 let null_str = if is_optional { "NULL" } else { "NOT NULL" };
                 writeln!(
                     sql_file,
-                    "\t{} {} {},",
+                    "{}{} {} {},", sql_file.indent(1),
                     var.name,
                     convert_type(&var.var_type),
                     null_str
@@ -273,7 +292,7 @@ fn generate_table(
     // Approach: always emit trailing commas above, then add a closing line without one.
     // The PRIMARY KEY line above serves as the last "guaranteed" line; the field lines
     // all get a trailing comma which is fine because at minimum `id` is always present.
-    writeln!(sql_file, "\tCONSTRAINT pk_{} PRIMARY KEY (id)", oml_object.name)?;
+    writeln!(sql_file, "{}CONSTRAINT pk_{} PRIMARY KEY (id)", sql_file.indent(1), oml_object.name)?;
     writeln!(sql_file, ");")?;
 
     // Junction tables for dynamic-array fields
@@ -282,10 +301,10 @@ fn generate_table(
         let junction_name = format!("{}_{}", oml_object.name, var.name);
         writeln!(sql_file, "-- Junction table for {}.{} (list {})", oml_object.name, var.name, var.var_type)?;
         writeln!(sql_file, "CREATE TABLE {} (", junction_name)?;
-        writeln!(sql_file, "\tid         INT NOT NULL AUTO_INCREMENT PRIMARY KEY,")?;
-        writeln!(sql_file, "\tparent_id  INT NOT NULL,")?;
-        writeln!(sql_file, "\tvalue      {} NOT NULL,", convert_type(&var.var_type))?;
-        writeln!(sql_file, "\tCONSTRAINT fk_{}_{} FOREIGN KEY (parent_id) REFERENCES {}(id)", junction_name, oml_object.name, oml_object.name)?;
+        writeln!(sql_file, "{}id         INT NOT NULL AUTO_INCREMENT PRIMARY KEY,", sql_file.indent(1))?;
+        writeln!(sql_file, "{}parent_id  INT NOT NULL,", sql_file.indent(1))?;
+        writeln!(sql_file, "{}value      {} NOT NULL,", sql_file.indent(1), convert_type(&var.var_type))?;
+        writeln!(sql_file, "{}CONSTRAINT fk_{}_{} FOREIGN KEY (parent_id) REFERENCES {}(id)", sql_file.indent(1), junction_name, oml_object.name, oml_object.name)?;
         writeln!(sql_file, ");")?;
     }
 
@@ -16,18 +16,18 @@ fn ensure_test_results_dir() {
 fn generate_and_write(oml_path: &str, file_name: &str) -> String {
     ensure_test_results_dir();
 
-    let generator = SqlGenerator;
+    let generator = SqlGenerator::default();
 
     let path = Path::new(oml_path);
     let (oml_objects, _imports) = OmlObject::get_from_file(path)
-        .expect(&format!("Failed to parse OML file: {}", oml_path));
+        .unwrap_or_else(|_| panic!("Failed to parse OML file: {}", oml_path));
 
     let sql_output = generator.generate(&oml_objects, file_name)
-        .expect(&format!("Failed to generate SQL for: {}", file_name));
+        .unwrap_or_else(|_| panic!("Failed to generate SQL for: {}", file_name));
 
     let output_path = format!("{}/{}.{}", TEST_RESULTS_DIR, file_name, generator.extension());
     fs::write(&output_path, &sql_output)
-        .expect(&format!("Failed to write output file: {}", output_path));
+        .unwrap_or_else(|_| panic!("Failed to write output file: {}", output_path));
 
     sql_output
 }
@@ -117,9 +117,10 @@ fn test_enum_empty_generates_no_insert() {
         oml_type: ObjectType::ENUM,
         name: "Empty".to_string(),
         variables: vec![],
+        ..Default::default()
     };
 
-    let output = SqlGenerator.generate(std::slice::from_ref(&oml_object), "Empty").unwrap();
+    let output = SqlGenerator::default().generate(std::slice::from_ref(&oml_object), "Empty").unwrap();
     assert!(output.contains("CREATE TABLE Empty ("));
     assert!(!output.contains("INSERT INTO"));
 }
@@ -130,12 +131,13 @@ fn test_optional_field_allows_null() {
         oml_type: ObjectType::CLASS,
         name: "User".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string() },
-            Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "email".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string(), ..Default::default() },
+            Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "email".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = SqlGenerator.generate(std::slice::from_ref(&oml_object), "User").unwrap();
+    let output = SqlGenerator::default().generate(std::slice::from_ref(&oml_object), "User").unwrap();
     assert!(output.contains("name TEXT NOT NULL"));
     assert!(output.contains("email TEXT NULL"));
 }
@@ -146,11 +148,12 @@ fn test_static_array_expands_to_n_columns() {
         oml_type: ObjectType::CLASS,
         name: "Rgb".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "uint8".to_string(), array_kind: ArrayKind::Static(3), name: "color".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "uint8".to_string(), array_kind: ArrayKind::Static(3), name: "color".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = SqlGenerator.generate(std::slice::from_ref(&oml_object), "Rgb").unwrap();
+    let output = SqlGenerator::default().generate(std::slice::from_ref(&oml_object), "Rgb").unwrap();
     assert!(output.contains("color_0 TINYINT UNSIGNED NOT NULL"));
     assert!(output.contains("color_1 TINYINT UNSIGNED NOT NULL"));
     assert!(output.contains("color_2 TINYINT UNSIGNED NOT NULL"));
@@ -164,12 +167,13 @@ fn test_dynamic_list_generates_junction_table_with_fk() {
         oml_type: ObjectType::CLASS,
         name: "Post".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "title".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::Dynamic, name: "tags".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "title".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::Dynamic, name: "tags".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = SqlGenerator.generate(std::slice::from_ref(&oml_object), "Post").unwrap();
+    let output = SqlGenerator::default().generate(std::slice::from_ref(&oml_object), "Post").unwrap();
     assert!(!output.contains("\ttags "));
     assert!(output.contains("CREATE TABLE Post_tags ("));
     assert!(output.contains("FOREIGN KEY (parent_id) REFERENCES Post(id)"));
@@ -191,10 +195,11 @@ fn test_all_builtin_types_convert_to_sql() {
         var_type: oml_type.to_string(),
         array_kind: ArrayKind::None,
         name: format!("field_{}", i),
+        ..Default::default()
     }).collect();
 
-    let oml_object = OmlObject { oml_type: ObjectType::CLASS, name: "AllTypes".to_string(), variables };
-    let output = SqlGenerator.generate(std::slice::from_ref(&oml_object), "AllTypes").unwrap();
+    let oml_object = OmlObject { oml_type: ObjectType::CLASS, name: "AllTypes".to_string(), variables, ..Default::default() };
+    let output = SqlGenerator::default().generate(std::slice::from_ref(&oml_object), "AllTypes").unwrap();
 
     for (i, (_, expected)) in pairs.iter().enumerate() {
         let expected_col = format!("field_{} {} NOT NULL", i, expected);
@@ -208,22 +213,23 @@ fn test_custom_type_maps_to_int_for_fk() {
         oml_type: ObjectType::CLASS,
         name: "Order".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "Customer".to_string(), array_kind: ArrayKind::None, name: "customer".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "Customer".to_string(), array_kind: ArrayKind::None, name: "customer".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = SqlGenerator.generate(std::slice::from_ref(&oml_object), "Order").unwrap();
+    let output = SqlGenerator::default().generate(std::slice::from_ref(&oml_object), "Order").unwrap();
     // Custom types stored as INT (FK reference placeholder)
     assert!(output.contains("customer INT NOT NULL"));
 }
 
 #[test]
 fn test_undecided_object_type_returns_error() {
-    let oml_object = OmlObject { oml_type: ObjectType::UNDECIDED, name: "Bad".to_string(), variables: vec![] };
-    assert!(SqlGenerator.generate(std::slice::from_ref(&oml_object), "Bad").is_err());
+    let oml_object = OmlObject { oml_type: ObjectType::UNDECIDED, name: "Bad".to_string(), variables: vec![], ..Default::default() };
+    assert!(SqlGenerator::default().generate(std::slice::from_ref(&oml_object), "Bad").is_err());
 }
 
 #[test]
 fn test_extension_is_sql() {
-    assert_eq!(SqlGenerator.extension(), "sql");
+    assert_eq!(SqlGenerator::default().extension(), "sql");
 }
@@ -2,10 +2,28 @@ use crate::core::oml_object::{
     OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
 };
 use crate::core::generate::{Generate, BackwardsGenerate};
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::naming::{Case, NamingConvention};
 use std::error::Error;
 use std::fmt::Write;
 
-pub struct TypescriptGenerator;
+#[derive(Default)]
+pub struct TypescriptGenerator {
+    /// Emit classes and structs as `export interface` instead of `export class`
+    pub use_interfaces: bool,
+    pub style: CodeStyle,
+}
+
+impl TypescriptGenerator {
+    pub fn new(use_interfaces: bool) -> Self {
+        Self { use_interfaces, style: CodeStyle::default() }
+    }
+
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
 
 impl BackwardsGenerate for TypescriptGenerator {
     fn reverse(&self, content: &str) -> Result<Vec<OmlObject>, Box<dyn Error>> {
@@ -20,7 +38,7 @@ impl BackwardsGenerate for TypescriptGenerator {
                 let name = trimmed
                     .strip_prefix("export enum ")
                     .unwrap()
-                    .trim_end_matches(|c: char| c == '{' || c == ' ')
+                    .trim_end_matches(['{', ' '])
                     .to_string();
                 let mut vars = Vec::new();
                 i += 1;
@@ -36,6 +54,7 @@ impl BackwardsGenerate for TypescriptGenerator {
                             var_type: "string".to_string(),
                             array_kind: ArrayKind::None,
                             name: variant,
+                            ..Default::default()
                         });
                     }
                     i += 1;
@@ -44,12 +63,16 @@ impl BackwardsGenerate for TypescriptGenerator {
                     oml_type: ObjectType::ENUM,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
-            } else if trimmed.starts_with("export class ") && trimmed.ends_with('{') {
+            } else if (trimmed.starts_with("export class ") || trimmed.starts_with("export interface "))
+                && trimmed.ends_with('{')
+            {
+                let is_interface = trimmed.starts_with("export interface ");
                 let name = trimmed
-                    .strip_prefix("export class ")
-                    .unwrap()
-                    .trim_end_matches(|c: char| c == '{' || c == ' ')
+                    .trim_start_matches("export class ")
+                    .trim_start_matches("export interface ")
+                    .trim_end_matches(['{', ' '])
                     .to_string();
                 let mut vars = Vec::new();
                 i += 1;
@@ -58,10 +81,14 @@ impl BackwardsGenerate for TypescriptGenerator {
                     if line == "}" { break; }
                     // Stop at constructor
                     if line.starts_with("constructor(") { break; }
-                    if line.ends_with(';') && !line.contains('(') {
-                        if let Some(var) = parse_ts_field(line) {
-                            vars.push(var);
+                    if line.ends_with(';') && !line.contains('(')
+                        && let Some(mut var) = parse_ts_field(line)
+                    {
+                        // interface members are always public
+                        if is_interface {
+                            var.visibility = VariableVisibility::PUBLIC;
                         }
+                        vars.push(var);
                     }
                     i += 1;
                 }
@@ -77,6 +104,7 @@ impl BackwardsGenerate for TypescriptGenerator {
                     oml_type: ObjectType::CLASS,
                     name,
                     variables: vars,
+                    ..Default::default()
                 });
                 continue;
             }
@@ -90,6 +118,7 @@ impl BackwardsGenerate for TypescriptGenerator {
 fn reverse_ts_type(ts_type: &str) -> String {
     match ts_type {
         "number" => "int32".to_string(),
+        "bigint" => "int64".to_string(),
         "boolean" => "bool".to_string(),
         "string" => "string".to_string(),
         other => other.to_string(),
@@ -145,6 +174,15 @@ fn parse_ts_field(line: &str) -> Option<Variable> {
         rest[colon_pos + 2..].trim()
     };
 
+    // Split off a static's initial value: "Type = value"
+    let (type_part, value) = match type_part.split_once(" = ") {
+        Some((type_part, value)) => {
+            let number = value.strip_suffix('n').filter(|n| n.parse::<i128>().is_ok());
+            (type_part, Some(number.unwrap_or(value).to_string()))
+        }
+        None => (type_part, None),
+    };
+
     // Remove "| null" suffix
     let type_part = type_part.trim_end_matches("| null").trim();
 
@@ -180,12 +218,23 @@ fn parse_ts_field(line: &str) -> Option<Variable> {
         var_type,
         array_kind,
         name,
+        value,
+        ..Default::default()
     })
 }
 
+/// Words reserved in TypeScript, which fields are renamed away from.
+const KEYWORDS: [&str; 46] = [
+    "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do",
+    "else", "enum", "export", "extends", "false", "finally", "for", "function", "if", "import",
+    "in", "instanceof", "new", "null", "return", "super", "switch", "this", "throw", "true", "try",
+    "typeof", "var", "void", "while", "with", "implements", "interface", "let", "package",
+    "private", "protected", "public", "static", "yield", "await",
+];
+
 impl Generate for TypescriptGenerator {
     fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
-        let mut ts_file = String::new();
+        let mut ts_file = CodeWriter::new(self.style);
 
         writeln!(ts_file, "// This file has been generated from {}.oml", file_name)?;
         writeln!(ts_file)?;
@@ -193,9 +242,11 @@ impl Generate for TypescriptGenerator {
         for (i, oml_object) in oml_objects.iter().enumerate() {
             match &oml_object.oml_type {
                 ObjectType::ENUM => generate_enum(oml_object, &mut ts_file)?,
-                ObjectType::CLASS => generate_class(oml_object, &mut ts_file)?,
                 // TypeScript has no struct keyword; structs map to classes
-                ObjectType::STRUCT => generate_class(oml_object, &mut ts_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT if self.use_interfaces => {
+                    generate_interface(oml_object, &mut ts_file)?
+                }
+                ObjectType::CLASS | ObjectType::STRUCT => generate_class(oml_object, &mut ts_file)?,
                 ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
             }
             if i < oml_objects.len() - 1 {
@@ -203,21 +254,34 @@ impl Generate for TypescriptGenerator {
             }
         }
 
-        Ok(ts_file)
+        Ok(ts_file.finish())
     }
 
     fn extension(&self) -> &str {
         "ts"
     }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn naming(&self) -> NamingConvention {
+        NamingConvention {
+            types: Case::Pascal,
+            fields: Case::Camel,
+            constants: Case::ScreamingSnake,
+            enum_values: Case::ScreamingSnake,
+        }
+    }
 }
 
-fn generate_enum(oml_object: &OmlObject, ts_file: &mut String) -> Result<(), std::fmt::Error> {
-    writeln!(ts_file, "export enum {} {{", oml_object.name)?;
+fn generate_enum(oml_object: &OmlObject, ts_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    ts_file.open_block(0, &format!("export enum {}", oml_object.name))?;
     let length = oml_object.variables.len();
 
     for (index, var) in oml_object.variables.iter().enumerate() {
         let name = var.name.to_uppercase();
-        write!(ts_file, "\t{} = \"{}\"", name, name)?;
+        write!(ts_file, "{}{} = \"{}\"", ts_file.indent(1), name, name)?;
         if index == length - 1 {
             writeln!(ts_file)?;
         } else {
@@ -232,9 +296,9 @@ fn generate_enum(oml_object: &OmlObject, ts_file: &mut String) -> Result<(), std
 
 fn generate_class(
     oml_object: &OmlObject,
-    ts_file: &mut String,
+    ts_file: &mut CodeWriter,
 ) -> Result<(), std::fmt::Error> {
-    writeln!(ts_file, "export class {} {{", oml_object.name)?;
+    ts_file.open_block(0, &format!("export class {}", oml_object.name))?;
 
     if oml_object.variables.is_empty() {
         writeln!(ts_file, "}}")?;
@@ -255,42 +319,62 @@ fn generate_class(
         .collect();
 
     if !instance_vars.is_empty() {
-        // Required params first, then optional
-        let required: Vec<&&Variable> = instance_vars
-            .iter()
-            .filter(|v| !v.var_mod.contains(&VariableModifier::OPTIONAL))
-            .collect();
-        let optional: Vec<&&Variable> = instance_vars
-            .iter()
-            .filter(|v| v.var_mod.contains(&VariableModifier::OPTIONAL))
-            .collect();
-
-        let total = required.len() + optional.len();
-        let mut index = 0;
-
-        write!(ts_file, "\tconstructor(")?;
+        // Required params first, then those with a default: a value, or
+        // null when optional
+        let has_default = |v: &&&Variable| v.value.is_some() || v.var_mod.contains(&VariableModifier::OPTIONAL);
+        let required: Vec<&&Variable> = instance_vars.iter().filter(|v| !has_default(v)).collect();
+        let optional: Vec<&&Variable> = instance_vars.iter().filter(has_default).collect();
+
+        let mut params: Vec<String> = Vec::new();
         for var in &required {
             let ts_type = type_annotation(&var.var_type, &var.array_kind);
-            write!(ts_file, "{}: {}", var.name, ts_type)?;
-            index += 1;
-            if index < total { write!(ts_file, ", ")?; }
+            params.push(format!("{}: {}", var.name, ts_type));
         }
         for var in &optional {
             let ts_type = type_annotation(&var.var_type, &var.array_kind);
-            write!(ts_file, "{}: {} | null = null", var.name, ts_type)?;
-            index += 1;
-            if index < total { write!(ts_file, ", ")?; }
+            match &var.value {
+                Some(value) => params.push(format!("{}: {} = {}", var.name, ts_type, ts_literal(&var.var_type, value))),
+                None => params.push(format!("{}: {} | null = null", var.name, ts_type)),
+            }
         }
-        writeln!(ts_file, ") {{")?;
+        ts_file.open_block(1, &format!("constructor({})", params.join(", ")))?;
 
         for var in &required {
-            writeln!(ts_file, "\t\tthis.{} = {};", var.name, var.name)?;
+            writeln!(ts_file, "{}this.{} = {};", ts_file.indent(2), var.name, var.name)?;
         }
         for var in &optional {
-            writeln!(ts_file, "\t\tthis.{} = {};", var.name, var.name)?;
+            writeln!(ts_file, "{}this.{} = {};", ts_file.indent(2), var.name, var.name)?;
+        }
+
+        writeln!(ts_file, "{}}}", ts_file.indent(1))?;
+    }
+
+    writeln!(ts_file, "}}")?;
+
+    Ok(())
+}
+
+fn generate_interface(
+    oml_object: &OmlObject,
+    ts_file: &mut CodeWriter,
+) -> Result<(), std::fmt::Error> {
+    ts_file.open_block(0, &format!("export interface {}", oml_object.name))?;
+
+    // Interfaces describe instances only, so statics and visibility are dropped
+    let (static_vars, instance_vars): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    for var in instance_vars {
+        write!(ts_file, "{}", ts_file.indent(1))?;
+        if var.is_const() {
+            write!(ts_file, "readonly ")?;
         }
+        write_name_and_type(var, ts_file)?;
+    }
 
-        writeln!(ts_file, "\t}}")?;
+    for var in static_vars {
+        writeln!(ts_file, "{}// static {} is not representable in an interface", ts_file.indent(1), var.name)?;
     }
 
     writeln!(ts_file, "}}")?;
@@ -299,8 +383,8 @@ fn generate_class(
 }
 
 /// Writes a single class field declaration.
-fn write_field(var: &Variable, ts_file: &mut String) -> Result<(), std::fmt::Error> {
-    write!(ts_file, "\t")?;
+fn write_field(var: &Variable, ts_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    write!(ts_file, "{}", ts_file.indent(1))?;
 
     // Visibility
     match var.visibility {
@@ -315,12 +399,29 @@ fn write_field(var: &Variable, ts_file: &mut String) -> Result<(), std::fmt::Err
     }
 
     // readonly for const (without mut override)
-    if var.var_mod.contains(&VariableModifier::CONST)
-        && !var.var_mod.contains(&VariableModifier::MUT)
-    {
+    if var.is_const() {
         write!(ts_file, "readonly ")?;
     }
 
+    // statics start from their value; instance fields get it in the constructor
+    if var.var_mod.contains(&VariableModifier::STATIC) && let Some(value) = &var.value {
+        let ts_type = type_annotation(&var.var_type, &var.array_kind);
+        return writeln!(ts_file, "{}: {} = {};", var.name, ts_type, ts_literal(&var.var_type, value));
+    }
+
+    write_name_and_type(var, ts_file)
+}
+
+/// An OML `value` as a TypeScript literal; 64-bit integers are `bigint`s.
+fn ts_literal(var_type: &str, value: &str) -> String {
+    match var_type {
+        "int64" | "uint64" => format!("{}n", value),
+        _ => value.to_string(),
+    }
+}
+
+/// Writes `name: type;`, or `name?: type | null;` for optional members.
+fn write_name_and_type(var: &Variable, ts_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
     let ts_type = type_annotation(&var.var_type, &var.array_kind);
 
     if var.var_mod.contains(&VariableModifier::OPTIONAL) {
@@ -335,9 +436,11 @@ fn write_field(var: &Variable, ts_file: &mut String) -> Result<(), std::fmt::Err
 #[inline]
 fn convert_type(var_type: &str) -> String {
     match var_type {
-        "int8" | "int16" | "int32" | "int64"
-        | "uint8" | "uint16" | "uint32" | "uint64"
+        "int8" | "int16" | "int32"
+        | "uint8" | "uint16" | "uint32"
         | "float" | "double" => "number".to_string(),
+        // 64-bit integers exceed Number.MAX_SAFE_INTEGER
+        "int64" | "uint64" => "bigint".to_string(),
         "bool" => "boolean".to_string(),
         "string" | "char" => "string".to_string(),
         other => other.to_string(),
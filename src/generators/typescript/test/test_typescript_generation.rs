@@ -1,7 +1,7 @@
 use std::fs;
 use std::path::Path;
 
-use crate::core::generate::Generate;
+use crate::core::generate::{Generate, BackwardsGenerate};
 use crate::core::oml_object::{
     OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
 };
@@ -16,18 +16,18 @@ fn ensure_test_results_dir() {
 fn generate_and_write(oml_path: &str, file_name: &str) -> String {
     ensure_test_results_dir();
 
-    let generator = TypescriptGenerator;
+    let generator = TypescriptGenerator::default();
 
     let path = Path::new(oml_path);
     let (oml_objects, _imports) = OmlObject::get_from_file(path)
-        .expect(&format!("Failed to parse OML file: {}", oml_path));
+        .unwrap_or_else(|_| panic!("Failed to parse OML file: {}", oml_path));
 
     let ts_output = generator.generate(&oml_objects, file_name)
-        .expect(&format!("Failed to generate TypeScript for: {}", file_name));
+        .unwrap_or_else(|_| panic!("Failed to generate TypeScript for: {}", file_name));
 
     let output_path = format!("{}/{}.{}", TEST_RESULTS_DIR, file_name, generator.extension());
     fs::write(&output_path, &ts_output)
-        .expect(&format!("Failed to write output file: {}", output_path));
+        .unwrap_or_else(|_| panic!("Failed to write output file: {}", output_path));
 
     ts_output
 }
@@ -132,11 +132,12 @@ fn test_enum_single_variant_no_trailing_comma() {
         oml_type: ObjectType::ENUM,
         name: "Single".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Only".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "".to_string(), array_kind: ArrayKind::None, name: "Only".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = TypescriptGenerator.generate(std::slice::from_ref(&oml_object), "Single").unwrap();
+    let output = TypescriptGenerator::default().generate(std::slice::from_ref(&oml_object), "Single").unwrap();
     assert!(output.contains("\tONLY = \"ONLY\""));
     assert!(!output.contains("ONLY = \"ONLY\","));
 }
@@ -147,9 +148,10 @@ fn test_empty_class_no_constructor() {
         oml_type: ObjectType::CLASS,
         name: "Empty".to_string(),
         variables: vec![],
+        ..Default::default()
     };
 
-    let output = TypescriptGenerator.generate(std::slice::from_ref(&oml_object), "Empty").unwrap();
+    let output = TypescriptGenerator::default().generate(std::slice::from_ref(&oml_object), "Empty").unwrap();
     assert!(output.contains("export class Empty {"));
     assert!(!output.contains("constructor"));
 }
@@ -160,11 +162,12 @@ fn test_const_field_generates_readonly() {
         oml_type: ObjectType::CLASS,
         name: "Config".to_string(),
         variables: vec![
-            Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "version".to_string() },
+            Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "version".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = TypescriptGenerator.generate(std::slice::from_ref(&oml_object), "Config").unwrap();
+    let output = TypescriptGenerator::default().generate(std::slice::from_ref(&oml_object), "Config").unwrap();
     assert!(output.contains("public readonly version: string;"));
 }
 
@@ -174,12 +177,13 @@ fn test_static_field_not_in_constructor() {
         oml_type: ObjectType::CLASS,
         name: "Counter".to_string(),
         variables: vec![
-            Variable { var_mod: vec![VariableModifier::STATIC], visibility: VariableVisibility::PUBLIC, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "count".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string() },
+            Variable { var_mod: vec![VariableModifier::STATIC], visibility: VariableVisibility::PUBLIC, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "count".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE, var_type: "string".to_string(), array_kind: ArrayKind::None, name: "name".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = TypescriptGenerator.generate(std::slice::from_ref(&oml_object), "Counter").unwrap();
+    let output = TypescriptGenerator::default().generate(std::slice::from_ref(&oml_object), "Counter").unwrap();
     assert!(output.contains("public static count: number;"));
     assert!(!output.contains("this.count"));
 }
@@ -190,13 +194,14 @@ fn test_visibility_modifiers_emitted() {
         oml_type: ObjectType::CLASS,
         name: "Vis".to_string(),
         variables: vec![
-            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC,    var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "pub_val".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PROTECTED, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "prot_val".to_string() },
-            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE,   var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "priv_val".to_string() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC,    var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "pub_val".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PROTECTED, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "prot_val".to_string(), ..Default::default() },
+            Variable { var_mod: vec![], visibility: VariableVisibility::PRIVATE,   var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "priv_val".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
 
-    let output = TypescriptGenerator.generate(std::slice::from_ref(&oml_object), "Vis").unwrap();
+    let output = TypescriptGenerator::default().generate(std::slice::from_ref(&oml_object), "Vis").unwrap();
     assert!(output.contains("public pub_val: number;"));
     assert!(output.contains("protected prot_val: number;"));
     assert!(output.contains("private priv_val: number;"));
@@ -205,8 +210,8 @@ fn test_visibility_modifiers_emitted() {
 #[test]
 fn test_all_builtin_types_convert_to_ts() {
     let vars: Vec<(&str, &str)> = vec![
-        ("int8",   "number"), ("int16",  "number"), ("int32",  "number"), ("int64",  "number"),
-        ("uint8",  "number"), ("uint16", "number"), ("uint32", "number"), ("uint64", "number"),
+        ("int8",   "number"), ("int16",  "number"), ("int32",  "number"), ("int64",  "bigint"),
+        ("uint8",  "number"), ("uint16", "number"), ("uint32", "number"), ("uint64", "bigint"),
         ("float",  "number"), ("double", "number"),
         ("bool",   "boolean"),
         ("string", "string"), ("char",   "string"),
@@ -218,10 +223,11 @@ fn test_all_builtin_types_convert_to_ts() {
         var_type: oml_type.to_string(),
         array_kind: ArrayKind::None,
         name: format!("field_{}", i),
+        ..Default::default()
     }).collect();
 
-    let oml_object = OmlObject { oml_type: ObjectType::CLASS, name: "AllTypes".to_string(), variables };
-    let output = TypescriptGenerator.generate(std::slice::from_ref(&oml_object), "AllTypes").unwrap();
+    let oml_object = OmlObject { oml_type: ObjectType::CLASS, name: "AllTypes".to_string(), variables, ..Default::default() };
+    let output = TypescriptGenerator::default().generate(std::slice::from_ref(&oml_object), "AllTypes").unwrap();
 
     for (i, (_, expected)) in vars.iter().enumerate() {
         let expected_field = format!("field_{}: {};", i, expected);
@@ -231,11 +237,68 @@ fn test_all_builtin_types_convert_to_ts() {
 
 #[test]
 fn test_undecided_object_type_returns_error() {
-    let oml_object = OmlObject { oml_type: ObjectType::UNDECIDED, name: "Bad".to_string(), variables: vec![] };
-    assert!(TypescriptGenerator.generate(std::slice::from_ref(&oml_object), "Bad").is_err());
+    let oml_object = OmlObject { oml_type: ObjectType::UNDECIDED, name: "Bad".to_string(), variables: vec![], ..Default::default() };
+    assert!(TypescriptGenerator::default().generate(std::slice::from_ref(&oml_object), "Bad").is_err());
 }
 
 #[test]
 fn test_extension_is_ts() {
-    assert_eq!(TypescriptGenerator.extension(), "ts");
+    assert_eq!(TypescriptGenerator::default().extension(), "ts");
+}
+
+#[test]
+fn test_interface_mode_emits_interfaces() {
+    let oml_object = OmlObject {
+        oml_type: ObjectType::CLASS,
+        name: "User".to_string(),
+        variables: vec![
+            Variable { var_mod: vec![VariableModifier::CONST], visibility: VariableVisibility::PRIVATE, var_type: "int64".to_string(), array_kind: ArrayKind::None, name: "id".to_string(), ..Default::default() },
+            Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PUBLIC, var_type: "string".to_string(), array_kind: ArrayKind::Dynamic, name: "tags".to_string(), ..Default::default() },
+            Variable { var_mod: vec![VariableModifier::STATIC], visibility: VariableVisibility::PUBLIC, var_type: "int32".to_string(), array_kind: ArrayKind::None, name: "count".to_string(), ..Default::default() },
+        ],
+        ..Default::default()
+    };
+
+    let output = TypescriptGenerator::new(true).generate(std::slice::from_ref(&oml_object), "User").unwrap();
+    assert!(output.contains("export interface User {"), "Got: {}", output);
+    assert!(output.contains("\treadonly id: bigint;"), "Got: {}", output);
+    assert!(output.contains("\ttags?: string[] | null;"), "Got: {}", output);
+    assert!(output.contains("// static count is not representable in an interface"), "Got: {}", output);
+    assert!(!output.contains("constructor"), "Got: {}", output);
+    assert!(!output.contains("private"), "Got: {}", output);
+}
+
+#[test]
+fn test_interface_round_trips_through_reverse() {
+    let oml_object = OmlObject {
+        oml_type: ObjectType::STRUCT,
+        name: "Point".to_string(),
+        variables: vec![
+            Variable { var_mod: vec![], visibility: VariableVisibility::PUBLIC, var_type: "int64".to_string(), array_kind: ArrayKind::None, name: "x".to_string(), ..Default::default() },
+            Variable { var_mod: vec![VariableModifier::OPTIONAL], visibility: VariableVisibility::PUBLIC, var_type: "bool".to_string(), array_kind: ArrayKind::None, name: "visible".to_string(), ..Default::default() },
+        ],
+        ..Default::default()
+    };
+
+    let generator = TypescriptGenerator::new(true);
+    let output = generator.generate(std::slice::from_ref(&oml_object), "Point").unwrap();
+    let reversed = generator.reverse(&output).unwrap();
+
+    assert_eq!(reversed.len(), 1);
+    assert_eq!(reversed[0].name, "Point");
+    assert_eq!(reversed[0].variables, oml_object.variables);
+}
+
+#[test]
+fn test_values_initialise_statics_and_default_params() {
+    let objects = OmlObject::scan_file(
+        "class Car {\n public static const int32 MAX_SPEED = 120;\n public int64 mileage = 5;\n public string name;\n}\n".to_string()
+    ).unwrap();
+
+    let output = TypescriptGenerator::default().generate(&objects, "Car").unwrap();
+    assert!(output.contains("\tpublic static readonly MAX_SPEED: number = 120;\n"), "Got: {}", output);
+    assert!(output.contains("\tconstructor(name: string, mileage: bigint = 5n) {\n"), "Got: {}", output);
+
+    let reversed = TypescriptGenerator::default().reverse(&output).unwrap();
+    assert_eq!(reversed[0].variables[0].value.as_deref(), Some("120"));
 }
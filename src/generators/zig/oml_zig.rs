@@ -0,0 +1,264 @@
+use crate::core::oml_object::{
+    OmlObject, ObjectType, Variable, VariableVisibility, VariableModifier, ArrayKind
+};
+use crate::core::generate::Generate;
+use crate::core::code_writer::{CodeStyle, CodeWriter};
+use crate::core::utils::to_snake_case;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Default)]
+pub struct ZigGenerator {
+    pub style: CodeStyle,
+}
+
+impl ZigGenerator {
+    pub fn with_style(mut self, style: CodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Words reserved in Zig, which fields are renamed away from.
+const KEYWORDS: [&str; 46] = [
+    "addrspace", "align", "allowzero", "and", "anyframe", "anytype", "asm", "break", "callconv",
+    "catch", "comptime", "const", "continue", "defer", "else", "enum", "errdefer", "error",
+    "export", "extern", "fn", "for", "if", "inline", "linksection", "noalias", "noinline",
+    "nosuspend", "opaque", "or", "orelse", "packed", "pub", "resume", "return", "struct", "suspend",
+    "switch", "test", "threadlocal", "try", "union", "unreachable", "var", "volatile", "while",
+];
+
+impl Generate for ZigGenerator {
+    fn generate(&self, oml_objects: &[OmlObject], file_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut zig_file = CodeWriter::new(self.style);
+
+        writeln!(zig_file, "// This file has been generated from {}.oml", file_name)?;
+        writeln!(zig_file)?;
+
+        for (i, oml_object) in oml_objects.iter().enumerate() {
+            match &oml_object.oml_type {
+                ObjectType::ENUM => generate_enum(oml_object, &mut zig_file)?,
+                ObjectType::CLASS | ObjectType::STRUCT => generate_struct(oml_object, &mut zig_file)?,
+                ObjectType::UNDECIDED => return Err("Cannot generate code for UNDECIDED object type".into()),
+            }
+            if i < oml_objects.len() - 1 {
+                writeln!(zig_file)?;
+            }
+        }
+
+        Ok(zig_file.finish())
+    }
+
+    fn extension(&self) -> &str {
+        "zig"
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+}
+
+/// Enum with the smallest unsigned tag type that fits and explicit values,
+/// so the tags stay stable when the enum crosses a C or wire boundary.
+fn generate_enum(oml_object: &OmlObject, zig_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let tag_type = match oml_object.variables.len() {
+        0..=256 => "u8",
+        257..=65536 => "u16",
+        _ => "u32",
+    };
+
+    zig_file.open_block(0, &format!("pub const {} = enum({})", oml_object.name, tag_type))?;
+    for (index, var) in oml_object.variables.iter().enumerate() {
+        writeln!(zig_file, "{}{} = {},", zig_file.indent(1), to_snake_case(&var.name), index)?;
+    }
+    writeln!(zig_file, "}};")?;
+
+    Ok(())
+}
+
+/// Instance fields become struct fields, optional ones defaulting to null.
+/// Statics become container-level declarations: `const` for constants and
+/// `var` otherwise. Zig fields are always public, so visibility only
+/// applies to the declarations.
+fn generate_struct(oml_object: &OmlObject, zig_file: &mut CodeWriter) -> Result<(), std::fmt::Error> {
+    let (static_vars, fields): (Vec<&Variable>, Vec<&Variable>) = oml_object.variables
+        .iter()
+        .partition(|v| v.var_mod.contains(&VariableModifier::STATIC));
+
+    zig_file.open_block(0, &format!("pub const {} = struct", oml_object.name))?;
+
+    for var in &fields {
+        let default = if is_optional(var) { " = null" } else { "" };
+        writeln!(zig_file, "{}{}: {}{},", zig_file.indent(1), to_snake_case(&var.name), zig_type(var), default)?;
+    }
+
+    if !static_vars.is_empty() && !fields.is_empty() {
+        writeln!(zig_file)?;
+    }
+
+    for var in &static_vars {
+        let visibility = if var.visibility == VariableVisibility::PUBLIC { "pub " } else { "" };
+        let keyword = if is_const(var) { "const" } else { "var" };
+        writeln!(
+            zig_file,
+            "{}{}{} {}: {} = {};",
+            zig_file.indent(1),
+            visibility,
+            keyword,
+            to_snake_case(&var.name),
+            zig_type(var),
+            default_value(var),
+        )?;
+    }
+
+    writeln!(zig_file, "}};")?;
+
+    Ok(())
+}
+
+#[inline]
+fn is_const(var: &Variable) -> bool {
+    var.is_const()
+}
+
+#[inline]
+fn is_optional(var: &Variable) -> bool {
+    var.var_mod.contains(&VariableModifier::OPTIONAL)
+}
+
+#[inline]
+fn convert_type(var_type: &str) -> String {
+    match var_type {
+        "int8" => "i8".to_string(),
+        "int16" => "i16".to_string(),
+        "int32" => "i32".to_string(),
+        "int64" => "i64".to_string(),
+        "uint8" => "u8".to_string(),
+        "uint16" => "u16".to_string(),
+        "uint32" => "u32".to_string(),
+        "uint64" => "u64".to_string(),
+        "float" => "f32".to_string(),
+        "double" => "f64".to_string(),
+        "bool" => "bool".to_string(),
+        "string" => "[]const u8".to_string(),
+        "char" => "u8".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Dynamic arrays are slices, read-only for const fields.
+fn zig_type(var: &Variable) -> String {
+    let base = convert_type(&var.var_type);
+    let ty = match &var.array_kind {
+        ArrayKind::None => base,
+        ArrayKind::Static(n) => format!("[{}]{}", n, base),
+        ArrayKind::Dynamic if is_const(var) => format!("[]const {}", base),
+        ArrayKind::Dynamic => format!("[]{}", base),
+    };
+    if is_optional(var) {
+        format!("?{}", ty)
+    } else {
+        ty
+    }
+}
+
+/// Initial value for container-level declarations, which Zig requires.
+fn default_value(var: &Variable) -> String {
+    if let Some(value) = &var.value {
+        return value.clone();
+    }
+    if is_optional(var) {
+        return "null".to_string();
+    }
+    let scalar = match var.var_type.as_str() {
+        "int8" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" | "uint64" | "char" => "0",
+        "float" | "double" => "0.0",
+        "bool" => "false",
+        "string" => "\"\"",
+        _ => "undefined",
+    };
+    match &var.array_kind {
+        ArrayKind::None => scalar.to_string(),
+        ArrayKind::Static(n) => format!("[_]{}{{{}}} ** {}", convert_type(&var.var_type), scalar, n),
+        ArrayKind::Dynamic => "&.{}".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::fixtures::{member, generated};
+
+    #[test]
+    fn test_struct_fields_and_optionals() {
+        let obj = OmlObject {
+            oml_type: ObjectType::STRUCT,
+            name: "Sensor".to_string(),
+            variables: vec![
+                member("id", "uint16", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("label", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("ringBuffer", "double", VariableVisibility::PUBLIC, vec![], ArrayKind::Static(16)),
+                member("alerts", "string", VariableVisibility::PUBLIC, vec![VariableModifier::CONST], ArrayKind::Dynamic),
+                member("offset", "float", VariableVisibility::PRIVATE, vec![VariableModifier::OPTIONAL], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&ZigGenerator::default(), &obj).contains(
+            "pub const Sensor = struct {\n\
+             \tid: u16,\n\
+             \tlabel: []const u8,\n\
+             \tring_buffer: [16]f64,\n\
+             \talerts: []const []const u8,\n\
+             \toffset: ?f32 = null,\n\
+             };\n"
+        ));
+    }
+
+    #[test]
+    fn test_statics_become_declarations() {
+        let obj = OmlObject {
+            oml_type: ObjectType::CLASS,
+            name: "Limits".to_string(),
+            variables: vec![
+                member("value", "int32", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("max_size", "uint32", VariableVisibility::PUBLIC,
+                    vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::None),
+                member("counter", "int64", VariableVisibility::PRIVATE, vec![VariableModifier::STATIC], ArrayKind::None),
+                member("gains", "float", VariableVisibility::PUBLIC,
+                    vec![VariableModifier::STATIC, VariableModifier::CONST], ArrayKind::Static(3)),
+            ],
+            ..Default::default()
+        };
+        let out = generated(&ZigGenerator::default(), &obj);
+        assert!(out.contains(
+            "\tvalue: i32,\n\n\
+             \tpub const max_size: u32 = 0;\n\
+             \tvar counter: i64 = 0;\n\
+             \tpub const gains: [3]f32 = [_]f32{0.0} ** 3;\n\
+             };\n"
+        ), "Got: {}", out);
+    }
+
+    #[test]
+    fn test_enum_with_explicit_tags() {
+        let obj = OmlObject {
+            oml_type: ObjectType::ENUM,
+            name: "Mode".to_string(),
+            variables: vec![
+                member("IDLE", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+                member("FAST_RUN", "string", VariableVisibility::PUBLIC, vec![], ArrayKind::None),
+            ],
+            ..Default::default()
+        };
+        assert!(generated(&ZigGenerator::default(), &obj).contains("pub const Mode = enum(u8) {\n\tidle = 0,\n\tfast_run = 1,\n};\n"));
+    }
+
+    #[test]
+    fn test_statics_start_from_their_values() {
+        let objects = OmlObject::scan_file(
+            "class Car {\n public static const int64 max_speed = 120;\n public static const float ratio = 2;\n}\n".to_string()
+        ).unwrap();
+        let out = ZigGenerator::default().generate(&objects, "car").unwrap();
+        assert!(out.contains("\tpub const max_speed: i64 = 120;\n\tpub const ratio: f32 = 2;\n"), "Got: {}", out);
+    }
+}
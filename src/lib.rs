@@ -0,0 +1,6 @@
+//! The parser and generators behind the `oml` binary, also used by the
+//! benchmarks in `benches/`.
+
+pub mod cli;
+pub mod core;
+pub mod generators;
@@ -1,144 +1,683 @@
-mod core;
-mod cli;
-mod generators;
-
 use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::io::{self, IsTerminal, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use clap::Parser;
-use cli::oml::{OmlCli, Commands, get_backwards_generator, get_generators_from_flags};
-use crate::core::import_resolver::resolve_all;
-use crate::core::oml_object::OmlObject;
-use crate::core::backwards_converting::OmlGenerator;
-use crate::core::generate::Generate;
+use oml::cli::oml::{OmlCli, Commands, GeneratorArgs, get_backwards_generator};
+use oml::cli::repl::Repl;
+use oml::cli::scaffold;
+use oml::core::import_resolver::ImportResolver;
+use oml::core::oml_object::{DeclaredObjects, DuplicateObject, ObjectType, OmlFile, OmlObject};
+use oml::core::backwards_converting::OmlGenerator;
+use oml::core::banner::Banner;
+use oml::core::generated_comment::{self, GeneratedComment, SourceNames};
+use oml::core::errors::SourceError;
+use oml::core::generate::{Generate, validate_identifiers};
+use oml::core::highlight::{self, HighlightFormat};
+use oml::core::diagnostics::{self, Diagnostic, display_path};
+use oml::core::build_cache::{BuildCache, hash_file};
+use oml::core::dir_parser::parse_dir_from_string;
+use oml::core::output_file::OutputFile;
+use oml::core::parallel::for_each_in_order;
+use oml::core::schema_diff::diff_schemas;
+use oml::core::utils::{fnv1a, to_snake_case};
+use oml::core::verify::{CheckError, Verifier, declaring_object};
 
 fn main() {
     let cli = OmlCli::parse();
+    diagnostics::set_message_format(cli.message_format);
+    diagnostics::set_color(cli.color);
+    let mut failures = Failures::new(cli.fail_fast);
 
     // Handle subcommands
-    if let Some(command) = &cli.command {
-        match command {
-            Commands::Revert { files, output } => {
-                handle_revert(files, output);
-                return;
+    match &cli.command {
+        Some(Commands::Revert { files, output }) => handle_revert(files, output),
+        Some(Commands::Translate { files, output, generator_args }) => {
+            handle_translate(files, output, generator_args, &mut failures);
+        }
+        Some(Commands::Diff { old, new, depth }) => {
+            if !handle_diff(old, new, *depth) {
+                std::process::exit(1);
             }
-            Commands::Translate { files, output, cpp, python, java, kotlin, rust, typescript, sql, use_data_class } => {
-                handle_translate(files, output, *cpp, *python, *java, *kotlin, *rust, *typescript, *sql, *use_data_class);
-                return;
+        }
+        Some(Commands::Repl { generator_args }) => {
+            let stdin = io::stdin();
+            let prompt = stdin.is_terminal();
+            if let Err(e) = Repl::new(generator_args).run(stdin.lock(), &mut io::stdout(), prompt) {
+                diagnostics::emit(&Diagnostic::error(format!("Failed to read the input: {}", e)));
             }
         }
+        Some(Commands::New { kind, name, members, output, force }) => handle_new(kind, name, members, output, *force),
+        Some(Commands::Highlight { files, format, standalone }) => handle_highlight(files, *format, *standalone, &mut failures),
+        None => generate(&cli, &mut failures),
     }
+    failures.finish();
+}
 
+/// Generates code for the inputs on the command line.
+fn generate(cli: &OmlCli, failures: &mut Failures) {
     if !cli.has_inputs() {
         OmlCli::print_help();
         return;
     }
 
-    let root_files = match cli.get_files() {
-        Ok(files) => files,
+    let header = match cli.generator_args.banner() {
+        Ok(banner) => FileHeader { banner, generated_comment: cli.generator_args.generated_comment },
         Err(e) => {
-            eprintln!("An error was encountered when parsing the input files: {:?}", e);
+            diagnostics::emit(&Diagnostic::error(format!("Failed to read the header file: {}", e)));
             return;
         }
     };
 
-    if root_files.is_empty() {
-        eprintln!("No .oml files found");
+    let output_dir = Path::new(&cli.output);
+
+    // everything besides the inputs that shapes the outputs, and --verify,
+    // which only checks the outputs a run generates
+    let options = fnv1a(format!(
+        "{} {:?} {:?} {:?}", env!("CARGO_PKG_VERSION"), cli.generator_args, header.banner, cli.verify
+    ).as_bytes());
+    let previous = if cli.no_cache { None } else { BuildCache::load(output_dir) };
+    if let Some(cache) = &previous
+        && cli.list_files().is_ok_and(|roots| cache.is_fresh(options, &roots, output_dir))
+    {
+        for path in cache.outputs() {
+            println!("Unchanged {}", output_dir.join(path).display());
+        }
         return;
     }
 
-    // Remember which paths are "root" files so we only generate code for them,
-    // not for files that were pulled in transitively via imports.
-    let root_paths: HashSet<_> = root_files.iter().map(|f| f.path.clone()).collect();
+    let generators = cli.get_generators();
 
-    // Resolve all imports (parses imported files, detects cycles).
-    let (all_files, imported_names) = match resolve_all(root_files) {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("Import error: {}", e);
+    if generators.is_empty() {
+        diagnostics::emit(&Diagnostic::error("No language flag specified (e.g. --cpp)"));
+        return;
+    }
+    if let Err(e) = cli.generator_args.check_targets() {
+        diagnostics::emit(&Diagnostic::error(e));
+        return;
+    }
+
+    let verifier = (!cli.verify.is_empty()).then(|| Verifier::new(&cli.verify, cli.verify_flags.as_deref().unwrap_or_default()));
+
+    if let Err(e) = fs::create_dir_all(output_dir) {
+        diagnostics::emit(&Diagnostic::error(format!("Failed to create output directory '{}': {}", cli.output, e)));
+        return;
+    }
+
+    let mut cache = BuildCache { options, ..Default::default() };
+    let generation = Generation {
+        args: &cli.generator_args,
+        generators: &generators,
+        header: &header,
+        previous: previous.as_ref(),
+        options,
+        output_dir,
+        verifier: verifier.as_ref(),
+    };
+
+    // Every input is checked before the first module is generated, so that
+    // objects declared twice are reported before anything is written
+    let Some(inputs) = Intake::new(&cli.generator_args, cli.fail_fast).run(cli) else {
+        return;
+    };
+    failures.failed_inputs.extend(inputs.failed);
+    if inputs.roots.is_empty() {
+        return;
+    }
+
+    let root_files = &inputs.roots;
+    let modules: Vec<Module> = if cli.generator_args.single_file {
+        vec![Module::combined(root_files)]
+    } else {
+        root_files.iter().cloned().map(Module::of_file).collect()
+    };
+    generation.run(&modules, |report| {
+        for message in &report.messages {
+            message.print();
+        }
+        failures.failed.extend(report.failed);
+        cache.modules.insert(report.key, report.outputs);
+        if failures.stop() { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    });
+    if failures.stop() {
+        return;
+    }
+
+    let mut hashes: Vec<(PathBuf, u64)> = inputs
+        .resolver
+        .files()
+        .filter_map(|f| hash_file(&f.path).map(|hash| (f.path.clone(), hash)))
+        .collect();
+    hashes.sort();
+    cache.inputs = hashes;
+    cache.roots = inputs.root_paths;
+
+    // Package files list every input, in the order given
+
+    let names: Vec<String> = root_files.iter().map(|f| cli.generator_args.source_names.name(f)).collect();
+    let name = names.join(", ");
+    let text: Vec<u8> = root_files.iter().flat_map(|f| fs::read(&f.path).unwrap_or_default()).collect();
+    for generator in &generators {
+        let renamed: Vec<Vec<OmlObject>> = modules
+            .iter()
+            .map(|m| cli.generator_args.objects_for(generator.as_ref(), &m.objects))
+            .collect();
+        let package: Vec<(&str, &[OmlObject])> = modules
+            .iter()
+            .zip(&renamed)
+            .map(|(m, objects)| (m.file_name.as_str(), objects.as_slice()))
+            .collect();
+        let objects: Vec<OmlObject> = renamed.concat();
+        let source = Source { name: &name, text: &text, objects: &objects };
+        let generated: Vec<String> = modules.iter().map(|m| format!("{}.oml", m.file_name)).collect();
+        match generator.generate_package_files(&package) {
+            Ok(files) => {
+                for file in files {
+                    let output_path = output_dir.join(&file.path);
+                    let content = generated_comment::rename_source(&file.content, generator.comment_syntax(), &generated.join(", "), &name);
+                    match write_generated_file(&output_path, &header, &content, generator.as_ref(), &source) {
+                        Ok((written, hash)) => {
+                            println!("{} {}", if written { "Generated" } else { "Unchanged" }, output_path.display());
+                            cache.package_outputs.push((file.path, hash));
+                        }
+                        Err(e) => {
+                            failures.add(
+                                output_path.display().to_string(),
+                                Diagnostic::error(format!("Failed to write {}: {}", output_path.display(), e)),
+                            );
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                failures.add(
+                    format!("{} package files", generator.extension()),
+                    Diagnostic::error(format!("Failed to generate {}: {}", generator.extension(), e)),
+                );
+            }
+        }
+        if failures.stop() {
             return;
         }
+    }
+
+    // a failed run leaves the cache as it was, so the next one retries
+    if failures.failed.is_empty() && failures.failed_inputs.is_empty() && let Err(e) = cache.save(output_dir) {
+        let path = output_dir.join(BuildCache::FILE_NAME);
+        failures.add(path.display().to_string(), Diagnostic::error(format!("Failed to write {}: {}", path.display(), e)));
+    }
+}
+
+/// An error pointing at the second declaration of an object and naming
+/// where the first is.
+fn duplicate_diagnostic(duplicate: &DuplicateObject) -> Diagnostic {
+    let first = &duplicate.first;
+    let location = match first.line {
+        0 => display_path(&first.path),
+        line => format!("{}:{}", display_path(&first.path), line),
     };
+    let message = if duplicate.differs_in_case() {
+        format!(
+            "Object '{}' collides with '{}' declared at {}: object names are compared ignoring case",
+            duplicate.duplicate.name, first.name, location
+        )
+    } else {
+        format!("Object '{}' is already declared at {}", duplicate.duplicate.name, location)
+    };
+    Diagnostic::error(message).with_file(&duplicate.duplicate.path).with_line(duplicate.duplicate.line)
+}
 
-    // Validate custom/nested types for every file, taking imports into account.
-    for oml_file in &all_files {
-        let extra = imported_names
-            .get(&oml_file.path)
-            .cloned()
-            .unwrap_or_default();
-        if let Err(e) = OmlObject::validate_custom_types(&oml_file.objects, &extra) {
-            eprintln!("Type error in {}.oml: {}", oml_file.file_name, e);
-            return;
+/// The inputs of a run, parsed and checked.
+struct Inputs {
+    /// Every file read, the inputs and what they import
+    resolver: ImportResolver,
+    /// The inputs to generate, each once, in the order given
+    roots: Vec<Arc<OmlFile>>,
+    /// The inputs' paths as they were listed, for the build cache
+    root_paths: Vec<PathBuf>,
+    /// The inputs left out because they did not parse or failed a check
+    failed: Vec<String>,
+}
+
+/// Parses the inputs and checks each against the files parsed before it.
+/// Files are parsed in parallel and checked in input order.
+struct Intake<'a> {
+    args: &'a GeneratorArgs,
+    fail_fast: bool,
+    resolver: ImportResolver,
+    declared: DeclaredObjects,
+    /// With `--skip-empty`, the empty objects of the files so far
+    skipped: HashSet<String>,
+    roots: Vec<Arc<OmlFile>>,
+    root_set: HashSet<PathBuf>,
+    root_paths: Vec<PathBuf>,
+    /// The inputs that did not parse or failed a check, which are left out
+    failed: Vec<String>,
+}
+
+impl<'a> Intake<'a> {
+    fn new(args: &'a GeneratorArgs, fail_fast: bool) -> Self {
+        Intake {
+            args,
+            fail_fast,
+            resolver: ImportResolver::default(),
+            declared: DeclaredObjects::default(),
+            skipped: HashSet::new(),
+            roots: Vec::new(),
+            root_set: HashSet::new(),
+            root_paths: Vec::new(),
+            failed: Vec::new(),
         }
     }
 
-    let generators = cli.get_generators();
+    /// Takes in the inputs of `cli`. `None` if the run has to stop, which
+    /// has been reported.
+    fn run(mut self, cli: &OmlCli) -> Option<Inputs> {
+        let parsed = cli.for_each_file(|parsed| match parsed {
+            Ok(file) => self.take(file),
+            Err(diagnostic) => {
+                let input = diagnostic.file.as_deref().map(display_path).unwrap_or_default();
+                self.fail(input, diagnostic)
+            }
+        });
+        if let Err(e) = parsed {
+            diagnostics::emit(&Diagnostic::error(format!(
+                "An error was encountered when parsing the input files: an input {}", e
+            )));
+            return None;
+        }
+        if self.stop() {
+            return None;
+        }
+        if self.root_paths.is_empty() && self.failed.is_empty() {
+            diagnostics::emit(&Diagnostic::error("No .oml files found"));
+            return None;
+        }
+        Some(Inputs { resolver: self.resolver, roots: self.roots, root_paths: self.root_paths, failed: self.failed })
+    }
 
-    if generators.is_empty() {
-        eprintln!("No language flag specified (e.g. --cpp)");
-        return;
+    /// Resolves the imports of the input `file` and checks it and the files
+    /// it pulls in, then adds it to the inputs to generate.
+    fn take(&mut self, file: OmlFile) -> ControlFlow<()> {
+        self.root_paths.push(file.path.clone());
+        if self.root_set.contains(&file.path) {
+            return ControlFlow::Continue(());
+        }
+        let root = Arc::new(file.clone());
+
+        let input = display_path(&file.path);
+        let added = match self.resolver.add(file) {
+            Ok(added) => added,
+            Err(e) => return self.fail(input, Diagnostic::from_error(e.as_ref())),
+        };
+
+        // Objects of the same name would be written to the same outputs.
+        // The first declaration read is kept.
+        for path in &added {
+            let file = self.resolver.file(path).expect("added files are known");
+            let duplicates = self.declared.add(file);
+            if let Some((last, others)) = duplicates.split_last() {
+                others.iter().map(duplicate_diagnostic).for_each(|d| diagnostics::emit(&d));
+                return self.fail(input, duplicate_diagnostic(last));
+            }
+            if self.args.skip_empty {
+                self.skipped.extend(file.objects.iter().filter(|o| o.is_empty()).map(|o| o.name.clone()));
+            }
+        }
+
+        // Validate custom/nested types and enum variants, taking imports into account.
+        for path in &added {
+            if let Err(diagnostic) = self.validate(path) {
+                return self.fail(input, diagnostic);
+            }
+        }
+
+        self.root_set.insert(root.path.clone());
+        self.roots.push(root);
+        ControlFlow::Continue(())
     }
 
-    let output_dir = Path::new(&cli.output);
+    fn validate(&mut self, path: &Path) -> Result<(), Diagnostic> {
+        let extra = self.resolver.imported_names(path);
+        let file = self.resolver.file(path).expect("added files are known");
+        let skipped: HashSet<&str> = self.skipped.iter().map(String::as_str).collect();
+        OmlObject::validate_custom_types(&file.objects, &extra)
+            .and_then(|()| OmlObject::validate_modifiers(&file.objects))
+            .and_then(|()| OmlObject::validate_enum_variants(&file.objects))
+            .and_then(|()| OmlObject::validate_skipped_types(&file.objects, &skipped))
+            .map_err(|e| Diagnostic::from_error(e.as_ref()).with_file(&file.path))
+    }
 
-    if let Err(e) = fs::create_dir_all(output_dir) {
-        eprintln!("Failed to create output directory '{}': {}", cli.output, e);
-        return;
+    /// Reports `diagnostic` and leaves `input` out; with `--fail-fast`, the
+    /// inputs after it are not read.
+    fn fail(&mut self, input: String, diagnostic: Diagnostic) -> ControlFlow<()> {
+        diagnostics::emit(&diagnostic);
+        self.failed.push(input);
+        if self.fail_fast { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
     }
 
-    // Only generate code for the files the user explicitly passed in.
-    for oml_file in all_files.iter().filter(|f| root_paths.contains(&f.path)) {
-        for generator in &generators {
-            match generator.generate(&oml_file.objects, &oml_file.file_name) {
-                Ok(content) => {
-                    let output_path = output_dir.join(
-                        format!("{}.{}", oml_file.file_name, generator.extension())
-                    );
-                    if let Err(e) = fs::write(&output_path, &content) {
-                        eprintln!("Failed to write {}: {}", output_path.display(), e);
-                    } else {
-                        println!("Generated {}", output_path.display());
+    /// `Failures::stop`, for the inputs.
+    fn stop(&self) -> bool {
+        self.fail_fast && diagnostics::error_count() > 0
+    }
+}
+
+/// The outputs that failed to generate or be written. Each is reported as
+/// it happens; the run carries on past them and lists them at the end, or
+/// with `--fail-fast` stops at the first.
+struct Failures {
+    fail_fast: bool,
+    /// The inputs that were left out
+    failed_inputs: Vec<String>,
+    failed: Vec<String>,
+}
+
+impl Failures {
+    fn new(fail_fast: bool) -> Self {
+        Failures { fail_fast, failed_inputs: Vec::new(), failed: Vec::new() }
+    }
+
+    /// Reports `diagnostic` about the output `what`.
+    fn add(&mut self, what: String, diagnostic: Diagnostic) {
+        diagnostics::emit(&diagnostic);
+        self.failed.push(what);
+    }
+
+    /// Whether the run has to stop now: with `--fail-fast`, after any error
+    /// at all, including inputs that were skipped.
+    fn stop(&self) -> bool {
+        self.fail_fast && diagnostics::error_count() > 0
+    }
+
+    /// Lists what failed, then exits with status 1 if any error was reported.
+    fn finish(&self) {
+        if !self.fail_fast {
+            for (failed, kind) in [(&self.failed_inputs, "input"), (&self.failed, "output")] {
+                let count = match failed.len() {
+                    0 => continue,
+                    1 => format!("1 {}", kind),
+                    n => format!("{} {}s", n, kind),
+                };
+                diagnostics::emit(&Diagnostic::error(format!("{} failed: {}", count, failed.join(", "))));
+            }
+        }
+        if diagnostics::error_count() > 0 {
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The name `--single-file` output is written under, e.g. `models.h`.
+const SINGLE_FILE_NAME: &str = "models";
+
+/// What one generated module is made from: a single OML file, or with
+/// `--single-file` all of them.
+struct Module {
+    file_name: String,
+    objects: Vec<OmlObject>,
+    sources: Vec<Arc<OmlFile>>,
+}
+
+impl Module {
+    fn of_file(file: Arc<OmlFile>) -> Self {
+        Module { file_name: file.file_name.clone(), objects: file.objects.clone(), sources: vec![file] }
+    }
+
+    /// The `--single-file` module of all of `files`.
+    fn combined(files: &[Arc<OmlFile>]) -> Self {
+        let objects: Vec<OmlObject> = files.iter().flat_map(|f| f.objects.iter().cloned()).collect();
+        Module {
+            file_name: SINGLE_FILE_NAME.to_string(),
+            objects: OmlObject::dependency_order(&objects),
+            sources: files.to_vec(),
+        }
+    }
+
+    /// The source files as named in the generated comment, e.g. `car.oml, engine.oml`.
+    fn source_names(&self, style: SourceNames) -> String {
+        let names: Vec<String> = self.sources.iter().map(|f| style.name(f)).collect();
+        names.join(", ")
+    }
+
+    fn source_text(&self) -> Vec<u8> {
+        self.sources.iter().flat_map(|f| fs::read(&f.path).unwrap_or_default()).collect()
+    }
+
+    /// What the module's outputs are recorded under in the build cache:
+    /// `options` with everything generating the module reads.
+    fn cache_key(&self, options: u64, source_names: &str, source_text: &[u8]) -> u64 {
+        let mut bytes = format!("{:016x} {} {}\n", options, self.file_name, source_names).into_bytes();
+        bytes.extend_from_slice(source_text);
+        fnv1a(&bytes)
+    }
+
+    /// `diagnostic` pointing at the module's source, when it has only one.
+    fn locate(&self, diagnostic: Diagnostic) -> Diagnostic {
+        match self.sources.as_slice() {
+            [source] => diagnostic.with_file(&source.path),
+            _ => diagnostic,
+        }
+    }
+
+    /// `content` with its generated comment naming the actual sources,
+    /// `source_names`, rather than the module.
+    fn with_sources(&self, content: &str, generator: &dyn Generate, source_names: &str) -> String {
+        let generated_name = format!("{}.oml", self.file_name);
+        generated_comment::rename_source(content, generator.comment_syntax(), &generated_name, source_names)
+    }
+}
+
+/// Everything generating a module takes besides the module itself.
+struct Generation<'a> {
+    args: &'a GeneratorArgs,
+    generators: &'a [Box<dyn Generate>],
+    header: &'a FileHeader,
+    previous: Option<&'a BuildCache>,
+    options: u64,
+    output_dir: &'a Path,
+    /// Set by `--verify`
+    verifier: Option<&'a Verifier>,
+}
+
+impl Generation<'_> {
+    /// Generates and writes `modules` on one worker thread per core.
+    /// `report` gets the reports in module order, each as soon as those
+    /// before it are in, and can stop the run.
+    fn run(&self, modules: &[Module], report: impl FnMut(ModuleReport) -> ControlFlow<()>) {
+        for_each_in_order(modules.iter(), |module| self.module(module), report);
+    }
+
+    /// Generates the files of `module` for every generator, or takes them
+    /// from the build cache if they are still as the last run wrote them.
+    fn module(&self, module: &Module) -> ModuleReport {
+        let name = module.source_names(self.args.source_names);
+        let text = module.source_text();
+        let key = module.cache_key(self.options, &name, &text);
+        let mut report = ModuleReport { key, outputs: Vec::new(), messages: Vec::new(), failed: Vec::new() };
+
+        if let Some(outputs) = self.previous.and_then(|cache| cache.module_outputs(key, self.output_dir)) {
+            for (path, _) in outputs {
+                report.messages.push(Message::Out(format!("Unchanged {}", self.output_dir.join(path).display())));
+            }
+            report.outputs = outputs.to_vec();
+            return report;
+        }
+
+        for generator in self.generators {
+            let objects = self.args.objects_for(generator.as_ref(), &module.objects);
+            match validate_identifiers(generator.as_ref(), &objects).and_then(|()| generator.generate_files(&objects, &module.file_name)) {
+                Ok(files) => {
+                    let source = Source { name: &name, text: &text, objects: &objects };
+                    for file in files {
+                        let output_path = self.output_dir.join(&file.path);
+                        let content = module.with_sources(&file.content, generator.as_ref(), &name);
+                        match write_generated_file(&output_path, self.header, &content, generator.as_ref(), &source) {
+                            Ok((written, hash)) => {
+                                let verb = if written { "Generated" } else { "Unchanged" };
+                                report.messages.push(Message::Out(format!("{} {}", verb, output_path.display())));
+                                report.outputs.push((file.path, hash));
+                                if let Some(target) = generator.verify_target()
+                                    && let Some(checked) = self.verifier.and_then(|verifier| verifier.check(target, &output_path))
+                                {
+                                    report_check(checked, &output_path, &objects, module, &mut report);
+                                }
+                            }
+                            Err(e) => {
+                                let message = format!("Failed to write {}: {}", output_path.display(), e);
+                                report.messages.push(Message::Diagnostic(Diagnostic::error(message)));
+                                report.failed.push(output_path.display().to_string());
+                            }
+                        }
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to generate {} for {}: {}", generator.extension(), oml_file.file_name, e);
+                    let diagnostic = match e.downcast_ref::<SourceError>() {
+                        Some(_) => Diagnostic::from_error(e.as_ref()),
+                        None => Diagnostic::error(format!("Failed to generate {}: {}", generator.extension(), e)),
+                    };
+                    report.messages.push(Message::Diagnostic(module.locate(diagnostic)));
+                    report.failed.push(format!("{}.{}", module.file_name, generator.extension()));
                 }
             }
         }
+        report
+    }
+}
+
+/// Reports what `--verify` found wrong with the file at `path`, naming the
+/// object each error falls in.
+fn report_check(checked: io::Result<Vec<CheckError>>, path: &Path, objects: &[OmlObject], module: &Module, report: &mut ModuleReport) {
+    let errors = match checked {
+        Ok(errors) if errors.is_empty() => return,
+        Ok(errors) => errors,
+        Err(e) => vec![CheckError { line: None, message: format!("the check could not be run: {}", e) }],
+    };
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let names: Vec<&str> = objects.iter().map(|o| o.name.as_str()).collect();
+    for error in errors {
+        let place = match error.line {
+            Some(line) => format!("{}:{}", path.display(), line),
+            None => path.display().to_string(),
+        };
+        let message = match error.line.and_then(|line| declaring_object(&content, &names, line)) {
+            Some(object) => format!("Generated '{}' does not compile ({}): {}", object, place, error.message),
+            None => format!("Generated {} does not compile: {}", place, error.message),
+        };
+        report.messages.push(Message::Diagnostic(module.locate(Diagnostic::error(message))));
+    }
+    report.failed.push(path.display().to_string());
+}
+
+/// What came of generating one module.
+struct ModuleReport {
+    /// What the outputs are recorded under in the build cache
+    key: u64,
+    outputs: Vec<(PathBuf, u64)>,
+    /// Output held back so that it is printed in module order
+    messages: Vec<Message>,
+    /// The outputs that could not be generated or written
+    failed: Vec<String>,
+}
+
+enum Message {
+    Out(String),
+    Diagnostic(Diagnostic),
+}
+
+impl Message {
+    fn print(&self) {
+        match self {
+            Message::Out(line) => println!("{}", line),
+            Message::Diagnostic(diagnostic) => diagnostics::emit(diagnostic),
+        }
     }
 }
 
-fn handle_translate(
-    files: &[String], output: &str,
-    cpp: bool, python: bool, java: bool, kotlin: bool,
-    rust: bool, typescript: bool, sql: bool, use_data_class: bool,
-) {
+/// The input a generated file was made from.
+struct Source<'a> {
+    /// File name as shown in the generated comment, such as `car.oml`
+    name: &'a str,
+    text: &'a [u8],
+    objects: &'a [OmlObject],
+}
+
+/// The comments put at the top of every generated file.
+struct FileHeader {
+    banner: Option<Banner>,
+    generated_comment: GeneratedComment,
+}
+
+impl FileHeader {
+    /// Writes `content` to `out` with the header on top.
+    fn write(&self, content: &str, generator: &dyn Generate, source: &Source, out: &mut impl Write) -> io::Result<()> {
+        let syntax = generator.comment_syntax();
+        let content = self.generated_comment.apply(content, syntax, source.name, source.text);
+        match &self.banner {
+            Some(banner) => banner.write(&content, syntax, source.name, source.objects, out),
+            None => out.write_all(content.as_bytes()),
+        }
+    }
+}
+
+/// Writes one generated file with its header, streamed through an
+/// `OutputFile` that leaves the file alone if it already has this content.
+/// Returns whether it was written and the hash of its content.
+fn write_generated_file(
+    output_path: &Path,
+    header: &FileHeader,
+    content: &str,
+    generator: &dyn Generate,
+    source: &Source,
+) -> io::Result<(bool, u64)> {
+    let mut out = OutputFile::create(output_path)?;
+    header.write(content, generator, source, &mut out)?;
+    out.finish()
+}
+
+fn handle_translate(files: &[String], output: &str, generator_args: &GeneratorArgs, failures: &mut Failures) {
     if files.is_empty() {
-        eprintln!("No files specified for translate");
+        diagnostics::emit(&Diagnostic::error("No files specified for translate"));
         return;
     }
 
-    let generators = get_generators_from_flags(cpp, python, java, kotlin, rust, typescript, sql, use_data_class);
+    let generators = generator_args.generators();
     if generators.is_empty() {
-        eprintln!("No target language specified (e.g. --java)");
+        diagnostics::emit(&Diagnostic::error("No target language specified (e.g. --java)"));
         return;
     }
+    if let Err(e) = generator_args.check_targets() {
+        diagnostics::emit(&Diagnostic::error(e));
+        return;
+    }
+
+    let header = match generator_args.banner() {
+        Ok(banner) => FileHeader { banner, generated_comment: generator_args.generated_comment },
+        Err(e) => {
+            diagnostics::emit(&Diagnostic::error(format!("Failed to read the header file: {}", e)));
+            return;
+        }
+    };
 
     let output_dir = Path::new(output);
     if let Err(e) = fs::create_dir_all(output_dir) {
-        eprintln!("Failed to create output directory '{}': {}", output, e);
+        diagnostics::emit(&Diagnostic::error(format!("Failed to create output directory '{}': {}", output, e)));
         return;
     }
 
     // Resolve inputs: expand directories into individual supported files
     let resolved = resolve_translate_inputs(files);
     if resolved.is_empty() {
-        eprintln!("No supported source files found");
+        diagnostics::emit(&Diagnostic::error("No supported source files found"));
         return;
     }
 
     for file_path in &resolved {
+        if failures.stop() {
+            return;
+        }
         let path = Path::new(file_path);
 
         let extension = path.extension().and_then(|e| e.to_str()).unwrap();
@@ -146,7 +685,8 @@ fn handle_translate(
         let backwards_gen = match get_backwards_generator(extension) {
             Some(g) => g,
             None => {
-                eprintln!("Unsupported source file type '.{}' for translate", extension);
+                let message = format!("Unsupported source file type '.{}' for translate", extension);
+                failures.add(file_path.clone(), Diagnostic::error(message).with_file(path));
                 continue;
             }
         };
@@ -154,7 +694,7 @@ fn handle_translate(
         let content = match fs::read_to_string(path) {
             Ok(c) => c,
             Err(e) => {
-                eprintln!("Failed to read '{}': {}", file_path, e);
+                failures.add(file_path.clone(), Diagnostic::error(format!("Failed to read '{}': {}", file_path, e)));
                 continue;
             }
         };
@@ -162,7 +702,7 @@ fn handle_translate(
         let oml_objects: Vec<OmlObject> = match backwards_gen.reverse(&content) {
             Ok(objects) => objects,
             Err(e) => {
-                eprintln!("Failed to parse '{}': {}", file_path, e);
+                failures.add(file_path.clone(), Diagnostic::error(format!("Failed to parse '{}': {}", file_path, e)));
                 continue;
             }
         };
@@ -175,23 +715,36 @@ fn handle_translate(
         let file_stem = path.file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("output");
+        // The generators name the source after the stem, as if it were OML
+        let name = format!("{}.oml", file_stem);
 
         for generator in &generators {
-            match generator.generate(&oml_objects, file_stem) {
-                Ok(generated) => {
-                    let output_path = output_dir.join(
-                        format!("{}.{}", file_stem, generator.extension())
-                    );
-                    if let Err(e) = fs::write(&output_path, &generated) {
-                        eprintln!("Failed to write {}: {}", output_path.display(), e);
-                    } else {
-                        println!("Translated {} -> {}", file_path, output_path.display());
+            let oml_objects = generator_args.objects_for(generator.as_ref(), &oml_objects);
+            let source = Source { name: &name, text: content.as_bytes(), objects: &oml_objects };
+            match generator.generate_files(&oml_objects, file_stem) {
+                Ok(files) => {
+                    for file in files {
+                        let output_path = output_dir.join(&file.path);
+                        match write_generated_file(&output_path, &header, &file.content, generator.as_ref(), &source) {
+                            Ok((true, _)) => println!("Translated {} -> {}", file_path, output_path.display()),
+                            Ok((false, _)) => println!("Unchanged {}", output_path.display()),
+                            Err(e) => failures.add(
+                                output_path.display().to_string(),
+                                Diagnostic::error(format!("Failed to write {}: {}", output_path.display(), e)),
+                            ),
+                        }
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to generate {} for '{}': {}", generator.extension(), file_path, e);
+                    failures.add(
+                        format!("{}.{}", file_stem, generator.extension()),
+                        Diagnostic::error(format!("Failed to generate {} for '{}': {}", generator.extension(), file_path, e)),
+                    );
                 }
             }
+            if failures.stop() {
+                return;
+            }
         }
     }
 }
@@ -246,6 +799,43 @@ fn collect_supported_files(dir: &Path, out: &mut Vec<String>) {
     }
 }
 
+/// Parses every object below `path` into one flat list.
+fn collect_schema(path: &str, depth: usize) -> Option<Vec<OmlObject>> {
+    match parse_dir_from_string(path.to_string(), depth) {
+        Ok(files) => Some(files.into_iter().flat_map(|f| f.objects).collect()),
+        Err(e) => {
+            diagnostics::emit(&Diagnostic::error(format!("Failed to parse '{}': the path {}", path, e)));
+            None
+        }
+    }
+}
+
+/// Prints the differences between two schema versions. Returns `false` when
+/// either side could not be parsed or a breaking change was found.
+fn handle_diff(old: &str, new: &str, depth: usize) -> bool {
+    let (Some(old_objects), Some(new_objects)) = (collect_schema(old, depth), collect_schema(new, depth)) else {
+        return false;
+    };
+
+    let mut changes = diff_schemas(&old_objects, &new_objects);
+    if changes.is_empty() {
+        println!("No schema changes");
+        return true;
+    }
+
+    // Breaking changes first so they are the first thing a reviewer sees.
+    changes.sort_by_key(|c| c.compatibility);
+    for change in &changes {
+        println!("{}", change);
+    }
+
+    let breaking = changes.iter().filter(|c| c.is_breaking()).count();
+    println!();
+    println!("{} breaking, {} compatible change(s)", breaking, changes.len() - breaking);
+
+    breaking == 0
+}
+
 fn handle_revert(files: &[String], output: &str) {
     if files.is_empty() {
         eprintln!("No files specified for revert");
@@ -319,3 +909,64 @@ fn handle_revert(files: &[String], output: &str) {
         }
     }
 }
+
+/// Writes the .oml file for `oml new`.
+fn handle_new(kind: &ObjectType, name: &str, members: &[String], output: &str, force: bool) {
+    let scaffolded = scaffold::scaffold(kind.clone(), name, members)
+        .and_then(|obj| scaffold::render(&obj).map(|content| (obj, content)));
+    let (obj, content) = match scaffolded {
+        Ok(scaffolded) => scaffolded,
+        Err(e) => {
+            diagnostics::emit(&Diagnostic::error(e));
+            return;
+        }
+    };
+
+    let path = Path::new(output).join(format!("{}.oml", to_snake_case(name)));
+    if path.exists() && !force {
+        diagnostics::emit(&Diagnostic::error("The file already exists; pass --force to replace it").with_file(&path));
+        return;
+    }
+    if let Err(e) = fs::create_dir_all(output).and_then(|()| fs::write(&path, content)) {
+        diagnostics::emit(&Diagnostic::error(format!("Failed to write {}: {}", path.display(), e)));
+        return;
+    }
+    println!("Created {}", path.display());
+
+    for custom in scaffold::custom_types(&obj) {
+        let message = format!("'{}' is not a built-in type; add an `import` of the file declaring it", custom);
+        diagnostics::emit(&Diagnostic::warning(message).with_file(&path));
+    }
+}
+
+/// Prints `files` highlighted in `format`.
+fn handle_highlight(files: &[String], format: HighlightFormat, standalone: bool, failures: &mut Failures) {
+    if files.is_empty() {
+        diagnostics::emit(&Diagnostic::error("No files specified for highlight"));
+        return;
+    }
+
+    let mut blocks = Vec::new();
+    for file in files {
+        match fs::read_to_string(file) {
+            Ok(source) => {
+                let mut block = highlight::highlight(&source, format);
+                // files run into one another without it
+                if !block.ends_with('\n') {
+                    block.push('\n');
+                }
+                blocks.push(block);
+            }
+            Err(e) => failures.add(file.clone(), Diagnostic::error(format!("Failed to read '{}': {}", file, e))),
+        }
+        if failures.stop() {
+            return;
+        }
+    }
+
+    if standalone && format == HighlightFormat::Html {
+        print!("{}", highlight::html_page(&files.join(", "), &blocks));
+    } else {
+        print!("{}", blocks.concat());
+    }
+}